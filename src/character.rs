@@ -1,6 +1,6 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use crate::core::paths::Layout;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
@@ -16,7 +16,7 @@ pub fn load_character_config() -> Result<CharacterConfig> {
     let character_name = env::var("CHARACTER_NAME").unwrap_or_else(|_| "rina".to_string());
     
     // Construct path to character config
-    let mut config_path = PathBuf::from("characters");
+    let mut config_path = Layout::from_env().characters_dir();
     config_path.push(&character_name);
     config_path.push("config.json");
 
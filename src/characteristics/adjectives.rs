@@ -9,6 +9,10 @@ impl Characteristic for Adjectives {
         "Adjectives:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        30
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(character.adjectives.join("\n"))
     }
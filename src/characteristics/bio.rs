@@ -9,6 +9,10 @@ impl Characteristic for Bio {
         "Bio:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        60
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(format!("Headline: {}\nKey Traits:\n{}", 
             character.bio.headline,
@@ -9,6 +9,10 @@ impl Characteristic for Lore {
         "Lore:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        50
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(character.lore.join("\n"))
     }
@@ -9,6 +9,10 @@ impl Characteristic for PostExamples {
         "Post Examples:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        10
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(character.post_style_examples.join("\n"))
     }
@@ -9,6 +9,10 @@ impl Characteristic for Styles {
         "Styles:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        40
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(character.styles.join("\n"))
     }
@@ -9,6 +9,10 @@ impl Characteristic for Topics {
         "Topics:".to_string()
     }
 
+    fn get_priority(&self) -> u8 {
+        20
+    }
+
     fn get_traits(&self, character: &Character) -> io::Result<String> {
         Ok(character.topics.join("\n"))
     }
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use rand::Rng;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+
+const MAX_ATTEMPTS: usize = 3;
+
+// FUD about a trending token, grounded in its numbers
+pub struct EditorializedFud;
+
+#[async_trait(?Send)]
+impl ContentGenerator for EditorializedFud {
+    fn name(&self) -> &'static str {
+        ContentGenerators::FUD
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let Some((target, token_summary)) = runtime.pick_fud_target().await? else {
+            return Ok(None);
+        };
+
+        if let Some((website, screenshot)) = runtime.website_roast_image(&target).await {
+            let text = runtime.agent().generate_website_roast(&token_summary, &website).await?;
+            if runtime.is_postable(&text) {
+                return Ok(Some(Post {
+                    text,
+                    image: Some(screenshot),
+                    target: Some(target),
+                    context: Some(token_summary),
+                    prediction: None,
+                    news: None,
+                    space: None,
+                    chart_image: None,
+                    reaction_clip: None,
+                }));
+            }
+        }
+
+        let prebond = runtime.use_prebond_prompt(&target.token.mint);
+        let mut attempts = 0;
+        let text = loop {
+            let fud = if prebond {
+                runtime.agent().generate_prebond_fud(&token_summary).await?
+            } else {
+                runtime.agent().generate_editorialized_fud(&token_summary).await?
+            };
+            if runtime.is_postable(&fud) || attempts >= MAX_ATTEMPTS {
+                break fud;
+            }
+            attempts += 1;
+        };
+
+        // Half the time try a scam alert card built from the token's own logo,
+        // otherwise (or if that fails) maybe a reaction clip, else sometimes one of
+        // the stock charts
+        let (try_card, try_chart) = {
+            let mut rng = rand::thread_rng();
+            (rng.gen_bool(0.5), rng.gen_bool(0.3))
+        };
+        let card = if try_card {
+            runtime.build_token_card(&target.token).await
+        } else {
+            None
+        };
+        let (image, chart_image, reaction_clip) = match card {
+            Some(card) => (Some(card), None, None),
+            None => match runtime.reaction_clip() {
+                Some(clip) => (Some(clip.bytes), None, Some(clip.name)),
+                None if try_chart => {
+                    let (image, chart_image) = runtime.random_chart_image().map(|chart| (chart.bytes, chart.name)).unzip();
+                    (image, chart_image, None)
+                }
+                None => (None, None, None),
+            },
+        };
+
+        Ok(Some(Post {
+            text,
+            image,
+            target: Some(target),
+            context: Some(token_summary),
+            prediction: None,
+            news: None,
+            space: None,
+            chart_image,
+            reaction_clip,
+        }))
+    }
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+
+// An in-character post that isn't about any token
+pub struct GenericShitpost;
+
+#[async_trait(?Send)]
+impl ContentGenerator for GenericShitpost {
+    fn name(&self) -> &'static str {
+        ContentGenerators::SHITPOST
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let post = runtime.agent().generate_post().await?;
+        Ok(Some(Post::text(post)))
+    }
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use crate::core::config::HeuristConfig;
+use crate::core::content::{ContentGenerator, Post};
+use crate::core::runtime::Runtime;
+
+// A captioned image: generated with Heurist when it's configured, else a stock chart
+pub struct ImageMeme;
+
+impl ImageMeme {
+    async fn heurist_image(runtime: &Runtime, heurist: &HeuristConfig) -> Result<Vec<u8>, anyhow::Error> {
+        let image_url = runtime.agent().generate_image(heurist).await?;
+        runtime.agent().prepare_image_for_tweet(&image_url).await
+    }
+}
+
+#[async_trait(?Send)]
+impl ContentGenerator for ImageMeme {
+    fn name(&self) -> &'static str {
+        "image_meme"
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let generated = match runtime.heurist() {
+            Some(heurist) => match Self::heurist_image(runtime, heurist).await {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    eprintln!("Failed to generate meme image: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let (image, chart_image) = match generated {
+            Some(image) => (image, None),
+            None => match runtime.random_chart_image() {
+                Some(chart) => (chart.bytes, Some(chart.name)),
+                None => return Ok(None),
+            },
+        };
+
+        let caption = runtime.agent().generate_meme_caption().await?;
+        Ok(Some(Post {
+            text: caption,
+            image: Some(image),
+            target: None,
+            context: None,
+            prediction: None,
+            news: None,
+            space: None,
+            chart_image,
+            reaction_clip: None,
+        }))
+    }
+}
@@ -0,0 +1,10 @@
+pub mod editorialized_fud;
+pub mod generic_shitpost;
+pub mod image_meme;
+pub mod network_fud;
+pub mod news;
+pub mod prediction;
+pub mod shill;
+pub mod space;
+pub mod token_comparison;
+pub mod trending_summary;
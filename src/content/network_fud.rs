@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+
+// FUD about the chain itself: congestion, priority fees, failed transactions
+pub struct NetworkFud;
+
+#[async_trait(?Send)]
+impl ContentGenerator for NetworkFud {
+    fn name(&self) -> &'static str {
+        ContentGenerators::NETWORK
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let Some(stats) = runtime.network_stats_summary().await else {
+            return Ok(None);
+        };
+        let text = runtime.agent().generate_network_fud(&stats).await?;
+        if !runtime.is_postable(&text) {
+            return Ok(None);
+        }
+        Ok(Some(Post {
+            context: Some(stats),
+            ..Post::text(text)
+        }))
+    }
+}
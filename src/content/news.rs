@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+use crate::models::NewsReaction;
+
+// Reaction to a fresh Solana or memecoin headline from CryptoPanic or RSS
+pub struct NewsReactionPost;
+
+#[async_trait(?Send)]
+impl ContentGenerator for NewsReactionPost {
+    fn name(&self) -> &'static str {
+        ContentGenerators::NEWS
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let Some(headline) = runtime.fresh_headline().await else {
+            return Ok(None);
+        };
+        let context = headline.prompt_line();
+        let text = runtime.agent().generate_news_reaction(&context).await?;
+        if !runtime.is_postable(&text) {
+            return Ok(None);
+        }
+        Ok(Some(Post {
+            context: Some(format!("{}\n{}", context, headline.url)),
+            news: Some(NewsReaction {
+                url: headline.url,
+                title: headline.title,
+                reacted_at: Utc::now(),
+            }),
+            ..Post::text(text)
+        }))
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use crate::core::content::{ContentGenerator, Post};
+use crate::core::runtime::Runtime;
+
+const MAX_ATTEMPTS: usize = 3;
+
+// A concrete market cap call on a trending token, graded publicly once its deadline passes
+pub struct PredictionPost;
+
+#[async_trait(?Send)]
+impl ContentGenerator for PredictionPost {
+    fn name(&self) -> &'static str {
+        "prediction"
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let Some((target, token_summary)) = runtime.pick_fud_target().await? else {
+            return Ok(None);
+        };
+        let Some(prediction) = runtime.new_prediction(&target) else {
+            return Ok(None);
+        };
+        let claim = prediction.claim();
+        let figure = prediction.target_figure().to_lowercase();
+
+        // The number has to be in the tweet or there's nothing to grade
+        let mut text = None;
+        for _ in 0..MAX_ATTEMPTS {
+            let candidate = runtime.agent().generate_prediction(&token_summary, &claim).await?;
+            if candidate.to_lowercase().contains(&figure) && runtime.is_postable(&candidate) {
+                text = Some(candidate);
+                break;
+            }
+        }
+        let text = text.unwrap_or_else(|| format!("calling it now: ${} {}. screenshot this", target.token.symbol, claim));
+
+        Ok(Some(Post {
+            text,
+            image: None,
+            target: Some(target),
+            context: Some(format!("{}Call: {}\n", token_summary, claim)),
+            prediction: Some(prediction),
+            news: None,
+            space: None,
+            chart_image: None,
+            reaction_clip: None,
+        }))
+    }
+}
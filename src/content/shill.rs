@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+
+// Promotes our own token; picked by shill.weight rather than the content schedule
+pub struct Shill;
+
+#[async_trait(?Send)]
+impl ContentGenerator for Shill {
+    fn name(&self) -> &'static str {
+        ContentGenerators::SHILL
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        Ok(runtime.generate_shill().await?.map(Post::text))
+    }
+}
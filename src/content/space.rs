@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use crate::core::content::{ContentGenerator, ContentGenerators, Post};
+use crate::core::runtime::Runtime;
+use crate::models::SpaceComment;
+
+// Snark about a scheduled Space in the niche, before it starts
+pub struct SpaceCommentary;
+
+#[async_trait(?Send)]
+impl ContentGenerator for SpaceCommentary {
+    fn name(&self) -> &'static str {
+        ContentGenerators::SPACE
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let Some(space) = runtime.upcoming_space().await else {
+            return Ok(None);
+        };
+        let context = space.prompt_line();
+        let text = runtime.agent().generate_space_commentary(&context).await?;
+        if !runtime.is_postable(&text) {
+            return Ok(None);
+        }
+        Ok(Some(Post {
+            context: Some(format!("{}\n{}", context, space.url())),
+            space: Some(SpaceComment {
+                space_id: space.id,
+                title: space.title,
+                commented_at: Utc::now(),
+            }),
+            ..Post::text(text)
+        }))
+    }
+}
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use rand::seq::SliceRandom;
+use crate::core::content::{ContentGenerator, Post};
+use crate::core::runtime::Runtime;
+use crate::media::comparison_card::ComparisonCard;
+
+// How far down the trending list the pair is picked from
+const CANDIDATES: usize = 10;
+
+// Two trending tokens side by side in a rendered table, both declared garbage
+pub struct TokenComparison;
+
+#[async_trait(?Send)]
+impl ContentGenerator for TokenComparison {
+    fn name(&self) -> &'static str {
+        "comparison"
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let tokens = runtime.trending_tokens(CANDIDATES).await?;
+        let pair: Vec<_> = tokens.choose_multiple(&mut rand::thread_rng(), 2).collect();
+        let [first, second] = pair[..] else {
+            return Ok(None);
+        };
+
+        let image = match ComparisonCard::render(first, second) {
+            Ok(image) => image,
+            Err(e) => {
+                eprintln!("Failed to render comparison card for ${} and ${}: {}", first.token.symbol, second.token.symbol, e);
+                return Ok(None);
+            }
+        };
+        let card = ComparisonCard::rows(first, second)
+            .iter()
+            .map(|row| row.join(" | "))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let summaries = vec![runtime.token_summary(first), runtime.token_summary(second)];
+        let text = runtime.agent().generate_matchup_fud(&summaries, &card).await?;
+        Ok(Some(Post {
+            image: Some(image),
+            context: Some(summaries.join("\n")),
+            ..Post::text(text)
+        }))
+    }
+}
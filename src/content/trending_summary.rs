@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use crate::core::content::{ContentGenerator, Post};
+use crate::core::runtime::Runtime;
+
+const TOKENS_IN_SUMMARY: usize = 5;
+
+// Roundup of the current top tokens
+pub struct TrendingSummary;
+
+#[async_trait(?Send)]
+impl ContentGenerator for TrendingSummary {
+    fn name(&self) -> &'static str {
+        "trending_summary"
+    }
+
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error> {
+        let summaries = runtime.trending_summaries(TOKENS_IN_SUMMARY).await?;
+        if summaries.is_empty() {
+            return Ok(None);
+        }
+        let summaries = summaries.join("\n");
+        let summary = runtime.agent().generate_trending_summary(&summaries).await?;
+        Ok(Some(Post {
+            context: Some(summaries),
+            ..Post::text(summary)
+        }))
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+
+use super::character::AdaptiveScheduleSettings;
+use crate::models::{Memory, TweetType};
+
+// Fewer measured posts than this and the interval is left alone
+const MIN_MEASURED_POSTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntervalChange {
+    Widened,
+    Tightened,
+    Unchanged,
+}
+
+// Stretches the gap between scheduled posts while nobody engages and shrinks it
+// again when posts start landing
+pub struct AdaptiveSchedule;
+
+impl AdaptiveSchedule {
+    // Average engagement of the latest original posts whose metrics have had time to
+    // settle; None until enough of them have been measured
+    pub fn recent_engagement(settings: &AdaptiveScheduleSettings, memory: &Memory, now: DateTime<Utc>) -> Option<f64> {
+        let settled_before = now - chrono::Duration::minutes(settings.min_post_age_minutes);
+        let totals: Vec<u64> = memory.tweets
+            .iter()
+            .rev()
+            .filter(|t| matches!(t.tweet_type, TweetType::Original) && t.timestamp <= settled_before)
+            .filter_map(|t| t.metrics.as_ref().map(|metrics| metrics.total()))
+            .take(settings.lookback_posts)
+            .collect();
+        if totals.len() < MIN_MEASURED_POSTS.min(settings.lookback_posts.max(1)) {
+            return None;
+        }
+        Some(totals.iter().sum::<u64>() as f64 / totals.len() as f64)
+    }
+
+    // Interval in minutes after the next adjustment, kept within the configured bounds
+    pub fn next_interval(settings: &AdaptiveScheduleSettings, current: i64, engagement: Option<f64>) -> (i64, IntervalChange) {
+        let min = settings.min_interval_minutes.max(1);
+        let max = settings.max_interval_minutes.max(min);
+        let current = current.clamp(min, max);
+        let step = settings.step.max(1.0);
+
+        let next = match engagement {
+            Some(avg) if avg < settings.low_engagement => (current as f64 * step).round() as i64,
+            Some(avg) if avg > settings.high_engagement => (current as f64 / step).round() as i64,
+            _ => current,
+        }
+        .clamp(min, max);
+
+        let change = match next.cmp(&current) {
+            std::cmp::Ordering::Greater => IntervalChange::Widened,
+            std::cmp::Ordering::Less => IntervalChange::Tightened,
+            std::cmp::Ordering::Equal => IntervalChange::Unchanged,
+        };
+        (next, change)
+    }
+}
@@ -2,100 +2,564 @@ use rig::agent::Agent as RigAgent;
 use rig::providers::anthropic::completion::CompletionModel;
 use rig::providers::anthropic::{self, CLAUDE_3_HAIKU};
 use rig::completion::Prompt;
-use rand::{self, Rng};
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 
+use super::aggression::Aggression;
+use super::audit::{AuditEntry, AuditLog};
+use super::latency_budget;
+use super::clock::{Clock, SystemClock};
+use super::character::{GenerationParams, GenerationSettings, LanguageSettings, StreamGuardSettings};
+use super::config::HeuristConfig;
+use super::instruction_builder::ExampleRotation;
+use super::pipeline::{Phase, Pipeline};
+use super::prompt_budget::{self, estimate_tokens, PromptSection};
+use super::stream_guard::{SseReader, StreamGuard, StreamOutcome};
+use super::style::StyleEngine;
+use super::telemetry::Tracer;
+use crate::models::AvoidedTopic;
 
-use std::{
-    env,
-    time::{SystemTime, UNIX_EPOCH},
-}; 
+
+use std::time::{SystemTime, UNIX_EPOCH}; 
 
 use teloxide::prelude::*;
 
+tokio::task_local! {
+    // Language of the mention being answered, used instead of the character's own.
+    // Scoped to the future drafting that reply, so mentions can be drafted side by side.
+    static REPLY_LANGUAGE: Option<String>;
+}
+
 pub struct Agent {
-    agent: RigAgent<CompletionModel>,
+    client: anthropic::Client,
+    generation: GenerationSettings,
+    language: LanguageSettings,
+    sections: Vec<PromptSection>,
+    max_input_tokens: usize,
     anthropic_api_key: String,
     pub prompt: String,
-    fud_analysis: FudAnalysis, 
+    style: Arc<Mutex<StyleEngine>>,
+    // Topics the consistency check flagged, shared with the runtime
+    avoided_topics: Arc<Mutex<Vec<AvoidedTopic>>>,
+    // The runtime's clock, which decides when an avoided topic expires
+    clock: Arc<dyn Clock>,
+    // The operator's aggression dial, shared with the runtime
+    aggression: Arc<Mutex<Aggression>>,
+    stats: Mutex<GenerationStats>,
+    tracer: Tracer,
+    audit: AuditLog,
+    // Longest a single model call may take
+    llm_timeout: Option<std::time::Duration>,
+    // Audit id of the latest completion, which post-processing is recorded against
+    last_completion: Mutex<Option<String>>,
+    // Terms checked while a completion streams in; without them completions aren't streamed
+    stream_guard: Option<StreamGuard>,
+    max_restarts: u32,
+    // Text stages between the model's answer and the post
+    pipeline: Pipeline,
+    // Client with ANTHROPIC_PROXY / ANTHROPIC_USER_AGENT; when set, completions skip
+    // rig, whose client can't be configured, and go to the Messages API directly
+    http: Option<reqwest::Client>,
+    // The character's examples/ posts, a few added to the preamble of each text call
+    examples: Mutex<ExampleRotation>,
+}
+
+// Model calls since the runtime last collected them
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GenerationStats {
+    pub count: u64,
+    pub total_ms: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GenerationTask {
+    Classify,
+    ClassifyBatch,
+    Reply,
+    Post,
+    Fud,
+    Custom,
+    Review,
 }
 
-#[derive(Debug, PartialEq)]
+impl GenerationTask {
+    // Sane defaults: deterministic classification, creative posting
+    fn default_params(self) -> (f64, u64) {
+        match self {
+            GenerationTask::Classify => (0.0, 16),
+            GenerationTask::ClassifyBatch => (0.0, 128),
+            GenerationTask::Reply => (0.9, 512),
+            GenerationTask::Post => (0.9, 512),
+            GenerationTask::Fud => (1.0, 512),
+            GenerationTask::Custom => (0.8, 1024),
+            GenerationTask::Review => (0.0, 1024),
+        }
+    }
+
+    // Classification answers are a word or two; only written text is worth guarding
+    fn writes_text(self) -> bool {
+        !matches!(self, GenerationTask::Classify | GenerationTask::ClassifyBatch | GenerationTask::Review)
+    }
+
+    fn configured(self, settings: &GenerationSettings) -> &GenerationParams {
+        match self {
+            GenerationTask::Classify => &settings.classify,
+            GenerationTask::ClassifyBatch => &settings.classify_batch,
+            GenerationTask::Reply => &settings.reply,
+            GenerationTask::Post => &settings.post,
+            GenerationTask::Fud => &settings.fud,
+            GenerationTask::Custom => &settings.custom,
+            GenerationTask::Review => &settings.review,
+        }
+    }
+}
+
+const ANTHROPIC_MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DESCRIBE_IMAGES_MAX_TOKENS: u64 = 300;
+
+// Most mentions sent to the model in a single classification prompt
+pub const CLASSIFY_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResponseDecision {
     Respond,
     Ignore,
 }
 
-#[derive(Debug, Clone)]
-struct FudAnalysis {
-    word_frequencies: HashMap<String, usize>,
-    pattern_frequencies: HashMap<String, usize>,
+impl ResponseDecision {
+    // Decisions from a batch answer like ["RESPOND", "IGNORE"]; None unless there
+    // is exactly one readable decision per mention
+    pub fn parse_batch(response: &str, count: usize) -> Option<Vec<ResponseDecision>> {
+        let start = response.find('[')?;
+        let end = response.rfind(']')?;
+        if end < start {
+            return None;
+        }
+        let values: Vec<serde_json::Value> = serde_json::from_str(&response[start..=end]).ok()?;
+        if values.len() != count {
+            return None;
+        }
+        values
+            .iter()
+            .map(|value| match value {
+                serde_json::Value::String(s) => {
+                    match s.trim_matches(|c| c == '[' || c == ']').to_uppercase().as_str() {
+                        "RESPOND" => Some(ResponseDecision::Respond),
+                        "IGNORE" => Some(ResponseDecision::Ignore),
+                        _ => None,
+                    }
+                }
+                serde_json::Value::Bool(true) => Some(ResponseDecision::Respond),
+                serde_json::Value::Bool(false) => Some(ResponseDecision::Ignore),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
-impl FudAnalysis {
-    fn new() -> Self {
-        FudAnalysis {
-            word_frequencies: HashMap::new(),
-            pattern_frequencies: HashMap::new(),
+impl Agent {
+    pub fn new(anthropic_api_key: &str, prompt: &str, generation: GenerationSettings) -> Self {
+        let client = anthropic::ClientBuilder::new(anthropic_api_key).build();
+        Agent { 
+            client,
+            generation,
+            language: LanguageSettings::default(),
+            sections: Vec::new(),
+            max_input_tokens: 0,
+            anthropic_api_key: anthropic_api_key.to_string(),
+            prompt: prompt.to_string(),
+            style: Arc::new(Mutex::new(StyleEngine::default())),
+            avoided_topics: Arc::new(Mutex::new(Vec::new())),
+            clock: Arc::new(SystemClock),
+            aggression: Arc::new(Mutex::new(Aggression::default())),
+            stats: Mutex::new(GenerationStats::default()),
+            tracer: Tracer::default(),
+            audit: AuditLog::default(),
+            llm_timeout: None,
+            last_completion: Mutex::new(None),
+            stream_guard: None,
+            max_restarts: 0,
+            pipeline: Pipeline::default(),
+            http: None,
+            examples: Mutex::new(ExampleRotation::default()),
         }
     }
 
-    fn update(&mut self, text: &str) {
-        // Update word frequencies
-        for word in text.split_whitespace() {
-            *self.word_frequencies.entry(word.to_lowercase()).or_insert(0) += 1;
+    // Startup check of the API key; listing models doesn't spend any tokens
+    pub async fn verify_api_key(&self) -> anyhow::Result<()> {
+        let response = self
+            .http()
+            .get("https://api.anthropic.com/v1/models")
+            .header("x-api-key", &self.anthropic_api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("API key check failed with status: {}", status));
         }
+        Ok(())
+    }
 
-        // Update pattern frequencies (basic phrases)
-        let patterns = ["ser", "ngmi", "wen", "just", "literally"];
-        for pattern in patterns.iter() {
-            if text.to_lowercase().contains(pattern) {
-                *self.pattern_frequencies.entry(pattern.to_string()).or_insert(0) += 1;
-            }
+    // Share one style engine between the runtime and every agent
+    pub fn with_style(mut self, style: Arc<Mutex<StyleEngine>>) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_avoided_topics(mut self, topics: Arc<Mutex<Vec<AvoidedTopic>>>) -> Self {
+        self.avoided_topics = topics;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_aggression(mut self, aggression: Arc<Mutex<Aggression>>) -> Self {
+        self.aggression = aggression;
+        self
+    }
+
+    fn aggression(&self) -> MutexGuard<'_, Aggression> {
+        self.aggression.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn with_tracer(mut self, tracer: Tracer) -> Self {
+        self.tracer = tracer;
+        self
+    }
+
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    pub fn with_llm_timeout(mut self, limit: Option<std::time::Duration>) -> Self {
+        self.llm_timeout = limit;
+        self
+    }
+
+    pub(crate) fn style(&self) -> MutexGuard<'_, StyleEngine> {
+        self.style.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Let the agent trim the character preamble, section by section, to stay under the budget
+    pub fn with_prompt_budget(mut self, sections: Vec<PromptSection>, max_input_tokens: usize) -> Self {
+        self.sections = sections;
+        self.max_input_tokens = max_input_tokens;
+        self
+    }
+
+    pub fn with_stream_guard(mut self, settings: &StreamGuardSettings, banned_words: &[String]) -> Self {
+        let guard = StreamGuard::new(banned_words.iter().chain(&settings.blocklist));
+        self.stream_guard = (settings.enabled && !guard.is_empty()).then_some(guard);
+        self.max_restarts = settings.max_restarts;
+        self
+    }
+
+    pub fn with_http(mut self, client: Option<reqwest::Client>) -> Self {
+        self.http = client;
+        self
+    }
+
+    fn http(&self) -> reqwest::Client {
+        self.http.clone().unwrap_or_default()
+    }
+
+    pub fn with_examples(mut self, examples: ExampleRotation) -> Self {
+        self.examples = Mutex::new(examples);
+        self
+    }
+
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    pub fn with_language(mut self, language: LanguageSettings) -> Self {
+        self.language = language;
+        self
+    }
+
+    // Runs `future` with every generation in it written in `language`, e.g. while
+    // answering one mention
+    pub async fn in_reply_language<F: std::future::Future>(language: Option<String>, future: F) -> F::Output {
+        REPLY_LANGUAGE.scope(language, future).await
+    }
+
+    pub fn reply_language(&self) -> Option<String> {
+        REPLY_LANGUAGE.try_with(Clone::clone).ok().flatten()
+    }
+
+    // Language, length, tone and avoided topic requirement lines for a generation prompt
+    fn language_requirements(&self, max_chars: usize) -> String {
+        let language = self.reply_language().unwrap_or_else(|| self.language.pick());
+        let requirements = if LanguageSettings::is_english(&language) {
+            format!("- Stay under {} characters", max_chars)
+        } else {
+            let max_chars = if LanguageSettings::is_cjk(&language) {
+                max_chars / 2
+            } else {
+                max_chars
+            };
+            format!(
+                "- Write entirely in {}, keeping token symbols and crypto slang untranslated\n- Stay under {} characters",
+                language,
+                max_chars
+            )
+        };
+        let requirements = match self.aggression().tone_requirement() {
+            Some(tone) => format!("{}\n{}", requirements, tone),
+            None => requirements,
+        };
+        match self.avoided_topics_requirement() {
+            Some(avoid) => format!("{}\n{}", requirements, avoid),
+            None => requirements,
         }
     }
 
-    fn is_overused(&self, text: &str) -> bool {
-        // Check for overused words
-        let words: Vec<&str> = text.split_whitespace().collect();
-        for word in words {
-            if let Some(count) = self.word_frequencies.get(&word.to_lowercase()) {
-                if *count > 5 {
-                    return true;
-                }
+    // Topics the consistency check caught the character flip-flopping on
+    fn avoided_topics_requirement(&self) -> Option<String> {
+        let now = self.clock.now();
+        let topics = self.avoided_topics.lock().unwrap_or_else(|e| e.into_inner());
+        let active: Vec<&str> = topics
+            .iter()
+            .filter(|topic| topic.until > now)
+            .map(|topic| topic.topic.as_str())
+            .collect();
+        (!active.is_empty()).then(|| format!("- Don't bring up {}; you've contradicted yourself on it lately", active.join(", ")))
+    }
+
+    // Build a rig agent with the sampling parameters configured for this task
+    fn agent_for(&self, task: GenerationTask, preamble: &str) -> RigAgent<CompletionModel> {
+        let (default_temperature, default_max_tokens) = task.default_params();
+        let params = task.configured(&self.generation);
+
+        let mut builder = self.client
+            .agent(CLAUDE_3_HAIKU)
+            .preamble(preamble)
+            .temperature(params.temperature.unwrap_or(default_temperature))
+            .max_tokens(params.max_tokens.unwrap_or(default_max_tokens));
+
+        if let Some(top_p) = params.top_p {
+            builder = builder.additional_params(json!({ "top_p": top_p }));
+        }
+
+        builder.build()
+    }
+
+    // Run a prompt for the task, timing it for the stats dashboard
+    async fn complete(&self, task: GenerationTask, prompt: &str) -> Result<String, anyhow::Error> {
+        // The character prompt already goes out as the preamble, don't pay for it twice
+        let prompt = prompt
+            .strip_prefix(self.prompt.as_str())
+            .map(str::trim_start)
+            .unwrap_or(prompt);
+        let task_tokens = estimate_tokens(prompt);
+        // Fresh examples for every call, kept whole while the character preamble is trimmed
+        let examples = match self.examples.lock() {
+            Ok(mut examples) if task.writes_text() => examples.sample_section(&mut rand::thread_rng()),
+            _ => String::new(),
+        };
+        let preamble = self.preamble_within_budget(task_tokens + estimate_tokens(&examples)) + &examples;
+        let preamble_tokens = estimate_tokens(&preamble);
+        println!(
+            "Prompt size for {:?}: ~{} tokens (preamble ~{}, task ~{})",
+            task,
+            preamble_tokens + task_tokens,
+            preamble_tokens,
+            task_tokens
+        );
+
+        let started = Instant::now();
+        let attributes = [
+            ("provider", "anthropic".to_string()),
+            ("task", format!("{:?}", task)),
+            ("prompt_tokens", (preamble_tokens + task_tokens).to_string()),
+        ];
+        let guard = self.guard_for(task);
+        let call = async {
+            match guard.as_ref() {
+                Some(guard) => self.complete_guarded(task, &preamble, prompt, guard).await,
+                None if self.http.is_some() => self.complete_direct(task, &preamble, prompt).await,
+                None => Ok::<_, anyhow::Error>(self.agent_for(task, &preamble).prompt(prompt).await?),
+            }
+        };
+        let result = self
+            .tracer
+            .in_span("llm_call", &attributes, latency_budget::within("llm_call", self.llm_timeout, call))
+            .await;
+
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.count += 1;
+            stats.total_ms += started.elapsed().as_millis() as u64;
+            if result.is_err() {
+                stats.errors += 1;
             }
         }
+        self.audit_completion(
+            &format!("{:?}", task),
+            CLAUDE_3_HAIKU,
+            preamble,
+            prompt.to_string(),
+            result.as_ref().map_err(|e| e.to_string()),
+            started,
+        );
 
-        // Check for overused patterns
-        for (pattern, count) in &self.pattern_frequencies {
-            if *count > 3 && text.to_lowercase().contains(pattern) {
-                return true;
+        let text = result?;
+        if !task.writes_text() {
+            return Ok(text);
+        }
+        self.pipeline.run(self, task, Phase::Completion, text).await
+    }
+
+    // Banned terms for a text-writing task, with the aggression dial's tame words while
+    // it's turned down. Those apply even when stream_guard is off.
+    pub(crate) fn guard_for(&self, task: GenerationTask) -> Option<StreamGuard> {
+        if !task.writes_text() {
+            return None;
+        }
+        let aggression = self.aggression();
+        let tame_words = aggression.tame_words();
+        match &self.stream_guard {
+            Some(guard) => Some(guard.with_terms(tame_words)),
+            None if !tame_words.is_empty() => Some(StreamGuard::new(tame_words)),
+            None => None,
+        }
+    }
+
+    // Stream the completion, starting over whenever a guarded term shows up in it
+    async fn complete_guarded(
+        &self,
+        task: GenerationTask,
+        preamble: &str,
+        prompt: &str,
+        guard: &StreamGuard,
+    ) -> Result<String, anyhow::Error> {
+        for attempt in 1..=self.max_restarts + 1 {
+            match self.stream_once(task, preamble, prompt, guard).await? {
+                StreamOutcome::Finished(text) => return Ok(text),
+                StreamOutcome::Aborted { term, partial } => println!(
+                    "Cut off generation {} of {} at banned term '{}' after {} chars",
+                    attempt,
+                    self.max_restarts + 1,
+                    term,
+                    partial.chars().count()
+                ),
             }
         }
+        Err(anyhow::anyhow!("Generation kept using banned terms after {} restarts", self.max_restarts))
+    }
 
-        false
+    // Messages API request with the sampling parameters configured for this task
+    fn messages_body(&self, task: GenerationTask, preamble: &str, prompt: &str, stream: bool) -> Value {
+        let (default_temperature, default_max_tokens) = task.default_params();
+        let params = task.configured(&self.generation);
+        let mut body = json!({
+            "model": CLAUDE_3_HAIKU,
+            "system": preamble,
+            "max_tokens": params.max_tokens.unwrap_or(default_max_tokens),
+            "temperature": params.temperature.unwrap_or(default_temperature),
+            "stream": stream,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = json!(top_p);
+        }
+        body
     }
-}
 
-impl Agent {
-    pub fn new(anthropic_api_key: &str, prompt: &str) -> Self {
-        let client = anthropic::ClientBuilder::new(anthropic_api_key).build();
-        let rng = rand::thread_rng();
-        let temperature = 0.9;
+    async fn complete_direct(&self, task: GenerationTask, preamble: &str, prompt: &str) -> Result<String, anyhow::Error> {
+        let response = self
+            .http()
+            .post(ANTHROPIC_MESSAGES_URL)
+            .header("x-api-key", &self.anthropic_api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&self.messages_body(task, preamble, prompt, false))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Completion failed with status: {}", status));
+        }
+        let response: Value = response.json().await?;
+        Self::message_text(&response).ok_or_else(|| anyhow::anyhow!("Completion had no text"))
+    }
 
-        let agent = client
-            .agent(CLAUDE_3_HAIKU)
-            .preamble(prompt)
-            .temperature(temperature)
-            .max_tokens(4096)
-            .build();
-        Agent { 
-            agent,
-            anthropic_api_key: anthropic_api_key.to_string(),
-            prompt: prompt.to_string(),
-            fud_analysis: FudAnalysis::new(),  // Initialize FudAnalysis
+    async fn stream_once(
+        &self,
+        task: GenerationTask,
+        preamble: &str,
+        prompt: &str,
+        guard: &StreamGuard,
+    ) -> Result<StreamOutcome, anyhow::Error> {
+        let body = self.messages_body(task, preamble, prompt, true);
+        let mut response = self
+            .http()
+            .post(ANTHROPIC_MESSAGES_URL)
+            .header("x-api-key", &self.anthropic_api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Streaming completion failed with status: {}", status));
         }
+
+        let mut reader = SseReader::new();
+        let mut text = String::new();
+        while let Some(chunk) = response.chunk().await? {
+            for delta in reader.push(&chunk)? {
+                text.push_str(&delta);
+            }
+            // Dropping the response closes the stream, so nothing more is generated or billed
+            if let Some(term) = guard.partial_violation(&text) {
+                return Ok(StreamOutcome::Aborted { term: term.to_string(), partial: text });
+            }
+        }
+        if !reader.finished() {
+            return Err(anyhow::anyhow!("Completion stream ended early"));
+        }
+        // A term the text ends on was held back while more could follow
+        if let Some(term) = guard.violation(&text) {
+            return Ok(StreamOutcome::Aborted { term: term.to_string(), partial: text });
+        }
+        Ok(StreamOutcome::Finished(text))
+    }
+
+    // Full character preamble if it fits next to the task prompt, otherwise a trimmed one
+    fn preamble_within_budget(&self, task_tokens: usize) -> String {
+        if self.sections.is_empty() || self.max_input_tokens == 0 {
+            return self.prompt.clone();
+        }
+        let budget = self.max_input_tokens.saturating_sub(task_tokens);
+        if estimate_tokens(&self.prompt) <= budget {
+            return self.prompt.clone();
+        }
+
+        let fitted = prompt_budget::fit(&self.sections, budget);
+        println!(
+            "Preamble over budget ({} tokens), trimmed {} to ~{} tokens",
+            budget,
+            fitted.trimmed.join(", "),
+            fitted.tokens
+        );
+        fitted.text
+    }
+
+    // Hand over the counters collected since the last call and reset them
+    pub fn take_generation_stats(&self) -> GenerationStats {
+        self.stats
+            .lock()
+            .map(|mut stats| std::mem::take(&mut *stats))
+            .unwrap_or_default()
     }
 
     pub async fn should_respond(&self, tweet: &str) -> Result<ResponseDecision, anyhow::Error> {
@@ -111,7 +575,7 @@ impl Agent {
             - Spam/nonsensical\n\
             Answer:"
         );
-        let response = self.agent.prompt(&prompt).await?;
+        let response = self.complete(GenerationTask::Classify, &prompt).await?;
         let response = response.to_uppercase();
         Ok(if response.contains("[RESPOND]") {
             ResponseDecision::Respond
@@ -120,6 +584,56 @@ impl Agent {
         })
     }
 
+    // One call for up to CLASSIFY_BATCH_SIZE mentions; falls back to asking about
+    // each one separately if the model's answer can't be read
+    pub async fn should_respond_batch(&self, tweets: &[&str]) -> Result<Vec<ResponseDecision>, anyhow::Error> {
+        if tweets.len() > CLASSIFY_BATCH_SIZE {
+            return Err(anyhow::anyhow!(
+                "Can't classify {} mentions at once, the limit is {}",
+                tweets.len(),
+                CLASSIFY_BATCH_SIZE
+            ));
+        }
+        if tweets.len() <= 1 {
+            return self.should_respond_each(tweets).await;
+        }
+
+        let numbered: Vec<String> = tweets
+            .iter()
+            .enumerate()
+            .map(|(i, tweet)| format!("{}. {}", i + 1, tweet.replace('\n', " ")))
+            .collect();
+        let prompt = format!(
+            "Tweets:\n{}\n\
+            Task: Decide RESPOND or IGNORE for each tweet based on:\n\
+            RESPOND if:\n\
+            - Direct mention/address\n\
+            - Contains question\n\
+            - Contains command/request\n\
+            IGNORE if:\n\
+            - Unrelated content\n\
+            - Spam/nonsensical\n\
+            Answer with only a JSON array of {} strings in the same order, e.g. [\"RESPOND\", \"IGNORE\"]:",
+            numbered.join("\n"),
+            tweets.len()
+        );
+        let response = self.complete(GenerationTask::ClassifyBatch, &prompt).await?;
+        if let Some(decisions) = ResponseDecision::parse_batch(&response, tweets.len()) {
+            return Ok(decisions);
+        }
+
+        println!("Couldn't read batch classification, classifying mentions one by one");
+        self.should_respond_each(tweets).await
+    }
+
+    async fn should_respond_each(&self, tweets: &[&str]) -> Result<Vec<ResponseDecision>, anyhow::Error> {
+        let mut decisions = Vec::new();
+        for tweet in tweets {
+            decisions.push(self.should_respond(tweet).await?);
+        }
+        Ok(decisions)
+    }
+
     pub async fn generate_reply(&self, tweet: &str) -> Result<String, anyhow::Error> {
         let prompt = format!(
             "Task: Generate a post/reply in your voice, style and perspective while using this as context:\n\
@@ -128,35 +642,230 @@ impl Agent {
             - Uses all lowercase\n\
             - Avoids punctuation\n\
             - Is direct and very sarcastic\n\
-            - Stays under 280 characters\n\
+            {}\n\
             Write only the response text, nothing else:",
-            tweet
+            tweet,
+            self.language_requirements(280)
+        );
+        let response = self.complete(GenerationTask::Reply, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // What the images attached to a mention show, for the reply prompt to work from.
+    // Goes straight to the Messages API, since rig only sends text.
+    pub async fn describe_images(&self, model: Option<&str>, image_urls: &[String]) -> Result<String, anyhow::Error> {
+        let mut content: Vec<Value> = image_urls
+            .iter()
+            .map(|url| json!({ "type": "image", "source": { "type": "url", "url": url } }))
+            .collect();
+        let instruction = "Describe what these images show in two or three plain sentences. \
+            If one is a price chart, say which token if visible, the timeframe, the trend, \
+            and any lines or levels drawn on it. Just describe, no opinions.";
+        content.push(json!({ "type": "text", "text": instruction }));
+        let body = json!({
+            "model": model.unwrap_or(CLAUDE_3_HAIKU),
+            "max_tokens": DESCRIBE_IMAGES_MAX_TOKENS,
+            "temperature": 0.0,
+            "messages": [{ "role": "user", "content": content }],
+        });
+
+        let started = Instant::now();
+        let attributes = [
+            ("provider", "anthropic".to_string()),
+            ("task", "DescribeImages".to_string()),
+            ("images", image_urls.len().to_string()),
+        ];
+        let request = async {
+            let response = self
+                .http()
+                .post(ANTHROPIC_MESSAGES_URL)
+                .header("x-api-key", &self.anthropic_api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                .json(&body)
+                .send()
+                .await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(anyhow::anyhow!("Image description failed with status: {}", status));
+            }
+            let response: Value = response.json().await?;
+            Self::message_text(&response).ok_or_else(|| anyhow::anyhow!("Image description had no text"))
+        };
+        let result = self
+            .tracer
+            .in_span("llm_call", &attributes, latency_budget::within("llm_call", self.llm_timeout, request))
+            .await;
+
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.count += 1;
+            stats.total_ms += started.elapsed().as_millis() as u64;
+            if result.is_err() {
+                stats.errors += 1;
+            }
+        }
+        self.audit_completion(
+            "DescribeImages",
+            model.unwrap_or(CLAUDE_3_HAIKU),
+            String::new(),
+            format!("{}\nImages: {}", instruction, image_urls.join(" ")),
+            result.as_ref().map_err(|e| e.to_string()),
+            started,
+        );
+        Ok(result?)
+    }
+
+    fn audit_completion(
+        &self,
+        task: &str,
+        model: &str,
+        preamble: String,
+        prompt: String,
+        result: Result<&String, String>,
+        started: Instant,
+    ) {
+        if !self.audit.is_enabled() {
+            return;
+        }
+        let id = format!("{:016x}", rand::random::<u64>());
+        let (response, error) = match result {
+            Ok(response) => (Some(response.clone()), None),
+            Err(e) => (None, Some(e)),
+        };
+        self.audit.record(&AuditEntry::Completion {
+            id: id.clone(),
+            at: chrono::Utc::now(),
+            task: task.to_string(),
+            model: model.to_string(),
+            preamble,
+            prompt,
+            response,
+            error,
+            latency_ms: started.elapsed().as_millis() as u64,
+        });
+        *self.last_completion.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(id);
+    }
+
+    // Record a post-processing step against the latest completion, if it changed anything
+    pub fn audit_transform(&self, step: &str, before: &str, after: &str) {
+        if !self.audit.is_enabled() || before == after {
+            return;
+        }
+        let id = self.last_completion.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        self.audit.record(&AuditEntry::Transform {
+            id: id.unwrap_or_default(),
+            at: chrono::Utc::now(),
+            step: step.to_string(),
+            before: before.to_string(),
+            after: after.to_string(),
+        });
+    }
+
+    // Run a response headed for a post through the pipeline's post stages (trim, style, ...)
+    async fn post_process(&self, task: GenerationTask, response: &str) -> Result<String, anyhow::Error> {
+        self.pipeline.run(self, task, Phase::Post, response.to_string()).await
+    }
+
+    // Text blocks of a Messages API response, joined
+    pub fn message_text(response: &Value) -> Option<String> {
+        let text: Vec<&str> = response["content"]
+            .as_array()?
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        let text = text.join(" ").trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+
+    // Reply that goes after what the mention attached, usually a chart someone is proud of
+    pub async fn generate_attachment_reply(&self, tweet: &str, attachment: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "Task: Reply in your voice, style and perspective to a post that came with an image.\n\
+            Their post: '{}'\n\
+            What they attached: {}\n\
+            Generate a brief, single response that:\n\
+            - Roasts what they posted; if it's a chart, mock the pattern they think they see \
+            (e.g. \"that's not support, that's a cliff\")\n\
+            - Uses all lowercase\n\
+            - Is direct and very sarcastic\n\
+            {}\n\
+            Write only the response text, nothing else:",
+            tweet,
+            attachment,
+            self.language_requirements(280)
+        );
+        let response = self.complete(GenerationTask::Reply, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // Reviews the character's numbered recent posts; answers with a JSON array of findings
+    pub async fn review_consistency(&self, posts: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "Your recent posts, oldest first:\n{}\n\n\
+            Task: Review them as an editor keeping the character coherent. Find posts that:\n\
+            - Break your persona (tone, opinions or voice you wouldn't have)\n\
+            - Contradict another post (e.g. praising a token you trashed the day before)\n\
+            Changing your mind after a token dumped or pumped is fine if the post says so.\n\
+            Answer with only a JSON array, [] if nothing is wrong, e.g.\n\
+            [{{\"posts\": [3, 17], \"issue\": \"called $BONK a rug, then said it's going higher\", \"topic\": \"$BONK\"}}]\n\
+            \"topic\" is what to stop posting about for a while; leave it out when there's nothing specific:",
+            posts
+        );
+        let response = self.complete(GenerationTask::Review, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // Pulls factual claims about the token out of a mention; answers with a JSON array of strings
+    pub async fn extract_token_facts(&self, symbol: &str, mention: &str, max: usize) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "Someone posted this about ${symbol}:\n'{mention}'\n\n\
+            Task: List the factual claims it makes about ${symbol} or its team, such as \"dev doxxed yesterday\", \"LP unlocked\" or \"top wallet sold 30%\".\n\
+            - At most {max}, each a short standalone sentence under 120 characters\n\
+            - Only things that happened or are true of the token, not opinions, predictions, insults or questions\n\
+            - Keep their numbers and timing as they wrote them\n\
+            Answer with only a JSON array of strings, [] if there are none:"
+        );
+        let response = self.complete(GenerationTask::Review, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // The character's own edit of a draft post, used by the pipeline's critique stage
+    pub async fn critique(&self, draft: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "Your draft post:\n'{}'\n\n\
+            Task: Read it as your own harshest editor. If it's generic, wordy or the joke doesn't land, \
+            rewrite it sharper; if it's already good, keep it.\n\
+            - Keep the same language, token symbols and numbers\n\
+            - Stay under 280 characters\n\
+            Write only the final post text, nothing else:",
+            draft
         );
-        let response = self.agent.prompt(&prompt).await?;
+        let response = self.complete(GenerationTask::Review, &prompt).await?;
         Ok(response.trim().to_string())
     }
 
     pub async fn generate_custom_response(&self, prompt: &str) -> Result<String, anyhow::Error> {
-        let response = self.agent
-            .prompt(prompt)
+        let response = self.complete(GenerationTask::Custom, prompt)
             .await?;
 
         Ok(response.trim().to_string())
     }
 
     pub async fn generate_post(&self) -> Result<String, anyhow::Error> {
-        let prompt = r#"Write a 1-3 sentence post that would be engaging to readers. Your response should be the EXACT text of the tweet only, with no introductions, meta-commentary, or explanations.
+        let prompt = format!(r#"Write a 1-3 sentence post that would be engaging to readers. Your response should be the EXACT text of the tweet only, with no introductions, meta-commentary, or explanations.
 
             Requirements:
-            - Stay under 280 characters
+            {}
             - No emojis
             - No hashtags
             - No questions
             - Brief, concise statements only
             - Focus on personal experiences, observations, or thoughts
-            - Write ONLY THE TWEET TEXT with no additional words or commentary"#;
+            - Write ONLY THE TWEET TEXT with no additional words or commentary"#,
+            self.language_requirements(280)
+        );
         
-        let response = self.agent.prompt(&prompt).await?;
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
         Ok(response.trim().to_string())
     }
 
@@ -172,7 +881,7 @@ impl Agent {
             - Transform these elements creatively - don't use them verbatim\n\
             - Create unexpected analogies or metaphors\n\
             - Mix technical and casual language\n\
-            - Stay under 280 characters\n\
+            {}\n\
             - do not include any tickers or ticker symbols\n\
             - Use all lowercase\n\
             - Sound authentic - like a real frustrated trader\n\
@@ -180,14 +889,15 @@ impl Agent {
             self.prompt,    
             intro,
             reason,
-            closing
+            closing,
+            self.language_requirements(280)
         );
 
-        let response = self.agent.prompt(&prompt).await?;
-        Ok(self.ensure_unique_style(response.trim())?)
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
     }
 
-    pub async fn generate_editorialized_fud(&mut self, token_info: &str) -> Result<String, anyhow::Error> {
+    pub async fn generate_editorialized_fud(&self, token_info: &str) -> Result<String, anyhow::Error> {
         let prompt = format!(
             "{}\n\nTask: Generate unique, creative FUD about this token:\n{}\n\
             Requirements:\n\
@@ -195,7 +905,8 @@ impl Agent {
             - dont encapsulate your response in quotes\n\
             - Always use proper token symbol from the info\n\
             - Use numbers from the token info creatively and sarcastically\n\
-            - Stay under 350 characters no matter what.\n\
+            - If the info has a bundle check, call it out with its exact numbers: share of supply, wallets, fresh wallets\n\
+            {}\n\
             - Use all lowercase except for token symbols\n\
             - Avoid repetitive phrases and metaphors\n\
             - Variety is key - use different structures and approaches\n\
@@ -217,16 +928,15 @@ impl Agent {
             Write ONLY the tweet text with no additional commentary:",
             self.prompt,
             token_info,
+            self.language_requirements(350),
         );
     
         // Try generating a response up to 3 times if we get repetitive content
         for attempt in 0..3 {
-            let response = self.agent.prompt(&prompt).await?;
-            let processed_response = self.ensure_unique_style(response.trim())?;
+            let response = self.complete(GenerationTask::Fud, &prompt).await?;
+            let processed_response = self.post_process(GenerationTask::Fud, &response).await?;
             
-            if attempt == 2 || !self.fud_analysis.is_overused(&processed_response) {
-                // Update our analysis with the new content
-                self.fud_analysis.update(&processed_response);
+            if attempt == 2 || !self.style().is_overused(&processed_response) {
                 return Ok(processed_response);
             }
             
@@ -239,80 +949,377 @@ impl Agent {
         Err(anyhow::anyhow!("Failed to generate unique FUD content"))
     }
 
-    fn ensure_unique_style(&self, response: &str) -> Result<String, anyhow::Error> {
-        use rand::seq::SliceRandom;
-        let mut rng = rand::thread_rng();
+    // FUD for a token still on its pump.fun bonding curve: it hasn't even graduated yet
+    pub async fn generate_prebond_fud(&self, token_info: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Generate FUD about this pump.fun token that is still on its bonding curve:\n{}\n\
+            Requirements:\n\
+            - Mock that it hasn't graduated yet, using the real bonding curve progress from the info\n\
+            - If the dev bought a big share at launch, make that the punchline; if the dev bought nothing, mock that instead\n\
+            - If it never made king of the hill, or fell off it, rub it in\n\
+            - If the info has a bundle check, call it out with its exact numbers: share of supply, wallets, fresh wallets\n\
+            - Don't treat it like a real market yet: no talk of listings, partnerships or roadmaps\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            token_info,
+            self.language_requirements(280),
+        );
 
-        // Common patterns to detect and vary
-        let common_patterns = [
-            "ser", "ngmi", "wen", "just", "literally", "probably",
-            "definitely", "obviously", "clearly", "absolutely"
-        ];
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
 
-        let mut processed = response.to_string();
+    // FUD about the chain itself rather than any token
+    pub async fn generate_network_fud(&self, network_stats: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Write FUD about the state of the Solana network right now.\n\
+            Network stats:\n{}\n\
+            Requirements:\n\
+            - Use the real numbers: TPS, priority fees, failed transactions, Jito tips\n\
+            - If fees or failures are high, mock the congestion (exit liquidity that can't even exit, paying tips to get rugged faster)\n\
+            - If the chain is quiet, mock that instead: nobody is using it\n\
+            - Don't mention any specific token\n\
+            {}\n\
+            - Use all lowercase\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            network_stats,
+            self.language_requirements(280),
+        );
 
-        // Check for overuse of common patterns
-        let mut pattern_count = 0;
-        for pattern in common_patterns.iter() {
-            if processed.to_lowercase().contains(pattern) {
-                pattern_count += 1;
-            }
-        }
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
 
-        // If too many common patterns, try to replace some
-        if pattern_count > 2 {
-            // Alternative expressions to mix things up
-            let alternatives = vec![
-                "looking kinda", "straight up", "ngl", "fr fr",
-                "lowkey", "highkey", "certified", "actual"
-            ];
-
-            for pattern in common_patterns.iter() {
-                if processed.to_lowercase().contains(pattern) && rng.gen_bool(0.7) {
-                    if let Some(alt) = alternatives.choose(&mut rng) {
-                        processed = processed.replacen(pattern, alt, 1);
-                    }
-                }
-            }
-        }
+    // Take on a fresh crypto headline, in character
+    pub async fn generate_news_reaction(&self, headline: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: React to this crypto news headline.\n\
+            Headline: {}\n\
+            Requirements:\n\
+            - Make clear what the news is so the tweet stands on its own\n\
+            - Give your cynical read on it: who's exit liquidity, who's about to get rugged, why it's not as good as it sounds\n\
+            - Don't invent details that aren't in the headline\n\
+            {}\n\
+            - Use all lowercase\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            headline,
+            self.language_requirements(280),
+        );
 
-        // Check sentence structure patterns
-        let starts_with_common = [
-            "another", "just", "ser", "breaking:", "imagine"
-        ];
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
+        self.post_process(GenerationTask::Post, &response).await
+    }
 
-        let starts_common = starts_with_common.iter()
-            .any(|&start| processed.to_lowercase().starts_with(start));
-
-        // If it starts with a common pattern, maybe add a variation
-        if starts_common && rng.gen_bool(0.6) {
-            let variations = [
-                "bruh", "certified", "actual", "friendly reminder:",
-                "psa:", "reminder:", "daily dose of"
-            ];
-            if let Some(variation) = variations.choose(&mut rng) {
-                processed = format!("{} {}", variation, processed);
-            }
-        }
+    // Jab at a Space in the niche before it starts
+    pub async fn generate_space_commentary(&self, space: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Write a snarky tweet about this upcoming Twitter Space.\n\
+            Space: {}\n\
+            Requirements:\n\
+            - Make clear which Space it is and when, so the tweet stands on its own\n\
+            - Predict what will really happen in it: the shilling, the exit liquidity, the hosts' bags\n\
+            - Tag the hosts only if they're given above\n\
+            {}\n\
+            - Use all lowercase\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            space,
+            self.language_requirements(280),
+        );
 
-        // Add occasional punctuation variation
-        if !processed.contains('?') && !processed.contains('!') && rng.gen_bool(0.3) {
-            let punctuation = ["..", "...", "!!", "!?", "???"]
-                .choose(&mut rng)
-                .unwrap();
-            processed = format!("{}{}", processed, punctuation);
-        }
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
+        self.post_process(GenerationTask::Post, &response).await
+    }
 
-        Ok(processed)
+    // Tweet making a concrete call on a token; `claim` is the exact call and must appear in it
+    pub async fn generate_prediction(&self, token_info: &str, claim: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Make a public prediction about this token. The call is: {}\n\
+            Token info:\n{}\n\
+            Requirements:\n\
+            - State the call exactly as given, including the number and the deadline\n\
+            - Sound certain; this gets graded publicly later\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            claim,
+            token_info,
+            self.language_requirements(240),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
     }
 
-    pub async fn generate_image(&self) -> Result<String, anyhow::Error> {
+    // Follow-up to a graded prediction, gloating or coping depending on the result
+    pub async fn generate_prediction_result(&self, result_info: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Your prediction's deadline has passed. Post the result.\n\
+            {}\n\
+            Requirements:\n\
+            - Quote the real numbers from the result\n\
+            - If you called it, gloat; if you missed, own it in character without pretending you were right\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            result_info,
+            self.language_requirements(220),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    // Opening tweet of the weekly recap thread; the leaderboard follows in replies
+    pub async fn generate_weekly_recap(&self, recap: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Open a thread recapping this week's carnage among the tokens you FUDded.\n\
+            {}\n\
+            Requirements:\n\
+            - Gloat about the worst dump using its real percentage\n\
+            - Tease that the full leaderboard is in the thread below\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            recap,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    pub async fn generate_campaign_recap(&self, campaign: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Wrap up your campaign against this token now that it's over.\n\
+            {}\n\
+            Requirements:\n\
+            - Call out how the price did over the campaign using its real percentage\n\
+            - If it went up, cope or double down; never pretend it dumped\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            campaign,
+            // The stats block goes underneath
+            self.language_requirements(180),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    pub async fn generate_trend_reply(&self, token_info: &str, tweet: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Reply to this tweet hyping a trending token with targeted FUD.\n\
+            Tweet: '{}'\n\
+            Token info:\n{}\n\
+            Requirements:\n\
+            - Ground the criticism in the numbers from the token info\n\
+            - Respond to what the tweet actually says\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the reply text with no additional commentary:",
+            self.prompt,
+            tweet,
+            token_info,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    // Caption for a screenshot of the token's website, roasting how it looks
+    pub async fn generate_website_roast(&self, token_info: &str, website: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: You're posting a screenshot of this token's website ({}). Write the tweet that goes with it.\n\
+            Token info:\n{}\n\
+            Requirements:\n\
+            - Mock the site's design and copy: the template, the fonts, the roadmap, the promises\n\
+            - Quote the real website copy or description from the info if there is any\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            website,
+            token_info,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    // One-line caption for an image post
+    pub async fn generate_meme_caption(&self) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Write a caption for a meme image you're posting about the state of the memecoin market.\n\
+            Requirements:\n\
+            - One short line, it's the image that does the talking\n\
+            {}\n\
+            - No hashtags\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the caption text with no additional commentary:",
+            self.prompt,
+            self.language_requirements(120),
+        );
+
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // Morning-style roundup of what's trending, in character
+    pub async fn generate_trending_summary(&self, token_summaries: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: Write a short roundup of today's trending tokens.\n\
+            Trending tokens:\n{}\n\
+            Requirements:\n\
+            - Mention two or three of the tokens by symbol with one cynical line each\n\
+            - Use the real numbers from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            token_summaries,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    // One reply trashing every token in a "$A vs $B" style question, each with its own stats
+    pub async fn generate_comparison_fud(&self, token_summaries: &[String], tweet: &str) -> Result<String, anyhow::Error> {
+        let tokens = token_summaries
+            .iter()
+            .enumerate()
+            .map(|(i, summary)| format!("Token {}:\n{}", i + 1, summary))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "{}\n\nTask: Someone asked you to compare these tokens. Reply with FUD that trashes all of them.\n\
+            Tweet: '{}'\n\
+            {}\n\
+            Requirements:\n\
+            - Hit every token with a criticism grounded in its own numbers\n\
+            - Don't pick a winner, they're all going to zero\n\
+            - Always use the proper token symbols from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the reply text with no additional commentary:",
+            self.prompt,
+            tweet,
+            tokens,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    // A post to go with the side-by-side card: neither token is the better bet
+    pub async fn generate_matchup_fud(&self, token_summaries: &[String], card: &str) -> Result<String, anyhow::Error> {
+        let tokens = token_summaries
+            .iter()
+            .enumerate()
+            .map(|(i, summary)| format!("Token {}:\n{}", i + 1, summary))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "{}\n\nTask: Write a tweet to go with an image comparing two trending tokens side by side. Declare both of them garbage.\n\
+            {}\n\
+            The image shows:\n{}\n\
+            Requirements:\n\
+            - Name both tokens by symbol and knock each one with a number from the image\n\
+            - Don't pick a winner, it's a race to zero\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            tokens,
+            card,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        self.post_process(GenerationTask::Fud, &response).await
+    }
+
+    // Promote our own token; `task` is the character's filled-in shill template
+    pub async fn generate_shill(&self, task: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: {}\n\
+            Requirements:\n\
+            - Never FUD or doubt this token, it is the one exception to your cynicism\n\
+            - Keep the token symbol exactly as given\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            task,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Post, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+    pub async fn generate_whale_alert(&self, token_info: &str, trade_info: &str) -> Result<String, anyhow::Error> {
+        let prompt = format!(
+            "{}\n\nTask: You posted FUD about this token earlier. A whale just dumped it. Write a gleeful follow-up.\n\
+            Token info:\n{}\n\
+            The sell:\n{}\n\
+            Requirements:\n\
+            - Gloat that you called it (e.g. 'dev just moved 12% of supply, told you')\n\
+            - Use the real numbers from the sell\n\
+            - Always use proper token symbol from the info\n\
+            {}\n\
+            - Use all lowercase except for token symbols\n\
+            - dont encapsulate your response in quotes\n\
+            Write ONLY the tweet text with no additional commentary:",
+            self.prompt,
+            token_info,
+            trade_info,
+            self.language_requirements(280),
+        );
+
+        let response = self.complete(GenerationTask::Fud, &prompt).await?;
+        Ok(response.trim().to_string())
+    }
+
+
+    pub async fn generate_image(&self, heurist: &HeuristConfig) -> Result<String, anyhow::Error> {
         let client = reqwest::Client::builder().build()?;
-        dotenv::dotenv().ok();
-        let heuris_api = env::var("HEURIS_API")
-            .map_err(|_| anyhow::anyhow!("HEURIS_API not found in environment"))?;
-        let base_prompt = env::var("IMAGE_PROMPT")
-            .map_err(|_| anyhow::anyhow!("IMAGE_PROMPT not found in environment"))?;
+        let heuris_api = heurist.api_key.expose();
+        let base_prompt = &heurist.image_prompt;
         let deadline = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 300;
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert("Authorization", format!("Bearer {}", heuris_api).parse()?);
@@ -0,0 +1,140 @@
+use std::fs;
+use std::io;
+
+use super::character::AggressionSettings;
+use super::paths;
+
+pub const MIN_LEVEL: u8 = 1;
+pub const MAX_LEVEL: u8 = 10;
+// The character exactly as its prompt is written: no tone line, emojis as configured
+pub const DEFAULT_LEVEL: u8 = 7;
+
+// Left by `aggression <level|reset>` for the running bot to pick up
+pub const AGGRESSION_REQUEST_PATH: &str = "./storage/aggression_request";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggressionCommand {
+    Set(u8),
+    // Back to the character's own level
+    Reset,
+    Current,
+}
+
+// The aggression dial, shared by the runtime and every agent so a change applies to
+// the next generation
+#[derive(Debug, Clone, Default)]
+pub struct Aggression {
+    settings: AggressionSettings,
+    // Set by the operator; wins over the character's level until reset
+    override_level: Option<u8>,
+}
+
+impl Aggression {
+    pub fn new(settings: AggressionSettings, override_level: Option<u8>) -> Self {
+        Aggression { settings, override_level }
+    }
+
+    // A character swap brings its own level; an operator override stays
+    pub fn set_settings(&mut self, settings: AggressionSettings) {
+        self.settings = settings;
+    }
+
+    pub fn set_override(&mut self, level: Option<u8>) {
+        self.override_level = level.map(|level| level.clamp(MIN_LEVEL, MAX_LEVEL));
+    }
+
+    pub fn override_level(&self) -> Option<u8> {
+        self.override_level
+    }
+
+    pub fn level(&self) -> u8 {
+        self.override_level.unwrap_or(self.settings.level).clamp(MIN_LEVEL, MAX_LEVEL)
+    }
+
+    pub fn is_tame(&self) -> bool {
+        self.level() <= self.settings.tame_below
+    }
+
+    // Requirement line for generation prompts; none at the default level
+    pub fn tone_requirement(&self) -> Option<&'static str> {
+        match self.level() {
+            1..=2 => Some("- Keep it gentle: playful doubt about the token only. No insults, no profanity, nothing about the devs or holders as people"),
+            3..=4 => Some("- Tone it down: dry skepticism about the numbers and the chart, no profanity and nothing personal"),
+            5..=6 => Some("- Be blunt but measured: mock the token, not the people behind it"),
+            DEFAULT_LEVEL => None,
+            8..=9 => Some("- Turn it up: harsher and more cutting than usual"),
+            _ => Some("- Go all out: the most savage take you can write, within the rules above"),
+        }
+    }
+
+    // Extra banned terms while the dial is down
+    pub fn tame_words(&self) -> &[String] {
+        if self.is_tame() {
+            &self.settings.tame_words
+        } else {
+            &[]
+        }
+    }
+
+    // Multiplies the character's emoji_chance: none while tame, more above the default
+    pub fn emoji_scale(&self) -> f64 {
+        if self.is_tame() {
+            0.0
+        } else {
+            self.level() as f64 / DEFAULT_LEVEL as f64
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.override_level {
+            Some(level) => format!("Aggression {}/{} (set by the operator; the character's is {})", level, MAX_LEVEL, self.settings.level),
+            None => format!("Aggression {}/{} (the character's own)", self.level(), MAX_LEVEL),
+        }
+    }
+
+    pub fn parse_level(text: &str) -> Option<u8> {
+        text.trim().parse::<u8>().ok().filter(|level| (MIN_LEVEL..=MAX_LEVEL).contains(level))
+    }
+
+    // "aggression <1-10>", "aggression reset" or "aggression", with or without a
+    // leading slash and the @botname Telegram appends in groups
+    pub fn parse_command(text: &str) -> Option<AggressionCommand> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.trim_start_matches('/');
+        let command = command.split('@').next().unwrap_or(command);
+        if !command.eq_ignore_ascii_case("aggression") {
+            return None;
+        }
+        match (words.next(), words.next()) {
+            (None, _) => Some(AggressionCommand::Current),
+            (Some(action), None) if action.eq_ignore_ascii_case("reset") => Some(AggressionCommand::Reset),
+            (Some(level), None) => Self::parse_level(level).map(AggressionCommand::Set),
+            _ => None,
+        }
+    }
+
+    pub fn write_request(command: &AggressionCommand) -> io::Result<()> {
+        let request = match command {
+            AggressionCommand::Set(level) => level.to_string(),
+            AggressionCommand::Reset => "reset".to_string(),
+            AggressionCommand::Current => return Ok(()),
+        };
+        let path = paths::resolve(AGGRESSION_REQUEST_PATH);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, request)
+    }
+
+    // The pending request, removed so it's only applied once
+    pub fn take_request() -> io::Result<Option<AggressionCommand>> {
+        let path = paths::resolve(AGGRESSION_REQUEST_PATH);
+        let request = match fs::read_to_string(&path) {
+            Ok(request) => request,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        fs::remove_file(&path)?;
+        Ok(Self::parse_command(&format!("aggression {}", request.trim())))
+    }
+}
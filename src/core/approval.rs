@@ -0,0 +1,52 @@
+use crate::models::HeldReply;
+use crate::providers::twitter::Twitter;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApprovalCommand {
+    Approve(String),
+    Reject(String),
+    Pending,
+}
+
+// Replies held back by an `escalate` reply rule, released or dropped by Telegram admins
+pub struct Approvals;
+
+impl Approvals {
+    // "/approve <tweet id>", "/reject <tweet id>" or "/pending", with the @botname
+    // Telegram appends in groups
+    pub fn parse_command(text: &str) -> Option<ApprovalCommand> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.trim_start_matches('/');
+        let command = command.split('@').next().unwrap_or(command).to_lowercase();
+        match (command.as_str(), words.next(), words.next()) {
+            ("approve", Some(id), None) => Some(ApprovalCommand::Approve(id.to_string())),
+            ("reject", Some(id), None) => Some(ApprovalCommand::Reject(id.to_string())),
+            ("pending", None, _) => Some(ApprovalCommand::Pending),
+            _ => None,
+        }
+    }
+
+    // Sent to admins when a reply is held
+    pub fn notice(held: &HeldReply) -> String {
+        format!(
+            "Reply held by rule {}\nMention: {}\n{}\nDraft: {}\n\n/approve {} or /reject {}",
+            held.rule,
+            held.mention,
+            Twitter::tweet_url(&held.reply_to),
+            held.text,
+            held.reply_to,
+            held.reply_to
+        )
+    }
+
+    pub fn pending_summary(held: &[HeldReply]) -> String {
+        if held.is_empty() {
+            return "No replies waiting for approval".to_string();
+        }
+        let lines: Vec<String> = held
+            .iter()
+            .map(|reply| format!("{} ({}): {}", reply.reply_to, reply.rule, reply.text))
+            .collect();
+        format!("{} waiting for approval:\n{}", held.len(), lines.join("\n"))
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use super::config::AuditConfig;
+use super::paths;
+
+pub const DEFAULT_AUDIT_DIR: &str = "./storage/audit";
+pub const DEFAULT_MAX_FILE_MB: u64 = 50;
+
+// One line of the audit log. A completion and the transformations applied to its
+// response share an id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEntry {
+    Completion {
+        id: String,
+        at: DateTime<Utc>,
+        task: String,
+        model: String,
+        // System prompt as sent, after any trimming to the prompt budget
+        preamble: String,
+        prompt: String,
+        response: Option<String>,
+        error: Option<String>,
+        latency_ms: u64,
+    },
+    Transform {
+        id: String,
+        at: DateTime<Utc>,
+        step: String,
+        before: String,
+        after: String,
+    },
+}
+
+struct AuditWriter {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    // Day and part being written, so a full file isn't stat'ed on every line
+    current: Mutex<Option<(NaiveDate, u32)>>,
+}
+
+// Every prompt, raw response and post-processing step, one JSONL file per day under
+// storage/audit/, for working out why the bot said something. A day's file moves
+// on to a numbered part once it reaches the size limit. Cloned into every agent;
+// does nothing unless AUDIT_LOG is on.
+#[derive(Clone, Default)]
+pub struct AuditLog {
+    writer: Option<Arc<AuditWriter>>,
+}
+
+impl AuditLog {
+    pub fn new(config: Option<&AuditConfig>) -> Self {
+        match config {
+            Some(config) => Self::in_dir(paths::resolve(DEFAULT_AUDIT_DIR), config.max_file_mb.max(1) * 1024 * 1024),
+            None => AuditLog::default(),
+        }
+    }
+
+    pub fn in_dir(dir: impl AsRef<Path>, max_file_bytes: u64) -> Self {
+        AuditLog {
+            writer: Some(Arc::new(AuditWriter {
+                dir: dir.as_ref().to_path_buf(),
+                max_file_bytes,
+                current: Mutex::new(None),
+            })),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    // 2024-05-01.jsonl, then 2024-05-01.1.jsonl once that is full
+    pub fn file_name(date: NaiveDate, part: u32) -> String {
+        match part {
+            0 => format!("{}.jsonl", date.format("%Y-%m-%d")),
+            part => format!("{}.{}.jsonl", date.format("%Y-%m-%d"), part),
+        }
+    }
+
+    // Failures are logged and dropped; auditing never holds up posting
+    pub fn record(&self, entry: &AuditEntry) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        if let Err(e) = writer.append(entry) {
+            eprintln!("Failed to write audit log: {}", e);
+        }
+    }
+}
+
+impl AuditWriter {
+    fn append(&self, entry: &AuditEntry) -> std::io::Result<()> {
+        let line = serde_json::to_string(entry)?;
+        let date = Utc::now().date_naive();
+        let mut current = self.current.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut part = match *current {
+            Some((day, part)) if day == date => part,
+            _ => 0,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        let mut path = self.dir.join(AuditLog::file_name(date, part));
+        while fs::metadata(&path).is_ok_and(|meta| meta.len() >= self.max_file_bytes) {
+            part += 1;
+            path = self.dir.join(AuditLog::file_name(date, part));
+        }
+        *current = Some((date, part));
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::Duration;
+use serde::Serialize;
+
+use super::character::TargetSelectionSettings;
+use crate::models::{TokenSnapshot, TrendingSnapshot};
+
+// Horizons we score picks over, in hours
+pub const HORIZONS: [i64; 2] = [24, 72];
+// A pick counts as having dumped if it lost at least this much
+const DUMP_THRESHOLD_PERCENT: f64 = -30.0;
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct Outcome {
+    // Picks we found a later price for
+    pub measured: usize,
+    pub avg_change_percent: Option<f64>,
+    pub dumped_percent: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct HorizonResult {
+    pub hours: i64,
+    pub selected: Outcome,
+    pub baseline: Outcome,
+}
+
+// How the current target filters would have done against everything that was trending
+#[derive(Serialize, Debug)]
+pub struct BacktestReport {
+    pub snapshots: usize,
+    pub trending_seen: usize,
+    pub selected: usize,
+    pub horizons: Vec<HorizonResult>,
+}
+
+pub struct Backtest;
+
+impl Backtest {
+    // Scheduled FUD picks one random eligible token, so every eligible token is scored
+    // as an equally likely pick; the baseline is every trending token unfiltered
+    pub fn run(snapshots: &[TrendingSnapshot], filters: &TargetSelectionSettings) -> BacktestReport {
+        let mut snapshots: Vec<&TrendingSnapshot> = snapshots.iter().collect();
+        snapshots.sort_by_key(|s| s.timestamp);
+
+        let mut trending_seen = 0;
+        let mut selected = 0;
+        let mut changes: Vec<(Vec<f64>, Vec<f64>)> = HORIZONS.iter().map(|_| (Vec::new(), Vec::new())).collect();
+
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            for token in &snapshot.tokens {
+                if token.mint.is_empty() || token.price_usd <= 0.0 {
+                    continue;
+                }
+                trending_seen += 1;
+                let is_selected = filters.matches(token);
+                if is_selected {
+                    selected += 1;
+                }
+
+                for (h, hours) in HORIZONS.iter().enumerate() {
+                    let Some(later_price) = Self::price_after(&snapshots[i + 1..], snapshot, token, *hours) else {
+                        continue;
+                    };
+                    let change = (later_price - token.price_usd) / token.price_usd * 100.0;
+                    changes[h].1.push(change);
+                    if is_selected {
+                        changes[h].0.push(change);
+                    }
+                }
+            }
+        }
+
+        let horizons = HORIZONS
+            .iter()
+            .zip(changes)
+            .map(|(hours, (selected, baseline))| HorizonResult {
+                hours: *hours,
+                selected: Self::outcome(&selected),
+                baseline: Self::outcome(&baseline),
+            })
+            .collect();
+
+        BacktestReport {
+            snapshots: snapshots.len(),
+            trending_seen,
+            selected,
+            horizons,
+        }
+    }
+
+    // Price of the token in the first later snapshot at least `hours` after this one,
+    // allowing a quarter of the horizon of slack since snapshots aren't evenly spaced
+    fn price_after(
+        later: &[&TrendingSnapshot],
+        snapshot: &TrendingSnapshot,
+        token: &TokenSnapshot,
+        hours: i64,
+    ) -> Option<f64> {
+        let target = snapshot.timestamp + Duration::hours(hours);
+        let latest = target + Duration::hours(hours) / 4;
+        later
+            .iter()
+            .skip_while(|s| s.timestamp < target)
+            .take_while(|s| s.timestamp <= latest)
+            .find_map(|s| s.tokens.iter().find(|t| t.mint == token.mint && t.price_usd > 0.0))
+            .map(|t| t.price_usd)
+    }
+
+    fn outcome(changes: &[f64]) -> Outcome {
+        if changes.is_empty() {
+            return Outcome::default();
+        }
+        let measured = changes.len();
+        let avg = changes.iter().sum::<f64>() / measured as f64;
+        let dumped = changes.iter().filter(|c| **c <= DUMP_THRESHOLD_PERCENT).count();
+        Outcome {
+            measured,
+            avg_change_percent: Some(avg),
+            dumped_percent: Some(dumped as f64 / measured as f64 * 100.0),
+        }
+    }
+}
+
+impl BacktestReport {
+    pub fn print(&self) {
+        println!("=== FUD Target Backtest ===");
+        println!(
+            "Snapshots: {}  Trending tokens seen: {}  Passed filters: {}",
+            self.snapshots, self.trending_seen, self.selected
+        );
+        for horizon in &self.horizons {
+            println!("\n+{}h:", horizon.hours);
+            Self::print_outcome("selected", &horizon.selected);
+            Self::print_outcome("baseline", &horizon.baseline);
+        }
+        if self.horizons.iter().all(|h| h.baseline.measured == 0) {
+            println!("\nNo token was seen again 24h+ later; let the bot record more snapshots first.");
+        }
+    }
+
+    fn print_outcome(label: &str, outcome: &Outcome) {
+        match (outcome.avg_change_percent, outcome.dumped_percent) {
+            (Some(avg), Some(dumped)) => println!(
+                "  {:<9} {:>5} measured  avg {:>+7.1}%  dumped {:>5.1}%",
+                label, outcome.measured, avg, dumped
+            ),
+            _ => println!("  {:<9} no data", label),
+        }
+    }
+}
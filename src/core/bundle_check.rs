@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+
+use super::character::BundleCheckSettings;
+use crate::providers::solana_rpc::{SignatureInfo, TokenBalanceChange};
+
+// A wallet's purchase in the launch window
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchBuy {
+    pub wallet: String,
+    pub amount: f64,
+    // The buy's transaction, for looking at what the wallet did before it
+    pub signature: String,
+}
+
+// Bundled buying at launch: how much of the supply went to wallets buying in the
+// first block(s), and how many of those wallets had never been used before
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundleReport {
+    pub launch_slots: u64,
+    pub wallets: usize,
+    pub fresh_wallets: usize,
+    pub supply_pct: f64,
+}
+
+impl BundleReport {
+    // A line for the token summary, written so the FUD can quote the numbers as they are
+    pub fn summary(&self) -> String {
+        let window = match self.launch_slots {
+            1 => "in the launch block".to_string(),
+            slots => format!("in the first {} blocks", slots),
+        };
+        let fresh = match self.fresh_wallets {
+            0 => String::new(),
+            n if n == self.wallets => ", all of them fresh wallets".to_string(),
+            n => format!(", {} of them fresh wallets", n),
+        };
+        format!(
+            "Bundle check: {:.1}% of supply bought {} by {} wallets{}\n",
+            self.supply_pct, window, self.wallets, fresh
+        )
+    }
+}
+
+// Spots bundled launches from RPC data alone: buys landing in the same block(s)
+// as the token's first transaction, spread over several wallets
+pub struct BundleCheck;
+
+impl BundleCheck {
+    // Successful transactions in the first `launch_slots` slots, oldest first. Expects
+    // every signature back to the token's creation, newest first as the RPC returns
+    // them; a partial history would put the launch somewhere in the middle of its
+    // trading.
+    pub fn launch_window(signatures: &[SignatureInfo], launch_slots: u64, limit: usize) -> Vec<&SignatureInfo> {
+        let Some(first_slot) = signatures.iter().map(|info| info.slot).min() else {
+            return Vec::new();
+        };
+        // By slot, then by position in the slot, so the create transaction is never
+        // the one `limit` cuts off
+        let mut window: Vec<(usize, &SignatureInfo)> = signatures
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(_, info)| !info.failed && info.slot < first_slot + launch_slots.max(1))
+            .collect();
+        window.sort_by_key(|(index, info)| (info.slot, *index));
+        let mut window: Vec<&SignatureInfo> = window.into_iter().map(|(_, info)| info).collect();
+        window.truncate(limit);
+        window
+    }
+
+    // What each wallet bought in the launch window, from the balance changes of its
+    // transactions. Anything whose balance went down in the window is the curve or a
+    // pool handing out tokens, or a sniper already selling, and isn't a buyer.
+    pub fn launch_buys(transactions: &[(String, Vec<TokenBalanceChange>)]) -> Vec<LaunchBuy> {
+        let sellers: HashSet<&str> = transactions
+            .iter()
+            .flat_map(|(_, changes)| changes)
+            .filter(|change| change.amount < 0.0)
+            .map(|change| change.owner.as_str())
+            .collect();
+        transactions
+            .iter()
+            .flat_map(|(signature, changes)| changes.iter().map(move |change| (signature, change)))
+            .filter(|(_, change)| change.amount > 0.0 && !sellers.contains(change.owner.as_str()))
+            .map(|(signature, change)| LaunchBuy {
+                wallet: change.owner.clone(),
+                amount: change.amount,
+                signature: signature.clone(),
+            })
+            .collect()
+    }
+
+    // None when the launch doesn't look bundled: a lone buyer is just the dev, and a
+    // small share isn't worth a tweet
+    pub fn report(
+        settings: &BundleCheckSettings,
+        buys: &[LaunchBuy],
+        fresh: &HashSet<String>,
+        supply: f64,
+    ) -> Option<BundleReport> {
+        if supply <= 0.0 {
+            return None;
+        }
+        let wallets: HashSet<&str> = buys.iter().filter(|buy| buy.amount > 0.0).map(|buy| buy.wallet.as_str()).collect();
+        let bought: f64 = buys.iter().map(|buy| buy.amount.max(0.0)).sum();
+        let supply_pct = bought / supply * 100.0;
+        if wallets.len() < settings.min_wallets || supply_pct < settings.min_supply_pct {
+            return None;
+        }
+        Some(BundleReport {
+            launch_slots: settings.launch_slots.max(1),
+            wallets: wallets.len(),
+            fresh_wallets: wallets.iter().filter(|wallet| fresh.contains(**wallet)).count(),
+            supply_pct,
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+use super::character::CallbackSettings;
+use crate::models::Tweet;
+
+// Standard BM25 parameters: term frequency saturation and length normalization
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+// Words every token summary and FUD post has, which only add noise to matching
+const STOPWORDS: [&str; 40] = [
+    "the", "and", "for", "with", "this", "that", "are", "was", "you", "your", "its", "not", "but", "have", "has",
+    "from", "they", "their", "just", "all", "any", "can", "who", "what", "will", "about", "into", "out", "more",
+    "than", "been", "our", "token", "price", "market", "cap", "liquidity", "volume", "holders", "usd",
+];
+
+// First and last line of prompt_section, so a saved context can be told apart from it
+const SECTION_START: &str = "A past post of yours about a similar situation that landed well";
+const SECTION_END: &str = "Call back to it if it fits";
+
+// BM25 over a fixed set of documents
+pub struct Bm25 {
+    docs: Vec<HashMap<String, usize>>,
+    lengths: Vec<usize>,
+    average_length: f64,
+    doc_freq: HashMap<String, usize>,
+}
+
+impl Bm25 {
+    // Lowercased words of three or more letters; numbers and stopwords are dropped
+    pub fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .map(str::to_lowercase)
+            .filter(|word| word.chars().count() >= 3)
+            .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+            .filter(|word| !STOPWORDS.contains(&word.as_str()))
+            .collect()
+    }
+
+    pub fn new(documents: &[String]) -> Self {
+        let mut docs = Vec::with_capacity(documents.len());
+        let mut lengths = Vec::with_capacity(documents.len());
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+        for document in documents {
+            let words = Self::tokenize(document);
+            lengths.push(words.len());
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for word in words {
+                *counts.entry(word).or_insert(0) += 1;
+            }
+            for word in counts.keys() {
+                *doc_freq.entry(word.clone()).or_insert(0) += 1;
+            }
+            docs.push(counts);
+        }
+        let average_length = if lengths.is_empty() {
+            0.0
+        } else {
+            lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+        };
+        Bm25 { docs, lengths, average_length, doc_freq }
+    }
+
+    pub fn score(&self, query: &[String], doc: usize) -> f64 {
+        let (Some(counts), Some(&length)) = (self.docs.get(doc), self.lengths.get(doc)) else {
+            return 0.0;
+        };
+        let total = self.docs.len() as f64;
+        let norm = if self.average_length > 0.0 { length as f64 / self.average_length } else { 0.0 };
+        query
+            .iter()
+            .filter_map(|word| {
+                let tf = *counts.get(word)? as f64;
+                let df = self.doc_freq.get(word).copied().unwrap_or(0) as f64;
+                let idf = ((total - df + 0.5) / (df + 0.5) + 1.0).ln();
+                Some(idf * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * norm)))
+            })
+            .sum()
+    }
+
+    // Documents matching the query, best first
+    pub fn search(&self, query: &str) -> Vec<(usize, f64)> {
+        let mut query = Self::tokenize(query);
+        query.sort();
+        query.dedup();
+        let mut scored: Vec<(usize, f64)> = (0..self.docs.len())
+            .map(|doc| (doc, self.score(&query, doc)))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored
+    }
+}
+
+// A well-received past post about a similar situation, for running gags and callbacks
+pub struct Callbacks;
+
+impl Callbacks {
+    // Posts are matched on the summary they were written from as well as their text.
+    // Ones about the same token are left to past takes, and recent ones would read as
+    // repeating ourselves rather than a callback.
+    pub fn find<'a>(tweets: &'a [Tweet], situation: &str, exclude_mint: &str, settings: &CallbackSettings, now: DateTime<Utc>) -> Option<&'a Tweet> {
+        let newest = now - Duration::days(settings.min_age_days);
+        let oldest = now - Duration::days(settings.lookback_days);
+        let candidates: Vec<&Tweet> = tweets
+            .iter()
+            .filter(|tweet| tweet.twitter_id.is_some() && tweet.timestamp <= newest && tweet.timestamp >= oldest)
+            .filter(|tweet| exclude_mint.is_empty() || tweet.token_mint.as_deref() != Some(exclude_mint))
+            .filter(|tweet| tweet.metrics.as_ref().is_some_and(|metrics| metrics.total() >= settings.min_engagement))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let documents: Vec<String> = candidates
+            .iter()
+            .map(|tweet| format!("{}\n{}", Self::base_context(tweet.context.as_deref().unwrap_or_default()), tweet.text))
+            .collect();
+        // The closest matches, with engagement breaking near-ties toward the funnier line
+        Bm25::new(&documents)
+            .search(situation)
+            .into_iter()
+            .filter(|(_, score)| *score >= settings.min_score)
+            .map(|(doc, score)| {
+                let engagement = candidates[doc].metrics.as_ref().map_or(0, |metrics| metrics.total());
+                (doc, score * (1.0 + (engagement as f64).ln_1p() / 10.0))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(doc, _)| candidates[doc])
+    }
+
+    // A post's saved context without the callback section it was written with, which
+    // would otherwise match the next situation on the older post's words and chain
+    // callbacks to callbacks
+    pub fn base_context(context: &str) -> String {
+        let mut lines = Vec::new();
+        let mut in_section = false;
+        for line in context.lines() {
+            if line.starts_with(SECTION_START) {
+                in_section = true;
+            }
+            if !in_section {
+                lines.push(line);
+            }
+            if in_section && line.starts_with(SECTION_END) {
+                in_section = false;
+            }
+        }
+        lines.join("\n")
+    }
+
+    pub fn prompt_section(tweet: &Tweet, now: DateTime<Utc>) -> String {
+        let likes = tweet.metrics.as_ref().map_or(0, |metrics| metrics.likes);
+        format!(
+            "{} ({} days ago, {} likes): '{}'\n\
+            {} (a running gag, 'said the same about the last one'), but don't repeat it word for word.\n",
+            SECTION_START,
+            now.signed_duration_since(tweet.timestamp).num_days(),
+            likes,
+            tweet.text,
+            SECTION_END
+        )
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Duration, Utc};
+use std::fs;
+use std::io;
+
+use super::follow_up::FollowUp;
+use super::paths;
+use super::token_extractor::TokenExtractor;
+use crate::models::{Campaign, TokenSnapshot};
+use crate::providers::solanatracker::SolanaTracker;
+
+// Left by `campaign start|stop` for the running bot to pick up
+pub const CAMPAIGN_REQUEST_PATH: &str = "./storage/campaign_request";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CampaignCommand {
+    // No hours means campaigns.default_hours
+    Start { mint: String, hours: Option<i64> },
+    Stop,
+    Status,
+}
+
+impl Campaign {
+    pub fn start(token: &TokenSnapshot, hours: i64, source: &str, now: DateTime<Utc>) -> Self {
+        Campaign {
+            mint: token.mint.clone(),
+            symbol: token.symbol.clone(),
+            source: source.to_string(),
+            started_at: now,
+            ends_at: now + Duration::hours(hours),
+            start_price: token.price_usd,
+            start_market_cap: token.market_cap,
+            low_price: token.price_usd,
+            high_price: token.price_usd,
+            last_price: token.price_usd,
+            last_market_cap: token.market_cap,
+            last_checked_at: Some(now),
+            posts: 0,
+            mentions: Vec::new(),
+            finished_at: None,
+            recap_tweet_id: None,
+        }
+    }
+
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        self.finished_at.is_none() && now < self.ends_at
+    }
+
+    // Past its end and still waiting for the recap
+    pub fn is_over(&self, now: DateTime<Utc>) -> bool {
+        self.finished_at.is_none() && now >= self.ends_at
+    }
+
+    pub fn price_check_due(&self, interval: Duration, now: DateTime<Utc>) -> bool {
+        self.last_checked_at.is_none_or(|at| now.signed_duration_since(at) >= interval)
+    }
+
+    // A price check; a token the API reports at zero is left at the last good price
+    pub fn observe(&mut self, token: &TokenSnapshot, now: DateTime<Utc>) {
+        self.last_checked_at = Some(now);
+        if token.price_usd <= 0.0 {
+            return;
+        }
+        self.last_price = token.price_usd;
+        self.last_market_cap = token.market_cap;
+        self.low_price = self.low_price.min(token.price_usd);
+        self.high_price = self.high_price.max(token.price_usd);
+    }
+
+    pub fn change_pct(&self) -> f64 {
+        if self.start_price <= 0.0 {
+            return 0.0;
+        }
+        (self.last_price / self.start_price - 1.0) * 100.0
+    }
+
+    // "$SYMBOL" anywhere in the text, or the mint
+    pub fn mentioned_in(&self, text: &str) -> bool {
+        if text.contains(&self.mint) {
+            return true;
+        }
+        let tag = format!("${}", self.symbol.to_lowercase());
+        text.to_lowercase()
+            .split(|c: char| !(c.is_alphanumeric() || c == '$' || c == '_'))
+            .any(|word| word == tag)
+    }
+
+    // False if it was already counted
+    pub fn record_mention(&mut self, tweet_id: &str) -> bool {
+        if self.mentions.iter().any(|id| id == tweet_id) {
+            return false;
+        }
+        self.mentions.push(tweet_id.to_string());
+        true
+    }
+
+    pub fn hours(&self) -> i64 {
+        self.ends_at.signed_duration_since(self.started_at).num_hours()
+    }
+
+    // Numbers for the recap tweet's prompt
+    pub fn summary(&self) -> String {
+        format!(
+            "Campaign target: ${} ({})\nLength: {}h\nPrice at start: {}\nPrice now: {} ({:+.1}%)\nLowest price: {}\nHighest price: {}\nMarket cap now: {}\nPosts about it: {}\nMentions of it we got: {}\n",
+            self.symbol,
+            self.mint,
+            self.hours(),
+            FollowUp::format_price(self.start_price),
+            FollowUp::format_price(self.last_price),
+            self.change_pct(),
+            FollowUp::format_price(self.low_price),
+            FollowUp::format_price(self.high_price),
+            SolanaTracker::format_currency(self.last_market_cap),
+            self.posts,
+            self.mentions.len(),
+        )
+    }
+
+    // Stats block posted under the recap text
+    pub fn recap_lines(&self) -> String {
+        format!(
+            "${} over {}h: {:+.1}%\nlow {} | high {}\n{} posts, {} mentions",
+            self.symbol,
+            self.hours(),
+            self.change_pct(),
+            FollowUp::format_price(self.low_price),
+            FollowUp::format_price(self.high_price),
+            self.posts,
+            self.mentions.len(),
+        )
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> String {
+        let left = self.ends_at.signed_duration_since(now);
+        format!(
+            "Campaign on ${} ({}), {}h{:02}m left: {:+.1}% since it started, {} posts, {} mentions",
+            self.symbol,
+            self.mint,
+            left.num_hours().max(0),
+            (left.num_minutes() % 60).max(0),
+            self.change_pct(),
+            self.posts,
+            self.mentions.len(),
+        )
+    }
+}
+
+// Starting and stopping campaigns, from the CLI or Telegram
+pub struct Campaigns;
+
+impl Campaigns {
+    // "campaign start <mint> [hours]", "campaign stop" or "campaign", with or without
+    // a leading slash and the @botname Telegram appends in groups
+    pub fn parse_command(text: &str) -> Option<CampaignCommand> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.trim_start_matches('/');
+        let command = command.split('@').next().unwrap_or(command);
+        if !command.eq_ignore_ascii_case("campaign") {
+            return None;
+        }
+        match (words.next(), words.next(), words.next(), words.next()) {
+            (None, ..) => Some(CampaignCommand::Status),
+            (Some(action), None, ..) if action.eq_ignore_ascii_case("status") => Some(CampaignCommand::Status),
+            (Some(action), None, ..) if action.eq_ignore_ascii_case("stop") => Some(CampaignCommand::Stop),
+            (Some(action), Some(mint), hours, None) if action.eq_ignore_ascii_case("start") => {
+                if !TokenExtractor::is_solana_address(mint) {
+                    return None;
+                }
+                let hours = match hours {
+                    Some(hours) => Some(hours.trim_end_matches('h').parse::<i64>().ok().filter(|hours| *hours > 0)?),
+                    None => None,
+                };
+                Some(CampaignCommand::Start { mint: mint.to_string(), hours })
+            }
+            _ => None,
+        }
+    }
+
+    // Index of the campaign that hasn't been wrapped up yet, if any
+    pub fn current(campaigns: &[Campaign]) -> Option<usize> {
+        campaigns.iter().rposition(|campaign| campaign.finished_at.is_none())
+    }
+
+    pub fn running(campaigns: &[Campaign], now: DateTime<Utc>) -> Option<&Campaign> {
+        campaigns.iter().rev().find(|campaign| campaign.is_running(now))
+    }
+
+    pub fn write_request(command: &CampaignCommand) -> io::Result<()> {
+        let request = match command {
+            CampaignCommand::Start { mint, hours: Some(hours) } => format!("start {} {}", mint, hours),
+            CampaignCommand::Start { mint, hours: None } => format!("start {}", mint),
+            CampaignCommand::Stop => "stop".to_string(),
+            CampaignCommand::Status => return Ok(()),
+        };
+        let path = paths::resolve(CAMPAIGN_REQUEST_PATH);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, request)
+    }
+
+    // The pending request, removed so it's only applied once
+    pub fn take_request() -> io::Result<Option<CampaignCommand>> {
+        let path = paths::resolve(CAMPAIGN_REQUEST_PATH);
+        let request = match fs::read_to_string(&path) {
+            Ok(request) => request,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        fs::remove_file(&path)?;
+        Ok(Self::parse_command(&format!("campaign {}", request.trim())))
+    }
+}
@@ -1,4 +1,13 @@
+use chrono::{DateTime, Utc, Weekday};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::language_detect::LanguageDetector;
+use super::reply_rules::ReplyRule;
+use crate::models::{OnChainReceipt, TokenSnapshot};
+use crate::providers::wallet::{BUY_OVERHEAD_LAMPORTS, SIGNATURE_FEE_LAMPORTS};
 
 #[derive(Deserialize)]
 pub struct CharacterBio {
@@ -21,4 +30,1560 @@ pub struct Character {
     pub styles: Vec<String>,
     pub topics: Vec<String>,
     pub post_style_examples: Vec<String>,
-} 
\ No newline at end of file
+    #[serde(default)]
+    pub generation: GenerationSettings,
+    #[serde(flatten)]
+    pub settings: CharacterSettings,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct GenerationParams {
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u64>,
+    pub top_p: Option<f64>,
+}
+
+// Per-task overrides; anything left unset falls back to the task's defaults
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct GenerationSettings {
+    pub classify: GenerationParams,
+    pub classify_batch: GenerationParams,
+    pub reply: GenerationParams,
+    pub post: GenerationParams,
+    pub fud: GenerationParams,
+    pub custom: GenerationParams,
+    pub review: GenerationParams,
+}
+
+// Runtime behaviour for the character, read by Runtime rather than the prompt builder
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct CharacterSettings {
+    #[serde(default)]
+    pub trend_engagement: TrendEngagementSettings,
+    #[serde(default)]
+    pub network_fud: NetworkFudSettings,
+    #[serde(default)]
+    pub predictions: PredictionSettings,
+    #[serde(default)]
+    pub news: NewsSettings,
+    #[serde(default)]
+    pub spaces: SpaceSettings,
+    #[serde(default)]
+    pub communities: CommunitySettings,
+    #[serde(default)]
+    pub chart_images: ChartImageSettings,
+    #[serde(default)]
+    pub reaction_clips: ReactionClipSettings,
+    #[serde(default)]
+    pub timeline_sync: TimelineSyncSettings,
+    #[serde(default)]
+    pub stream_guard: StreamGuardSettings,
+    #[serde(default)]
+    pub pipeline: PipelineSettings,
+    #[serde(default)]
+    pub outbox: OutboxSettings,
+    #[serde(default)]
+    pub storage: StorageSettings,
+    #[serde(default)]
+    pub content_buffer: ContentBufferSettings,
+    #[serde(default)]
+    pub consistency_check: ConsistencyCheckSettings,
+    #[serde(default)]
+    pub telegram_inline: TelegramInlineSettings,
+    #[serde(default)]
+    pub address_check: AddressCheckSettings,
+    #[serde(default)]
+    pub weekly_recap: WeeklyRecapSettings,
+    #[serde(default)]
+    pub language: LanguageSettings,
+    #[serde(default)]
+    pub whale_alerts: WhaleAlertSettings,
+    #[serde(default)]
+    pub reply_queue: ReplyQueueSettings,
+    #[serde(default)]
+    pub api_quota: ApiQuotaSettings,
+    #[serde(default)]
+    pub adaptive_schedule: AdaptiveScheduleSettings,
+    #[serde(default)]
+    pub quiet_hours: QuietHoursSettings,
+    // IANA name like "America/New_York" that schedule hours are read in; UTC when unset
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub attachments: AttachmentSettings,
+    #[serde(default)]
+    pub shill: ShillSettings,
+    #[serde(default)]
+    pub phrase_decay: PhraseDecaySettings,
+    #[serde(default)]
+    pub decoration: DecorationSettings,
+    #[serde(default)]
+    pub aggression: AggressionSettings,
+    #[serde(default)]
+    pub campaigns: CampaignSettings,
+    #[serde(default)]
+    pub past_takes: PastTakesSettings,
+    #[serde(default)]
+    pub learned_facts: LearnedFactsSettings,
+    #[serde(default)]
+    pub callbacks: CallbackSettings,
+    #[serde(default)]
+    pub fud_targets: TargetSelectionSettings,
+    #[serde(default)]
+    pub target_scoring: TargetScoringSettings,
+    #[serde(default)]
+    pub token_heat: TokenHeatSettings,
+    #[serde(default)]
+    pub latency_budget: LatencyBudgetSettings,
+    #[serde(default)]
+    pub price_check: PriceCheckSettings,
+    #[serde(default)]
+    pub token_metadata: TokenMetadataSettings,
+    #[serde(default)]
+    pub pump_fun: PumpFunSettings,
+    #[serde(default)]
+    pub birdeye: BirdeyeSettings,
+    #[serde(default)]
+    pub bundle_check: BundleCheckSettings,
+    #[serde(default)]
+    pub website_roast: WebsiteRoastSettings,
+    #[serde(default)]
+    pub onchain_receipts: OnChainReceiptSettings,
+    #[serde(default)]
+    pub spam_filter: SpamFilterSettings,
+    // Checked in order against every mention before the reply is generated
+    #[serde(default)]
+    pub reply_rules: Vec<ReplyRule>,
+    #[serde(default)]
+    pub reply_limits: ReplyLimitSettings,
+    #[serde(default)]
+    pub reply_policy: ReplyPolicySettings,
+    #[serde(default)]
+    pub prompt_budget: PromptBudgetSettings,
+    #[serde(default)]
+    pub few_shot: FewShotSettings,
+    #[serde(default)]
+    pub example_rotation: ExampleRotationSettings,
+    // Time-of-day table for what scheduled posts should be; empty means always FUD
+    #[serde(default)]
+    pub content_schedule: Vec<ContentSlot>,
+    // Words the character must never use; checked against its own content by validate-character
+    #[serde(default)]
+    pub banned_words: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TrendEngagementSettings {
+    pub enabled: bool,
+    pub schedule_minutes: Vec<u32>,
+    pub tokens_to_search: usize,
+    pub min_engagement: usize,
+    pub max_replies_per_hour: usize,
+    pub author_cooldown_hours: i64,
+}
+
+impl Default for TrendEngagementSettings {
+    fn default() -> Self {
+        TrendEngagementSettings {
+            enabled: false,
+            schedule_minutes: vec![7, 37],
+            tokens_to_search: 5,
+            min_engagement: 20,
+            max_replies_per_hour: 2,
+            author_cooldown_hours: 24,
+        }
+    }
+}
+
+// Posts about Solana congestion and fees, on their own schedule next to token FUD
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct NetworkFudSettings {
+    pub enabled: bool,
+    // Hours to post in, in the character's timezone, at `minute` past the hour
+    pub schedule_hours: Vec<u32>,
+    pub minute: u32,
+}
+
+impl Default for NetworkFudSettings {
+    fn default() -> Self {
+        NetworkFudSettings {
+            enabled: false,
+            schedule_hours: vec![3, 11, 19],
+            minute: 50,
+        }
+    }
+}
+
+// Falsifiable market cap calls (the "prediction" content type) and how they're graded
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PredictionSettings {
+    // How far under its current market cap the token is called to go, in percent
+    pub drop_pct: f64,
+    pub deadline_hours: i64,
+    // How often open calls have their market cap checked
+    pub check_interval_minutes: i64,
+}
+
+impl Default for PredictionSettings {
+    fn default() -> Self {
+        PredictionSettings {
+            drop_pct: 50.0,
+            deadline_hours: 72,
+            check_interval_minutes: 30,
+        }
+    }
+}
+
+// Where the "news" content type finds headlines and which ones it reacts to
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct NewsSettings {
+    // RSS feeds read alongside CryptoPanic
+    pub feeds: Vec<String>,
+    // A headline needs one of these in its title, matched as whole words
+    pub keywords: Vec<String>,
+    pub max_age_hours: i64,
+}
+
+impl Default for NewsSettings {
+    fn default() -> Self {
+        NewsSettings {
+            feeds: vec![
+                "https://cointelegraph.com/rss".to_string(),
+                "https://decrypt.co/feed".to_string(),
+            ],
+            keywords: ["solana", "sol", "memecoin", "memecoins", "meme coin", "pump.fun", "bonk", "dogwifhat"]
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            max_age_hours: 12,
+        }
+    }
+}
+
+// What the "space" content type searches for and how far ahead it looks
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SpaceSettings {
+    // Each term is a separate Spaces search, in order, until one turns something up
+    pub search_terms: Vec<String>,
+    pub lookahead_hours: i64,
+}
+
+impl Default for SpaceSettings {
+    fn default() -> Self {
+        SpaceSettings {
+            search_terms: vec!["solana".to_string(), "memecoin".to_string()],
+            lookahead_hours: 24,
+        }
+    }
+}
+
+// Mentions posted inside a Twitter Community
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct CommunitySettings {
+    // Look up which mentions came from a Community; one extra tweet lookup per sweep
+    pub enabled: bool,
+    // Reply to them, knowing the Community's name, or leave them alone
+    pub reply: bool,
+}
+
+impl Default for CommunitySettings {
+    fn default() -> Self {
+        CommunitySettings { enabled: false, reply: true }
+    }
+}
+
+// How often the stock charts in storage/charts may be reused
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ChartImageSettings {
+    // A chart posted within this many days isn't attached again
+    pub reuse_after_days: i64,
+}
+
+impl Default for ChartImageSettings {
+    fn default() -> Self {
+        ChartImageSettings { reuse_after_days: 7 }
+    }
+}
+
+// GIFs and short MP4s from storage/videos attached to FUD now and then
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReactionClipSettings {
+    pub enabled: bool,
+    // Chance per FUD post that doesn't get a token card
+    pub chance: f64,
+    // A clip posted within this many days isn't attached again
+    pub reuse_after_days: i64,
+}
+
+impl Default for ReactionClipSettings {
+    fn default() -> Self {
+        ReactionClipSettings { enabled: false, chance: 0.2, reuse_after_days: 14 }
+    }
+}
+
+// Streaming completions so one that uses a banned term is cut off and regenerated
+// right away, instead of paying for the whole tweet and throwing it out
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct StreamGuardSettings {
+    pub enabled: bool,
+    // Checked along with banned_words
+    pub blocklist: Vec<String>,
+    // Fresh attempts after a cut-off completion before the generation fails
+    pub max_restarts: u32,
+}
+
+impl Default for StreamGuardSettings {
+    fn default() -> Self {
+        StreamGuardSettings { enabled: false, blocklist: Vec::new(), max_restarts: 2 }
+    }
+}
+
+// Text stages each generation goes through after the model call, by name. sanitize and
+// safety run on every text the model writes; trim, style, length and critique only on
+// text headed for a post.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PipelineSettings {
+    pub stages: Vec<String>,
+}
+
+impl Default for PipelineSettings {
+    fn default() -> Self {
+        PipelineSettings {
+            stages: ["sanitize", "safety", "trim", "style"].iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+// Checking memory against the account's real timeline at startup
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TimelineSyncSettings {
+    pub enabled: bool,
+    // How many of our latest tweets to look at, 5 to 100
+    pub max_tweets: usize,
+}
+
+impl Default for TimelineSyncSettings {
+    fn default() -> Self {
+        TimelineSyncSettings { enabled: true, max_tweets: 50 }
+    }
+}
+
+// Contract addresses found in mentions are confirmed as token mints over RPC
+// before they're looked up, so wallets and token accounts aren't FUDded
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct AddressCheckSettings {
+    pub verify_on_chain: bool,
+}
+
+// Size quotas for the subdirectories of storage/ the bot writes to, and the free disk
+// space it keeps before refusing to generate images
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct StorageSettings {
+    pub enabled: bool,
+    pub interval_minutes: i64,
+    // Most megabytes a subdirectory may hold before its oldest files are deleted
+    pub quotas_mb: BTreeMap<String, u64>,
+    pub min_free_mb: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        StorageSettings {
+            enabled: true,
+            interval_minutes: 60,
+            quotas_mb: [("audit", 1024), ("export", 256)]
+                .iter()
+                .map(|(name, mb)| (name.to_string(), *mb))
+                .collect(),
+            min_free_mb: 500,
+        }
+    }
+}
+
+// Posts are written to storage/outbox/ before they're sent and retried from there
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct OutboxSettings {
+    pub enabled: bool,
+    // Tries in all, including the first
+    pub max_attempts: u32,
+    // Wait after the first failure, doubled after each one after that
+    pub base_backoff_minutes: i64,
+    pub max_backoff_minutes: i64,
+    // Posts older than this are dropped instead of going out late
+    pub max_age_hours: i64,
+    // How long delivered and abandoned items stay on disk
+    pub keep_days: i64,
+}
+
+impl Default for OutboxSettings {
+    fn default() -> Self {
+        OutboxSettings {
+            enabled: true,
+            max_attempts: 6,
+            base_backoff_minutes: 5,
+            max_backoff_minutes: 240,
+            max_age_hours: 12,
+            keep_days: 7,
+        }
+    }
+}
+
+// Weekly thread ranking the tokens we FUDded by how far they've dumped since
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct WeeklyRecapSettings {
+    pub enabled: bool,
+    // Day and hour to post in the character's timezone, e.g. "sun"
+    pub weekday: Weekday,
+    pub hour: u32,
+    pub minute: u32,
+    pub top_tokens: usize,
+}
+
+impl Default for WeeklyRecapSettings {
+    fn default() -> Self {
+        WeeklyRecapSettings {
+            enabled: false,
+            weekday: Weekday::Sun,
+            hour: 18,
+            minute: 20,
+            top_tokens: 5,
+        }
+    }
+}
+
+// Widens the gap between scheduled posts while they get no engagement and tightens it
+// when they do. Scheduled posts still only go out on the quarter-hour marks.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct AdaptiveScheduleSettings {
+    pub enabled: bool,
+    pub min_interval_minutes: i64,
+    pub max_interval_minutes: i64,
+    // How many recent original posts to average
+    pub lookback_posts: usize,
+    // Younger posts haven't collected their engagement yet
+    pub min_post_age_minutes: i64,
+    // Average likes + retweets + replies + quotes per post
+    pub low_engagement: f64,
+    pub high_engagement: f64,
+    // Factor the interval is multiplied or divided by per adjustment
+    pub step: f64,
+}
+
+impl Default for AdaptiveScheduleSettings {
+    fn default() -> Self {
+        AdaptiveScheduleSettings {
+            enabled: false,
+            min_interval_minutes: 15,
+            max_interval_minutes: 180,
+            lookback_posts: 8,
+            min_post_age_minutes: 60,
+            low_engagement: 2.0,
+            high_engagement: 25.0,
+            step: 1.5,
+        }
+    }
+}
+
+// Scheduled posts generated ahead of time and kept in the outbox until their slot
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ContentBufferSettings {
+    pub enabled: bool,
+    // Posts kept ready at once
+    pub size: usize,
+    // Buffered posts older than this are thrown away
+    pub max_age_minutes: i64,
+    // Older than this, the token's numbers are looked up again before posting
+    pub refresh_after_minutes: i64,
+}
+
+impl Default for ContentBufferSettings {
+    fn default() -> Self {
+        ContentBufferSettings {
+            enabled: false,
+            size: 2,
+            max_age_minutes: 90,
+            refresh_after_minutes: 15,
+        }
+    }
+}
+
+// Periodically asks the model whether recent posts contradict the persona or each other
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ConsistencyCheckSettings {
+    pub enabled: bool,
+    pub interval_hours: i64,
+    // Latest posts reviewed per check
+    pub sample_size: usize,
+    // Keep topics the check flags out of generated posts
+    pub flag_topics: bool,
+    pub avoid_days: i64,
+}
+
+impl Default for ConsistencyCheckSettings {
+    fn default() -> Self {
+        ConsistencyCheckSettings {
+            enabled: false,
+            interval_hours: 24,
+            sample_size: 50,
+            flag_topics: false,
+            avoid_days: 7,
+        }
+    }
+}
+
+// Telegram inline mode: "@bot $TICKER" in any chat returns FUD and a stat card
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TelegramInlineSettings {
+    pub enabled: bool,
+    // How long a token's answer is reused, here and by Telegram
+    pub cache_minutes: i64,
+    // Queries answered per poll, and at most this many being written at once; the
+    // rest go unanswered
+    pub max_per_poll: usize,
+    // Fresh answers each Telegram user gets per hour; cached tokens don't count, 0 is no limit
+    pub max_per_user_per_hour: usize,
+}
+
+impl Default for TelegramInlineSettings {
+    fn default() -> Self {
+        TelegramInlineSettings {
+            enabled: false,
+            cache_minutes: 10,
+            max_per_poll: 3,
+            max_per_user_per_hour: 10,
+        }
+    }
+}
+
+// Hours the bot doesn't post on its own schedule (end exclusive), in the character's
+// timezone. Mentions are still answered.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct QuietHoursSettings {
+    pub enabled: bool,
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        QuietHoursSettings {
+            enabled: false,
+            start_hour: 4,
+            end_hour: 10,
+        }
+    }
+}
+
+// What a scheduled post does when a stage runs out of time
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutFallback {
+    // Use the last trending list if it's recent enough, otherwise post generic content
+    CachedThenGeneric,
+    Generic,
+    // Give up on this slot
+    Skip,
+}
+
+// Per-stage time limits for scheduled posts, so one slow provider doesn't hold the
+// loop past the next slot
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct LatencyBudgetSettings {
+    pub enabled: bool,
+    // Trending list from SolanaTracker
+    pub fetch_seconds: u64,
+    // Supply, metadata and bonding curve lookups for the picked token, together
+    pub enrich_seconds: u64,
+    // Each model call
+    pub llm_seconds: u64,
+    // Oldest trending list still used when a fetch times out
+    pub cache_max_age_minutes: i64,
+    pub on_timeout: TimeoutFallback,
+}
+
+impl Default for LatencyBudgetSettings {
+    fn default() -> Self {
+        LatencyBudgetSettings {
+            enabled: true,
+            fetch_seconds: 15,
+            enrich_seconds: 20,
+            llm_seconds: 45,
+            cache_max_age_minutes: 60,
+            on_timeout: TimeoutFallback::CachedThenGeneric,
+        }
+    }
+}
+
+impl LatencyBudgetSettings {
+    // None when budgets are off or the stage has no limit
+    pub fn limit(&self, seconds: u64) -> Option<std::time::Duration> {
+        (self.enabled && seconds > 0).then(|| std::time::Duration::from_secs(seconds))
+    }
+}
+
+// FUD per token shared by every character pointed at the same store, so several
+// personas don't pile onto one trending token
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TokenHeatSettings {
+    pub enabled: bool,
+    // Each FUD's contribution halves this often
+    pub half_life_hours: f64,
+    // Tokens at or above this are skipped; one FUD counts 1.0 when it's posted
+    pub max_heat: f64,
+    pub store_path: String,
+}
+
+impl Default for TokenHeatSettings {
+    fn default() -> Self {
+        TokenHeatSettings {
+            enabled: false,
+            half_life_hours: 6.0,
+            max_heat: 0.5,
+            store_path: "./storage/token_heat.kv".to_string(),
+        }
+    }
+}
+
+// Images attached to mentions, described by a vision-capable model so the reply
+// can go after the chart itself instead of just the text
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct AttachmentSettings {
+    pub enabled: bool,
+    // Images per mention sent to the model; the rest are ignored
+    pub max_images: usize,
+    // Anthropic model used for the description, when it should differ from the default
+    pub vision_model: Option<String>,
+}
+
+impl Default for AttachmentSettings {
+    fn default() -> Self {
+        AttachmentSettings {
+            enabled: false,
+            max_images: 2,
+            vision_model: None,
+        }
+    }
+}
+
+// Monthly Twitter API caps. Every endpoint's calls are tracked; only the ones
+// listed in `limits` are enforced.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ApiQuotaSettings {
+    // Day of the month (UTC) the plan's caps reset, 1-28
+    pub reset_day: u32,
+    // Calls allowed per window, keyed by endpoint ("tweets", "mentions", "search", ...)
+    pub limits: BTreeMap<String, u64>,
+    // Share of the "tweets" limit kept for scheduled posts; replies stop once only this is left
+    pub post_reserve_pct: f64,
+}
+
+impl Default for ApiQuotaSettings {
+    fn default() -> Self {
+        ApiQuotaSettings {
+            reset_day: 1,
+            limits: BTreeMap::new(),
+            post_reserve_pct: 20.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReplyQueueSettings {
+    pub replies_per_window: u32,
+    pub window_minutes: i64,
+    pub max_attempts: u32,
+}
+
+impl Default for ReplyQueueSettings {
+    fn default() -> Self {
+        ReplyQueueSettings {
+            replies_per_window: 5,
+            window_minutes: 15,
+            max_attempts: 3,
+        }
+    }
+}
+
+impl ReplyQueueSettings {
+    // Gap between queued replies so a full window's worth is spread evenly across it
+    pub fn spacing(&self) -> chrono::Duration {
+        let window_seconds = self.window_minutes.max(1) * 60;
+        chrono::Duration::seconds(window_seconds / i64::from(self.replies_per_window.max(1)))
+    }
+
+    pub fn window(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.window_minutes.max(1))
+    }
+}
+
+// How long posted phrases count as "recent" when rejecting repetitive output
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PhraseDecaySettings {
+    pub max_phrases: usize,
+    pub max_age_hours: i64,
+}
+
+impl Default for PhraseDecaySettings {
+    fn default() -> Self {
+        PhraseDecaySettings {
+            max_phrases: 200,
+            max_age_hours: 24,
+        }
+    }
+}
+
+// Emojis and punctuation the style engine may add to generated posts, per character
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DecorationSettings {
+    pub emojis: Vec<String>,
+    // Chance a post gets between one and max_emojis of them
+    pub emoji_chance: f64,
+    pub max_emojis: usize,
+    // Trailing punctuation added to posts without a ! or ? in them
+    pub punctuation: Vec<String>,
+    pub punctuation_chance: f64,
+    // Removed from every generated post, whoever wrote them in
+    pub never_use: Vec<String>,
+}
+
+impl Default for DecorationSettings {
+    fn default() -> Self {
+        DecorationSettings {
+            emojis: ["💀", "🤡", "🚮", "🗑️", "⚰️", "🤮", "🚨", "⚠️", "🤢", "💩"]
+                .iter()
+                .map(|emoji| emoji.to_string())
+                .collect(),
+            emoji_chance: 0.0,
+            max_emojis: 2,
+            punctuation: ["..", "...", "!!", "!?", "???"].iter().map(|p| p.to_string()).collect(),
+            punctuation_chance: 0.3,
+            never_use: Vec::new(),
+        }
+    }
+}
+
+// How hard the character goes, from 1 to 10; 7 is the character as written. The
+// operator can move it while running, with /aggression on Telegram or the CLI.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct AggressionSettings {
+    pub level: u8,
+    // Banned along with banned_words while the level is at or below tame_below
+    pub tame_words: Vec<String>,
+    pub tame_below: u8,
+}
+
+impl Default for AggressionSettings {
+    fn default() -> Self {
+        AggressionSettings { level: 7, tame_words: Vec::new(), tame_below: 4 }
+    }
+}
+
+// FUD campaigns on one token, started with `campaign start <mint> [hours]`
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct CampaignSettings {
+    // Chance a scheduled post slot goes to FUD on the campaign token. Those slots
+    // don't wait out the adaptive schedule's hold.
+    pub target_share: f64,
+    pub price_check_minutes: i64,
+    pub default_hours: i64,
+    pub max_hours: i64,
+    // Post a recap when the campaign ends
+    pub recap: bool,
+}
+
+impl Default for CampaignSettings {
+    fn default() -> Self {
+        CampaignSettings {
+            target_share: 0.6,
+            price_check_minutes: 30,
+            default_hours: 24,
+            max_hours: 168,
+            recap: true,
+        }
+    }
+}
+
+// Input token budget per model call; the character preamble is trimmed to fit
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PromptBudgetSettings {
+    pub max_input_tokens: usize,
+}
+
+impl Default for PromptBudgetSettings {
+    fn default() -> Self {
+        PromptBudgetSettings {
+            max_input_tokens: 8_000,
+        }
+    }
+}
+
+// Mints of majors with centralized exchange listings; FUDding them just looks lame.
+// By mint, since anyone can launch a copycat under the same symbol.
+pub const CEX_LISTED_MINTS: &[&str] = &[
+    "So11111111111111111111111111111111111111112",  // SOL
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", // USDT
+    "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",  // JUP
+    "jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL",  // JTO
+    "HZ1JovNiVvGrGNiiYvEozEVgZ58xaU3RKwX8eACQBCt3", // PYTH
+    "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R", // RAY
+    "orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kektZE",  // ORCA
+    "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263", // BONK
+    "EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm", // WIF
+    "rndrizKT3MK1iimdxRdWabcF7Zg7AR5T4nud4EkHBof",  // RENDER
+    "hntyVP6YFm1Hg25TN9WGLqM12b8TQmcknKrdu1oxWux",  // HNT
+    "85VBFQZC9TZkfaptBWjvUw7YbZjy52A6mjtPGjstQAmQ", // W
+    "TNSRxcUxoT9xBG3de7PiJyTDYu7kskLqcpddxnEJAS6",  // TNSR
+    "ukHH6c7mMyiWCf1b9pnWe25TSpkDDt3H5pQZgZ74J82",  // BOME
+    "7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hr", // POPCAT
+    "MEW1gQWJ3nEXg2qgERiKu7FAFj79PHvQVREQUzScPP5",  // MEW
+    "6p6xgHyF7AeE6TZkSmFsko444wqoP15icUSqi2jfGiPN", // TRUMP
+    "2zMMhcVQEXDtdE6vsFS7S7D5oUodfJHE8vd1gnBouauv", // PENGU
+];
+
+// CoinGecko ids of the exchanges whose listing puts a token out of the bot's league
+const MAJOR_EXCHANGES: &[&str] = &[
+    "binance", "gdax", "kraken", "okex", "bybit_spot", "kucoin", "bitget", "gate", "upbit", "htx", "crypto_com",
+];
+
+// Filters a trending token must pass to be picked for scheduled FUD; unset bounds don't filter
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TargetSelectionSettings {
+    pub min_market_cap: Option<f64>,
+    pub max_market_cap: Option<f64>,
+    pub min_liquidity: Option<f64>,
+    pub min_change_24h: Option<f64>,
+    pub max_change_24h: Option<f64>,
+    pub max_age_hours: Option<f64>,
+    pub exclude_cex_listed: bool,
+    // Extra mint addresses to treat as CEX listed
+    pub cex_listed: Vec<String>,
+    // Also ask CoinGecko where the picked token trades, for listings the lists above miss
+    pub check_listings: bool,
+    // CoinGecko exchange ids that count as a major listing
+    pub major_exchanges: Vec<String>,
+}
+
+impl Default for TargetSelectionSettings {
+    fn default() -> Self {
+        TargetSelectionSettings {
+            min_market_cap: None,
+            max_market_cap: None,
+            min_liquidity: None,
+            min_change_24h: None,
+            max_change_24h: None,
+            max_age_hours: None,
+            exclude_cex_listed: true,
+            cex_listed: Vec::new(),
+            check_listings: true,
+            major_exchanges: MAJOR_EXCHANGES.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+}
+
+impl TargetSelectionSettings {
+    pub fn matches(&self, token: &TokenSnapshot) -> bool {
+        let within = |value: f64, min: Option<f64>, max: Option<f64>| {
+            min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+        };
+        // Tokens without 24h change data only pass when momentum isn't filtered on
+        let momentum_ok = match token.change_24h {
+            Some(change) => within(change, self.min_change_24h, self.max_change_24h),
+            None => self.min_change_24h.is_none() && self.max_change_24h.is_none(),
+        };
+        // Same for tokens whose pool creation time we don't know
+        let age_ok = match token.age_hours {
+            Some(age) => self.max_age_hours.is_none_or(|max| age <= max),
+            None => self.max_age_hours.is_none(),
+        };
+        within(token.market_cap, self.min_market_cap, self.max_market_cap)
+            && self.min_liquidity.is_none_or(|min| token.liquidity_usd >= min)
+            && momentum_ok
+            && age_ok
+            && !(self.exclude_cex_listed && self.is_cex_listed(token))
+    }
+
+    pub fn is_cex_listed(&self, token: &TokenSnapshot) -> bool {
+        !token.mint.is_empty()
+            && (CEX_LISTED_MINTS.contains(&token.mint.as_str()) || self.cex_listed.iter().any(|listed| listed.trim() == token.mint))
+    }
+}
+
+// How much each signal makes a token a more tempting FUD target. Every eligible
+// token keeps `base_weight` so the pick never becomes fully predictable.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TargetScoringSettings {
+    pub enabled: bool,
+    pub base_weight: f64,
+    // Big 1h pumps make the FUD more ironic
+    pub gain_1h_weight: f64,
+    // Lopsided buys vs sells look like bots or a coordinated exit
+    pub trade_imbalance_weight: f64,
+    // Little liquidity behind a big market cap
+    pub thin_liquidity_weight: f64,
+}
+
+impl Default for TargetScoringSettings {
+    fn default() -> Self {
+        TargetScoringSettings {
+            enabled: true,
+            base_weight: 0.25,
+            gain_1h_weight: 1.0,
+            trade_imbalance_weight: 1.0,
+            thin_liquidity_weight: 1.0,
+        }
+    }
+}
+
+// Cross-check of the market cap against GeckoTerminal before FUD quotes it
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PriceCheckSettings {
+    pub enabled: bool,
+    // Sources disagreeing by more than this factor either way count as a mismatch
+    pub max_ratio: f64,
+    pub on_mismatch: MismatchAction,
+}
+
+impl Default for PriceCheckSettings {
+    fn default() -> Self {
+        PriceCheckSettings {
+            enabled: true,
+            max_ratio: 3.0,
+            on_mismatch: MismatchAction::SkipNumbers,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchAction {
+    // Leave the market cap out of the prompt
+    SkipNumbers,
+    // Hand the model both figures and have it mock the discrepancy
+    Mock,
+}
+
+// Metaplex metadata (description, website, socials) added to token summaries
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct TokenMetadataSettings {
+    pub enabled: bool,
+    // Also pull the title and meta description of the token's website
+    pub fetch_website: bool,
+}
+
+impl Default for TokenMetadataSettings {
+    fn default() -> Self {
+        TokenMetadataSettings {
+            enabled: true,
+            fetch_website: true,
+        }
+    }
+}
+
+// Bonding curve progress, dev buy and king of the hill status for pump.fun tokens
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PumpFunSettings {
+    pub enabled: bool,
+    // Pre-bond tokens get their own prompt instead of the usual FUD
+    pub prebond_prompt: bool,
+}
+
+impl Default for PumpFunSettings {
+    fn default() -> Self {
+        PumpFunSettings {
+            enabled: true,
+            prebond_prompt: true,
+        }
+    }
+}
+
+// Security flags and recent trade flow from Birdeye for FUD targets. Needs BIRDEYE_API_KEY.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct BirdeyeSettings {
+    pub enabled: bool,
+    // Latest swaps summarized into buys, sells and wallets; 0 skips the trades lookup
+    pub trades: usize,
+    // Deepest pools summed up by the DEX providing the liquidity; 0 skips the lookup
+    pub markets: usize,
+}
+
+impl Default for BirdeyeSettings {
+    fn default() -> Self {
+        BirdeyeSettings {
+            enabled: true,
+            trades: 50,
+            markets: 10,
+        }
+    }
+}
+
+// Bundled buys at launch, worked out from the token's first transactions over RPC
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct BundleCheckSettings {
+    pub enabled: bool,
+    // Blocks from the token's first transaction that count as the launch
+    pub launch_slots: u64,
+    // Launch transactions read at most; each one is an RPC call
+    pub max_transactions: usize,
+    // Pages of 1,000 signatures read looking for the launch; older tokens are skipped
+    pub max_signature_pages: usize,
+    // A buyer with at most this many transactions before its launch buy is a fresh wallet
+    pub fresh_wallet_max_txs: usize,
+    // Reported only with at least this many buyers holding this share of supply
+    pub min_wallets: usize,
+    pub min_supply_pct: f64,
+}
+
+impl Default for BundleCheckSettings {
+    fn default() -> Self {
+        BundleCheckSettings {
+            enabled: false,
+            launch_slots: 1,
+            max_transactions: 25,
+            max_signature_pages: 3,
+            fresh_wallet_max_txs: 3,
+            min_wallets: 3,
+            min_supply_pct: 5.0,
+        }
+    }
+}
+
+// Screenshot the FUDded token's website and roast its design instead of the usual FUD.
+// Needs SCREENSHOT_API_KEY and a website in the token's metadata.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct WebsiteRoastSettings {
+    // Chance per scheduled FUD post
+    pub chance: f64,
+}
+
+impl Default for WebsiteRoastSettings {
+    fn default() -> Self {
+        WebsiteRoastSettings { chance: 0.25 }
+    }
+}
+
+// Hard ceiling on a single dust buy, whatever the character asks for (0.01 SOL)
+pub const MAX_RECEIPT_BUY_LAMPORTS: u64 = 10_000_000;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptMode {
+    // A memo transaction naming the token; costs only the network fee
+    Memo,
+    // A dust buy of the token through Jupiter
+    Buy,
+}
+
+// On-chain "proof i looked at this" attached to scheduled FUD. Needs SOLANA_WALLET_KEY.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct OnChainReceiptSettings {
+    pub enabled: bool,
+    pub mode: ReceiptMode,
+    pub buy_lamports: u64,
+    pub slippage_bps: u16,
+    pub max_receipts_per_day: usize,
+    pub max_lamports_per_day: u64,
+}
+
+impl Default for OnChainReceiptSettings {
+    fn default() -> Self {
+        OnChainReceiptSettings {
+            enabled: false,
+            mode: ReceiptMode::Memo,
+            buy_lamports: 100_000,
+            slippage_bps: 500,
+            max_receipts_per_day: 3,
+            max_lamports_per_day: 5_000_000,
+        }
+    }
+}
+
+impl OnChainReceiptSettings {
+    // Worst-case cost of one receipt in the configured mode
+    pub fn cost_lamports(&self) -> u64 {
+        match self.mode {
+            ReceiptMode::Memo => SIGNATURE_FEE_LAMPORTS,
+            ReceiptMode::Buy => self.buy_lamports + BUY_OVERHEAD_LAMPORTS,
+        }
+    }
+
+    // Why another receipt can't be sent right now, if it can't
+    pub fn refusal(&self, receipts: &[OnChainReceipt], now: DateTime<Utc>) -> Option<String> {
+        if !self.enabled {
+            return Some("disabled".to_string());
+        }
+        if self.mode == ReceiptMode::Buy && (self.buy_lamports == 0 || self.buy_lamports > MAX_RECEIPT_BUY_LAMPORTS) {
+            return Some(format!("buy_lamports must be between 1 and {}", MAX_RECEIPT_BUY_LAMPORTS));
+        }
+
+        let since = now - chrono::Duration::hours(24);
+        let today: Vec<&OnChainReceipt> = receipts.iter().filter(|r| r.timestamp > since).collect();
+        if today.len() >= self.max_receipts_per_day {
+            return Some(format!("already sent {} receipts in the last 24h", today.len()));
+        }
+        let spent: u64 = today.iter().map(|r| r.lamports).sum();
+        if spent + self.cost_lamports() > self.max_lamports_per_day {
+            return Some(format!(
+                "spent {} of {} lamports in the last 24h",
+                spent, self.max_lamports_per_day
+            ));
+        }
+        None
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SpamFilterSettings {
+    pub enabled: bool,
+    // Mentions from accounts younger than this are dropped; 0 turns the check off
+    pub min_account_age_days: i64,
+    pub min_followers: u64,
+    // This many mentions with the same text within a day look like a bot campaign
+    pub max_identical_mentions: usize,
+    // Usernames (with or without @) or numeric user ids that never get a reply
+    pub muted: Vec<String>,
+    // Also never reply to accounts blocked or muted on Twitter, re-synced this often
+    pub sync_twitter_blocks: bool,
+    pub block_sync_hours: i64,
+}
+
+impl Default for SpamFilterSettings {
+    fn default() -> Self {
+        SpamFilterSettings {
+            enabled: true,
+            min_account_age_days: 7,
+            min_followers: 5,
+            max_identical_mentions: 3,
+            muted: Vec::new(),
+            sync_twitter_blocks: true,
+            block_sync_hours: 6,
+        }
+    }
+}
+
+impl SpamFilterSettings {
+    pub fn is_muted(&self, user_id: Option<u64>, username: Option<&str>) -> bool {
+        self.muted.iter().any(|entry| {
+            let entry = entry.trim().trim_start_matches('@');
+            user_id.is_some_and(|id| entry == id.to_string())
+                || username.is_some_and(|name| entry.eq_ignore_ascii_case(name))
+        })
+    }
+
+    // Account age, followers and muted usernames all need a user lookup
+    pub fn needs_author_lookup(&self) -> bool {
+        self.enabled && (self.min_account_age_days > 0 || self.min_followers > 0 || !self.muted.is_empty())
+    }
+}
+
+// Loop protection for accounts that answer every reply the bot makes
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReplyLimitSettings {
+    // Replies the bot makes in one conversation before it stops; 0 means no limit
+    pub max_thread_depth: u32,
+    // How long the bot ignores someone who pushed a thread to the limit
+    pub user_cooldown_hours: i64,
+}
+
+impl Default for ReplyLimitSettings {
+    fn default() -> Self {
+        ReplyLimitSettings {
+            max_thread_depth: 3,
+            user_cooldown_hours: 12,
+        }
+    }
+}
+
+// Who the bot replies to at all, on top of the spam filter. Everything is off by default.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReplyPolicySettings {
+    // Only reply to accounts that follow the bot
+    pub followers_only: bool,
+    pub verified_only: bool,
+    // 0 turns each check off
+    pub min_followers: u64,
+    pub min_account_age_days: i64,
+    // Usernames (with or without @) or numeric user ids the policy never applies to
+    pub exempt: Vec<String>,
+    // How often the bot's follower list is re-fetched for followers_only
+    pub follower_sync_hours: i64,
+}
+
+impl Default for ReplyPolicySettings {
+    fn default() -> Self {
+        ReplyPolicySettings {
+            followers_only: false,
+            verified_only: false,
+            min_followers: 0,
+            min_account_age_days: 0,
+            exempt: Vec::new(),
+            follower_sync_hours: 6,
+        }
+    }
+}
+
+impl ReplyPolicySettings {
+    pub fn is_enabled(&self) -> bool {
+        self.followers_only || self.verified_only || self.min_followers > 0 || self.min_account_age_days > 0
+    }
+
+    // Everything but followers_only works from the author lookup, as do exempt usernames
+    pub fn needs_author_lookup(&self) -> bool {
+        self.verified_only || self.min_followers > 0 || self.min_account_age_days > 0 || (self.is_enabled() && !self.exempt.is_empty())
+    }
+
+    pub fn is_exempt(&self, user_id: Option<u64>, username: Option<&str>) -> bool {
+        self.exempt.iter().any(|entry| {
+            let entry = entry.trim().trim_start_matches('@');
+            user_id.is_some_and(|id| entry == id.to_string())
+                || username.is_some_and(|name| entry.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+// Relative weights of each content type between two hours (end exclusive) in the
+// character's timezone
+#[derive(Deserialize, Clone, Debug)]
+pub struct ContentSlot {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    #[serde(default)]
+    pub fud: u32,
+    #[serde(default)]
+    pub trending_summary: u32,
+    #[serde(default)]
+    pub shitpost: u32,
+    // Weights for other registered content types, e.g. image_meme
+    #[serde(flatten)]
+    pub other: HashMap<String, u32>,
+}
+
+impl ContentSlot {
+    pub fn weight(&self, name: &str) -> u32 {
+        match name {
+            "fud" => self.fud,
+            "trending_summary" => self.trending_summary,
+            "shitpost" => self.shitpost,
+            _ => self.other.get(name).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn total_weight(&self) -> u32 {
+        self.fud + self.trending_summary + self.shitpost + self.other.values().sum::<u32>()
+    }
+}
+
+// How much of its own history about a token the bot is shown before posting about it again
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PastTakesSettings {
+    pub enabled: bool,
+    pub max_takes: usize,
+    pub lookback_days: i64,
+}
+
+impl Default for PastTakesSettings {
+    fn default() -> Self {
+        PastTakesSettings {
+            enabled: true,
+            max_takes: 3,
+            lookback_days: 14,
+        }
+    }
+}
+
+// Claims about a token that people make in mentions ("dev doxxed yesterday", "LP
+// unlocked"), pulled out by the model and shown to later FUD about that token
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct LearnedFactsSettings {
+    pub enabled: bool,
+    // Mentions shorter than this once tickers, handles and links are stripped aren't sent for extraction
+    pub min_mention_chars: usize,
+    pub max_per_mention: usize,
+    // Oldest facts about a token are dropped past this
+    pub max_per_token: usize,
+    pub lookback_days: i64,
+    pub max_in_prompt: usize,
+    // Different accounts that must have made a claim before it's used, so one account
+    // can't feed the character a story about a token
+    pub min_authors: usize,
+}
+
+impl Default for LearnedFactsSettings {
+    fn default() -> Self {
+        LearnedFactsSettings {
+            enabled: false,
+            min_mention_chars: 20,
+            max_per_mention: 3,
+            max_per_token: 20,
+            lookback_days: 7,
+            max_in_prompt: 5,
+            min_authors: 3,
+        }
+    }
+}
+
+// One well-received past post about a similar situation, found by BM25 over the
+// posts and the summaries they were written from, so FUD can call back to it
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct CallbackSettings {
+    pub enabled: bool,
+    // Likes + retweets + replies + quotes a post needs to be called back to
+    pub min_engagement: u64,
+    // Newer posts are too fresh for a callback to land
+    pub min_age_days: i64,
+    pub lookback_days: i64,
+    // Least BM25 score for a post to count as similar
+    pub min_score: f64,
+}
+
+impl Default for CallbackSettings {
+    fn default() -> Self {
+        CallbackSettings {
+            enabled: true,
+            min_engagement: 20,
+            min_age_days: 3,
+            lookback_days: 90,
+            min_score: 4.0,
+        }
+    }
+}
+
+// The bot's own best-received posts, shown back to it as examples in the live prompt
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct FewShotSettings {
+    pub enabled: bool,
+    pub examples: usize,
+    // Likes + retweets + replies + quotes a post needs to be used as an example
+    pub min_engagement: u64,
+}
+
+impl Default for FewShotSettings {
+    fn default() -> Self {
+        FewShotSettings {
+            enabled: false,
+            examples: 3,
+            min_engagement: 20,
+        }
+    }
+}
+
+// Curated posts from the character's examples/ directory, a few picked at random for
+// each generation call
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ExampleRotationSettings {
+    // Examples per call; 0 leaves examples/ unused
+    pub per_call: usize,
+}
+
+impl Default for ExampleRotationSettings {
+    fn default() -> Self {
+        ExampleRotationSettings { per_call: 3 }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ShillSettings {
+    pub enabled: bool,
+    // Chance that a scheduled post slot shills our own token instead of FUDding another
+    pub weight: f64,
+    // Task given to the model; {symbol} and {address} are filled from memory
+    pub prompt_template: String,
+}
+
+impl Default for ShillSettings {
+    fn default() -> Self {
+        ShillSettings {
+            enabled: false,
+            weight: 0.15,
+            prompt_template: "Write a post shilling your own token ${symbol} (CA: {address}). \
+                Stay in character: every other memecoin is a rug, this is the only one you'd hold."
+                .to_string(),
+        }
+    }
+}
+
+impl ShillSettings {
+    pub const PLACEHOLDERS: [&'static str; 2] = ["{symbol}", "{address}"];
+
+    pub fn render(&self, symbol: &str, address: &str) -> String {
+        self.prompt_template
+            .replace("{symbol}", symbol)
+            .replace("{address}", address)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct LanguageSettings {
+    pub primary: String,
+    pub rotation: Vec<String>,
+    pub rotation_chance: f64,
+    pub replies: ReplyLanguageSettings,
+}
+
+impl Default for LanguageSettings {
+    fn default() -> Self {
+        LanguageSettings {
+            primary: "English".to_string(),
+            rotation: Vec::new(),
+            rotation_chance: 0.0,
+            replies: ReplyLanguageSettings::default(),
+        }
+    }
+}
+
+// Answering mentions in the language they were written in
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ReplyLanguageSettings {
+    pub match_mention: bool,
+    // Languages we'll answer in, by name or two-letter code
+    pub allowed: Vec<String>,
+    // For anything else, or mentions too short to tell
+    pub fallback: String,
+}
+
+impl Default for ReplyLanguageSettings {
+    fn default() -> Self {
+        ReplyLanguageSettings {
+            match_mention: false,
+            allowed: ["English", "Spanish", "Portuguese", "French", "German", "Turkish", "Chinese", "Japanese", "Korean"]
+                .iter()
+                .map(|language| language.to_string())
+                .collect(),
+            fallback: "English".to_string(),
+        }
+    }
+}
+
+impl ReplyLanguageSettings {
+    // Language a reply to this mention should be in, or None to leave it to the usual rotation
+    pub fn for_mention(&self, text: &str) -> Option<String> {
+        if !self.match_mention {
+            return None;
+        }
+        let language = LanguageDetector::detect(text)
+            .filter(|detected| self.allowed.iter().any(|allowed| LanguageDetector::is_language(allowed, detected)))
+            .map(str::to_string)
+            .unwrap_or_else(|| self.fallback.clone());
+        Some(language)
+    }
+}
+
+impl LanguageSettings {
+    // Pick the language for a single generation, occasionally rotating away from the primary
+    pub fn pick(&self) -> String {
+        let mut rng = rand::thread_rng();
+        if !self.rotation.is_empty() && rng.gen_bool(self.rotation_chance.clamp(0.0, 1.0)) {
+            if let Some(language) = self.rotation.choose(&mut rng) {
+                return language.clone();
+            }
+        }
+        self.primary.clone()
+    }
+
+    pub fn is_english(language: &str) -> bool {
+        matches!(language.trim().to_lowercase().as_str(), "english" | "en")
+    }
+
+    // Twitter counts CJK characters double, so these languages get half the character budget
+    pub fn is_cjk(language: &str) -> bool {
+        matches!(
+            language.trim().to_lowercase().as_str(),
+            "chinese" | "mandarin" | "cantonese" | "japanese" | "korean" | "zh" | "ja" | "ko"
+        )
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MarketCapBand {
+    // Upper bound of the band; the last band usually leaves this unset
+    #[serde(default)]
+    pub max_market_cap: Option<f64>,
+    pub min_sell_usd: f64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct WhaleAlertSettings {
+    pub enabled: bool,
+    pub check_interval_minutes: i64,
+    pub watch_hours: i64,
+    pub max_alerts_per_token: usize,
+    pub bands: Vec<MarketCapBand>,
+}
+
+impl Default for WhaleAlertSettings {
+    fn default() -> Self {
+        WhaleAlertSettings {
+            enabled: false,
+            check_interval_minutes: 5,
+            watch_hours: 24,
+            max_alerts_per_token: 1,
+            bands: vec![
+                MarketCapBand { max_market_cap: Some(1_000_000.0), min_sell_usd: 5_000.0 },
+                MarketCapBand { max_market_cap: Some(10_000_000.0), min_sell_usd: 25_000.0 },
+                MarketCapBand { max_market_cap: None, min_sell_usd: 100_000.0 },
+            ],
+        }
+    }
+}
+
+impl WhaleAlertSettings {
+    // Minimum sell size worth gloating about for a token of this market cap
+    pub fn threshold_for(&self, market_cap: f64) -> Option<f64> {
+        self.bands
+            .iter()
+            .find(|band| band.max_market_cap.is_none_or(|max| market_cap <= max))
+            .map(|band| band.min_sell_usd)
+    }
+}
@@ -0,0 +1,232 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::character_switch::CharacterSwitch;
+use super::instruction_builder::{ExampleRotation, InstructionBuilder, EXAMPLES_DIR};
+use super::paths::Layout;
+use super::validator::{CharacterValidator, ValidationReport};
+use crate::error::{FudError, Result};
+
+pub const BUNDLE_FORMAT: &str = "chainfud-character";
+// Bumped whenever a bundle's layout changes; older bundles are upgraded on import
+pub const BUNDLE_SCHEMA_VERSION: u64 = 2;
+pub const BUNDLE_EXTENSION: &str = "character.json";
+// Where an imported bundle's examples/ posts are written
+pub const IMPORTED_EXAMPLES_FILE: &str = "imported.txt";
+
+// character.json keys and the bundle section they go in. Anything else (banned
+// words, schedules, templates and the rest of the settings) goes in `settings`.
+const PROMPT_KEYS: [&str; 3] = ["character", "alias", "instructions"];
+const TRAIT_KEYS: [&str; 5] = ["adjectives", "bio", "lore", "styles", "topics"];
+const EXAMPLES_KEY: &str = "post_style_examples";
+// Settings that belong to whoever runs the bot rather than to the character: what
+// it spends, where its files go and its API plan. Left out of exports and dropped
+// from imports, so a shared bundle can't turn on spending or move storage. "a.b"
+// is field b of section a.
+pub const OPERATOR_SETTINGS: [&str; 6] =
+    ["onchain_receipts", "storage", "api_quota", "outbox", "latency_budget", "token_heat.store_path"];
+
+// A character packed into one file to hand to another operator: character.json split
+// into sections, with a format name and schema version so a bundle from another
+// version of the bot is read correctly or refused
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CharacterBundle {
+    pub format: String,
+    pub schema_version: u64,
+    // The directory name under characters/ it was exported from
+    pub name: String,
+    #[serde(default)]
+    pub exported_at: Option<DateTime<Utc>>,
+    // Name, alias and the base and suffix instructions
+    pub prompt: Map<String, Value>,
+    // Adjectives, bio, lore, styles and topics
+    #[serde(default)]
+    pub traits: Map<String, Value>,
+    #[serde(default)]
+    pub post_examples: Vec<Value>,
+    #[serde(default)]
+    pub settings: Map<String, Value>,
+    // The posts in the character's examples/ directory
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+impl CharacterBundle {
+    pub fn from_character(name: &str, character: Value, now: DateTime<Utc>) -> Result<Self> {
+        let Value::Object(fields) = character else {
+            return Err(FudError::parse("character", "character.json is not an object"));
+        };
+        let mut bundle = CharacterBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            schema_version: BUNDLE_SCHEMA_VERSION,
+            name: name.to_string(),
+            exported_at: Some(now),
+            prompt: Map::new(),
+            traits: Map::new(),
+            post_examples: Vec::new(),
+            settings: Map::new(),
+            examples: Vec::new(),
+        };
+        for (key, value) in fields {
+            if PROMPT_KEYS.contains(&key.as_str()) {
+                bundle.prompt.insert(key, value);
+            } else if TRAIT_KEYS.contains(&key.as_str()) {
+                bundle.traits.insert(key, value);
+            } else if key == EXAMPLES_KEY {
+                bundle.post_examples = match value {
+                    Value::Array(examples) => examples,
+                    other => vec![other],
+                };
+            } else {
+                bundle.settings.insert(key, value);
+            }
+        }
+        bundle.strip_operator_settings();
+        Ok(bundle)
+    }
+
+    // The OPERATOR_SETTINGS the bundle carries
+    pub fn operator_settings(&self) -> Vec<&'static str> {
+        OPERATOR_SETTINGS
+            .into_iter()
+            .filter(|setting| match setting.split_once('.') {
+                Some((section, field)) => self.settings.get(section).and_then(|section| section.get(field)).is_some(),
+                None => self.settings.contains_key(*setting),
+            })
+            .collect()
+    }
+
+    // Removes the OPERATOR_SETTINGS, returning the ones that were there
+    pub fn strip_operator_settings(&mut self) -> Vec<&'static str> {
+        let found = self.operator_settings();
+        for setting in &found {
+            match setting.split_once('.') {
+                Some((section, field)) => {
+                    if let Some(Value::Object(section)) = self.settings.get_mut(section) {
+                        section.remove(field);
+                    }
+                }
+                None => {
+                    self.settings.remove(*setting);
+                }
+            }
+        }
+        found
+    }
+
+    // characters/<name>/character.json under `layout`
+    pub fn export(layout: &Layout, name: &str, now: DateTime<Utc>) -> Result<Self> {
+        let path = layout.character_file(name);
+        let data = fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => FudError::NotFound(format!("character '{}'", name)),
+            _ => FudError::Io(e),
+        })?;
+        let character: Value = serde_json::from_str(&data).map_err(|e| FudError::parse(format!("character '{}'", name), e))?;
+        let mut bundle = Self::from_character(name, character, now)?;
+        let examples_dir = path.parent().expect("character files live in a directory").join(EXAMPLES_DIR);
+        bundle.examples = ExampleRotation::load(&examples_dir, 0)?.posts().to_vec();
+        Ok(bundle)
+    }
+
+    // A bundle file, upgraded to the current schema. One from a newer version of the
+    // bot is refused rather than imported with sections it doesn't know about dropped.
+    pub fn parse(data: &str) -> Result<Self> {
+        let mut bundle: Value = serde_json::from_str(data).map_err(|e| FudError::parse("character bundle", e))?;
+        if bundle.get("format").and_then(Value::as_str) != Some(BUNDLE_FORMAT) {
+            return Err(FudError::parse("character bundle", format!("not a {} bundle", BUNDLE_FORMAT)));
+        }
+        let version = bundle.get("schema_version").and_then(Value::as_u64).unwrap_or(0);
+        if version == 0 || version > BUNDLE_SCHEMA_VERSION {
+            return Err(FudError::parse(
+                "character bundle",
+                format!("schema version {} isn't supported (this build reads up to {})", version, BUNDLE_SCHEMA_VERSION),
+            ));
+        }
+        // Each change to the layout bumps the version and adds the step up from the one
+        // before here, so older bundles still import
+        if version < 2 {
+            // Version 2 added the examples/ posts
+            bundle["examples"] = Value::Array(Vec::new());
+        }
+        bundle["schema_version"] = Value::from(BUNDLE_SCHEMA_VERSION);
+        serde_json::from_value(bundle).map_err(|e| FudError::parse("character bundle", e))
+    }
+
+    // The character.json the bundle was made from
+    pub fn to_character(&self) -> Value {
+        let mut fields = Map::new();
+        fields.extend(self.prompt.clone());
+        fields.extend(self.traits.clone());
+        fields.insert(EXAMPLES_KEY.to_string(), Value::Array(self.post_examples.clone()));
+        fields.extend(self.settings.clone());
+        Value::Object(fields)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| FudError::parse("character bundle", e))
+    }
+
+    // Unpacks the bundle into characters/<name>/ (the bundle's own name unless `name`
+    // is given) if it loads and passes validation; when the report has errors nothing
+    // is written. Its example posts go in examples/imported.txt, and OPERATOR_SETTINGS
+    // are left out. An existing character is only replaced with `overwrite`, and then
+    // its own examples/ posts go too, so it's left with the bundle's alone.
+    pub fn import(&self, layout: &Layout, name: Option<&str>, overwrite: bool) -> Result<(PathBuf, ValidationReport)> {
+        let name = name.unwrap_or(&self.name);
+        if !CharacterSwitch::is_valid_name(name) {
+            return Err(FudError::parse("character bundle", format!("'{}' is not a valid character name", name)));
+        }
+        let path = layout.character_file(name);
+        if path.exists() && !overwrite {
+            return Err(FudError::Other(anyhow::anyhow!("character '{}' already exists", name)));
+        }
+        let dir = path.parent().expect("character files live in a directory");
+        fs::create_dir_all(dir)?;
+        let staged = dir.join(format!("{}.import", BUNDLE_EXTENSION));
+        let mut character = self.clone();
+        character.strip_operator_settings();
+        let json = serde_json::to_string_pretty(&character.to_character()).map_err(|e| FudError::parse("character", e))?;
+        fs::write(&staged, json)?;
+        let report = Self::validate(&staged);
+        if report.as_ref().is_ok_and(ValidationReport::is_ok) {
+            fs::rename(&staged, &path)?;
+            let examples_dir = dir.join(EXAMPLES_DIR);
+            Self::remove_examples(&examples_dir)?;
+            if !self.examples.is_empty() {
+                fs::create_dir_all(&examples_dir)?;
+                fs::write(examples_dir.join(IMPORTED_EXAMPLES_FILE), self.examples.join("\n\n") + "\n")?;
+            }
+        } else {
+            let _ = fs::remove_file(&staged);
+            // A directory made just for this import isn't left behind empty
+            let _ = fs::remove_dir(dir);
+        }
+        Ok((path, report?))
+    }
+
+    // The .txt files ExampleRotation reads; anything else in the directory is left
+    fn remove_examples(dir: &Path) -> Result<()> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension == "txt") {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(path: &Path) -> Result<ValidationReport> {
+        let character = InstructionBuilder::load_character_file(path).map_err(|e| FudError::parse("character", e))?;
+        let mut instruction_builder = InstructionBuilder::new();
+        instruction_builder.build_instructions_from_file(path)?;
+        Ok(CharacterValidator::validate(&character, instruction_builder.get_instructions()))
+    }
+}
@@ -0,0 +1,62 @@
+use std::fs;
+use std::io;
+
+use super::paths;
+
+// Left by `character use <name>` for the running bot to pick up
+pub const CHARACTER_REQUEST_PATH: &str = "./storage/character_request";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CharacterCommand {
+    Use(String),
+    Current,
+}
+
+// Switching the active character without a restart, from the CLI or Telegram
+pub struct CharacterSwitch;
+
+impl CharacterSwitch {
+    // "character use <name>" or "character current", with or without a leading slash
+    // (and the @botname Telegram appends in groups)
+    pub fn parse_command(text: &str) -> Option<CharacterCommand> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.trim_start_matches('/');
+        let command = command.split('@').next().unwrap_or(command);
+        if !command.eq_ignore_ascii_case("character") {
+            return None;
+        }
+        match (words.next(), words.next(), words.next()) {
+            (Some(action), Some(name), None) if action.eq_ignore_ascii_case("use") => Some(CharacterCommand::Use(name.to_string())),
+            (Some(action), None, None) if action.eq_ignore_ascii_case("current") => Some(CharacterCommand::Current),
+            (None, _, _) => Some(CharacterCommand::Current),
+            _ => None,
+        }
+    }
+
+    // Characters are loaded from characters/<name>/, so only plain directory names
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 64
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    pub fn write_request(name: &str) -> io::Result<()> {
+        let path = paths::resolve(CHARACTER_REQUEST_PATH);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, name)
+    }
+
+    // The pending request, removed so it's only applied once
+    pub fn take_request() -> io::Result<Option<String>> {
+        let path = paths::resolve(CHARACTER_REQUEST_PATH);
+        let name = match fs::read_to_string(&path) {
+            Ok(name) => name,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        fs::remove_file(&path)?;
+        Ok(Some(name.trim().to_string()))
+    }
+}
@@ -1,5 +1,6 @@
 use std::io;
 use super::character::Character;
+use super::prompt_budget::PromptSection;
 use crate::characteristics::{
     bio::Bio,
     lore::Lore,
@@ -11,6 +12,8 @@ use crate::characteristics::{
 
 pub trait Characteristic {
     fn get_header(&self) -> String;
+    // Lower priorities are trimmed first when the prompt is over budget
+    fn get_priority(&self) -> u8;
     fn get_traits(&self, character: &Character) -> io::Result<String>;
 }
 
@@ -28,17 +31,16 @@ impl Characteristics {
         ]
     }
 
-    pub fn build_characteristics_instructions(character: &Character) -> String {
-        let mut chars_instruction = String::new();
-        let characteristics = Self::get_characteristics();
-
-        for characteristic in characteristics {
-            chars_instruction += &characteristic.get_header();
-            chars_instruction += "\n";
-            chars_instruction += &characteristic.get_traits(character).unwrap();
-            chars_instruction += "\n";
-        }
-
-        chars_instruction
+    pub fn build_characteristics_sections(character: &Character) -> Vec<PromptSection> {
+        Self::get_characteristics()
+            .iter()
+            .map(|characteristic| {
+                PromptSection::trimmable(
+                    &characteristic.get_header(),
+                    &characteristic.get_traits(character).unwrap(),
+                    characteristic.get_priority(),
+                )
+            })
+            .collect()
     }
 }
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use std::sync::Mutex;
+
+use super::character::{CharacterSettings, QuietHoursSettings};
+use super::local_time::LocalTime;
+
+// Minutes past the hour when the scheduled FUD post goes out
+pub const FUD_SCHEDULE_MINUTES: &[u32] = &[0, 15, 30, 45];
+
+// Where the runtime gets the time from, so scheduling can be driven by hand in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+// A clock that only moves when it's told to
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        MockClock { now: Mutex::new(now) }
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// The time checks behind the main loop's schedule
+pub struct Schedule;
+
+impl Schedule {
+    // The first of `minutes` (past the hour) to start after `after`
+    pub fn next_minute_mark(minutes: &[u32], after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)?;
+        (1..=60)
+            .map(|i| start + Duration::minutes(i))
+            .find(|mark| minutes.contains(&mark.minute()))
+    }
+
+    // Whether one of `minutes` started in (since, now]. A mark that passed while the
+    // previous cycle was still running counts too, so a slow cycle never skips one.
+    pub fn mark_between(minutes: &[u32], since: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        Self::next_minute_mark(minutes, since).is_some_and(|mark| mark <= now)
+    }
+
+    // When the loop should wake next: the earliest upcoming event, but never idle
+    // longer than `max_idle`. Events at or before `after` were already handled or
+    // can't run yet, so they don't hold the loop awake.
+    pub fn next_wake<I>(after: DateTime<Utc>, events: I, max_idle: Duration) -> DateTime<Utc>
+    where
+        I: IntoIterator<Item = Option<DateTime<Utc>>>,
+    {
+        events
+            .into_iter()
+            .flatten()
+            .filter(|at| *at > after)
+            .fold(after + max_idle, |wake, at| wake.min(at))
+    }
+
+    // Whether `interval` has passed since `last`; true when it never happened
+    pub fn elapsed(last: Option<DateTime<Utc>>, interval: Duration, now: DateTime<Utc>) -> bool {
+        last.is_none_or(|last| now.signed_duration_since(last) >= interval)
+    }
+
+    // Inside the quiet window; it wraps past midnight when start_hour is after end_hour.
+    // The hours are read in whatever timezone `now` is in.
+    pub fn is_quiet<T: Timelike>(settings: &QuietHoursSettings, now: T) -> bool {
+        if !settings.enabled || settings.start_hour == settings.end_hour {
+            return false;
+        }
+        let hour = now.hour();
+        if settings.start_hour < settings.end_hour {
+            (settings.start_hour..settings.end_hour).contains(&hour)
+        } else {
+            hour >= settings.start_hour || hour < settings.end_hour
+        }
+    }
+
+    // The first of `minutes` after `after` that falls outside the quiet hours in `local`;
+    // None if there isn't one within a day
+    pub fn next_outside_quiet(
+        minutes: &[u32],
+        quiet: &QuietHoursSettings,
+        local: LocalTime,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut mark = Self::next_minute_mark(minutes, after)?;
+        while Self::is_quiet(quiet, local.at(mark)) {
+            if mark > after + Duration::days(1) {
+                return None;
+            }
+            mark = Self::next_minute_mark(minutes, mark)?;
+        }
+        Some(mark)
+    }
+    // When the next scheduled post goes out: the first schedule mark after any adaptive
+    // hold (`next_tweet`) that isn't in the quiet hours
+    pub fn next_scheduled_post(settings: &CharacterSettings, next_tweet: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let held_until = next_tweet.filter(|next| settings.adaptive_schedule.enabled && now < *next);
+        Self::next_outside_quiet(
+            FUD_SCHEDULE_MINUTES,
+            &settings.quiet_hours,
+            LocalTime::from_settings(settings),
+            held_until.unwrap_or(now),
+        )
+    }
+}
@@ -0,0 +1,544 @@
+use std::{env, fmt, net::SocketAddr};
+use super::audit::DEFAULT_MAX_FILE_MB;
+use super::telemetry::{parse_headers, DEFAULT_SERVICE_NAME};
+use crate::providers::screenshot::DEFAULT_SCREENSHOT_API_URL;
+use crate::providers::solana_rpc::DEFAULT_RPC_URL;
+use crate::providers::solanatracker::DEFAULT_TRENDING_TTL_SECONDS;
+use crate::providers::wallet::Keypair;
+
+// Wraps a credential so it can't end up in logs through {:?}
+#[derive(Clone, PartialEq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    Missing(&'static str),
+    Invalid { name: &'static str, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Missing(name) => write!(f, "{} is not set", name),
+            ConfigError::Invalid { name, reason } => write!(f, "{} is invalid: {}", name, reason),
+        }
+    }
+}
+
+// Every problem found in the environment, so they can all be fixed in one go
+#[derive(Debug)]
+pub struct ConfigErrors(pub Vec<ConfigError>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid configuration ({} problem(s)):", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+#[derive(Debug, Clone)]
+pub struct TwitterCredentials {
+    pub consumer_key: Secret,
+    pub consumer_secret: Secret,
+    pub access_token: Secret,
+    pub access_token_secret: Secret,
+}
+
+#[derive(Debug, Clone)]
+pub struct TelegramConfig {
+    pub bot_token: Secret,
+    pub channel: Option<String>,
+    // Telegram user ids allowed to send the bot commands; none means commands are off
+    pub admin_ids: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HeuristConfig {
+    pub api_key: Secret,
+    pub image_prompt: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScreenshotConfig {
+    pub api_key: Secret,
+    pub api_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    pub endpoint: String,
+    // Raw OTEL_EXPORTER_OTLP_HEADERS; usually an API key for the collector
+    pub headers: Option<Secret>,
+    pub service_name: String,
+}
+
+// Shared state for running several instances; see memory_backend
+#[derive(Debug, Clone)]
+pub struct PostgresConfig {
+    pub url: Secret,
+}
+
+// Twitter call counts shared between instances on the same API keys; see rate_limiter
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    pub url: Secret,
+    // Instances with the same prefix count against the same quota
+    pub key_prefix: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    // A day's audit file moves on to a new part past this size
+    pub max_file_mb: u64,
+}
+
+// Proxy and user agent for one provider's HTTP calls; both unset means a plain client
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientConfig {
+    // http://, https://, socks5:// or socks5h:// URL, possibly with credentials in it
+    pub proxy: Option<Secret>,
+    pub user_agent: Option<String>,
+}
+
+const DEFAULT_RATE_LIMIT_PREFIX: &str = "chainfud:quota";
+
+const PROXY_SCHEMES: [&str; 4] = ["http://", "https://", "socks5://", "socks5h://"];
+
+impl HttpClientConfig {
+    pub fn is_set(&self) -> bool {
+        self.proxy.is_some() || self.user_agent.is_some()
+    }
+
+    // A provider with its own settings ignores HTTPS_PROXY and the like, which are
+    // only there for the Twitter library's client and every client without settings
+    pub fn client(&self) -> reqwest::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if self.is_set() {
+            builder = builder.no_proxy();
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.expose())?);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        builder.build()
+    }
+
+    // "socks5://host:1080", without any credentials in the URL
+    pub fn proxy_host(&self) -> Option<String> {
+        let proxy = self.proxy.as_ref()?.expose();
+        let (scheme, rest) = proxy.split_once("://")?;
+        let host = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+        Some(format!("{}://{}", scheme, host.trim_end_matches('/')))
+    }
+}
+
+// Everything read from the environment. Optional integrations are None when
+// their variables aren't set and are simply switched off.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub anthropic_api_key: Secret,
+    pub twitter: TwitterCredentials,
+    pub solana_tracker_api_key: Secret,
+    // How long a trending list is reused before it's fetched again; 0 fetches every time
+    pub trending_cache_ttl_seconds: u64,
+    pub character_name: String,
+    pub debug_mode: bool,
+    pub telegram: Option<TelegramConfig>,
+    pub heurist: Option<HeuristConfig>,
+    pub screenshot: Option<ScreenshotConfig>,
+    pub discord_webhook_urls: Option<Secret>,
+    // Provider URLs often carry an API key in the path
+    pub solana_rpc_url: Secret,
+    // Hot wallet key for on-chain receipts; keep only dust in it
+    pub solana_wallet_key: Option<Secret>,
+    pub telemetry: Option<TelemetryConfig>,
+    // Headlines for the "news" content type; RSS feeds work without it
+    pub cryptopanic_api_key: Option<Secret>,
+    // Token security and trade data for FUD targets
+    pub birdeye_api_key: Option<Secret>,
+    // Exchange listings work without it, on a lower rate limit
+    pub coingecko_api_key: Option<Secret>,
+    pub audit: Option<AuditConfig>,
+    // Where /healthz and /readyz are served for uptime monitors
+    pub status_addr: Option<SocketAddr>,
+    // Bearer token for flipping feature flags over the status endpoint
+    pub status_admin_token: Option<Secret>,
+    pub twitter_http: HttpClientConfig,
+    pub solana_tracker_http: HttpClientConfig,
+    pub anthropic_http: HttpClientConfig,
+    // MEMORY_BACKEND=postgres; None keeps the shared stores in files under storage/
+    pub postgres: Option<PostgresConfig>,
+    pub rate_limit_redis: Option<RedisConfig>,
+}
+
+struct EnvReader<F: Fn(&str) -> Option<String>> {
+    lookup: F,
+    errors: Vec<ConfigError>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<F> {
+    // Blank values count as unset
+    fn optional(&self, name: &str) -> Option<String> {
+        (self.lookup)(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&mut self, name: &'static str) -> String {
+        self.optional(name).unwrap_or_else(|| {
+            self.errors.push(ConfigError::Missing(name));
+            String::new()
+        })
+    }
+
+    fn secret(&mut self, name: &'static str) -> Secret {
+        Secret(self.required(name))
+    }
+
+    fn flag(&mut self, name: &'static str) -> bool {
+        match self.optional(name) {
+            None => false,
+            Some(value) => value.to_lowercase().parse::<bool>().unwrap_or_else(|_| {
+                self.invalid(name, &format!("expected true or false, got '{}'", value));
+                false
+            }),
+        }
+    }
+
+    // <PREFIX>_PROXY and <PREFIX>_USER_AGENT, checked by building a client with them
+    fn http(&mut self, proxy_var: &'static str, user_agent_var: &'static str) -> HttpClientConfig {
+        let proxy = self.optional(proxy_var);
+        if proxy.as_deref().is_some_and(|url| !PROXY_SCHEMES.iter().any(|scheme| url.starts_with(scheme))) {
+            self.invalid(proxy_var, "expected an http://, https://, socks5:// or socks5h:// URL");
+            return HttpClientConfig::default();
+        }
+        let user_agent = self.optional(user_agent_var);
+        if user_agent.as_deref().is_some_and(|ua| reqwest::header::HeaderValue::from_str(ua).is_err()) {
+            self.invalid(user_agent_var, "contains characters a header can't carry");
+            return HttpClientConfig::default();
+        }
+        let config = HttpClientConfig { proxy: proxy.map(Secret), user_agent };
+        if let Err(e) = config.client() {
+            self.invalid(proxy_var, &e.to_string());
+            return HttpClientConfig::default();
+        }
+        config
+    }
+
+    fn invalid(&mut self, name: &'static str, reason: &str) {
+        self.errors.push(ConfigError::Invalid {
+            name,
+            reason: reason.to_string(),
+        });
+    }
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigErrors> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigErrors> {
+        let mut reader = EnvReader { lookup, errors: Vec::new() };
+
+        let anthropic_api_key = reader.secret("ANTHROPIC_API_KEY");
+        let twitter = TwitterCredentials {
+            consumer_key: reader.secret("TWITTER_CONSUMER_KEY"),
+            consumer_secret: reader.secret("TWITTER_CONSUMER_SECRET"),
+            access_token: reader.secret("TWITTER_ACCESS_TOKEN"),
+            access_token_secret: reader.secret("TWITTER_ACCESS_TOKEN_SECRET"),
+        };
+        let solana_tracker_api_key = reader.secret("SOLANA_TRACKER_API_KEY");
+        let character_name = reader.required("CHARACTER_NAME");
+
+        let trending_cache_ttl_seconds = match reader.optional("TRENDING_CACHE_TTL_SECONDS").map(|v| v.parse::<u64>()) {
+            None => DEFAULT_TRENDING_TTL_SECONDS,
+            Some(Ok(seconds)) => seconds,
+            Some(Err(_)) => {
+                reader.invalid("TRENDING_CACHE_TTL_SECONDS", "expected a whole number of seconds");
+                DEFAULT_TRENDING_TTL_SECONDS
+            }
+        };
+
+        let debug_mode = reader.flag("DEBUG_MODE");
+
+        let channel = reader.optional("TELEGRAM_CHANNEL_ID");
+        let admin_ids = reader.optional("TELEGRAM_ADMIN_IDS");
+        let parsed_admin_ids: Option<Vec<u64>> = admin_ids
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| id.parse().ok())
+            .collect();
+        if parsed_admin_ids.is_none() {
+            reader.invalid("TELEGRAM_ADMIN_IDS", "expected numeric Telegram user ids separated by commas");
+        }
+        let telegram = match reader.optional("TELEGRAM_BOT_TOKEN") {
+            Some(token) => Some(TelegramConfig {
+                bot_token: Secret(token),
+                channel,
+                admin_ids: parsed_admin_ids.unwrap_or_default(),
+            }),
+            None => {
+                if channel.is_some() {
+                    reader.invalid("TELEGRAM_CHANNEL_ID", "set without TELEGRAM_BOT_TOKEN");
+                }
+                if admin_ids.is_some() {
+                    reader.invalid("TELEGRAM_ADMIN_IDS", "set without TELEGRAM_BOT_TOKEN");
+                }
+                None
+            }
+        };
+
+        let heurist = match (reader.optional("HEURIS_API"), reader.optional("IMAGE_PROMPT")) {
+            (Some(api_key), Some(image_prompt)) => Some(HeuristConfig {
+                api_key: Secret(api_key),
+                image_prompt,
+            }),
+            (Some(_), None) => {
+                reader.invalid("HEURIS_API", "image generation also needs IMAGE_PROMPT");
+                None
+            }
+            (None, Some(_)) => {
+                reader.invalid("IMAGE_PROMPT", "image generation also needs HEURIS_API");
+                None
+            }
+            (None, None) => None,
+        };
+
+        let screenshot_url = reader.optional("SCREENSHOT_API_URL");
+        let screenshot = match reader.optional("SCREENSHOT_API_KEY") {
+            Some(api_key) => Some(ScreenshotConfig {
+                api_key: Secret(api_key),
+                api_url: screenshot_url.unwrap_or_else(|| DEFAULT_SCREENSHOT_API_URL.to_string()),
+            }),
+            None => {
+                if screenshot_url.is_some() {
+                    reader.invalid("SCREENSHOT_API_URL", "set without SCREENSHOT_API_KEY");
+                }
+                None
+            }
+        };
+
+        let discord_webhook_urls = reader.optional("DISCORD_WEBHOOK_URLS").map(Secret);
+        let solana_rpc_url = Secret(
+            reader
+                .optional("SOLANA_RPC_URL")
+                .unwrap_or_else(|| DEFAULT_RPC_URL.to_string()),
+        );
+
+        let solana_wallet_key = reader.optional("SOLANA_WALLET_KEY").map(Secret);
+        if let Some(key) = &solana_wallet_key {
+            if let Err(e) = Keypair::parse(key.expose()) {
+                reader.invalid("SOLANA_WALLET_KEY", &e.to_string());
+            }
+        }
+
+        let otlp_headers = reader.optional("OTEL_EXPORTER_OTLP_HEADERS").map(Secret);
+        if otlp_headers.as_ref().is_some_and(|h| parse_headers(h.expose()).is_none()) {
+            reader.invalid("OTEL_EXPORTER_OTLP_HEADERS", "expected key=value pairs separated by commas");
+        }
+        let telemetry = match reader.optional("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            Some(endpoint) if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") => {
+                reader.invalid("OTEL_EXPORTER_OTLP_ENDPOINT", "must be an http(s) URL");
+                None
+            }
+            Some(endpoint) => Some(TelemetryConfig {
+                endpoint,
+                headers: otlp_headers,
+                service_name: reader
+                    .optional("OTEL_SERVICE_NAME")
+                    .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string()),
+            }),
+            None => {
+                if otlp_headers.is_some() {
+                    reader.invalid("OTEL_EXPORTER_OTLP_HEADERS", "set without OTEL_EXPORTER_OTLP_ENDPOINT");
+                }
+                None
+            }
+        };
+
+        let cryptopanic_api_key = reader.optional("CRYPTOPANIC_API_KEY").map(Secret);
+        let birdeye_api_key = reader.optional("BIRDEYE_API_KEY").map(Secret);
+        let coingecko_api_key = reader.optional("COINGECKO_API_KEY").map(Secret);
+
+        let audit_max_mb = reader.optional("AUDIT_LOG_MAX_MB");
+        let parsed_audit_max_mb = match audit_max_mb.as_deref().map(str::parse::<u64>) {
+            None => DEFAULT_MAX_FILE_MB,
+            Some(Ok(mb)) if mb > 0 => mb,
+            Some(_) => {
+                reader.invalid("AUDIT_LOG_MAX_MB", "expected a whole number of megabytes above 0");
+                DEFAULT_MAX_FILE_MB
+            }
+        };
+        let audit = if reader.flag("AUDIT_LOG") {
+            Some(AuditConfig { max_file_mb: parsed_audit_max_mb })
+        } else {
+            if audit_max_mb.is_some() && reader.optional("AUDIT_LOG").is_none() {
+                reader.invalid("AUDIT_LOG_MAX_MB", "set without AUDIT_LOG");
+            }
+            None
+        };
+
+        let status_addr = match reader.optional("STATUS_ADDR").map(|v| v.parse::<SocketAddr>()) {
+            None => None,
+            Some(Ok(addr)) => Some(addr),
+            Some(Err(_)) => {
+                reader.invalid("STATUS_ADDR", "expected an address and port like 127.0.0.1:8080");
+                None
+            }
+        };
+        let status_admin_token = reader.optional("STATUS_ADMIN_TOKEN").map(Secret);
+        if status_admin_token.is_some() && reader.optional("STATUS_ADDR").is_none() {
+            reader.invalid("STATUS_ADMIN_TOKEN", "set without STATUS_ADDR");
+        }
+
+        let twitter_http = reader.http("TWITTER_PROXY", "TWITTER_USER_AGENT");
+        let solana_tracker_http = reader.http("SOLANATRACKER_PROXY", "SOLANATRACKER_USER_AGENT");
+        let anthropic_http = reader.http("ANTHROPIC_PROXY", "ANTHROPIC_USER_AGENT");
+
+        let database_url = reader.optional("DATABASE_URL");
+        let postgres = match reader.optional("MEMORY_BACKEND").map(|backend| backend.to_lowercase()).as_deref() {
+            None | Some("files") => None,
+            Some("postgres") => match database_url {
+                Some(url) if url.starts_with("postgres://") || url.starts_with("postgresql://") => {
+                    Some(PostgresConfig { url: Secret(url) })
+                }
+                Some(_) => {
+                    reader.invalid("DATABASE_URL", "expected a postgres:// or postgresql:// URL");
+                    None
+                }
+                None => {
+                    reader.invalid("MEMORY_BACKEND", "postgres needs DATABASE_URL");
+                    None
+                }
+            },
+            Some(_) => {
+                reader.invalid("MEMORY_BACKEND", "expected files or postgres");
+                None
+            }
+        };
+
+        let rate_limit_prefix = reader.optional("RATE_LIMIT_REDIS_PREFIX");
+        let rate_limit_redis = match reader.optional("RATE_LIMIT_REDIS_URL") {
+            Some(url) if url.starts_with("redis://") || url.starts_with("unix://") => Some(RedisConfig {
+                url: Secret(url),
+                key_prefix: rate_limit_prefix.unwrap_or_else(|| DEFAULT_RATE_LIMIT_PREFIX.to_string()),
+            }),
+            Some(_) => {
+                reader.invalid("RATE_LIMIT_REDIS_URL", "expected a redis:// or unix:// URL");
+                None
+            }
+            None => {
+                if rate_limit_prefix.is_some() {
+                    reader.invalid("RATE_LIMIT_REDIS_PREFIX", "set without RATE_LIMIT_REDIS_URL");
+                }
+                None
+            }
+        };
+
+        if !reader.errors.is_empty() {
+            return Err(ConfigErrors(reader.errors));
+        }
+
+        Ok(Config {
+            anthropic_api_key,
+            twitter,
+            solana_tracker_api_key,
+            trending_cache_ttl_seconds,
+            character_name,
+            debug_mode,
+            telegram,
+            heurist,
+            screenshot,
+            discord_webhook_urls,
+            solana_rpc_url,
+            solana_wallet_key,
+            telemetry,
+            cryptopanic_api_key,
+            birdeye_api_key,
+            coingecko_api_key,
+            audit,
+            status_addr,
+            status_admin_token,
+            twitter_http,
+            solana_tracker_http,
+            anthropic_http,
+            postgres,
+            rate_limit_redis,
+        })
+    }
+
+    pub fn print_features(&self) {
+        let enabled = |on: bool| if on { "enabled" } else { "disabled" };
+        println!("Telegram: {}", enabled(self.telegram.is_some()));
+        println!(
+            "Telegram channel mirroring: {}",
+            enabled(self.telegram.as_ref().is_some_and(|t| t.channel.is_some()))
+        );
+        println!(
+            "Telegram commands: {}",
+            enabled(self.telegram.as_ref().is_some_and(|t| !t.admin_ids.is_empty()))
+        );
+        println!("Discord webhooks: {}", enabled(self.discord_webhook_urls.is_some()));
+        println!("Heurist image generation: {}", enabled(self.heurist.is_some()));
+        println!("Website screenshots: {}", enabled(self.screenshot.is_some()));
+        println!("Solana wallet: {}", enabled(self.solana_wallet_key.is_some()));
+        println!("OTLP tracing: {}", enabled(self.telemetry.is_some()));
+        println!("CryptoPanic news: {}", enabled(self.cryptopanic_api_key.is_some()));
+        println!("Birdeye token security: {}", enabled(self.birdeye_api_key.is_some()));
+        println!("CoinGecko API key: {}", enabled(self.coingecko_api_key.is_some()));
+        println!("Audit log: {}", enabled(self.audit.is_some()));
+        match self.status_addr {
+            Some(addr) if self.status_admin_token.is_some() => println!("Status endpoints: {} (flag changes enabled)", addr),
+            Some(addr) => println!("Status endpoints: {}", addr),
+            None => println!("Status endpoints: disabled"),
+        }
+        for (provider, http) in [
+            ("Twitter", &self.twitter_http),
+            ("SolanaTracker", &self.solana_tracker_http),
+            ("Anthropic", &self.anthropic_http),
+        ] {
+            match (http.proxy_host(), &http.user_agent) {
+                (Some(proxy), Some(user_agent)) => println!("{} HTTP: via {}, user agent \"{}\"", provider, proxy, user_agent),
+                (Some(proxy), None) => println!("{} HTTP: via {}", provider, proxy),
+                (None, Some(user_agent)) => println!("{} HTTP: user agent \"{}\"", provider, user_agent),
+                (None, None) => {}
+            }
+        }
+        println!("Memory backend: {}", if self.postgres.is_some() { "postgres" } else { "files" });
+        match &self.rate_limit_redis {
+            Some(redis) => println!("API quota: shared through Redis ({})", redis.key_prefix),
+            None => println!("API quota: per instance"),
+        }
+        match self.trending_cache_ttl_seconds {
+            0 => println!("Trending cache: disabled"),
+            ttl => println!("Trending cache: {}s", ttl),
+        }
+    }
+}
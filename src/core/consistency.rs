@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::models::{AvoidedTopic, Memory, Tweet};
+
+// Something the model found wrong with the character's recent posts
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ConsistencyFinding {
+    // Numbers of the posts involved, as listed in the prompt
+    #[serde(default)]
+    pub posts: Vec<usize>,
+    pub issue: String,
+    // What to steer clear of for a while, e.g. a token's symbol
+    #[serde(default)]
+    pub topic: Option<String>,
+}
+
+// Periodic review of the character's own history, looking for posts that break
+// the persona or contradict each other
+pub struct ConsistencyCheck;
+
+impl ConsistencyCheck {
+    // The latest `size` posts, oldest first
+    pub fn sample(memory: &Memory, size: usize) -> Vec<&Tweet> {
+        let mut posts: Vec<&Tweet> = memory.tweets
+            .iter()
+            .rev()
+            .filter(|tweet| !tweet.text.trim().is_empty())
+            .take(size)
+            .collect();
+        posts.reverse();
+        posts
+    }
+
+    // One numbered line per post, with how long ago it went out
+    pub fn numbered(posts: &[&Tweet], now: DateTime<Utc>) -> String {
+        posts
+            .iter()
+            .enumerate()
+            .map(|(i, tweet)| {
+                let age = now.signed_duration_since(tweet.timestamp);
+                let when = if age.num_days() > 0 {
+                    format!("{} days ago", age.num_days())
+                } else {
+                    format!("{} hours ago", age.num_hours().max(0))
+                };
+                format!("{}. ({}) {}", i + 1, when, tweet.text.replace('\n', " "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // The model's JSON array of findings, ignoring any text around it
+    pub fn parse(response: &str) -> Option<Vec<ConsistencyFinding>> {
+        let start = response.find('[')?;
+        let end = response.rfind(']')?;
+        if end < start {
+            return None;
+        }
+        let findings: Vec<ConsistencyFinding> = serde_json::from_str(&response[start..=end]).ok()?;
+        Some(findings.into_iter().filter(|finding| !finding.issue.trim().is_empty()).collect())
+    }
+
+    // Adds the findings' topics to the avoid list until `until`, dropping expired
+    // ones. A topic already on the list (ignoring case) is extended instead.
+    pub fn flag_topics(avoided: &mut Vec<AvoidedTopic>, findings: &[ConsistencyFinding], now: DateTime<Utc>, until: DateTime<Utc>) -> usize {
+        avoided.retain(|topic| topic.until > now);
+        let mut flagged = 0;
+        for finding in findings {
+            let Some(topic) = finding.topic.as_deref().map(str::trim).filter(|topic| !topic.is_empty()) else {
+                continue;
+            };
+            match avoided.iter_mut().find(|avoided| avoided.topic.eq_ignore_ascii_case(topic)) {
+                Some(existing) => {
+                    existing.until = existing.until.max(until);
+                    existing.reason = finding.issue.clone();
+                }
+                None => {
+                    avoided.push(AvoidedTopic {
+                        topic: topic.to_string(),
+                        reason: finding.issue.clone(),
+                        flagged_at: now,
+                        until,
+                    });
+                    flagged += 1;
+                }
+            }
+        }
+        flagged
+    }
+
+    // Whether a token is on the avoid list: a topic naming its symbol (with or
+    // without the $, ignoring case) or its mint, and not yet expired
+    pub fn is_avoided(avoided: &[AvoidedTopic], symbol: &str, mint: &str, now: DateTime<Utc>) -> bool {
+        let symbol = symbol.trim_start_matches('$');
+        avoided.iter().filter(|topic| topic.until > now).any(|topic| {
+            let topic = topic.topic.trim();
+            (!mint.is_empty() && topic == mint) || (!symbol.is_empty() && topic.trim_start_matches('$').eq_ignore_ascii_case(symbol))
+        })
+    }
+}
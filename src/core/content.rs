@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+
+use super::character::ContentSlot;
+use super::runtime::Runtime;
+use crate::content::{
+    editorialized_fud::EditorializedFud,
+    generic_shitpost::GenericShitpost,
+    image_meme::ImageMeme,
+    network_fud::NetworkFud,
+    news::NewsReactionPost,
+    prediction::PredictionPost,
+    shill::Shill,
+    space::SpaceCommentary,
+    token_comparison::TokenComparison,
+    trending_summary::TrendingSummary,
+};
+use crate::models::{NewsReaction, Prediction, SpaceComment};
+use crate::providers::solanatracker::TokenResponse;
+
+// A finished scheduled post, ready for the runtime to publish
+pub struct Post {
+    pub text: String,
+    pub image: Option<Vec<u8>>,
+    // Set when the post is FUD about this token: it goes to Discord and is watched for whale sells
+    pub target: Option<TokenResponse>,
+    // What the text was generated from (token summaries), saved with the post for datasets
+    pub context: Option<String>,
+    // A call to record once posted, so it can be graded later
+    pub prediction: Option<Prediction>,
+    // Headline this reacts to, remembered once posted so it isn't reacted to again
+    pub news: Option<NewsReaction>,
+    // Space this comments on, remembered once posted so it isn't brought up twice
+    pub space: Option<SpaceComment>,
+    // Stock chart attached as the image, counted once posted
+    pub chart_image: Option<String>,
+    // Same for a reaction clip from storage/videos
+    pub reaction_clip: Option<String>,
+}
+
+impl Post {
+    pub fn text(text: String) -> Self {
+        Post {
+            text,
+            image: None,
+            target: None,
+            context: None,
+            prediction: None,
+            news: None,
+            space: None,
+            chart_image: None,
+            reaction_clip: None,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+pub trait ContentGenerator {
+    // Key for this kind of post in content_schedule slots and in logs
+    fn name(&self) -> &'static str;
+
+    fn weight(&self, slot: &ContentSlot) -> u32 {
+        slot.weight(self.name())
+    }
+
+    // None when there's nothing to post right now; the runtime falls back to FUD
+    async fn generate(&self, runtime: &mut Runtime) -> Result<Option<Post>, anyhow::Error>;
+}
+
+pub struct ContentGenerators;
+
+impl ContentGenerators {
+    pub const FUD: &'static str = "fud";
+    pub const SHILL: &'static str = "shill";
+    pub const SHITPOST: &'static str = "shitpost";
+    pub const NETWORK: &'static str = "network";
+    pub const NEWS: &'static str = "news";
+    pub const SPACE: &'static str = "space";
+
+    pub fn get_generators() -> Vec<Box<dyn ContentGenerator>> {
+        vec![
+            Box::new(EditorializedFud),
+            Box::new(TrendingSummary),
+            Box::new(GenericShitpost),
+            Box::new(ImageMeme),
+            Box::new(NetworkFud),
+            Box::new(NewsReactionPost),
+            Box::new(PredictionPost),
+            Box::new(Shill),
+            Box::new(SpaceCommentary),
+            Box::new(TokenComparison),
+        ]
+    }
+
+    pub fn find<'a>(generators: &'a [Box<dyn ContentGenerator>], name: &str) -> Option<&'a dyn ContentGenerator> {
+        generators
+            .iter()
+            .find(|generator| generator.name() == name)
+            .map(|generator| generator.as_ref())
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::character::ContentBufferSettings;
+use super::follow_up::FollowUp;
+use super::outbox::OutboxItem;
+use crate::models::TokenSnapshot;
+use crate::providers::solanatracker::SolanaTracker;
+
+// Scheduled posts written ahead of their slot and kept in the outbox, so a slow
+// model call never makes the post at the minute mark late
+pub struct ContentBuffer;
+
+impl ContentBuffer {
+    // Too old to post at all; the take would have moved on
+    pub fn is_fresh(item: &OutboxItem, settings: &ContentBufferSettings, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(item.created_at) < Duration::minutes(settings.max_age_minutes)
+    }
+
+    // Old enough that the token numbers in the text should be brought up to date
+    pub fn needs_refresh(item: &OutboxItem, settings: &ContentBufferSettings, now: DateTime<Utc>) -> bool {
+        item.target_snapshot.is_some()
+            && now.signed_duration_since(item.created_at) >= Duration::minutes(settings.refresh_after_minutes)
+    }
+
+    // Swaps the market cap, liquidity and price the text was written with for current
+    // ones, as they're formatted in token summaries. Anything the model reworded stays.
+    pub fn refresh_numbers(text: &str, then: &TokenSnapshot, now: &TokenSnapshot) -> String {
+        let mut swaps: Vec<(String, String)> = [
+            (SolanaTracker::format_currency(then.market_cap), SolanaTracker::format_currency(now.market_cap)),
+            (SolanaTracker::format_currency(then.liquidity_usd), SolanaTracker::format_currency(now.liquidity_usd)),
+            (FollowUp::format_price(then.price_usd), FollowUp::format_price(now.price_usd)),
+        ]
+        .into_iter()
+        .filter(|(old, new)| old != new)
+        .collect();
+        // Longest first, so a price never replaces part of a market cap
+        swaps.sort_by_key(|(old, _)| std::cmp::Reverse(old.len()));
+
+        // One pass, so a new number is never swapped again
+        let mut refreshed = String::with_capacity(text.len());
+        let mut rest = text;
+        while !rest.is_empty() {
+            let swap = swaps.iter().find(|(old, _)| {
+                rest.starts_with(old.as_str())
+                    && !rest[old.len()..].starts_with(|c: char| c.is_alphanumeric())
+            });
+            match swap {
+                Some((old, new)) => {
+                    refreshed.push_str(new);
+                    rest = &rest[old.len()..];
+                }
+                None => {
+                    let next = rest.chars().next().map_or(1, char::len_utf8);
+                    refreshed.push_str(&rest[..next]);
+                    rest = &rest[next..];
+                }
+            }
+        }
+        refreshed
+    }
+}
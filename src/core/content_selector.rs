@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+use super::character::{CharacterSettings, ContentSlot};
+use super::content::{ContentGenerator, ContentGenerators};
+use super::local_time::LocalTime;
+
+// Decides what a scheduled post slot should be, based on the time of day
+pub struct ContentSelector;
+
+impl ContentSelector {
+    pub fn pick(settings: &CharacterSettings, generators: &[Box<dyn ContentGenerator>], now: DateTime<Utc>) -> &'static str {
+        let hour = LocalTime::from_settings(settings).hour(now);
+        Self::pick_with(settings, generators, hour, &mut rand::thread_rng())
+    }
+
+    // Name of the content generator to run
+    pub fn pick_with<R: Rng>(
+        settings: &CharacterSettings,
+        generators: &[Box<dyn ContentGenerator>],
+        hour: u32,
+        rng: &mut R,
+    ) -> &'static str {
+        let shill = &settings.shill;
+        if shill.enabled && rng.gen_bool(shill.weight.clamp(0.0, 1.0)) {
+            return ContentGenerators::SHILL;
+        }
+
+        // Outside any configured slot we do what the bot has always done
+        let Some(slot) = Self::slot_for(&settings.content_schedule, hour) else {
+            return ContentGenerators::FUD;
+        };
+
+        let weighted: Vec<(&'static str, u32)> = generators
+            .iter()
+            .map(|generator| (generator.name(), generator.weight(slot)))
+            .collect();
+        let total: u32 = weighted.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return ContentGenerators::FUD;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+        for (name, weight) in weighted {
+            if roll < weight {
+                return name;
+            }
+            roll -= weight;
+        }
+        ContentGenerators::FUD
+    }
+
+    // First slot covering the hour; slots may wrap past midnight (e.g. 22 -> 4)
+    pub fn slot_for(schedule: &[ContentSlot], hour: u32) -> Option<&ContentSlot> {
+        schedule.iter().find(|slot| {
+            if slot.start_hour <= slot.end_hour {
+                (slot.start_hour..slot.end_hour).contains(&hour)
+            } else {
+                hour >= slot.start_hour || hour < slot.end_hour
+            }
+        })
+    }
+}
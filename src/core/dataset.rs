@@ -0,0 +1,96 @@
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::prompt_budget::PromptSection;
+use crate::models::{Memory, Tweet, TweetType};
+
+pub const DEFAULT_DATASET_PATH: &str = "./storage/dataset.jsonl";
+pub const DEFAULT_TOP_POSTS: usize = 200;
+pub const DEFAULT_MIN_ENGAGEMENT: u64 = 10;
+
+// Sits between post examples and topics, so it's trimmed early but not first
+const FEW_SHOT_PRIORITY: u8 = 15;
+const FEW_SHOT_HEADER: &str = "Your best-received posts (match their energy, never repeat them):";
+
+// Instruction used for posts that weren't generated from token data
+const NO_CONTEXT_INSTRUCTION: &str = "Write a new post.";
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+// One chat-format training example: character preamble, input, the post that did well
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DatasetExample {
+    pub messages: Vec<ChatMessage>,
+}
+
+impl DatasetExample {
+    pub fn from_tweet(tweet: &Tweet) -> Self {
+        let user = match &tweet.context {
+            Some(context) if !context.trim().is_empty() => context.clone(),
+            _ => NO_CONTEXT_INSTRUCTION.to_string(),
+        };
+        let mut messages = Vec::new();
+        if !tweet.prompt.trim().is_empty() {
+            messages.push(ChatMessage { role: "system", content: tweet.prompt.clone() });
+        }
+        messages.push(ChatMessage { role: "user", content: user });
+        messages.push(ChatMessage { role: "assistant", content: tweet.text.clone() });
+        DatasetExample { messages }
+    }
+}
+
+pub struct DatasetBuilder;
+
+impl DatasetBuilder {
+    // Posted originals with at least min_engagement, most engaged first
+    pub fn top_posts(memory: &Memory, top: usize, min_engagement: u64) -> Vec<&Tweet> {
+        let mut posts: Vec<(&Tweet, u64)> = memory
+            .tweets
+            .iter()
+            .filter(|tweet| matches!(tweet.tweet_type, TweetType::Original))
+            .filter_map(|tweet| tweet.metrics.as_ref().map(|metrics| (tweet, metrics.total())))
+            .filter(|(_, engagement)| *engagement >= min_engagement)
+            .collect();
+        posts.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.timestamp.cmp(&a.0.timestamp)));
+        posts.into_iter().take(top).map(|(tweet, _)| tweet).collect()
+    }
+
+    pub fn to_jsonl(posts: &[&Tweet]) -> io::Result<String> {
+        let mut out = String::new();
+        for tweet in posts {
+            out.push_str(&serde_json::to_string(&DatasetExample::from_tweet(tweet))?);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    // Writes the dataset and returns how many examples went into it
+    pub fn write(memory: &Memory, top: usize, min_engagement: u64, path: &Path) -> io::Result<usize> {
+        let posts = Self::top_posts(memory, top, min_engagement);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, Self::to_jsonl(&posts)?)?;
+        Ok(posts.len())
+    }
+
+    // Prompt section with the best posts, or None until enough have been measured
+    pub fn few_shot_section(memory: &Memory, examples: usize, min_engagement: u64) -> Option<PromptSection> {
+        let posts = Self::top_posts(memory, examples, min_engagement);
+        if posts.is_empty() {
+            return None;
+        }
+        let body = posts
+            .iter()
+            .map(|tweet| format!("- {}", tweet.text.split_whitespace().collect::<Vec<_>>().join(" ")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(PromptSection::trimmable(FEW_SHOT_HEADER, &body, FEW_SHOT_PRIORITY))
+    }
+}
@@ -0,0 +1,242 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::content::Post;
+use super::outbox::{Outbox, OutboxItem};
+use crate::error::FudError;
+use crate::media::media_kind::MediaKind;
+
+pub const DEFAULT_DRAFTS_DIR: &str = "./storage/drafts";
+
+const CONTEXT_HEADING: &str = "**Token context**";
+const TEXT_HEADING: &str = "**Final text**";
+const FENCE_OPEN: &str = "```text";
+const FENCE_CLOSE: &str = "```";
+
+// A post written while tweet_mode was off, kept for whoever is tuning the prompts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draft {
+    pub id: String,
+    pub label: String,
+    pub written_at: DateTime<Utc>,
+    pub text: String,
+    pub context: Option<String>,
+    // Symbol and mint of the token the post is about
+    pub token: Option<(String, String)>,
+    // File next to the day's drafts
+    pub image: Option<String>,
+    pub promoted_at: Option<DateTime<Utc>>,
+}
+
+impl Draft {
+    pub fn new(label: &str, post: &Post, now: DateTime<Utc>) -> Self {
+        let id = format!("{}-{:04x}", now.format("%Y%m%dT%H%M%S%3f"), rand::random::<u16>());
+        Draft {
+            image: post
+                .image
+                .as_deref()
+                .map(|bytes| format!("{}.{}", id, MediaKind::detect(bytes).extension())),
+            id,
+            label: label.to_string(),
+            written_at: now,
+            text: post.text.clone(),
+            context: post.context.clone(),
+            token: post.target.as_ref().map(|target| (target.token.symbol.clone(), target.token.mint.clone())),
+            promoted_at: None,
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            format!("## {}, {} UTC", self.label, self.written_at.format("%H:%M")),
+            String::new(),
+            format!("- id: {}", self.id),
+            format!("- written: {}", self.written_at.to_rfc3339()),
+        ];
+        if let Some((symbol, mint)) = &self.token {
+            lines.push(format!("- token: ${} ({})", symbol, mint));
+        }
+        if let Some(image) = &self.image {
+            lines.push(format!("- image: {}", image));
+        }
+        if let Some(at) = self.promoted_at {
+            lines.push(format!("- promoted: {}", at.to_rfc3339()));
+        }
+        if let Some(context) = &self.context {
+            lines.extend([String::new(), CONTEXT_HEADING.to_string(), String::new(), FENCE_OPEN.to_string()]);
+            lines.push(context.trim_end().to_string());
+            lines.push(FENCE_CLOSE.to_string());
+        }
+        lines.extend([String::new(), TEXT_HEADING.to_string(), String::new(), FENCE_OPEN.to_string()]);
+        lines.push(self.text.clone());
+        lines.push(FENCE_CLOSE.to_string());
+        lines.push(String::new());
+        lines.join("\n") + "\n"
+    }
+
+    // Every draft in a day's file. Drafts without an id or time are skipped.
+    pub fn parse(markdown: &str) -> Vec<Draft> {
+        let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+        for line in markdown.lines() {
+            match line.strip_prefix("## ") {
+                Some(heading) => sections.push((heading, Vec::new())),
+                None => {
+                    if let Some((_, lines)) = sections.last_mut() {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+        sections.into_iter().filter_map(|(heading, lines)| Self::parse_one(heading, &lines)).collect()
+    }
+
+    fn parse_one(heading: &str, lines: &[&str]) -> Option<Draft> {
+        let time = |value: &str| DateTime::parse_from_rfc3339(value).ok().map(|at| at.with_timezone(&Utc));
+        let mut id = None;
+        let mut written_at = None;
+        let mut token = None;
+        let mut image = None;
+        let mut promoted_at = None;
+        let mut context = None;
+        let mut text = None;
+
+        let mut lines = lines.iter();
+        let mut heading_seen = None;
+        while let Some(line) = lines.next() {
+            if *line == CONTEXT_HEADING || *line == TEXT_HEADING {
+                heading_seen = Some(*line);
+            } else if *line == FENCE_OPEN {
+                let block = lines.by_ref().take_while(|line| **line != FENCE_CLOSE).copied().collect::<Vec<_>>().join("\n");
+                if heading_seen.take() == Some(CONTEXT_HEADING) {
+                    context = Some(block);
+                } else {
+                    text = Some(block);
+                }
+            } else if let Some((key, value)) = line.strip_prefix("- ").and_then(|field| field.split_once(": ")) {
+                match key {
+                    "id" => id = Some(value.to_string()),
+                    "written" => written_at = time(value),
+                    "token" => {
+                        token = value
+                            .split_once(" (")
+                            .map(|(symbol, mint)| (symbol.trim_start_matches('$').to_string(), mint.trim_end_matches(')').to_string()));
+                    }
+                    "image" => image = Some(value.to_string()),
+                    "promoted" => promoted_at = time(value),
+                    _ => {}
+                }
+            }
+        }
+
+        let label = heading.rsplit_once(", ").map_or(heading, |(label, _)| label);
+        Some(Draft {
+            id: id?,
+            label: label.to_string(),
+            written_at: written_at?,
+            text: text.unwrap_or_default(),
+            context,
+            token,
+            image,
+            promoted_at,
+        })
+    }
+
+    // The queued post; the running bot looks the token up again when it sends it
+    pub fn to_outbox_item(&self, now: DateTime<Utc>, has_image: bool) -> OutboxItem {
+        let post = Post {
+            context: self.context.clone(),
+            ..Post::text(self.text.clone())
+        };
+        let mut item = OutboxItem::new(&self.label, &post, now);
+        item.has_image = has_image;
+        item.target_mint = self.token.as_ref().map(|(_, mint)| mint.clone()).filter(|mint| !mint.is_empty());
+        item
+    }
+}
+
+// storage/drafts/: one Markdown file per day, YYYY-MM-DD.md, with images beside it
+pub struct DraftLog {
+    dir: PathBuf,
+}
+
+impl DraftLog {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        DraftLog { dir: dir.as_ref().to_path_buf() }
+    }
+
+    pub fn day_path(&self, day: NaiveDate) -> PathBuf {
+        self.dir.join(format!("{}.md", day.format("%Y-%m-%d")))
+    }
+
+    fn header(day: NaiveDate) -> String {
+        format!(
+            "# Drafts for {}\n\nWritten while tweet_mode was off. Promote one to the outbox with `drafts promote <id>`.\n\n",
+            day.format("%Y-%m-%d")
+        )
+    }
+
+    // Adds the draft to its day's file, starting the file if it's the first one
+    pub fn append(&self, draft: &Draft, image: Option<&[u8]>) -> Result<PathBuf, FudError> {
+        fs::create_dir_all(&self.dir)?;
+        if let (Some(name), Some(bytes)) = (&draft.image, image) {
+            fs::write(self.dir.join(name), bytes)?;
+        }
+        let day = draft.written_at.date_naive();
+        let path = self.day_path(day);
+        let mut markdown = fs::read_to_string(&path).unwrap_or_else(|_| Self::header(day));
+        markdown.push_str(&draft.to_markdown());
+        fs::write(&path, markdown)?;
+        Ok(path)
+    }
+
+    // The draft with this id and the file it's in, newest files first
+    pub fn find(&self, id: &str) -> Result<(PathBuf, Draft), FudError> {
+        let mut days: Vec<PathBuf> = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "md"))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        days.sort();
+        for path in days.into_iter().rev() {
+            let markdown = fs::read_to_string(&path)?;
+            if let Some(draft) = Draft::parse(&markdown).into_iter().find(|draft| draft.id == id) {
+                return Ok((path, draft));
+            }
+        }
+        Err(FudError::NotFound(format!("draft {} in {}", id, self.dir.display())))
+    }
+
+    // Queues the draft in the outbox and marks it promoted so it isn't queued twice
+    pub fn promote(&self, id: &str, outbox: &Outbox, now: DateTime<Utc>) -> Result<OutboxItem, FudError> {
+        let (path, draft) = self.find(id)?;
+        if let Some(at) = draft.promoted_at {
+            return Err(FudError::Other(anyhow::anyhow!("Draft {} was already promoted at {}", id, at.format("%Y-%m-%d %H:%M UTC"))));
+        }
+        let image = match &draft.image {
+            Some(name) => Some(fs::read(self.dir.join(name))?),
+            None => None,
+        };
+        let item = draft.to_outbox_item(now, image.is_some());
+        outbox.enqueue(&item, image.as_deref())?;
+
+        // Only the field is added, so notes written into the file are kept
+        let id_line = format!("- id: {}", id);
+        let markdown = fs::read_to_string(&path)?
+            .lines()
+            .map(|line| {
+                if line == id_line {
+                    format!("{}\n- promoted: {}", line, now.to_rfc3339())
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&path, markdown + "\n")?;
+        Ok(item)
+    }
+}
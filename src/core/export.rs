@@ -0,0 +1,183 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::models::{Memory, Tweet, TweetType};
+use crate::providers::twitter::Twitter;
+
+pub const DEFAULT_EXPORT_DIR: &str = "./storage/export";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "markdown" | "md" => Some(ExportFormat::Markdown),
+            "csv" => Some(ExportFormat::Csv),
+            "jsonl" => Some(ExportFormat::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+// One remembered tweet, flattened for export
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ExportRecord {
+    pub timestamp: String,
+    pub kind: &'static str,
+    pub twitter_id: Option<String>,
+    pub url: Option<String>,
+    pub reply_to: Option<String>,
+    pub text: String,
+    pub prompt: String,
+    pub likes: Option<u64>,
+    pub retweets: Option<u64>,
+    pub replies: Option<u64>,
+    pub quotes: Option<u64>,
+}
+
+impl ExportRecord {
+    const CSV_HEADER: &'static str =
+        "timestamp,kind,twitter_id,url,reply_to,text,prompt,likes,retweets,replies,quotes";
+
+    pub fn from_tweet(tweet: &Tweet) -> Self {
+        let metrics = tweet.metrics.as_ref();
+        ExportRecord {
+            timestamp: tweet.timestamp.to_rfc3339(),
+            kind: match tweet.tweet_type {
+                TweetType::Original => "post",
+                TweetType::Reply => "reply",
+            },
+            twitter_id: tweet.twitter_id.clone(),
+            url: tweet.twitter_id.as_deref().map(Twitter::tweet_url),
+            reply_to: tweet.reply_to.clone(),
+            text: tweet.text.clone(),
+            prompt: tweet.prompt.clone(),
+            likes: metrics.map(|m| m.likes),
+            retweets: metrics.map(|m| m.retweets),
+            replies: metrics.map(|m| m.replies),
+            quotes: metrics.map(|m| m.quotes),
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let number = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        [
+            csv_field(&self.timestamp),
+            csv_field(self.kind),
+            csv_field(self.twitter_id.as_deref().unwrap_or_default()),
+            csv_field(self.url.as_deref().unwrap_or_default()),
+            csv_field(self.reply_to.as_deref().unwrap_or_default()),
+            csv_field(&self.text),
+            csv_field(&self.prompt),
+            number(self.likes),
+            number(self.retweets),
+            number(self.replies),
+            number(self.quotes),
+        ]
+        .join(",")
+    }
+
+    fn markdown_entry(&self, time: &str) -> String {
+        let mut heading = format!("## {} UTC · {}", time, self.kind);
+        if let (Some(id), Some(url)) = (&self.twitter_id, &self.url) {
+            heading.push_str(&format!(" · [{}]({})", id, url));
+        }
+        if let Some(reply_to) = &self.reply_to {
+            heading.push_str(&format!(" · reply to {}", reply_to));
+        }
+
+        let quoted = self.text.lines().map(|line| format!("> {}", line)).collect::<Vec<_>>().join("\n");
+        let engagement = match (self.likes, self.retweets, self.replies, self.quotes) {
+            (Some(likes), Some(retweets), Some(replies), Some(quotes)) => format!(
+                "Engagement: {} likes, {} retweets, {} replies, {} quotes",
+                likes, retweets, replies, quotes
+            ),
+            _ => "Engagement: not fetched".to_string(),
+        };
+
+        format!(
+            "{}\n\n{}\n\n{}\n\n<details><summary>Prompt</summary>\n\n```\n{}\n```\n</details>\n",
+            heading, quoted, engagement, self.prompt
+        )
+    }
+}
+
+// Quote every text field; doubled quotes inside
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+// Dumps remembered tweets to one file per UTC day, for auditing and dataset building
+pub struct Exporter;
+
+impl Exporter {
+    pub fn by_day(memory: &Memory) -> BTreeMap<NaiveDate, Vec<&Tweet>> {
+        let mut days: BTreeMap<NaiveDate, Vec<&Tweet>> = BTreeMap::new();
+        for tweet in &memory.tweets {
+            days.entry(tweet.timestamp.date_naive()).or_default().push(tweet);
+        }
+        for tweets in days.values_mut() {
+            tweets.sort_by_key(|tweet| tweet.timestamp);
+        }
+        days
+    }
+
+    pub fn render(format: ExportFormat, date: NaiveDate, tweets: &[&Tweet]) -> io::Result<String> {
+        let records: Vec<ExportRecord> = tweets.iter().map(|tweet| ExportRecord::from_tweet(tweet)).collect();
+        Ok(match format {
+            ExportFormat::Jsonl => {
+                let mut out = String::new();
+                for record in &records {
+                    out.push_str(&serde_json::to_string(record)?);
+                    out.push('\n');
+                }
+                out
+            }
+            ExportFormat::Csv => {
+                let mut out = format!("{}\n", ExportRecord::CSV_HEADER);
+                for record in &records {
+                    out.push_str(&record.csv_row());
+                    out.push('\n');
+                }
+                out
+            }
+            ExportFormat::Markdown => {
+                let mut out = format!("# {}\n\n", date);
+                for (record, tweet) in records.iter().zip(tweets) {
+                    out.push_str(&record.markdown_entry(&tweet.timestamp.format("%H:%M").to_string()));
+                    out.push('\n');
+                }
+                out
+            }
+        })
+    }
+
+    // Writes <dir>/<YYYY-MM-DD>.<ext> for every day with tweets and returns the paths
+    pub fn write_all(memory: &Memory, format: ExportFormat, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+        let mut written = Vec::new();
+        for (date, tweets) in Self::by_day(memory) {
+            let path = dir.join(format!("{}.{}", date, format.extension()));
+            fs::write(&path, Self::render(format, date, &tweets)?)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
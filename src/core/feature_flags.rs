@@ -0,0 +1,144 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::paths;
+use crate::models::FeatureFlags;
+
+// Left by `flags <name> on|off` (or the status endpoint) for the running bot to pick
+// up, one request per line so requests made at the same time don't overwrite each other
+pub const FLAGS_REQUEST_PATH: &str = "./storage/flags_request";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    ImagePosts,
+    NotificationReplies,
+    Threads,
+    ImageGen,
+}
+
+impl Flag {
+    pub const ALL: [Flag; 4] = [
+        Flag::ImagePosts,
+        Flag::NotificationReplies,
+        Flag::Threads,
+        Flag::ImageGen,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flag::ImagePosts => "image_posts",
+            Flag::NotificationReplies => "notification_replies",
+            Flag::Threads => "threads",
+            Flag::ImageGen => "image_gen",
+        }
+    }
+
+    // Dashes work too, since they're easier to type on a phone
+    pub fn parse(text: &str) -> Option<Flag> {
+        let name = text.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter().find(|flag| flag.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlagCommand {
+    Set(Flag, bool),
+    List,
+}
+
+impl FeatureFlags {
+    pub fn get(&self, flag: Flag) -> bool {
+        match flag {
+            Flag::ImagePosts => self.image_posts,
+            Flag::NotificationReplies => self.notification_replies,
+            Flag::Threads => self.threads,
+            Flag::ImageGen => self.image_gen,
+        }
+    }
+
+    pub fn set(&mut self, flag: Flag, on: bool) {
+        let field = match flag {
+            Flag::ImagePosts => &mut self.image_posts,
+            Flag::NotificationReplies => &mut self.notification_replies,
+            Flag::Threads => &mut self.threads,
+            Flag::ImageGen => &mut self.image_gen,
+        };
+        *field = on;
+    }
+
+    pub fn describe(&self) -> String {
+        Flag::ALL
+            .iter()
+            .map(|flag| format!("{}: {}", flag.name(), if self.get(*flag) { "on" } else { "off" }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub struct FeatureSwitch;
+
+impl FeatureSwitch {
+    // "flags <name> on|off" or "flags", with or without a leading slash and the
+    // @botname Telegram appends in groups
+    pub fn parse_command(text: &str) -> Option<FlagCommand> {
+        let mut words = text.split_whitespace();
+        let command = words.next()?.trim_start_matches('/');
+        let command = command.split('@').next().unwrap_or(command);
+        if !command.eq_ignore_ascii_case("flags") {
+            return None;
+        }
+        match (words.next(), words.next(), words.next()) {
+            (None, _, _) => Some(FlagCommand::List),
+            (Some(flag), Some(state), None) => {
+                let on = match state.to_ascii_lowercase().as_str() {
+                    "on" | "true" => true,
+                    "off" | "false" => false,
+                    _ => return None,
+                };
+                Flag::parse(flag).map(|flag| FlagCommand::Set(flag, on))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn write_request(command: &FlagCommand) -> io::Result<()> {
+        Self::append_request(&paths::resolve(FLAGS_REQUEST_PATH), command)
+    }
+
+    // Adds the request as one line, in a single write so concurrent writers' lines
+    // don't interleave
+    pub fn append_request(path: &Path, command: &FlagCommand) -> io::Result<()> {
+        let FlagCommand::Set(flag, on) = command else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(format!("{} {}\n", flag.name(), if *on { "on" } else { "off" }).as_bytes())
+    }
+
+    pub fn take_requests() -> io::Result<Vec<FlagCommand>> {
+        Self::take_requests_from(&paths::resolve(FLAGS_REQUEST_PATH))
+    }
+
+    // The pending requests, oldest first, removed so each is only applied once. The
+    // file is moved aside before it's read, so a request written meanwhile starts a
+    // new one instead of being deleted unread.
+    pub fn take_requests_from(path: &Path) -> io::Result<Vec<FlagCommand>> {
+        let taken = path.with_extension("taken");
+        match fs::rename(path, &taken) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        }
+        let requests = fs::read_to_string(&taken)?;
+        fs::remove_file(&taken)?;
+        Ok(requests
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| Self::parse_command(&format!("flags {}", line.trim())))
+            .collect())
+    }
+}
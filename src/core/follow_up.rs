@@ -0,0 +1,100 @@
+use crate::models::{FuddedToken, TokenSnapshot};
+use crate::providers::solanatracker::SolanaTracker;
+
+// What someone replying under one of our FUD tweets wants to know
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FollowUpQuestion {
+    Source,
+    MarketCap,
+    Price,
+    Liquidity,
+}
+
+// Words that give the question away, checked in this order
+const QUESTIONS: &[(FollowUpQuestion, &[&str])] = &[
+    (FollowUpQuestion::Source, &["source", "sauce", "src", "proof", "receipts", "citation", "evidence"]),
+    (FollowUpQuestion::MarketCap, &["mcap", "marketcap", "market cap", "mc", "fdv", "valuation"]),
+    (FollowUpQuestion::Liquidity, &["liquidity", "liq", "lp"]),
+    (FollowUpQuestion::Price, &["price", "trading at"]),
+];
+
+// Answers follow-up questions about the token a FUD tweet was about, with fresh
+// numbers instead of another insult
+pub struct FollowUp;
+
+impl FollowUp {
+    // Only questions count: "source?" does, a reply that just says "source" doesn't
+    pub fn detect(text: &str) -> Option<FollowUpQuestion> {
+        let text = text.to_lowercase();
+        // Replies start with the handles they're addressed to
+        let opening = text.split_whitespace().find(|word| !word.starts_with('@')).unwrap_or_default();
+        let is_question = text.contains('?') || ["what", "how", "wen", "where"].iter().any(|word| opening.starts_with(word));
+        if !is_question {
+            return None;
+        }
+        // Padded words, so "mc" doesn't match inside "mcdonalds"
+        let words: String = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let words = format!(" {} ", words);
+        QUESTIONS
+            .iter()
+            .find(|(_, phrases)| phrases.iter().any(|phrase| words.contains(&format!(" {} ", phrase))))
+            .map(|(question, _)| *question)
+    }
+
+    // `then` is what we saw when the tweet went out, for the since-then change
+    pub fn answer(question: FollowUpQuestion, now: &TokenSnapshot, then: Option<&FuddedToken>) -> String {
+        let mcap = SolanaTracker::format_currency(now.market_cap);
+        let liquidity = SolanaTracker::format_currency(now.liquidity_usd);
+        match question {
+            FollowUpQuestion::Source => format!(
+                "source: the chain, ser\n\n${} ca: {}\nmcap {} | liq {}\n\nverify it yourself: https://solscan.io/token/{}",
+                now.symbol, now.mint, mcap, liquidity, now.mint
+            ),
+            FollowUpQuestion::MarketCap => {
+                let since = then.map(|then| Self::since(then.market_cap, now.market_cap, &SolanaTracker::format_currency(then.market_cap)));
+                format!("${} mcap is {} right now{}\n\nstill ngmi", now.symbol, mcap, since.unwrap_or_default())
+            }
+            FollowUpQuestion::Price => {
+                let since = then.map(|then| Self::since(then.price_usd, now.price_usd, &Self::format_price(then.price_usd)));
+                format!(
+                    "${} is at {} right now{}\n\nprice is temporary, rugs are forever",
+                    now.symbol,
+                    Self::format_price(now.price_usd),
+                    since.unwrap_or_default()
+                )
+            }
+            FollowUpQuestion::Liquidity => {
+                let share = if now.market_cap > 0.0 {
+                    format!(", {:.1}% of the mcap", now.liquidity_usd / now.market_cap * 100.0)
+                } else {
+                    String::new()
+                };
+                format!("${} has {} of liquidity left{}\n\nexit door is getting smaller", now.symbol, liquidity, share)
+            }
+        }
+    }
+
+    // ", was $1.2M when i called it (-40.0%)", or nothing when there's no earlier number
+    fn since(then: f64, now: f64, then_formatted: &str) -> String {
+        if then <= 0.0 {
+            return String::new();
+        }
+        format!(", was {} when i called it ({:+.1}%)", then_formatted, (now - then) / then * 100.0)
+    }
+
+    // Memecoin prices are tiny, so small ones keep enough digits to mean something
+    pub fn format_price(price: f64) -> String {
+        if price >= 1.0 {
+            format!("${:.2}", price)
+        } else if price >= 0.01 {
+            format!("${:.4}", price)
+        } else {
+            let digits = format!("{:.12}", price);
+            format!("${}", digits.trim_end_matches('0').trim_end_matches('.'))
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Twitter,
+    SolanaTracker,
+    Anthropic,
+    Telegram,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 4] = [
+        Provider::Twitter,
+        Provider::SolanaTracker,
+        Provider::Anthropic,
+        Provider::Telegram,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::Twitter => "Twitter",
+            Provider::SolanaTracker => "SolanaTracker",
+            Provider::Anthropic => "Anthropic",
+            Provider::Telegram => "Telegram",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderStatus {
+    Unchecked,
+    // Detail such as the account name the credentials belong to
+    Ok(String),
+    Degraded(String),
+    // Optional integration without credentials
+    Disabled,
+}
+
+// Result of the startup preflight. Degraded integrations are skipped by the run
+// loop and re-checked every few minutes instead of failing on every use.
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    statuses: Vec<(Provider, ProviderStatus)>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl Default for HealthReport {
+    fn default() -> Self {
+        HealthReport {
+            statuses: Provider::ALL.iter().map(|p| (*p, ProviderStatus::Unchecked)).collect(),
+            checked_at: None,
+        }
+    }
+}
+
+impl HealthReport {
+    pub fn set(&mut self, provider: Provider, status: ProviderStatus) {
+        if let Some(entry) = self.statuses.iter_mut().find(|(p, _)| *p == provider) {
+            entry.1 = status;
+        }
+    }
+
+    pub fn status(&self, provider: Provider) -> &ProviderStatus {
+        self.statuses
+            .iter()
+            .find(|(p, _)| *p == provider)
+            .map(|(_, status)| status)
+            .unwrap_or(&ProviderStatus::Unchecked)
+    }
+
+    // Unchecked counts as available so nothing is blocked before the preflight runs
+    pub fn is_available(&self, provider: Provider) -> bool {
+        matches!(self.status(provider), ProviderStatus::Ok(_) | ProviderStatus::Unchecked)
+    }
+
+    pub fn degraded(&self) -> Vec<Provider> {
+        self.statuses
+            .iter()
+            .filter(|(_, status)| matches!(status, ProviderStatus::Degraded(_)))
+            .map(|(provider, _)| *provider)
+            .collect()
+    }
+
+    pub fn print(&self) {
+        println!("=== Provider Health ===");
+        for (provider, status) in &self.statuses {
+            match status {
+                ProviderStatus::Unchecked => println!("  {:<14} not checked", provider.name()),
+                ProviderStatus::Disabled => println!("  {:<14} disabled (not configured)", provider.name()),
+                ProviderStatus::Ok(detail) if detail.is_empty() => println!("  {:<14} ok", provider.name()),
+                ProviderStatus::Ok(detail) => println!("  {:<14} ok ({})", provider.name(), detail),
+                ProviderStatus::Degraded(reason) => println!("  {:<14} DEGRADED: {}", provider.name(), reason),
+            }
+        }
+        let degraded = self.degraded();
+        if !degraded.is_empty() {
+            let names: Vec<&str> = degraded.iter().map(|p| p.name()).collect();
+            println!("Continuing without: {}", names.join(", "));
+        }
+        println!("=======================\n");
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use super::agent::Agent;
+use super::follow_up::FollowUp;
+use super::token_extractor::{TokenCandidate, TokenExtractor};
+use super::token_lookup::TokenLookup;
+use crate::models::TokenSnapshot;
+use crate::providers::solanatracker::SolanaTracker;
+use crate::providers::telegram::{InlineRequest, InlineResult, Telegram};
+
+// Answers by lowercased token, with when each was written
+pub type InlineCards = Arc<Mutex<HashMap<String, (DateTime<Utc>, InlineCard)>>>;
+
+// What an inline query for a token gets back, cached per token
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineCard {
+    pub symbol: String,
+    pub fud: String,
+    pub stats: String,
+}
+
+// Telegram inline mode: "@bot $TICKER" in any chat offers a FUD snippet and a
+// compact stat card for the token
+pub struct InlineFud;
+
+impl InlineFud {
+    // An address, a cashtag, or a bare ticker on its own
+    pub fn parse(query: &str) -> Option<TokenCandidate> {
+        let query = query.trim();
+        if let Some(candidate) = TokenExtractor::extract_first(query) {
+            return Some(candidate);
+        }
+        let is_ticker = query.len() <= 15
+            && query.starts_with(|c: char| c.is_ascii_alphabetic())
+            && query.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_ticker.then(|| TokenCandidate::Ticker(query.to_string()))
+    }
+
+    // The key an answer to the query is cached under
+    pub fn cache_key(query: &str) -> Option<String> {
+        Self::parse(query).map(|candidate| candidate.into_parts().0.to_lowercase())
+    }
+
+    // Each user's latest query only; the earlier ones were typed on the way to it
+    pub fn latest_per_user(queries: Vec<InlineRequest>) -> Vec<InlineRequest> {
+        let mut latest: HashMap<u64, usize> = HashMap::new();
+        for (i, query) in queries.iter().enumerate() {
+            latest.insert(query.user_id, i);
+        }
+        queries
+            .into_iter()
+            .enumerate()
+            .filter(|(i, query)| latest.get(&query.user_id) == Some(i))
+            .map(|(_, query)| query)
+            .collect()
+    }
+
+    pub fn stat_card(token: &TokenSnapshot) -> String {
+        let mut changes = Vec::new();
+        if let Some(change) = token.change_1h {
+            changes.push(format!("1h {:+.1}%", change));
+        }
+        if let Some(change) = token.change_24h {
+            changes.push(format!("24h {:+.1}%", change));
+        }
+        let mut price = format!("price {}", FollowUp::format_price(token.price_usd));
+        for change in changes {
+            price.push_str(&format!(" | {}", change));
+        }
+        format!(
+            "${}\nmcap {} | liq {}\n{}\nca: {}",
+            token.symbol,
+            SolanaTracker::format_currency(token.market_cap),
+            SolanaTracker::format_currency(token.liquidity_usd),
+            price,
+            token.mint
+        )
+    }
+
+    pub fn results(card: &InlineCard) -> Vec<InlineResult> {
+        vec![
+            InlineResult {
+                id: "fud".to_string(),
+                title: format!("FUD ${}", card.symbol),
+                description: card.fud.chars().take(100).collect(),
+                text: card.fud.clone(),
+            },
+            InlineResult {
+                id: "stats".to_string(),
+                title: format!("${} stats", card.symbol),
+                description: card.stats.lines().skip(1).take(2).collect::<Vec<_>>().join(" | "),
+                text: card.stats.clone(),
+            },
+        ]
+    }
+}
+
+// Fresh answers each user got in the last hour. Cached tokens cost nothing and
+// aren't counted.
+#[derive(Debug, Default)]
+pub struct InlineRateLimit {
+    answered: HashMap<u64, VecDeque<DateTime<Utc>>>,
+}
+
+impl InlineRateLimit {
+    // Whether the user may have another fresh answer, counting it if so; 0 is no limit
+    pub fn allow(&mut self, user_id: u64, per_hour: usize, now: DateTime<Utc>) -> bool {
+        let hour_ago = now - Duration::hours(1);
+        self.answered.retain(|_, times| {
+            while times.front().is_some_and(|at| *at <= hour_ago) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+        let times = self.answered.entry(user_id).or_default();
+        if per_hour > 0 && times.len() >= per_hour {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+}
+
+// What answering an inline query needs, cloned into the task that writes the
+// answer so a slow model call doesn't hold up the run loop
+#[derive(Clone)]
+pub struct InlineResponder {
+    pub lookup: TokenLookup,
+    pub agent: Arc<Agent>,
+    pub telegram: Telegram,
+    pub cards: InlineCards,
+    pub cache_minutes: i64,
+    // The bot's own mint and symbol, which get no answer
+    pub own_mint: String,
+    pub own_symbol: String,
+}
+
+impl InlineResponder {
+    pub fn cached(&self, query: &str, now: DateTime<Utc>) -> Option<InlineCard> {
+        let key = InlineFud::cache_key(query)?;
+        let ttl = Duration::minutes(self.cache_minutes);
+        let mut cards = self.cards.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        cards.retain(|_, (cached_at, _)| now.signed_duration_since(*cached_at) < ttl);
+        cards.get(&key).map(|(_, card)| card.clone())
+    }
+
+    // Answers the query, with nothing when it isn't a token we'd FUD or writing the
+    // card failed; the error is returned for the stats
+    pub async fn answer(&self, query: &InlineRequest, now: DateTime<Utc>) -> anyhow::Result<()> {
+        let card = match self.cached(&query.query, now) {
+            Some(card) => Ok(Some(card)),
+            None => self.card(&query.query, now).await,
+        };
+        let results = match &card {
+            Ok(Some(card)) => InlineFud::results(card),
+            _ => Vec::new(),
+        };
+        let cache_seconds = u32::try_from(self.cache_minutes.max(0) * 60).unwrap_or(u32::MAX);
+        if let Err(e) = self.telegram.answer_inline(&query.id, &results, cache_seconds).await {
+            eprintln!("Failed to answer inline query '{}': {}", query.query, e);
+        }
+        card.map(|_| ())
+    }
+
+    fn is_own_token(&self, token: &str) -> bool {
+        TokenExtractor::is_own_token(token, &self.own_mint, &self.own_symbol)
+    }
+
+    async fn card(&self, query: &str, now: DateTime<Utc>) -> anyhow::Result<Option<InlineCard>> {
+        let Some(candidate) = InlineFud::parse(query) else {
+            return Ok(None);
+        };
+        let (token, is_address) = candidate.into_parts();
+        if self.is_own_token(&token) {
+            return Ok(None);
+        }
+        let Some(found) = self.lookup.lookup(&token, is_address).await.filter(|found| !found.pools.is_empty()) else {
+            return Ok(None);
+        };
+        if self.is_own_token(&found.token.mint) {
+            return Ok(None);
+        }
+        let summary = self.lookup.solana_tracker.format_token_summary(&found);
+        let fud = self.agent.generate_editorialized_fud(&summary).await?;
+        let card = InlineCard {
+            symbol: found.token.symbol.clone(),
+            fud,
+            stats: InlineFud::stat_card(&found.snapshot()),
+        };
+        println!("Answered inline query for ${}", card.symbol);
+        let mut cards = self.cards.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        cards.insert(token.to_lowercase(), (now, card.clone()));
+        Ok(Some(card))
+    }
+}
@@ -1,39 +1,151 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::fs;
 use std::io;
-use super::character::Character;
+use std::path::Path;
+use super::character::{Character, CharacterSettings, GenerationSettings};
 use super::characteristics::Characteristics;
+use super::paths::Layout;
+use super::prompt_budget::{self, PromptSection};
+
+// Next to character.json; every .txt file in it holds past posts, separated by blank lines
+pub const EXAMPLES_DIR: &str = "examples";
+const EXAMPLES_HEADER: &str = "Examples of your past posts. Match their voice, but don't copy their structure or wording:";
+
+// The curated examples a character ships with. Each generation call gets a few at
+// random, and the ones shown last time sit out the next call while there are enough
+// others, so the model doesn't settle into one example's shape.
+#[derive(Debug, Clone, Default)]
+pub struct ExampleRotation {
+    examples: Vec<String>,
+    per_call: usize,
+    last: Vec<usize>,
+}
+
+impl ExampleRotation {
+    pub fn new(examples: Vec<String>, per_call: usize) -> Self {
+        ExampleRotation { examples, per_call, last: Vec::new() }
+    }
+
+    // Empty when the directory doesn't exist; files are read in name order
+    pub fn load(dir: &Path, per_call: usize) -> io::Result<Self> {
+        if !dir.is_dir() {
+            return Ok(Self::new(Vec::new(), per_call));
+        }
+        let mut files: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        files.sort();
+        let mut examples = Vec::new();
+        for file in files {
+            examples.extend(Self::parse(&fs::read_to_string(file)?));
+        }
+        Ok(Self::new(examples, per_call))
+    }
+
+    // Posts separated by blank lines, each folded onto one line
+    pub fn parse(text: &str) -> Vec<String> {
+        let mut posts = Vec::new();
+        let mut post: Vec<&str> = Vec::new();
+        for line in text.lines().chain(std::iter::once("")) {
+            if line.trim().is_empty() {
+                if !post.is_empty() {
+                    posts.push(post.join(" ").split_whitespace().collect::<Vec<_>>().join(" "));
+                    post.clear();
+                }
+            } else {
+                post.push(line);
+            }
+        }
+        posts
+    }
+
+    pub fn posts(&self) -> &[String] {
+        &self.examples
+    }
+
+    pub fn len(&self) -> usize {
+        self.examples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.examples.is_empty()
+    }
+
+    pub fn sample<R: Rng>(&mut self, rng: &mut R) -> Vec<String> {
+        let count = self.per_call.min(self.examples.len());
+        if count == 0 {
+            return Vec::new();
+        }
+        let fresh: Vec<usize> = (0..self.examples.len()).filter(|i| !self.last.contains(i)).collect();
+        let pool: Vec<usize> = if fresh.len() >= count { fresh } else { (0..self.examples.len()).collect() };
+        let mut picked: Vec<usize> = pool.choose_multiple(rng, count).copied().collect();
+        picked.shuffle(rng);
+        self.last = picked.clone();
+        picked.iter().map(|i| self.examples[*i].clone()).collect()
+    }
+
+    // The sampled examples as a block for the end of the preamble; empty without any
+    pub fn sample_section<R: Rng>(&mut self, rng: &mut R) -> String {
+        let examples = self.sample(rng);
+        if examples.is_empty() {
+            return String::new();
+        }
+        let body = examples.iter().map(|example| format!("- {}", example)).collect::<Vec<_>>().join("\n");
+        format!("\n\n{}\n{}", EXAMPLES_HEADER, body)
+    }
+}
 
 pub struct InstructionBuilder {
     instructions: String,
+    sections: Vec<PromptSection>,
+    generation: GenerationSettings,
+    settings: CharacterSettings,
+    examples: ExampleRotation,
 }
 
 impl InstructionBuilder {
     pub fn new() -> Self {
         Self {
             instructions: String::new(),
+            sections: Vec::new(),
+            generation: GenerationSettings::default(),
+            settings: CharacterSettings::default(),
+            examples: ExampleRotation::default(),
         }
     }
 
+    // characters/<name>/character.json under FUD_HOME
     pub fn load_character(character_name: &str) -> io::Result<Character> {
-        let path = format!("./characters/{}/character.json", character_name);
-        let data = fs::read_to_string(&path)?;
+        Self::load_character_file(&Layout::from_env().character_file(character_name))
+    }
+
+    pub fn load_character_file(path: &Path) -> io::Result<Character> {
+        let data = fs::read_to_string(path)?;
         serde_json::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     pub fn build_instructions(&mut self, character_name: &str) -> io::Result<()> {
+        self.build_instructions_from_file(&Layout::from_env().character_file(character_name))
+    }
+
+    pub fn build_instructions_from_file(&mut self, path: &Path) -> io::Result<()> {
         self.instructions.clear();
         
-        let character = Self::load_character(character_name)?;
+        let character = Self::load_character_file(path)?;
         
-        // Add base instructions
-        self.add_instruction(&character.instructions.base);
+        // Base instructions, characteristics, then suffix instructions
+        self.sections.clear();
+        self.sections.push(PromptSection::fixed(&character.instructions.base));
+        self.sections.extend(Characteristics::build_characteristics_sections(&character));
+        self.sections.push(PromptSection::fixed(&character.instructions.suffix));
+        self.add_instruction(&prompt_budget::render(&self.sections));
 
-        // Add characteristics
-        let characteristics = Characteristics::build_characteristics_instructions(&character);
-        self.add_instruction(&characteristics);
-
-        // Add suffix instructions
-        self.add_instruction(&character.instructions.suffix);
+        self.generation = character.generation;
+        self.settings = character.settings;
+        let examples_dir = path.parent().unwrap_or(Path::new(".")).join(EXAMPLES_DIR);
+        self.examples = ExampleRotation::load(&examples_dir, self.settings.example_rotation.per_call)?;
 
         Ok(())
     }
@@ -54,4 +166,24 @@ impl InstructionBuilder {
     pub fn get_instructions(&self) -> &str {
         &self.instructions
     }
+
+    // The instructions split into sections the agent can trim to fit its budget
+    pub fn get_sections(&self) -> &[PromptSection] {
+        &self.sections
+    }
+
+    // Get the character's per-task generation parameters
+    pub fn get_generation_settings(&self) -> &GenerationSettings {
+        &self.generation
+    }
+
+    // Get the character's runtime behaviour settings
+    pub fn get_character_settings(&self) -> &CharacterSettings {
+        &self.settings
+    }
+
+    // The examples/ directory's posts, for the agent to sample per call
+    pub fn get_examples(&self) -> &ExampleRotation {
+        &self.examples
+    }
 }
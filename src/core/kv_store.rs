@@ -0,0 +1,263 @@
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::Result;
+
+// Logs shorter than this are never worth compacting
+const MIN_COMPACT_OPS: usize = 64;
+
+// Decides a key's new value from its current one, for KvStore::update
+pub type Update = Box<dyn Fn(Option<&str>) -> Option<String> + Send>;
+
+// String keys to string values, written one record at a time. Runtime state that
+// changes on every mention or post goes through this instead of a whole-file rewrite.
+pub trait KvStore: Send {
+    fn get(&self, key: &str) -> Option<String>;
+    fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    fn insert(&mut self, key: &str, value: &str) -> Result<()>;
+    // True if the key was there
+    fn remove(&mut self, key: &str) -> Result<bool>;
+    // Writes what `update` makes of the current value, or nothing if it returns None;
+    // true if it wrote. A shared store holds the key locked while it decides.
+    fn update(&mut self, key: &str, update: Update) -> Result<bool> {
+        let Some(value) = update(self.get(key).as_deref()) else {
+            return Ok(false);
+        };
+        self.insert(key, &value)?;
+        Ok(true)
+    }
+    // All entries in key order
+    fn entries(&self) -> Vec<(String, String)>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Zero-padded microseconds, so key order is time order
+pub fn time_key(at: DateTime<Utc>) -> String {
+    format!("{:020}", at.timestamp_micros().max(0))
+}
+
+pub fn parse_time_key(key: &str) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_micros(key.parse().ok()?)
+}
+
+// Store a value under its time, nudged forward a microsecond at a time past any
+// record that already has it
+pub fn insert_timed(store: &mut dyn KvStore, at: DateTime<Utc>, value: &str) -> Result<()> {
+    let mut at = at;
+    while store.contains(&time_key(at)) {
+        at += chrono::Duration::microseconds(1);
+    }
+    store.insert(&time_key(at), value)
+}
+
+// Drop time-keyed records from before the cutoff; how many went
+pub fn remove_before(store: &mut dyn KvStore, cutoff: DateTime<Utc>) -> Result<usize> {
+    let expired: Vec<String> = store
+        .entries()
+        .into_iter()
+        .map(|(key, _)| key)
+        .take_while(|key| parse_time_key(key).is_some_and(|at| at < cutoff))
+        .collect();
+    for key in &expired {
+        store.remove(key)?;
+    }
+    Ok(expired.len())
+}
+
+// One line of the log; no value means the key was removed
+#[derive(Serialize, Deserialize)]
+struct LogOp {
+    k: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    v: Option<String>,
+}
+
+// Append-only JSON lines replayed into memory on open. Each write is a single
+// appended line, and the file is rewritten only once superseded lines outnumber
+// live ones, so updates cost the same however large the store grows.
+//
+// Several processes can share one file (token heat does): every write and rewrite
+// holds an exclusive lock on `<file>.lock`, and first replays the file again if
+// anyone else has written to it since this process last did.
+pub struct LogStore {
+    // None keeps everything in memory, for when the storage dir isn't writable
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, String>,
+    // Lines in the file, live or not
+    ops: usize,
+    // Length and modification time of the file as this process last left it
+    seen: Option<(u64, SystemTime)>,
+}
+
+impl LogStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut store = LogStore { path: Some(path.clone()), entries: BTreeMap::new(), ops: 0, seen: None };
+        let _lock = Self::lock(&path)?;
+        // Rewrite past a torn line so the next append doesn't land on the end of it
+        if store.replay(&path)? {
+            store.compact_locked(&path)?;
+        }
+        store.compact_if_needed(&path)?;
+        store.seen = Self::stamp(&path);
+        Ok(store)
+    }
+
+    pub fn in_memory() -> Self {
+        LogStore { path: None, entries: BTreeMap::new(), ops: 0, seen: None }
+    }
+
+    // Held until the returned file is dropped
+    fn lock(path: &Path) -> Result<File> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let lock = OpenOptions::new().create(true).truncate(false).write(true).open(path.with_extension("lock"))?;
+        lock.lock_exclusive()?;
+        Ok(lock)
+    }
+
+    fn stamp(path: &Path) -> Option<(u64, SystemTime)> {
+        let metadata = fs::metadata(path).ok()?;
+        Some((metadata.len(), metadata.modified().ok()?))
+    }
+
+    // Reads the whole file into `entries`; true if it ends in a torn line
+    fn replay(&mut self, path: &Path) -> Result<bool> {
+        self.entries.clear();
+        self.ops = 0;
+        let mut torn = false;
+        if let Ok(contents) = fs::read_to_string(path) {
+            for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                self.ops += 1;
+                // A crash mid-append leaves a torn last line; the rest is still good
+                let Ok(op) = serde_json::from_str::<LogOp>(line) else {
+                    torn = true;
+                    continue;
+                };
+                Self::apply(&mut self.entries, op);
+            }
+            // A line cut off right before its newline still parses, but the next
+            // append would be glued onto it
+            torn |= !contents.is_empty() && !contents.ends_with('\n');
+        }
+        Ok(torn)
+    }
+
+    fn apply(entries: &mut BTreeMap<String, String>, op: LogOp) {
+        match op.v {
+            Some(value) => entries.insert(op.k, value),
+            None => entries.remove(&op.k),
+        };
+    }
+
+    // Decides on a change from the current entries and writes it. With a file, that
+    // happens under the lock and after catching up with other processes' writes.
+    fn write(&mut self, change: impl FnOnce(&BTreeMap<String, String>) -> Option<LogOp>) -> Result<bool> {
+        let Some(path) = self.path.clone() else {
+            let Some(op) = change(&self.entries) else {
+                return Ok(false);
+            };
+            Self::apply(&mut self.entries, op);
+            return Ok(true);
+        };
+        let _lock = Self::lock(&path)?;
+        if Self::stamp(&path) != self.seen && self.replay(&path)? {
+            self.compact_locked(&path)?;
+        }
+        let Some(op) = change(&self.entries) else {
+            self.seen = Self::stamp(&path);
+            return Ok(false);
+        };
+        // One write, so a crash can't leave the line without its newline
+        let line = format!("{}\n", serde_json::to_string(&op)?);
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(line.as_bytes())?;
+        Self::apply(&mut self.entries, op);
+        self.ops += 1;
+        self.compact_if_needed(&path)?;
+        self.seen = Self::stamp(&path);
+        Ok(true)
+    }
+
+    fn compact_if_needed(&mut self, path: &Path) -> Result<()> {
+        if self.ops > MIN_COMPACT_OPS && self.ops > self.entries.len() * 2 {
+            self.compact_locked(path)?;
+        }
+        Ok(())
+    }
+
+    // Rewrite the log with one line per live entry, including whatever other
+    // processes have written
+    pub fn compact(&mut self) -> Result<()> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
+        };
+        let _lock = Self::lock(&path)?;
+        if Self::stamp(&path) != self.seen {
+            self.replay(&path)?;
+        }
+        self.compact_locked(&path)?;
+        self.seen = Self::stamp(&path);
+        Ok(())
+    }
+
+    // compact, for a caller already holding the lock with `entries` up to date
+    fn compact_locked(&mut self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        for (key, value) in &self.entries {
+            let op = LogOp { k: key.clone(), v: Some(value.clone()) };
+            writeln!(file, "{}", serde_json::to_string(&op)?)?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        self.ops = self.entries.len();
+        Ok(())
+    }
+}
+
+impl KvStore for LogStore {
+    fn get(&self, key: &str) -> Option<String> {
+        self.entries.get(key).cloned()
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn insert(&mut self, key: &str, value: &str) -> Result<()> {
+        self.write(|entries| {
+            (entries.get(key).map(String::as_str) != Some(value)).then(|| LogOp { k: key.to_string(), v: Some(value.to_string()) })
+        })?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<bool> {
+        self.write(|entries| entries.contains_key(key).then(|| LogOp { k: key.to_string(), v: None }))
+    }
+
+    // Decided under the file lock, so processes sharing the file take turns
+    fn update(&mut self, key: &str, update: Update) -> Result<bool> {
+        self.write(|entries| update(entries.get(key).map(String::as_str)).map(|value| LogOp { k: key.to_string(), v: Some(value) }))
+    }
+
+    fn entries(&self) -> Vec<(String, String)> {
+        self.entries.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
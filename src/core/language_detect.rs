@@ -0,0 +1,128 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+type CharRange = (char, char);
+
+const KANA: CharRange = ('\u{3040}', '\u{30FF}');
+const HAN: CharRange = ('\u{4E00}', '\u{9FFF}');
+
+// Languages told apart by their script alone: name, code and character ranges.
+// Chinese and Japanese share Han characters and are handled on their own.
+const SCRIPTS: &[(&str, &str, &[CharRange])] = &[
+    ("Korean", "ko", &[('\u{AC00}', '\u{D7AF}'), ('\u{1100}', '\u{11FF}'), ('\u{3130}', '\u{318F}')]),
+    ("Russian", "ru", &[('\u{0400}', '\u{04FF}')]),
+    ("Arabic", "ar", &[('\u{0600}', '\u{06FF}')]),
+    ("Hebrew", "he", &[('\u{0590}', '\u{05FF}')]),
+    ("Thai", "th", &[('\u{0E00}', '\u{0E7F}')]),
+    ("Hindi", "hi", &[('\u{0900}', '\u{097F}')]),
+    ("Greek", "el", &[('\u{0370}', '\u{03FF}')]),
+];
+
+// Common words only one of these languages uses, for text in Latin script
+const WORDS: &[(&str, &str, &[&str])] = &[
+    ("English", "en", &[
+        "the", "is", "are", "this", "that", "you", "your", "and", "to", "of", "it", "what", "why", "how", "with",
+        "for", "be", "my", "will", "just", "have", "about", "when", "was", "can", "from", "they", "dont", "don't",
+        "its", "it's", "gonna", "bro",
+    ]),
+    ("Spanish", "es", &[
+        "el", "los", "las", "es", "por", "pero", "como", "esto", "este", "esta", "muy", "más", "qué", "porque",
+        "yo", "del", "al", "lo", "tiene", "hay", "eso", "también", "cuando", "ser", "sí", "hermano", "y",
+    ]),
+    ("Portuguese", "pt", &[
+        "não", "nao", "você", "voce", "isso", "esse", "essa", "muito", "dos", "das", "tem", "mais", "também",
+        "é", "um", "uma", "com", "mas", "cara", "vai", "mano",
+    ]),
+    ("French", "fr", &[
+        "le", "les", "est", "et", "je", "vous", "pas", "une", "des", "du", "ce", "cette", "qui", "avec", "pour",
+        "mais", "sur", "c'est", "très", "elle", "ça", "sont", "frère",
+    ]),
+    ("German", "de", &[
+        "der", "die", "das", "und", "ist", "nicht", "ich", "ein", "eine", "mit", "auf", "für", "sind", "wie",
+        "aber", "auch", "noch", "schon", "den", "dem", "bruder",
+    ]),
+    ("Italian", "it", &[
+        "che", "è", "non", "per", "sono", "questo", "questa", "molto", "perché", "gli", "della", "anche", "ma",
+        "ci", "ho", "hai", "fratello",
+    ]),
+    ("Dutch", "nl", &["het", "een", "niet", "ik", "dat", "van", "zijn", "maar", "ook", "wat", "met", "voor", "dit"]),
+    ("Turkish", "tr", &[
+        "bu", "ve", "bir", "için", "çok", "değil", "ama", "gibi", "daha", "var", "yok", "ben", "sen",
+    ]),
+    ("Indonesian", "id", &[
+        "yang", "dan", "ini", "itu", "tidak", "aku", "saya", "kamu", "ada", "dengan", "untuk", "dari", "bisa",
+        "sudah", "juga", "gak", "ga",
+    ]),
+];
+
+// Common words a Latin-script language needs before it's called, ahead of the runner-up
+const MIN_WORD_HITS: usize = 2;
+
+// Best guess at the language a mention is written in, without a model call.
+// Script gives most languages away; Latin-script ones are told apart by their
+// common words. Handles, links, tickers and addresses are ignored.
+pub struct LanguageDetector;
+
+impl LanguageDetector {
+    fn strip_noise(text: &str) -> String {
+        static NOISE: OnceLock<Regex> = OnceLock::new();
+        let noise = NOISE.get_or_init(|| Regex::new(r"https?://\S+|[@$#]\w+|\b[1-9A-HJ-NP-Za-km-z]{32,44}\b").unwrap());
+        noise.replace_all(text, " ").to_string()
+    }
+
+    // Language name (as in the character settings), or None when there's too little to go on
+    pub fn detect(text: &str) -> Option<&'static str> {
+        let text = Self::strip_noise(text);
+
+        let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+        if letters.is_empty() {
+            return None;
+        }
+        let in_script = |ranges: &[CharRange]| {
+            letters.iter().filter(|c| ranges.iter().any(|(start, end)| (*start..=*end).contains(*c))).count()
+        };
+        // Any kana among the Han characters makes it Japanese
+        let kana = in_script(&[KANA]);
+        if (kana + in_script(&[HAN])) * 3 >= letters.len() {
+            return Some(if kana > 0 { "Japanese" } else { "Chinese" });
+        }
+        let script = SCRIPTS
+            .iter()
+            .map(|(name, _, ranges)| (*name, in_script(ranges)))
+            .find(|(_, count)| *count * 3 >= letters.len());
+        if let Some((name, _)) = script {
+            return Some(name);
+        }
+
+        let words: Vec<String> = text
+            .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let mut scores: Vec<(&'static str, usize)> = WORDS
+            .iter()
+            .map(|(name, _, common)| (*name, words.iter().filter(|word| common.contains(&word.as_str())).count()))
+            .collect();
+        scores.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+        match scores.as_slice() {
+            [(name, best), (_, second), ..] if *best >= MIN_WORD_HITS && best > second => Some(name),
+            _ => None,
+        }
+    }
+
+    // Whether a configured language (name or two-letter code, any case) is this one
+    pub fn is_language(configured: &str, language: &str) -> bool {
+        let configured = configured.trim();
+        if configured.eq_ignore_ascii_case(language) {
+            return true;
+        }
+        let code = SCRIPTS
+            .iter()
+            .map(|(name, code, _)| (*name, *code))
+            .chain([("Japanese", "ja"), ("Chinese", "zh")])
+            .chain(WORDS.iter().map(|(name, code, _)| (*name, *code)))
+            .find(|(name, _)| name.eq_ignore_ascii_case(language))
+            .map(|(_, code)| code);
+        code.is_some_and(|code| configured.eq_ignore_ascii_case(code))
+    }
+}
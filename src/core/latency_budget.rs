@@ -0,0 +1,19 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::FudError;
+
+// Run a stage under its time limit; without one it just runs
+pub async fn within<T, E, F>(stage: &'static str, limit: Option<Duration>, stage_future: F) -> Result<T, FudError>
+where
+    E: Into<FudError>,
+    F: Future<Output = Result<T, E>>,
+{
+    let Some(limit) = limit else {
+        return stage_future.await.map_err(Into::into);
+    };
+    match tokio::time::timeout(limit, stage_future).await {
+        Ok(result) => result.map_err(Into::into),
+        Err(_) => Err(FudError::Timeout { stage, seconds: limit.as_secs() }),
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+
+use super::character::LearnedFactsSettings;
+use super::token_extractor::TokenExtractor;
+use crate::models::TokenFact;
+
+// Anything longer is the model retelling the mention rather than stating a fact
+const MAX_CLAIM_CHARS: usize = 160;
+
+pub struct LearnedFacts;
+
+impl LearnedFacts {
+    // Whether the mention says anything beyond naming a token: tickers, addresses,
+    // @handles and links are left out before measuring it
+    pub fn worth_extracting(text: &str, settings: &LearnedFactsSettings) -> bool {
+        let words: Vec<&str> = text
+            .split_whitespace()
+            .filter(|word| !word.starts_with('@') && !word.starts_with('$') && !word.starts_with("http"))
+            .filter(|word| !TokenExtractor::is_solana_address(word))
+            .collect();
+        words.join(" ").chars().count() >= settings.min_mention_chars
+    }
+
+    // Claims from the model's answer, a JSON array of strings; anything unreadable is no claims
+    pub fn parse(response: &str, max: usize) -> Vec<String> {
+        let (Some(start), Some(end)) = (response.find('['), response.rfind(']')) else {
+            return Vec::new();
+        };
+        if end < start {
+            return Vec::new();
+        }
+        let claims: Vec<String> = serde_json::from_str(&response[start..=end]).unwrap_or_default();
+        let mut kept: Vec<String> = Vec::new();
+        for claim in claims {
+            let claim = claim.trim().trim_end_matches('.').to_string();
+            if claim.is_empty() || claim.chars().count() > MAX_CLAIM_CHARS || kept.iter().any(|k| k.eq_ignore_ascii_case(&claim)) {
+                continue;
+            }
+            kept.push(claim);
+        }
+        kept.truncate(max);
+        kept
+    }
+
+    // Adds the claims not already known for their token, then drops that token's
+    // oldest past `max_per_token`. A known claim from someone new gets them added to
+    // its authors. Returns how many facts were added or changed.
+    pub fn add(facts: &mut Vec<TokenFact>, new: Vec<TokenFact>, max_per_token: usize) -> usize {
+        let mut added = 0;
+        for fact in new {
+            let known = facts
+                .iter_mut()
+                .find(|known| known.mint == fact.mint && known.claim.eq_ignore_ascii_case(&fact.claim));
+            if let Some(known) = known {
+                let before = known.authors.len();
+                for author in fact.authors {
+                    if !known.authors.contains(&author) {
+                        known.authors.push(author);
+                    }
+                }
+                if known.authors.len() > before {
+                    added += 1;
+                }
+                continue;
+            }
+            let mint = fact.mint.clone();
+            facts.push(fact);
+            added += 1;
+
+            let count = facts.iter().filter(|known| known.mint == mint).count();
+            if count > max_per_token {
+                if let Some(oldest) = facts.iter().position(|known| known.mint == mint) {
+                    facts.remove(oldest);
+                }
+            }
+        }
+        added
+    }
+
+    // The token's facts learned since `since` that at least `min_authors` accounts
+    // made, newest first
+    pub fn relevant<'a>(facts: &'a [TokenFact], mint: &str, since: DateTime<Utc>, max: usize, min_authors: usize) -> Vec<&'a TokenFact> {
+        facts
+            .iter()
+            .rev()
+            .filter(|fact| fact.mint == mint && fact.learned_at >= since && fact.authors.len() >= min_authors.max(1))
+            .take(max)
+            .collect()
+    }
+
+    // A section for the token summary. They're hearsay, and the prompt says so.
+    pub fn prompt_section(facts: &[&TokenFact], symbol: &str, now: DateTime<Utc>) -> String {
+        let mut section = format!("What people have told you about ${} in replies (unverified):\n", symbol);
+        for fact in facts {
+            let age = now.signed_duration_since(fact.learned_at);
+            let when = if age.num_days() > 0 {
+                format!("{} days ago", age.num_days())
+            } else {
+                format!("{} hours ago", age.num_hours().max(1))
+            };
+            section.push_str(&format!("- ({}) {}\n", when, fact.claim));
+        }
+        section.push_str("Use these if they fit, as things people are saying rather than confirmed facts.\n");
+        section
+    }
+}
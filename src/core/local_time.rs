@@ -0,0 +1,62 @@
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+
+use super::character::CharacterSettings;
+
+// The character's timezone. Schedule hours and weekdays are read in it, and times in
+// logs, stats and the status endpoint are shown in it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalTime {
+    tz: Tz,
+}
+
+impl Default for LocalTime {
+    fn default() -> Self {
+        LocalTime { tz: Tz::UTC }
+    }
+}
+
+impl LocalTime {
+    // An IANA name such as "America/New_York"; None if chrono-tz doesn't know it
+    pub fn parse(name: &str) -> Option<Self> {
+        name.trim().parse::<Tz>().ok().map(|tz| LocalTime { tz })
+    }
+
+    // UTC when the character sets no timezone, or one the validator already rejected
+    pub fn from_settings(settings: &CharacterSettings) -> Self {
+        settings.timezone.as_deref().and_then(Self::parse).unwrap_or_default()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.tz.name()
+    }
+
+    pub fn is_utc(&self) -> bool {
+        self.tz == Tz::UTC
+    }
+
+    pub fn at(&self, at: DateTime<Utc>) -> DateTime<Tz> {
+        at.with_timezone(&self.tz)
+    }
+
+    pub fn hour(&self, at: DateTime<Utc>) -> u32 {
+        self.at(at).hour()
+    }
+
+    pub fn weekday(&self, at: DateTime<Utc>) -> Weekday {
+        self.at(at).weekday()
+    }
+
+    // "14:30 EDT", or "14:30 UTC" with no timezone set
+    pub fn show(&self, at: DateTime<Utc>) -> String {
+        self.at(at).format("%H:%M %Z").to_string()
+    }
+
+    // "14:30 EDT (18:30 UTC)", for log lines an operator compares against server logs
+    pub fn show_with_utc(&self, at: DateTime<Utc>) -> String {
+        if self.is_utc() {
+            return self.show(at);
+        }
+        format!("{} ({})", self.show(at), at.format("%H:%M UTC"))
+    }
+}
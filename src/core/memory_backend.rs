@@ -0,0 +1,276 @@
+use native_tls::TlsConnector;
+use postgres::Client;
+use postgres_native_tls::MakeTlsConnector;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::config::PostgresConfig;
+use super::kv_store::{KvStore, LogStore, Update};
+use crate::error::{FudError, Result};
+
+// Schema changes, applied in order and recorded in fud_migrations. One that has
+// shipped is never edited; the next change gets the next version.
+pub const MIGRATIONS: [(i32, &str); 1] = [(
+    1,
+    "CREATE TABLE fud_kv (
+        store TEXT NOT NULL,
+        key TEXT NOT NULL,
+        value TEXT NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (store, key)
+    )",
+)];
+
+// Advisory lock held while migrating, so instances starting together take turns
+const MIGRATION_LOCK: i64 = 0x6675_645f_6b76;
+// Used unless the URL sets connect_timeout
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// After a failed connect, calls fail straight away for this long instead of each
+// one waiting on another attempt
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+// Longest a call waits on the connection thread
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+// The migrations a database that has `applied` still needs, in order
+pub fn pending(applied: &[i32]) -> Vec<(i32, &'static str)> {
+    MIGRATIONS.iter().filter(|(version, _)| !applied.contains(version)).copied().collect()
+}
+
+type Job = Box<dyn FnOnce(Option<&mut Client>) + Send>;
+
+fn postgres_error(e: impl Into<anyhow::Error>) -> FudError {
+    FudError::provider("Postgres", e)
+}
+
+// One connection, owned by its own thread. The blocking client can't be called from
+// inside the tokio runtime, so calls are handed to the thread and waited on.
+#[derive(Clone)]
+pub struct PgConnection {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl PgConnection {
+    // Nothing is connected until the first call. A dropped connection is opened again
+    // on the next call after the backoff; calls in between fail.
+    pub fn open(url: &str) -> Self {
+        let (jobs, received) = mpsc::channel::<Job>();
+        let url = url.to_string();
+        thread::Builder::new()
+            .name("fud-postgres".to_string())
+            .spawn(move || {
+                let mut client: Option<Client> = None;
+                let mut retry_at = Instant::now();
+                for job in received {
+                    if client.as_ref().is_none_or(|client| client.is_closed()) && Instant::now() >= retry_at {
+                        client = match Self::connect(&url) {
+                            Ok(client) => Some(client),
+                            Err(e) => {
+                                eprintln!("Postgres: couldn't connect: {}", e);
+                                retry_at = Instant::now() + RECONNECT_BACKOFF;
+                                None
+                            }
+                        };
+                    }
+                    job(client.as_mut());
+                }
+            })
+            .expect("failed to start the Postgres thread");
+        PgConnection { jobs }
+    }
+
+    // sslmode in the URL decides on TLS; by default it's used when the server offers it
+    fn connect(url: &str) -> Result<Client, anyhow::Error> {
+        let tls = MakeTlsConnector::new(TlsConnector::new()?);
+        let mut config: postgres::Config = url.parse()?;
+        if config.get_connect_timeout().is_none() {
+            config.connect_timeout(CONNECT_TIMEOUT);
+        }
+        let mut client = config.connect(tls)?;
+        let applied = Self::migrate(&mut client)?;
+        if applied > 0 {
+            println!("Postgres: applied {} migration(s)", applied);
+        }
+        Ok(client)
+    }
+
+    fn migrate(client: &mut Client) -> Result<usize, postgres::Error> {
+        let mut tx = client.transaction()?;
+        tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK])?;
+        tx.batch_execute(
+            "CREATE TABLE IF NOT EXISTS fud_migrations (
+                version INTEGER PRIMARY KEY,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )?;
+        let applied: Vec<i32> = tx.query("SELECT version FROM fud_migrations", &[])?.iter().map(|row| row.get(0)).collect();
+        let pending = pending(&applied);
+        for (version, sql) in &pending {
+            tx.batch_execute(sql)?;
+            tx.execute("INSERT INTO fud_migrations (version) VALUES ($1)", &[version])?;
+        }
+        tx.commit()?;
+        Ok(pending.len())
+    }
+
+    fn call<T: Send + 'static>(
+        &self,
+        query: impl FnOnce(&mut Client) -> Result<T, postgres::Error> + Send + 'static,
+    ) -> Result<T> {
+        let (reply, answer) = mpsc::channel();
+        let job: Job = Box::new(move |client| {
+            let result = match client {
+                Some(client) => query(client).map_err(postgres_error),
+                None => Err(postgres_error(anyhow::anyhow!("not connected"))),
+            };
+            let _ = reply.send(result);
+        });
+        self.jobs.send(job).map_err(|_| postgres_error(anyhow::anyhow!("connection thread stopped")))?;
+        let wait = || {
+            answer.recv_timeout(CALL_TIMEOUT).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => postgres_error(anyhow::anyhow!("no answer in {}s", CALL_TIMEOUT.as_secs())),
+                mpsc::RecvTimeoutError::Disconnected => postgres_error(anyhow::anyhow!("connection thread stopped")),
+            })
+        };
+        // Stores are called from async code; on the multi-threaded runtime the wait
+        // gives up its worker so other tasks keep running meanwhile
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(wait)?
+            }
+            _ => wait()?,
+        }
+    }
+
+    // Connects (and migrates) now rather than on the first store call
+    pub fn check(&self) -> Result<()> {
+        self.call(|client| client.simple_query("SELECT 1").map(|_| ()))
+    }
+
+    pub fn store(&self, name: &str) -> PgStore {
+        PgStore { connection: self.clone(), store: name.to_string() }
+    }
+}
+
+// A KvStore as the rows of fud_kv with one `store` name, shared by every instance
+// on the database. Reads that fail are logged and read as empty.
+pub struct PgStore {
+    connection: PgConnection,
+    store: String,
+}
+
+impl PgStore {
+    fn read<T>(&self, what: &str, result: Result<T>, fallback: T) -> T {
+        result.unwrap_or_else(|e| {
+            eprintln!("Failed to read {} from {}: {}", what, self.store, e);
+            fallback
+        })
+    }
+}
+
+impl KvStore for PgStore {
+    fn get(&self, key: &str) -> Option<String> {
+        let (store, key) = (self.store.clone(), key.to_string());
+        let found = self.connection.call(move |client| {
+            let row = client.query_opt("SELECT value FROM fud_kv WHERE store = $1 AND key = $2", &[&store, &key])?;
+            Ok(row.map(|row| row.get(0)))
+        });
+        self.read("a key", found, None)
+    }
+
+    fn insert(&mut self, key: &str, value: &str) -> Result<()> {
+        let (store, key, value) = (self.store.clone(), key.to_string(), value.to_string());
+        self.connection.call(move |client| {
+            client.execute(
+                "INSERT INTO fud_kv (store, key, value) VALUES ($1, $2, $3)
+                 ON CONFLICT (store, key) DO UPDATE SET value = EXCLUDED.value, updated_at = now()",
+                &[&store, &key, &value],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn remove(&mut self, key: &str) -> Result<bool> {
+        let (store, key) = (self.store.clone(), key.to_string());
+        self.connection.call(move |client| {
+            Ok(client.execute("DELETE FROM fud_kv WHERE store = $1 AND key = $2", &[&store, &key])? > 0)
+        })
+    }
+
+    // The row is locked until the update commits, so two instances claiming the same
+    // key take turns and the second sees what the first wrote. A key that has no row
+    // yet can't be locked; the insert then only goes through if nobody beat us to it.
+    fn update(&mut self, key: &str, update: Update) -> Result<bool> {
+        let (store, key) = (self.store.clone(), key.to_string());
+        self.connection.call(move |client| {
+            let mut tx = client.transaction()?;
+            let current: Option<String> = tx
+                .query_opt("SELECT value FROM fud_kv WHERE store = $1 AND key = $2 FOR UPDATE", &[&store, &key])?
+                .map(|row| row.get(0));
+            let Some(value) = update(current.as_deref()) else {
+                return Ok(false);
+            };
+            let written = match current {
+                Some(_) => tx.execute(
+                    "UPDATE fud_kv SET value = $3, updated_at = now() WHERE store = $1 AND key = $2",
+                    &[&store, &key, &value],
+                )?,
+                None => tx.execute(
+                    "INSERT INTO fud_kv (store, key, value) VALUES ($1, $2, $3) ON CONFLICT (store, key) DO NOTHING",
+                    &[&store, &key, &value],
+                )?,
+            };
+            tx.commit()?;
+            Ok(written > 0)
+        })
+    }
+
+    // In byte order, the same as LogStore, so time keys come out oldest first
+    fn entries(&self) -> Vec<(String, String)> {
+        let store = self.store.clone();
+        let entries = self.connection.call(move |client| {
+            let rows = client.query("SELECT key, value FROM fud_kv WHERE store = $1 ORDER BY key COLLATE \"C\"", &[&store])?;
+            Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+        });
+        self.read("entries", entries, Vec::new())
+    }
+
+    fn len(&self) -> usize {
+        let store = self.store.clone();
+        let count = self.connection.call(move |client| {
+            let count: i64 = client.query_one("SELECT count(*) FROM fud_kv WHERE store = $1", &[&store])?.get(0);
+            Ok(count as usize)
+        });
+        self.read("the size", count, 0)
+    }
+}
+
+// Where the state that has to be shared between instances lives: the responded index,
+// phrase history and token heat. Files under storage/ by default; with Postgres,
+// every instance on the database reads and writes the same rows.
+#[derive(Clone)]
+pub enum MemoryBackend {
+    Files,
+    Postgres(PgConnection),
+}
+
+impl MemoryBackend {
+    pub fn from_config(postgres: Option<&PostgresConfig>) -> Self {
+        match postgres {
+            Some(postgres) => MemoryBackend::Postgres(PgConnection::open(postgres.url.expose())),
+            None => MemoryBackend::Files,
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        matches!(self, MemoryBackend::Postgres(_))
+    }
+
+    // The named store: the file `file` opens, or the store's rows on Postgres
+    pub fn open(&self, name: &str, file: impl FnOnce() -> Result<LogStore>) -> Result<Box<dyn KvStore>> {
+        match self {
+            MemoryBackend::Files => Ok(Box::new(file()?)),
+            MemoryBackend::Postgres(connection) => Ok(Box::new(connection.store(name))),
+        }
+    }
+}
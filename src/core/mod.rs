@@ -1,5 +1,67 @@
+pub mod adaptive_schedule;
 pub mod agent;
+pub mod aggression;
+pub mod approval;
+pub mod audit;
+pub mod backtest;
+pub mod bundle_check;
+pub mod callbacks;
+pub mod campaign;
 pub mod characteristics;
+pub mod clock;
+pub mod config;
+pub mod consistency;
+pub mod content;
+pub mod content_buffer;
+pub mod content_selector;
+pub mod dataset;
+pub mod drafts;
+pub mod export;
+pub mod feature_flags;
+pub mod follow_up;
+pub mod health;
+pub mod inline_fud;
 pub mod instruction_builder;
+pub mod kv_store;
+pub mod language_detect;
+pub mod latency_budget;
+pub mod learned_facts;
+pub mod local_time;
+pub mod memory_backend;
+pub mod outbox;
+pub mod paths;
+pub mod phrase_tracker;
+pub mod pipeline;
+pub mod predictions;
+pub mod price_check;
+pub mod prompt_budget;
+pub mod quota;
+pub mod rate_limiter;
+pub mod reply_limits;
+pub mod reply_policy;
+pub mod reply_rules;
+pub mod responded;
 pub mod runtime;
+pub mod sanitizer;
+pub mod setup;
+pub mod spam_filter;
+pub mod stats;
+pub mod status;
+pub mod storage;
+pub mod stream_guard;
+pub mod target_scorer;
+pub mod telemetry;
+pub mod timeline_sync;
+pub mod style;
+pub mod token_extractor;
+pub mod token_heat;
+pub mod token_lookup;
+pub mod tweet_preview;
 pub mod character;
+pub mod character_bundle;
+pub mod character_switch;
+pub mod validator;
+pub mod weekly_recap;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,292 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use super::character::OutboxSettings;
+use super::content::Post;
+use crate::error::FudError;
+use crate::models::{NewsReaction, Prediction, SpaceComment, TokenSnapshot};
+use crate::providers::solanatracker::TokenResponse;
+
+pub const DEFAULT_OUTBOX_DIR: &str = "./storage/outbox";
+
+// A post written to disk before it's sent, so it survives Twitter erroring or the
+// bot dying. Delivered items keep their tweet ID until they're pruned.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboxItem {
+    pub id: String,
+    // What kind of post it is, for the logs
+    pub label: String,
+    pub text: String,
+    // The image is kept next to the item as <id>.img
+    pub has_image: bool,
+    pub context: Option<String>,
+    pub prediction: Option<Prediction>,
+    pub news: Option<NewsReaction>,
+    pub space: Option<SpaceComment>,
+    pub chart_image: Option<String>,
+    #[serde(default)]
+    pub reaction_clip: Option<String>,
+    // Token the post FUDs; looked up again when a retry goes out
+    pub target_mint: Option<String>,
+    // The token's numbers when the text was written
+    #[serde(default)]
+    pub target_snapshot: Option<TokenSnapshot>,
+    // Written ahead of its slot and held until a scheduled post takes it
+    #[serde(default)]
+    pub buffered: bool,
+    pub created_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub twitter_id: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    // Out of attempts, or too old to still be worth posting
+    pub abandoned: bool,
+}
+
+impl OutboxItem {
+    pub fn new(label: &str, post: &Post, now: DateTime<Utc>) -> Self {
+        OutboxItem {
+            id: format!("{}-{:04x}", now.format("%Y%m%dT%H%M%S%3f"), rand::random::<u16>()),
+            label: label.to_string(),
+            text: post.text.clone(),
+            has_image: post.image.is_some(),
+            context: post.context.clone(),
+            prediction: post.prediction.clone(),
+            news: post.news.clone(),
+            space: post.space.clone(),
+            chart_image: post.chart_image.clone(),
+            reaction_clip: post.reaction_clip.clone(),
+            target_mint: post
+                .target
+                .as_ref()
+                .map(|target| target.token.mint.clone())
+                .filter(|mint| !mint.is_empty()),
+            target_snapshot: post.target.as_ref().map(|target| target.snapshot()),
+            buffered: false,
+            created_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            twitter_id: None,
+            delivered_at: None,
+            abandoned: false,
+        }
+    }
+
+    // The post to send again, with the image and token read back in
+    pub fn to_post(&self, image: Option<Vec<u8>>, target: Option<TokenResponse>) -> Post {
+        Post {
+            text: self.text.clone(),
+            image,
+            target,
+            context: self.context.clone(),
+            prediction: self.prediction.clone(),
+            news: self.news.clone(),
+            space: self.space.clone(),
+            chart_image: self.chart_image.clone(),
+            reaction_clip: self.reaction_clip.clone(),
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.twitter_id.is_none() && !self.abandoned
+    }
+
+    // Buffered posts wait for their slot instead
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.is_pending() && !self.buffered && self.next_attempt_at <= now
+    }
+
+    pub fn is_stale(&self, settings: &OutboxSettings, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.created_at) >= Duration::hours(settings.max_age_hours)
+    }
+
+    pub fn mark_delivered(&mut self, twitter_id: &str, now: DateTime<Utc>) {
+        self.twitter_id = Some(twitter_id.to_string());
+        self.delivered_at = Some(now);
+    }
+
+    // Counts a failed attempt and pushes the next one back, or gives up
+    pub fn mark_failed(&mut self, error: &str, settings: &OutboxSettings, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(error.to_string());
+        if self.attempts >= settings.max_attempts || self.is_stale(settings, now) {
+            self.abandoned = true;
+        } else {
+            self.next_attempt_at = now + Self::backoff(self.attempts, settings);
+        }
+    }
+
+    // Doubles from base_backoff_minutes with each failed attempt, up to max_backoff_minutes
+    pub fn backoff(attempts: u32, settings: &OutboxSettings) -> Duration {
+        let base = settings.base_backoff_minutes.max(1);
+        let factor = 1i64 << attempts.saturating_sub(1).min(16);
+        Duration::minutes(base.saturating_mul(factor).min(settings.max_backoff_minutes.max(base)))
+    }
+
+    // Delivered or abandoned long enough ago to drop
+    pub fn is_expired(&self, keep: Duration, now: DateTime<Utc>) -> bool {
+        let finished = match self.delivered_at {
+            Some(at) => at,
+            None if self.abandoned => self.next_attempt_at.max(self.created_at),
+            None => return false,
+        };
+        now.signed_duration_since(finished) >= keep
+    }
+}
+
+// Items as last read, with the directory's modification time then
+type Cached = Option<(SystemTime, Vec<OutboxItem>)>;
+
+// The on-disk queue in storage/outbox/: one JSON file per post, written whole to a
+// temporary file and renamed so a crash never leaves half an item behind. Items are
+// kept in memory and only read again once the directory changes under them, e.g.
+// when the CLI promotes a draft.
+pub struct Outbox {
+    dir: PathBuf,
+    cache: Mutex<Cached>,
+}
+
+impl Outbox {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Outbox { dir: dir.as_ref().to_path_buf(), cache: Mutex::new(None) }
+    }
+
+    fn cache(&self) -> MutexGuard<'_, Cached> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn dir_stamp(&self) -> Option<SystemTime> {
+        fs::metadata(&self.dir).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    // Applies a write this outbox just made to the cached items, if they were current
+    // before it (`before` is the directory's time then); otherwise the next load reads
+    // the directory again
+    fn written(&self, before: Option<SystemTime>, change: impl FnOnce(&mut Vec<OutboxItem>)) {
+        let mut cache = self.cache();
+        let after = self.dir_stamp();
+        match (cache.as_mut(), before, after) {
+            (Some((seen, items)), Some(before), Some(after)) if *seen == before => {
+                change(items);
+                *seen = after;
+            }
+            _ => *cache = None,
+        }
+    }
+
+    fn sort(items: &mut [OutboxItem]) {
+        items.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    }
+
+    fn item_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn image_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.img", id))
+    }
+
+    fn write(path: &Path, bytes: &[u8]) -> Result<(), FudError> {
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    // Adds a new item, image first so the item never points at a missing file
+    pub fn enqueue(&self, item: &OutboxItem, image: Option<&[u8]>) -> Result<(), FudError> {
+        fs::create_dir_all(&self.dir)?;
+        if let Some(image) = image {
+            let before = self.dir_stamp();
+            Self::write(&self.image_path(&item.id), image)?;
+            self.written(before, |_| {});
+        }
+        self.save(item)
+    }
+
+    pub fn save(&self, item: &OutboxItem) -> Result<(), FudError> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_vec_pretty(item).map_err(|e| FudError::parse("outbox item", e))?;
+        let before = self.dir_stamp();
+        Self::write(&self.item_path(&item.id), &json)?;
+        self.written(before, |items| {
+            match items.iter_mut().find(|known| known.id == item.id) {
+                Some(known) => *known = item.clone(),
+                None => items.push(item.clone()),
+            }
+            Self::sort(items);
+        });
+        Ok(())
+    }
+
+    // Every item, oldest first, from memory unless the directory has changed
+    pub fn load(&self) -> Result<Vec<OutboxItem>, FudError> {
+        let stamp = self.dir_stamp();
+        let mut cache = self.cache();
+        if let (Some(stamp), Some((seen, items))) = (stamp, cache.as_ref()) {
+            if *seen == stamp {
+                return Ok(items.clone());
+            }
+        }
+        let items = self.read_all()?;
+        *cache = stamp.map(|stamp| (stamp, items.clone()));
+        Ok(items)
+    }
+
+    // Unreadable files are reported and left alone
+    fn read_all(&self) -> Result<Vec<OutboxItem>, FudError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut items = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            match fs::read(&path).map_err(FudError::from).and_then(|bytes| {
+                serde_json::from_slice::<OutboxItem>(&bytes).map_err(|e| FudError::parse("outbox item", e))
+            }) {
+                Ok(item) => items.push(item),
+                Err(e) => eprintln!("Skipping outbox file {}: {}", path.display(), e),
+            }
+        }
+        Self::sort(&mut items);
+        Ok(items)
+    }
+
+    pub fn image(&self, item: &OutboxItem) -> Option<Vec<u8>> {
+        if !item.has_image {
+            return None;
+        }
+        fs::read(self.image_path(&item.id)).ok()
+    }
+
+    // Drops the item and its image; the image goes as soon as the post is finished with
+    pub fn remove(&self, item: &OutboxItem) -> Result<(), FudError> {
+        self.remove_image(item)?;
+        let before = self.dir_stamp();
+        match fs::remove_file(self.item_path(&item.id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        self.written(before, |items| items.retain(|known| known.id != item.id));
+        Ok(())
+    }
+
+    pub fn remove_image(&self, item: &OutboxItem) -> Result<(), FudError> {
+        let before = self.dir_stamp();
+        match fs::remove_file(self.image_path(&item.id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        self.written(before, |_| {});
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::character::CharacterSettings;
+use super::instruction_builder::InstructionBuilder;
+use super::validator::{CharacterValidator, ValidationReport};
+use crate::media::{chart_images::ChartImages, reaction_clips::ReactionClips, token_card::TokenCard};
+
+pub const HOME_VAR: &str = "FUD_HOME";
+
+// Where storage/ and characters/ live: FUD_HOME, or the working directory when it's
+// unset. In a container this is the mounted volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    home: PathBuf,
+}
+
+impl Layout {
+    pub fn new<P: AsRef<Path>>(home: P) -> Self {
+        Layout { home: home.as_ref().to_path_buf() }
+    }
+
+    pub fn from_env() -> Self {
+        match env::var(HOME_VAR) {
+            Ok(home) if !home.trim().is_empty() => Self::new(home.trim()),
+            _ => Self::new("."),
+        }
+    }
+
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    // A default like "./storage/outbox", or a path from a setting, under the base
+    // directory. Absolute paths are left alone.
+    pub fn resolve<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let path = path.as_ref();
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        self.home.join(path.strip_prefix(".").unwrap_or(path))
+    }
+
+    pub fn storage_dir(&self) -> PathBuf {
+        self.home.join("storage")
+    }
+
+    pub fn characters_dir(&self) -> PathBuf {
+        self.home.join("characters")
+    }
+
+    pub fn character_file(&self, character_name: &str) -> PathBuf {
+        self.characters_dir().join(character_name).join("character.json")
+    }
+
+    // Checks the layout the bot expects before it's started against it: errors for
+    // what stops it from running, warnings for what only switches something off
+    pub fn doctor(&self, character_name: Option<&str>) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        if !self.home.is_dir() {
+            report.errors.push(format!("{} {} is not a directory", HOME_VAR, self.home.display()));
+            return report;
+        }
+        let settings = self.check_character(character_name, &mut report);
+        self.check_storage(&mut report);
+        self.check_media(&settings, &mut report);
+        report
+    }
+
+    fn check_character(&self, character_name: Option<&str>, report: &mut ValidationReport) -> CharacterSettings {
+        let characters = self.characters_dir();
+        if !characters.is_dir() {
+            report.errors.push(format!("{} is missing", characters.display()));
+            return CharacterSettings::default();
+        }
+        let Some(name) = character_name else {
+            report.warnings.push("CHARACTER_NAME is not set; not checking a character".to_string());
+            return CharacterSettings::default();
+        };
+        let path = self.character_file(name);
+        let mut instruction_builder = InstructionBuilder::new();
+        let loaded = InstructionBuilder::load_character_file(&path)
+            .and_then(|character| instruction_builder.build_instructions_from_file(&path).map(|_| character));
+        match loaded {
+            Ok(character) => {
+                let found = CharacterValidator::validate(&character, instruction_builder.get_instructions());
+                report.errors.extend(found.errors.into_iter().map(|e| format!("{}: {}", name, e)));
+                report.warnings.extend(found.warnings.into_iter().map(|w| format!("{}: {}", name, w)));
+                instruction_builder.get_character_settings().clone()
+            }
+            Err(e) => {
+                report.errors.push(format!("couldn't load {}: {}", path.display(), e));
+                CharacterSettings::default()
+            }
+        }
+    }
+
+    fn check_storage(&self, report: &mut ValidationReport) {
+        let storage = self.storage_dir();
+        if let Err(e) = fs::create_dir_all(&storage) {
+            report.errors.push(format!("couldn't create {}: {}", storage.display(), e));
+            return;
+        }
+        let probe = storage.join(".doctor");
+        if let Err(e) = fs::write(&probe, b"ok").and_then(|_| fs::remove_file(&probe)) {
+            report.errors.push(format!("{} is not writable: {}", storage.display(), e));
+            return;
+        }
+        let memory = storage.join("memory.json");
+        if let Ok(data) = fs::read_to_string(&memory) {
+            if let Err(e) = serde_json::from_str::<crate::models::Memory>(&data) {
+                report.errors.push(format!("{} doesn't parse: {}", memory.display(), e));
+            }
+        }
+    }
+
+    fn check_media(&self, settings: &CharacterSettings, report: &mut ValidationReport) {
+        let charts = self.resolve(ChartImages::DIR);
+        if !charts.is_dir() || ChartImages::list(&charts).is_err() {
+            report.warnings.push(format!("no PNGs in {}; FUD posts go out without a stock chart", charts.display()));
+        }
+        let font = self.resolve(TokenCard::FONT_PATH);
+        if !font.is_file() {
+            report.warnings.push(format!("{} is missing; token cards are drawn without captions", font.display()));
+        }
+        let clips = self.resolve(ReactionClips::DIR);
+        if settings.reaction_clips.enabled && !clips.is_dir() {
+            report.warnings.push(format!("reaction_clips is enabled but {} is missing", clips.display()));
+        }
+    }
+}
+
+// `path` under the base directory read from FUD_HOME
+pub fn resolve<P: AsRef<Path>>(path: P) -> PathBuf {
+    Layout::from_env().resolve(path)
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+
+// Words per phrase when checking posts for repetition
+const PHRASE_WORDS: usize = 3;
+
+// Recently posted n-word phrases, evicted oldest-first by count and by age
+pub struct PhraseTracker {
+    phrases: VecDeque<(String, DateTime<Utc>)>,
+    counts: HashMap<String, usize>,
+    phrase_words: usize,
+    max_phrases: usize,
+    max_age: Duration,
+}
+
+impl PhraseTracker {
+    pub fn new(max_phrases: usize, max_age: Duration) -> Self {
+        Self::with_phrase_words(PHRASE_WORDS, max_phrases, max_age)
+    }
+
+    // A single-word tracker doubles as a decaying word frequency table
+    pub fn with_phrase_words(phrase_words: usize, max_phrases: usize, max_age: Duration) -> Self {
+        Self {
+            phrases: VecDeque::new(),
+            counts: HashMap::new(),
+            phrase_words: phrase_words.max(1),
+            max_phrases,
+            max_age,
+        }
+    }
+
+    // Lowercased, with punctuation stuck to the ends of words dropped
+    pub fn words_in(text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '$').to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect()
+    }
+
+    fn phrases_in(&self, text: &str) -> Vec<String> {
+        Self::words_in(text)
+            .windows(self.phrase_words)
+            .map(|window| window.join(" "))
+            .collect()
+    }
+
+    // Copies of the phrase still inside the window
+    pub fn count(&self, phrase: &str) -> usize {
+        self.counts.get(&phrase.to_lowercase()).copied().unwrap_or(0)
+    }
+
+    // Whether the text reuses any phrase still inside the window
+    pub fn contains_recent(&mut self, text: &str) -> bool {
+        self.contains_recent_at(text, Utc::now())
+    }
+
+    pub fn contains_recent_at(&mut self, text: &str, now: DateTime<Utc>) -> bool {
+        self.expire(now);
+        self.phrases_in(text)
+            .iter()
+            .any(|phrase| self.counts.contains_key(phrase))
+    }
+
+    // Remember the phrases of a text we just posted
+    pub fn record(&mut self, text: &str) {
+        self.record_at(text, Utc::now());
+    }
+
+    pub fn record_at(&mut self, text: &str, now: DateTime<Utc>) {
+        for phrase in self.phrases_in(text) {
+            *self.counts.entry(phrase.clone()).or_insert(0) += 1;
+            self.phrases.push_back((phrase, now));
+        }
+        self.expire(now);
+    }
+
+    // Drop phrases past the max age, then the oldest ones over the size limit
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - self.max_age;
+        while let Some((_, recorded_at)) = self.phrases.front() {
+            if *recorded_at > cutoff && self.phrases.len() <= self.max_phrases {
+                break;
+            }
+            if let Some((phrase, _)) = self.phrases.pop_front() {
+                self.forget(&phrase);
+            }
+        }
+    }
+
+    fn forget(&mut self, phrase: &str) {
+        if let Some(count) = self.counts.get_mut(phrase) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(phrase);
+            }
+        }
+    }
+}
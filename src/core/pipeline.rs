@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+
+use super::agent::{Agent, GenerationTask};
+use super::character::PipelineSettings;
+use super::sanitizer::Sanitizer;
+use super::stream_guard::StreamGuard;
+use super::tweet_preview::TweetPreview;
+
+pub const STAGE_NAMES: [&str; 6] = ["sanitize", "safety", "trim", "style", "length", "critique"];
+
+// When a stage runs: on every text the model writes, or only on text headed for a
+// post (FUD, news reactions, recaps and the like)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Completion,
+    Post,
+}
+
+// One step between the model's answer and the text the runtime posts
+#[async_trait]
+pub trait Stage: Send + Sync {
+    fn name(&self) -> &str;
+    fn phase(&self) -> Phase;
+    async fn apply(&self, agent: &Agent, task: GenerationTask, text: String) -> Result<String, anyhow::Error>;
+}
+
+// Strips fences, "Here's a tweet:" preambles and trailing commentary
+pub struct Sanitize;
+
+#[async_trait]
+impl Stage for Sanitize {
+    fn name(&self) -> &str {
+        "sanitize"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Completion
+    }
+
+    async fn apply(&self, _agent: &Agent, _task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        Ok(Sanitizer::clean(&text))
+    }
+}
+
+// Refuses text with a banned term in it, including the aggression dial's tame words.
+// Streamed completions are already cut off at one; this catches the rest.
+pub struct Safety;
+
+#[async_trait]
+impl Stage for Safety {
+    fn name(&self) -> &str {
+        "safety"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Completion
+    }
+
+    async fn apply(&self, agent: &Agent, task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        if let Some(guard) = agent.guard_for(task) {
+            if let Some(term) = guard.violation(&text) {
+                return Err(anyhow::anyhow!("Generation used banned term '{}'", term));
+            }
+        }
+        Ok(text)
+    }
+}
+
+pub struct Trim;
+
+#[async_trait]
+impl Stage for Trim {
+    fn name(&self) -> &str {
+        "trim"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Post
+    }
+
+    async fn apply(&self, _agent: &Agent, _task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        Ok(text.trim().to_string())
+    }
+}
+
+// Swaps out the model's stock filler through the shared style engine
+pub struct Style;
+
+#[async_trait]
+impl Stage for Style {
+    fn name(&self) -> &str {
+        "style"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Post
+    }
+
+    async fn apply(&self, agent: &Agent, _task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        Ok(agent.style().vary(&text))
+    }
+}
+
+// Cuts an overlong post back to the last whole sentence that fits in a tweet, instead
+// of having the runtime throw it away and generate another
+pub struct Length;
+
+impl Length {
+    pub fn shorten(text: &str) -> String {
+        if TweetPreview::new(text).fits() {
+            return text.to_string();
+        }
+        text.char_indices()
+            .rev()
+            .filter(|(_, c)| matches!(c, '.' | '!' | '?' | '\n'))
+            .map(|(i, c)| text[..i + c.len_utf8()].trim_end())
+            .find(|cut| !cut.is_empty() && TweetPreview::new(cut).fits())
+            .unwrap_or(text)
+            .to_string()
+    }
+}
+
+#[async_trait]
+impl Stage for Length {
+    fn name(&self) -> &str {
+        "length"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Post
+    }
+
+    async fn apply(&self, _agent: &Agent, _task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        Ok(Self::shorten(&text))
+    }
+}
+
+// Has the model review its own draft and rewrite it; one extra call per post
+pub struct Critique;
+
+impl Critique {
+    // The rewrite, unless it's empty or brings in a term the draft's task is guarded
+    // against. The rewrite comes after the safety stage, so it's checked here instead.
+    pub fn accept(guard: Option<&StreamGuard>, draft: String, revised: String) -> String {
+        if revised.is_empty() {
+            return draft;
+        }
+        if let Some(term) = guard.and_then(|guard| guard.violation(&revised)) {
+            eprintln!("Critique used banned term '{}', keeping the draft", term);
+            return draft;
+        }
+        revised
+    }
+}
+
+#[async_trait]
+impl Stage for Critique {
+    fn name(&self) -> &str {
+        "critique"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Post
+    }
+
+    async fn apply(&self, agent: &Agent, task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        let revised = Sanitizer::clean(&agent.critique(&text).await?);
+        Ok(Self::accept(agent.guard_for(task).as_ref(), text, revised))
+    }
+}
+
+// The stages configured for a character, run in order within each phase
+pub struct Pipeline {
+    stages: Vec<Box<dyn Stage>>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::from_settings(&PipelineSettings::default())
+    }
+}
+
+impl Pipeline {
+    pub fn stage(name: &str) -> Option<Box<dyn Stage>> {
+        let stage: Box<dyn Stage> = match name {
+            "sanitize" => Box::new(Sanitize),
+            "safety" => Box::new(Safety),
+            "trim" => Box::new(Trim),
+            "style" => Box::new(Style),
+            "length" => Box::new(Length),
+            "critique" => Box::new(Critique),
+            _ => return None,
+        };
+        Some(stage)
+    }
+
+    // Unknown names are skipped; the validator reports them at startup
+    pub fn from_settings(settings: &PipelineSettings) -> Self {
+        Pipeline {
+            stages: settings.stages.iter().filter_map(|name| Self::stage(name)).collect(),
+        }
+    }
+
+    // Adds a stage after the configured ones, e.g. a translation step
+    pub fn with_stage(mut self, stage: Box<dyn Stage>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    // Runs the phase's stages over `text`, recording each change against the latest completion
+    pub async fn run(&self, agent: &Agent, task: GenerationTask, phase: Phase, text: String) -> Result<String, anyhow::Error> {
+        let mut text = text;
+        for stage in self.stages.iter().filter(|stage| stage.phase() == phase) {
+            let next = stage.apply(agent, task, text.clone()).await?;
+            agent.audit_transform(stage.name(), &text, &next);
+            text = next;
+        }
+        Ok(text)
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::models::{Prediction, PredictionOutcome};
+use crate::providers::solanatracker::SolanaTracker;
+
+// A dead token can drop off the API; after this long past the deadline the call
+// is graded on the lowest market cap seen instead of waiting forever
+const GRADE_WITHOUT_DATA_HOURS: i64 = 24;
+
+impl Prediction {
+    // Call the token under `drop_pct` below its current market cap within `hours`
+    pub fn call(mint: &str, symbol: &str, market_cap: f64, drop_pct: f64, hours: i64, now: DateTime<Utc>) -> Self {
+        Prediction {
+            mint: mint.to_string(),
+            symbol: symbol.to_string(),
+            tweet_id: None,
+            made_at: now,
+            deadline: now + Duration::hours(hours),
+            start_market_cap: market_cap,
+            target_market_cap: round_figure(market_cap * (1.0 - drop_pct.clamp(0.0, 99.0) / 100.0)),
+            lowest_market_cap: market_cap,
+            outcome: None,
+        }
+    }
+
+    // "$92K"; the tweet has to contain this for the call to count
+    pub fn target_figure(&self) -> String {
+        SolanaTracker::format_currency(self.target_market_cap)
+            .replace(".0K", "K")
+            .replace(".0M", "M")
+            .replace(".0B", "B")
+    }
+
+    // "sub-$92K mcap within 72h", the exact claim the tweet has to make
+    pub fn claim(&self) -> String {
+        format!(
+            "sub-{} mcap within {}h",
+            self.target_figure(),
+            self.deadline.signed_duration_since(self.made_at).num_hours()
+        )
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    // A market cap checked at `at`. Only ones from before the deadline count toward
+    // the call; the first check after it is usually the one that grades it.
+    pub fn observe(&mut self, market_cap: f64, at: DateTime<Utc>) {
+        if at <= self.deadline {
+            self.lowest_market_cap = self.lowest_market_cap.min(market_cap);
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.is_open() && now >= self.deadline
+    }
+
+    // Whether an unreachable token has been gone long enough to grade on what was seen
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.is_open() && now >= self.deadline + Duration::hours(GRADE_WITHOUT_DATA_HOURS)
+    }
+
+    // Hit if the market cap went under the target at any check while the call was open
+    pub fn grade(&mut self, final_market_cap: Option<f64>, now: DateTime<Utc>) -> &PredictionOutcome {
+        if let Some(market_cap) = final_market_cap {
+            self.observe(market_cap, now);
+        }
+        self.outcome.insert(PredictionOutcome {
+            hit: self.lowest_market_cap <= self.target_market_cap,
+            graded_at: now,
+            final_market_cap: final_market_cap.unwrap_or(self.lowest_market_cap),
+        })
+    }
+
+    // Real numbers for the result tweet
+    pub fn result_info(&self) -> String {
+        let Some(outcome) = &self.outcome else {
+            return String::new();
+        };
+        format!(
+            "Call: ${} {}\nMarket cap when called: {}\nLowest market cap seen: {}\nMarket cap now: {}\nResult: {}\n",
+            self.symbol,
+            self.claim(),
+            SolanaTracker::format_currency(self.start_market_cap),
+            SolanaTracker::format_currency(self.lowest_market_cap),
+            SolanaTracker::format_currency(outcome.final_market_cap),
+            if outcome.hit { "called it" } else { "missed" },
+        )
+    }
+}
+
+// Two significant figures, so calls read like "$92K" rather than "$91,537"
+fn round_figure(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let magnitude = 10f64.powi(value.log10().floor() as i32 - 1);
+    (value / magnitude).round() * magnitude
+}
+
+// Graded calls so far
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct PredictionRecord {
+    pub wins: usize,
+    pub losses: usize,
+    pub open: usize,
+}
+
+impl PredictionRecord {
+    pub fn from_predictions(predictions: &[Prediction]) -> Self {
+        predictions.iter().fold(PredictionRecord::default(), |mut record, prediction| {
+            match &prediction.outcome {
+                Some(outcome) if outcome.hit => record.wins += 1,
+                Some(_) => record.losses += 1,
+                None => record.open += 1,
+            }
+            record
+        })
+    }
+
+    // Appended to every result tweet
+    pub fn line(&self) -> String {
+        format!("prediction record: {}-{}", self.wins, self.losses)
+    }
+}
@@ -0,0 +1,54 @@
+use super::character::{MismatchAction, PriceCheckSettings};
+use crate::providers::solanatracker::SolanaTracker;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceCheck {
+    Agree,
+    // How many times larger the bigger figure is
+    Mismatch { ratio: f64 },
+    // One side had no usable figure
+    Unverified,
+}
+
+impl PriceCheck {
+    pub fn compare(ours: f64, theirs: Option<f64>, max_ratio: f64) -> PriceCheck {
+        let Some(theirs) = theirs.filter(|value| *value > 0.0) else {
+            return PriceCheck::Unverified;
+        };
+        if ours <= 0.0 {
+            return PriceCheck::Unverified;
+        }
+        let ratio = ours.max(theirs) / ours.min(theirs);
+        if ratio > max_ratio.max(1.0) {
+            PriceCheck::Mismatch { ratio }
+        } else {
+            PriceCheck::Agree
+        }
+    }
+
+    // Rewrite the token summary handed to the model according to the check
+    pub fn apply(&self, summary: &str, ours: f64, theirs: Option<f64>, settings: &PriceCheckSettings) -> String {
+        let PriceCheck::Mismatch { ratio } = self else {
+            return summary.to_string();
+        };
+        match settings.on_mismatch {
+            MismatchAction::SkipNumbers => {
+                let mut kept: String = summary
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with("Market Cap:"))
+                    .map(|line| format!("{}\n", line))
+                    .collect();
+                kept.push_str("Market cap data is unreliable for this token; don't quote a market cap or price.\n");
+                kept
+            }
+            MismatchAction::Mock => format!(
+                "{}Data sources can't agree on this token: one says {} market cap, another says {} ({:.0}x apart). \
+                 Mock the discrepancy instead of quoting either as fact.\n",
+                summary,
+                SolanaTracker::format_currency(ours),
+                SolanaTracker::format_currency(theirs.unwrap_or_default()),
+                ratio,
+            ),
+        }
+    }
+}
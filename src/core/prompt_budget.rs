@@ -0,0 +1,83 @@
+// Rough chars-per-token ratio for English prose; close enough for budgeting
+const CHARS_PER_TOKEN: usize = 4;
+
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(CHARS_PER_TOKEN)
+}
+
+// One block of the character preamble. Sections without a priority (base and
+// suffix instructions) are never trimmed; the rest go lowest priority first.
+#[derive(Debug, Clone)]
+pub struct PromptSection {
+    pub header: Option<String>,
+    pub lines: Vec<String>,
+    pub priority: Option<u8>,
+}
+
+impl PromptSection {
+    pub fn fixed(text: &str) -> Self {
+        PromptSection {
+            header: None,
+            lines: vec![text.to_string()],
+            priority: None,
+        }
+    }
+
+    pub fn trimmable(header: &str, body: &str, priority: u8) -> Self {
+        PromptSection {
+            header: Some(header.to_string()),
+            lines: body.lines().map(String::from).collect(),
+            priority: Some(priority),
+        }
+    }
+
+    fn render(&self) -> String {
+        match &self.header {
+            Some(header) => format!("{}\n{}\n", header, self.lines.join("\n")),
+            None => self.lines.join("\n"),
+        }
+    }
+}
+
+pub fn render(sections: &[PromptSection]) -> String {
+    sections.iter().map(PromptSection::render).collect()
+}
+
+#[derive(Debug)]
+pub struct FittedPrompt {
+    pub text: String,
+    pub tokens: usize,
+    // Headers of the sections that lost lines
+    pub trimmed: Vec<String>,
+}
+
+// Drop lines from the end of the lowest-priority sections until the preamble fits
+pub fn fit(sections: &[PromptSection], budget_tokens: usize) -> FittedPrompt {
+    let mut working: Vec<PromptSection> = sections.to_vec();
+    let mut trimmed = Vec::new();
+
+    let mut order: Vec<usize> = (0..working.len())
+        .filter(|i| working[*i].priority.is_some())
+        .collect();
+    order.sort_by_key(|i| working[*i].priority);
+
+    for i in order {
+        if estimate_tokens(&render(&working)) <= budget_tokens {
+            break;
+        }
+        let header = working[i].header.clone().unwrap_or_default();
+        while !working[i].lines.is_empty() && estimate_tokens(&render(&working)) > budget_tokens {
+            working[i].lines.pop();
+        }
+        trimmed.push(header);
+    }
+
+    // Sections trimmed to nothing shouldn't leave a dangling header behind
+    working.retain(|section| section.priority.is_none() || !section.lines.is_empty());
+    let text = render(&working);
+    FittedPrompt {
+        tokens: estimate_tokens(&text),
+        text,
+        trimmed,
+    }
+}
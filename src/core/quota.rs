@@ -0,0 +1,171 @@
+use chrono::{DateTime, Datelike, Months, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::character::ApiQuotaSettings;
+use crate::models::ApiQuotaLedger;
+use crate::providers::twitter::ENDPOINT_TWEETS;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+// How readily a job gives up its calls when the quota runs low. Sweeps go first,
+// then replies, and scheduled posts keep running until the cap itself is hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaPriority {
+    // Notification sweeps, trend searches, metrics and block syncs: stay on pace
+    Sweep,
+    // Queued replies and alerts: may spend ahead of pace but not the post reserve
+    Reply,
+    Post,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuotaBlock {
+    Exhausted { endpoint: String, limit: u64 },
+    // Spending faster than the window allows
+    OverPace { endpoint: String, used: u64, allowed: u64 },
+    // Replying would eat into the posts kept for scheduled posts
+    PostReserve { remaining: u64 },
+}
+
+impl fmt::Display for QuotaBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaBlock::Exhausted { endpoint, limit } => write!(f, "{} quota used up ({} calls this window)", endpoint, limit),
+            QuotaBlock::OverPace { endpoint, used, allowed } => {
+                write!(f, "{} is ahead of pace ({} calls, {} allowed so far)", endpoint, used, allowed)
+            }
+            QuotaBlock::PostReserve { remaining } => write!(f, "only {} posts left, kept for scheduled posts", remaining),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct QuotaUsage {
+    pub endpoint: String,
+    pub calls: u64,
+    pub limit: Option<u64>,
+}
+
+// Twitter calls per endpoint per monthly window, kept in memory so restarts don't reset it
+pub struct QuotaLedger;
+
+impl QuotaLedger {
+    // From the last reset day to the next one
+    pub fn window(settings: &ApiQuotaSettings, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let day = settings.reset_day.clamp(1, 28);
+        let this_month = Utc
+            .with_ymd_and_hms(now.year(), now.month(), day, 0, 0, 0)
+            .single()
+            .expect("days 1-28 exist in every month");
+        let start = if now >= this_month {
+            this_month
+        } else {
+            this_month - Months::new(1)
+        };
+        (start, start + Months::new(1))
+    }
+
+    // Start a fresh window once the current one has ended. A changed reset day only
+    // takes effect then, so counts are never thrown away mid-window.
+    pub fn roll(ledger: &mut ApiQuotaLedger, settings: &ApiQuotaSettings, now: DateTime<Utc>) -> bool {
+        if ledger.window_end.is_some_and(|end| now < end) {
+            return false;
+        }
+        let (start, end) = Self::window(settings, now);
+        ledger.window_start = Some(start);
+        ledger.window_end = Some(end);
+        ledger.calls.clear();
+        true
+    }
+
+    // Add calls the client made since the last flush; true if the ledger changed
+    pub fn record(
+        ledger: &mut ApiQuotaLedger,
+        settings: &ApiQuotaSettings,
+        calls: &BTreeMap<&str, u64>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let mut changed = Self::roll(ledger, settings, now);
+        for (endpoint, count) in calls {
+            *ledger.calls.entry(endpoint.to_string()).or_insert(0) += count;
+            changed = true;
+        }
+        if ledger.limits != settings.limits {
+            ledger.limits = settings.limits.clone();
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn used(ledger: &ApiQuotaLedger, endpoint: &str) -> u64 {
+        ledger.calls.get(endpoint).copied().unwrap_or(0)
+    }
+
+    // Calls the pace allows by now: the elapsed share of the limit plus a day's worth
+    pub fn paced_allowance(limit: u64, start: DateTime<Utc>, end: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+        let total = end.signed_duration_since(start).num_seconds().max(1) as f64;
+        let elapsed = (now.signed_duration_since(start).num_seconds() as f64).clamp(0.0, total);
+        let per_day = limit as f64 * SECONDS_PER_DAY / total;
+        let allowed = limit as f64 * elapsed / total + per_day;
+        (allowed.ceil() as u64).min(limit)
+    }
+
+    fn post_reserve(settings: &ApiQuotaSettings, limit: u64) -> u64 {
+        (limit as f64 * settings.post_reserve_pct.clamp(0.0, 100.0) / 100.0).ceil() as u64
+    }
+
+    // Why a job using these endpoints should sit this one out, if it should
+    pub fn check(
+        ledger: &ApiQuotaLedger,
+        settings: &ApiQuotaSettings,
+        priority: QuotaPriority,
+        endpoints: &[&str],
+        now: DateTime<Utc>,
+    ) -> Option<QuotaBlock> {
+        let (start, end) = match (ledger.window_start, ledger.window_end) {
+            (Some(start), Some(end)) if now < end => (start, end),
+            // Window ended without a flush; nothing has been spent in the new one yet
+            _ => return None,
+        };
+
+        for endpoint in endpoints {
+            let Some(&limit) = settings.limits.get(*endpoint) else {
+                continue;
+            };
+            let used = Self::used(ledger, endpoint);
+            if used >= limit {
+                return Some(QuotaBlock::Exhausted { endpoint: endpoint.to_string(), limit });
+            }
+            if priority == QuotaPriority::Sweep {
+                let allowed = Self::paced_allowance(limit, start, end, now);
+                if used >= allowed {
+                    return Some(QuotaBlock::OverPace { endpoint: endpoint.to_string(), used, allowed });
+                }
+            }
+            if priority != QuotaPriority::Post && *endpoint == ENDPOINT_TWEETS {
+                let remaining = limit - used;
+                if remaining <= Self::post_reserve(settings, limit) {
+                    return Some(QuotaBlock::PostReserve { remaining });
+                }
+            }
+        }
+        None
+    }
+
+    // Every endpoint called this window or given a limit
+    pub fn usage(ledger: &ApiQuotaLedger) -> Vec<QuotaUsage> {
+        let mut endpoints: Vec<&String> = ledger.calls.keys().chain(ledger.limits.keys()).collect();
+        endpoints.sort();
+        endpoints.dedup();
+        endpoints
+            .into_iter()
+            .map(|endpoint| QuotaUsage {
+                endpoint: endpoint.clone(),
+                calls: Self::used(ledger, endpoint),
+                limit: ledger.limits.get(endpoint).copied(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::character::ApiQuotaSettings;
+use super::config::RedisConfig;
+use super::quota::QuotaLedger;
+use crate::models::ApiQuotaLedger;
+use crate::providers::twitter::ENDPOINTS;
+
+// Redis calls are made from the main loop, so a slow or unreachable server gives up quickly
+const REDIS_TIMEOUT: Duration = Duration::from_secs(2);
+// Window counts outlive their window by this long, for anyone reading them afterwards
+const REDIS_KEEP_DAYS: i64 = 7;
+
+// Keeps the Twitter call counts the quota checks are made against: this process's
+// own, or every instance's on the same API keys
+pub trait RateLimiter: Send {
+    fn name(&self) -> &'static str;
+    // Adds the calls made since the last flush and brings `ledger` up to date with
+    // the window's totals; true if the ledger changed
+    fn record(
+        &mut self,
+        ledger: &mut ApiQuotaLedger,
+        settings: &ApiQuotaSettings,
+        calls: &BTreeMap<&str, u64>,
+        now: DateTime<Utc>,
+    ) -> bool;
+}
+
+// Counts in memory.json, per process
+pub struct LocalLimiter;
+
+impl RateLimiter for LocalLimiter {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn record(
+        &mut self,
+        ledger: &mut ApiQuotaLedger,
+        settings: &ApiQuotaSettings,
+        calls: &BTreeMap<&str, u64>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        QuotaLedger::record(ledger, settings, calls, now)
+    }
+}
+
+// Counts in Redis, one key per endpoint per window, added to by every instance and
+// read back into the ledger. While Redis can't be reached the calls are counted
+// locally and held back, and sent once it's back.
+pub struct RedisLimiter {
+    client: redis::Client,
+    connection: Option<redis::Connection>,
+    key_prefix: String,
+    unsent: BTreeMap<String, u64>,
+}
+
+impl RedisLimiter {
+    pub fn new(config: &RedisConfig) -> redis::RedisResult<Self> {
+        Ok(RedisLimiter {
+            client: redis::Client::open(config.url.expose())?,
+            connection: None,
+            key_prefix: config.key_prefix.clone(),
+            unsent: BTreeMap::new(),
+        })
+    }
+
+    // "chainfud:quota:2024-05-01:tweets"; the window's start date keeps windows apart
+    pub fn key(&self, window_start: DateTime<Utc>, endpoint: &str) -> String {
+        format!("{}:{}:{}", self.key_prefix, window_start.format("%Y-%m-%d"), endpoint)
+    }
+
+    // Calls counted while Redis was unreachable, not yet added to its totals
+    pub fn unsent(&self) -> &BTreeMap<String, u64> {
+        &self.unsent
+    }
+
+    fn connection(&mut self) -> redis::RedisResult<&mut redis::Connection> {
+        if self.connection.is_none() {
+            let connection = self.client.get_connection_with_timeout(REDIS_TIMEOUT)?;
+            connection.set_read_timeout(Some(REDIS_TIMEOUT))?;
+            connection.set_write_timeout(Some(REDIS_TIMEOUT))?;
+            self.connection = Some(connection);
+        }
+        Ok(self.connection.as_mut().expect("just connected"))
+    }
+
+    // Adds the unsent calls and reads every endpoint's total, in one round trip
+    fn sync(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> redis::RedisResult<BTreeMap<String, u64>> {
+        let expire_at = (end + chrono::Duration::days(REDIS_KEEP_DAYS)).timestamp();
+        let mut endpoints: Vec<String> = ENDPOINTS.iter().map(|endpoint| endpoint.to_string()).collect();
+        endpoints.extend(self.unsent.keys().filter(|endpoint| !ENDPOINTS.contains(&endpoint.as_str())).cloned());
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (endpoint, count) in &self.unsent {
+            let key = self.key(start, endpoint);
+            pipe.incr(&key, *count).ignore().expire_at(&key, expire_at).ignore();
+        }
+        for endpoint in &endpoints {
+            pipe.get(self.key(start, endpoint));
+        }
+        let totals: Vec<Option<u64>> = match pipe.query(self.connection()?) {
+            Ok(totals) => totals,
+            Err(e) => {
+                // Opened again on the next flush
+                self.connection = None;
+                return Err(e);
+            }
+        };
+        self.unsent.clear();
+        Ok(endpoints
+            .into_iter()
+            .zip(totals)
+            .filter_map(|(endpoint, total)| total.filter(|total| *total > 0).map(|total| (endpoint, total)))
+            .collect())
+    }
+}
+
+impl RateLimiter for RedisLimiter {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn record(
+        &mut self,
+        ledger: &mut ApiQuotaLedger,
+        settings: &ApiQuotaSettings,
+        calls: &BTreeMap<&str, u64>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let mut changed = QuotaLedger::roll(ledger, settings, now);
+        if changed {
+            // Calls held back from the last window don't count against this one
+            self.unsent.clear();
+        }
+        for (endpoint, count) in calls {
+            *self.unsent.entry(endpoint.to_string()).or_insert(0) += count;
+        }
+        let (Some(start), Some(end)) = (ledger.window_start, ledger.window_end) else {
+            return changed;
+        };
+        match self.sync(start, end) {
+            Ok(totals) => {
+                if ledger.calls != totals {
+                    ledger.calls = totals;
+                    changed = true;
+                }
+            }
+            Err(e) => {
+                eprintln!("Redis rate limiter unavailable, counting this instance's calls only: {}", e);
+                for (endpoint, count) in calls {
+                    *ledger.calls.entry(endpoint.to_string()).or_insert(0) += count;
+                    changed = true;
+                }
+            }
+        }
+        if ledger.limits != settings.limits {
+            ledger.limits = settings.limits.clone();
+            changed = true;
+        }
+        changed
+    }
+}
+
+// Redis when RATE_LIMIT_REDIS_URL is set, otherwise per process
+pub fn from_config(redis: Option<&RedisConfig>) -> Box<dyn RateLimiter> {
+    let Some(config) = redis else {
+        return Box::new(LocalLimiter);
+    };
+    match RedisLimiter::new(config) {
+        Ok(limiter) => Box::new(limiter),
+        Err(e) => {
+            eprintln!("Invalid RATE_LIMIT_REDIS_URL, counting API calls per instance: {}", e);
+            Box::new(LocalLimiter)
+        }
+    }
+}
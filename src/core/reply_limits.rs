@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+use super::character::ReplyLimitSettings;
+use crate::memory::MemoryStore;
+use crate::models::Memory;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplyLimit {
+    CoolingDown { until: DateTime<Utc> },
+    ThreadDepth { depth: u32 },
+}
+
+impl fmt::Display for ReplyLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplyLimit::CoolingDown { until } => write!(f, "author on cooldown until {}", until.format("%Y-%m-%d %H:%M UTC")),
+            ReplyLimit::ThreadDepth { depth } => write!(f, "already replied {} times in this thread", depth),
+        }
+    }
+}
+
+// Keeps the bot out of endless back-and-forths with accounts that answer every reply
+pub struct ReplyLimits;
+
+impl ReplyLimits {
+    // `pending` counts replies already planned in this conversation during the current sweep
+    pub fn check(
+        settings: &ReplyLimitSettings,
+        memory: &Memory,
+        conversation_id: &str,
+        author_id: Option<&str>,
+        pending: u32,
+        now: DateTime<Utc>,
+    ) -> Option<ReplyLimit> {
+        if let Some(until) = author_id.and_then(|author| MemoryStore::cooldown_until(memory, author, now)) {
+            return Some(ReplyLimit::CoolingDown { until });
+        }
+
+        let depth = MemoryStore::thread_depth(memory, conversation_id) + pending;
+        if settings.max_thread_depth > 0 && depth >= settings.max_thread_depth {
+            return Some(ReplyLimit::ThreadDepth { depth });
+        }
+        None
+    }
+
+    // When someone hits the depth limit, how long to ignore them; None if cooldowns are off
+    pub fn cooldown_end(settings: &ReplyLimitSettings, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (settings.user_cooldown_hours > 0).then(|| now + chrono::Duration::hours(settings.user_cooldown_hours))
+    }
+}
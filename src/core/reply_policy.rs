@@ -0,0 +1,107 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
+use std::fmt;
+
+use super::character::ReplyPolicySettings;
+use crate::models::MentionAuthor;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyReason {
+    NotFollower,
+    NotVerified,
+    FewFollowers { followers: u64 },
+    NewAccount { age_days: i64 },
+    // The lookup the policy needs failed; the mention is tried again next sweep
+    Unknown,
+}
+
+impl fmt::Display for PolicyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyReason::NotFollower => write!(f, "doesn't follow us"),
+            PolicyReason::NotVerified => write!(f, "not verified"),
+            PolicyReason::FewFollowers { followers } => write!(f, "only {} followers", followers),
+            PolicyReason::NewAccount { age_days } => write!(f, "account is {} days old", age_days),
+            PolicyReason::Unknown => write!(f, "couldn't look the author up"),
+        }
+    }
+}
+
+// Which mention authors are eligible for a reply under the character's reply
+// policy. Author details come from the spam filter's cache; the bot's followers
+// are kept here and re-synced every follower_sync_hours.
+#[derive(Default)]
+pub struct ReplyPolicy {
+    followers: HashSet<u64>,
+    // False when the sync stopped at its page cap, so an account missing from
+    // `followers` may still follow us
+    complete: bool,
+    synced_at: Option<DateTime<Utc>>,
+}
+
+impl ReplyPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_followers(&mut self, ids: impl IntoIterator<Item = u64>, complete: bool, now: DateTime<Utc>) {
+        self.followers = ids.into_iter().collect();
+        self.complete = complete;
+        self.synced_at = Some(now);
+    }
+
+    pub fn needs_follower_sync(&self, settings: &ReplyPolicySettings, now: DateTime<Utc>) -> bool {
+        settings.followers_only
+            && self.synced_at.is_none_or(|at| now.signed_duration_since(at) >= Duration::hours(settings.follower_sync_hours))
+    }
+
+    // None when the author may get a reply
+    pub fn check(
+        &self,
+        settings: &ReplyPolicySettings,
+        author_id: Option<u64>,
+        author: Option<&MentionAuthor>,
+        now: DateTime<Utc>,
+    ) -> Option<PolicyReason> {
+        if !settings.is_enabled() || settings.is_exempt(author_id, author.map(|a| a.username.as_str())) {
+            return None;
+        }
+        if settings.followers_only {
+            let Some(id) = author_id.filter(|_| self.synced_at.is_some()) else {
+                return Some(PolicyReason::Unknown);
+            };
+            if !self.followers.contains(&id) {
+                return Some(if self.complete { PolicyReason::NotFollower } else { PolicyReason::Unknown });
+            }
+        }
+
+        if !(settings.verified_only || settings.min_followers > 0 || settings.min_account_age_days > 0) {
+            return None;
+        }
+        let Some(author) = author else {
+            return Some(PolicyReason::Unknown);
+        };
+        if settings.verified_only && author.verified != Some(true) {
+            return Some(PolicyReason::NotVerified);
+        }
+        if settings.min_followers > 0 {
+            match author.followers {
+                Some(followers) if followers < settings.min_followers => return Some(PolicyReason::FewFollowers { followers }),
+                Some(_) => {}
+                None => return Some(PolicyReason::Unknown),
+            }
+        }
+        if settings.min_account_age_days > 0 {
+            match author.created_at {
+                Some(created_at) => {
+                    let age_days = now.signed_duration_since(created_at).num_days();
+                    if age_days < settings.min_account_age_days {
+                        return Some(PolicyReason::NewAccount { age_days });
+                    }
+                }
+                None => return Some(PolicyReason::Unknown),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,92 @@
+use rand::seq::SliceRandom;
+use regex::Regex;
+use serde::Deserialize;
+
+// What a matching rule does with a mention instead of the usual reply logic
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    // No reply at all
+    Ignore,
+    // One of the rule's `replies`, word for word
+    Canned,
+    // FUD about the token in the mention, or the regex's first capture
+    TokenLookup,
+    // The generic insult, even if a token is mentioned
+    Insult,
+    // Write the reply as usual but hold it until a Telegram admin approves it
+    Escalate,
+}
+
+// Operator rule for mentions, checked in order before anything is generated. A
+// rule matches when every condition it sets holds; one with none never matches.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ReplyRule {
+    #[serde(default)]
+    pub name: String,
+    // Any of these phrases in the mention, ignoring case
+    #[serde(default)]
+    pub any: Vec<String>,
+    // Every one of these phrases in the mention, ignoring case
+    #[serde(default)]
+    pub all: Vec<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+    pub action: RuleAction,
+    #[serde(default)]
+    pub replies: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    // Rule name, or its position when it has none
+    pub label: String,
+    pub action: RuleAction,
+    // First capture group of the rule's regex, if it has one
+    pub capture: Option<String>,
+    pub canned_reply: Option<String>,
+}
+
+pub struct ReplyRules;
+
+impl ReplyRules {
+    pub fn has_conditions(rule: &ReplyRule) -> bool {
+        !rule.any.is_empty() || !rule.all.is_empty() || rule.regex.is_some()
+    }
+
+    // None when the rule doesn't match; otherwise the regex capture, if any
+    fn matches(rule: &ReplyRule, text: &str) -> Option<Option<String>> {
+        if !Self::has_conditions(rule) {
+            return None;
+        }
+        let lower = text.to_lowercase();
+        if !rule.any.is_empty() && !rule.any.iter().any(|phrase| lower.contains(&phrase.to_lowercase())) {
+            return None;
+        }
+        if !rule.all.iter().all(|phrase| lower.contains(&phrase.to_lowercase())) {
+            return None;
+        }
+        let Some(pattern) = &rule.regex else {
+            return Some(None);
+        };
+        // Invalid patterns are reported by the validator and never match here
+        let captures = Regex::new(pattern).ok()?.captures(text)?;
+        Some(captures.get(1).map(|capture| capture.as_str().to_string()))
+    }
+
+    // First rule that matches the mention
+    pub fn evaluate(rules: &[ReplyRule], text: &str) -> Option<RuleMatch> {
+        rules.iter().enumerate().find_map(|(i, rule)| {
+            let capture = Self::matches(rule, text)?;
+            let label = match rule.name.trim() {
+                "" => format!("reply_rules[{}]", i),
+                name => name.to_string(),
+            };
+            let canned_reply = match rule.action {
+                RuleAction::Canned => rule.replies.choose(&mut rand::thread_rng()).cloned(),
+                _ => None,
+            };
+            Some(RuleMatch { label, action: rule.action, capture, canned_reply })
+        })
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+
+use super::kv_store::KvStore;
+use crate::error::Result;
+use crate::models::Memory;
+
+// How far we got with a mention. Written before the step it names, so a crash
+// never leaves a reply sent without the index knowing about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseState {
+    // Looked at and not answered: ignored, spam, over a limit
+    Handled,
+    // Waiting on an admin's approval
+    Held,
+    // In the reply queue
+    Queued,
+    // Handed to Twitter and not yet confirmed. Found at startup it means we died
+    // mid-send, and the reply may well be up, so it isn't sent again.
+    Sending,
+    Replied,
+    // Twitter refused the reply because the mention was deleted, is protected or
+    // doesn't take replies from us; never tried again
+    Unrepliable,
+}
+
+impl ResponseState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseState::Handled => "handled",
+            ResponseState::Held => "held",
+            ResponseState::Queued => "queued",
+            ResponseState::Sending => "sending",
+            ResponseState::Replied => "replied",
+            ResponseState::Unrepliable => "unrepliable",
+        }
+    }
+
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "handled" => Some(ResponseState::Handled),
+            "held" => Some(ResponseState::Held),
+            "queued" => Some(ResponseState::Queued),
+            "sending" => Some(ResponseState::Sending),
+            "replied" => Some(ResponseState::Replied),
+            "unrepliable" => Some(ResponseState::Unrepliable),
+            _ => None,
+        }
+    }
+}
+
+// The value stored for a mention, "<state> <rfc3339>". Older stores hold just the
+// time, which reads as handled.
+pub fn parse_entry(value: &str) -> (ResponseState, Option<DateTime<Utc>>) {
+    let (state, at) = match value.split_once(' ') {
+        Some((word, at)) => (ResponseState::parse(word).unwrap_or(ResponseState::Handled), at),
+        None => (ResponseState::Handled, value),
+    };
+    (state, DateTime::parse_from_rfc3339(at.trim()).ok().map(|at| at.with_timezone(&Utc)))
+}
+
+// Every mention either notification flow has dealt with, keyed by tweet ID, in
+// storage/processed_tweets.kv. Both flows check it before replying and move a
+// mention through it before each step, one appended line at a time.
+pub struct RespondedIndex {
+    store: Box<dyn KvStore>,
+}
+
+impl RespondedIndex {
+    pub fn new(store: Box<dyn KvStore>) -> Self {
+        RespondedIndex { store }
+    }
+
+    pub fn state(&self, tweet_id: &str) -> Option<ResponseState> {
+        self.store.get(tweet_id).map(|value| parse_entry(&value).0)
+    }
+
+    pub fn contains(&self, tweet_id: &str) -> bool {
+        self.store.contains(tweet_id)
+    }
+
+    pub fn record(&mut self, tweet_id: &str, state: ResponseState, now: DateTime<Utc>) -> Result<()> {
+        self.store.insert(tweet_id, &format!("{} {}", state.as_str(), now.to_rfc3339()))
+    }
+
+    // Records the first step for a mention nobody has dealt with yet, as one locked
+    // step like `claim`. False if it already has a row: another instance drafted,
+    // queued or answered it meanwhile.
+    pub fn take(&mut self, tweet_id: &str, state: ResponseState, now: DateTime<Utc>) -> Result<bool> {
+        let value = format!("{} {}", state.as_str(), now.to_rfc3339());
+        self.store.update(tweet_id, Box::new(move |current| current.is_none().then(|| value.clone())))
+    }
+
+    // Moves a mention to sending unless it's already been dealt with, in one step a
+    // shared store holds the row locked for, so two instances never both answer it.
+    // False if it's taken: sending, replied, or handled without a reply.
+    pub fn claim(&mut self, tweet_id: &str, now: DateTime<Utc>) -> Result<bool> {
+        let sending = format!("{} {}", ResponseState::Sending.as_str(), now.to_rfc3339());
+        self.store.update(
+            tweet_id,
+            Box::new(move |current| {
+                let state = current.map(|value| parse_entry(value).0);
+                matches!(state, None | Some(ResponseState::Queued)).then(|| sending.clone())
+            }),
+        )
+    }
+
+    // Forget a mention so the next sweep picks it up again, after a send that failed
+    // before anything reached Twitter
+    pub fn release(&mut self, tweet_id: &str) -> Result<bool> {
+        self.store.remove(tweet_id)
+    }
+
+    // Replies, queued replies and held replies memory.json knows about and the index
+    // doesn't, from before the index tracked them. Tweets from before `cutoff` are
+    // left out; they'd only be pruned again on the next start.
+    pub fn seed(&mut self, memory: &Memory, cutoff: DateTime<Utc>, now: DateTime<Utc>) -> Result<usize> {
+        let queued = memory.reply_queue.iter().map(|reply| (&reply.reply_to, ResponseState::Queued));
+        let held = memory.held_replies.iter().map(|reply| (&reply.reply_to, ResponseState::Held));
+        let replied = memory
+            .tweets
+            .iter()
+            .filter(|tweet| tweet.timestamp > cutoff)
+            .filter_map(|tweet| tweet.reply_to.as_ref())
+            .map(|reply_to| (reply_to, ResponseState::Replied));
+
+        let mut added = 0;
+        for (tweet_id, state) in queued.chain(held).chain(replied) {
+            if self.contains(tweet_id) {
+                continue;
+            }
+            self.record(tweet_id, state, now)?;
+            added += 1;
+        }
+        Ok(added)
+    }
+}
@@ -1,80 +1,463 @@
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, Utc};
 use rand::Rng;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use futures::future::join_all;
+use tokio::sync::Semaphore;
 use tokio::time::{sleep, Duration};
-use std::path::PathBuf;
-use std::error::Error;
-use std::fs;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
-use std::path::Path;
+use std::net::SocketAddr;
 
 use crate::{
-    core::agent::{Agent, ResponseDecision},
+    content::network_fud::NetworkFud,
+    core::adaptive_schedule::{AdaptiveSchedule, IntervalChange},
+    core::clock::{Clock, Schedule, SystemClock, FUD_SCHEDULE_MINUTES},
+    core::consistency::ConsistencyCheck,
+    core::content_buffer::ContentBuffer,
+    core::inline_fud::{InlineCards, InlineFud, InlineRateLimit, InlineResponder},
+    core::agent::{Agent, ResponseDecision, CLASSIFY_BATCH_SIZE},
+    core::aggression::{Aggression, AggressionCommand},
+    core::campaign::{CampaignCommand, Campaigns},
+    core::feature_flags::{FeatureSwitch, FlagCommand},
+    core::approval::{ApprovalCommand, Approvals},
+    core::audit::AuditLog,
+    core::bundle_check::{BundleCheck, BundleReport, LaunchBuy},
+    core::character::{AggressionSettings, CharacterSettings, GenerationSettings, LanguageSettings, ReceiptMode, TimeoutFallback},
+    core::character_switch::{CharacterCommand, CharacterSwitch},
+    core::config::{Config, HeuristConfig, HttpClientConfig, Secret},
+    core::content::{ContentGenerator, ContentGenerators, Post},
+    core::content_selector::ContentSelector,
+    core::dataset::DatasetBuilder,
+    core::drafts::{Draft, DraftLog, DEFAULT_DRAFTS_DIR},
+    core::follow_up::{FollowUp, FollowUpQuestion},
+    core::health::{HealthReport, Provider, ProviderStatus},
+    core::status::{StatusServer, StatusSnapshot},
+    core::storage::{StorageManager, SweepReport},
+    core::instruction_builder::{ExampleRotation, InstructionBuilder},
+    core::learned_facts::LearnedFacts,
+    core::local_time::LocalTime,
+    core::memory_backend::MemoryBackend,
+    core::callbacks::Callbacks,
+    core::latency_budget,
+    core::outbox::{Outbox, OutboxItem, DEFAULT_OUTBOX_DIR},
+    core::paths,
+    core::kv_store::{KvStore, LogStore},
+    core::predictions::PredictionRecord,
+    core::price_check::PriceCheck,
+    core::pipeline::Pipeline,
+    core::prompt_budget::{self, PromptSection},
+    core::quota::{QuotaLedger, QuotaPriority},
+    core::rate_limiter::{self, RateLimiter},
+    core::reply_limits::{ReplyLimit, ReplyLimits},
+    core::reply_policy::{PolicyReason, ReplyPolicy},
+    core::reply_rules::{ReplyRules, RuleAction, RuleMatch},
+    core::responded::{RespondedIndex, ResponseState},
+    core::spam_filter::SpamFilter,
+    core::stream_guard::StreamGuard,
+    core::style::StyleEngine,
+    core::target_scorer::TargetScorer,
+    core::token_extractor::{TokenCandidate, TokenExtractor},
+    core::token_lookup::TokenLookup,
+    core::token_heat::TokenHeat,
+    core::telemetry::Tracer,
+    core::timeline_sync::TimelineSync,
+    core::tweet_preview::{TweetPreview, PREVIEW_WIDTH},
+    core::validator::CharacterValidator,
+    core::weekly_recap::{RecapEntry, WeeklyRecap},
+    error::FudError,
+    media::chart_images::{ChartImage, ChartImages},
+    media::reaction_clips::ReactionClips,
+    media::token_card::TokenCard,
     memory::MemoryStore,
-    models::Memory,
+    models::{AvoidedTopic, Campaign, CharacterSwap, FuddedToken, HeldReply, Memory, OnChainReceipt, Prediction, TokenFact, TokenSnapshot, TrendEngagement, TrendingSnapshot, Tweet, WatchedToken},
     models::CharacterConfig,
-    providers::telegram::Telegram,
-    providers::twitter::Twitter,
-    providers::solanatracker::SolanaTracker,
+    providers::telegram::{InlineRequest, Telegram},
+    providers::birdeye::{Birdeye, LiquiditySplit, TradeFlow},
+    providers::coingecko::CoinGecko,
+    providers::discord::Discord,
+    providers::geckoterminal::GeckoTerminal,
+    providers::metaplex::{Metaplex, TokenMetadata},
+    providers::network_stats::NetworkMonitor,
+    providers::news::{self, Headline, NewsClient},
+    providers::pumpfun::{BondingStatus, PumpFun},
+    providers::screenshot::ScreenshotApi,
+    providers::solana_rpc::{RpcRejected, SolanaRpc},
+    providers::wallet::Wallet,
+    providers::twitter::{ReferencedPost, ScheduledSpace, TimelinePost, Twitter, ENDPOINT_BLOCKS, ENDPOINT_COMMUNITIES, ENDPOINT_FOLLOWERS, ENDPOINT_MENTIONS, ENDPOINT_SEARCH, ENDPOINT_SPACES, ENDPOINT_TIMELINE, ENDPOINT_TWEETS, ENDPOINT_TWEET_LOOKUP},
+    providers::solanatracker::{SolanaTracker, TokenInfo, TokenResponse},
 };
+use twitter_v2::data::ReferencedTweetKind;
+
+// How often engagement metrics of recent posts are refreshed, and for how long after posting
+const METRICS_REFRESH_MINUTES: i64 = 60;
+const METRICS_TRACK_DAYS: i64 = 3;
+// Replies sent per notification sweep, however many mentions were classified
+const MAX_REPLIES_PER_SWEEP: usize = 3;
+// Mentions drafted at once; each can make several API and model calls
+const MAX_CONCURRENT_DRAFTS: usize = 3;
+// Least time between two of our own tweets
+const TWEET_COOLDOWN_MINUTES: i64 = 5;
+// How often mentions are swept
+const NOTIFICATION_INTERVAL_MINUTES: i64 = 5;
+// How often integrations that failed the preflight are checked again
+const HEALTH_RECHECK_MINUTES: i64 = 10;
+// Bonding curves move fast; how long a pump.fun lookup is reused
+const BONDING_STATUS_TTL_MINUTES: i64 = 10;
+// Birdeye security and trade lookups are reused this long
+const SECURITY_INFO_TTL_MINUTES: i64 = 10;
+// When a mint's listings were checked, and the major exchanges it was found on
+type CexListing = (DateTime<Utc>, Vec<String>);
+
+// Exchange listings are re-checked after this long
+const CEX_LISTING_TTL_HOURS: i64 = 24;
+// Picks thrown back for a CEX listing before giving up on the slot
+const MAX_LISTED_PICKS: usize = 5;
+// getSignaturesForAddress page size, and RPC calls the bundle check makes at once
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+const BUNDLE_RPC_CONCURRENCY: usize = 5;
+// getTokenSupply calls made at once while filling in the trending list
+const SUPPLY_RPC_CONCURRENCY: usize = 5;
+// A launch doesn't change, but the tokens worth checking do; results older than
+// this are dropped so the cache only holds recent targets
+const BUNDLE_REPORT_TTL_HOURS: i64 = 24;
+// When a mint's launch was checked, and what it found
+type CheckedBundle = (DateTime<Utc>, Option<BundleReport>);
+// How often Telegram is asked for admin commands
+const COMMAND_POLL_SECONDS: i64 = 10;
+// How often it's asked when inline queries are answered too
+const INLINE_POLL_SECONDS: i64 = 2;
+// Longest the loop sleeps, for checks that run on their own intervals (queues, whale
+// alerts, predictions, metrics)
+const MAX_IDLE_SECONDS: i64 = 60;
+// The status endpoints report the loop as stuck after this long without a
+// finished cycle; rate limit waits can hold a cycle for 15 minutes
+const STATUS_STALE_MINUTES: i64 = 30;
+// The content buffer isn't topped up this close to a scheduled post
+const BUFFER_FILL_LEAD_MINUTES: i64 = 3;
+// Least time between two attempts to top up the content buffer
+const BUFFER_FILL_INTERVAL_MINUTES: i64 = 2;
 
 pub struct Runtime {
     anthropic_api_key: String,
+    // Set when ANTHROPIC_PROXY or ANTHROPIC_USER_AGENT is
+    anthropic_http: Option<reqwest::Client>,
     twitter: Twitter,
-    agents: Vec<Agent>,
+    agents: Vec<Arc<Agent>>,
     memory: Memory,
-    processed_tweets: HashSet<String>,
-    telegram: Telegram,
+    // Where the responded index, phrase history and token heat live
+    backend: MemoryBackend,
+    // Keeps the API quota ledger's counts, per instance or shared through Redis
+    rate_limiter: Box<dyn RateLimiter>,
+    responded: RespondedIndex,
+    telegram: Option<Telegram>,
+    discord: Discord,
     cached_user_id: Option<u64>,
     last_notification_check: Option<DateTime<Utc>>,
     last_tweet_time: Option<DateTime<Utc>>,
+    last_whale_check: Option<DateTime<Utc>>,
+    last_snapshot_time: Option<DateTime<Utc>>,
+    last_metrics_refresh: Option<DateTime<Utc>>,
+    last_block_sync: Option<DateTime<Utc>>,
+    storage: StorageManager,
+    last_storage_sweep: Option<DateTime<Utc>>,
+    // What the latest storage sweep found; image generation waits while disk is low
+    storage_report: Option<SweepReport>,
+    reply_policy: ReplyPolicy,
+    last_prediction_check: Option<DateTime<Utc>>,
+    last_command_poll: Option<DateTime<Utc>>,
     solana_tracker: SolanaTracker,
+    gecko_terminal: GeckoTerminal,
+    solana_rpc: SolanaRpc,
+    metaplex: Metaplex,
+    // Published metadata by mint; None once we know a token has none
+    // Behind locks so mentions can be drafted side by side; never held across an await
+    token_metadata: Mutex<HashMap<String, Option<TokenMetadata>>>,
+    pump_fun: PumpFun,
+    network_monitor: NetworkMonitor,
+    news: NewsClient,
+    // Inline query answers by token, with when they were made, shared with the
+    // tasks that write them
+    inline_cards: InlineCards,
+    inline_limit: InlineRateLimit,
+    // Inline answers being written right now, and how many failed since last counted
+    inline_in_flight: Arc<AtomicUsize>,
+    inline_errors: Arc<AtomicUsize>,
+    // Community names by ID, looked up once per run
+    community_names: HashMap<String, String>,
+    // Bonding curve status by mint, with when it was fetched
+    bonding_status: Mutex<HashMap<String, (DateTime<Utc>, BondingStatus)>>,
+    birdeye: Option<Birdeye>,
+    // Birdeye summary lines by mint, with when they were fetched
+    security_info: Mutex<HashMap<String, (DateTime<Utc>, String)>>,
+    coingecko: CoinGecko,
+    // Major exchanges each checked mint is listed on (empty if none), with when it was checked
+    cex_listings: Mutex<HashMap<String, CexListing>>,
+    // Bundle check results by mint, with when they were worked out
+    bundle_reports: Mutex<HashMap<String, CheckedBundle>>,
+    spam_filter: SpamFilter,
+    heurist: Option<HeuristConfig>,
+    screenshot: Option<ScreenshotApi>,
+    wallet: Option<Wallet>,
     character_config: CharacterConfig,
-    recent_phrases: HashSet<String>,
-    max_recent_phrases: usize,
+    // Directory name under characters/ the agents were built from
+    character_name: String,
+    style: Arc<Mutex<StyleEngine>>,
+    // Topics the consistency check flagged, as last saved in memory
+    avoided_topics: Arc<Mutex<Vec<AvoidedTopic>>>,
+    aggression: Arc<Mutex<Aggression>>,
+    settings: CharacterSettings,
+    health: HealthReport,
+    // What /healthz and /readyz report, refreshed every cycle
+    status: Arc<Mutex<StatusSnapshot>>,
+    status_addr: Option<SocketAddr>,
+    status_admin_token: Option<String>,
+    tracer: Tracer,
+    audit: AuditLog,
+    outbox: Outbox,
+    drafts: DraftLog,
+    clock: Arc<dyn Clock>,
+    last_buffer_fill: Option<DateTime<Utc>>,
+    // Start of the previous and the current loop cycle
+    schedule_window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    // Set while a scheduled slot is writing FUD about the campaign token
+    campaign_slot: bool,
 }
 
 impl Runtime {
-    pub fn new(
-        anthropic_api_key: &str,
-        twitter_consumer_key: &str,
-        twitter_consumer_secret: &str,
-        twitter_access_token: &str,
-        twitter_access_token_secret: &str,
-        telegram_bot_token: &str,
-        solana_tracker_api_key: &str,
-        character_config: CharacterConfig,
-    ) -> Self {
+    // Config::from_env already built each client once, so this can't fail here
+    fn http_client(http: &HttpClientConfig) -> reqwest::Client {
+        http.client().expect("HTTP client config was validated at startup")
+    }
+
+    // twitter_v2 builds its own client, which only follows the standard proxy variables
+    fn has_env_proxy() -> bool {
+        ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+            .iter()
+            .any(|var| std::env::var(var).is_ok_and(|value| !value.is_empty()))
+    }
+
+    pub fn new(config: &Config, character_config: CharacterConfig) -> Self {
         let twitter = Twitter::new(
-            twitter_consumer_key,
-            twitter_consumer_secret,
-            twitter_access_token,
-            twitter_access_token_secret,
+            config.twitter.consumer_key.expose(),
+            config.twitter.consumer_secret.expose(),
+            config.twitter.access_token.expose(),
+            config.twitter.access_token_secret.expose(),
+        )
+        .with_client(Self::http_client(&config.twitter_http));
+        if config.twitter_http.proxy.is_some() && !Self::has_env_proxy() {
+            eprintln!(
+                "Warning: TWITTER_PROXY covers media uploads and DMs only; also set HTTPS_PROXY for the rest of the Twitter API"
+            );
+        }
+        if Self::has_env_proxy() {
+            println!("HTTPS_PROXY is set: every request without its own *_PROXY or *_USER_AGENT goes through it");
+        }
+        let telegram = config.telegram.as_ref().map(|telegram_config| {
+            let mut telegram = Telegram::new(telegram_config.bot_token.expose());
+            if let Some(channel) = &telegram_config.channel {
+                telegram.set_channel(channel);
+                println!("Mirroring posted tweets to Telegram channel {}", channel);
+            }
+            telegram.set_admins(telegram_config.admin_ids.clone());
+            telegram
+        });
+        let discord = Discord::new(
+            config
+                .discord_webhook_urls
+                .as_ref()
+                .map(|urls| Discord::parse_webhook_urls(urls.expose()))
+                .unwrap_or_default(),
         );
-        let telegram = Telegram::new(telegram_bot_token);
         let agents = Vec::new();
         let memory = MemoryStore::load_memory().unwrap_or_else(|_| Memory::default());
-        let processed_tweets = MemoryStore::load_processed_tweets().unwrap_or_else(|_| HashSet::new());
-        let solana_tracker = SolanaTracker::new(solana_tracker_api_key);
+        let backend = Self::open_backend(config);
+        let responded = Self::open_responded(&backend, &memory);
+        let phrase_history = Self::open_phrase_history(&backend, &memory);
+        let solana_tracker = SolanaTracker::new(config.solana_tracker_api_key.expose())
+            .with_client(Self::http_client(&config.solana_tracker_http))
+            .with_trending_ttl(config.trending_cache_ttl_seconds);
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        let avoided_topics = Arc::new(Mutex::new(memory.avoided_topics.clone()));
+        let aggression = Arc::new(Mutex::new(Aggression::new(AggressionSettings::default(), memory.aggression_override)));
         Runtime {
             memory,
-            anthropic_api_key: anthropic_api_key.to_string(),
+            backend,
+            rate_limiter: rate_limiter::from_config(config.rate_limit_redis.as_ref()),
+            anthropic_api_key: config.anthropic_api_key.expose().to_string(),
+            anthropic_http: config.anthropic_http.is_set().then(|| Self::http_client(&config.anthropic_http)),
             agents,
             twitter,
-            processed_tweets,
+            responded,
             telegram,
+            discord,
             cached_user_id: None,
             last_notification_check: None,
             last_tweet_time: None,
+            last_whale_check: None,
+            last_snapshot_time: None,
+            last_metrics_refresh: None,
+            last_block_sync: None,
+            storage: StorageManager::default(),
+            last_storage_sweep: None,
+            storage_report: None,
+            reply_policy: ReplyPolicy::new(),
+            last_prediction_check: None,
+            last_command_poll: None,
             solana_tracker,
+            gecko_terminal: GeckoTerminal::new(),
+            solana_rpc: SolanaRpc::new(config.solana_rpc_url.expose()),
+            metaplex: Metaplex::new(),
+            token_metadata: Mutex::new(HashMap::new()),
+            pump_fun: PumpFun::new(),
+            network_monitor: NetworkMonitor::new(),
+            news: NewsClient::new(config.cryptopanic_api_key.as_ref().map(Secret::expose)),
+            inline_cards: InlineCards::default(),
+            inline_limit: InlineRateLimit::default(),
+            inline_in_flight: Arc::new(AtomicUsize::new(0)),
+            inline_errors: Arc::new(AtomicUsize::new(0)),
+            community_names: HashMap::new(),
+            bonding_status: Mutex::new(HashMap::new()),
+            birdeye: config.birdeye_api_key.as_ref().map(|key| Birdeye::new(key.expose())),
+            security_info: Mutex::new(HashMap::new()),
+            bundle_reports: Mutex::new(HashMap::new()),
+            coingecko: CoinGecko::new(config.coingecko_api_key.as_ref().map(Secret::expose)),
+            cex_listings: Mutex::new(HashMap::new()),
+            spam_filter: SpamFilter::new(),
+            heurist: config.heurist.clone(),
+            screenshot: config.screenshot.clone().map(ScreenshotApi::new),
+            wallet: config
+                .solana_wallet_key
+                .as_ref()
+                .and_then(|key| Wallet::new(key.expose()).ok()),
             character_config,
-            recent_phrases: HashSet::new(),
-            max_recent_phrases: 50,
+            character_name: String::new(),
+            style: Arc::new(Mutex::new(StyleEngine::default().with_history(phrase_history, clock.now()))),
+            avoided_topics,
+            aggression,
+            settings: CharacterSettings::default(),
+            health: HealthReport::default(),
+            status: Arc::new(Mutex::new(StatusSnapshot::new(clock.now()))),
+            status_addr: config.status_addr,
+            status_admin_token: config.status_admin_token.as_ref().map(|token| token.expose().to_string()),
+            tracer: Tracer::new(config.telemetry.as_ref()),
+            audit: AuditLog::new(config.audit.as_ref()),
+            outbox: Outbox::new(paths::resolve(DEFAULT_OUTBOX_DIR)),
+            drafts: DraftLog::new(paths::resolve(DEFAULT_DRAFTS_DIR)),
+            clock,
+            last_buffer_fill: None,
+            schedule_window: None,
+            campaign_slot: false,
+        }
+    }
+
+    // Connected up front so a bad DATABASE_URL shows at startup. The bot still starts;
+    // until the database is back nothing can be claimed, so no replies go out.
+    fn open_backend(config: &Config) -> MemoryBackend {
+        let backend = MemoryBackend::from_config(config.postgres.as_ref());
+        if let MemoryBackend::Postgres(connection) = &backend {
+            match connection.check() {
+                Ok(()) => println!("Shared state: Postgres"),
+                Err(e) => eprintln!("Shared state: Postgres unavailable, replies are paused until it's back: {}", e),
+            }
+        }
+        backend
+    }
+
+    // The responded index, with anything memory.json already records added in
+    fn open_responded(backend: &MemoryBackend, memory: &Memory) -> RespondedIndex {
+        let now = Utc::now();
+        let mut store = Self::store_or_in_memory(
+            "processed notifications",
+            backend.open("processed_tweets", MemoryStore::open_processed_tweets),
+        );
+        // The file store prunes itself on open
+        if backend.is_shared() {
+            if let Err(e) = MemoryStore::prune_processed(store.as_mut(), now) {
+                eprintln!("Failed to prune the responded index: {}", e);
+            }
+        }
+        let mut responded = RespondedIndex::new(store);
+        match responded.seed(memory, MemoryStore::processed_cutoff(now), now) {
+            Ok(0) => {}
+            Ok(added) => println!("Added {} replies from memory to the responded index", added),
+            Err(e) => eprintln!("Failed to add replies from memory to the responded index: {}", e),
+        }
+        responded
+    }
+
+    fn open_phrase_history(backend: &MemoryBackend, memory: &Memory) -> Box<dyn KvStore> {
+        let mut store = Self::store_or_in_memory(
+            "phrase history",
+            backend.open("phrase_history", || MemoryStore::open_phrase_history(memory)),
+        );
+        if backend.is_shared() && store.is_empty() {
+            if let Err(e) = MemoryStore::seed_phrase_history(store.as_mut(), memory, Utc::now()) {
+                eprintln!("Failed to seed the phrase history: {}", e);
+            }
+        }
+        store
+    }
+
+    // Moves a mention along in the responded index. False if that couldn't be written,
+    // in which case nothing should be sent for it.
+    fn mark_responded(&mut self, tweet_id: &str, state: ResponseState) -> bool {
+        match self.responded.record(tweet_id, state, self.clock.now()) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Failed to mark {} as {}: {}", tweet_id, state.as_str(), e);
+                false
+            }
+        }
+    }
+
+    // Marks a mention this instance just drafted a reply for, unless another instance
+    // got to it first. False means nothing more should be done with it.
+    fn take_responded(&mut self, tweet_id: &str, state: ResponseState) -> bool {
+        match self.responded.take(tweet_id, state, self.clock.now()) {
+            Ok(true) => true,
+            Ok(false) => {
+                println!("Mention {} was already picked up elsewhere, dropping the draft", tweet_id);
+                false
+            }
+            Err(e) => {
+                eprintln!("Failed to mark {} as {}: {}", tweet_id, state.as_str(), e);
+                false
+            }
+        }
+    }
+
+    // Takes a mention for sending. Ok(false) when another instance (or a crash) already
+    // has it; either way nothing should be sent unless this is Ok(true).
+    fn claim_responded(&mut self, tweet_id: &str) -> Result<bool, FudError> {
+        let claimed = self.responded.claim(tweet_id, self.clock.now());
+        match &claimed {
+            Ok(true) => {}
+            Ok(false) => println!("Mention {} is already being answered, skipping it", tweet_id),
+            Err(e) => eprintln!("Failed to mark {} as sending: {}", tweet_id, e),
+        }
+        claimed
+    }
+
+    fn release_responded(&mut self, tweet_id: &str) {
+        if let Err(e) = self.responded.release(tweet_id) {
+            eprintln!("Failed to release {} from the responded index: {}", tweet_id, e);
+        }
+    }
+
+    // A store that couldn't be opened still works for this run, it just isn't saved
+    fn store_or_in_memory(name: &str, opened: Result<Box<dyn KvStore>, FudError>) -> Box<dyn KvStore> {
+        match opened {
+            Ok(store) => store,
+            Err(e) => {
+                eprintln!("Failed to open {}, keeping it in memory only: {}", name, e);
+                Box::new(LogStore::in_memory())
+            }
         }
     }
 
-    async fn run_debug_test(&mut self) -> Result<(), anyhow::Error> {
+    async fn run_debug_test(&mut self) -> Result<(), FudError> {
         println!("\n=== Running Debug Mode FUD Generation Test ===");
         println!("Fetching trending tokens...");
         
@@ -82,7 +465,7 @@ impl Runtime {
         println!("Retrieved {} tokens", tokens.len());
         
         let mut rng = rand::thread_rng();
-        let agent = &mut self.agents[0];
+        let agent = &self.agents[0];
         
         println!("\nGenerating 5 sample FUD tweets:\n");
         for i in 1..=5 {
@@ -102,35 +485,35 @@ impl Runtime {
     }
 
     fn contains_recent_phrase(&mut self, text: &str) -> bool {
-        // Split into 3-word phrases
-        let words: Vec<&str> = text.split_whitespace().collect();
-        for window in words.windows(3) {
-            let phrase = window.join(" ").to_lowercase();
-            if self.recent_phrases.contains(&phrase) {
-                return true;
-            }
-        }
-        
-        // Add new phrases
-        for window in words.windows(3) {
-            let phrase = window.join(" ").to_lowercase();
-            self.recent_phrases.insert(phrase);
-        }
-        
-        // Maintain size limit
-        if self.recent_phrases.len() > self.max_recent_phrases {
-            let phrases: Vec<String> = self.recent_phrases.iter()
-                .take(self.recent_phrases.len() - self.max_recent_phrases)
-                .cloned()
-                .collect();
-            for phrase in phrases {
-                self.recent_phrases.remove(&phrase);
-            }
+        if self.style().repeats_recent_phrase(text) {
+            return true;
         }
-        
+        self.style().record(text);
         false
     }
 
+    // Dry-run output: how the tweet would look and whether it fits
+    fn print_preview(text: &str) {
+        println!("{}", TweetPreview::new(text).render(PREVIEW_WIDTH));
+    }
+
+    // Drafts go to storage/drafts for reading over, and can be promoted to the outbox from there
+    fn save_draft(&self, label: &str, post: &Post) {
+        let draft = Draft::new(label, post, self.clock.now());
+        match self.drafts.append(&draft, post.image.as_deref()) {
+            Ok(path) => println!("Draft {} written to {}", draft.id, path.display()),
+            Err(e) => eprintln!("Failed to write draft {}: {}", label, e),
+        }
+    }
+
+    fn style(&self) -> MutexGuard<'_, StyleEngine> {
+        Self::lock(&self.style)
+    }
+
+    fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     fn get_fud_examples() -> Vec<&'static str> {
         vec![
             "Dev wallet holds 99.9% of supply (trust me bro)",
@@ -151,40 +534,501 @@ impl Runtime {
         ]
     }
 
-    pub fn add_agent(&mut self, prompt: &str) {
-        let agent = Agent::new(&self.anthropic_api_key, prompt);
-        self.agents.push(agent);
+    // Validate a character and rebuild the settings and agents from it. Nothing changes
+    // unless it loads cleanly, so a bad swap leaves the current character running.
+    pub fn use_character(&mut self, name: &str) -> Result<(), FudError> {
+        let report = CharacterValidator::validate_directory(name)?;
+        report.print(name);
+        if !report.is_ok() {
+            return Err(anyhow::anyhow!("Character '{}' failed validation", name).into());
+        }
+
+        let mut instruction_builder = InstructionBuilder::new();
+        instruction_builder
+            .build_instructions(name)
+            .map_err(|e| FudError::parse(format!("character '{}'", name), e))?;
+        self.set_character_settings(instruction_builder.get_character_settings().clone());
+        self.agents.clear();
+        self.add_agent(
+            instruction_builder.get_instructions(),
+            instruction_builder.get_generation_settings().clone(),
+            instruction_builder.get_sections().to_vec(),
+            instruction_builder.get_examples().clone(),
+        );
+        self.character_name = name.to_string();
+        Ok(())
     }
 
-    async fn should_allow_tweet(&self) -> bool {
-        match self.last_tweet_time {
-            None => true,
-            Some(last_tweet) => {
-                // Only allow tweet if at least 5 minutes have passed since last tweet
-                let duration = Utc::now().signed_duration_since(last_tweet);
-                duration.num_minutes() >= 5
+    // Swap characters while running and log it to memory; returns what to tell the requester
+    fn switch_character(&mut self, name: &str, source: &str) -> Result<String, FudError> {
+        if !CharacterSwitch::is_valid_name(name) {
+            return Err(FudError::NotFound(format!("character '{}'", name)));
+        }
+        if name == self.character_name {
+            return Ok(format!("Already running {}", name));
+        }
+
+        let from = self.character_name.clone();
+        self.use_character(name)?;
+        println!("Switched character from {} to {} ({})", from, name, source);
+        let swap = CharacterSwap {
+            from: from.clone(),
+            to: name.to_string(),
+            source: source.to_string(),
+            at: self.clock.now(),
+        };
+        if let Err(e) = MemoryStore::record_character_swap(&mut self.memory, swap) {
+            eprintln!("Failed to record character swap: {}", e);
+        }
+        Ok(format!("Switched character from {} to {}", from, name))
+    }
+
+    // Move the aggression dial, or put it back to the character's level, and keep it
+    // in memory so a restart doesn't undo it; returns what to tell the requester
+    fn run_aggression_command(&mut self, command: AggressionCommand, source: &str) -> String {
+        let level = match command {
+            AggressionCommand::Current => return Self::lock(&self.aggression).describe(),
+            AggressionCommand::Set(level) => Some(level),
+            AggressionCommand::Reset => None,
+        };
+        let mut aggression = Self::lock(&self.aggression);
+        aggression.set_override(level);
+        let scale = aggression.emoji_scale();
+        let reply = aggression.describe();
+        let level = aggression.override_level();
+        drop(aggression);
+        self.style().set_emoji_scale(scale);
+        println!("{} ({})", reply, source);
+        if let Err(e) = MemoryStore::set_aggression_override(&mut self.memory, level) {
+            eprintln!("Failed to save aggression level: {}", e);
+        }
+        self.drop_untamed_text();
+        reply
+    }
+
+    // Text written before the dial went down can hold words the new level bans.
+    // Pending outbox posts with one are abandoned; queued and held replies with one
+    // are dropped and the mention released, so a later sweep drafts it again.
+    fn drop_untamed_text(&mut self) {
+        let guard = StreamGuard::new(Self::lock(&self.aggression).tame_words());
+        if guard.is_empty() {
+            return;
+        }
+        match self.outbox.load() {
+            Ok(items) => {
+                for mut item in items.into_iter().filter(OutboxItem::is_pending) {
+                    let Some(term) = guard.violation(&item.text) else {
+                        continue;
+                    };
+                    println!("Dropping queued {} {}, it says '{}'", item.label, item.id, term);
+                    item.abandoned = true;
+                    if let Err(e) = self.outbox.save(&item).and_then(|_| self.outbox.remove_image(&item)) {
+                        eprintln!("Failed to update outbox item {}: {}", item.id, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to read the outbox: {}", e),
+        }
+
+        let queued: Vec<String> = self
+            .memory
+            .reply_queue
+            .iter()
+            .filter(|reply| guard.violation(&reply.text).is_some())
+            .map(|reply| reply.reply_to.clone())
+            .collect();
+        for reply_to in queued {
+            println!("Dropping the queued reply to {}, it's too harsh for the new level", reply_to);
+            match MemoryStore::remove_queued_reply(&mut self.memory, &reply_to) {
+                Ok(()) => self.release_responded(&reply_to),
+                Err(e) => eprintln!("Failed to drop the queued reply to {}: {}", reply_to, e),
+            }
+        }
+        let held: Vec<String> = self
+            .memory
+            .held_replies
+            .iter()
+            .filter(|held| guard.violation(&held.text).is_some())
+            .map(|held| held.reply_to.clone())
+            .collect();
+        for reply_to in held {
+            println!("Dropping the held reply to {}, it's too harsh for the new level", reply_to);
+            match MemoryStore::take_held_reply(&mut self.memory, &reply_to) {
+                Ok(_) => self.release_responded(&reply_to),
+                Err(e) => eprintln!("Failed to drop the held reply to {}: {}", reply_to, e),
+            }
+        }
+    }
+
+    // Flip a feature on or off and keep it in memory; returns what to tell the requester
+    fn run_flag_command(&mut self, command: FlagCommand, source: &str) -> String {
+        let FlagCommand::Set(flag, on) = command else {
+            return self.memory.feature_flags.describe();
+        };
+        let mut flags = self.memory.feature_flags.clone();
+        flags.set(flag, on);
+        let reply = format!("Turned {} {}", flag.name(), if on { "on" } else { "off" });
+        println!("{} ({})", reply, source);
+        if let Err(e) = MemoryStore::set_feature_flags(&mut self.memory, flags) {
+            eprintln!("Failed to save feature flags: {}", e);
+        }
+        reply
+    }
+
+    // Start a campaign, end it early or report on it; returns what to tell the requester.
+    // Ending early just moves the end up, so the recap still goes out.
+    async fn run_campaign_command(&mut self, command: CampaignCommand, source: &str) -> String {
+        let now = self.clock.now();
+        match command {
+            CampaignCommand::Status => match Campaigns::running(&self.memory.campaigns, now) {
+                Some(campaign) => campaign.status(now),
+                None => "No campaign running".to_string(),
+            },
+            CampaignCommand::Stop => {
+                let Some(i) = Campaigns::current(&self.memory.campaigns) else {
+                    return "No campaign running".to_string();
+                };
+                let campaign = &mut self.memory.campaigns[i];
+                campaign.ends_at = campaign.ends_at.min(now);
+                let reply = format!("Ending the campaign on ${}, recap to follow", campaign.symbol);
+                println!("{} ({})", reply, source);
+                if let Err(e) = MemoryStore::save_memory(&self.memory) {
+                    eprintln!("Failed to save campaign: {}", e);
+                }
+                reply
+            }
+            CampaignCommand::Start { mint, hours } => {
+                if let Some(i) = Campaigns::current(&self.memory.campaigns) {
+                    return format!("Already running a campaign on ${}; stop it first", self.memory.campaigns[i].symbol);
+                }
+                if self.is_own_token(&mint) {
+                    return "Not running a campaign against our own token".to_string();
+                }
+                let settings = &self.settings.campaigns;
+                let hours = hours.unwrap_or(settings.default_hours).min(settings.max_hours);
+                let token = match self.solana_tracker.get_token_by_address(&mint).await {
+                    Ok(token) => token,
+                    Err(e) => return format!("Couldn't look up {}: {}", mint, e),
+                };
+                let campaign = Campaign::start(&token.snapshot(), hours, source, now);
+                let reply = format!("Started a {}h campaign on ${}", hours, campaign.symbol);
+                println!("{} ({})", reply, source);
+                if let Err(e) = MemoryStore::start_campaign(&mut self.memory, campaign) {
+                    eprintln!("Failed to save campaign: {}", e);
+                }
+                reply
+            }
+        }
+    }
+
+    // Character, aggression, campaign and flag requests left by the CLI, and admin commands sent over Telegram
+    async fn handle_character_commands(&mut self, now: DateTime<Utc>) {
+        match CharacterSwitch::take_request() {
+            Ok(Some(name)) => {
+                if let Err(e) = self.switch_character(&name, "cli") {
+                    eprintln!("Couldn't switch character to {}: {}", name, e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to read character request: {}", e),
+        }
+        match Aggression::take_request() {
+            Ok(Some(command)) => {
+                self.run_aggression_command(command, "cli");
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to read aggression request: {}", e),
+        }
+        match Campaigns::take_request() {
+            Ok(Some(command)) => {
+                let reply = self.run_campaign_command(command, "cli").await;
+                println!("{}", reply);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to read campaign request: {}", e),
+        }
+        match FeatureSwitch::take_requests() {
+            Ok(commands) => {
+                for command in commands {
+                    self.run_flag_command(command, "cli");
+                }
+            }
+            Err(e) => eprintln!("Failed to read flags request: {}", e),
+        }
+
+        let due = Schedule::elapsed(self.last_command_poll, self.command_poll_interval(), now);
+        if !due || !self.health.is_available(Provider::Telegram) {
+            return;
+        }
+        let Some(telegram) = self.telegram.as_mut().filter(|telegram| telegram.polls_updates()) else {
+            return;
+        };
+        self.last_command_poll = Some(now);
+        let updates = match telegram.poll_updates().await {
+            Ok(updates) => updates,
+            Err(e) => {
+                eprintln!("Failed to poll Telegram commands: {}", e);
+                return;
+            }
+        };
+        self.answer_inline_queries(updates.inline_queries);
+        for _ in 0..self.inline_errors.swap(0, Ordering::SeqCst) {
+            self.record_api_error("telegram_inline");
+        }
+
+        for message in updates.messages {
+            let reply = if let Some(command) = Approvals::parse_command(&message.text) {
+                self.run_approval_command(command)
+            } else if let Some(command) = Aggression::parse_command(&message.text) {
+                self.run_aggression_command(command, &format!("telegram:{}", message.user_id))
+            } else if let Some(command) = Campaigns::parse_command(&message.text) {
+                self.run_campaign_command(command, &format!("telegram:{}", message.user_id)).await
+            } else if let Some(command) = FeatureSwitch::parse_command(&message.text) {
+                self.run_flag_command(command, &format!("telegram:{}", message.user_id))
+            } else {
+                match CharacterSwitch::parse_command(&message.text) {
+                    Some(CharacterCommand::Use(name)) => match self.switch_character(&name, &format!("telegram:{}", message.user_id)) {
+                        Ok(reply) => reply,
+                        Err(e) => format!("Couldn't switch to {}: {}", name, e),
+                    },
+                    Some(CharacterCommand::Current) => format!("Running character: {}", self.character_name),
+                    None => "Commands: /character use <name>, /character current, /aggression <1-10|reset>, /campaign start <mint> [hours], /campaign stop, /campaign, /flags <name> on|off, /flags, /pending, /approve <tweet id>, /reject <tweet id>".to_string(),
+                }
+            };
+            if let Some(telegram) = &self.telegram {
+                if let Err(e) = telegram.reply(message.chat_id, &reply).await {
+                    eprintln!("Failed to answer Telegram command: {}", e);
+                }
+            }
+        }
+    }
+
+    // Inline queries go stale within seconds, so Telegram is polled more often for them
+    fn command_poll_interval(&self) -> chrono::Duration {
+        if self.telegram.as_ref().is_some_and(Telegram::answers_inline) {
+            chrono::Duration::seconds(INLINE_POLL_SECONDS)
+        } else {
+            chrono::Duration::seconds(COMMAND_POLL_SECONDS)
+        }
+    }
+
+    // Each query is answered in its own task, so a slow model call doesn't hold up the
+    // loop or make later queries expire. Users past max_per_user_per_hour fresh answers,
+    // and queries beyond max_per_poll being written at once, get nothing.
+    fn answer_inline_queries(&mut self, queries: Vec<InlineRequest>) {
+        let settings = self.settings.telegram_inline.clone();
+        let (Some(telegram), Some(agent)) = (self.telegram.clone(), self.agents.first().cloned()) else {
+            return;
+        };
+        let responder = InlineResponder {
+            lookup: self.token_lookup(),
+            agent,
+            telegram,
+            cards: self.inline_cards.clone(),
+            cache_minutes: settings.cache_minutes,
+            own_mint: self.memory.token_address.clone(),
+            own_symbol: self.memory.token_symbol.clone(),
+        };
+        let now = self.clock.now();
+        for query in InlineFud::latest_per_user(queries) {
+            if self.inline_in_flight.load(Ordering::SeqCst) >= settings.max_per_poll {
+                println!("Already answering {} inline queries, skipping '{}'", settings.max_per_poll, query.query);
+                continue;
+            }
+            let fresh = responder.cached(&query.query, now).is_none();
+            if fresh && !self.inline_limit.allow(query.user_id, settings.max_per_user_per_hour, now) {
+                println!("Telegram user {} is over the inline limit, skipping '{}'", query.user_id, query.query);
+                continue;
             }
+            let responder = responder.clone();
+            let in_flight = self.inline_in_flight.clone();
+            let errors = self.inline_errors.clone();
+            in_flight.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                if let Err(e) = responder.answer(&query, now).await {
+                    eprintln!("Failed to answer inline query '{}': {}", query.query, e);
+                    errors.fetch_add(1, Ordering::SeqCst);
+                }
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    fn run_approval_command(&mut self, command: ApprovalCommand) -> String {
+        match command {
+            ApprovalCommand::Pending => Approvals::pending_summary(&self.memory.held_replies),
+            ApprovalCommand::Reject(tweet_id) => match MemoryStore::take_held_reply(&mut self.memory, &tweet_id) {
+                Ok(Some(_)) => {
+                    self.mark_responded(&tweet_id, ResponseState::Handled);
+                    format!("Dropped the reply to {}", tweet_id)
+                }
+                Ok(None) => format!("No reply held for {}", tweet_id),
+                Err(e) => format!("Couldn't drop the reply to {}: {}", tweet_id, e),
+            },
+            ApprovalCommand::Approve(tweet_id) => {
+                let held = match MemoryStore::take_held_reply(&mut self.memory, &tweet_id) {
+                    Ok(Some(held)) => held,
+                    Ok(None) => return format!("No reply held for {}", tweet_id),
+                    Err(e) => return format!("Couldn't release the reply to {}: {}", tweet_id, e),
+                };
+                let spacing = self.settings.reply_queue.spacing();
+                let token = held.token_mint.map(|mint| (mint, held.token_symbol.unwrap_or_default()));
+                self.mark_responded(&held.reply_to, ResponseState::Queued);
+                match MemoryStore::enqueue_reply(&mut self.memory, held.reply_to, &held.text, &held.prompt, token, spacing, self.clock.now()) {
+                    Ok(scheduled_at) => format!("Queued the reply to {} for {}", tweet_id, scheduled_at.format("%H:%M:%S")),
+                    Err(e) => format!("Couldn't queue the reply to {}: {}", tweet_id, e),
+                }
+            }
+        }
+    }
+
+    pub fn add_agent(
+        &mut self,
+        prompt: &str,
+        generation: GenerationSettings,
+        mut sections: Vec<PromptSection>,
+        examples: ExampleRotation,
+    ) {
+        // Best-received posts go in just ahead of the suffix instructions
+        let few_shot = &self.settings.few_shot;
+        let few_shot_section = if few_shot.enabled {
+            DatasetBuilder::few_shot_section(&self.memory, few_shot.examples, few_shot.min_engagement)
+        } else {
+            None
+        };
+        let prompt = match few_shot_section {
+            Some(section) => {
+                println!("Adding {} high-engagement posts as examples to the prompt", section.lines.len());
+                sections.insert(sections.len().saturating_sub(1), section);
+                prompt_budget::render(&sections)
+            }
+            None => prompt.to_string(),
+        };
+
+        if !examples.is_empty() {
+            println!("Rotating {} example posts from the character's examples/ into the prompt", examples.len());
+        }
+
+        let agent = Agent::new(&self.anthropic_api_key, &prompt, generation)
+            .with_style(self.style.clone())
+            .with_avoided_topics(self.avoided_topics.clone())
+            .with_clock(self.clock.clone())
+            .with_aggression(self.aggression.clone())
+            .with_tracer(self.tracer.clone())
+            .with_audit(self.audit.clone())
+            .with_llm_timeout(self.settings.latency_budget.limit(self.settings.latency_budget.llm_seconds))
+            .with_language(self.settings.language.clone())
+            .with_stream_guard(&self.settings.stream_guard, &self.settings.banned_words)
+            .with_pipeline(Pipeline::from_settings(&self.settings.pipeline))
+            .with_http(self.anthropic_http.clone())
+            .with_examples(examples)
+            .with_prompt_budget(sections, self.settings.prompt_budget.max_input_tokens);
+        self.agents.push(Arc::new(agent));
+    }
+
+    // Best effort: a Telegram outage shouldn't affect posting on Twitter
+    async fn mirror_to_telegram(&self, text: &str, tweet_id: &str, image: Option<Vec<u8>>) {
+        let Some(telegram) = &self.telegram else {
+            return;
+        };
+        if !telegram.has_channel() || !self.health.is_available(Provider::Telegram) {
+            return;
+        }
+        if let Err(e) = telegram.mirror_tweet(text, tweet_id, image).await {
+            eprintln!("Failed to mirror tweet {} to Telegram: {}", tweet_id, e);
+        }
+    }
+
+    async fn send_fud_to_discord(&self, token: &TokenResponse, text: &str, tweet_id: &str, image: Option<Vec<u8>>) {
+        if !self.discord.is_enabled() {
+            return;
+        }
+        if let Err(e) = self.discord.post_fud(&token.snapshot(), text, Some(tweet_id), image).await {
+            eprintln!("Failed to send FUD to Discord: {}", e);
+        }
+    }
+
+    pub fn set_character_settings(&mut self, settings: CharacterSettings) {
+        // Swap the contents so agents already holding the engine see the new limits
+        let mut style = self.style();
+        let mut engine = StyleEngine::from_settings(&settings.phrase_decay).with_decoration(settings.decoration.clone());
+        let mut aggression = Self::lock(&self.aggression);
+        aggression.set_settings(settings.aggression.clone());
+        engine.set_emoji_scale(aggression.emoji_scale());
+        drop(aggression);
+        if let Some(history) = style.take_history() {
+            engine = engine.with_history(history, self.clock.now());
         }
+        *style = engine;
+        drop(style);
+        if let Some(telegram) = self.telegram.as_mut() {
+            telegram.set_inline(settings.telegram_inline.enabled);
+        }
+        self.settings = settings;
+    }
+
+    async fn should_allow_tweet(&self) -> bool {
+        // Only allow tweet if at least 5 minutes have passed since last tweet
+        Schedule::elapsed(self.last_tweet_time, chrono::Duration::minutes(TWEET_COOLDOWN_MINUTES), self.clock.now())
     }
 
     //  Method to check if it's time for scheduled actions
     async fn should_run_scheduled_action(&self, minutes: &[u32]) -> bool {
-        let now = Utc::now();
-        let is_minute_mark = minutes.contains(&now.minute()) && now.second() == 0;
+        let Some((since, now)) = self.schedule_window else {
+            return false;
+        };
+        let is_due = Schedule::mark_between(minutes, since, now);
         // Only log when we're at a minute we care about
-        if now.second() == 0 && minutes.contains(&now.minute()) {
-            println!("Scheduled check at {:02}:{:02} - {}", 
-                now.hour(), 
-                now.minute(),
-                if is_minute_mark { "Running" } else { "Waiting" }
-            );
+        if is_due {
+            println!("Scheduled check at {} - Running", self.local_time().show(now));
+        }
+        is_due
+    }
+
+    // Everything the loop has to wake up for next, scheduled minute marks first
+    fn upcoming_events(&self, after: DateTime<Utc>) -> Vec<Option<DateTime<Utc>>> {
+        let mut events = vec![Schedule::next_minute_mark(FUD_SCHEDULE_MINUTES, after)];
+        let network = &self.settings.network_fud;
+        if network.enabled {
+            events.push(Schedule::next_minute_mark(&[network.minute], after));
+        }
+        let trends = &self.settings.trend_engagement;
+        if trends.enabled {
+            events.push(Schedule::next_minute_mark(&trends.schedule_minutes, after));
+        }
+        let recap = &self.settings.weekly_recap;
+        if recap.enabled {
+            events.push(Schedule::next_minute_mark(&[recap.minute], after));
+        }
+        events.push(
+            self.last_notification_check
+                .map(|last| last + chrono::Duration::minutes(NOTIFICATION_INTERVAL_MINUTES)),
+        );
+        if self.telegram.as_ref().is_some_and(Telegram::polls_updates) {
+            events.push(self.last_command_poll.map(|last| last + self.command_poll_interval()));
         }
-        is_minute_mark
+        events.push(self.memory.reply_queue.iter().map(|reply| reply.scheduled_at).min());
+        events
+    }
+
+    // Scheduled posts, network FUD, trend replies and recaps wait out the quiet hours
+    fn in_quiet_hours(&self) -> bool {
+        Schedule::is_quiet(&self.settings.quiet_hours, self.local_time().at(self.clock.now()))
+    }
+
+    // The timezone schedule hours are read in and times are logged in
+    fn local_time(&self) -> LocalTime {
+        LocalTime::from_settings(&self.settings)
+    }
+
+    // When the next scheduled post is due to go out, past quiet hours and any adaptive hold
+    fn next_scheduled_post(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Schedule::next_scheduled_post(&self.settings, self.memory.next_tweet, now)
     }
 
-    pub async fn run(&mut self) -> Result<(), anyhow::Error> {
+    pub async fn run(&mut self) -> Result<(), FudError> {
         if self.agents.is_empty() {
-            return Err(anyhow::anyhow!("No agents available"));
+            return Err(FudError::NotFound("agent".to_string()));
         }
     
         // Check if enough time has passed since last tweet
@@ -197,31 +1041,42 @@ impl Runtime {
         let selected_agent = &self.agents[rng.gen_range(0..self.agents.len())];
         
         // This is where we decide what to tweet
-        let tweet_content = if rng.gen_bool(0.5) {
-            // Use the agent's normal post
-            selected_agent
-                .generate_post()
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to generate post: {}", e))?
-        } else {
-            // Get tokens and generate FUD
-            let tokens = self.solana_tracker.get_top_tokens(35).await?;
-            let random_token = tokens.get(rng.gen_range(0..tokens.len()))
-                .ok_or_else(|| anyhow::anyhow!("No tokens available"))?;
-            self.solana_tracker.generate_fud(random_token)
+        let mut attempts = 0;
+        let tweet_content = loop {
+            let content = if rng.gen_bool(0.5) {
+                // Use the agent's normal post
+                selected_agent
+                    .generate_post()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to generate post: {}", e))?
+            } else {
+                // Get tokens and generate FUD
+                let tokens = self.solana_tracker.get_top_tokens(35).await?;
+                let random_token = tokens.get(rng.gen_range(0..tokens.len()))
+                    .ok_or_else(|| FudError::NotFound("trending token".to_string()))?;
+                self.solana_tracker.generate_fud(random_token)
+            };
+
+            if !self.style().repeats_recent_phrase(&content) || attempts >= 2 {
+                break content;
+            }
+            println!("Generated repetitive post, retrying...");
+            attempts += 1;
         };
     
         println!("Generated tweet content: {}", tweet_content);
+        self.style().record(&tweet_content);
     
         // Only proceed with tweeting if tweet_mode is true
         if self.memory.tweet_mode {
             match self.twitter.tweet(tweet_content.clone()).await {
                 Ok(tweet_result) => {
                     // Update last tweet time
-                    self.last_tweet_time = Some(Utc::now());
+                    self.last_tweet_time = Some(self.clock.now());
                     
                     // Get the tweet ID from the tweet result
                     let twitter_id = Some(tweet_result.id.to_string());
+                    self.mirror_to_telegram(&tweet_content, &tweet_result.id.to_string(), None).await;
     
                     // Save to memory
                     match MemoryStore::add_to_memory(
@@ -229,6 +1084,7 @@ impl Runtime {
                         &tweet_content,
                         &selected_agent.prompt,
                         twitter_id,
+                        self.clock.now(),
                     ) {
                         Ok(_) => println!("Response saved to memory."),
                         Err(e) => eprintln!("Failed to save response to memory: {}", e),
@@ -238,7 +1094,7 @@ impl Runtime {
                     Ok(())
                 }
                 Err(e) => {
-                    if e.to_string().contains("429") {
+                    if e.is_rate_limited() {
                         println!("Rate limit hit, waiting 15 minutes before retrying...");
                         sleep(Duration::from_secs(15 * 60)).await;
                         Ok(())
@@ -249,11 +1105,14 @@ impl Runtime {
             }
         } else {
             // If tweet_mode is false, just save to memory without tweeting
+            Self::print_preview(&tweet_content);
+            self.save_draft("tweet", &Post::text(tweet_content.clone()));
             match MemoryStore::add_to_memory(
                 &mut self.memory,
                 &tweet_content,
                 &selected_agent.prompt,
                 None,
+                self.clock.now(),
             ) {
                 Ok(_) => println!("Response saved to memory (tweet_mode disabled)."),
                 Err(e) => eprintln!("Failed to save response to memory: {}", e),
@@ -262,577 +1121,3324 @@ impl Runtime {
         }
     }
 
-    async fn ensure_user_id(&mut self) -> Result<u64, anyhow::Error> {
+    async fn ensure_user_id(&mut self) -> Result<u64, FudError> {
         if let Some(id) = self.cached_user_id {
             Ok(id)
         } else {
             let user = self.twitter.get_user_id().await?;
             let numeric_id = match user.to_string().parse::<u64>() {
                 Ok(id) => id,
-                Err(_) => return Err(anyhow::anyhow!("Failed to parse user ID")),
+                Err(e) => return Err(FudError::parse("user ID", e)),
             };
             self.cached_user_id = Some(numeric_id);
             Ok(numeric_id)
         }
     }
 
-    async fn should_check_notifications(&self) -> bool {
-        match self.last_notification_check {
-            None => true,
-            Some(last_check) => {
-                let duration = Utc::now().signed_duration_since(last_check);
-                duration.num_minutes() >= 5
-            }
+    // After a crash memory can be behind what actually posted: fill in tweet IDs,
+    // drop queued replies that already went out and restart the post cooldown
+    // from the newest real tweet, so nothing is posted twice
+    async fn sync_with_timeline(&mut self) {
+        let settings = self.settings.timeline_sync.clone();
+        if !settings.enabled || !self.memory.tweet_mode || !self.providers_available(&[Provider::Twitter]) {
+            return;
         }
-    }
-
-    async fn handle_notifications(&mut self) -> Result<(), anyhow::Error> {
-        if self.agents.is_empty() {
-            return Err(anyhow::anyhow!("No agents available"));
+        if !self.quota_allows("timeline sync", QuotaPriority::Sweep, &[ENDPOINT_TIMELINE]) {
+            return;
         }
-    
-        // Only proceed if enough time has passed since last check
-        if !self.should_check_notifications().await {
-            return Ok(());
+        let timeline = match self.ensure_user_id().await {
+            Ok(user_id) => self.twitter.get_own_timeline(user_id, settings.max_tweets).await,
+            Err(e) => Err(e),
+        };
+        let timeline = match timeline {
+            Ok(timeline) => timeline,
+            Err(e) => {
+                eprintln!("Couldn't fetch our timeline to check memory against: {}", e);
+                return;
+            }
+        };
+
+        let report = TimelineSync::reconcile(&mut self.memory, &timeline, self.clock.now());
+        self.mark_outbox_posted(&timeline);
+        if report.changed() {
+            println!(
+                "Timeline sync: {} tweet IDs filled in, {} queued replies already sent, {} tweets missing from memory",
+                report.backfilled, report.dequeued, report.recovered
+            );
+            if let Err(e) = MemoryStore::save_memory(&self.memory) {
+                eprintln!("Failed to save synced memory: {}", e);
+            }
+        } else {
+            println!("Timeline sync: memory matches the last {} tweets", timeline.len());
         }
-    
-        let user_id = self.ensure_user_id().await?;
-        
-        match self.twitter.get_notifications(user_id).await {
-            Ok(notifications) => {
-                self.last_notification_check = Some(Utc::now());
-                
-                // Process notifications...
-                let new_notifications: Vec<_> = notifications
-                    .into_iter()
-                    .filter(|tweet| !self.processed_tweets.contains(&tweet.id.to_string()))
-                    .collect();
-    
-                println!("Found {} new notifications", new_notifications.len());
-    
-                // Take up to 3 notifications to process
-                let notifications_to_process = &new_notifications[..new_notifications.len().min(3)];
-                
-                for tweet in notifications_to_process {
-                    let tweet_id = tweet.id.to_string();
-                    let selected_agent = &mut self.agents[0];  // Changed to mut reference
-    
-                    match selected_agent.should_respond(&tweet.text).await? {
-                        ResponseDecision::Respond => {
-                            println!("Generating reply to: {}", tweet.text);
-                            let reply = selected_agent.generate_reply(&tweet.text).await?;
-    
-                            // Save to memory as a reply
-                            if let Err(e) = MemoryStore::add_reply_to_memory(
-                                &mut self.memory,
-                                &reply,
-                                &selected_agent.prompt,
-                                Some(tweet_id.clone()),
-                                tweet.id.to_string(),
-                            ) {
-                                eprintln!("Failed to save response to memory: {}", e);
-                            }
-    
-                            match self.twitter.reply_to_tweet(&tweet_id, reply.to_string()).await {
-                                Ok(_) => {
-                                    println!("Successfully replied to tweet {}", tweet_id);
-                                    // Add a delay between replies to avoid rate limits
-                                    sleep(Duration::from_secs(30)).await;
-                                }
-                                Err(e) => {
-                                    if e.to_string().contains("429") {
-                                        println!("Rate limit hit, stopping notification processing");
-                                        break;
-                                    } else {
-                                        println!("Error sending reply: {}", e);
-                                    }
-                                }
-                            }
-                        }
-                        ResponseDecision::Ignore => {
-                            println!("Agent decided to ignore tweet: {}", tweet.text);
-                        }
-                    }
-    
-                    self.processed_tweets.insert(tweet_id);
-                }
-    
-                // Save all processed tweets at the end
-                MemoryStore::save_processed_tweets(&self.processed_tweets)?;
-                
-                Ok(())
+        if let Some(newest) = report.newest {
+            if self.last_tweet_time.is_none_or(|last| last < newest) {
+                println!("Last tweet on the timeline was at {}", newest.format("%Y-%m-%d %H:%M:%S UTC"));
+                self.last_tweet_time = Some(newest);
             }
+        }
+    }
+
+    // Queued posts that went out before the bot could record it, so they aren't sent twice
+    fn mark_outbox_posted(&self, timeline: &[TimelinePost]) {
+        let items = match self.outbox.load() {
+            Ok(items) => items,
             Err(e) => {
-                if e.to_string().contains("429") {
-                    println!("Rate limit hit for notifications, will retry in 15 minutes");
-                    self.last_notification_check = Some(Utc::now());
-                    Ok(())
-                } else {
-                    Err(e)
-                }
+                eprintln!("Failed to read the outbox: {}", e);
+                return;
+            }
+        };
+        for mut item in items.into_iter().filter(OutboxItem::is_pending) {
+            let Some(post) = TimelineSync::find_posted(&item.text, timeline) else {
+                continue;
+            };
+            println!("Queued {} already went out as {}", item.label, post.id);
+            item.mark_delivered(&post.id, post.created_at.unwrap_or_else(Utc::now));
+            if let Err(e) = self.outbox.save(&item).and_then(|_| self.outbox.remove_image(&item)) {
+                eprintln!("Failed to update outbox item {}: {}", item.id, e);
             }
         }
-    
     }
 
-    fn schedule_next_tweet(&mut self) {
+    async fn should_check_notifications(&self) -> bool {
+        Schedule::elapsed(
+            self.last_notification_check,
+            chrono::Duration::minutes(NOTIFICATION_INTERVAL_MINUTES),
+            self.clock.now(),
+        )
+    }
+
+    fn schedule_next_tweet(&mut self) {
         let mut rng = rand::thread_rng();
         let delay_secs = rng.gen_range(5 * 60..15 * 60); 
-        let next_tweet = Utc::now() + chrono::Duration::seconds(delay_secs as i64);
+        let next_tweet = self.clock.now() + chrono::Duration::seconds(delay_secs as i64);
         self.memory.next_tweet = Some(next_tweet);
 
-        // Save the updated next_tweet time
-        if let Err(e) = MemoryStore::save_memory(&self.memory) {
-            eprintln!("Failed to save next tweet time: {}", e);
+        // Save the updated next_tweet time
+        if let Err(e) = MemoryStore::save_memory(&self.memory) {
+            eprintln!("Failed to save next tweet time: {}", e);
+        }
+    }
+
+    async fn wait_until_next_tweet(&self) -> bool {
+        if let Some(next_tweet) = self.memory.next_tweet {
+            let now = self.clock.now();
+            if next_tweet > now {
+                let duration = next_tweet.signed_duration_since(now);
+                if duration.num_seconds() > 0 {
+                    sleep(Duration::from_secs(duration.num_seconds() as u64)).await;
+                }
+                true
+            } else {
+                true // Time has passed, ready to tweet
+            }
+        } else {
+            false // No scheduled tweet
+        }
+    }
+
+    pub async fn get_trending_solana_summary(&self) -> Result<String, FudError> {
+        let tokens = self.solana_tracker.get_top_tokens(5).await?;
+        Ok(self.solana_tracker.format_tokens_summary(&tokens, 5))
+    }
+
+    pub async fn run_periodically(&mut self) -> Result<(), FudError> {
+        println!("=== Starting FUD Bot ===");
+        println!("Character type: {}", self.character_config.name);
+        println!("Tweet mode enabled: {}", self.memory.tweet_mode);
+        println!("Debug mode enabled: {}", self.memory.debug_mode);
+        println!("Number of agents: {}", self.agents.len());
+        let now = self.clock.now();
+        println!("Schedule timezone: {} (now {})", self.local_time().name(), self.local_time().show_with_utc(now));
+        if let Some(next) = self.next_scheduled_post(now) {
+            println!("Next scheduled post: {}", self.local_time().show_with_utc(next));
+        }
+        
+        if let Some(last_time) = self.last_tweet_time {
+            println!("Last tweet time: {:?}", last_time);
+        } else {
+            println!("No previous tweets recorded");
+        }
+        println!("======================\n");
+
+        self.run_preflight(&Provider::ALL).await;
+        self.health.print();
+        self.publish_status();
+        if let Some(addr) = self.status_addr {
+            let status = self.status.clone();
+            let admin_token = self.status_admin_token.clone();
+            tokio::spawn(async move {
+                let stale_after = chrono::Duration::minutes(STATUS_STALE_MINUTES);
+                if let Err(e) = StatusServer::serve(addr, status, stale_after, admin_token).await {
+                    eprintln!("Status endpoints stopped: {}", e);
+                }
+            });
+        }
+        self.sync_with_timeline().await;
+
+        // Run debug test if conditions are met
+        if self.memory.debug_mode && !self.memory.tweet_mode {
+            self.run_debug_test().await?;
+            return Ok(());  // Exit after debug test
+        }
+        
+        // Original periodic run loop
+        loop {
+            let now = self.clock.now();
+            // Scheduled actions fire for any of their minute marks since the last cycle started
+            let since = self.schedule_window.map_or(now - chrono::Duration::seconds(1), |(_, last)| last);
+            self.schedule_window = Some((since, now));
+            
+            if self.character_config.name == "fud" {
+                self.recheck_degraded_providers().await;
+                self.handle_character_commands(now).await;
+
+                let quiet = self.in_quiet_hours();
+
+                if !quiet
+                    && self.should_run_scheduled_action(FUD_SCHEDULE_MINUTES).await
+                    && self.providers_available(&[Provider::Twitter, Provider::Anthropic])
+                {
+                    println!("Starting FUD generation attempt at {}...", self.local_time().show(now));
+                    // A running campaign takes its share of the slots, and those go out
+                    // whatever the adaptive schedule says
+                    let campaign_slot = Campaigns::running(&self.memory.campaigns, now).is_some()
+                        && thread_rng().gen_bool(self.settings.campaigns.target_share.clamp(0.0, 1.0));
+                    
+                    if !self.should_allow_tweet().await {
+                        println!("Rate limit cooldown in effect, skipping this cycle");
+                    } else if let Some(next) = self.adaptive_post_hold(now).filter(|_| !campaign_slot) {
+                        println!("Adaptive schedule: holding scheduled posts until {}", self.local_time().show_with_utc(next));
+                    } else if self.quota_allows("scheduled post", QuotaPriority::Post, &[ENDPOINT_TWEETS]) {
+                        self.tracer.start_trace("scheduled_post");
+                        let result = if campaign_slot {
+                            self.run_campaign_post().await
+                        } else {
+                            self.run_scheduled_post().await
+                        };
+                        self.tracer.finish_trace(result.as_ref().err());
+                        match result {
+                            Ok(_) => {
+                                println!("Successfully completed scheduled post cycle");
+                                self.adapt_post_interval(now);
+                            }
+                            Err(e) => {
+                                eprintln!("Error generating FUD: {}", e);
+                                self.record_api_error("fud");
+                            }
+                        }
+                    }
+                }
+
+                let network = &self.settings.network_fud;
+                if network.enabled
+                    && !quiet
+                    && network.schedule_hours.contains(&self.local_time().hour(now))
+                    && self.providers_available(&[Provider::Twitter, Provider::Anthropic])
+                    && self.should_run_scheduled_action(&[network.minute]).await
+                {
+                    if !self.should_allow_tweet().await {
+                        println!("Rate limit cooldown in effect, skipping network FUD");
+                    } else if self.quota_allows("network FUD", QuotaPriority::Post, &[ENDPOINT_TWEETS]) {
+                        if let Err(e) = self.run_network_fud().await {
+                            eprintln!("Error posting network FUD: {}", e);
+                            self.record_api_error("network");
+                        }
+                    }
+                }
+
+                if self.should_sweep_storage() {
+                    self.sweep_storage();
+                }
+
+                if self.providers_available(&[Provider::Twitter]) && self.should_sync_blocks() {
+                    if !self.quota_allows("block sync", QuotaPriority::Sweep, &[ENDPOINT_BLOCKS]) {
+                        self.last_block_sync = Some(now);
+                    } else if let Err(e) = self.sync_blocked_users().await {
+                        eprintln!("Error syncing Twitter blocks and mutes: {}", e);
+                        self.record_api_error("blocks");
+                    }
+                }
+
+                if self.providers_available(&[Provider::Twitter])
+                    && self.memory.feature_flags.notification_replies
+                    && self.should_check_notifications().await
+                {
+                    // First to go when the quota runs low: sweeps read mentions and spend replies
+                    if !self.quota_allows("notification sweep", QuotaPriority::Sweep, &[ENDPOINT_MENTIONS, ENDPOINT_TWEETS]) {
+                        self.last_notification_check = Some(now);
+                    } else {
+                        self.tracer.start_trace("notifications");
+                        let result = self.handle_notifications_fud().await;
+                        self.tracer.finish_trace(result.as_ref().err());
+                        if let Err(e) = result {
+                            eprintln!("Error handling FUD notifications: {}", e);
+                            self.record_api_error("notifications");
+                        }
+                    }
+                }   
+
+                if self.providers_available(&[Provider::Twitter]) {
+                    self.process_outbox().await;
+                    if let Err(e) = self.process_reply_queue().await {
+                        eprintln!("Error sending queued reply: {}", e);
+                        self.record_api_error("reply_queue");
+                    }
+                }
+
+                if self.providers_available(&[Provider::SolanaTracker]) && self.should_check_whale_alerts() {
+                    if !self.quota_allows("whale alerts", QuotaPriority::Reply, &[ENDPOINT_TWEETS]) {
+                        self.last_whale_check = Some(now);
+                    } else if let Err(e) = self.check_whale_alerts().await {
+                        eprintln!("Error checking whale alerts: {}", e);
+                        self.record_api_error("whale_alerts");
+                    }
+                }
+
+                if self.settings.trend_engagement.enabled
+                    && !quiet
+                    && self.providers_available(&[Provider::Twitter, Provider::SolanaTracker])
+                    && self.should_run_scheduled_action(&self.settings.trend_engagement.schedule_minutes).await
+                    && self.quota_allows("trend engagement", QuotaPriority::Sweep, &[ENDPOINT_SEARCH, ENDPOINT_TWEETS])
+                {
+                    self.tracer.start_trace("trend_engagement");
+                    let result = self.engage_with_trends().await;
+                    self.tracer.finish_trace(result.as_ref().err());
+                    if let Err(e) = result {
+                        eprintln!("Error engaging with trends: {}", e);
+                        self.record_api_error("trends");
+                    }
+                }
+
+                let recap = &self.settings.weekly_recap;
+                if !quiet
+                    && WeeklyRecap::is_due(recap, self.memory.last_weekly_recap, self.local_time().at(now))
+                    && self.providers_available(&[Provider::Twitter, Provider::SolanaTracker, Provider::Anthropic])
+                    && self.should_run_scheduled_action(&[recap.minute]).await
+                    && self.quota_allows("weekly recap", QuotaPriority::Post, &[ENDPOINT_TWEETS])
+                {
+                    if let Err(e) = self.post_weekly_recap().await {
+                        eprintln!("Error posting weekly recap: {}", e);
+                        self.record_api_error("weekly_recap");
+                    }
+                }
+
+                if self.providers_available(&[Provider::SolanaTracker]) {
+                    if let Err(e) = self.track_campaign(quiet).await {
+                        eprintln!("Error tracking campaign: {}", e);
+                        self.record_api_error("campaign");
+                    }
+                }
+
+                if self.providers_available(&[Provider::SolanaTracker, Provider::Anthropic]) && self.should_check_predictions() {
+                    if let Err(e) = self.check_predictions().await {
+                        eprintln!("Error grading predictions: {}", e);
+                        self.record_api_error("predictions");
+                    }
+                }
+
+                if self.providers_available(&[Provider::Anthropic]) && self.should_check_consistency() {
+                    if let Err(e) = self.check_consistency().await {
+                        eprintln!("Error checking persona consistency: {}", e);
+                        self.record_api_error("consistency");
+                    }
+                }
+
+                if self.providers_available(&[Provider::Twitter]) && self.should_refresh_metrics() {
+                    if !self.quota_allows("metrics refresh", QuotaPriority::Sweep, &[ENDPOINT_TWEET_LOOKUP]) {
+                        self.last_metrics_refresh = Some(now);
+                    } else if let Err(e) = self.refresh_engagement_metrics().await {
+                        eprintln!("Error refreshing engagement metrics: {}", e);
+                        self.record_api_error("metrics");
+                    }
+                }
+
+                if !quiet && self.providers_available(&[Provider::Anthropic]) {
+                    self.fill_content_buffer().await;
+                }
+
+                self.collect_generation_stats();
+                self.flush_api_calls();
+            }
+            self.publish_status();
+
+            // Sleep until the next thing is due; a mark that passed during a slow cycle is due now
+            let wake = Schedule::next_wake(now, self.upcoming_events(now), chrono::Duration::seconds(MAX_IDLE_SECONDS));
+            if let Ok(until_wake) = wake.signed_duration_since(self.clock.now()).to_std() {
+                sleep(until_wake).await;
+            }
+        }
+    }
+
+    // Hands the status endpoints what this cycle ended with
+    fn publish_status(&self) {
+        let outbox = match self.outbox.load() {
+            Ok(items) => items.iter().filter(|item| item.is_pending()).count(),
+            Err(e) => {
+                eprintln!("Failed to read the outbox: {}", e);
+                0
+            }
+        };
+        let mut status = Self::lock(&self.status);
+        status.updated_at = self.clock.now();
+        status.set_health(&self.health);
+        status.last_post_at = self.last_tweet_time;
+        status.reply_queue = self.memory.reply_queue.len();
+        status.held_replies = self.memory.held_replies.len();
+        status.outbox = outbox;
+        status.flags = self.memory.feature_flags.clone();
+        status.local_time = self.local_time();
+        status.next_post_at = self.next_scheduled_post(status.updated_at);
+        status.storage = self.storage_report.clone();
+        status.trending_cache = self.solana_tracker.trending_cache_metrics();
+    }
+
+    async fn run_preflight(&mut self, providers: &[Provider]) {
+        for provider in providers {
+            if *provider == Provider::Telegram && self.telegram.is_none() {
+                self.health.set(*provider, ProviderStatus::Disabled);
+                continue;
+            }
+            let status = match self.check_provider(*provider).await {
+                Ok(detail) => ProviderStatus::Ok(detail),
+                Err(e) => ProviderStatus::Degraded(e.to_string()),
+            };
+            self.health.set(*provider, status);
+        }
+        self.health.checked_at = Some(self.clock.now());
+    }
+
+    async fn check_provider(&mut self, provider: Provider) -> Result<String, FudError> {
+        match provider {
+            Provider::Twitter => {
+                let (id, username) = self.twitter.verify_credentials().await?;
+                self.cached_user_id = Some(id);
+                Ok(format!("@{}", username))
+            }
+            Provider::SolanaTracker => {
+                self.solana_tracker.check_api_key().await?;
+                Ok(String::new())
+            }
+            Provider::Anthropic => match self.agents.first() {
+                Some(agent) => {
+                    agent.verify_api_key().await?;
+                    Ok(String::new())
+                }
+                None => Ok("no agents loaded".to_string()),
+            },
+            Provider::Telegram => match &self.telegram {
+                Some(telegram) => Ok(format!("@{}", telegram.verify().await?)),
+                None => Ok(String::new()),
+            },
+        }
+    }
+
+    async fn recheck_degraded_providers(&mut self) {
+        let degraded = self.health.degraded();
+        if degraded.is_empty() {
+            return;
+        }
+        if !Schedule::elapsed(self.health.checked_at, chrono::Duration::minutes(HEALTH_RECHECK_MINUTES), self.clock.now()) {
+            return;
+        }
+
+        self.run_preflight(&degraded).await;
+        for provider in degraded {
+            match self.health.status(provider) {
+                ProviderStatus::Degraded(reason) => eprintln!("{} still degraded: {}", provider.name(), reason),
+                _ => println!("{} is healthy again, resuming", provider.name()),
+            }
+        }
+    }
+
+    fn providers_available(&self, providers: &[Provider]) -> bool {
+        providers.iter().all(|provider| self.health.is_available(*provider))
+    }
+
+    fn record_api_error(&mut self, source: &str) {
+        if let Err(e) = MemoryStore::record_api_error(&mut self.memory, source) {
+            eprintln!("Failed to record error stats: {}", e);
+        }
+    }
+
+    // When the adaptive schedule wants the next scheduled post to wait
+    fn adaptive_post_hold(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.settings.adaptive_schedule.enabled {
+            return None;
+        }
+        self.memory.next_tweet.filter(|next| now < *next)
+    }
+
+    // Re-read recent engagement and push the next scheduled post out (or pull it in)
+    fn adapt_post_interval(&mut self, now: DateTime<Utc>) {
+        let settings = &self.settings.adaptive_schedule;
+        if !settings.enabled {
+            return;
+        }
+        let engagement = AdaptiveSchedule::recent_engagement(settings, &self.memory, now);
+        let current = self.memory.post_interval_minutes.unwrap_or(settings.min_interval_minutes);
+        let (interval, change) = AdaptiveSchedule::next_interval(settings, current, engagement);
+        let engagement = engagement.map_or("not enough data".to_string(), |avg| format!("{:.1} avg engagement", avg));
+        match change {
+            IntervalChange::Widened => println!("Adaptive schedule: {}, widening post interval to {} minutes", engagement, interval),
+            IntervalChange::Tightened => println!("Adaptive schedule: {}, tightening post interval to {} minutes", engagement, interval),
+            IntervalChange::Unchanged => println!("Adaptive schedule: {}, keeping post interval at {} minutes", engagement, interval),
+        }
+
+        self.memory.post_interval_minutes = Some(interval);
+        self.memory.next_tweet = Some(now + chrono::Duration::minutes(interval));
+        if let Err(e) = MemoryStore::save_memory(&self.memory) {
+            eprintln!("Failed to save next tweet time: {}", e);
+        }
+    }
+
+    // Move the Twitter client's call counts into the persisted quota ledger, and with a
+    // shared limiter bring in what the other instances spent
+    fn flush_api_calls(&mut self) {
+        let calls = self.twitter.take_calls();
+        let now = self.clock.now();
+        if self.rate_limiter.record(&mut self.memory.api_quota, &self.settings.api_quota, &calls, now) {
+            if let Err(e) = MemoryStore::save_memory(&self.memory) {
+                eprintln!("Failed to save API quota ledger: {}", e);
+            }
+        }
+    }
+
+    // False (and logged) when the quota says the job should sit this one out
+    fn quota_allows(&mut self, job: &str, priority: QuotaPriority, endpoints: &[&str]) -> bool {
+        self.flush_api_calls();
+        match QuotaLedger::check(&self.memory.api_quota, &self.settings.api_quota, priority, endpoints, self.clock.now()) {
+            Some(block) => {
+                println!("Skipping {}: {}", job, block);
+                false
+            }
+            None => true,
+        }
+    }
+
+    // Move the agents' model call timings into memory for the stats command
+    fn collect_generation_stats(&mut self) {
+        let (mut count, mut total_ms, mut errors) = (0, 0, 0);
+        for agent in &self.agents {
+            let stats = agent.take_generation_stats();
+            count += stats.count;
+            total_ms += stats.total_ms;
+            errors += stats.errors;
+        }
+        if count == 0 {
+            return;
+        }
+        if let Err(e) = MemoryStore::record_generations(&mut self.memory, count, total_ms, errors) {
+            eprintln!("Failed to record generation stats: {}", e);
+        }
+    }
+
+    fn extract_ticker_or_address(text: &str) -> Option<(String, bool)> {  // Returns (token, is_address)
+        let candidate = TokenExtractor::extract_first(text)?;
+        match &candidate {
+            TokenCandidate::Address(address) => println!("Found Solana address: {}", address),
+            TokenCandidate::Ticker(ticker) => println!("Found ticker: {}", ticker),
+        }
+        Some(candidate.into_parts())
+    }
+
+    ////////////////////////
+    /// FUD-SPECIFIC ACTIONS
+    ////////////////////////
+    fn format_ticker_for_response(ticker: &str) -> String {
+        ticker.to_uppercase()
+    }
+
+    // The stock chart least recently posted, if one hasn't been used too recently
+    pub(crate) fn random_chart_image(&self) -> Option<ChartImage> {
+        let dir = &paths::resolve(ChartImages::DIR);
+        let names = match ChartImages::list(dir) {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("Failed to list chart images: {}", e);
+                return None;
+            }
+        };
+        let reuse_after = chrono::Duration::days(self.settings.chart_images.reuse_after_days);
+        let Some(name) = ChartImages::pick(names, &self.memory.image_usage, reuse_after, self.clock.now()) else {
+            println!("Every chart image was used in the last {} days, posting without one", reuse_after.num_days());
+            return None;
+        };
+        match ChartImages::read(dir, &name) {
+            Ok(chart) => Some(chart),
+            Err(e) => {
+                eprintln!("Failed to read chart image {}: {}", name, e);
+                None
+            }
+        }
+    }
+
+    // Now and then a GIF or MP4 from storage/videos, least recently posted first.
+    // None when clips are off, the roll fails or every clip was used too recently.
+    pub(crate) fn reaction_clip(&self) -> Option<ChartImage> {
+        let settings = &self.settings.reaction_clips;
+        if !settings.enabled || !rand::thread_rng().gen_bool(settings.chance.clamp(0.0, 1.0)) {
+            return None;
+        }
+        let dir = &paths::resolve(ReactionClips::DIR);
+        let names = match ReactionClips::list(dir) {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("Failed to list reaction clips: {}", e);
+                return None;
+            }
+        };
+        let reuse_after = chrono::Duration::days(settings.reuse_after_days);
+        let name = ChartImages::pick(names, &self.memory.clip_usage, reuse_after, self.clock.now())?;
+        match ChartImages::read(dir, &name) {
+            Ok(clip) => Some(clip),
+            Err(e) => {
+                eprintln!("Failed to read reaction clip {}: {}", name, e);
+                None
+            }
+        }
+    }
+
+    pub(crate) async fn build_token_card(&self, token: &TokenInfo) -> Option<Vec<u8>> {
+        let logo = match self.solana_tracker.fetch_token_image(token).await {
+            Ok(logo) => logo,
+            Err(e) => {
+                eprintln!("Failed to fetch image for ${}: {}", token.symbol, e);
+                return None;
+            }
+        };
+
+        // Decoding and resizing the logo is CPU work, kept off the async workers
+        let symbol = token.symbol.clone();
+        match tokio::task::spawn_blocking(move || TokenCard::render(&logo, &symbol)).await {
+            Ok(Ok(card)) => Some(card),
+            Ok(Err(e)) => {
+                eprintln!("Failed to render token card for ${}: {}", token.symbol, e);
+                None
+            }
+            Err(e) => {
+                eprintln!("Failed to render token card for ${}: {}", token.symbol, e);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn agent(&self) -> &Agent {
+        &self.agents[0]
+    }
+
+    pub(crate) fn heurist(&self) -> Option<&HeuristConfig> {
+        self.heurist
+            .as_ref()
+            .filter(|_| self.memory.feature_flags.image_gen && self.has_disk_headroom())
+    }
+
+    fn has_disk_headroom(&self) -> bool {
+        self.storage_report
+            .as_ref()
+            .is_none_or(|report| report.has_headroom(&self.settings.storage))
+    }
+
+    // Now and then, a screenshot of the target's website (from its metadata) to roast.
+    // None when screenshots aren't configured, the roll fails or the capture does.
+    pub(crate) async fn website_roast_image(&self, target: &TokenResponse) -> Option<(String, Vec<u8>)> {
+        let screenshot = self.screenshot.as_ref()?;
+        let website = Self::lock(&self.token_metadata)
+            .get(&target.token.mint)?
+            .as_ref()?
+            .website
+            .clone()?;
+        if !rand::thread_rng().gen_bool(self.settings.website_roast.chance.clamp(0.0, 1.0)) {
+            return None;
+        }
+
+        println!("Taking a screenshot of {} for ${}", website, target.token.symbol);
+        match screenshot.capture(&website).await {
+            Ok(image) => Some((website, image)),
+            Err(e) => {
+                eprintln!("Website screenshot failed, posting regular FUD: {}", e);
+                None
+            }
+        }
+    }
+
+    // TPS, priority fees, failure rate and Jito tips, formatted for the prompt.
+    // None when the RPC can't provide them.
+    pub(crate) async fn network_stats_summary(&self) -> Option<String> {
+        match self.network_monitor.fetch(&self.solana_rpc).await {
+            Ok(stats) => Some(stats.summary()),
+            Err(e) => {
+                eprintln!("Couldn't fetch Solana network stats: {}", e);
+                None
+            }
+        }
+    }
+
+    // Newest fresh headline about Solana or memecoins we haven't posted about yet
+    pub(crate) async fn fresh_headline(&self) -> Option<Headline> {
+        let settings = &self.settings.news;
+        let headlines = self.news.fetch_headlines(&settings.feeds).await;
+        let found = headlines.len();
+        let headline = news::pick_headline(
+            headlines,
+            &settings.keywords,
+            chrono::Duration::hours(settings.max_age_hours),
+            self.clock.now(),
+            |headline| MemoryStore::has_reacted_to_news(&self.memory, &headline.url, &headline.title),
+        );
+        if headline.is_none() {
+            println!("No new headlines to react to among {} fetched", found);
+        }
+        headline
+    }
+
+    // Biggest scheduled Space in the niche coming up soon that we haven't posted about
+    pub(crate) async fn upcoming_space(&mut self) -> Option<ScheduledSpace> {
+        let settings = self.settings.spaces.clone();
+        let now = self.clock.now();
+        for term in settings.search_terms.iter().map(|term| term.trim()).filter(|term| !term.is_empty()) {
+            if !self.quota_allows("space search", QuotaPriority::Sweep, &[ENDPOINT_SPACES]) {
+                return None;
+            }
+            let spaces = match self.twitter.search_scheduled_spaces(term).await {
+                Ok(spaces) => spaces,
+                Err(e) => {
+                    eprintln!("Couldn't search Spaces for '{}': {}", term, e);
+                    self.record_api_error("spaces");
+                    continue;
+                }
+            };
+            let found = spaces.len();
+            let space = ScheduledSpace::pick(spaces, chrono::Duration::hours(settings.lookahead_hours), now, |space| {
+                MemoryStore::has_commented_on_space(&self.memory, &space.id)
+            });
+            match space {
+                Some(space) => return Some(space),
+                None => println!("No new Spaces to comment on among {} scheduled for '{}'", found, term),
+            }
+        }
+        None
+    }
+
+    // Trending token for scheduled FUD: filtered, scored, with its supply and market
+    // cap checked. Returns the token and the summary to prompt the model with.
+    pub(crate) async fn pick_fud_target(&mut self) -> Result<Option<(TokenResponse, String)>, FudError> {
+        if std::mem::take(&mut self.campaign_slot) {
+            if let Some(mut target) = self.campaign_target().await {
+                let enriched = self.enrich_target(&mut target).await?;
+                return Ok(Some((target, enriched)));
+            }
+        }
+
+        let tracer = self.tracer.clone();
+        let budget = self.settings.latency_budget.clone();
+        let fetch = latency_budget::within("fetch_trending", budget.limit(budget.fetch_seconds), self.solana_tracker.get_top_tokens(30));
+        let tokens = match tracer.in_span("fetch_trending", &[("provider", "solanatracker".to_string())], fetch).await {
+            Ok(tokens) => {
+                self.record_trending_snapshot(&tokens);
+                tokens
+            }
+            Err(e) if e.is_timeout() && budget.on_timeout == TimeoutFallback::CachedThenGeneric => {
+                let max_age = chrono::Duration::minutes(budget.cache_max_age_minutes);
+                let Some((fetched_at, tokens)) = self.solana_tracker.cached_top_tokens(30, max_age) else {
+                    return Err(e);
+                };
+                println!("{}, using the trending list from {}", e, fetched_at.format("%H:%M"));
+                tokens
+            }
+            Err(e) => return Err(e),
+        };
+
+        let candidates = tokens.len();
+        let picked = tracer
+            .in_span("select_target", &[("candidates", candidates.to_string())], async {
+                let mut tokens = tokens;
+                // Market cap bounds and scoring need the real figure, not the 1B guess
+                self.fill_token_supplies(&mut tokens).await;
+                for _ in 0..MAX_LISTED_PICKS {
+                    let Some(target) = self.select_fud_target(tokens.clone()) else {
+                        return Ok::<_, FudError>(None);
+                    };
+                    if !self.is_major_listed(&target).await {
+                        return Ok(Some(target));
+                    }
+                    tokens.retain(|t| t.token.mint != target.token.mint);
+                }
+                println!("Every pick was listed on a major exchange, skipping this slot");
+                Ok(None)
+            })
+            .await?;
+        let Some(mut target) = picked else {
+            return Ok(None);
+        };
+        let enriched = self.enrich_target(&mut target).await?;
+        Ok(Some((target, enriched)))
+    }
+
+    // The running campaign's token, fresh from the API. A campaign on a token the
+    // consistency check put on the avoid list sits its slots out until it comes off.
+    async fn campaign_target(&self) -> Option<TokenResponse> {
+        let now = self.clock.now();
+        let campaign = Campaigns::running(&self.memory.campaigns, now)?;
+        if ConsistencyCheck::is_avoided(&Self::lock(&self.avoided_topics), &campaign.symbol, &campaign.mint, now) {
+            println!("Campaign token ${} is on the avoid list, picking from trending instead", campaign.symbol);
+            return None;
+        }
+        match self.solana_tracker.get_token_by_address(&campaign.mint).await {
+            Ok(token) => {
+                println!("Campaign slot: FUDding ${}", campaign.symbol);
+                Some(token)
+            }
+            Err(e) => {
+                eprintln!("Failed to fetch campaign token ${}, picking from trending instead: {}", campaign.symbol, e);
+                None
+            }
+        }
+    }
+
+    // Summary of the target with supply, metadata and bonding curve filled in, as far
+    // as the latency budget allows
+    async fn enrich_target(&self, target: &mut TokenResponse) -> Result<String, FudError> {
+        let tracer = self.tracer.clone();
+        let budget = self.settings.latency_budget.clone();
+        let symbol = target.token.symbol.clone();
+        let enrich = latency_budget::within("enrich_target", budget.limit(budget.enrich_seconds), async {
+            // Trending picks were filled in before scoring; this is for campaign targets
+            self.fill_token_supply(target).await;
+            let token_summary = self.summary_with_past_takes(target);
+            let token_summary = self.with_learned_facts(target, token_summary);
+            let token_summary = self.with_callback(target, token_summary);
+            let token_summary = self.cross_check_market_cap(target, token_summary).await;
+            let token_summary = self.with_token_metadata(target, token_summary).await;
+            let token_summary = self.with_bonding_curve(target, token_summary).await;
+            let token_summary = self.with_security_info(target, token_summary).await;
+            Ok::<_, FudError>(self.with_bundle_check(target, token_summary).await)
+        });
+        match tracer.in_span("enrich_target", &[("symbol", symbol)], enrich).await {
+            Ok(summary) => Ok(summary),
+            // The lookups only add detail; the trending data is enough to write FUD from
+            Err(e) if e.is_timeout() => {
+                println!("{}, using the basic summary for ${}", e, target.token.symbol);
+                Ok(self.with_learned_facts(target, self.summary_with_past_takes(target)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Filter the trending list and pick one by score
+    fn select_fud_target(&self, mut tokens: Vec<TokenResponse>) -> Option<TokenResponse> {
+        // Never FUD our own mint
+        tokens.retain(|t| !self.is_own_token(&t.token.mint) && !self.is_own_token(&t.token.symbol));
+        tokens.retain(|t| self.settings.fud_targets.matches(&t.snapshot()));
+        self.skip_avoided_tokens(&mut tokens);
+        self.skip_hot_tokens(&mut tokens);
+        if tokens.is_empty() {
+            println!("No tokens available to FUD");
+            return None;
+        }
+
+        let snapshots: Vec<TokenSnapshot> = tokens.iter().map(|t| t.snapshot()).collect();
+        let scoring = &self.settings.target_scoring;
+        let picked = TargetScorer::pick_with(scoring, &snapshots, &mut rand::thread_rng());
+        if let Some((i, score)) = picked {
+            let reason = if scoring.enabled { score.describe(scoring) } else { "uniform random".to_string() };
+            println!("Picked ${} out of {} eligible tokens ({})", snapshots[i].symbol, snapshots.len(), reason);
+        }
+        picked.and_then(|(i, _)| tokens.into_iter().nth(i))
+    }
+
+    // Whether CoinGecko has the token on a major exchange. The static list in
+    // fud_targets already ran; this catches the listings it doesn't know about. If
+    // CoinGecko can't be reached the token is let through.
+    async fn is_major_listed(&self, target: &TokenResponse) -> bool {
+        let settings = &self.settings.fud_targets;
+        let mint = &target.token.mint;
+        if !settings.exclude_cex_listed || !settings.check_listings || mint.is_empty() {
+            return false;
+        }
+        let now = self.clock.now();
+        let cached = Self::lock(&self.cex_listings)
+            .get(mint)
+            .filter(|(checked_at, _)| now.signed_duration_since(*checked_at) < chrono::Duration::hours(CEX_LISTING_TTL_HOURS))
+            .map(|(_, exchanges)| exchanges.clone());
+        let exchanges = match cached {
+            Some(exchanges) => exchanges,
+            None => match self.coingecko.get_listing(mint).await {
+                Ok(listing) => {
+                    let exchanges = listing.map(|listing| listing.major_exchanges(&settings.major_exchanges)).unwrap_or_default();
+                    Self::lock(&self.cex_listings).insert(mint.clone(), (now, exchanges.clone()));
+                    exchanges
+                }
+                Err(e) => {
+                    eprintln!("Couldn't check exchange listings for ${}: {}", target.token.symbol, e);
+                    return false;
+                }
+            },
+        };
+        if exchanges.is_empty() {
+            return false;
+        }
+        println!("Skipping ${}, listed on {}", target.token.symbol, exchanges.join(", "));
+        true
+    }
+
+    // Shared by every character on the same store_path, or on the same database
+    fn open_token_heat(&self) -> Result<Box<dyn KvStore>, FudError> {
+        let path = paths::resolve(&self.settings.token_heat.store_path);
+        self.backend.open("token_heat", || LogStore::open(path))
+    }
+
+    // Drops tokens the consistency check put on the avoid list
+    fn skip_avoided_tokens(&self, tokens: &mut Vec<TokenResponse>) {
+        let now = self.clock.now();
+        let avoided = Self::lock(&self.avoided_topics);
+        tokens.retain(|t| {
+            let skip = ConsistencyCheck::is_avoided(&avoided, &t.token.symbol, &t.token.mint, now);
+            if skip {
+                println!("Skipping ${}, on the avoid list", t.token.symbol);
+            }
+            !skip
+        });
+    }
+
+    // Leave tokens other characters (or this one) have just FUDded alone
+    fn skip_hot_tokens(&self, tokens: &mut Vec<TokenResponse>) {
+        let settings = &self.settings.token_heat;
+        if !settings.enabled {
+            return;
+        }
+        let store = match self.open_token_heat() {
+            Ok(store) => store,
+            Err(e) => {
+                eprintln!("Failed to read token heat, not filtering on it: {}", e);
+                return;
+            }
+        };
+        let now = self.clock.now();
+        tokens.retain(|t| match TokenHeat::too_hot(settings, store.as_ref(), &t.token.mint, now) {
+            Some(heat) => {
+                println!(
+                    "Skipping ${}, heat {:.2} (FUDded by {})",
+                    t.token.symbol,
+                    heat,
+                    TokenHeat::fudded_by(store.as_ref(), &t.token.mint).join(", ")
+                );
+                false
+            }
+            None => true,
+        });
+    }
+
+    fn record_token_heat(&self, mint: &str) {
+        let settings = &self.settings.token_heat;
+        if !settings.enabled || mint.is_empty() {
+            return;
+        }
+        let recorded = self
+            .open_token_heat()
+            .and_then(|mut store| TokenHeat::record(settings, store.as_mut(), mint, &self.character_name, self.clock.now()));
+        if let Err(e) = recorded {
+            eprintln!("Failed to record token heat: {}", e);
+        }
+    }
+
+    // The top trending tokens that aren't ours
+    pub(crate) async fn trending_tokens(&self, count: usize) -> Result<Vec<TokenResponse>, FudError> {
+        let mut tokens = self.solana_tracker.get_top_tokens(10).await?;
+        tokens.retain(|t| !self.is_own_token(&t.token.mint) && !self.is_own_token(&t.token.symbol));
+        tokens.truncate(count);
+        Ok(tokens)
+    }
+
+    pub(crate) async fn trending_summaries(&self, count: usize) -> Result<Vec<String>, FudError> {
+        let tokens = self.trending_tokens(count).await?;
+        Ok(tokens.iter().map(|token| self.token_summary(token)).collect())
+    }
+
+    pub(crate) fn token_summary(&self, token: &TokenResponse) -> String {
+        self.solana_tracker.format_token_summary(token)
+    }
+
+    // Generated text is worth posting if it doesn't repeat us and fits in a tweet
+    pub(crate) fn is_postable(&self, text: &str) -> bool {
+        if self.style().repeats_recent_phrase(text) {
+            println!("Generated text repeats a recent phrase, retrying...");
+            return false;
+        }
+        if !TweetPreview::new(text).fits() {
+            println!("Generated text is over the length limit, retrying...");
+            return false;
+        }
+        true
+    }
+
+    // Whether a mint address or ticker refers to the token this bot represents
+    fn is_own_token(&self, token: &str) -> bool {
+        TokenExtractor::is_own_token(token, &self.memory.token_address, &self.memory.token_symbol)
+    }
+
+    pub(crate) async fn generate_shill(&self) -> Result<Option<String>, FudError> {
+        if self.memory.token_symbol.is_empty() || self.memory.token_address.is_empty() {
+            println!("Shill mode is on but token_symbol/token_address aren't set in memory");
+            return Ok(None);
+        }
+        let symbol = self.memory.token_symbol.trim_start_matches('$');
+        let task = self.settings.shill.render(symbol, &self.memory.token_address);
+        Ok(Some(self.agents[0].generate_shill(&task).await?))
+    }
+
+    // Keep an hourly record of the trending list for the backtest command
+    fn record_trending_snapshot(&mut self, tokens: &[TokenResponse]) {
+        const SNAPSHOT_INTERVAL_MINUTES: i64 = 60;
+
+        let now = self.clock.now();
+        if self.last_snapshot_time.is_some_and(|last| now.signed_duration_since(last).num_minutes() < SNAPSHOT_INTERVAL_MINUTES) {
+            return;
+        }
+        let snapshot = TrendingSnapshot {
+            timestamp: now,
+            tokens: tokens.iter().map(|t| t.snapshot()).collect(),
+        };
+        match MemoryStore::append_trending_snapshot(&snapshot) {
+            Ok(_) => self.last_snapshot_time = Some(now),
+            Err(e) => eprintln!("Failed to record trending snapshot: {}", e),
+        }
+    }
+
+    // Fill a scheduled post slot with whatever the content schedule calls for right now
+    async fn run_scheduled_post(&mut self) -> Result<(), FudError> {
+        if let Some((mut item, mut post)) = self.take_buffered_post().await {
+            println!("Scheduled content: buffered {} from {}", item.label, self.local_time().show(item.created_at));
+            if let Some(target) = post.target.clone() {
+                post.text = self.with_onchain_receipt(std::mem::take(&mut post.text), &target).await;
+                item.text = post.text.clone();
+            }
+            return self.deliver_from_outbox(item, post).await;
+        }
+
+        let generators = ContentGenerators::get_generators();
+        let name = ContentSelector::pick(&self.settings, &generators, self.clock.now());
+        println!("Scheduled content: {}", name);
+
+        let (mut post, mut label) = self.generate_within_budget(&generators, name).await?;
+        // Anything that has nothing to post right now falls back to FUD
+        if post.is_none() && name != ContentGenerators::FUD {
+            println!("Nothing to post for {}, falling back to FUD", name);
+            (post, label) = self.generate_within_budget(&generators, ContentGenerators::FUD).await?;
+        }
+
+        match post {
+            Some(post) => self.publish(post, label).await,
+            None => Ok(()),
+        }
+    }
+
+    // A scheduled slot given to the campaign: FUD on its token, skipping the buffer
+    // and the content schedule
+    async fn run_campaign_post(&mut self) -> Result<(), FudError> {
+        println!("Scheduled content: campaign FUD");
+        let generators = ContentGenerators::get_generators();
+        self.campaign_slot = true;
+        let result = self.generate_within_budget(&generators, ContentGenerators::FUD).await;
+        self.campaign_slot = false;
+        match result? {
+            (Some(post), _) => self.publish(post, "campaign FUD").await,
+            (None, _) => Ok(()),
+        }
+    }
+
+    // Price checks on the running campaign's token, and the recap once it's over
+    async fn track_campaign(&mut self, quiet: bool) -> Result<(), FudError> {
+        let now = self.clock.now();
+        let Some(i) = Campaigns::current(&self.memory.campaigns) else {
+            return Ok(());
+        };
+        let interval = chrono::Duration::minutes(self.settings.campaigns.price_check_minutes);
+        let campaign = &self.memory.campaigns[i];
+        let over = campaign.is_over(now);
+        // One last check before the recap so it has the closing price
+        if over || campaign.price_check_due(interval, now) {
+            let mint = campaign.mint.clone();
+            let token = self.solana_tracker.get_token_by_address(&mint).await?;
+            self.memory.campaigns[i].observe(&token.snapshot(), now);
+            MemoryStore::save_memory(&self.memory)?;
+        }
+        // The recap waits for quiet hours to end and for Anthropic to be back
+        if !over || quiet || !self.providers_available(&[Provider::Anthropic]) {
+            return Ok(());
+        }
+
+        // Marked finished first so a failed recap isn't retried every cycle
+        self.memory.campaigns[i].finished_at = Some(now);
+        MemoryStore::save_memory(&self.memory)?;
+        let campaign = self.memory.campaigns[i].clone();
+        println!("Campaign on ${} is over: {:+.1}%", campaign.symbol, campaign.change_pct());
+        if !self.settings.campaigns.recap {
+            return Ok(());
+        }
+        if !self.quota_allows("campaign recap", QuotaPriority::Post, &[ENDPOINT_TWEETS]) {
+            return Ok(());
+        }
+        self.post_campaign_recap(&campaign).await
+    }
+
+    async fn post_campaign_recap(&mut self, campaign: &Campaign) -> Result<(), FudError> {
+        let intro = self.agents[0].generate_campaign_recap(&campaign.summary()).await?;
+        let text = format!("{}\n\n{}", intro, campaign.recap_lines());
+        if !self.memory.tweet_mode {
+            println!("Tweet mode is disabled, campaign recap not posted:");
+            Self::print_preview(&text);
+            return Ok(());
+        }
+
+        let tweet = self.twitter.tweet(text.clone()).await?;
+        let tweet_id = tweet.id.to_string();
+        println!("Posted campaign recap for ${}", campaign.symbol);
+        self.last_tweet_time = Some(self.clock.now());
+        let agent_prompt = self.agents[0].prompt.clone();
+        if let Err(e) = MemoryStore::add_post_to_memory(&mut self.memory, &text, &agent_prompt, Some(tweet_id.clone()), Some(campaign.summary()), self.clock.now()) {
+            eprintln!("Failed to save campaign recap to memory: {}", e);
+        }
+        if let Err(e) = MemoryStore::record_tweet_token(&mut self.memory, &tweet_id, &campaign.mint, &campaign.symbol) {
+            eprintln!("Failed to save the token the campaign recap was about: {}", e);
+        }
+        if let Some(finished) = self.memory.campaigns.iter_mut().rev().find(|c| c.mint == campaign.mint && c.started_at == campaign.started_at) {
+            finished.recap_tweet_id = Some(tweet_id.clone());
+        }
+        if let Err(e) = MemoryStore::save_memory(&self.memory) {
+            eprintln!("Failed to save campaign recap tweet: {}", e);
+        }
+        self.mirror_to_telegram(&text, &tweet_id, None).await;
+        Ok(())
+    }
+
+    // Run one generator. If a stage ran out of time the latency budget decides
+    // whether a generic post goes out in its place.
+    async fn generate_within_budget(
+        &mut self,
+        generators: &[Box<dyn ContentGenerator>],
+        name: &'static str,
+    ) -> Result<(Option<Post>, &'static str), FudError> {
+        let Some(generator) = ContentGenerators::find(generators, name) else {
+            return Ok((None, name));
+        };
+        let error = match generator.generate(self).await {
+            Ok(post) => return Ok((post, name)),
+            Err(e) => FudError::from(e),
+        };
+        let fallback = self.settings.latency_budget.on_timeout;
+        if !error.is_timeout() || fallback == TimeoutFallback::Skip || name == ContentGenerators::SHITPOST {
+            return Err(error);
+        }
+        let Some(generic) = ContentGenerators::find(generators, ContentGenerators::SHITPOST) else {
+            return Err(error);
+        };
+        println!("{} for {}, posting generic content instead", error, name);
+        Ok((generic.generate(self).await?, ContentGenerators::SHITPOST))
+    }
+
+    // Buffered posts that are still fresh, oldest first; stale ones are dropped
+    fn buffered_posts(&self, now: DateTime<Utc>) -> Vec<OutboxItem> {
+        let settings = &self.settings.content_buffer;
+        let items = match self.outbox.load() {
+            Ok(items) => items,
+            Err(e) => {
+                eprintln!("Failed to read the outbox: {}", e);
+                return Vec::new();
+            }
+        };
+        let (fresh, stale): (Vec<OutboxItem>, Vec<OutboxItem>) = items
+            .into_iter()
+            .filter(|item| item.buffered && item.is_pending())
+            .partition(|item| ContentBuffer::is_fresh(item, settings, now));
+        for mut item in stale {
+            println!("Dropping buffered {} from {}, it's gone stale", item.label, self.local_time().show(item.created_at));
+            item.abandoned = true;
+            if let Err(e) = self.outbox.save(&item).and_then(|_| self.outbox.remove_image(&item)) {
+                eprintln!("Failed to update outbox item {}: {}", item.id, e);
+            }
+        }
+        fresh
+    }
+
+    // Generate the next scheduled post ahead of time while nothing else is due
+    async fn fill_content_buffer(&mut self) {
+        let settings = self.settings.content_buffer.clone();
+        if !settings.enabled || !self.settings.outbox.enabled || !self.memory.tweet_mode {
+            return;
+        }
+        let now = self.clock.now();
+        let Some(slot) = Schedule::next_minute_mark(FUD_SCHEDULE_MINUTES, now) else {
+            return;
+        };
+        // Too close to the slot, or tried too recently
+        if slot.signed_duration_since(now) < chrono::Duration::minutes(BUFFER_FILL_LEAD_MINUTES)
+            || !Schedule::elapsed(self.last_buffer_fill, chrono::Duration::minutes(BUFFER_FILL_INTERVAL_MINUTES), now)
+        {
+            return;
+        }
+        if self.buffered_posts(now).len() >= settings.size {
+            return;
+        }
+        self.last_buffer_fill = Some(now);
+
+        let generators = ContentGenerators::get_generators();
+        let name = ContentSelector::pick(&self.settings, &generators, slot);
+        let mut generated = self.generate_within_budget(&generators, name).await;
+        if matches!(generated, Ok((None, _))) && name != ContentGenerators::FUD {
+            generated = self.generate_within_budget(&generators, ContentGenerators::FUD).await;
+        }
+        let (post, label) = match generated {
+            Ok((Some(post), label)) => (post, label),
+            Ok((None, _)) => return,
+            Err(e) => {
+                eprintln!("Failed to generate a post for the buffer: {}", e);
+                self.record_api_error("content_buffer");
+                return;
+            }
+        };
+        let mut item = OutboxItem::new(label, &post, self.clock.now());
+        item.buffered = true;
+        match self.outbox.enqueue(&item, post.image.as_deref()) {
+            Ok(()) => println!("Buffered a {} for the {} slot", label, self.local_time().show(slot)),
+            Err(e) => eprintln!("Failed to write the buffered {} to the outbox: {}", label, e),
+        }
+    }
+
+    // The oldest buffered post, with its token looked up again and the numbers in
+    // the text refreshed if they're stale. None means a new post has to be written.
+    async fn take_buffered_post(&mut self) -> Option<(OutboxItem, Post)> {
+        let settings = self.settings.content_buffer.clone();
+        if !settings.enabled || !self.settings.outbox.enabled || !self.memory.tweet_mode {
+            return None;
+        }
+        let now = self.clock.now();
+        let mut item = self.buffered_posts(now).into_iter().next()?;
+        let target = match item.target_mint.clone() {
+            Some(mint) => match self.solana_tracker.get_token_by_address(&mint).await {
+                Ok(token) => Some(token),
+                Err(e) => {
+                    eprintln!("Couldn't look up {} for the buffered {}, writing a new post: {}", mint, item.label, e);
+                    return None;
+                }
+            },
+            None => None,
+        };
+        if let (Some(then), Some(target)) = (item.target_snapshot.clone(), &target) {
+            if ContentBuffer::needs_refresh(&item, &settings, now) {
+                let current = target.snapshot();
+                item.text = ContentBuffer::refresh_numbers(&item.text, &then, &current);
+                item.target_snapshot = Some(current);
+            }
+        }
+        item.buffered = false;
+        let post = item.to_post(self.outbox.image(&item), target);
+        Some((item, post))
+    }
+
+    // Network FUD runs on its own schedule, outside the content_schedule pick
+    async fn run_network_fud(&mut self) -> Result<(), FudError> {
+        match NetworkFud.generate(self).await? {
+            Some(post) => self.publish(post, ContentGenerators::NETWORK).await,
+            None => {
+                println!("No network FUD to post this time");
+                Ok(())
+            }
+        }
+    }
+
+    fn receipt_line(signature: &str) -> String {
+        format!("proof i looked at this garbage on-chain: https://solscan.io/tx/{}", signature)
+    }
+
+    // Send a memo or dust buy for the FUDded token and cite its signature in the tweet.
+    // Any cap, length or chain problem just means the tweet goes out without one.
+    async fn with_onchain_receipt(&mut self, text: String, target: &TokenResponse) -> String {
+        let settings = self.settings.onchain_receipts.clone();
+        let Some(wallet) = &self.wallet else {
+            return text;
+        };
+        if !settings.enabled || target.token.mint.is_empty() {
+            return text;
+        }
+        if let Some(reason) = settings.refusal(&self.memory.onchain_receipts, self.clock.now()) {
+            println!("No on-chain receipt for ${}: {}", target.token.symbol, reason);
+            return text;
+        }
+        // Signatures are up to 88 characters; check the tweet still fits before spending anything
+        let placeholder = format!("{}\n\n{}", text, Self::receipt_line(&"1".repeat(88)));
+        if !TweetPreview::new(&placeholder).fits() {
+            println!("No room in the tweet for an on-chain receipt");
+            return text;
+        }
+
+        let prepared = match settings.mode {
+            ReceiptMode::Memo => {
+                let memo = format!("fud receipt: ${} {}", target.token.symbol, target.token.mint);
+                wallet.memo_transaction(&self.solana_rpc, &memo).await
+            }
+            ReceiptMode::Buy => {
+                wallet
+                    .buy_transaction(&self.solana_rpc, &target.token.mint, settings.buy_lamports, settings.slippage_bps)
+                    .await
+            }
+        };
+        let transaction = match prepared {
+            Ok(transaction) => transaction,
+            Err(e) => {
+                eprintln!("On-chain receipt for ${} failed: {}", target.token.symbol, e);
+                return text;
+            }
+        };
+
+        // Counted against the caps before it's sent: a send that times out may still land
+        let receipt = OnChainReceipt {
+            timestamp: self.clock.now(),
+            mint: target.token.mint.clone(),
+            signature: transaction.signature.clone(),
+            lamports: transaction.max_lamports.max(settings.cost_lamports()),
+        };
+        if let Err(e) = MemoryStore::record_receipt(&mut self.memory, receipt) {
+            eprintln!("Failed to reserve the on-chain receipt's spend, not sending it: {}", e);
+            return text;
+        }
+        match self.solana_rpc.send_transaction(&transaction.bytes).await {
+            Ok(signature) => {
+                println!("Sent on-chain receipt for ${}: {}", target.token.symbol, signature);
+                format!("{}\n\n{}", text, Self::receipt_line(&signature))
+            }
+            Err(e) if RpcRejected::is(&e) => {
+                eprintln!("On-chain receipt for ${} was rejected: {}", target.token.symbol, e);
+                if let Err(e) = MemoryStore::release_receipt(&mut self.memory, &transaction.signature) {
+                    eprintln!("Failed to release the on-chain receipt's spend: {}", e);
+                }
+                text
+            }
+            Err(e) => {
+                eprintln!(
+                    "On-chain receipt for ${} may not have gone out, its spend stays counted: {}",
+                    target.token.symbol, e
+                );
+                text
+            }
+        }
+    }
+
+    // Tweet a generated post (with its image, if any), then mirror and remember it
+    async fn publish(&mut self, mut post: Post, label: &str) -> Result<(), FudError> {
+        if !self.memory.tweet_mode {
+            let with_image = if post.image.is_some() { " (with image)" } else { "" };
+            println!("Tweet mode is disabled, {}{} not posted:", label, with_image);
+            Self::print_preview(&post.text);
+            self.save_draft(label, &post);
+            return Ok(());
+        }
+        if let Some(target) = post.target.clone() {
+            post.text = self.with_onchain_receipt(std::mem::take(&mut post.text), &target).await;
+        }
+        if !self.settings.outbox.enabled {
+            return self.send_post(post, label).await.map(|_| ());
+        }
+
+        let item = OutboxItem::new(label, &post, self.clock.now());
+        if let Err(e) = self.outbox.enqueue(&item, post.image.as_deref()) {
+            eprintln!("Failed to write {} to the outbox, it won't be retried: {}", label, e);
+            return self.send_post(post, label).await.map(|_| ());
+        }
+        self.deliver_from_outbox(item, post).await
+    }
+
+    // Sends a queued post and records how it went, so it's retried later if it failed
+    async fn deliver_from_outbox(&mut self, mut item: OutboxItem, post: Post) -> Result<(), FudError> {
+        let label = item.label.clone();
+        let result = self.send_post(post, &label).await;
+        let now = self.clock.now();
+        match &result {
+            Ok(twitter_id) => item.mark_delivered(twitter_id, now),
+            Err(e) => {
+                item.mark_failed(&e.to_string(), &self.settings.outbox, now);
+                if item.abandoned {
+                    eprintln!("Giving up on {} after {} attempts", label, item.attempts);
+                } else {
+                    println!(
+                        "{} kept in the outbox, next attempt at {}",
+                        label,
+                        item.next_attempt_at.format("%H:%M:%S UTC")
+                    );
+                }
+            }
+        }
+        if let Err(e) = self.outbox.save(&item) {
+            eprintln!("Failed to update outbox item {}: {}", item.id, e);
+        }
+        if !item.is_pending() {
+            if let Err(e) = self.outbox.remove_image(&item) {
+                eprintln!("Failed to remove outbox image {}: {}", item.id, e);
+            }
+        }
+        result.map(|_| ())
+    }
+
+    // Retries the oldest post in the outbox that's due, one per pass. Posts that
+    // have sat too long are dropped, and finished items are pruned.
+    async fn process_outbox(&mut self) {
+        let settings = self.settings.outbox.clone();
+        if !settings.enabled || !self.memory.tweet_mode {
+            return;
+        }
+        let items = match self.outbox.load() {
+            Ok(items) => items,
+            Err(e) => {
+                eprintln!("Failed to read the outbox: {}", e);
+                return;
+            }
+        };
+        let now = self.clock.now();
+        let keep = chrono::Duration::days(settings.keep_days);
+        for item in items.iter().filter(|item| item.is_expired(keep, now)) {
+            if let Err(e) = self.outbox.remove(item) {
+                eprintln!("Failed to prune outbox item {}: {}", item.id, e);
+            }
+        }
+
+        let Some(mut item) = items.into_iter().find(|item| item.is_due(now)) else {
+            return;
+        };
+        if item.is_stale(&settings, now) {
+            println!("Dropping {} from the outbox, it's been waiting since {}", item.label, item.created_at);
+            item.abandoned = true;
+            if let Err(e) = self.outbox.save(&item).and_then(|_| self.outbox.remove_image(&item)) {
+                eprintln!("Failed to update outbox item {}: {}", item.id, e);
+            }
+            return;
+        }
+        if !self.should_allow_tweet().await
+            || !self.quota_allows("outbox retry", QuotaPriority::Post, &[ENDPOINT_TWEETS])
+        {
+            return;
+        }
+
+        let image = self.outbox.image(&item);
+        // The token is fetched again so Discord and the whale watch get current numbers
+        let target = match &item.target_mint {
+            Some(mint) => match self.solana_tracker.get_token_by_address(mint).await {
+                Ok(token) => Some(token),
+                Err(e) => {
+                    eprintln!("Couldn't look up {} for the queued {}, posting without it: {}", mint, item.label, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        println!("Retrying {} from the outbox (attempt {})", item.label, item.attempts + 1);
+        let post = item.to_post(image, target);
+        if let Err(e) = self.deliver_from_outbox(item, post).await {
+            eprintln!("Queued post failed again: {}", e);
+            self.record_api_error("outbox");
+        }
+    }
+
+    // Posts the tweet and does the bookkeeping that follows; returns the tweet ID
+    async fn send_post(&mut self, mut post: Post, label: &str) -> Result<String, FudError> {
+        if !self.memory.feature_flags.image_posts && post.image.take().is_some() {
+            println!("Image posts are switched off, posting {} as text only", label);
+        }
+        let tracer = self.tracer.clone();
+        let image = match post.image {
+            Some(image) => match tracer
+                .in_span("media_upload", &[("provider", "twitter".to_string())], self.twitter.upload_bytes(image.clone()))
+                .await
+            {
+                Ok(media_id) => Some((media_id, image)),
+                Err(e) => {
+                    eprintln!("Failed to upload image for {}, posting text only: {}", label, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let attributes = [("provider", "twitter".to_string()), ("label", label.to_string())];
+        let tweet = match &image {
+            Some((media_id, _)) => {
+                let user_id = self.ensure_user_id().await?;
+                tracer
+                    .in_span("post", &attributes, self.twitter.tweet_with_image(post.text.clone(), *media_id, user_id))
+                    .await?
+            }
+            None => tracer.in_span("post", &attributes, self.twitter.tweet(post.text.clone())).await?,
+        };
+        let image = image.map(|(_, bytes)| bytes);
+
+        let now = self.clock.now();
+        let twitter_id = tweet.id.to_string();
+        println!("Posted scheduled {} at {}", label, self.local_time().show(now));
+        self.last_tweet_time = Some(now);
+        self.style().record(&post.text);
+        self.mirror_to_telegram(&post.text, &twitter_id, image.clone()).await;
+        let agent_prompt = self.agents[0].prompt.clone();
+        if let Err(e) = MemoryStore::add_post_to_memory(
+            &mut self.memory,
+            &post.text,
+            &agent_prompt,
+            Some(twitter_id.clone()),
+            post.context.clone(),
+            self.clock.now(),
+        ) {
+            eprintln!("Failed to save {} to memory: {}", label, e);
+        }
+
+        if let Some(mut prediction) = post.prediction.take() {
+            prediction.tweet_id = Some(twitter_id.clone());
+            if post.target.is_none() {
+                if let Err(e) = MemoryStore::record_tweet_token(&mut self.memory, &twitter_id, &prediction.mint, &prediction.symbol) {
+                    eprintln!("Failed to save the token {} was about: {}", label, e);
+                }
+            }
+            if let Err(e) = MemoryStore::add_prediction(&mut self.memory, prediction) {
+                eprintln!("Failed to save prediction: {}", e);
+            }
+        }
+        if let Some(reaction) = post.news.take() {
+            if let Err(e) = MemoryStore::record_news_reaction(&mut self.memory, reaction) {
+                eprintln!("Failed to save news reaction: {}", e);
+            }
+        }
+        if let Some(name) = post.chart_image.take() {
+            if let Err(e) = MemoryStore::record_image_use(&mut self.memory, &name, self.clock.now()) {
+                eprintln!("Failed to save chart image use: {}", e);
+            }
+        }
+        if let Some(name) = post.reaction_clip.take() {
+            if let Err(e) = MemoryStore::record_clip_use(&mut self.memory, &name, self.clock.now()) {
+                eprintln!("Failed to save reaction clip use: {}", e);
+            }
+        }
+        if let Some(comment) = post.space.take() {
+            if let Err(e) = MemoryStore::record_space_comment(&mut self.memory, comment) {
+                eprintln!("Failed to save Space comment: {}", e);
+            }
+        }
+
+        // Remember what we FUDded so follow-ups in the thread can be traced back to the token
+        if let Some(target) = &post.target {
+            let snapshot = target.snapshot();
+            let fudded = FuddedToken {
+                mint: snapshot.mint,
+                symbol: snapshot.symbol,
+                tweet_id: Some(twitter_id.clone()),
+                fudded_at: now,
+                price_usd: snapshot.price_usd,
+                market_cap: snapshot.market_cap,
+            };
+            if let Err(e) = MemoryStore::record_fud(&mut self.memory, fudded) {
+                eprintln!("Failed to record FUD for the weekly recap: {}", e);
+            }
+            if !target.token.mint.is_empty() {
+                if let Err(e) = MemoryStore::record_tweet_token(&mut self.memory, &twitter_id, &target.token.mint, &target.token.symbol) {
+                    eprintln!("Failed to save the token {} was about: {}", label, e);
+                }
+            }
+            self.record_token_heat(&target.token.mint);
+            if let Err(e) = MemoryStore::record_campaign_post(&mut self.memory, &target.token.mint, now) {
+                eprintln!("Failed to count the post toward the campaign: {}", e);
+            }
+            self.send_fud_to_discord(target, &post.text, &twitter_id, image).await;
+            if self.settings.whale_alerts.enabled && !target.token.mint.is_empty() {
+                let watched = WatchedToken {
+                    mint: target.token.mint.clone(),
+                    symbol: target.token.symbol.clone(),
+                    tweet_id: Some(twitter_id.clone()),
+                    fudded_at: now,
+                    alerts_sent: 0,
+                    last_trade_time: now.timestamp_millis(),
+                };
+                let watch_for = chrono::Duration::hours(self.settings.whale_alerts.watch_hours);
+                if let Err(e) = MemoryStore::watch_token(&mut self.memory, watched, watch_for) {
+                    eprintln!("Failed to add token to whale watch: {}", e);
+                }
+            }
+        }
+        Ok(twitter_id)
+    }
+
+    fn should_sweep_storage(&self) -> bool {
+        let settings = &self.settings.storage;
+        settings.enabled
+            && Schedule::elapsed(self.last_storage_sweep, chrono::Duration::minutes(settings.interval_minutes), self.clock.now())
+    }
+
+    // Trim storage/ back under its quotas and check how much disk is left
+    fn sweep_storage(&mut self) {
+        self.last_storage_sweep = Some(self.clock.now());
+        let had_headroom = self.has_disk_headroom();
+        let report = self.storage.sweep(&self.settings.storage);
+        let has_headroom = report.has_headroom(&self.settings.storage);
+        if let Some(free) = report.free_bytes.filter(|_| had_headroom != has_headroom) {
+            let free_mb = free / (1024 * 1024);
+            if has_headroom {
+                println!("Storage: {}MB free again, image generation is back on", free_mb);
+            } else {
+                println!(
+                    "Storage: only {}MB free, under storage.min_free_mb ({}MB); not generating images until there's room",
+                    free_mb, self.settings.storage.min_free_mb
+                );
+            }
+        }
+        self.storage_report = Some(report);
+    }
+
+    fn should_sync_blocks(&self) -> bool {
+        let settings = &self.settings.spam_filter;
+        settings.sync_twitter_blocks
+            && Schedule::elapsed(self.last_block_sync, chrono::Duration::hours(settings.block_sync_hours), self.clock.now())
+    }
+
+    // Mirror the account's Twitter blocks and mutes into the spam filter
+    async fn sync_blocked_users(&mut self) -> Result<(), FudError> {
+        self.last_block_sync = Some(self.clock.now());
+        let user_id = self.ensure_user_id().await?;
+        let blocked = self.twitter.get_blocked_and_muted(user_id).await?;
+        println!("Synced {} blocked or muted accounts from Twitter", blocked.len());
+        self.spam_filter.set_blocked(blocked);
+        Ok(())
+    }
+
+    // Thread ranking this week's FUDded tokens by how far they've dumped since, mirrored to Telegram
+    async fn post_weekly_recap(&mut self) -> Result<(), FudError> {
+        let now = self.clock.now();
+        self.memory.last_weekly_recap = Some(now);
+        MemoryStore::save_memory(&self.memory)?;
+
+        let fudded = WeeklyRecap::fudded_this_week(&self.memory.fud_history, now);
+        let mut entries = Vec::new();
+        for token in &fudded {
+            match self.solana_tracker.get_token_by_address(&token.mint).await {
+                Ok(current) => {
+                    let price = current.snapshot().price_usd;
+                    if price > 0.0 {
+                        entries.push(RecapEntry::new(token, price));
+                    }
+                }
+                Err(e) => eprintln!("Failed to fetch ${} for the weekly recap: {}", token.symbol, e),
+            }
+        }
+        let recap = WeeklyRecap::rank(entries, fudded.len(), self.settings.weekly_recap.top_tokens);
+        if recap.entries.is_empty() {
+            println!("Nothing we FUDded this week has dumped, skipping the weekly recap");
+            return Ok(());
+        }
+
+        let intro = self.agents[0].generate_weekly_recap(&recap.summary()).await?;
+        let mut thread = recap.thread(&intro);
+        if !self.memory.feature_flags.threads {
+            thread.truncate(1);
+        }
+        let full_text = format!("{}\n\n{}", intro, recap.lines().join("\n"));
+        if !self.memory.tweet_mode {
+            println!("Tweet mode is disabled, weekly recap not posted:");
+            for tweet in &thread {
+                Self::print_preview(tweet);
+            }
+            return Ok(());
+        }
+
+        let agent_prompt = self.agents[0].prompt.clone();
+        let head = self.twitter.tweet(intro.clone()).await?;
+        let head_id = head.id.to_string();
+        if let Err(e) = MemoryStore::add_post_to_memory(&mut self.memory, &intro, &agent_prompt, Some(head_id.clone()), Some(recap.summary()), self.clock.now()) {
+            eprintln!("Failed to save weekly recap to memory: {}", e);
+        }
+        let mut previous = head_id.clone();
+        for text in thread.iter().skip(1) {
+            let reply = match self.twitter.reply_to_tweet(&previous, text.clone()).await {
+                Ok(reply) => reply,
+                Err(e) => {
+                    eprintln!("Failed to post the rest of the weekly recap thread: {}", e);
+                    break;
+                }
+            };
+            let reply_id = reply.id.to_string();
+            if let Err(e) = MemoryStore::add_reply_to_memory(&mut self.memory, text, &agent_prompt, Some(reply_id.clone()), previous.clone(), self.clock.now()) {
+                eprintln!("Failed to save weekly recap reply to memory: {}", e);
+            }
+            previous = reply_id;
+        }
+        println!("Posted weekly recap of {} tokens", recap.entries.len());
+        self.mirror_to_telegram(&full_text, &head_id, None).await;
+        Ok(())
+    }
+
+    // A new call on the token, or None when its market cap is only an estimate or
+    // there's already an open call on it
+    pub(crate) fn new_prediction(&self, target: &TokenResponse) -> Option<Prediction> {
+        let market_cap = target.market_cap();
+        if market_cap.estimated || market_cap.usd <= 0.0 {
+            println!("No prediction on ${}: its market cap is only an estimate", target.token.symbol);
+            return None;
+        }
+        if self.memory.predictions.iter().any(|p| p.is_open() && p.mint == target.token.mint) {
+            println!("Already have an open prediction on ${}", target.token.symbol);
+            return None;
+        }
+        let settings = &self.settings.predictions;
+        Some(Prediction::call(
+            &target.token.mint,
+            &target.token.symbol,
+            market_cap.usd,
+            settings.drop_pct,
+            settings.deadline_hours,
+            self.clock.now(),
+        ))
+    }
+
+    fn should_check_predictions(&self) -> bool {
+        self.memory.predictions.iter().any(Prediction::is_open)
+            && Schedule::elapsed(
+                self.last_prediction_check,
+                chrono::Duration::minutes(self.settings.predictions.check_interval_minutes),
+                self.clock.now(),
+            )
+    }
+
+    // Track the lowest market cap of every open call and grade the ones past their deadline
+    async fn check_predictions(&mut self) -> Result<(), FudError> {
+        let now = self.clock.now();
+        self.last_prediction_check = Some(now);
+
+        for i in 0..self.memory.predictions.len() {
+            let prediction = self.memory.predictions[i].clone();
+            if !prediction.is_open() {
+                continue;
+            }
+            let market_cap = match self.solana_tracker.get_token_by_address(&prediction.mint).await {
+                Ok(mut token) => {
+                    self.fill_token_supply(&mut token).await;
+                    Some(token.market_cap()).filter(|m| !m.estimated).map(|m| m.usd)
+                }
+                Err(e) => {
+                    eprintln!("Failed to fetch ${} to grade its prediction: {}", prediction.symbol, e);
+                    None
+                }
+            };
+
+            let entry = &mut self.memory.predictions[i];
+            if let Some(market_cap) = market_cap {
+                entry.observe(market_cap, now);
+            }
+            let due = if market_cap.is_some() { entry.is_due(now) } else { entry.is_overdue(now) };
+            if !due {
+                continue;
+            }
+            let hit = entry.grade(market_cap, now).hit;
+            println!("Prediction on ${} graded: {}", entry.symbol, if hit { "hit" } else { "miss" });
+            let graded = entry.clone();
+            // The grade stands even if the result can't be posted
+            if let Err(e) = self.post_prediction_result(&graded).await {
+                eprintln!("Failed to post prediction result for ${}: {}", graded.symbol, e);
+            }
+        }
+
+        MemoryStore::save_memory(&self.memory)?;
+        Ok(())
+    }
+
+    // Reply to the original call with the result and the running record
+    async fn post_prediction_result(&mut self, prediction: &Prediction) -> Result<(), FudError> {
+        let record = PredictionRecord::from_predictions(&self.memory.predictions);
+        let info = prediction.result_info();
+        let generated = self.agents[0].generate_prediction_result(&info).await?;
+        let mut text = format!("{}\n\n{}", generated, record.line());
+        if !TweetPreview::new(&text).fits() {
+            let verdict = if prediction.outcome.as_ref().is_some_and(|o| o.hit) { "called it" } else { "missed" };
+            text = format!("${} {}: {}\n\n{}", prediction.symbol, prediction.claim(), verdict, record.line());
+        }
+
+        if !self.memory.tweet_mode {
+            println!("Tweet mode is disabled, prediction result not posted:");
+            Self::print_preview(&text);
+            return Ok(());
+        }
+        let posted = match &prediction.tweet_id {
+            Some(tweet_id) => self.twitter.reply_to_tweet(tweet_id, text.clone()).await?,
+            None => self.twitter.tweet(text.clone()).await?,
+        };
+        println!("Posted prediction result for ${}: {}", prediction.symbol, text);
+        self.mirror_to_telegram(&text, &posted.id.to_string(), None).await;
+        let agent_prompt = self.agents[0].prompt.clone();
+        let saved = match &prediction.tweet_id {
+            Some(tweet_id) => MemoryStore::add_reply_to_memory(
+                &mut self.memory,
+                &text,
+                &agent_prompt,
+                Some(posted.id.to_string()),
+                tweet_id.clone(),
+                self.clock.now(),
+            ),
+            None => MemoryStore::add_to_memory(&mut self.memory, &text, &agent_prompt, Some(posted.id.to_string()), self.clock.now()),
+        };
+        if let Err(e) = saved.and_then(|_| {
+            MemoryStore::record_tweet_token(&mut self.memory, &posted.id.to_string(), &prediction.mint, &prediction.symbol)
+        }) {
+            eprintln!("Failed to save prediction result to memory: {}", e);
+        }
+        Ok(())
+    }
+
+    fn should_check_consistency(&self) -> bool {
+        let settings = &self.settings.consistency_check;
+        settings.enabled
+            && Schedule::elapsed(
+                self.memory.last_consistency_check,
+                chrono::Duration::hours(settings.interval_hours.max(1)),
+                self.clock.now(),
+            )
+    }
+
+    // Ask the model whether recent posts break the persona or contradict each other.
+    // Findings are logged; with flag_topics their topics are kept out of new posts.
+    async fn check_consistency(&mut self) -> Result<(), FudError> {
+        let settings = self.settings.consistency_check.clone();
+        let now = self.clock.now();
+        let posts = ConsistencyCheck::sample(&self.memory, settings.sample_size);
+        if posts.len() < 2 {
+            MemoryStore::record_consistency_check(&mut self.memory, &[], now, None)?;
+            return Ok(());
+        }
+        let count = posts.len();
+        let numbered = ConsistencyCheck::numbered(&posts, now);
+        let response = self.agents[0].review_consistency(&numbered).await?;
+        let Some(findings) = ConsistencyCheck::parse(&response) else {
+            println!("Couldn't read the consistency check, trying again next time");
+            MemoryStore::record_consistency_check(&mut self.memory, &[], now, None)?;
+            return Ok(());
+        };
+
+        if findings.is_empty() {
+            println!("Consistency check: nothing off in the last {} posts", count);
+        }
+        for finding in &findings {
+            let posts: Vec<String> = finding.posts.iter().map(usize::to_string).collect();
+            println!("Consistency check: {} (posts {})", finding.issue, posts.join(", "));
+        }
+        let avoid_until = settings.flag_topics.then(|| now + chrono::Duration::days(settings.avoid_days));
+        let flagged = MemoryStore::record_consistency_check(&mut self.memory, &findings, now, avoid_until)?;
+        if flagged > 0 {
+            println!("Avoiding {} new topic(s) for {} days", flagged, settings.avoid_days);
+        }
+        *Self::lock(&self.avoided_topics) = self.memory.avoided_topics.clone();
+        Ok(())
+    }
+
+    fn should_refresh_metrics(&self) -> bool {
+        Schedule::elapsed(self.last_metrics_refresh, chrono::Duration::minutes(METRICS_REFRESH_MINUTES), self.clock.now())
+    }
+
+    // Pull likes/retweets/replies for everything we posted in the last few days
+    async fn refresh_engagement_metrics(&mut self) -> Result<(), FudError> {
+        let now = self.clock.now();
+        self.last_metrics_refresh = Some(now);
+        let since = now - chrono::Duration::days(METRICS_TRACK_DAYS);
+        let ids: Vec<u64> = self.memory.tweets
+            .iter()
+            .rev()
+            .filter(|t| t.timestamp >= since)
+            .filter_map(|t| t.twitter_id.as_ref()?.parse().ok())
+            .take(100)
+            .collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let metrics = self.twitter.get_tweet_metrics(&ids).await?;
+        println!("Refreshed engagement metrics for {} tweets", metrics.len());
+        MemoryStore::record_metrics(&mut self.memory, metrics)?;
+        Ok(())
+    }
+
+    fn should_check_whale_alerts(&self) -> bool {
+        if !self.settings.whale_alerts.enabled {
+            return false;
+        }
+        Schedule::elapsed(
+            self.last_whale_check,
+            chrono::Duration::minutes(self.settings.whale_alerts.check_interval_minutes),
+            self.clock.now(),
+        )
+    }
+
+    // Look for big sells on tokens we recently FUDded and gloat about them in the original thread
+    async fn check_whale_alerts(&mut self) -> Result<(), FudError> {
+        if self.agents.is_empty() {
+            return Err(FudError::NotFound("agent".to_string()));
+        }
+
+        let settings = self.settings.whale_alerts.clone();
+        let now = self.clock.now();
+        self.last_whale_check = Some(now);
+
+        let cutoff = now - chrono::Duration::hours(settings.watch_hours);
+        let watched: Vec<WatchedToken> = self.memory.watched_tokens
+            .iter()
+            .filter(|t| t.fudded_at > cutoff && t.alerts_sent < settings.max_alerts_per_token)
+            .cloned()
+            .collect();
+
+        for watched_token in watched {
+            let token = match self.solana_tracker.get_token_by_address(&watched_token.mint).await {
+                Ok(token) => token,
+                Err(e) => {
+                    eprintln!("Failed to fetch ${} for whale watch: {}", watched_token.symbol, e);
+                    continue;
+                }
+            };
+            let trades = match self.solana_tracker.get_token_trades(&watched_token.mint).await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    eprintln!("Failed to fetch trades for ${}: {}", watched_token.symbol, e);
+                    continue;
+                }
+            };
+
+            let market_cap = token.market_cap().usd;
+            let Some(threshold) = settings.threshold_for(market_cap) else {
+                continue;
+            };
+
+            let biggest_sell = trades
+                .iter()
+                .filter(|t| t.is_sell() && t.time > watched_token.last_trade_time && t.volume >= threshold)
+                .max_by(|a, b| a.volume.partial_cmp(&b.volume).unwrap_or(std::cmp::Ordering::Equal))
+                .cloned();
+            let newest_trade_time = trades
+                .iter()
+                .map(|t| t.time)
+                .max()
+                .unwrap_or(watched_token.last_trade_time)
+                .max(watched_token.last_trade_time);
+
+            let mut alert_sent = false;
+            if let Some(sell) = biggest_sell {
+                let supply_share = if market_cap > 0.0 { sell.volume / market_cap * 100.0 } else { 0.0 };
+                let wallet: String = sell.wallet.chars().take(6).collect();
+                let trade_info = format!(
+                    "Sold: {} ({:.0} tokens at ${:.8})\nApprox share of supply: {:.1}%\nWallet: {}...\n",
+                    SolanaTracker::format_currency(sell.volume),
+                    sell.amount,
+                    sell.price_usd,
+                    supply_share,
+                    wallet,
+                );
+                println!("Whale sell on ${}: {}", watched_token.symbol, trade_info.replace('\n', " "));
+
+                let token_summary = self.summary_with_past_takes(&token);
+                // A failed generation only costs this token's alert; its trades are
+                // read again next check, and the rest still get theirs and are saved
+                let alert = match self.agents[0].generate_whale_alert(&token_summary, &trade_info).await {
+                    Ok(alert) => alert,
+                    Err(e) => {
+                        eprintln!("Failed to write whale alert for ${}: {}", watched_token.symbol, e);
+                        self.record_api_error("whale_alerts");
+                        continue;
+                    }
+                };
+                let agent_prompt = self.agents[0].prompt.clone();
+
+                if self.memory.tweet_mode {
+                    let posted = match &watched_token.tweet_id {
+                        Some(tweet_id) => self.twitter.reply_to_tweet(tweet_id, alert.clone()).await,
+                        None => self.twitter.tweet(alert.clone()).await,
+                    };
+                    match posted {
+                        Ok(tweet) => {
+                            println!("Posted whale alert for ${}: {}", watched_token.symbol, alert);
+                            self.mirror_to_telegram(&alert, &tweet.id.to_string(), None).await;
+                            let saved = match &watched_token.tweet_id {
+                                Some(tweet_id) => MemoryStore::add_reply_to_memory(
+                                    &mut self.memory,
+                                    &alert,
+                                    &agent_prompt,
+                                    Some(tweet.id.to_string()),
+                                    tweet_id.clone(),
+                                    self.clock.now(),
+                                ),
+                                None => MemoryStore::add_to_memory(&mut self.memory, &alert, &agent_prompt, Some(tweet.id.to_string()), self.clock.now()),
+                            };
+                            let saved = saved.and_then(|_| {
+                                MemoryStore::record_tweet_token(
+                                    &mut self.memory,
+                                    &tweet.id.to_string(),
+                                    &watched_token.mint,
+                                    &watched_token.symbol,
+                                )
+                            });
+                            if let Err(e) = saved {
+                                eprintln!("Failed to save whale alert to memory: {}", e);
+                            }
+                            alert_sent = true;
+                        }
+                        Err(e) => eprintln!("Failed to post whale alert: {}", e),
+                    }
+                } else {
+                    println!("Tweet mode is disabled, whale alert not posted:");
+                    Self::print_preview(&alert);
+                    alert_sent = true;
+                }
+            }
+
+            if let Some(entry) = self.memory.watched_tokens.iter_mut().find(|t| t.mint == watched_token.mint) {
+                entry.last_trade_time = newest_trade_time;
+                if alert_sent {
+                    entry.alerts_sent += 1;
+                }
+            }
+        }
+
+        MemoryStore::save_memory(&self.memory)?;
+        Ok(())
+    }
+
+    // Search Twitter for tweets shilling trending tokens and reply to the most engaged one
+    async fn engage_with_trends(&mut self) -> Result<(), FudError> {
+        if self.agents.is_empty() {
+            return Err(FudError::NotFound("agent".to_string()));
+        }
+
+        let settings = self.settings.trend_engagement.clone();
+        let now = self.clock.now();
+
+        let replies_last_hour = MemoryStore::count_trend_engagements_since(
+            &self.memory,
+            now - chrono::Duration::hours(1),
+        );
+        if replies_last_hour >= settings.max_replies_per_hour {
+            println!("Trend engagement hourly cap reached ({}/{}), skipping", replies_last_hour, settings.max_replies_per_hour);
+            return Ok(());
+        }
+
+        let user_id = self.ensure_user_id().await?.to_string();
+        let cooldown_start = now - chrono::Duration::hours(settings.author_cooldown_hours);
+
+        let mut tokens = self.solana_tracker.get_top_tokens(settings.tokens_to_search).await?;
+        tokens.shuffle(&mut thread_rng());
+
+        for token in tokens {
+            let symbol = token.token.symbol.clone();
+            if symbol.is_empty() || self.is_own_token(&symbol) || self.is_own_token(&token.token.mint) {
+                continue;
+            }
+
+            let query = format!("${} -is:retweet -is:reply", symbol);
+            let tweets = match self.twitter.search_recent_tweets(&query, 50).await {
+                Ok(tweets) => tweets,
+                Err(e) => {
+                    eprintln!("Error searching tweets for ${}: {}", symbol, e);
+                    if e.is_rate_limited() {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
+
+            // Pick the most engaged tweet we haven't replied to from an author not on cooldown
+            let target = tweets
+                .into_iter()
+                .filter_map(|tweet| {
+                    let author_id = tweet.author_id.as_ref()?.to_string();
+                    let metrics = tweet.public_metrics.as_ref()?;
+                    let engagement = metrics.like_count
+                        + metrics.retweet_count * 2
+                        + metrics.reply_count
+                        + metrics.quote_count.unwrap_or(0);
+                    Some((tweet, author_id, engagement))
+                })
+                .filter(|(tweet, author_id, engagement)| {
+                    *engagement >= settings.min_engagement
+                        && *author_id != user_id
+                        && !tweet.author_id.is_some_and(|id| self.spam_filter.is_blocked(id.as_u64()))
+                        && !MemoryStore::engaged_with_author_since(&self.memory, author_id, cooldown_start)
+                        && !MemoryStore::has_replied_to(&self.memory, &tweet.id.to_string())
+                })
+                .max_by_key(|(_, _, engagement)| *engagement);
+
+            let Some((tweet, author_id, engagement)) = target else {
+                println!("No eligible tweets found for ${}", symbol);
+                continue;
+            };
+
+            println!("Engaging with tweet {} about ${} (engagement {})", tweet.id, symbol, engagement);
+            let token_summary = self.summary_with_past_takes(&token);
+            let token_summary = self.with_learned_facts(&token, token_summary);
+            let token_summary = self.cross_check_market_cap(&token, token_summary).await;
+            let token_summary = self.with_token_metadata(&token, token_summary).await;
+            let token_summary = self.with_bonding_curve(&token, token_summary).await;
+            let reply = self.agents[0].generate_trend_reply(&token_summary, &tweet.text).await?;
+            let tweet_id = tweet.id.to_string();
+
+            if self.memory.tweet_mode {
+                match self.twitter.reply_to_tweet(&tweet_id, reply.clone()).await {
+                    Ok(_) => {}
+                    // Gone or protected since the search; another token's tweet can still go out
+                    Err(e) if e.is_unrepliable() => {
+                        println!("Skipping trend reply: {}", e);
+                        self.mark_responded(&tweet_id, ResponseState::Unrepliable);
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to post trend reply: {}", e);
+                        return Ok(());
+                    }
+                }
+                println!("Posted trend reply to {}: {}", tweet_id, reply);
+                self.mark_responded(&tweet_id, ResponseState::Replied);
+                self.style().record(&reply);
+            } else {
+                println!("Tweet mode is disabled, trend reply not posted:");
+                Self::print_preview(&reply);
+            }
+
+            let agent_prompt = self.agents[0].prompt.clone();
+            if let Err(e) = MemoryStore::add_reply_to_memory(
+                &mut self.memory,
+                &reply,
+                &agent_prompt,
+                None,
+                tweet_id.clone(),
+                self.clock.now(),
+            ) {
+                eprintln!("Failed to save trend reply to memory: {}", e);
+            }
+
+            let engagement = TrendEngagement {
+                tweet_id,
+                author_id,
+                token_symbol: symbol,
+                timestamp: now,
+            };
+            if let Err(e) = MemoryStore::add_trend_engagement(
+                &mut self.memory,
+                engagement,
+                chrono::Duration::hours(settings.author_cooldown_hours.max(1)),
+            ) {
+                eprintln!("Failed to save trend engagement: {}", e);
+            }
+
+            // One reply per cycle; the hourly cap spreads out the rest
+            break;
+        }
+
+        Ok(())
+    }
+
+    // Send the next queued reply once its slot comes up. A mention that can no longer
+    // take a reply costs nothing, so the one after it goes out in the same cycle.
+    async fn process_reply_queue(&mut self) -> Result<(), FudError> {
+        while self.send_next_queued_reply().await? {}
+        Ok(())
+    }
+
+    // True when the reply was dropped without anything reaching Twitter and the next
+    // one can be tried right away
+    async fn send_next_queued_reply(&mut self) -> Result<bool, FudError> {
+        let Some(reply) = MemoryStore::next_due_reply(&self.memory, self.clock.now()) else {
+            return Ok(false);
+        };
+
+        // Left mid-send by a crash; it may well have gone out, so it isn't sent again
+        if self.responded.state(&reply.reply_to) == Some(ResponseState::Sending) {
+            println!("Reply to {} was being sent when we stopped, dropping it instead of risking a double reply", reply.reply_to);
+            MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+            return Ok(false);
+        }
+
+        if !self.memory.tweet_mode {
+            println!("Tweet mode is disabled, dropping queued reply to {}", reply.reply_to);
+            MemoryStore::forget_reply(&mut self.memory, &reply.reply_to);
+            MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+            self.mark_responded(&reply.reply_to, ResponseState::Handled);
+            return Ok(false);
+        }
+
+        if !self.quota_allows("queued reply", QuotaPriority::Reply, &[ENDPOINT_TWEETS]) {
+            MemoryStore::delay_reply_queue(&mut self.memory, self.settings.reply_queue.window())?;
+            return Ok(false);
+        }
+
+        match self.claim_responded(&reply.reply_to) {
+            Ok(true) => {}
+            // Another instance queued it too and got there first
+            Ok(false) => {
+                MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+                return Ok(false);
+            }
+            Err(_) => return Ok(false),
+        }
+        match self.twitter.reply_to_tweet(&reply.reply_to, reply.text.clone()).await {
+            Ok(posted) => {
+                println!("Successfully replied to tweet {}", reply.reply_to);
+                self.mark_responded(&reply.reply_to, ResponseState::Replied);
+                self.style().record(&reply.text);
+                MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+                if let Err(e) = MemoryStore::add_reply_to_memory(
+                    &mut self.memory,
+                    &reply.text,
+                    &reply.prompt,
+                    Some(posted.id.to_string()),
+                    reply.reply_to.clone(),
+                    self.clock.now(),
+                ) {
+                    eprintln!("Failed to save response to memory: {}", e);
+                }
+                if let Some(mint) = &reply.token_mint {
+                    let symbol = reply.token_symbol.as_deref().unwrap_or_default();
+                    if let Err(e) = MemoryStore::record_tweet_token(&mut self.memory, &posted.id.to_string(), mint, symbol) {
+                        eprintln!("Failed to save the token the reply was about: {}", e);
+                    }
+                }
+            }
+            Err(e) if e.is_rate_limited() => {
+                println!("Rate limit hit, pushing reply queue back one window");
+                self.mark_responded(&reply.reply_to, ResponseState::Queued);
+                MemoryStore::delay_reply_queue(&mut self.memory, self.settings.reply_queue.window())?;
+            }
+            Err(e) if e.is_unrepliable() => {
+                println!("Dropping queued reply: {}", e);
+                self.mark_responded(&reply.reply_to, ResponseState::Unrepliable);
+                MemoryStore::forget_reply(&mut self.memory, &reply.reply_to);
+                MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+                return Ok(true);
+            }
+            Err(e) => {
+                println!("Failed to reply to tweet {}: {}", reply.reply_to, e);
+                self.mark_responded(&reply.reply_to, ResponseState::Queued);
+                let max_attempts = self.settings.reply_queue.max_attempts;
+                let spacing = self.settings.reply_queue.spacing();
+                let mut give_up = false;
+                if let Some(queued) = self.memory.reply_queue.iter_mut().find(|r| r.reply_to == reply.reply_to) {
+                    queued.attempts += 1;
+                    queued.scheduled_at = self.clock.now() + spacing;
+                    give_up = queued.attempts >= max_attempts;
+                }
+                if give_up {
+                    println!("Giving up on reply to {} after {} attempts", reply.reply_to, max_attempts);
+                    self.mark_responded(&reply.reply_to, ResponseState::Unrepliable);
+                    MemoryStore::forget_reply(&mut self.memory, &reply.reply_to);
+                    MemoryStore::remove_queued_reply(&mut self.memory, &reply.reply_to)?;
+                } else {
+                    MemoryStore::save_memory(&self.memory)?;
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub async fn handle_notifications_fud(&mut self) -> Result<(), FudError> {
+        if self.agents.is_empty() {
+            return Err(FudError::NotFound("agent".to_string()));
+        }
+    
+        if !self.should_check_notifications().await {
+            return Ok(());
+        }
+    
+        println!("Checking notifications...");
+        let user_id = self.ensure_user_id().await?;
+    
+        match self.twitter.get_notifications(user_id).await {
+            Ok(notifications) => {
+                println!("Found {} total notifications", notifications.tweets.len());
+                self.last_notification_check = Some(self.clock.now());
+                let images = notifications.images;
+                let referenced = notifications.referenced;
+                let campaign = Campaigns::running(&self.memory.campaigns, self.clock.now()).cloned();
+                if let Some(campaign) = &campaign {
+                    let mentions: Vec<String> = notifications
+                        .tweets
+                        .iter()
+                        .filter(|tweet| campaign.mentioned_in(&tweet.text))
+                        .map(|tweet| tweet.id.to_string())
+                        .collect();
+                    match MemoryStore::record_campaign_mentions(&mut self.memory, &mentions, self.clock.now()) {
+                        Ok(0) => {}
+                        Ok(added) => println!("{} new mentions of campaign token ${}", added, campaign.symbol),
+                        Err(e) => eprintln!("Failed to save campaign mentions: {}", e),
+                    }
+                }
+                
+                let unresponded_notifications: Vec<_> = notifications
+                    .tweets
+                    .into_iter()
+                    .filter(|tweet| !self.responded.contains(&tweet.id.to_string()))
+                    .collect();
+                let unresponded_notifications = self.filter_spam_mentions(unresponded_notifications).await;
+                let unresponded_notifications = self.filter_reply_policy(unresponded_notifications).await;
+                let unresponded_notifications = self.filter_reply_limits(unresponded_notifications);
+                let (unresponded_notifications, communities) = self.filter_community_mentions(unresponded_notifications).await;
+                
+                println!("Processing {} unresponded notifications", unresponded_notifications.len());
+                
+                let candidates: Vec<_> = {
+                    use rand::seq::SliceRandom;
+                    let mut rng = rand::thread_rng();
+                    // Mentions of the campaign token get answered first
+                    let (mut selected, mut rest): (Vec<_>, Vec<_>) = unresponded_notifications
+                        .into_iter()
+                        .partition(|tweet| campaign.as_ref().is_some_and(|campaign| campaign.mentioned_in(&tweet.text)));
+                    selected.shuffle(&mut rng);
+                    rest.shuffle(&mut rng);
+                    selected.append(&mut rest);
+                    selected.truncate(CLASSIFY_BATCH_SIZE);
+                    selected
+                };
+                // One classification call for the batch, then at most 3 replies per sweep;
+                // the rest are left for the next one
+                let mut notifications_to_process = self.classify_mentions(candidates).await;
+                notifications_to_process.truncate(MAX_REPLIES_PER_SWEEP);
+
+                println!("Processing {} notifications", notifications_to_process.len());
+                
+                // Lookups and generations for the batch run side by side, a few at a
+                // time; the bookkeeping after is done one mention at a time, and the
+                // replies themselves go out through the queue at its own pace
+                let permits = Semaphore::new(MAX_CONCURRENT_DRAFTS);
+                let drafts = {
+                    let this = &*self;
+                    join_all(notifications_to_process.into_iter().map(|tweet| {
+                        let tweet_id = tweet.id.to_string();
+                        let (images, referenced, community) =
+                            (images.get(&tweet_id), referenced.get(&tweet_id), communities.get(&tweet_id));
+                        let permits = &permits;
+                        async move {
+                            let _permit = permits.acquire().await;
+                            this.draft_reply(tweet, images, referenced, community).await
+                        }
+                    }))
+                    .await
+                };
+
+                let mut first_error = None;
+                for draft in drafts {
+                    if draft.vision_failed {
+                        self.record_api_error("vision");
+                    }
+                    let tweet = draft.tweet;
+                    let tweet_id = tweet.id.to_string();
+                    if !draft.facts.is_empty() {
+                        let max_per_token = self.settings.learned_facts.max_per_token;
+                        match MemoryStore::add_token_facts(&mut self.memory, draft.facts, max_per_token) {
+                            Ok(0) => {}
+                            Ok(added) => println!("Learned or confirmed {} facts from {}", added, tweet_id),
+                            Err(e) => eprintln!("Failed to save facts from {}: {}", tweet_id, e),
+                        }
+                    }
+                    let (fud_response, reply_token, rule) = match draft.reply {
+                        Ok(DraftedReply::Reply { text, token, rule }) => (text, token, rule),
+                        Ok(DraftedReply::Ignored) => {
+                            self.mark_responded(&tweet_id, ResponseState::Handled);
+                            continue;
+                        }
+                        Ok(DraftedReply::Skipped) => continue,
+                        Err(e) => {
+                            eprintln!("Failed to draft a reply to {}: {}", tweet_id, e);
+                            first_error.get_or_insert(e);
+                            continue;
+                        }
+                    };
+    
+                    let agent_prompt = self.agents[0].prompt.clone();
+                    if let Some(rule) = rule.filter(|rule| rule.action == RuleAction::Escalate) {
+                        let held = HeldReply {
+                            reply_to: tweet_id.clone(),
+                            mention: tweet.text.clone(),
+                            text: fud_response,
+                            prompt: agent_prompt,
+                            rule: rule.label,
+                            held_at: self.clock.now(),
+                            token_mint: reply_token.as_ref().map(|(mint, _)| mint.clone()),
+                            token_symbol: reply_token.map(|(_, symbol)| symbol),
+                        };
+                        self.hold_for_approval(held).await;
+                        continue;
+                    }
+                    if let Err(e) = MemoryStore::record_thread_reply(&mut self.memory, &Self::conversation_of(&tweet), self.clock.now()) {
+                        eprintln!("Failed to record thread depth: {}", e);
+                    }
+
+                    if self.memory.tweet_mode {
+                        // Replies go out from the queue so a restart mid-sweep doesn't lose them
+                        let spacing = self.settings.reply_queue.spacing();
+                        if !self.take_responded(&tweet_id, ResponseState::Queued) {
+                            continue;
+                        }
+                        match MemoryStore::enqueue_reply(&mut self.memory, tweet_id.clone(), &fud_response, &agent_prompt, reply_token, spacing, self.clock.now()) {
+                            Ok(scheduled_at) => println!(
+                                "Queued reply to tweet {} for {}",
+                                tweet_id,
+                                scheduled_at.format("%H:%M:%S")
+                            ),
+                            Err(e) => {
+                                eprintln!("Failed to queue reply: {}", e);
+                                self.release_responded(&tweet_id);
+                            }
+                        }
+                    } else {
+                        if let Err(e) = MemoryStore::add_reply_to_memory(
+                            &mut self.memory,
+                            &fud_response,
+                            &agent_prompt,
+                            None,
+                            tweet_id.clone(),
+                            self.clock.now(),
+                        ) {
+                            eprintln!("Failed to save response to memory: {}", e);
+                        }
+                        self.mark_responded(&tweet_id, ResponseState::Handled);
+                        println!("Tweet mode is disabled, skipping reply:");
+                        Self::print_preview(&fud_response);
+                    }
+                }
+                if let Some(e) = first_error {
+                    return Err(e);
+                }
+                
+                Ok(())
+            }
+            Err(e) => {
+                if e.is_rate_limited() {
+                    println!("Rate limit hit for notifications, will retry in 15 minutes");
+                    self.last_notification_check = Some(self.clock.now());
+                    Ok(())
+                } else {
+                    println!("Error getting notifications: {}", e);
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Drops the mentions the model says aren't worth a reply, deciding for the whole
+    // batch in one call. Mentions a reply rule matches aren't asked about, the rule
+    // decides for them. Ignored ones are marked handled; if the call fails, all are kept.
+    async fn classify_mentions(&mut self, mentions: Vec<twitter_v2::Tweet>) -> Vec<twitter_v2::Tweet> {
+        let unruled: Vec<usize> = mentions
+            .iter()
+            .enumerate()
+            .filter(|(_, tweet)| ReplyRules::evaluate(&self.settings.reply_rules, &tweet.text).is_none())
+            .map(|(i, _)| i)
+            .collect();
+        if unruled.is_empty() {
+            return mentions;
+        }
+        let texts: Vec<&str> = unruled.iter().map(|i| mentions[*i].text.as_str()).collect();
+        let decisions = match self.agents[0].should_respond_batch(&texts).await {
+            Ok(decisions) => decisions,
+            Err(e) => {
+                eprintln!("Failed to classify mentions, answering all of them: {}", e);
+                return mentions;
+            }
+        };
+        let ignored: Vec<usize> = unruled
+            .into_iter()
+            .zip(decisions)
+            .filter(|(_, decision)| *decision == ResponseDecision::Ignore)
+            .map(|(i, _)| i)
+            .collect();
+        let mut kept = Vec::new();
+        for (i, tweet) in mentions.into_iter().enumerate() {
+            if ignored.contains(&i) {
+                println!("Agent decided to ignore tweet: {}", tweet.text);
+                self.mark_responded(&tweet.id.to_string(), ResponseState::Handled);
+            } else {
+                kept.push(tweet);
+            }
+        }
+        kept
+    }
+
+    // Works out the reply to one mention without touching memory, so a batch can be
+    // drafted side by side. The reply is written in the mention's language when allowed.
+    async fn draft_reply(
+        &self,
+        tweet: twitter_v2::Tweet,
+        images: Option<&Vec<String>>,
+        referenced: Option<&Vec<ReferencedPost>>,
+        community: Option<&Option<String>>,
+    ) -> MentionDraft {
+        println!("Processing tweet: {}", tweet.text);
+        let reply_language = self.settings.language.replies.for_mention(&tweet.text);
+        if let Some(language) = &reply_language {
+            println!("Replying to {} in {}", tweet.id, language);
+        }
+        let (attachment, vision_failed) = match self.describe_attachment(images).await {
+            Ok(attachment) => (attachment, false),
+            Err(e) => {
+                eprintln!("Failed to describe attached images: {}", e);
+                (None, true)
+            }
+        };
+        let reply = Agent::in_reply_language(
+            reply_language,
+            self.draft_reply_text(&tweet, attachment, referenced, community),
+        )
+        .await;
+        let facts = match &reply {
+            Ok(DraftedReply::Reply { token: Some((mint, symbol)), rule, .. })
+                if !rule.as_ref().is_some_and(|rule| rule.action == RuleAction::Escalate) =>
+            {
+                self.extract_facts(&tweet, mint, symbol).await
+            }
+            _ => Vec::new(),
+        };
+        MentionDraft { tweet, reply, vision_failed, facts }
+    }
+
+    // What the mention claims about the token, for later FUD about it. Escalated
+    // mentions are left out; they're waiting on an admin for a reason.
+    async fn extract_facts(&self, tweet: &twitter_v2::Tweet, mint: &str, symbol: &str) -> Vec<TokenFact> {
+        let settings = &self.settings.learned_facts;
+        if !settings.enabled || settings.max_per_mention == 0 || !LearnedFacts::worth_extracting(&tweet.text, settings) {
+            return Vec::new();
+        }
+        let response = match self.agents[0].extract_token_facts(symbol, &tweet.text, settings.max_per_mention).await {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("Failed to extract facts from {}: {}", tweet.id, e);
+                return Vec::new();
+            }
+        };
+        let now = self.clock.now();
+        let authors: Vec<String> = tweet.author_id.iter().map(|id| id.to_string()).collect();
+        LearnedFacts::parse(&response, settings.max_per_mention)
+            .into_iter()
+            .map(|claim| TokenFact {
+                mint: mint.to_string(),
+                symbol: symbol.to_string(),
+                claim,
+                source_tweet_id: tweet.id.to_string(),
+                learned_at: now,
+                authors: authors.clone(),
+            })
+            .collect()
+    }
+
+    // A past post about a similar situation to call back to, below the summary
+    fn with_callback(&self, token: &TokenResponse, summary: String) -> String {
+        let settings = &self.settings.callbacks;
+        if !settings.enabled {
+            return summary;
+        }
+        let now = self.clock.now();
+        match Callbacks::find(&self.memory.tweets, &summary, &token.token.mint, settings, now) {
+            Some(tweet) => {
+                println!("Calling back to {} for ${}", tweet.twitter_id.as_deref().unwrap_or_default(), token.token.symbol);
+                format!("{}\n{}", summary, Callbacks::prompt_section(tweet, now))
+            }
+            None => summary,
+        }
+    }
+
+    // Facts people told us about the token lately, below its summary
+    fn with_learned_facts(&self, token: &TokenResponse, summary: String) -> String {
+        let settings = &self.settings.learned_facts;
+        if !settings.enabled || token.token.mint.is_empty() {
+            return summary;
+        }
+        let since = self.clock.now() - chrono::Duration::days(settings.lookback_days);
+        let facts = LearnedFacts::relevant(&self.memory.token_facts, &token.token.mint, since, settings.max_in_prompt, settings.min_authors);
+        if facts.is_empty() {
+            return summary;
+        }
+        format!("{}\n{}", summary, LearnedFacts::prompt_section(&facts, &token.token.symbol, self.clock.now()))
+    }
+
+    async fn draft_reply_text(
+        &self,
+        tweet: &twitter_v2::Tweet,
+        attachment: Option<String>,
+        referenced: Option<&Vec<ReferencedPost>>,
+        community: Option<&Option<String>>,
+    ) -> Result<DraftedReply, FudError> {
+        // The quoted or parent post, so the reply knows what they're reacting to
+        let context = referenced.map(|posts| ReferencedPost::prompt_context(posts));
+        let mention_text = match &context {
+            Some(context) => format!("{}\n{}", tweet.text, context),
+            None => tweet.text.clone(),
+        };
+        // Where they posted it, for the model only; the name isn't scanned for tokens
+        let context = match community.map(|name| Self::community_line(name.as_deref())) {
+            Some(line) => Some(match context {
+                Some(context) => format!("{}\n{}", context, line),
+                None => line,
+            }),
+            None => context,
+        };
+        
+        let rule = ReplyRules::evaluate(&self.settings.reply_rules, &tweet.text);
+        let action = rule.as_ref().map(|rule| rule.action);
+        if let Some(rule) = &rule {
+            println!("Reply rule {} matched ({:?})", rule.label, rule.action);
+        }
+        if action == Some(RuleAction::Ignore) {
+            return Ok(DraftedReply::Ignored);
+        }
+        // A token_lookup rule goes straight to FUD about its token
+        let rule_token = match &rule {
+            Some(rule) if rule.action == RuleAction::TokenLookup => Self::rule_token(rule.capture.as_deref(), &mention_text),
+            _ => None,
+        };
+
+        let canned_reply = rule.as_ref().and_then(|rule| rule.canned_reply.clone());
+        let follow_up = match FollowUp::detect(&tweet.text) {
+            Some(question) if canned_reply.is_none() && action != Some(RuleAction::Insult) && rule_token.is_none() => {
+                self.answer_follow_up(tweet, question).await
+            }
+            _ => None,
+        };
+
+        // (mint, symbol) of the token the reply ends up being about
+        let mut reply_token = None;
+        let text = if let Some(reply) = canned_reply {
+            reply
+        } else if action == Some(RuleAction::Insult) {
+            self.generate_insult_reply(&tweet.text, context.as_deref()).await?
+        } else if let Some((answer, token)) = follow_up {
+            reply_token = Some((token.token.mint.clone(), token.token.symbol.clone()));
+            answer
+        } else if let Some(request) = Self::is_token_info_request(&tweet.text).filter(|_| rule_token.is_none()) {
+            println!("Detected token info request: {:?}", request);
+            // Under one of our token threads, answer with that token's live data
+            match self.resolve_thread_token(tweet).await {
+                Some(token) => {
+                    reply_token = Some((token.token.mint.clone(), token.token.symbol.clone()));
+                    Self::format_thread_token_answer(request, &token)
+                }
+                None => self.handle_token_info_request(request),
+            }
+        } else if let Some(comparison) = match rule_token {
+            Some(_) => None,
+            None => self.generate_comparison_reply(&tweet.text, &mention_text).await?,
+        } {
+            comparison
+        } else if let Some((token, is_address)) = rule_token
+            .clone()
+            .or_else(|| Self::extract_ticker_or_address(&tweet.text))
+            .or_else(|| Self::extract_ticker_or_address(&mention_text))
+        {
+            println!("Found token/address in tweet: {} (is_address: {})", token, is_address);
+            
+            let token_info = self.lookup_token(&token, is_address).await;
+            let own_token = self.is_own_token(&token)
+                || token_info.as_ref().is_some_and(|t| self.is_own_token(&t.token.mint));
+            
+            if own_token {
+                println!("Mention is about our own token, shilling instead of FUD");
+                // Left for the next sweep rather than failing it
+                match self.generate_shill().await {
+                    Ok(Some(shill)) => shill,
+                    Ok(None) => return Ok(DraftedReply::Skipped),
+                    Err(e) => {
+                        eprintln!("Failed to write a shill reply to {}: {}", tweet.id, e);
+                        return Ok(DraftedReply::Skipped);
+                    }
+                }
+            } else if let Some(token) = token_info {
+                println!(
+                    "Found token {} with liquidity ${:.2}", 
+                    token.token.symbol,
+                    token.pools.first().map(|p| p.liquidity.usd).unwrap_or(0.0)
+                );
+                if !token.token.mint.is_empty() {
+                    reply_token = Some((token.token.mint.clone(), token.token.symbol.clone()));
+                }
+                let token_summary = self.summary_with_past_takes(&token);
+                let token_summary = self.with_learned_facts(&token, token_summary);
+                let token_summary = self.with_callback(&token, token_summary);
+                let token_summary = self.with_token_metadata(&token, token_summary).await;
+                let token_summary = self.with_bonding_curve(&token, token_summary).await;
+                let token_summary = match &attachment {
+                    Some(attachment) => format!("{}\nImage they posted: {}", token_summary, attachment),
+                    None => token_summary,
+                };
+                let token_summary = match &context {
+                    Some(context) => format!("{}\n{}", token_summary, context),
+                    None => token_summary,
+                };
+                if self.use_prebond_prompt(&token.token.mint) {
+                    self.agents[0].generate_prebond_fud(&token_summary).await?
+                } else {
+                    self.agents[0].generate_editorialized_fud(&token_summary).await?
+                }
+            } else {
+                println!("No token found for {}, using generic FUD", token);
+                self.solana_tracker.generate_generic_fud_with_agent(&self.agents[0]).await?
+            }
+        } else if let Some(attachment) = &attachment {
+            println!("No ticker/address found, roasting the attached image");
+            self.agents[0].generate_attachment_reply(&mention_text, attachment).await?
+        } else {
+            println!("No ticker/address found, generating generic insult response");
+            self.generate_insult_reply(&tweet.text, context.as_deref()).await?
+        };
+        Ok(DraftedReply::Reply { text, token: reply_token, rule })
+    }
+
+    // Vicious generic reply for mentions with no token to talk about
+    async fn generate_insult_reply(&self, text: &str, context: Option<&str>) -> Result<String, FudError> {
+        let task = r#"Task: Generate a vicious sarcastic insult response.
+        Requirements:
+        - Stay under 240 characters
+        - Be extremely condescending and mocking
+        - Question the person's intelligence and trading abilities
+        - Use all lowercase except for token symbols
+        - Focus on their lack of understanding or research
+        - Do not include tickers or symbols ($) in your response
+        - Do not mention specific tokens
+        Write ONLY the response text with no additional commentary:"#;
+        let prompt = match context {
+            Some(context) => format!("Their post: '{}'\n{}\n{}", text, context, task),
+            None => task.to_string(),
+        };
+        let prompt = match self.agents[0].reply_language().filter(|language| !LanguageSettings::is_english(language)) {
+            Some(language) => format!("Write the response entirely in {}, the language they wrote in.\n{}", language, prompt),
+            None => prompt,
+        };
+        Ok(self.agents[0].generate_custom_response(&prompt).await?)
+    }
+
+    // Token a token_lookup rule points at: its regex capture, else whatever the mention names
+    fn rule_token(capture: Option<&str>, mention_text: &str) -> Option<(String, bool)> {
+        let Some(capture) = capture.map(str::trim).filter(|capture| !capture.is_empty()) else {
+            return Self::extract_ticker_or_address(mention_text);
+        };
+        if let Some(candidate) = TokenExtractor::extract_first(capture) {
+            return Some(candidate.into_parts());
+        }
+        Some((capture.trim_start_matches('$').to_string(), false))
+    }
+
+    // Park a reply an escalate rule caught and tell the Telegram admins about it
+    async fn hold_for_approval(&mut self, held: HeldReply) {
+        if !self.take_responded(&held.reply_to, ResponseState::Held) {
+            return;
+        }
+        println!("Holding reply to {} for approval (rule {})", held.reply_to, held.rule);
+        let notice = Approvals::notice(&held);
+        if let Err(e) = MemoryStore::hold_reply(&mut self.memory, held) {
+            eprintln!("Failed to save held reply: {}", e);
+            return;
+        }
+        match self.telegram.as_ref().filter(|telegram| telegram.accepts_commands()) {
+            Some(telegram) => {
+                if let Err(e) = telegram.notify_admins(&notice).await {
+                    eprintln!("Failed to tell Telegram admins about a held reply: {}", e);
+                }
+            }
+            None => println!("No Telegram admins to approve it; held replies expire after a day:\n{}", notice),
+        }
+    }
+
+    // What the images on a mention show, when the character looks at attachments
+    async fn describe_attachment(&self, images: Option<&Vec<String>>) -> Result<Option<String>, anyhow::Error> {
+        let settings = self.settings.attachments.clone();
+        let Some(images) = images.filter(|_| settings.enabled && settings.max_images > 0) else {
+            return Ok(None);
+        };
+        let images = &images[..images.len().min(settings.max_images)];
+        let description = self.agents[0].describe_images(settings.vision_model.as_deref(), images).await?;
+        println!("Mention has {} image(s): {}", images.len(), description);
+        Ok(Some(description))
+    }
+
+    // Drop spam mentions before any model call. Authors are looked up once and
+    // cached; skipped mentions are marked processed so they aren't checked again.
+    // Accounts blocked or muted on Twitter are dropped even with the filter off.
+    async fn filter_spam_mentions(&mut self, mentions: Vec<twitter_v2::Tweet>) -> Vec<twitter_v2::Tweet> {
+        let settings = self.settings.spam_filter.clone();
+
+        if settings.needs_author_lookup() || self.settings.reply_policy.needs_author_lookup() {
+            let unknown = self
+                .spam_filter
+                .unknown_authors(mentions.iter().filter_map(|tweet| tweet.author_id.map(|id| id.as_u64())));
+            if !unknown.is_empty() {
+                match self.twitter.get_users(&unknown).await {
+                    Ok(authors) => self.spam_filter.remember_authors(authors),
+                    Err(e) => eprintln!("Couldn't look up mention authors, checking text only: {}", e),
+                }
+            }
+        }
+
+        let mut kept = Vec::new();
+        for tweet in mentions {
+            let tweet_id = tweet.id.to_string();
+            let author_id = tweet.author_id.map(|id| id.as_u64());
+            match self.spam_filter.check(&settings, &tweet_id, &tweet.text, author_id) {
+                Some(reason) => {
+                    println!("Skipping spam mention {} ({}): {}", tweet_id, reason, tweet.text);
+                    self.mark_responded(&tweet_id, ResponseState::Handled);
+                }
+                None => kept.push(tweet),
+            }
+        }
+        kept
+    }
+
+    // Drop mentions from authors the reply policy rules out, using the authors the
+    // spam filter looked up. Mentions whose author couldn't be checked are kept out
+    // of this sweep but not marked, so they're checked again next time.
+    async fn filter_reply_policy(&mut self, mentions: Vec<twitter_v2::Tweet>) -> Vec<twitter_v2::Tweet> {
+        let settings = self.settings.reply_policy.clone();
+        if !settings.is_enabled() || mentions.is_empty() {
+            return mentions;
+        }
+        let now = self.clock.now();
+        if self.reply_policy.needs_follower_sync(&settings, now)
+            && self.quota_allows("follower sync", QuotaPriority::Sweep, &[ENDPOINT_FOLLOWERS])
+        {
+            if let Err(e) = self.sync_followers().await {
+                eprintln!("Couldn't sync followers for the reply policy: {}", e);
+                self.record_api_error("followers");
+            }
+        }
+
+        let mut kept = Vec::new();
+        for tweet in mentions {
+            let tweet_id = tweet.id.to_string();
+            let author_id = tweet.author_id.map(|id| id.as_u64());
+            let author = author_id.and_then(|id| self.spam_filter.author(id));
+            match self.reply_policy.check(&settings, author_id, author, now) {
+                Some(PolicyReason::Unknown) => {
+                    println!("Holding off on mention {} until its author can be checked", tweet_id);
+                }
+                Some(reason) => {
+                    println!("Not replying to {} (reply policy: {}): {}", tweet_id, reason, tweet.text);
+                    self.mark_responded(&tweet_id, ResponseState::Handled);
+                }
+                None => kept.push(tweet),
+            }
+        }
+        kept
+    }
+
+    async fn sync_followers(&mut self) -> Result<(), FudError> {
+        let user_id = self.ensure_user_id().await?;
+        let (followers, complete) = self.twitter.get_followers(user_id).await?;
+        if complete {
+            println!("Synced {} followers for the reply policy", followers.len());
+        } else {
+            println!("Synced the first {} followers for the reply policy; anyone else is held, not refused", followers.len());
+        }
+        self.reply_policy.set_followers(followers, complete, self.clock.now());
+        Ok(())
+    }
+
+    // Find which mentions were posted in a Community, with its name. With replies
+    // to them turned off those mentions are dropped and marked processed.
+    async fn filter_community_mentions(
+        &mut self,
+        mentions: Vec<twitter_v2::Tweet>,
+    ) -> (Vec<twitter_v2::Tweet>, HashMap<String, Option<String>>) {
+        let settings = self.settings.communities.clone();
+        if !settings.enabled || mentions.is_empty() {
+            return (mentions, HashMap::new());
+        }
+        if !self.quota_allows("community lookup", QuotaPriority::Sweep, &[ENDPOINT_TWEET_LOOKUP]) {
+            return (mentions, HashMap::new());
+        }
+        let ids: Vec<String> = mentions.iter().map(|tweet| tweet.id.to_string()).collect();
+        let community_ids = match self.twitter.get_community_ids(&ids).await {
+            Ok(community_ids) => community_ids,
+            Err(e) => {
+                eprintln!("Couldn't check mentions for Communities: {}", e);
+                self.record_api_error("communities");
+                return (mentions, HashMap::new());
+            }
+        };
+
+        let mut names = HashMap::new();
+        for (tweet_id, community_id) in community_ids {
+            if !self.community_names.contains_key(&community_id)
+                && self.quota_allows("community lookup", QuotaPriority::Sweep, &[ENDPOINT_COMMUNITIES])
+            {
+                match self.twitter.get_community_name(&community_id).await {
+                    Ok(name) => {
+                        self.community_names.insert(community_id.clone(), name);
+                    }
+                    Err(e) => eprintln!("Couldn't look up Community {}: {}", community_id, e),
+                }
+            }
+            names.insert(tweet_id, self.community_names.get(&community_id).cloned());
+        }
+
+        if settings.reply {
+            return (mentions, names);
+        }
+        let mut kept = Vec::new();
+        for tweet in mentions {
+            let tweet_id = tweet.id.to_string();
+            match names.get(&tweet_id) {
+                Some(name) => {
+                    println!("Skipping mention {} from a Community ({}): {}", tweet_id, name.as_deref().unwrap_or("unknown"), tweet.text);
+                    self.mark_responded(&tweet_id, ResponseState::Handled);
+                }
+                None => kept.push(tweet),
+            }
+        }
+        (kept, names)
+    }
+
+    fn community_line(name: Option<&str>) -> String {
+        match name {
+            Some(name) => format!("They posted this in the \"{}\" community", name),
+            None => "They posted this in a community".to_string(),
         }
     }
 
-    async fn wait_until_next_tweet(&self) -> bool {
-        if let Some(next_tweet) = self.memory.next_tweet {
-            let now = Utc::now();
-            if next_tweet > now {
-                let duration = next_tweet.signed_duration_since(now);
-                if duration.num_seconds() > 0 {
-                    sleep(Duration::from_secs(duration.num_seconds() as u64)).await;
+    fn conversation_of(tweet: &twitter_v2::Tweet) -> String {
+        tweet.conversation_id.unwrap_or(tweet.id).to_string()
+    }
+
+    // Drop mentions from authors on cooldown or in threads we've already gone deep in.
+    // Hitting the depth limit puts the author on cooldown too.
+    fn filter_reply_limits(&mut self, mentions: Vec<twitter_v2::Tweet>) -> Vec<twitter_v2::Tweet> {
+        let settings = self.settings.reply_limits.clone();
+        let now = self.clock.now();
+        let mut pending: HashMap<String, u32> = HashMap::new();
+        let mut kept = Vec::new();
+        for tweet in mentions {
+            let tweet_id = tweet.id.to_string();
+            let conversation_id = Self::conversation_of(&tweet);
+            let author_id = tweet.author_id.map(|id| id.to_string());
+            let planned = pending.get(&conversation_id).copied().unwrap_or(0);
+            match ReplyLimits::check(&settings, &self.memory, &conversation_id, author_id.as_deref(), planned, now) {
+                Some(limit) => {
+                    println!("Not replying to {} ({}): {}", tweet_id, limit, tweet.text);
+                    if let (ReplyLimit::ThreadDepth { .. }, Some(author_id)) = (&limit, &author_id) {
+                        if let Some(until) = ReplyLimits::cooldown_end(&settings, now) {
+                            if let Err(e) = MemoryStore::start_user_cooldown(&mut self.memory, author_id, until) {
+                                eprintln!("Failed to save reply cooldown: {}", e);
+                            }
+                        }
+                    }
+                    self.mark_responded(&tweet_id, ResponseState::Handled);
+                }
+                None => {
+                    *pending.entry(conversation_id).or_default() += 1;
+                    kept.push(tweet);
                 }
-                true
-            } else {
-                true // Time has passed, ready to tweet
             }
-        } else {
-            false // No scheduled tweet
         }
+        kept
     }
 
-    pub async fn get_trending_solana_summary(&self) -> Result<String, anyhow::Error> {
-        let tokens = self.solana_tracker.get_top_tokens(5).await?;
-        Ok(self.solana_tracker.format_tokens_summary(&tokens, 5))
+    // Only price is known for tokens the tracker didn't report a market cap for;
+    // ask the chain for the real supply rather than assuming 1B
+    async fn fill_token_supply(&self, token: &mut TokenResponse) {
+        if !token.market_cap().estimated || token.token.mint.is_empty() {
+            return;
+        }
+        match self.solana_rpc.get_token_supply(&token.token.mint).await {
+            Ok(supply) => match supply.ui_amount() {
+                Some(amount) => token.set_supply(amount),
+                None => eprintln!("Unreadable supply '{}' for ${}", supply.amount, token.token.symbol),
+            },
+            Err(e) => eprintln!(
+                "Couldn't fetch supply of ${}, market cap will be estimated: {}",
+                token.token.symbol, e
+            ),
+        }
     }
 
-    pub async fn run_periodically(&mut self) -> Result<(), anyhow::Error> {
-        println!("=== Starting FUD Bot ===");
-        println!("Character type: {}", self.character_config.name);
-        println!("Tweet mode enabled: {}", self.memory.tweet_mode);
-        println!("Debug mode enabled: {}", self.memory.debug_mode);
-        println!("Number of agents: {}", self.agents.len());
-        
-        if let Some(last_time) = self.last_tweet_time {
-            println!("Last tweet time: {:?}", last_time);
-        } else {
-            println!("No previous tweets recorded");
+    // fill_token_supply for every token with an estimated market cap, a few at a time
+    async fn fill_token_supplies(&self, tokens: &mut [TokenResponse]) {
+        for chunk in tokens.chunks_mut(SUPPLY_RPC_CONCURRENCY) {
+            join_all(chunk.iter_mut().map(|token| self.fill_token_supply(token))).await;
         }
-        println!("======================\n");
+    }
 
-        // Run debug test if conditions are met
-        if self.memory.debug_mode && !self.memory.tweet_mode {
-            self.run_debug_test().await?;
-            return Ok(());  // Exit after debug test
+    // Compare SolanaTracker's market cap with GeckoTerminal's before the FUD quotes it
+    async fn cross_check_market_cap(&self, token: &TokenResponse, summary: String) -> String {
+        let settings = &self.settings.price_check;
+        if !settings.enabled || token.token.mint.is_empty() {
+            return summary;
         }
-        
-        // Original periodic run loop
-        loop {
-            let now = Utc::now();
-            
-            if self.character_config.name == "fud" {
-                if self.should_run_scheduled_action(&[0, 15, 30, 45]).await {
-                    println!("Starting FUD generation attempt at {:02}:{:02}...", 
-                        now.hour(), now.minute());
-                    
-                    if !self.should_allow_tweet().await {
-                        println!("Rate limit cooldown in effect, skipping this cycle");
-                    } else {
-                        
-                        match self.generate_and_post_fud().await {
-                            Ok(_) => println!("Successfully completed FUD generation cycle"),
-                            Err(e) => eprintln!("Error generating FUD: {}", e)
-                        }
-                    }
-                }
 
-                if self.should_check_notifications().await {
-                    if let Err(e) = self.handle_notifications_fud().await {
-                        eprintln!("Error handling FUD notifications: {}", e);
-                    }
-                }   
+        let ours = token.snapshot().market_cap;
+        let theirs = match self.gecko_terminal.get_token(&token.token.mint).await {
+            Ok(gecko) => gecko.market_cap(),
+            Err(e) => {
+                eprintln!("Couldn't cross-check market cap of ${}: {}", token.token.symbol, e);
+                None
             }
+        };
+
+        let check = PriceCheck::compare(ours, theirs, settings.max_ratio);
+        if let PriceCheck::Mismatch { ratio } = check {
+            println!(
+                "Market cap of ${} disagrees between sources: {} vs {} ({:.1}x), {:?}",
+                token.token.symbol,
+                SolanaTracker::format_currency(ours),
+                SolanaTracker::format_currency(theirs.unwrap_or_default()),
+                ratio,
+                settings.on_mismatch,
+            );
+        }
+        check.apply(&summary, ours, theirs, settings)
+    }
 
-            let next_second = (now + chrono::Duration::seconds(1))
-                .with_nanosecond(0)
-                .unwrap();
-            let duration_until_next = next_second.signed_duration_since(now);
-            if duration_until_next.num_milliseconds() > 0 {
-                sleep(Duration::from_millis(
-                    duration_until_next.num_milliseconds() as u64
-                )).await;
+    // Append the token's published description, website and socials so FUD can quote them
+    async fn with_token_metadata(&self, token: &TokenResponse, summary: String) -> String {
+        let settings = self.settings.token_metadata.clone();
+        let mint = token.token.mint.clone();
+        if !settings.enabled || mint.is_empty() {
+            return summary;
+        }
+
+        let cached = Self::lock(&self.token_metadata).contains_key(&mint);
+        if !cached {
+            match self.metaplex.fetch(&self.solana_rpc, &mint, settings.fetch_website).await {
+                Ok(metadata) => {
+                    Self::lock(&self.token_metadata).insert(mint.clone(), metadata);
+                }
+                // Not cached, so the next post about this token tries again
+                Err(e) => {
+                    eprintln!("Couldn't fetch metadata for ${}: {}", token.token.symbol, e);
+                    return summary;
+                }
             }
         }
+        match Self::lock(&self.token_metadata).get(&mint) {
+            Some(Some(metadata)) if !metadata.is_empty() => format!("{}{}", summary, metadata.summary()),
+            _ => summary,
+        }
     }
 
-    fn is_solana_address(text: &str) -> bool {
-        if text.len() < 32 || text.len() > 44 {
-            return false;
+    // Append where a pump.fun token is on its bonding curve, who bought at launch and
+    // whether it ever made king of the hill. Other tokens are left alone.
+    async fn with_bonding_curve(&self, token: &TokenResponse, summary: String) -> String {
+        let mint = token.token.mint.clone();
+        if !self.settings.pump_fun.enabled || !PumpFun::is_pump_token(&mint) {
+            return summary;
         }
 
-        // Check if string only contains valid base58 characters
-        let base58_chars = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-        text.chars().all(|c| base58_chars.contains(c))
+        let now = self.clock.now();
+        let fresh = Self::lock(&self.bonding_status)
+            .get(&mint)
+            .is_some_and(|(fetched_at, _)| now.signed_duration_since(*fetched_at).num_minutes() < BONDING_STATUS_TTL_MINUTES);
+        if !fresh {
+            match self.pump_fun.get_bonding_status(&mint).await {
+                Ok(status) => {
+                    Self::lock(&self.bonding_status).insert(mint.clone(), (now, status));
+                }
+                Err(e) => {
+                    eprintln!("Couldn't fetch pump.fun status for ${}: {}", token.token.symbol, e);
+                    return summary;
+                }
+            }
+        }
+        match Self::lock(&self.bonding_status).get(&mint) {
+            Some((_, status)) => format!("{}{}", summary, status.summary(now)),
+            None => summary,
+        }
     }
 
-    fn extract_ticker_or_address(text: &str) -> Option<(String, bool)> {  // Returns (token, is_address)
-        let words: Vec<&str> = text.split_whitespace().collect();
-        
-        // First try to find a $ prefixed ticker or direct address
-        for word in words.iter() {
-            let trimmed = word.trim();
-            
-            // Check for Solana address
-            if Self::is_solana_address(trimmed) {
-                println!("Found Solana address: {}", trimmed);
-                return Some((trimmed.to_string(), true));
+    // Birdeye's holder concentration, creator share and security flags, plus who's been
+    // trading lately. Left out if Birdeye isn't configured or can't be reached.
+    async fn with_security_info(&self, token: &TokenResponse, summary: String) -> String {
+        let Some(birdeye) = self.birdeye.as_ref().filter(|_| self.settings.birdeye.enabled) else {
+            return summary;
+        };
+        let mint = token.token.mint.clone();
+        if mint.is_empty() {
+            return summary;
+        }
+
+        let now = self.clock.now();
+        let cached = Self::lock(&self.security_info)
+            .get(&mint)
+            .filter(|(fetched_at, _)| now.signed_duration_since(*fetched_at).num_minutes() < SECURITY_INFO_TTL_MINUTES)
+            .map(|(_, lines)| lines.clone());
+        if let Some(lines) = cached {
+            return format!("{}{}", summary, lines);
+        }
+
+        let mut lines = match birdeye.get_token_security(&mint).await {
+            Ok(security) => security.summary(),
+            Err(e) => {
+                eprintln!("Couldn't fetch Birdeye security for ${}: {}", token.token.symbol, e);
+                return summary;
             }
-            
-            // Check for $ prefixed ticker
-            if trimmed.starts_with('$') && trimmed.len() > 1 {
-                // Strip non-alphanumeric characters from the end
-                let ticker = trimmed[1..].trim_end_matches(|c: char| !c.is_ascii_alphanumeric());
-                if !ticker.is_empty() {
-                    println!("Found $ prefixed ticker: {}", ticker);
-                    return Some((ticker.to_string(), false));
+        };
+        // Trades only add color; the security flags are worth having without them
+        if self.settings.birdeye.trades > 0 {
+            match birdeye.get_recent_trades(&mint, self.settings.birdeye.trades).await {
+                Ok(trades) => {
+                    if let Some(flow) = TradeFlow::from_trades(&trades) {
+                        lines.push_str(&flow.summary());
+                    }
                 }
+                Err(e) => eprintln!("Couldn't fetch Birdeye trades for ${}: {}", token.token.symbol, e),
             }
         }
-    
-        // If no $ ticker or address found, look for keywords followed by potential tickers
-        let text_lower = text.to_lowercase();
-        let trigger_words = ["thoughts on", "think of", "about", "contract", "address"];
-        
-        for trigger in trigger_words.iter() {
-            if let Some(pos) = text_lower.find(trigger) {
-                let after_trigger = &text[pos + trigger.len()..];
-                let potential_token = after_trigger
-                    .split_whitespace()
-                    .next()
-                    .map(|w| w.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '_'));
-                
-                if let Some(token) = potential_token {
-                    if !token.is_empty() {
-                        if Self::is_solana_address(token) {
-                            println!("Found Solana address after '{}': {}", trigger, token);
-                            return Some((token.to_string(), true));
-                        } else {
-                            println!("Found implied ticker from '{}': {}", trigger, token);
-                            return Some((token.to_string(), false));
-                        }
+        if self.settings.birdeye.markets > 0 {
+            match birdeye.get_markets(&mint, self.settings.birdeye.markets).await {
+                Ok(markets) => {
+                    if let Some(split) = LiquiditySplit::from_markets(&markets) {
+                        lines.push_str(&split.summary());
                     }
                 }
+                Err(e) => eprintln!("Couldn't fetch Birdeye markets for ${}: {}", token.token.symbol, e),
             }
         }
-        
-        None
+        Self::lock(&self.security_info).insert(mint, (now, lines.clone()));
+        format!("{}{}", summary, lines)
     }
-    
 
-    ////////////////////////
-    /// FUD-SPECIFIC ACTIONS
-    ////////////////////////
-    fn format_ticker_for_response(ticker: &str) -> String {
-        ticker.to_uppercase()
+    // How much of the supply was bundled at launch, when it was enough to call out
+    async fn with_bundle_check(&self, token: &TokenResponse, summary: String) -> String {
+        let mint = token.token.mint.clone();
+        if !self.settings.bundle_check.enabled || mint.is_empty() {
+            return summary;
+        }
+        let now = self.clock.now();
+        let ttl = chrono::Duration::hours(BUNDLE_REPORT_TTL_HOURS);
+        let cached = {
+            let mut reports = Self::lock(&self.bundle_reports);
+            reports.retain(|_, (checked_at, _)| now.signed_duration_since(*checked_at) < ttl);
+            reports.get(&mint).map(|(_, report)| report.clone())
+        };
+        let report = match cached {
+            Some(report) => report,
+            None => match self.check_bundles(&mint).await {
+                Ok(report) => {
+                    Self::lock(&self.bundle_reports).insert(mint, (now, report.clone()));
+                    report
+                }
+                Err(e) => {
+                    eprintln!("Couldn't run the bundle check for ${}: {}", token.token.symbol, e);
+                    return summary;
+                }
+            },
+        };
+        match report {
+            Some(report) => format!("{}{}", summary, report.summary()),
+            None => summary,
+        }
     }
 
-    fn get_random_images(count: usize) -> Result<Vec<PathBuf>, Box<dyn Error>> {
-        let source_dir = Path::new("./storage/charts");
-        let mut images: Vec<PathBuf> = Vec::new();
-        
-        // Read all PNG files from the directory
-        for entry in fs::read_dir(source_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if let Some(extension) = path.extension() {
-                if extension == "png" {
-                    images.push(path);
-                }
+    async fn check_bundles(&self, mint: &str) -> Result<Option<BundleReport>, anyhow::Error> {
+        let settings = self.settings.bundle_check.clone();
+
+        // Page back to the token's first transaction
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        let mut reached_launch = false;
+        for _ in 0..settings.max_signature_pages {
+            let page = self.solana_rpc.get_signatures_for_address(mint, before.as_deref(), SIGNATURE_PAGE_SIZE).await?;
+            reached_launch = page.len() < SIGNATURE_PAGE_SIZE;
+            before = page.last().map(|info| info.signature.clone());
+            signatures.extend(page);
+            if reached_launch {
+                break;
             }
         }
-    
-        if images.is_empty() {
-            return Err("No PNG images found in ./storage/charts directory".into());
+        if !reached_launch {
+            println!("Bundle check: more than {} transactions since launch, skipping", signatures.len());
+            return Ok(None);
         }
-    
-        // Shuffle and take requested number of images
-        let mut rng = thread_rng();
-        images.shuffle(&mut rng);
-        
-        // Take minimum of requested count and available images
-        let actual_count = count.min(images.len());
-        Ok(images.into_iter().take(actual_count).collect())
-    }
-    
 
-    async fn generate_and_post_fud(&mut self) -> Result<(), anyhow::Error> {
-        let now = Utc::now();
-    
-        if !self.should_allow_tweet().await {
-            println!("Skipping scheduled post - rate limit cooldown");
-            return Ok(());
+        let window = BundleCheck::launch_window(&signatures, settings.launch_slots, settings.max_transactions);
+        let mut transactions = Vec::new();
+        for chunk in window.chunks(BUNDLE_RPC_CONCURRENCY) {
+            let changes = join_all(chunk.iter().map(|info| self.solana_rpc.get_token_balance_changes(&info.signature, mint))).await;
+            for (info, changes) in chunk.iter().zip(changes) {
+                transactions.push((info.signature.clone(), changes?));
+            }
         }
-    
-        let tokens = self.solana_tracker.get_top_tokens(30).await?;
-        let mut rng = rand::thread_rng();
-        
-        if let Some(random_token) = tokens.get(rng.gen_range(0..tokens.len())) {
-            let token_summary = self.solana_tracker.format_token_summary(random_token);
-            let agent = &mut self.agents[0];
-            
-            let mut attempts = 0;
-            const MAX_ATTEMPTS: usize = 3;
-                
-            loop {
-                let fud = agent.generate_editorialized_fud(&token_summary).await?;
-                
-                let contains_recent = {
-                    let words: Vec<&str> = fud.split_whitespace().collect();
-                    let mut found = false;
-                    for window in words.windows(3) {
-                        let phrase = window.join(" ").to_lowercase();
-                        if self.recent_phrases.contains(&phrase) {
-                            found = true;
-                            break;
-                        }
-                    }
-                    found
-                };
-    
-                if !contains_recent || attempts >= MAX_ATTEMPTS {
-                    if self.memory.tweet_mode {
-                        // Get user ID once before the branching logic
-                        let user_id = self.ensure_user_id().await?;
-                        
-                        // 30% chance to post with image
-                        if rng.gen_bool(0.3) {
-                            match Self::get_random_images(1) {
-                                Ok(images) if !images.is_empty() => {
-                                    // Read the image file
-                                    if let Ok(image_data) = fs::read(&images[0]) {
-                                        // Upload the image and get media_id
-                                        match self.twitter.upload_bytes(image_data).await {
-                                            Ok(media_id) => {
-                                                match self.twitter.tweet_with_image(fud.clone(), media_id, user_id).await {
-                                                    Ok(_) => {
-                                                        println!("Posted scheduled FUD with image at {:02}:{:02}", now.hour(), now.minute());
-                                                        self.last_tweet_time = Some(now);
-                                                    }
-                                                    Err(e) => eprintln!("Failed to post FUD tweet with image: {}", e),
-                                                }
-                                            }
-                                            Err(e) => eprintln!("Failed to upload image: {}", e),
-                                        }
-                                    }
-                                }
-                                _ => eprintln!("Failed to get random image"),
-                            }
-                        } else {
-                            // Regular tweet without image
-                            match self.twitter.tweet(fud.clone()).await {
-                                Ok(_) => {
-                                    println!("Posted scheduled FUD at {:02}:{:02}", now.hour(), now.minute());
-                                    self.last_tweet_time = Some(now);
-                                }
-                                Err(e) => eprintln!("Failed to post FUD tweet: {}", e),
-                            }
-                        }
-                        
-                        // Update recent phrases
-                        let words: Vec<&str> = fud.split_whitespace().collect();
-                        for window in words.windows(3) {
-                            let phrase = window.join(" ").to_lowercase();
-                            self.recent_phrases.insert(phrase);
-                        }
-    
-                        if self.recent_phrases.len() > self.max_recent_phrases {
-                            let oldest: Vec<String> = self.recent_phrases
-                                .iter()
-                                .take(self.recent_phrases.len() - self.max_recent_phrases)
-                                .cloned()
-                                .collect();
-                            for phrase in oldest {
-                                self.recent_phrases.remove(&phrase);
-                            }
-                        }
+        let buys = BundleCheck::launch_buys(&transactions);
+        if buys.is_empty() {
+            return Ok(None);
+        }
+        let supply = self.solana_rpc.get_token_supply(mint).await?.ui_amount().unwrap_or(0.0);
+
+        // A wallet is fresh if it had next to nothing on it before its launch buy
+        let mut first_buys: Vec<&LaunchBuy> = Vec::new();
+        for buy in &buys {
+            if !first_buys.iter().any(|first| first.wallet == buy.wallet) {
+                first_buys.push(buy);
+            }
+        }
+        let mut fresh = HashSet::new();
+        for chunk in first_buys.chunks(BUNDLE_RPC_CONCURRENCY) {
+            let histories = join_all(chunk.iter().map(|buy| {
+                self.solana_rpc.get_signatures_for_address(&buy.wallet, Some(&buy.signature), settings.fresh_wallet_max_txs + 1)
+            }))
+            .await;
+            for (buy, history) in chunk.iter().zip(histories) {
+                match history {
+                    Ok(history) if history.len() <= settings.fresh_wallet_max_txs => {
+                        fresh.insert(buy.wallet.clone());
                     }
-                    break;
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Couldn't read the history of launch buyer {}: {}", buy.wallet, e),
                 }
-                
-                attempts += 1;
             }
         }
-        
-        Ok(())
+        Ok(BundleCheck::report(&settings, &buys, &fresh, supply))
     }
 
-    pub async fn handle_notifications_fud(&mut self) -> Result<(), anyhow::Error> {
-        if self.agents.is_empty() {
-            return Err(anyhow::anyhow!("No agents available"));
+    // Whether FUD about this token should use the pre-bond prompt; only known after
+    // its summary went through with_bonding_curve
+    pub(crate) fn use_prebond_prompt(&self, mint: &str) -> bool {
+        self.settings.pump_fun.prebond_prompt
+            && Self::lock(&self.bonding_status)
+                .get(mint)
+                .is_some_and(|(_, status)| status.is_pre_bond())
+    }
+
+    // Token summary plus what we've already said about it, so the bot doesn't contradict itself
+    fn summary_with_past_takes(&self, token: &TokenResponse) -> String {
+        let summary = self.solana_tracker.format_token_summary(token);
+        let settings = &self.settings.past_takes;
+        if !settings.enabled || settings.max_takes == 0 || token.token.symbol.is_empty() {
+            return summary;
         }
-    
-        if !self.should_check_notifications().await {
-            return Ok(());
+
+        let now = self.clock.now();
+        let since = now - chrono::Duration::days(settings.lookback_days);
+        let takes = MemoryStore::recent_takes_about(&self.memory, &token.token.symbol, since, settings.max_takes);
+        if takes.is_empty() {
+            return summary;
         }
-    
-        println!("Checking notifications...");
-        let user_id = self.ensure_user_id().await?;
-    
-        match self.twitter.get_notifications(user_id).await {
-            Ok(notifications) => {
-                println!("Found {} total notifications", notifications.len());
-                self.last_notification_check = Some(Utc::now());
-                
-                let unresponded_notifications: Vec<_> = notifications
-                    .into_iter()
-                    .filter(|tweet| {
-                        !self.memory.tweets.iter().any(|t| 
-                            t.reply_to.as_ref().map_or(false, |reply_id| reply_id == &tweet.id.to_string())
-                        )
-                    })
-                    .collect();
-                
-                println!("Processing {} unresponded notifications", unresponded_notifications.len());
-                
-                let mut rng = rand::thread_rng();
-                let notifications_to_process: Vec<_> = if unresponded_notifications.len() > 2 {
-                    use rand::seq::SliceRandom;
-                    let mut selected = unresponded_notifications.clone();
-                    selected.shuffle(&mut rng);
-                    selected.truncate(3);
-                    selected
-                } else {
-                    unresponded_notifications
-                };
-    
-                println!("Processing {} notifications", notifications_to_process.len());
-                
-                for tweet in notifications_to_process {
-                    println!("Processing tweet: {}", tweet.text);
-                    let tweet_id = tweet.id.to_string();
-                    
-                    // Generate the response before getting the mutable reference to the agent
-                    let fud_response = if let Some(request) = Self::is_token_info_request(&tweet.text) {
-                        println!("Detected token info request: {:?}", request);
-                        // Move token info handling logic here to avoid borrow conflicts
-                        match request {
-                            TokenInfoRequest::ContractAddress => {
-                                if self.memory.token_address.is_empty() {
-                                    "ser i would tell you but the devs haven't given me that info yet ngmi".to_string()
-                                } else {
-                                    format!("contract: {} \n\nape responsibly ser", self.memory.token_address)
-                                }
-                            },
-                            TokenInfoRequest::Ticker => {
-                                if self.memory.token_symbol.is_empty() {
-                                    "imagine asking for a ticker when the devs haven't even told me what it is yet".to_string()
-                                } else {
-                                    format!("${} \n\ndon't say i didn't warn you", self.memory.token_symbol)
-                                }
-                            }
-                        }
-                    } else if let Some((token, is_address)) = Self::extract_ticker_or_address(&tweet.text) {
-                        println!("Found token/address in tweet: {} (is_address: {})", token, is_address);
-                        
-                        let token_info = if is_address {
-                            self.solana_tracker.get_token_by_address(&token).await.ok()
-                        } else {
-                            let mut search_params = self.solana_tracker.create_search_params(token.clone());
-                            search_params.sort_by = Some("marketCapUsd".to_string());
-                            search_params.sort_order = Some("desc".to_string());
-                            search_params.limit = Some(1);
-                            search_params.freeze_authority = Some("null".to_string());
-                            search_params.mint_authority = Some("null".to_string());
-    
-                            match self.solana_tracker.token_search(search_params).await {
-                                Ok(results) => results.into_iter().next(),
-                                Err(e) => {
-                                    println!("Error searching for token {}: {}", token, e);
-                                    None
-                                }
-                            }                      
-                        };
-    
-                        // Get agent after token info lookup
-                        let selected_agent = &mut self.agents[0];
-                        
-                        if let Some(token) = token_info {
-                            println!(
-                                "Found token {} with liquidity ${:.2}", 
-                                token.token.symbol,
-                                token.pools.first().map(|p| p.liquidity.usd).unwrap_or(0.0)
-                            );
-                            let token_summary = self.solana_tracker.format_token_summary(&token);
-                            selected_agent.generate_editorialized_fud(&token_summary).await?
-                        } else {
-                            println!("No token found for {}, using generic FUD", token);
-                            self.solana_tracker.generate_generic_fud_with_agent(selected_agent).await?
-                        }
-                    } else {
-                        let selected_agent = &mut self.agents[0];
-                        println!("No ticker/address found, generating generic insult response");
-                        let prompt = r#"Task: Generate a vicious sarcastic insult response.
-                        Requirements:
-                        - Stay under 240 characters
-                        - Be extremely condescending and mocking
-                        - Question the person's intelligence and trading abilities
-                        - Use all lowercase except for token symbols
-                        - Focus on their lack of understanding or research
-                        - Do not include tickers or symbols ($) in your response
-                        - Do not mention specific tokens
-                        Write ONLY the response text with no additional commentary:"#;
-                        
-                        selected_agent.generate_custom_response(prompt).await?
-                    };
-    
-                    let agent_prompt = self.agents[0].prompt.clone();
-                    
-                    if let Err(e) = MemoryStore::add_reply_to_memory(
-                        &mut self.memory,
-                        &fud_response,
-                        &agent_prompt,
-                        Some(tweet_id.clone()),
-                        tweet.id.to_string(),
-                    ) {
-                        eprintln!("Failed to save response to memory: {}", e);
-                    }
-    
-                    if self.memory.tweet_mode {
-                        println!("Tweet mode is enabled, posting reply...");
-                        match self.twitter.reply_to_tweet(&tweet_id, fud_response.to_string()).await {
-                            Ok(_) => {
-                                println!("Successfully replied to tweet {}", tweet_id);
-                                sleep(Duration::from_secs(30)).await;
-                            }
-                            Err(e) => {
-                                println!("Failed to reply to tweet: {}", e);
-                                if e.to_string().contains("429") {
-                                    println!("Rate limit hit, stopping notification processing");
-                                    break;
-                                }
-                            }
-                        }
-                    } else {
-                        println!("Tweet mode is disabled, skipping reply");
-                    }
-                }
-                
-                Ok(())
+
+        let mut with_takes = format!("{}\nYour past takes on ${}:\n", summary, token.token.symbol);
+        for take in takes {
+            let age = now.signed_duration_since(take.timestamp);
+            let when = if age.num_days() > 0 {
+                format!("{} days ago", age.num_days())
+            } else {
+                format!("{} hours ago", age.num_hours().max(1))
+            };
+            with_takes.push_str(&format!("- ({}) {}\n", when, take.text));
+        }
+        with_takes.push_str("Stay consistent with these and reference them if it fits (e.g. 'said this was going to zero last week').\n");
+        with_takes
+    }
+
+    // Comparative FUD when a mention names several tokens; None if fewer than two resolve
+    // Tokens come from the mention itself; the reply sees it with any quoted or parent post
+    async fn generate_comparison_reply(&self, text: &str, mention_text: &str) -> Result<Option<String>, FudError> {
+        const MAX_COMPARED_TOKENS: usize = 3;
+
+        let candidates = TokenExtractor::extract_all(text);
+        if candidates.len() < 2 {
+            return Ok(None);
+        }
+
+        let mut tokens: Vec<TokenResponse> = Vec::new();
+        for candidate in candidates.into_iter().take(MAX_COMPARED_TOKENS) {
+            let (token, is_address) = candidate.into_parts();
+            // Never drag our own token into the FUD
+            if self.is_own_token(&token) {
+                continue;
             }
-            Err(e) => {
-                if e.to_string().contains("429") {
-                    println!("Rate limit hit for notifications, will retry in 15 minutes");
-                    self.last_notification_check = Some(Utc::now());
-                    Ok(())
-                } else {
-                    println!("Error getting notifications: {}", e);
-                    Err(e)
-                }
+            let Some(found) = self.lookup_token(&token, is_address).await else {
+                println!("No data for {}, leaving it out of the comparison", token);
+                continue;
+            };
+            if self.is_own_token(&found.token.mint) || tokens.iter().any(|t| t.token.mint == found.token.mint) {
+                continue;
             }
+            tokens.push(found);
+        }
+
+        if tokens.len() < 2 {
+            return Ok(None);
+        }
+
+        let symbols: Vec<&str> = tokens.iter().map(|t| t.token.symbol.as_str()).collect();
+        println!("Comparing {} tokens: {}", tokens.len(), symbols.join(", "));
+        let summaries: Vec<String> = tokens
+            .iter()
+            .map(|token| self.summary_with_past_takes(token))
+            .collect();
+        let reply = self.agents[0].generate_comparison_fud(&summaries, mention_text).await?;
+        Ok(Some(reply))
+    }
+
+    // Handles on the providers for finding a token, cheap to clone into a task
+    fn token_lookup(&self) -> TokenLookup {
+        TokenLookup {
+            solana_tracker: self.solana_tracker.clone(),
+            solana_rpc: self.solana_rpc.clone(),
+            gecko_terminal: self.gecko_terminal.clone(),
+            verify_on_chain: self.settings.address_check.verify_on_chain,
+        }
+    }
+
+    async fn lookup_token(&self, token: &str, is_address: bool) -> Option<TokenResponse> {
+        self.token_lookup().lookup(token, is_address).await
+    }
+
+    // Find the token a thread is about by locating our own tweet it replies to in memory
+    // Our tweet the mention replies to, or else the one that started its thread
+    fn thread_tweet(&self, tweet: &twitter_v2::Tweet) -> Option<&Tweet> {
+        let mut thread_ids: Vec<String> = tweet.referenced_tweets
+            .iter()
+            .flatten()
+            .filter(|r| r.kind == ReferencedTweetKind::RepliedTo)
+            .map(|r| r.id.to_string())
+            .collect();
+        if let Some(conversation_id) = &tweet.conversation_id {
+            thread_ids.push(conversation_id.to_string());
+        }
+
+        thread_ids.iter().find_map(|id| {
+            self.memory.tweets
+                .iter()
+                .rev()
+                .find(|t| t.twitter_id.as_deref() == Some(id.as_str()))
+        })
+    }
+
+    async fn resolve_thread_token(&self, tweet: &twitter_v2::Tweet) -> Option<TokenResponse> {
+        let our_tweet = self.thread_tweet(tweet)?;
+
+        // The mint saved with the tweet, or for older tweets whatever token the text names
+        let (token, is_address) = match &our_tweet.token_mint {
+            Some(mint) => (mint.clone(), true),
+            None => Self::extract_ticker_or_address(&our_tweet.text)?,
+        };
+        println!("Thread {} is about {}", our_tweet.twitter_id.as_deref().unwrap_or_default(), token);
+        let token = self.lookup_token(&token, is_address).await?;
+        if token.token.mint.is_empty() {
+            return None;
+        }
+        Some(token)
+    }
+
+    // A question like "source?" or "mcap now?" under one of our token tweets, answered
+    // with that token's current numbers and how they moved since we posted
+    async fn answer_follow_up(&self, tweet: &twitter_v2::Tweet, question: FollowUpQuestion) -> Option<(String, TokenResponse)> {
+        let token = self.resolve_thread_token(tweet).await?;
+        let thread_id = self.thread_tweet(tweet).and_then(|t| t.twitter_id.clone());
+        let then = self.memory.fud_history.iter().rev().find(|fudded| {
+            fudded.mint == token.token.mint && (thread_id.is_none() || fudded.tweet_id == thread_id)
+        });
+        println!("Answering {:?} follow-up about ${}", question, token.token.symbol);
+        Some((FollowUp::answer(question, &token.snapshot(), then), token))
+    }
+
+    fn format_thread_token_answer(request: TokenInfoRequest, token: &TokenResponse) -> String {
+        let risk = SolanaTracker::format_risk_summary(token);
+        match request {
+            TokenInfoRequest::ContractAddress => format!(
+                "${} ca: {}\n\n{}\n\ndon't say i didn't warn you",
+                token.token.symbol, token.token.mint, risk
+            ),
+            TokenInfoRequest::Ticker => format!(
+                "${}\n\n{}\n\nape responsibly ser",
+                token.token.symbol, risk
+            ),
         }
     }
 
@@ -958,6 +4564,29 @@ impl Runtime {
     }
 }
 
+// A mention's reply, worked out before any of the batch is queued
+struct MentionDraft {
+    tweet: twitter_v2::Tweet,
+    reply: Result<DraftedReply, FudError>,
+    // An attached image couldn't be described, so the reply went without it
+    vision_failed: bool,
+    // Claims the mention made about the token the reply is about
+    facts: Vec<TokenFact>,
+}
+
+enum DraftedReply {
+    // An ignore rule matched
+    Ignored,
+    // Nothing worth posting, e.g. no shill came out
+    Skipped,
+    Reply {
+        text: String,
+        // (mint, symbol) of the token the reply is about
+        token: Option<(String, String)>,
+        rule: Option<RuleMatch>,
+    },
+}
+
 #[derive(Debug)]
 enum TokenInfoRequest {
     ContractAddress,
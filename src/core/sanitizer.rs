@@ -0,0 +1,114 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+// Passes over the text; each one can uncover another wrapper ("Here's your tweet:" then quotes)
+const MAX_PASSES: usize = 4;
+
+// Quote pairs a whole response sometimes comes wrapped in
+const QUOTES: &[(char, char)] = &[('"', '"'), ('\u{201C}', '\u{201D}'), ('\'', '\''), ('`', '`')];
+
+// Strips the model's commentary around a tweet: a preamble like "Here's your tweet:",
+// a markdown fence, quotes around the whole thing and notes tacked on after it.
+// Deterministic, so the same response always comes out the same.
+pub struct Sanitizer;
+
+impl Sanitizer {
+    fn preamble() -> &'static Regex {
+        static PREAMBLE: OnceLock<Regex> = OnceLock::new();
+        PREAMBLE.get_or_init(|| {
+            Regex::new(
+                r"(?ix)^\s*
+                (?:(?:sure|okay|ok|certainly|absolutely|of\ course|alright|got\ it)[!,.]*\s*)?
+                (?:
+                    here(?:'s|\u{2019}s|\ is|\ are)\b[^\n:]{0,60}?\b
+                        (?:tweet|reply|response|post|fud|take|caption|version|roundup|summary|thread|draft|one)s?\b[^\n:]{0,30}:
+                    |(?:tweet|reply|response|post|caption|output)(?:\ text)?\s*:
+                )\s*",
+            )
+            .unwrap()
+        })
+    }
+
+    // Start of a line that talks about the tweet instead of being part of it. A bare
+    // "note:" can be FUD ("note: dev sold 40%"), so it only counts in brackets or when
+    // it's about the writing.
+    fn commentary() -> &'static Regex {
+        static COMMENTARY: OnceLock<Regex> = OnceLock::new();
+        COMMENTARY.get_or_init(|| {
+            Regex::new(
+                r"(?ix)^\s*
+                (?:
+                    [(*\[]+\s*(?:note|explanation)\b
+                    |(?:note|explanation)\s*:.*\b(?:tweet|reply|response|characters?|lowercase|requirements?|tone|instructions?)\b
+                    |(?:character|word)\ count\b
+                    |this\ (?:tweet|reply|response|post|version)\ (?:is|uses|keeps|stays|highlights|captures|combines|focuses|maintains|plays|mocks)\b
+                    |i(?:'ve|\u{2019}ve|\ have)\ (?:kept|used|made|written|included|tried|avoided|focused)\b
+                    |let\ me\ know\b
+                )",
+            )
+            .unwrap()
+        })
+    }
+
+    pub fn clean(text: &str) -> String {
+        let mut current = text.trim().to_string();
+        for _ in 0..MAX_PASSES {
+            let next = Self::unquote(&Self::strip_commentary(&Self::strip_preamble(&Self::strip_fence(&current))));
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        // Nothing left means it was all wrapper, or a misfire; keep what came back
+        if current.is_empty() {
+            return text.trim().to_string();
+        }
+        current
+    }
+
+    // ```text ... ``` around the whole response
+    pub fn strip_fence(text: &str) -> String {
+        let text = text.trim();
+        let Some(rest) = text.strip_prefix("```") else {
+            return text.to_string();
+        };
+        let Some(inner) = rest.trim_end().strip_suffix("```") else {
+            return text.to_string();
+        };
+        // The rest of the opening line is a language tag
+        match inner.split_once('\n') {
+            Some((tag, body)) if !tag.trim().contains(' ') => body.trim().to_string(),
+            _ => inner.trim().to_string(),
+        }
+    }
+
+    pub fn strip_preamble(text: &str) -> String {
+        Self::preamble().replace(text.trim(), "").trim().to_string()
+    }
+
+    // Drops everything from the first line of commentary on, as long as something
+    // comes before it
+    pub fn strip_commentary(text: &str) -> String {
+        let lines: Vec<&str> = text.trim().lines().collect();
+        let cut = lines.iter().skip(1).position(|line| Self::commentary().is_match(line)).map(|i| i + 1);
+        match cut {
+            Some(i) => lines[..i].join("\n").trim().to_string(),
+            None => text.trim().to_string(),
+        }
+    }
+
+    // Quotes around the whole text, left alone when the same quote shows up inside
+    // (two quoted phrases, or an apostrophe)
+    pub fn unquote(text: &str) -> String {
+        let text = text.trim();
+        for (open, close) in QUOTES {
+            let Some(inner) = text.strip_prefix(*open).and_then(|rest| rest.strip_suffix(*close)) else {
+                continue;
+            };
+            if !inner.is_empty() && !inner.contains(*open) && !inner.contains(*close) {
+                return inner.trim().to_string();
+            }
+        }
+        text.to_string()
+    }
+}
@@ -0,0 +1,318 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use super::agent::Agent;
+use super::character::GenerationSettings;
+use super::character_switch::CharacterSwitch;
+use super::instruction_builder::InstructionBuilder;
+use super::paths::Layout;
+use super::tweet_preview::{TweetPreview, PREVIEW_WIDTH};
+use crate::providers::{solanatracker::SolanaTracker, telegram::Telegram, twitter::Twitter};
+
+// Read by dotenv from the working directory on startup
+pub const ENV_PATH: &str = ".env";
+// Copied for a character that doesn't exist yet
+pub const TEMPLATE_CHARACTER: &str = "fud";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Provider {
+    Anthropic,
+    Twitter,
+    SolanaTracker,
+    Telegram,
+}
+
+impl Provider {
+    const ALL: [Provider; 4] = [Provider::Anthropic, Provider::Twitter, Provider::SolanaTracker, Provider::Telegram];
+
+    fn name(self) -> &'static str {
+        match self {
+            Provider::Anthropic => "Anthropic",
+            Provider::Twitter => "Twitter",
+            Provider::SolanaTracker => "SolanaTracker",
+            Provider::Telegram => "Telegram",
+        }
+    }
+
+    fn vars(self) -> &'static [&'static str] {
+        match self {
+            Provider::Anthropic => &["ANTHROPIC_API_KEY"],
+            Provider::Twitter => &[
+                "TWITTER_CONSUMER_KEY",
+                "TWITTER_CONSUMER_SECRET",
+                "TWITTER_ACCESS_TOKEN",
+                "TWITTER_ACCESS_TOKEN_SECRET",
+            ],
+            Provider::SolanaTracker => &["SOLANA_TRACKER_API_KEY"],
+            Provider::Telegram => &["TELEGRAM_BOT_TOKEN"],
+        }
+    }
+
+    // The bot runs without Telegram; everything else it needs
+    fn optional(self) -> bool {
+        self == Provider::Telegram
+    }
+
+    // The same checks the bot makes on startup; what the provider says about the keys
+    async fn verify(self, values: &[String]) -> anyhow::Result<String> {
+        match self {
+            Provider::Anthropic => {
+                Agent::new(&values[0], "", GenerationSettings::default()).verify_api_key().await?;
+                Ok("key accepted".to_string())
+            }
+            Provider::Twitter => {
+                let twitter = Twitter::new(&values[0], &values[1], &values[2], &values[3]);
+                let (_, username) = twitter.verify_credentials().await?;
+                Ok(format!("signed in as @{}", username))
+            }
+            Provider::SolanaTracker => {
+                SolanaTracker::new(&values[0]).check_api_key().await?;
+                Ok("key accepted".to_string())
+            }
+            Provider::Telegram => {
+                let username = Telegram::new(&values[0]).verify().await?;
+                Ok(format!("bot @{}", username))
+            }
+        }
+    }
+}
+
+// Shows only the end of a key that's already set
+fn mask(value: &str) -> String {
+    let tail: String = value.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+    format!("...{}", tail)
+}
+
+// A value as dotenv reads it back: quoted when it has spaces, quotes or a '#'
+fn env_value(value: &str) -> String {
+    if !value.is_empty() && !value.chars().any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '\\')) {
+        return value.to_string();
+    }
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// `existing` with each of `values` set: a line that already sets the variable is
+// replaced, the rest are appended. Comments and other settings are kept as they are.
+pub fn merge_env(existing: &str, values: &[(String, String)]) -> String {
+    let mut written = vec![false; values.len()];
+    let mut lines = Vec::new();
+    for line in existing.lines() {
+        let name = line.trim_start().trim_start_matches("export ").split('=').next().unwrap_or("").trim();
+        match values.iter().position(|(var, _)| var == name && line.contains('=')) {
+            Some(i) if !written[i] => {
+                lines.push(format!("{}={}", values[i].0, env_value(&values[i].1)));
+                written[i] = true;
+            }
+            // A second line for the same variable would win over ours
+            Some(_) => {}
+            None => lines.push(line.to_string()),
+        }
+    }
+    for (i, (var, value)) in values.iter().enumerate() {
+        if !written[i] {
+            lines.push(format!("{}={}", var, env_value(value)));
+        }
+    }
+    let mut env = lines.join("\n");
+    env.push('\n');
+    env
+}
+
+// The template character renamed to `name`
+pub fn starter_character(template: &str, name: &str) -> Result<String, serde_json::Error> {
+    let mut character: serde_json::Value = serde_json::from_str(template)?;
+    character["character"] = serde_json::Value::from(name);
+    character["alias"] = serde_json::Value::from(name);
+    if character["instructions"]["base"].is_string() {
+        character["instructions"]["base"] = serde_json::Value::from(format!("You are a character named {}.", name));
+    }
+    serde_json::to_string_pretty(&character)
+}
+
+// `setup`: asks for the API keys and checks each with its provider, writes .env and a
+// starter character, then writes a sample post without posting it
+pub struct Setup<R, W> {
+    input: R,
+    output: W,
+    layout: Layout,
+}
+
+impl<R: BufRead, W: Write> Setup<R, W> {
+    pub fn new(input: R, output: W, layout: Layout) -> Self {
+        Setup { input, output, layout }
+    }
+
+    // The answer, or `default` for an empty one. Asks again while there's neither,
+    // unless `optional`. None once the input is closed.
+    pub fn ask(&mut self, question: &str, default: Option<&str>, shown: Option<String>, optional: bool) -> io::Result<Option<String>> {
+        loop {
+            match (&shown, default) {
+                (Some(shown), _) => write!(self.output, "{} [{}]: ", question, shown)?,
+                (None, Some(default)) => write!(self.output, "{} [{}]: ", question, default)?,
+                (None, None) if optional => write!(self.output, "{} (optional, Enter to skip): ", question)?,
+                (None, None) => write!(self.output, "{}: ", question)?,
+            }
+            self.output.flush()?;
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let answer = line.trim();
+            if !answer.is_empty() {
+                return Ok(Some(answer.to_string()));
+            }
+            if let Some(default) = default {
+                return Ok(Some(default.to_string()));
+            }
+            if optional {
+                return Ok(Some(String::new()));
+            }
+        }
+    }
+
+    pub fn confirm(&mut self, question: &str, default: bool) -> io::Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        Ok(match self.ask(question, None, Some(hint.to_string()), true)?.as_deref() {
+            Some("") | None => default,
+            Some(answer) => answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"),
+        })
+    }
+
+    fn closed() -> anyhow::Error {
+        anyhow::anyhow!("setup cancelled: input closed")
+    }
+
+    // One provider's keys, asked for until they're accepted or kept anyway. Empty
+    // for an optional provider that was skipped; true if the keys were accepted.
+    async fn collect(&mut self, provider: Provider) -> anyhow::Result<(Vec<String>, bool)> {
+        writeln!(self.output, "\n{}", provider.name())?;
+        loop {
+            let mut values = Vec::new();
+            for var in provider.vars() {
+                let current = env::var(var).ok().filter(|value| !value.trim().is_empty());
+                let shown = current.as_deref().map(|value| format!("keep {}", mask(value)));
+                let answer = self.ask(var, current.as_deref(), shown, provider.optional())?.ok_or_else(Self::closed)?;
+                values.push(answer);
+            }
+            if values.iter().all(String::is_empty) {
+                writeln!(self.output, "  skipped")?;
+                return Ok((Vec::new(), false));
+            }
+            match provider.verify(&values).await {
+                Ok(detail) => {
+                    writeln!(self.output, "  ok: {}", detail)?;
+                    return Ok((values, true));
+                }
+                Err(e) => {
+                    writeln!(self.output, "  {} rejected them: {}", provider.name(), e)?;
+                    if !self.confirm("  Enter them again?", true)? {
+                        return Ok((values, false));
+                    }
+                }
+            }
+        }
+    }
+
+    fn ask_character(&mut self) -> anyhow::Result<String> {
+        let current = env::var("CHARACTER_NAME").ok().map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+        loop {
+            let name = self.ask("\nCHARACTER_NAME", current.as_deref(), None, false)?.ok_or_else(Self::closed)?;
+            if CharacterSwitch::is_valid_name(&name) {
+                return Ok(name);
+            }
+            writeln!(self.output, "  use letters, digits, '-' and '_' only")?;
+        }
+    }
+
+    // characters/<name>/character.json, copied from the template unless it's there already
+    fn write_character(&mut self, name: &str) -> anyhow::Result<()> {
+        let path = self.layout.character_file(name);
+        if path.exists() {
+            writeln!(self.output, "  using the existing {}", path.display())?;
+            return Ok(());
+        }
+        let template_path = self.layout.character_file(TEMPLATE_CHARACTER);
+        let template = fs::read_to_string(&template_path)
+            .map_err(|e| anyhow::anyhow!("couldn't read the template {}: {}", template_path.display(), e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, starter_character(&template, name)?)?;
+        writeln!(self.output, "  wrote {}, a copy of {} to make your own", path.display(), TEMPLATE_CHARACTER)?;
+        Ok(())
+    }
+
+    // A post written by the new character, shown and not posted. A FUD post about the
+    // top trending token when SolanaTracker can be reached, otherwise a plain one.
+    async fn sample(&mut self, name: &str, anthropic_key: &str, solana_tracker_key: Option<&str>) -> anyhow::Result<()> {
+        let mut instruction_builder = InstructionBuilder::new();
+        instruction_builder.build_instructions_from_file(&self.layout.character_file(name))?;
+        let agent = Agent::new(
+            anthropic_key,
+            instruction_builder.get_instructions(),
+            instruction_builder.get_generation_settings().clone(),
+        )
+        .with_examples(instruction_builder.get_examples().clone());
+        let mut token = None;
+        if let Some(key) = solana_tracker_key {
+            let solana_tracker = SolanaTracker::new(key);
+            if let Some(top) = solana_tracker.get_top_tokens(1).await.ok().and_then(|tokens| tokens.into_iter().next()) {
+                token = Some((top.token.symbol.clone(), solana_tracker.format_token_summary(&top)));
+            }
+        }
+        let text = match token {
+            Some((symbol, summary)) => {
+                writeln!(self.output, "\nSample FUD on ${}:", symbol)?;
+                agent.generate_editorialized_fud(&summary).await?
+            }
+            None => {
+                writeln!(self.output, "\nSample post:")?;
+                agent.generate_post().await?
+            }
+        };
+        writeln!(self.output, "{}", TweetPreview::new(&text).render(PREVIEW_WIDTH))?;
+        writeln!(self.output, "(not posted)")?;
+        Ok(())
+    }
+
+    pub async fn run(&mut self) -> anyhow::Result<()> {
+        writeln!(self.output, "ChainFud setup: press Enter to keep a value that's already set.")?;
+        let mut values: Vec<(String, String)> = Vec::new();
+        let mut verified = Vec::new();
+        for provider in Provider::ALL {
+            let (answers, ok) = self.collect(provider).await?;
+            if ok {
+                verified.push(provider);
+            }
+            values.extend(provider.vars().iter().map(|var| var.to_string()).zip(answers));
+        }
+
+        let name = self.ask_character()?;
+        self.write_character(&name)?;
+        values.push(("CHARACTER_NAME".to_string(), name.clone()));
+
+        let existing = fs::read_to_string(ENV_PATH).unwrap_or_default();
+        fs::write(ENV_PATH, merge_env(&existing, &values))?;
+        writeln!(self.output, "\nWrote {}", ENV_PATH)?;
+
+        let report = self.layout.doctor(Some(&name));
+        report.print("setup");
+        if !report.is_ok() {
+            return Err(anyhow::anyhow!("fix the errors above, then run `cargo run -- doctor`"));
+        }
+
+        let value = |var: &str| values.iter().find(|(name, _)| name == var).map(|(_, value)| value.clone());
+        if verified.contains(&Provider::Anthropic) {
+            let solana_tracker_key = value("SOLANA_TRACKER_API_KEY").filter(|_| verified.contains(&Provider::SolanaTracker));
+            let anthropic_key = value("ANTHROPIC_API_KEY").unwrap_or_default();
+            if let Err(e) = self.sample(&name, &anthropic_key, solana_tracker_key.as_deref()).await {
+                writeln!(self.output, "Couldn't write a sample post: {}", e)?;
+            }
+        } else {
+            writeln!(self.output, "Skipping the sample post: the Anthropic key wasn't accepted")?;
+        }
+        writeln!(self.output, "\nDone. Start the bot with `cargo run`.")?;
+        Ok(())
+    }
+}
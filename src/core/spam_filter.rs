@@ -0,0 +1,172 @@
+use chrono::{DateTime, Duration, Utc};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
+
+use super::character::SpamFilterSettings;
+use super::tweet_preview::url_regex;
+use crate::models::MentionAuthor;
+
+// Identical mentions are only counted against each other within this window
+const REPEAT_WINDOW_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpamReason {
+    BlockedOnTwitter,
+    Muted,
+    LinkOnly,
+    Repeated { count: usize },
+    NewAccount { age_days: i64 },
+    FewFollowers { followers: u64 },
+}
+
+impl fmt::Display for SpamReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpamReason::BlockedOnTwitter => write!(f, "author is blocked or muted on Twitter"),
+            SpamReason::Muted => write!(f, "author is muted"),
+            SpamReason::LinkOnly => write!(f, "link only"),
+            SpamReason::Repeated { count } => write!(f, "same text seen {} times today", count),
+            SpamReason::NewAccount { age_days } => write!(f, "account is {} days old", age_days),
+            SpamReason::FewFollowers { followers } => write!(f, "only {} followers", followers),
+        }
+    }
+}
+
+// Cheap checks run on mentions before the model is asked whether to reply.
+// Remembers recent mention texts and looked-up authors between sweeps.
+#[derive(Default)]
+pub struct SpamFilter {
+    // Normalized text -> ids of the mentions that used it, with when we saw them
+    seen: HashMap<String, Vec<(String, DateTime<Utc>)>>,
+    authors: HashMap<u64, MentionAuthor>,
+    // Accounts blocked or muted on Twitter, synced periodically; checked even with the filter off
+    blocked: HashSet<u64>,
+}
+
+fn mention_regex() -> &'static Regex {
+    static MENTION: OnceLock<Regex> = OnceLock::new();
+    MENTION.get_or_init(|| Regex::new(r"@\w+").unwrap())
+}
+
+impl SpamFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Lowercased text without @handles or links, so copy-pasted bot replies match
+    pub fn normalize(text: &str) -> String {
+        let text = url_regex().replace_all(text, " ");
+        let text = mention_regex().replace_all(&text, " ");
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    pub fn is_link_only(text: &str) -> bool {
+        url_regex().is_match(text)
+            && !Self::normalize(text).chars().any(|c| c.is_alphanumeric())
+    }
+
+    pub fn remember_authors(&mut self, authors: Vec<MentionAuthor>) {
+        for author in authors {
+            self.authors.insert(author.id, author);
+        }
+    }
+
+    pub fn set_blocked(&mut self, ids: impl IntoIterator<Item = u64>) {
+        self.blocked = ids.into_iter().collect();
+    }
+
+    pub fn is_blocked(&self, id: u64) -> bool {
+        self.blocked.contains(&id)
+    }
+
+    pub fn author(&self, id: u64) -> Option<&MentionAuthor> {
+        self.authors.get(&id)
+    }
+
+    // Ids from the list that haven't been looked up yet
+    pub fn unknown_authors(&self, ids: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        let mut unknown: Vec<u64> = ids.into_iter().filter(|id| !self.authors.contains_key(id)).collect();
+        unknown.sort_unstable();
+        unknown.dedup();
+        unknown
+    }
+
+    pub fn check(
+        &mut self,
+        settings: &SpamFilterSettings,
+        tweet_id: &str,
+        text: &str,
+        author_id: Option<u64>,
+    ) -> Option<SpamReason> {
+        self.check_at(settings, tweet_id, text, author_id, Utc::now())
+    }
+
+    pub fn check_at(
+        &mut self,
+        settings: &SpamFilterSettings,
+        tweet_id: &str,
+        text: &str,
+        author_id: Option<u64>,
+        now: DateTime<Utc>,
+    ) -> Option<SpamReason> {
+        if author_id.is_some_and(|id| self.is_blocked(id)) {
+            return Some(SpamReason::BlockedOnTwitter);
+        }
+        if !settings.enabled {
+            return None;
+        }
+        // Counted even when another check catches it, so a campaign is spotted early
+        let repeats = self.record(tweet_id, text, now);
+
+        let author = author_id.and_then(|id| self.authors.get(&id));
+        if settings.is_muted(author_id, author.map(|a| a.username.as_str())) {
+            return Some(SpamReason::Muted);
+        }
+        if Self::is_link_only(text) {
+            return Some(SpamReason::LinkOnly);
+        }
+        if settings.max_identical_mentions > 0 && repeats >= settings.max_identical_mentions {
+            return Some(SpamReason::Repeated { count: repeats });
+        }
+
+        let author = author?;
+        if settings.min_account_age_days > 0 {
+            if let Some(created_at) = author.created_at {
+                let age_days = now.signed_duration_since(created_at).num_days();
+                if age_days < settings.min_account_age_days {
+                    return Some(SpamReason::NewAccount { age_days });
+                }
+            }
+        }
+        if let Some(followers) = author.followers {
+            if followers < settings.min_followers {
+                return Some(SpamReason::FewFollowers { followers });
+            }
+        }
+        None
+    }
+
+    // Number of distinct mentions with this text in the window, this one included
+    fn record(&mut self, tweet_id: &str, text: &str, now: DateTime<Utc>) -> usize {
+        let cutoff = now - Duration::hours(REPEAT_WINDOW_HOURS);
+        self.seen.retain(|_, sightings| {
+            sightings.retain(|(_, at)| *at > cutoff);
+            !sightings.is_empty()
+        });
+
+        let normalized = Self::normalize(text);
+        if normalized.is_empty() {
+            return 0;
+        }
+        let sightings = self.seen.entry(normalized).or_default();
+        if !sightings.iter().any(|(id, _)| id == tweet_id) {
+            sightings.push((tweet_id.to_string(), now));
+        }
+        sightings.len()
+    }
+}
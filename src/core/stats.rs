@@ -0,0 +1,243 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::character::CharacterSettings;
+use super::clock::Schedule;
+use super::local_time::LocalTime;
+use super::predictions::PredictionRecord;
+use super::quota::{QuotaLedger, QuotaUsage};
+use crate::models::{Memory, TweetType};
+
+const TOP_ENTRIES: usize = 10;
+
+#[derive(Serialize, Debug)]
+pub struct DayStats {
+    pub date: String,
+    pub posts: usize,
+    pub replies: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CountEntry {
+    pub value: String,
+    pub count: usize,
+}
+
+// Summary of what the bot has been doing, built from memory for the `stats` command
+#[derive(Serialize, Debug)]
+pub struct StatsReport {
+    pub total_posts: usize,
+    pub total_replies: usize,
+    pub reply_rate: f64,
+    pub posts_per_day: Vec<DayStats>,
+    pub top_tokens: Vec<CountEntry>,
+    pub generations: u64,
+    pub avg_generation_ms: Option<f64>,
+    pub api_errors: BTreeMap<String, u64>,
+    pub top_phrases: Vec<CountEntry>,
+    pub predictions: PredictionRecord,
+    pub quota_window_start: Option<DateTime<Utc>>,
+    pub quota_window_end: Option<DateTime<Utc>>,
+    pub api_quota: Vec<QuotaUsage>,
+    pub timezone: String,
+    pub next_post_at: Option<DateTime<Utc>>,
+    // next_post_at in the character's timezone, e.g. "2026-10-16 14:30 EDT"
+    pub next_post_local: Option<String>,
+}
+
+impl StatsReport {
+    pub fn from_memory(memory: &Memory, now: DateTime<Utc>, days: i64) -> Self {
+        let total_replies = memory.tweets
+            .iter()
+            .filter(|t| matches!(t.tweet_type, TweetType::Reply))
+            .count();
+        let total_posts = memory.tweets.len() - total_replies;
+        let reply_rate = if memory.tweets.is_empty() {
+            0.0
+        } else {
+            total_replies as f64 / memory.tweets.len() as f64
+        };
+
+        let avg_generation_ms = if memory.usage.generations > 0 {
+            Some(memory.usage.generation_ms as f64 / memory.usage.generations as f64)
+        } else {
+            None
+        };
+
+        StatsReport {
+            total_posts,
+            total_replies,
+            reply_rate,
+            posts_per_day: Self::posts_per_day(memory, now, days),
+            top_tokens: Self::top_tokens(memory),
+            generations: memory.usage.generations,
+            avg_generation_ms,
+            api_errors: memory.usage.api_errors.clone(),
+            top_phrases: Self::top_phrases(memory),
+            predictions: PredictionRecord::from_predictions(&memory.predictions),
+            quota_window_start: memory.api_quota.window_start,
+            quota_window_end: memory.api_quota.window_end,
+            api_quota: QuotaLedger::usage(&memory.api_quota),
+            timezone: LocalTime::default().name().to_string(),
+            next_post_at: None,
+            next_post_local: None,
+        }
+    }
+
+    // The character's timezone and its next scheduled post, when there's a character to read
+    pub fn with_schedule(mut self, settings: &CharacterSettings, memory: &Memory, now: DateTime<Utc>) -> Self {
+        let local = LocalTime::from_settings(settings);
+        self.timezone = local.name().to_string();
+        self.next_post_at = Schedule::next_scheduled_post(settings, memory.next_tweet, now);
+        self.next_post_local = self.next_post_at.map(|at| local.at(at).format("%Y-%m-%d %H:%M %Z").to_string());
+        self
+    }
+
+    // One row per day for the last `days` days, oldest first, including quiet days
+    fn posts_per_day(memory: &Memory, now: DateTime<Utc>, days: i64) -> Vec<DayStats> {
+        let today = now.date_naive();
+        (0..days.max(1))
+            .rev()
+            .map(|offset| {
+                let date = today - Duration::days(offset);
+                let (mut posts, mut replies) = (0, 0);
+                for tweet in memory.tweets.iter().filter(|t| t.timestamp.date_naive() == date) {
+                    match tweet.tweet_type {
+                        TweetType::Original => posts += 1,
+                        TweetType::Reply => replies += 1,
+                    }
+                }
+                DayStats { date: date.to_string(), posts, replies }
+            })
+            .collect()
+    }
+
+    fn top_tokens(memory: &Memory) -> Vec<CountEntry> {
+        let own_symbol = memory.token_symbol.trim_start_matches('$').to_uppercase();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        for tweet in &memory.tweets {
+            let tickers: HashSet<String> = tweet.text
+                .split_whitespace()
+                .filter_map(|word| word.strip_prefix('$'))
+                .map(|ticker| {
+                    ticker
+                        .chars()
+                        .take_while(|c| c.is_ascii_alphanumeric())
+                        .collect::<String>()
+                        .to_uppercase()
+                })
+                .filter(|ticker| {
+                    // Skip prices like $0.01 and our own token
+                    ticker.chars().next().is_some_and(|c| c.is_ascii_alphabetic()) && *ticker != own_symbol
+                })
+                .collect();
+            for ticker in tickers {
+                *counts.entry(ticker).or_insert(0) += 1;
+            }
+        }
+
+        Self::top(counts, 1)
+    }
+
+    // Three-word phrases that keep showing up across different tweets
+    fn top_phrases(memory: &Memory) -> Vec<CountEntry> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        for tweet in &memory.tweets {
+            let words: Vec<String> = tweet.text
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '$').to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect();
+            let phrases: HashSet<String> = words.windows(3).map(|window| window.join(" ")).collect();
+            for phrase in phrases {
+                *counts.entry(phrase).or_insert(0) += 1;
+            }
+        }
+
+        Self::top(counts, 2)
+    }
+
+    fn top(counts: HashMap<String, usize>, min_count: usize) -> Vec<CountEntry> {
+        let mut entries: Vec<CountEntry> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= min_count)
+            .map(|(value, count)| CountEntry { value, count })
+            .collect();
+        entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        entries.truncate(TOP_ENTRIES);
+        entries
+    }
+
+    pub fn print(&self) {
+        println!("=== ChainFud Stats ===");
+        println!("Posts: {}  Replies: {}  Reply rate: {:.1}%", self.total_posts, self.total_replies, self.reply_rate * 100.0);
+        match &self.next_post_local {
+            Some(next) => println!("Next scheduled post: {} (schedule in {})", next, self.timezone),
+            None => println!("Schedule timezone: {}", self.timezone),
+        }
+
+        println!("\nPosts per day:");
+        for day in &self.posts_per_day {
+            println!("  {}  {:>3} posts  {:>3} replies", day.date, day.posts, day.replies);
+        }
+
+        println!("\nMost FUDded tokens:");
+        if self.top_tokens.is_empty() {
+            println!("  none yet");
+        }
+        for entry in &self.top_tokens {
+            println!("  ${:<12} {}", entry.value, entry.count);
+        }
+
+        println!("\nGenerations: {}", self.generations);
+        match self.avg_generation_ms {
+            Some(ms) => println!("Average generation latency: {:.0}ms", ms),
+            None => println!("Average generation latency: n/a"),
+        }
+
+        println!(
+            "\nPredictions: {} right, {} wrong, {} open",
+            self.predictions.wins, self.predictions.losses, self.predictions.open
+        );
+
+        println!("\nAPI errors:");
+        if self.api_errors.is_empty() {
+            println!("  none");
+        }
+        for (source, count) in &self.api_errors {
+            println!("  {:<14} {}", source, count);
+        }
+
+        match (self.quota_window_start, self.quota_window_end) {
+            (Some(start), Some(end)) => println!(
+                "\nTwitter API calls ({} to {}):",
+                start.format("%Y-%m-%d"),
+                end.format("%Y-%m-%d")
+            ),
+            _ => println!("\nTwitter API calls:"),
+        }
+        if self.api_quota.is_empty() {
+            println!("  none recorded");
+        }
+        for usage in &self.api_quota {
+            match usage.limit {
+                Some(limit) => {
+                    let pct = if limit > 0 { usage.calls as f64 / limit as f64 * 100.0 } else { 100.0 };
+                    println!("  {:<14} {:>6} / {:<6} ({:.0}%)", usage.endpoint, usage.calls, limit, pct);
+                }
+                None => println!("  {:<14} {:>6}", usage.endpoint, usage.calls),
+            }
+        }
+
+        println!("\nTop recurring phrases:");
+        if self.top_phrases.is_empty() {
+            println!("  none");
+        }
+        for entry in &self.top_phrases {
+            println!("  {:<40} {}", entry.value, entry.count);
+        }
+    }
+}
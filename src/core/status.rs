@@ -0,0 +1,241 @@
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use super::feature_flags::{Flag, FeatureSwitch, FlagCommand};
+use super::health::{HealthReport, Provider, ProviderStatus};
+use super::local_time::LocalTime;
+use super::storage::SweepReport;
+use crate::models::FeatureFlags;
+use crate::providers::solanatracker::CacheMetrics;
+
+// Longest request line read; the endpoints take no body
+const MAX_REQUEST_BYTES: usize = 2048;
+// A client that connects but never sends its request is dropped after this
+const REQUEST_READ_TIMEOUT_SECS: u64 = 5;
+
+// What the run loop last reported, for uptime monitors to poll
+#[derive(Debug, Clone)]
+pub struct StatusSnapshot {
+    pub started_at: DateTime<Utc>,
+    // End of the run loop's last cycle; an old one means the loop is stuck
+    pub updated_at: DateTime<Utc>,
+    pub providers: Vec<(Provider, ProviderStatus)>,
+    pub last_post_at: Option<DateTime<Utc>>,
+    pub reply_queue: usize,
+    pub held_replies: usize,
+    pub outbox: usize,
+    pub flags: FeatureFlags,
+    pub local_time: LocalTime,
+    pub next_post_at: Option<DateTime<Utc>>,
+    // Latest storage sweep, None before the first
+    pub storage: Option<SweepReport>,
+    pub trending_cache: CacheMetrics,
+}
+
+impl StatusSnapshot {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        StatusSnapshot {
+            started_at: now,
+            updated_at: now,
+            providers: Vec::new(),
+            last_post_at: None,
+            reply_queue: 0,
+            held_replies: 0,
+            outbox: 0,
+            flags: FeatureFlags::default(),
+            local_time: LocalTime::default(),
+            next_post_at: None,
+            storage: None,
+            trending_cache: CacheMetrics::default(),
+        }
+    }
+
+    pub fn set_health(&mut self, health: &HealthReport) {
+        self.providers = Provider::ALL.iter().map(|p| (*p, health.status(*p).clone())).collect();
+    }
+
+    // The run loop has finished a cycle recently
+    pub fn is_live(&self, stale_after: Duration, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.updated_at) < stale_after
+    }
+
+    // Live, and the providers every post needs are up
+    pub fn is_ready(&self, stale_after: Duration, now: DateTime<Utc>) -> bool {
+        self.is_live(stale_after, now)
+            && self
+                .providers
+                .iter()
+                .filter(|(provider, _)| matches!(provider, Provider::Twitter | Provider::Anthropic))
+                .all(|(_, status)| matches!(status, ProviderStatus::Ok(_)))
+    }
+
+    pub fn to_json(&self, stale_after: Duration, now: DateTime<Utc>) -> serde_json::Value {
+        let providers: serde_json::Map<String, serde_json::Value> = self
+            .providers
+            .iter()
+            .map(|(provider, status)| {
+                let (state, detail) = match status {
+                    ProviderStatus::Unchecked => ("unchecked", None),
+                    ProviderStatus::Ok(detail) => ("ok", Some(detail.as_str()).filter(|detail| !detail.is_empty())),
+                    ProviderStatus::Degraded(reason) => ("degraded", Some(reason.as_str())),
+                    ProviderStatus::Disabled => ("disabled", None),
+                };
+                (provider.name().to_string(), json!({ "status": state, "detail": detail }))
+            })
+            .collect();
+        let flags: serde_json::Map<String, serde_json::Value> =
+            Flag::ALL.iter().map(|flag| (flag.name().to_string(), json!(self.flags.get(*flag)))).collect();
+        let storage = self.storage.as_ref().map(|report| {
+            let dirs: serde_json::Map<String, serde_json::Value> = report
+                .usage
+                .iter()
+                .map(|dir| {
+                    let usage = json!({ "bytes": dir.bytes, "files": dir.files, "quota_bytes": dir.quota_bytes });
+                    (dir.name.clone(), usage)
+                })
+                .collect();
+            json!({ "free_bytes": report.free_bytes, "dirs": dirs })
+        });
+        json!({
+            "live": self.is_live(stale_after, now),
+            "ready": self.is_ready(stale_after, now),
+            "started_at": self.started_at.to_rfc3339(),
+            "updated_at": self.updated_at.to_rfc3339(),
+            "last_post_at": self.last_post_at.map(|at| at.to_rfc3339()),
+            "providers": providers,
+            "queues": {
+                "reply_queue": self.reply_queue,
+                "held_replies": self.held_replies,
+                "outbox": self.outbox,
+            },
+            "flags": flags,
+            "schedule": {
+                "timezone": self.local_time.name(),
+                "local_time": self.local_time.at(now).to_rfc3339(),
+                "next_post_at": self.next_post_at.map(|at| at.to_rfc3339()),
+                "next_post_local": self.next_post_at.map(|at| self.local_time.at(at).to_rfc3339()),
+            },
+            "storage": storage,
+            "trending_cache": {
+                "hits": self.trending_cache.hits,
+                "stale_hits": self.trending_cache.stale_hits,
+                "misses": self.trending_cache.misses,
+                "refreshes": self.trending_cache.refreshes,
+                "failed_refreshes": self.trending_cache.failed_refreshes,
+            },
+        })
+    }
+}
+
+// GET /healthz answers 200 while the run loop is cycling, /readyz while it can
+// also post; both return the full snapshot as JSON, and 503 otherwise.
+// POST /flags/<name>/<on|off> flips a feature flag when an admin token is set.
+pub struct StatusServer;
+
+impl StatusServer {
+    // Status code and body for a request path
+    pub fn respond(path: &str, snapshot: &StatusSnapshot, stale_after: Duration, now: DateTime<Utc>) -> (u16, String) {
+        let ok = match path.split('?').next().unwrap_or(path) {
+            "/healthz" => snapshot.is_live(stale_after, now),
+            "/readyz" => snapshot.is_ready(stale_after, now),
+            _ => return (404, json!({ "error": "not found" }).to_string()),
+        };
+        (if ok { 200 } else { 503 }, snapshot.to_json(stale_after, now).to_string())
+    }
+
+    // The flag change a POST asks for, or the status code and body to refuse it with
+    pub fn flag_request(path: &str, authorization: Option<&str>, admin_token: Option<&str>) -> Result<(Flag, bool), (u16, String)> {
+        let Some(rest) = path.strip_prefix("/flags/") else {
+            return Err((404, json!({ "error": "not found" }).to_string()));
+        };
+        let Some(admin_token) = admin_token else {
+            return Err((403, json!({ "error": "set STATUS_ADMIN_TOKEN to change flags over HTTP" }).to_string()));
+        };
+        let given = authorization.and_then(|value| value.strip_prefix("Bearer "));
+        if !given.is_some_and(|given| Self::token_matches(given, admin_token)) {
+            return Err((401, json!({ "error": "wrong or missing bearer token" }).to_string()));
+        }
+        match rest.split_once('/').and_then(|(flag, state)| FeatureSwitch::parse_command(&format!("flags {} {}", flag, state))) {
+            Some(FlagCommand::Set(flag, on)) => Ok((flag, on)),
+            _ => Err((400, json!({ "error": "expected /flags/<name>/<on|off>" }).to_string())),
+        }
+    }
+
+    // Compares digests of the two in full rather than stopping at the first differing
+    // byte, so response times say nothing about how much of a guess was right
+    fn token_matches(given: &str, expected: &str) -> bool {
+        let (given, expected) = (Sha256::digest(given.as_bytes()), Sha256::digest(expected.as_bytes()));
+        given.iter().zip(expected.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+
+    pub async fn serve(
+        addr: SocketAddr,
+        snapshot: Arc<Mutex<StatusSnapshot>>,
+        stale_after: Duration,
+        admin_token: Option<String>,
+    ) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        println!("Status endpoints listening on http://{}/healthz and /readyz", addr);
+        loop {
+            let (mut stream, _) = listener.accept().await?;
+            let snapshot = snapshot.clone();
+            let admin_token = admin_token.clone();
+            tokio::spawn(async move {
+                let mut buffer = vec![0u8; MAX_REQUEST_BYTES];
+                let read_timeout = std::time::Duration::from_secs(REQUEST_READ_TIMEOUT_SECS);
+                let read = match tokio::time::timeout(read_timeout, stream.read(&mut buffer)).await {
+                    Ok(Ok(read)) => read,
+                    Ok(Err(_)) | Err(_) => return,
+                };
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let mut lines = request.lines();
+                let mut words = lines.next().unwrap_or_default().split_whitespace();
+                let authorization = lines
+                    .take_while(|line| !line.is_empty())
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+                    .map(|(_, value)| value.trim());
+                let (status, body) = match (words.next(), words.next()) {
+                    (Some("GET" | "HEAD"), Some(path)) => {
+                        let snapshot = snapshot.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                        Self::respond(path, &snapshot, stale_after, Utc::now())
+                    }
+                    (Some("POST"), Some(path)) => match Self::flag_request(path, authorization, admin_token.as_deref()) {
+                        // Applied by the run loop along with CLI requests, within a minute
+                        Ok((flag, on)) => match FeatureSwitch::write_request(&FlagCommand::Set(flag, on)) {
+                            Ok(()) => (202, json!({ "flag": flag.name(), "on": on }).to_string()),
+                            Err(e) => (500, json!({ "error": e.to_string() }).to_string()),
+                        },
+                        Err(refusal) => refusal,
+                    },
+                    _ => (405, json!({ "error": "method not allowed" }).to_string()),
+                };
+                let reason = match status {
+                    200 => "OK",
+                    202 => "Accepted",
+                    400 => "Bad Request",
+                    401 => "Unauthorized",
+                    403 => "Forbidden",
+                    404 => "Not Found",
+                    405 => "Method Not Allowed",
+                    500 => "Internal Server Error",
+                    _ => "Service Unavailable",
+                };
+                let response = format!(
+                    "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    reason,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    }
+}
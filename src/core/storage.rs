@@ -0,0 +1,146 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::character::StorageSettings;
+use super::paths;
+
+pub const DEFAULT_STORAGE_DIR: &str = "./storage";
+const MB: u64 = 1024 * 1024;
+
+// Subdirectories holding files the operator put there, or posts that haven't gone out
+// yet; never trimmed to a quota
+pub const PROTECTED_DIRS: [&str; 4] = ["charts", "videos", "fonts", "outbox"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirUsage {
+    pub name: String,
+    pub bytes: u64,
+    pub files: usize,
+    pub quota_bytes: u64,
+}
+
+// What one sweep found and removed
+#[derive(Debug, Clone, Default)]
+pub struct SweepReport {
+    pub usage: Vec<DirUsage>,
+    pub deleted_files: usize,
+    pub freed_bytes: u64,
+    // None where the free space can't be read
+    pub free_bytes: Option<u64>,
+}
+
+impl SweepReport {
+    // Unknown free space counts as enough, so a platform without it doesn't lose images
+    pub fn has_headroom(&self, settings: &StorageSettings) -> bool {
+        self.free_bytes.is_none_or(|free| free >= settings.min_free_mb * MB)
+    }
+}
+
+struct StoredFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+}
+
+// Keeps the subdirectories of storage/ under their size quotas by deleting their
+// oldest files, and reads how much disk is left
+pub struct StorageManager {
+    root: PathBuf,
+}
+
+impl Default for StorageManager {
+    fn default() -> Self {
+        Self::new(paths::resolve(DEFAULT_STORAGE_DIR))
+    }
+}
+
+impl StorageManager {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        StorageManager { root: root.as_ref().to_path_buf() }
+    }
+
+    // Every file under `dir`, however deep
+    fn files(dir: &Path) -> io::Result<Vec<StoredFile>> {
+        let mut files = Vec::new();
+        if !dir.exists() {
+            return Ok(files);
+        }
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                files.extend(Self::files(&entry.path())?);
+            } else {
+                files.push(StoredFile {
+                    path: entry.path(),
+                    bytes: metadata.len(),
+                    modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+        Ok(files)
+    }
+
+    // Deletes the oldest files in `name` until it's under `quota_bytes`. The newest
+    // file is always kept, as it may still be being written to.
+    pub fn enforce(&self, name: &str, quota_bytes: u64) -> io::Result<(DirUsage, usize, u64)> {
+        let mut files = Self::files(&self.root.join(name))?;
+        files.sort_by_key(|file| file.modified);
+        let mut bytes: u64 = files.iter().map(|file| file.bytes).sum();
+        let mut deleted = 0;
+        let mut freed = 0;
+        let keep = files.len().saturating_sub(1);
+        for file in files.iter().take(keep) {
+            if bytes <= quota_bytes {
+                break;
+            }
+            fs::remove_file(&file.path)?;
+            bytes -= file.bytes;
+            freed += file.bytes;
+            deleted += 1;
+        }
+        let usage = DirUsage {
+            name: name.to_string(),
+            bytes,
+            files: files.len() - deleted,
+            quota_bytes,
+        };
+        Ok((usage, deleted, freed))
+    }
+
+    pub fn free_bytes(&self) -> Option<u64> {
+        fs2::available_space(&self.root).ok()
+    }
+
+    // Every quota'd directory brought under its quota. A directory that can't be read or
+    // trimmed is logged and left for the next sweep.
+    pub fn sweep(&self, settings: &StorageSettings) -> SweepReport {
+        let mut report = SweepReport::default();
+        for (name, quota_mb) in &settings.quotas_mb {
+            if PROTECTED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            match self.enforce(name, quota_mb * MB) {
+                Ok((usage, deleted, freed)) => {
+                    if deleted > 0 {
+                        println!(
+                            "Storage: deleted the {} oldest files in storage/{} ({:.1}MB) to stay under its {}MB quota",
+                            deleted,
+                            name,
+                            freed as f64 / MB as f64,
+                            quota_mb
+                        );
+                    }
+                    report.deleted_files += deleted;
+                    report.freed_bytes += freed;
+                    report.usage.push(usage);
+                }
+                Err(e) => eprintln!("Storage: couldn't trim storage/{}: {}", name, e),
+            }
+        }
+        report.free_bytes = self.free_bytes();
+        report
+    }
+}
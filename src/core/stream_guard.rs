@@ -0,0 +1,147 @@
+use serde_json::Value;
+
+// What one pass over a streamed completion ended with
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamOutcome {
+    Finished(String),
+    // A term showed up in the partial text, so the stream was cut there
+    Aborted { term: String, partial: String },
+}
+
+// Anthropic's server-sent events, fed in chunks as they arrive. Only text deltas
+// are kept; an error event fails the stream.
+#[derive(Default)]
+pub struct SseReader {
+    // Bytes of an event that hasn't fully arrived yet; a chunk can end mid character
+    pending: Vec<u8>,
+    finished: bool,
+}
+
+impl SseReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // message_stop was seen
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    // Text deltas from the events this chunk completed
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<String>, anyhow::Error> {
+        self.pending.extend_from_slice(chunk);
+        let mut deltas = Vec::new();
+        while let Some(end) = Self::event_end(&self.pending) {
+            let event: Vec<u8> = self.pending.drain(..end).collect();
+            let event = String::from_utf8_lossy(&event);
+            let data: String = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim)
+                .collect();
+            if data.is_empty() {
+                continue;
+            }
+            let data: Value = serde_json::from_str(&data)
+                .map_err(|e| anyhow::anyhow!("Unreadable stream event: {}", e))?;
+            match data["type"].as_str() {
+                Some("content_block_delta") => {
+                    if let Some(text) = data["delta"]["text"].as_str() {
+                        deltas.push(text.to_string());
+                    }
+                }
+                Some("message_stop") => self.finished = true,
+                Some("error") => {
+                    let message = data["error"]["message"].as_str().unwrap_or("unknown error");
+                    return Err(anyhow::anyhow!("Stream failed: {}", message));
+                }
+                _ => {}
+            }
+        }
+        Ok(deltas)
+    }
+
+    // Length of the first complete event, including the blank line ending it
+    fn event_end(pending: &[u8]) -> Option<usize> {
+        let lf = pending.windows(2).position(|w| w == b"\n\n").map(|i| i + 2);
+        let crlf = pending.windows(4).position(|w| w == b"\r\n\r\n").map(|i| i + 4);
+        match (lf, crlf) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+// Terms generated text must never contain, checked against the partial output
+// as it streams in so a bad completion can be dropped early
+#[derive(Debug, Clone, Default)]
+pub struct StreamGuard {
+    terms: Vec<String>,
+}
+
+impl StreamGuard {
+    pub fn new<I, S>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut terms: Vec<String> = terms
+            .into_iter()
+            .map(|term| term.as_ref().trim().to_lowercase())
+            .filter(|term| !term.is_empty())
+            .collect();
+        terms.sort();
+        terms.dedup();
+        StreamGuard { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // This guard with more terms added, e.g. the aggression dial's tame words
+    pub fn with_terms<S: AsRef<str>>(&self, extra: &[S]) -> Self {
+        Self::new(self.terms.iter().map(String::as_str).chain(extra.iter().map(AsRef::as_ref)))
+    }
+
+    // First term the text contains as a whole word, ignoring case; same matching as
+    // banned_words in the validator
+    pub fn violation(&self, text: &str) -> Option<&str> {
+        let text = text.to_lowercase();
+        self.terms.iter().find(|term| contains_term(&text, term, false)).map(String::as_str)
+    }
+
+    // Same for text still streaming in, where a term right at the end may yet turn
+    // out to be the start of a longer word
+    pub fn partial_violation(&self, text: &str) -> Option<&str> {
+        let text = text.to_lowercase();
+        self.terms.iter().find(|term| contains_term(&text, term, true)).map(String::as_str)
+    }
+}
+
+// Whether lowercase `text` has `term` with no letter or digit running on either side
+// of it, so "rug" matches "a rug." but not "drug" or "rugged". A term edge that isn't
+// alphanumeric itself, like the $ of "$rug", needs no boundary. With `open_end` a
+// match touching the end of the text doesn't count yet.
+pub fn contains_term(text: &str, term: &str, open_end: bool) -> bool {
+    if term.is_empty() {
+        return false;
+    }
+    let word_start = term.starts_with(char::is_alphanumeric);
+    let word_end = term.ends_with(char::is_alphanumeric);
+    let mut from = 0;
+    while let Some(found) = text[from..].find(term) {
+        let start = from + found;
+        let end = start + term.len();
+        let before = !word_start || text[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let after = match text[end..].chars().next() {
+            Some(c) => !word_end || !c.is_alphanumeric(),
+            None => !open_end || !word_end,
+        };
+        if before && after {
+            return true;
+        }
+        from = start + term.chars().next().map_or(1, char::len_utf8);
+    }
+    false
+}
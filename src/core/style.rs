@@ -0,0 +1,222 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use regex::Regex;
+
+use super::character::{DecorationSettings, PhraseDecaySettings};
+use super::kv_store::{insert_timed, parse_time_key, remove_before, KvStore};
+use super::phrase_tracker::PhraseTracker;
+
+// Filler the model leans on; more than a couple in one post reads as a tic
+const COMMON_PATTERNS: [&str; 10] = [
+    "ser", "ngmi", "wen", "just", "literally", "probably",
+    "definitely", "obviously", "clearly", "absolutely",
+];
+const ALTERNATIVES: [&str; 8] = [
+    "looking kinda", "straight up", "ngl", "fr fr",
+    "lowkey", "highkey", "certified", "actual",
+];
+const COMMON_OPENERS: [&str; 5] = ["another", "just", "ser", "breaking:", "imagine"];
+const OPENER_VARIATIONS: [&str; 7] = [
+    "bruh", "certified", "actual", "friendly reminder:",
+    "psa:", "reminder:", "daily dose of",
+];
+
+// Signature words wear out faster than ordinary vocabulary
+const SIGNATURE_PATTERNS: [&str; 5] = ["ser", "ngmi", "wen", "just", "literally"];
+const MAX_PATTERN_USES: usize = 3;
+const MAX_WORD_USES: usize = 5;
+// Shorter words ("the", "dev", "rug") are too common to count against a post
+const MIN_TRACKED_WORD_LEN: usize = 4;
+
+// All anti-repetition state in one place: recently posted phrases, a decaying
+// word frequency table, the substitutions that vary the model's tics, and the
+// character's emoji and punctuation policy. Runtime and every agent share one instance.
+pub struct StyleEngine {
+    phrases: PhraseTracker,
+    words: PhraseTracker,
+    decoration: DecorationSettings,
+    // From the aggression dial; multiplies decoration.emoji_chance
+    emoji_scale: f64,
+    max_age: Duration,
+    // Where recorded posts are kept across restarts, if anywhere
+    history: Option<Box<dyn KvStore>>,
+}
+
+impl Default for StyleEngine {
+    fn default() -> Self {
+        Self::from_settings(&PhraseDecaySettings::default())
+    }
+}
+
+impl StyleEngine {
+    pub fn new(max_phrases: usize, max_age: Duration) -> Self {
+        StyleEngine {
+            phrases: PhraseTracker::new(max_phrases, max_age),
+            words: PhraseTracker::with_phrase_words(1, max_phrases, max_age),
+            decoration: DecorationSettings::default(),
+            emoji_scale: 1.0,
+            max_age,
+            history: None,
+        }
+    }
+
+    pub fn with_decoration(mut self, decoration: DecorationSettings) -> Self {
+        self.decoration = decoration;
+        self
+    }
+
+    pub fn set_emoji_scale(&mut self, scale: f64) {
+        self.emoji_scale = scale.max(0.0);
+    }
+
+    // Persist recorded posts to this store, picking up the ones still inside the window
+    pub fn with_history(mut self, mut history: Box<dyn KvStore>, now: DateTime<Utc>) -> Self {
+        if let Err(e) = remove_before(history.as_mut(), now - self.max_age) {
+            eprintln!("Failed to expire phrase history: {}", e);
+        }
+        for (key, text) in history.entries() {
+            if let Some(at) = parse_time_key(&key) {
+                self.phrases.record_at(&text, at);
+                self.words.record_at(&text, at);
+            }
+        }
+        self.history = Some(history);
+        self
+    }
+
+    // Hand the store over to a rebuilt engine
+    pub fn take_history(&mut self) -> Option<Box<dyn KvStore>> {
+        self.history.take()
+    }
+
+    pub fn from_settings(settings: &PhraseDecaySettings) -> Self {
+        Self::new(settings.max_phrases, Duration::hours(settings.max_age_hours))
+    }
+
+    // Whether the text reuses a 3-word phrase from a recent post
+    pub fn repeats_recent_phrase(&mut self, text: &str) -> bool {
+        self.phrases.contains_recent(text)
+    }
+
+    // Whether the text leans on words we've used too much lately
+    pub fn is_overused(&mut self, text: &str) -> bool {
+        self.is_overused_at(text, Utc::now())
+    }
+
+    pub fn is_overused_at(&mut self, text: &str, now: DateTime<Utc>) -> bool {
+        self.words.expire(now);
+        PhraseTracker::words_in(text).iter().any(|word| {
+            let uses = self.words.count(word);
+            if SIGNATURE_PATTERNS.contains(&word.as_str()) {
+                uses > MAX_PATTERN_USES
+            } else {
+                word.chars().count() >= MIN_TRACKED_WORD_LEN && uses > MAX_WORD_USES
+            }
+        })
+    }
+
+    // Remember something we posted
+    pub fn record(&mut self, text: &str) {
+        self.record_at(text, Utc::now());
+    }
+
+    pub fn record_at(&mut self, text: &str, now: DateTime<Utc>) {
+        self.phrases.record_at(text, now);
+        self.words.record_at(text, now);
+        if let Some(history) = self.history.as_mut() {
+            let saved = remove_before(history.as_mut(), now - self.max_age)
+                .and_then(|_| insert_timed(history.as_mut(), now, text));
+            if let Err(e) = saved {
+                eprintln!("Failed to save phrase history: {}", e);
+            }
+        }
+    }
+
+    // Swap out some of the model's stock filler so consecutive posts read differently
+    pub fn vary(&self, text: &str) -> String {
+        self.vary_with(text, &mut rand::thread_rng())
+    }
+
+    pub fn vary_with<R: Rng>(&self, text: &str, rng: &mut R) -> String {
+        let mut processed = text.to_string();
+
+        let patterns: Vec<Regex> = COMMON_PATTERNS
+            .iter()
+            .map(|pattern| Regex::new(&format!(r"(?i)\b{}\b", regex::escape(pattern))).unwrap())
+            .collect();
+
+        // Too many common patterns at once, replace some
+        let pattern_count = patterns.iter().filter(|re| re.is_match(&processed)).count();
+        if pattern_count > 2 {
+            for re in &patterns {
+                if re.is_match(&processed) && rng.gen_bool(0.7) {
+                    if let Some(alt) = ALTERNATIVES.choose(rng) {
+                        processed = re.replacen(&processed, 1, *alt).into_owned();
+                    }
+                }
+            }
+        }
+
+        // Stock opener, maybe prefix a variation
+        let lower = processed.to_lowercase();
+        let starts_common = COMMON_OPENERS.iter().any(|start| lower.starts_with(start));
+        if starts_common && rng.gen_bool(0.6) {
+            if let Some(variation) = OPENER_VARIATIONS.choose(rng) {
+                processed = format!("{} {}", variation, processed);
+            }
+        }
+
+        self.decorate_with(&processed, rng)
+    }
+
+    // Punctuation and emojis the character allows, and none it never uses
+    pub fn decorate_with<R: Rng>(&self, text: &str, rng: &mut R) -> String {
+        let policy = &self.decoration;
+        let allowed = |options: &[String]| -> Vec<String> {
+            options
+                .iter()
+                .filter(|option| !option.is_empty() && !policy.never_use.contains(option))
+                .cloned()
+                .collect()
+        };
+        let mut processed = self.strip_never_used(text);
+
+        // Occasional punctuation variation
+        let punctuation = allowed(&policy.punctuation);
+        if !processed.contains('?')
+            && !processed.contains('!')
+            && rng.gen_bool(policy.punctuation_chance.clamp(0.0, 1.0))
+        {
+            if let Some(punctuation) = punctuation.choose(rng) {
+                processed = format!("{}{}", processed, punctuation);
+            }
+        }
+
+        // Emojis go at either end so they never split a sentence
+        let emojis = allowed(&policy.emojis);
+        if !emojis.is_empty() && policy.max_emojis > 0 && rng.gen_bool((policy.emoji_chance * self.emoji_scale).clamp(0.0, 1.0)) {
+            for _ in 0..rng.gen_range(1..=policy.max_emojis) {
+                let emoji = emojis.choose(rng).expect("emojis is not empty");
+                processed = if rng.gen_bool(0.5) {
+                    format!("{} {}", emoji, processed)
+                } else {
+                    format!("{} {}", processed, emoji)
+                };
+            }
+        }
+
+        processed
+    }
+
+    fn strip_never_used(&self, text: &str) -> String {
+        if self.decoration.never_use.is_empty() {
+            return text.to_string();
+        }
+        let mut stripped = text.to_string();
+        for banned in self.decoration.never_use.iter().filter(|banned| !banned.is_empty()) {
+            stripped = stripped.replace(banned.as_str(), "");
+        }
+        stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
@@ -0,0 +1,110 @@
+use rand::Rng;
+
+use super::character::TargetScoringSettings;
+use crate::models::TokenSnapshot;
+
+// A 1h gain this big (percent) maxes out the gain signal
+const FULL_GAIN_1H: f64 = 100.0;
+// Buys/sells this lopsided either way max out the imbalance signal
+const FULL_IMBALANCE_RATIO: f64 = 10.0;
+// Too few trades to read anything into the ratio
+const MIN_TRADES: u64 = 20;
+// Liquidity at or above this share of market cap counts as healthy
+const HEALTHY_LIQUIDITY_RATIO: f64 = 0.1;
+
+// Each signal is 0..1 before weighting
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TargetScore {
+    pub gain_1h: f64,
+    pub trade_imbalance: f64,
+    pub thin_liquidity: f64,
+    pub total: f64,
+}
+
+impl TargetScore {
+    pub fn describe(&self, settings: &TargetScoringSettings) -> String {
+        format!(
+            "score {:.2} = base {:.2} + 1h gain {:.2}x{:.2} + trade imbalance {:.2}x{:.2} + thin liquidity {:.2}x{:.2}",
+            self.total,
+            settings.base_weight,
+            self.gain_1h,
+            settings.gain_1h_weight,
+            self.trade_imbalance,
+            settings.trade_imbalance_weight,
+            self.thin_liquidity,
+            settings.thin_liquidity_weight,
+        )
+    }
+}
+
+// Picks which eligible trending token scheduled FUD goes after
+pub struct TargetScorer;
+
+impl TargetScorer {
+    pub fn score(settings: &TargetScoringSettings, token: &TokenSnapshot) -> TargetScore {
+        let gain_1h = token
+            .change_1h
+            .map(|change| (change / FULL_GAIN_1H).clamp(0.0, 1.0))
+            .unwrap_or(0.0);
+
+        let trade_imbalance = if token.buys + token.sells >= MIN_TRADES {
+            let ratio = (token.buys.max(1) as f64) / (token.sells.max(1) as f64);
+            (ratio.ln().abs() / FULL_IMBALANCE_RATIO.ln()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let thin_liquidity = if token.market_cap > 0.0 {
+            1.0 - (token.liquidity_usd / token.market_cap / HEALTHY_LIQUIDITY_RATIO).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let total = settings.base_weight.max(0.0)
+            + gain_1h * settings.gain_1h_weight.max(0.0)
+            + trade_imbalance * settings.trade_imbalance_weight.max(0.0)
+            + thin_liquidity * settings.thin_liquidity_weight.max(0.0);
+
+        TargetScore {
+            gain_1h,
+            trade_imbalance,
+            thin_liquidity,
+            total,
+        }
+    }
+
+    pub fn pick(settings: &TargetScoringSettings, tokens: &[TokenSnapshot]) -> Option<(usize, TargetScore)> {
+        Self::pick_with(settings, tokens, &mut rand::thread_rng())
+    }
+
+    // Random pick weighted by score; uniform when scoring is off or nothing scores
+    pub fn pick_with<R: Rng>(
+        settings: &TargetScoringSettings,
+        tokens: &[TokenSnapshot],
+        rng: &mut R,
+    ) -> Option<(usize, TargetScore)> {
+        if tokens.is_empty() {
+            return None;
+        }
+        if !settings.enabled {
+            return Some((rng.gen_range(0..tokens.len()), TargetScore::default()));
+        }
+
+        let scores: Vec<TargetScore> = tokens.iter().map(|token| Self::score(settings, token)).collect();
+        let total: f64 = scores.iter().map(|score| score.total).sum();
+        if total <= 0.0 {
+            let i = rng.gen_range(0..tokens.len());
+            return Some((i, scores[i]));
+        }
+
+        let mut roll = rng.gen_range(0.0..total);
+        for (i, score) in scores.iter().enumerate() {
+            if roll < score.total {
+                return Some((i, *score));
+            }
+            roll -= score.total;
+        }
+        let last = scores.len() - 1;
+        Some((last, scores[last]))
+    }
+}
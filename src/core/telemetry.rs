@@ -0,0 +1,242 @@
+use rand::Rng;
+use serde_json::{json, Value};
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::config::TelemetryConfig;
+
+pub const DEFAULT_SERVICE_NAME: &str = "chainfud";
+const EXPORT_TIMEOUT_SECS: u64 = 5;
+// OTLP span kind: internal
+const SPAN_KIND_INTERNAL: u8 = 1;
+// OTLP status codes
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+
+// One finished pipeline stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanRecord {
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub start_ns: u128,
+    pub end_ns: u128,
+    pub attributes: Vec<(String, String)>,
+    pub error: Option<String>,
+}
+
+// The cycle currently being traced; stages run one at a time, so one is enough
+struct ActiveTrace {
+    trace_id: String,
+    root_span_id: String,
+    name: String,
+    start_ns: u128,
+    spans: Vec<SpanRecord>,
+}
+
+struct Exporter {
+    client: reqwest::Client,
+    // Full /v1/traces URL
+    url: String,
+    headers: Vec<(String, String)>,
+    service_name: String,
+    active: Mutex<Option<ActiveTrace>>,
+}
+
+// Spans for each stage of a cycle (fetch trending, select target, LLM call, media
+// upload, post), exported over OTLP/HTTP as JSON when a cycle finishes. Cloned into
+// every agent; does nothing unless OTEL_EXPORTER_OTLP_ENDPOINT is set.
+#[derive(Clone, Default)]
+pub struct Tracer {
+    exporter: Option<Arc<Exporter>>,
+}
+
+fn now_ns() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0)
+}
+
+fn random_id(bytes: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..bytes).map(|_| format!("{:02x}", rng.gen::<u8>())).collect()
+}
+
+// "key=value,key2=value2", as in OTEL_EXPORTER_OTLP_HEADERS
+pub fn parse_headers(raw: &str) -> Option<Vec<(String, String)>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            Some((key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+// Collector URLs are given as a base; traces go to /v1/traces under it
+pub fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+// OTLP/JSON ExportTraceServiceRequest
+pub fn otlp_body(service_name: &str, spans: &[SpanRecord]) -> Value {
+    let attribute = |key: &str, value: &str| json!({ "key": key, "value": { "stringValue": value } });
+    let spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let status = match &span.error {
+                Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+                None => json!({ "code": STATUS_OK }),
+            };
+            json!({
+                "traceId": span.trace_id,
+                "spanId": span.span_id,
+                "parentSpanId": span.parent_span_id.clone().unwrap_or_default(),
+                "name": span.name,
+                "kind": SPAN_KIND_INTERNAL,
+                "startTimeUnixNano": span.start_ns.to_string(),
+                "endTimeUnixNano": span.end_ns.to_string(),
+                "attributes": span.attributes.iter().map(|(k, v)| attribute(k, v)).collect::<Vec<_>>(),
+                "status": status,
+            })
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [attribute("service.name", service_name)] },
+            "scopeSpans": [{ "scope": { "name": "chainfud" }, "spans": spans }],
+        }]
+    })
+}
+
+impl Tracer {
+    pub fn new(config: Option<&TelemetryConfig>) -> Self {
+        let Some(config) = config else {
+            return Tracer::default();
+        };
+        Tracer {
+            exporter: Some(Arc::new(Exporter {
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(EXPORT_TIMEOUT_SECS))
+                    .build()
+                    .unwrap_or_default(),
+                url: traces_url(&config.endpoint),
+                headers: config.headers.as_ref().and_then(|h| parse_headers(h.expose())).unwrap_or_default(),
+                service_name: config.service_name.clone(),
+                active: Mutex::new(None),
+            })),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.exporter.is_some()
+    }
+
+    // Begin a cycle; stages traced until finish_trace become its children
+    pub fn start_trace(&self, name: &str) {
+        let Some(exporter) = &self.exporter else {
+            return;
+        };
+        let mut active = exporter.active.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *active = Some(ActiveTrace {
+            trace_id: random_id(16),
+            root_span_id: random_id(8),
+            name: name.to_string(),
+            start_ns: now_ns(),
+            spans: Vec::new(),
+        });
+    }
+
+    // Close the cycle and ship its spans in the background
+    pub fn finish_trace<E: Display>(&self, error: Option<&E>) {
+        let Some(exporter) = &self.exporter else {
+            return;
+        };
+        let finished = exporter.active.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        let Some(trace) = finished else {
+            return;
+        };
+        let mut spans = trace.spans;
+        spans.push(SpanRecord {
+            trace_id: trace.trace_id,
+            span_id: trace.root_span_id,
+            parent_span_id: None,
+            name: trace.name,
+            start_ns: trace.start_ns,
+            end_ns: now_ns(),
+            attributes: Vec::new(),
+            error: error.map(|e| e.to_string()),
+        });
+        Self::export(exporter.clone(), spans);
+    }
+
+    // Time a stage. Inside a cycle it becomes a child of the cycle's root span;
+    // outside one it's exported on its own.
+    pub async fn in_span<T, E, F>(&self, name: &str, attributes: &[(&str, String)], stage: F) -> Result<T, E>
+    where
+        E: Display,
+        F: Future<Output = Result<T, E>>,
+    {
+        let Some(exporter) = &self.exporter else {
+            return stage.await;
+        };
+        let start_ns = now_ns();
+        let result = stage.await;
+        let mut span = SpanRecord {
+            trace_id: String::new(),
+            span_id: random_id(8),
+            parent_span_id: None,
+            name: name.to_string(),
+            start_ns,
+            end_ns: now_ns(),
+            attributes: attributes.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+
+        let mut active = exporter.active.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match active.as_mut() {
+            Some(trace) => {
+                span.trace_id = trace.trace_id.clone();
+                span.parent_span_id = Some(trace.root_span_id.clone());
+                trace.spans.push(span);
+            }
+            None => {
+                drop(active);
+                span.trace_id = random_id(16);
+                Self::export(exporter.clone(), vec![span]);
+            }
+        }
+        result
+    }
+
+    // Failures are logged and dropped; tracing never holds up posting
+    fn export(exporter: Arc<Exporter>, spans: Vec<SpanRecord>) {
+        tokio::spawn(async move {
+            let mut request = exporter
+                .client
+                .post(&exporter.url)
+                .json(&otlp_body(&exporter.service_name, &spans));
+            for (key, value) in &exporter.headers {
+                request = request.header(key.as_str(), value.as_str());
+            }
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    eprintln!("OTLP export failed with status: {}", response.status());
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("OTLP export failed: {}", e),
+            }
+        });
+    }
+}
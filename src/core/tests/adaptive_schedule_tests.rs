@@ -0,0 +1,52 @@
+// src/core/tests/adaptive_schedule_tests.rs
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use super::super::adaptive_schedule::{AdaptiveSchedule, IntervalChange};
+use super::super::character::AdaptiveScheduleSettings;
+use crate::models::{Memory, Tweet, TweetMetrics, TweetType};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+}
+
+fn post(minutes_ago: i64, engagement: Option<u64>, tweet_type: TweetType) -> Tweet {
+    Tweet {
+        twitter_id: Some("1".to_string()),
+        text: "dev is selling".to_string(),
+        timestamp: now() - Duration::minutes(minutes_ago),
+        tweet_type,
+        metrics: engagement.map(|likes| TweetMetrics { likes, ..Default::default() }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_recent_engagement_uses_settled_original_posts() {
+    let settings = AdaptiveScheduleSettings { lookback_posts: 3, ..Default::default() };
+    let mut memory = Memory::default();
+    memory.tweets.push(post(600, Some(100), TweetType::Original));
+    memory.tweets.push(post(300, Some(4), TweetType::Original));
+    memory.tweets.push(post(240, None, TweetType::Original));
+    memory.tweets.push(post(200, Some(2), TweetType::Original));
+    memory.tweets.push(post(180, Some(500), TweetType::Reply));
+    memory.tweets.push(post(120, Some(0), TweetType::Original));
+    // Too fresh to judge
+    memory.tweets.push(post(10, Some(0), TweetType::Original));
+
+    assert_eq!(AdaptiveSchedule::recent_engagement(&settings, &memory, now()), Some(2.0));
+
+    memory.tweets.drain(..2);
+    assert_eq!(AdaptiveSchedule::recent_engagement(&settings, &memory, now()), None);
+}
+
+#[test]
+fn test_interval_widens_and_tightens_within_bounds() {
+    let settings = AdaptiveScheduleSettings::default();
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 30, Some(0.5)), (45, IntervalChange::Widened));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 150, Some(0.0)), (180, IntervalChange::Widened));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 180, Some(0.0)), (180, IntervalChange::Unchanged));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 45, Some(60.0)), (30, IntervalChange::Tightened));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 20, Some(60.0)), (15, IntervalChange::Tightened));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 45, Some(10.0)), (45, IntervalChange::Unchanged));
+    assert_eq!(AdaptiveSchedule::next_interval(&settings, 45, None), (45, IntervalChange::Unchanged));
+}
@@ -0,0 +1,44 @@
+// src/core/tests/agent_tests.rs
+
+use crate::core::agent::{Agent, ResponseDecision};
+
+#[test]
+fn test_parse_batch_decisions() {
+    let decisions = ResponseDecision::parse_batch(r#"["RESPOND", "ignore", "[RESPOND]"]"#, 3);
+    assert_eq!(
+        decisions,
+        Some(vec![ResponseDecision::Respond, ResponseDecision::Ignore, ResponseDecision::Respond])
+    );
+}
+
+#[test]
+fn test_parse_batch_tolerates_surrounding_text() {
+    let response = "Here are the decisions:\n[\"IGNORE\", true]\nLet me know if you need more.";
+    assert_eq!(
+        ResponseDecision::parse_batch(response, 2),
+        Some(vec![ResponseDecision::Ignore, ResponseDecision::Respond])
+    );
+}
+
+#[test]
+fn test_parse_batch_rejects_wrong_count_or_unknown_values() {
+    assert_eq!(ResponseDecision::parse_batch(r#"["RESPOND"]"#, 2), None);
+    assert_eq!(ResponseDecision::parse_batch(r#"["RESPOND", "MAYBE"]"#, 2), None);
+    assert_eq!(ResponseDecision::parse_batch("RESPOND, IGNORE", 2), None);
+}
+
+#[test]
+fn test_message_text_joins_text_blocks() {
+    let response = serde_json::json!({
+        "content": [
+            { "type": "text", "text": "A 1h chart of $BONK" },
+            { "type": "tool_use", "id": "x" },
+            { "type": "text", "text": "bleeding through a drawn support line." }
+        ]
+    });
+    assert_eq!(
+        Agent::message_text(&response).as_deref(),
+        Some("A 1h chart of $BONK bleeding through a drawn support line.")
+    );
+    assert_eq!(Agent::message_text(&serde_json::json!({ "content": [] })), None);
+}
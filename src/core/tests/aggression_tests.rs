@@ -0,0 +1,78 @@
+// src/core/tests/aggression_tests.rs
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use super::super::aggression::{Aggression, AggressionCommand, DEFAULT_LEVEL};
+use super::super::character::{AggressionSettings, DecorationSettings};
+use super::super::stream_guard::StreamGuard;
+use super::super::style::StyleEngine;
+
+fn settings() -> AggressionSettings {
+    AggressionSettings { tame_words: vec!["retarded".to_string()], ..AggressionSettings::default() }
+}
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(Aggression::parse_command("/aggression 3"), Some(AggressionCommand::Set(3)));
+    assert_eq!(Aggression::parse_command("/aggression@chainfud_bot RESET"), Some(AggressionCommand::Reset));
+    assert_eq!(Aggression::parse_command("aggression"), Some(AggressionCommand::Current));
+    assert_eq!(Aggression::parse_command("/aggression 0"), None);
+    assert_eq!(Aggression::parse_command("/aggression 11"), None);
+    assert_eq!(Aggression::parse_command("/aggression 3 4"), None);
+    assert_eq!(Aggression::parse_command("/character current"), None);
+}
+
+#[test]
+fn test_default_level_leaves_the_character_alone() {
+    let aggression = Aggression::new(settings(), None);
+    assert_eq!(aggression.level(), DEFAULT_LEVEL);
+    assert_eq!(aggression.tone_requirement(), None);
+    assert!(aggression.tame_words().is_empty());
+    assert_eq!(aggression.emoji_scale(), 1.0);
+}
+
+#[test]
+fn test_dialing_down_tames_tone_words_and_emojis() {
+    let mut aggression = Aggression::new(settings(), None);
+    aggression.set_override(Some(3));
+    assert!(aggression.tone_requirement().is_some_and(|tone| tone.contains("nothing personal")));
+    assert_eq!(aggression.tame_words(), ["retarded".to_string()]);
+    assert_eq!(aggression.emoji_scale(), 0.0);
+
+    aggression.set_override(Some(10));
+    assert!(aggression.tone_requirement().is_some_and(|tone| tone.contains("savage")));
+    assert!(aggression.tame_words().is_empty());
+    assert!(aggression.emoji_scale() > 1.0);
+}
+
+#[test]
+fn test_override_survives_a_character_swap_until_reset() {
+    let mut aggression = Aggression::new(settings(), Some(2));
+    aggression.set_settings(AggressionSettings { level: 9, ..AggressionSettings::default() });
+    assert_eq!(aggression.level(), 2);
+    assert!(aggression.describe().contains("the character's is 9"));
+    aggression.set_override(None);
+    assert_eq!(aggression.level(), 9);
+}
+
+#[test]
+fn test_tame_words_join_the_stream_guard() {
+    let guard = StreamGuard::new(["rug"]).with_terms(&["Retarded".to_string()]);
+    assert_eq!(guard.violation("this is retarded"), Some("retarded"));
+    assert_eq!(guard.violation("classic rug"), Some("rug"));
+}
+
+#[test]
+fn test_emoji_scale_zero_adds_no_emojis() {
+    let mut style = StyleEngine::default().with_decoration(DecorationSettings {
+        emojis: vec!["🤡".to_string()],
+        emoji_chance: 1.0,
+        punctuation_chance: 0.0,
+        ..DecorationSettings::default()
+    });
+    style.set_emoji_scale(0.0);
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        assert_eq!(style.decorate_with("dev sold again", &mut rng), "dev sold again");
+    }
+}
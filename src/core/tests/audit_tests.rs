@@ -0,0 +1,64 @@
+// src/core/tests/audit_tests.rs
+
+use chrono::{NaiveDate, Utc};
+use std::fs;
+use super::super::audit::{AuditEntry, AuditLog};
+
+fn completion(prompt: &str) -> AuditEntry {
+    AuditEntry::Completion {
+        id: "abc".to_string(),
+        at: Utc::now(),
+        task: "Fud".to_string(),
+        model: "claude-3-haiku-20240307".to_string(),
+        preamble: "you are a fud bot".to_string(),
+        prompt: prompt.to_string(),
+        response: Some("ngmi".to_string()),
+        error: None,
+        latency_ms: 420,
+    }
+}
+
+#[test]
+fn test_file_names_per_day_and_part() {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    assert_eq!(AuditLog::file_name(date, 0), "2024-05-01.jsonl");
+    assert_eq!(AuditLog::file_name(date, 2), "2024-05-01.2.jsonl");
+}
+
+#[test]
+fn test_entries_round_trip_as_tagged_lines() {
+    let transform = AuditEntry::Transform {
+        id: "abc".to_string(),
+        at: Utc::now(),
+        step: "style_vary".to_string(),
+        before: "just another rug".to_string(),
+        after: "bruh just another rug".to_string(),
+    };
+    let line = serde_json::to_string(&transform).unwrap();
+    assert!(line.starts_with(r#"{"kind":"transform""#));
+    assert_eq!(serde_json::from_str::<AuditEntry>(&line).unwrap(), transform);
+}
+
+#[test]
+fn test_rotates_to_a_new_part_when_full() {
+    let dir = std::env::temp_dir().join(format!("chainfud-audit-{}", rand::random::<u64>()));
+    let audit = AuditLog::in_dir(&dir, 200);
+    for i in 0..3 {
+        audit.record(&completion(&format!("prompt {}", i)));
+    }
+
+    let today = Utc::now().date_naive();
+    let first = fs::read_to_string(dir.join(AuditLog::file_name(today, 0))).unwrap();
+    assert_eq!(first.lines().count(), 1);
+    let second = fs::read_to_string(dir.join(AuditLog::file_name(today, 1))).unwrap();
+    assert!(second.contains("prompt 1"));
+    assert!(dir.join(AuditLog::file_name(today, 2)).exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_disabled_log_writes_nothing() {
+    let audit = AuditLog::default();
+    assert!(!audit.is_enabled());
+    audit.record(&completion("ignored"));
+}
@@ -0,0 +1,80 @@
+// src/core/tests/backtest_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::backtest::Backtest;
+use super::super::character::TargetSelectionSettings;
+use crate::models::{TokenSnapshot, TrendingSnapshot};
+
+fn token(mint: &str, price_usd: f64, market_cap: f64) -> TokenSnapshot {
+    TokenSnapshot {
+        mint: mint.to_string(),
+        symbol: mint.to_uppercase(),
+        price_usd,
+        market_cap,
+        liquidity_usd: 50_000.0,
+        change_24h: None,
+        ..Default::default()
+    }
+}
+
+fn snapshots() -> Vec<TrendingSnapshot> {
+    let start = Utc::now() - Duration::days(5);
+    vec![
+        TrendingSnapshot {
+            timestamp: start,
+            tokens: vec![token("small", 1.0, 500_000.0), token("big", 1.0, 50_000_000.0)],
+        },
+        TrendingSnapshot {
+            timestamp: start + Duration::hours(25),
+            tokens: vec![token("small", 0.5, 250_000.0), token("big", 1.2, 60_000_000.0)],
+        },
+        TrendingSnapshot {
+            timestamp: start + Duration::hours(73),
+            tokens: vec![token("small", 0.2, 100_000.0)],
+        },
+    ]
+}
+
+#[test]
+fn test_filters_change_selection() {
+    let filters = TargetSelectionSettings {
+        max_market_cap: Some(1_000_000.0),
+        ..Default::default()
+    };
+    let report = Backtest::run(&snapshots(), &filters);
+
+    assert_eq!(report.snapshots, 3);
+    assert_eq!(report.trending_seen, 5);
+    assert_eq!(report.selected, 3);
+
+    let day = &report.horizons[0];
+    assert_eq!(day.hours, 24);
+    assert_eq!(day.selected.measured, 1);
+    assert_eq!(day.selected.avg_change_percent, Some(-50.0));
+    assert_eq!(day.selected.dumped_percent, Some(100.0));
+    assert_eq!(day.baseline.measured, 2);
+    let baseline_change = day.baseline.avg_change_percent.unwrap();
+    assert!((baseline_change + 15.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_longer_horizon_uses_later_snapshot() {
+    let report = Backtest::run(&snapshots(), &TargetSelectionSettings::default());
+
+    let three_days = &report.horizons[1];
+    assert_eq!(three_days.hours, 72);
+    assert_eq!(three_days.baseline.measured, 1);
+    let change = three_days.baseline.avg_change_percent.unwrap();
+    assert!((change + 80.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_momentum_filter_skips_tokens_without_data() {
+    let filters = TargetSelectionSettings {
+        min_change_24h: Some(50.0),
+        ..Default::default()
+    };
+    let report = Backtest::run(&snapshots(), &filters);
+    assert_eq!(report.selected, 0);
+    assert_eq!(report.horizons[0].selected.measured, 0);
+}
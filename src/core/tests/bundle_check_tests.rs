@@ -0,0 +1,69 @@
+// src/core/tests/bundle_check_tests.rs
+
+use std::collections::HashSet;
+
+use super::super::bundle_check::{BundleCheck, BundleReport};
+use super::super::character::BundleCheckSettings;
+use crate::providers::solana_rpc::{SignatureInfo, TokenBalanceChange};
+
+fn signature(signature: &str, slot: u64, failed: bool) -> SignatureInfo {
+    SignatureInfo { signature: signature.to_string(), slot, failed }
+}
+
+fn change(owner: &str, amount: f64) -> TokenBalanceChange {
+    TokenBalanceChange { owner: owner.to_string(), amount }
+}
+
+#[test]
+fn test_launch_window_is_the_first_slots_oldest_first() {
+    // Newest first, as the RPC returns them
+    let signatures = vec![
+        signature("late", 105, false),
+        signature("second_block", 101, false),
+        signature("failed", 100, true),
+        signature("bundle", 100, false),
+        signature("create", 100, false),
+    ];
+    let window: Vec<&str> = BundleCheck::launch_window(&signatures, 1, 10).iter().map(|info| info.signature.as_str()).collect();
+    assert_eq!(window, ["create", "bundle"]);
+    assert_eq!(BundleCheck::launch_window(&signatures, 2, 10).len(), 3);
+    let first: Vec<&str> = BundleCheck::launch_window(&signatures, 2, 1).iter().map(|info| info.signature.as_str()).collect();
+    assert_eq!(first, ["create"]);
+    assert!(BundleCheck::launch_window(&[], 1, 10).is_empty());
+}
+
+#[test]
+fn test_launch_buys_leave_out_the_curve_and_early_sellers() {
+    let transactions = vec![
+        ("create".to_string(), vec![change("curve", 1_000_000_000.0)]),
+        ("buy1".to_string(), vec![change("curve", -100_000_000.0), change("dev", 100_000_000.0)]),
+        ("buy2".to_string(), vec![change("curve", -50_000_000.0), change("w1", 50_000_000.0)]),
+        ("buy3".to_string(), vec![change("curve", -50_000_000.0), change("flipper", 50_000_000.0)]),
+        ("sell".to_string(), vec![change("flipper", -50_000_000.0), change("curve", 50_000_000.0)]),
+    ];
+    let buys = BundleCheck::launch_buys(&transactions);
+    let wallets: Vec<&str> = buys.iter().map(|buy| buy.wallet.as_str()).collect();
+    assert_eq!(wallets, ["dev", "w1"]);
+    assert_eq!(buys[1].signature, "buy2");
+}
+
+#[test]
+fn test_report_needs_enough_wallets_and_supply() {
+    let settings = BundleCheckSettings::default();
+    let transactions: Vec<(String, Vec<TokenBalanceChange>)> = (0..12)
+        .map(|i| (format!("sig{}", i), vec![change(&format!("w{}", i), 28_333_333.4)]))
+        .collect();
+    let buys = BundleCheck::launch_buys(&transactions);
+    let fresh: HashSet<String> = (0..9).map(|i| format!("w{}", i)).collect();
+
+    let report = BundleCheck::report(&settings, &buys, &fresh, 1_000_000_000.0).unwrap();
+    assert_eq!(report, BundleReport { launch_slots: 1, wallets: 12, fresh_wallets: 9, supply_pct: report.supply_pct });
+    assert!((report.supply_pct - 34.0).abs() < 0.01);
+    assert_eq!(report.summary(), "Bundle check: 34.0% of supply bought in the launch block by 12 wallets, 9 of them fresh wallets\n");
+
+    // Two buyers is a dev and a friend, not a bundle
+    assert!(BundleCheck::report(&settings, &buys[..2], &fresh, 1_000_000_000.0).is_none());
+    // A big supply makes the same buys a rounding error
+    assert!(BundleCheck::report(&settings, &buys, &fresh, 100_000_000_000.0).is_none());
+    assert!(BundleCheck::report(&settings, &buys, &fresh, 0.0).is_none());
+}
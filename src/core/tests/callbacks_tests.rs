@@ -0,0 +1,91 @@
+// src/core/tests/callbacks_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::callbacks::{Bm25, Callbacks};
+use super::super::character::CallbackSettings;
+use crate::models::{Tweet, TweetMetrics};
+
+fn post(id: &str, text: &str, context: &str, mint: &str, likes: u64, days_ago: i64) -> Tweet {
+    let now = Utc::now();
+    Tweet {
+        twitter_id: Some(id.to_string()),
+        text: text.to_string(),
+        timestamp: now - Duration::days(days_ago),
+        metrics: Some(TweetMetrics { likes, fetched_at: now, ..Default::default() }),
+        context: Some(context.to_string()),
+        token_mint: Some(mint.to_string()),
+        ..Default::default()
+    }
+}
+
+fn history() -> Vec<Tweet> {
+    vec![
+        post("1", "dev unlocked the LP and went to get milk", "Name: Frog. LP unlocked, dev wallet sold 40% of supply", "frog", 120, 20),
+        post("2", "another dog coin, another bagholder convention", "Name: Shiba Two. Dog themed meme, 3000 holders", "shib2", 300, 10),
+        post("3", "chart looks like a ski slope", "Name: Cat. Down 80% in 24h, bonding curve at 40%", "cat", 80, 15),
+        post("4", "dev sold into the LP unlock lol", "Name: Toad. LP unlocked yesterday, dev wallet dumping", "toad", 5, 12),
+    ]
+}
+
+#[test]
+fn test_tokenize_drops_noise() {
+    assert_eq!(Bm25::tokenize("The LP unlocked: 40% of $FROG supply, market cap $1.2M"), vec!["unlocked", "frog", "supply"]);
+}
+
+#[test]
+fn test_bm25_ranks_the_closer_document_first() {
+    let documents = vec![
+        "dev unlocked the liquidity and sold".to_string(),
+        "dog themed meme coin with many holders".to_string(),
+        "the unlock came and the dev sold everything".to_string(),
+    ];
+    let index = Bm25::new(&documents);
+    let results = index.search("dev sold after the unlock");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, 2);
+    assert!(index.search("completely unrelated words").is_empty());
+}
+
+#[test]
+fn test_finds_a_well_received_post_about_a_similar_situation() {
+    let settings = CallbackSettings { min_score: 1.0, ..Default::default() };
+    let history = history();
+    let now = Utc::now();
+    let situation = "Name: Pepe. LP unlocked an hour ago, dev wallet sold 25% of supply";
+
+    let found = Callbacks::find(&history, situation, "pepe", &settings, now).unwrap();
+    // Post 4 matches too but barely got any engagement
+    assert_eq!(found.twitter_id.as_deref(), Some("1"));
+
+    // Never the same token, whose history past takes already covers
+    let found = Callbacks::find(&history, situation, "frog", &settings, now);
+    assert!(found.is_none_or(|tweet| tweet.twitter_id.as_deref() != Some("1")));
+
+    let section = Callbacks::prompt_section(&history[0], now);
+    assert!(section.starts_with("A past post of yours about a similar situation that landed well (20 days ago, 120 likes): 'dev unlocked the LP"));
+}
+
+#[test]
+fn test_recent_and_unmatched_posts_are_left_out() {
+    let now = Utc::now();
+    let situation = "Name: Pepe. LP unlocked an hour ago, dev wallet sold 25% of supply";
+    let too_soon = CallbackSettings { min_score: 1.0, min_age_days: 30, lookback_days: 90, ..Default::default() };
+    assert!(Callbacks::find(&history(), situation, "pepe", &too_soon, now).is_none());
+
+    let settings = CallbackSettings { min_score: 1.0, ..Default::default() };
+    assert!(Callbacks::find(&history(), "Name: Moon. Airdrop season, staking rewards", "moon", &settings, now).is_none());
+}
+
+#[test]
+fn test_callback_sections_in_saved_contexts_are_not_matched() {
+    let now = Utc::now();
+    let settings = CallbackSettings { min_score: 1.0, ..Default::default() };
+    let section = Callbacks::prompt_section(&history()[0], now);
+    let context = format!("Name: Shiba Two. Dog themed meme\n{}Bonding curve at 90%", section);
+    assert_eq!(Callbacks::base_context(&context), "Name: Shiba Two. Dog themed meme\nBonding curve at 90%");
+
+    // Only the callback section it was written with mentions the LP unlock
+    let history = vec![post("5", "woof woof, bags", &context, "shib2", 300, 10)];
+    let situation = "Name: Pepe. LP unlocked an hour ago, dev wallet sold 25% of supply";
+    assert!(Callbacks::find(&history, situation, "pepe", &settings, now).is_none());
+}
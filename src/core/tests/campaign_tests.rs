@@ -0,0 +1,86 @@
+// src/core/tests/campaign_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::campaign::{CampaignCommand, Campaigns};
+use crate::models::{Campaign, TokenSnapshot};
+
+const MINT: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+fn token(price_usd: f64) -> TokenSnapshot {
+    TokenSnapshot {
+        mint: MINT.to_string(),
+        symbol: "RUG".to_string(),
+        price_usd,
+        market_cap: price_usd * 1_000_000_000.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(
+        Campaigns::parse_command(&format!("/campaign@chainfud_bot start {} 48h", MINT)),
+        Some(CampaignCommand::Start { mint: MINT.to_string(), hours: Some(48) })
+    );
+    assert_eq!(
+        Campaigns::parse_command(&format!("campaign start {}", MINT)),
+        Some(CampaignCommand::Start { mint: MINT.to_string(), hours: None })
+    );
+    assert_eq!(Campaigns::parse_command("/campaign stop"), Some(CampaignCommand::Stop));
+    assert_eq!(Campaigns::parse_command("/campaign"), Some(CampaignCommand::Status));
+    assert_eq!(Campaigns::parse_command("/campaign start not-a-mint"), None);
+    assert_eq!(Campaigns::parse_command(&format!("/campaign start {} 0", MINT)), None);
+    assert_eq!(Campaigns::parse_command("/aggression 3"), None);
+}
+
+#[test]
+fn test_observe_tracks_low_high_and_change() {
+    let now = Utc::now();
+    let mut campaign = Campaign::start(&token(1.0), 24, "cli", now);
+    campaign.observe(&token(0.5), now + Duration::hours(1));
+    campaign.observe(&token(1.5), now + Duration::hours(2));
+    campaign.observe(&token(0.8), now + Duration::hours(3));
+    // A zero from the API doesn't count as a price
+    campaign.observe(&token(0.0), now + Duration::hours(4));
+
+    assert_eq!(campaign.low_price, 0.5);
+    assert_eq!(campaign.high_price, 1.5);
+    assert!((campaign.change_pct() + 20.0).abs() < 1e-9);
+    assert_eq!(campaign.last_checked_at, Some(now + Duration::hours(4)));
+    assert!(!campaign.price_check_due(Duration::minutes(30), now + Duration::hours(4)));
+    assert!(campaign.price_check_due(Duration::minutes(30), now + Duration::minutes(270)));
+}
+
+#[test]
+fn test_mentions() {
+    let mut campaign = Campaign::start(&token(1.0), 24, "cli", Utc::now());
+    assert!(campaign.mentioned_in("what do you think of $rug?"));
+    assert!(campaign.mentioned_in(&format!("ca: {}", MINT)));
+    assert!(!campaign.mentioned_in("$RUGGED is different"));
+    assert!(!campaign.mentioned_in("rug pull incoming"));
+
+    assert!(campaign.record_mention("1"));
+    assert!(!campaign.record_mention("1"));
+    assert_eq!(campaign.mentions.len(), 1);
+}
+
+#[test]
+fn test_running_and_over() {
+    let now = Utc::now();
+    let mut finished = Campaign::start(&token(1.0), 24, "cli", now - Duration::days(3));
+    finished.finished_at = Some(now - Duration::days(2));
+    let mut campaigns = vec![finished, Campaign::start(&token(1.0), 24, "cli", now)];
+
+    assert_eq!(Campaigns::current(&campaigns), Some(1));
+    assert!(Campaigns::running(&campaigns, now + Duration::hours(1)).is_some());
+
+    // Past its end it's no longer running, but stays current until the recap
+    let later = now + Duration::hours(25);
+    assert!(Campaigns::running(&campaigns, later).is_none());
+    assert!(campaigns[1].is_over(later));
+    assert_eq!(Campaigns::current(&campaigns), Some(1));
+
+    campaigns[1].finished_at = Some(later);
+    assert_eq!(Campaigns::current(&campaigns), None);
+    assert!(!campaigns[1].is_over(later));
+}
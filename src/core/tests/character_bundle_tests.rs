@@ -0,0 +1,151 @@
+// src/core/tests/character_bundle_tests.rs
+
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::super::character_bundle::{CharacterBundle, BUNDLE_SCHEMA_VERSION, IMPORTED_EXAMPLES_FILE};
+use super::super::instruction_builder::{ExampleRotation, EXAMPLES_DIR};
+use super::super::paths::Layout;
+
+fn temp_home() -> PathBuf {
+    let home = std::env::temp_dir().join(format!("chainfud-bundle-{}", rand::random::<u64>()));
+    fs::create_dir_all(&home).unwrap();
+    home
+}
+
+fn fud_bundle() -> CharacterBundle {
+    CharacterBundle::export(&Layout::new("."), "fud", Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()).unwrap()
+}
+
+#[test]
+fn test_export_splits_character_into_sections() {
+    let mut character: Value = serde_json::from_str(&fs::read_to_string("characters/fud/character.json").unwrap()).unwrap();
+    character["banned_words"] = json!(["moon"]);
+    character["content_schedule"] = json!([]);
+    let bundle = CharacterBundle::from_character("fud", character.clone(), Utc::now()).unwrap();
+
+    assert_eq!(bundle.schema_version, BUNDLE_SCHEMA_VERSION);
+    assert!(bundle.prompt.contains_key("instructions"));
+    assert!(bundle.traits.contains_key("lore"));
+    assert_eq!(bundle.post_examples, character["post_style_examples"].as_array().unwrap().clone());
+    assert_eq!(bundle.settings.get("banned_words"), Some(&json!(["moon"])));
+    assert!(bundle.settings.contains_key("content_schedule"));
+    assert_eq!(bundle.to_character(), character);
+}
+
+#[test]
+fn test_operator_settings_stay_behind() {
+    let mut character: Value = serde_json::from_str(&fs::read_to_string("characters/fud/character.json").unwrap()).unwrap();
+    character["onchain_receipts"] = json!({ "enabled": true, "mode": "buy", "buy_lamports": 100_000_000 });
+    character["token_heat"] = json!({ "enabled": true, "store_path": "/etc/cron.d/fud" });
+    let bundle = CharacterBundle::from_character("fud", character, Utc::now()).unwrap();
+    assert!(!bundle.settings.contains_key("onchain_receipts"));
+    assert_eq!(bundle.settings["token_heat"], json!({ "enabled": true }));
+
+    let home = temp_home();
+    let layout = Layout::new(&home);
+    let mut shared = fud_bundle();
+    shared.settings.insert("storage".to_string(), json!({ "quotas_mb": { "memory": 1 } }));
+    shared.settings.insert("api_quota".to_string(), json!({ "reset_day": 3 }));
+    assert_eq!(shared.operator_settings(), vec!["storage", "api_quota"]);
+    let (path, _) = shared.import(&layout, None, false).unwrap();
+    let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(written.get("storage").is_none());
+    assert!(written.get("api_quota").is_none());
+    fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn test_bundle_round_trips_through_json() {
+    let bundle = fud_bundle();
+    assert_eq!(CharacterBundle::parse(&bundle.to_json().unwrap()).unwrap(), bundle);
+}
+
+#[test]
+fn test_parse_refuses_other_formats_and_newer_schemas() {
+    let mut bundle: Value = serde_json::from_str(&fud_bundle().to_json().unwrap()).unwrap();
+    bundle["schema_version"] = json!(BUNDLE_SCHEMA_VERSION + 1);
+    assert!(CharacterBundle::parse(&bundle.to_string()).is_err());
+
+    bundle["schema_version"] = json!(BUNDLE_SCHEMA_VERSION);
+    bundle["format"] = json!("something-else");
+    assert!(CharacterBundle::parse(&bundle.to_string()).is_err());
+
+    let character = fs::read_to_string("characters/fud/character.json").unwrap();
+    assert!(CharacterBundle::parse(&character).is_err());
+}
+
+#[test]
+fn test_parse_upgrades_version_one_bundles() {
+    let mut bundle: Value = serde_json::from_str(&fud_bundle().to_json().unwrap()).unwrap();
+    bundle["schema_version"] = json!(1);
+    bundle.as_object_mut().unwrap().remove("examples");
+    let parsed = CharacterBundle::parse(&bundle.to_string()).unwrap();
+    assert_eq!(parsed.schema_version, BUNDLE_SCHEMA_VERSION);
+    assert!(parsed.examples.is_empty());
+
+    bundle["schema_version"] = json!(0);
+    assert!(CharacterBundle::parse(&bundle.to_string()).is_err());
+}
+
+#[test]
+fn test_import_writes_character_and_refuses_to_overwrite() {
+    let home = temp_home();
+    let layout = Layout::new(&home);
+    let bundle = fud_bundle();
+
+    let (path, report) = bundle.import(&layout, Some("shared"), false).unwrap();
+    assert!(report.is_ok());
+    assert_eq!(path, layout.character_file("shared"));
+    let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written, bundle.to_character());
+
+    assert!(bundle.import(&layout, Some("shared"), false).is_err());
+    assert!(bundle.import(&layout, Some("shared"), true).is_ok());
+    assert!(bundle.import(&layout, Some("../escape"), false).is_err());
+    fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn test_bundle_carries_example_posts() {
+    let home = temp_home();
+    let source = Layout::new(&home);
+    let dir = source.characters_dir().join("fud");
+    fs::create_dir_all(dir.join(EXAMPLES_DIR)).unwrap();
+    fs::copy("characters/fud/character.json", dir.join("character.json")).unwrap();
+    fs::write(dir.join(EXAMPLES_DIR).join("posts.txt"), "dev is selling\n\nanother rug").unwrap();
+
+    let bundle = CharacterBundle::export(&source, "fud", Utc::now()).unwrap();
+    assert_eq!(bundle.examples, vec!["dev is selling", "another rug"]);
+
+    let (path, _) = bundle.import(&source, Some("copy"), false).unwrap();
+    let examples = path.parent().unwrap().join(EXAMPLES_DIR);
+    let imported = fs::read_to_string(examples.join(IMPORTED_EXAMPLES_FILE)).unwrap();
+    assert_eq!(imported, "dev is selling\n\nanother rug\n");
+
+    // Replacing a character replaces its examples too
+    fs::write(examples.join("old.txt"), "stale post").unwrap();
+    fs::write(examples.join("notes.md"), "kept").unwrap();
+    let mut replacement = bundle.clone();
+    replacement.examples = vec!["fresh post".to_string()];
+    replacement.import(&source, Some("copy"), true).unwrap();
+    assert_eq!(ExampleRotation::load(&examples, 0).unwrap().posts(), ["fresh post"]);
+    assert!(examples.join("notes.md").exists());
+    fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn test_import_writes_nothing_when_validation_fails() {
+    let home = temp_home();
+    let layout = Layout::new(&home);
+    let mut bundle = fud_bundle();
+    bundle.settings.insert("timezone".to_string(), json!("Mars/Olympus"));
+
+    let (path, report) = bundle.import(&layout, None, false).unwrap();
+    assert!(!report.is_ok());
+    assert!(!path.exists());
+    assert!(!layout.characters_dir().join("fud").exists());
+    fs::remove_dir_all(&home).unwrap();
+}
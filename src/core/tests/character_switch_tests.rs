@@ -0,0 +1,26 @@
+// src/core/tests/character_switch_tests.rs
+
+use super::super::character_switch::{CharacterCommand, CharacterSwitch};
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(CharacterSwitch::parse_command("character use degen"), Some(CharacterCommand::Use("degen".to_string())));
+    assert_eq!(
+        CharacterSwitch::parse_command("/character@chainfud_bot USE fud_v2"),
+        Some(CharacterCommand::Use("fud_v2".to_string()))
+    );
+    assert_eq!(CharacterSwitch::parse_command("/character"), Some(CharacterCommand::Current));
+    assert_eq!(CharacterSwitch::parse_command("/character current"), Some(CharacterCommand::Current));
+    assert_eq!(CharacterSwitch::parse_command("/character use"), None);
+    assert_eq!(CharacterSwitch::parse_command("/character use a b"), None);
+    assert_eq!(CharacterSwitch::parse_command("wen moon"), None);
+}
+
+#[test]
+fn test_character_names_stay_inside_characters_dir() {
+    assert!(CharacterSwitch::is_valid_name("fud"));
+    assert!(CharacterSwitch::is_valid_name("fud-v2_test"));
+    assert!(!CharacterSwitch::is_valid_name(""));
+    assert!(!CharacterSwitch::is_valid_name("../secrets"));
+    assert!(!CharacterSwitch::is_valid_name("fud/extra"));
+}
@@ -0,0 +1,140 @@
+// src/core/tests/clock_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::character::{CharacterSettings, QuietHoursSettings};
+use super::super::clock::{Clock, MockClock, Schedule, FUD_SCHEDULE_MINUTES};
+use super::super::local_time::LocalTime;
+
+fn clock(hour: u32, minute: u32, second: u32) -> MockClock {
+    MockClock::new(Utc.with_ymd_and_hms(2024, 5, 1, hour, minute, second).unwrap())
+}
+
+#[test]
+fn test_mock_clock_only_moves_when_told() {
+    let clock = clock(12, 0, 0);
+    let start = clock.now();
+    assert_eq!(clock.now(), start);
+    clock.advance(Duration::seconds(90));
+    assert_eq!(clock.now(), start + Duration::seconds(90));
+    clock.set(start);
+    assert_eq!(clock.now(), start);
+}
+
+#[test]
+fn test_next_minute_mark() {
+    let now = clock(12, 14, 59).now();
+    let at = |hour, minute| Some(Utc.with_ymd_and_hms(2024, 5, 1, hour, minute, 0).unwrap());
+    assert_eq!(Schedule::next_minute_mark(FUD_SCHEDULE_MINUTES, now), at(12, 15));
+    assert_eq!(Schedule::next_minute_mark(FUD_SCHEDULE_MINUTES, at(12, 15).unwrap()), at(12, 30));
+    assert_eq!(Schedule::next_minute_mark(&[5], now), at(13, 5));
+    assert_eq!(Schedule::next_minute_mark(&[], now), None);
+}
+
+#[test]
+fn test_minute_mark_fires_once_per_cycle() {
+    let clock = clock(12, 14, 59);
+    let mut since = clock.now();
+    clock.advance(Duration::milliseconds(400));
+    assert!(!Schedule::mark_between(FUD_SCHEDULE_MINUTES, since, clock.now()));
+    since = clock.now();
+    clock.advance(Duration::milliseconds(700));
+    assert!(Schedule::mark_between(FUD_SCHEDULE_MINUTES, since, clock.now()));
+    since = clock.now();
+    clock.advance(Duration::seconds(1));
+    assert!(!Schedule::mark_between(FUD_SCHEDULE_MINUTES, since, clock.now()));
+    assert!(!Schedule::mark_between(&[], since, clock.now()));
+}
+
+#[test]
+fn test_mark_missed_by_a_slow_cycle_still_fires() {
+    let clock = clock(12, 14, 30);
+    let since = clock.now();
+    // The previous cycle ran through the whole 12:15 minute
+    clock.advance(Duration::seconds(95));
+    assert!(Schedule::mark_between(FUD_SCHEDULE_MINUTES, since, clock.now()));
+}
+
+#[test]
+fn test_next_wake_takes_the_earliest_upcoming_event() {
+    let clock = clock(12, 14, 30);
+    let now = clock.now();
+    let idle = Duration::seconds(60);
+    let mark = Schedule::next_minute_mark(FUD_SCHEDULE_MINUTES, now);
+    assert_eq!(Schedule::next_wake(now, [mark, Some(now + Duration::minutes(3))], idle), mark.unwrap());
+    assert_eq!(Schedule::next_wake(now, [Some(now + Duration::seconds(10)), mark], idle), now + Duration::seconds(10));
+    // Overdue or missing events leave the idle cap
+    assert_eq!(Schedule::next_wake(now, [None, Some(now - Duration::minutes(1)), Some(now)], idle), now + idle);
+    assert_eq!(Schedule::next_wake(now, [Some(now + Duration::minutes(5))], idle), now + idle);
+}
+
+#[test]
+fn test_cooldown_elapses_after_the_interval() {
+    let clock = clock(12, 0, 0);
+    let interval = Duration::minutes(5);
+    assert!(Schedule::elapsed(None, interval, clock.now()));
+
+    let last = Some(clock.now());
+    clock.advance(Duration::minutes(4) + Duration::seconds(59));
+    assert!(!Schedule::elapsed(last, interval, clock.now()));
+    clock.advance(Duration::seconds(1));
+    assert!(Schedule::elapsed(last, interval, clock.now()));
+}
+
+#[test]
+fn test_quiet_hours_within_a_day() {
+    let settings = QuietHoursSettings { enabled: true, start_hour: 4, end_hour: 10 };
+    let clock = clock(3, 59, 59);
+    assert!(!Schedule::is_quiet(&settings, clock.now()));
+    clock.advance(Duration::seconds(1));
+    assert!(Schedule::is_quiet(&settings, clock.now()));
+    clock.advance(Duration::hours(6) - Duration::seconds(1));
+    assert!(Schedule::is_quiet(&settings, clock.now()));
+    clock.advance(Duration::seconds(1));
+    assert!(!Schedule::is_quiet(&settings, clock.now()));
+}
+
+#[test]
+fn test_quiet_hours_wrap_past_midnight() {
+    let settings = QuietHoursSettings { enabled: true, start_hour: 22, end_hour: 6 };
+    assert!(Schedule::is_quiet(&settings, clock(23, 30, 0).now()));
+    assert!(Schedule::is_quiet(&settings, clock(0, 0, 0).now()));
+    assert!(Schedule::is_quiet(&settings, clock(5, 59, 0).now()));
+    assert!(!Schedule::is_quiet(&settings, clock(6, 0, 0).now()));
+    assert!(!Schedule::is_quiet(&settings, clock(21, 59, 0).now()));
+}
+
+#[test]
+fn test_quiet_hours_off_or_empty_never_apply() {
+    let now = clock(5, 0, 0).now();
+    assert!(!Schedule::is_quiet(&QuietHoursSettings::default(), now));
+    let empty = QuietHoursSettings { enabled: true, start_hour: 5, end_hour: 5 };
+    assert!(!Schedule::is_quiet(&empty, now));
+}
+
+#[test]
+fn test_quiet_hours_in_the_character_timezone() {
+    let settings = QuietHoursSettings { enabled: true, start_hour: 22, end_hour: 6 };
+    let new_york = LocalTime::parse("America/New_York").unwrap();
+    // 03:30 UTC is 23:30 the evening before in New York
+    assert!(Schedule::is_quiet(&settings, new_york.at(clock(3, 30, 0).now())));
+    // 23:00 UTC is quiet in UTC but only 19:00 in New York
+    assert!(Schedule::is_quiet(&settings, clock(23, 0, 0).now()));
+    assert!(!Schedule::is_quiet(&settings, new_york.at(clock(23, 0, 0).now())));
+}
+
+#[test]
+fn test_next_post_skips_quiet_hours_and_adaptive_holds() {
+    let at = |hour, minute| Utc.with_ymd_and_hms(2024, 5, 1, hour, minute, 0).unwrap();
+    let quiet = QuietHoursSettings { enabled: true, start_hour: 4, end_hour: 10 };
+    let utc = LocalTime::default();
+    assert_eq!(Schedule::next_outside_quiet(FUD_SCHEDULE_MINUTES, &quiet, utc, at(3, 40)), Some(at(3, 45)));
+    assert_eq!(Schedule::next_outside_quiet(FUD_SCHEDULE_MINUTES, &quiet, utc, at(3, 50)), Some(at(10, 0)));
+    let all_day = QuietHoursSettings { enabled: true, start_hour: 0, end_hour: 24 };
+    assert_eq!(Schedule::next_outside_quiet(FUD_SCHEDULE_MINUTES, &all_day, utc, at(3, 50)), None);
+
+    let mut settings = CharacterSettings::default();
+    settings.adaptive_schedule.enabled = true;
+    assert_eq!(Schedule::next_scheduled_post(&settings, Some(at(13, 20)), at(12, 0)), Some(at(13, 30)));
+    // A hold that already ran out doesn't push anything back
+    assert_eq!(Schedule::next_scheduled_post(&settings, Some(at(11, 0)), at(12, 0)), Some(at(12, 15)));
+}
@@ -0,0 +1,83 @@
+// src/core/tests/consistency_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::consistency::{ConsistencyCheck, ConsistencyFinding};
+use crate::models::{AvoidedTopic, Memory, Tweet};
+
+fn tweet(text: &str, hours_ago: i64) -> Tweet {
+    let now = Utc.with_ymd_and_hms(2024, 5, 8, 12, 0, 0).unwrap();
+    Tweet {
+        internal_id: hours_ago as u64,
+        text: text.to_string(),
+        timestamp: now - Duration::hours(hours_ago),
+        ..Default::default()
+    }
+}
+
+fn finding(issue: &str, topic: Option<&str>) -> ConsistencyFinding {
+    ConsistencyFinding { posts: vec![1, 2], issue: issue.to_string(), topic: topic.map(str::to_string) }
+}
+
+#[test]
+fn test_sample_takes_the_latest_posts_oldest_first() {
+    let memory = Memory {
+        tweets: vec![tweet("first", 50), tweet("  ", 40), tweet("second", 30), tweet("third", 2)],
+        ..Memory::default()
+    };
+    let sample = ConsistencyCheck::sample(&memory, 2);
+    let texts: Vec<&str> = sample.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, vec!["second", "third"]);
+
+    let now = Utc.with_ymd_and_hms(2024, 5, 8, 12, 0, 0).unwrap();
+    assert_eq!(ConsistencyCheck::numbered(&sample, now), "1. (1 days ago) second\n2. (2 hours ago) third");
+}
+
+#[test]
+fn test_parse_reads_the_array_around_other_text() {
+    let response = r#"Here's what I found:
+[{"posts": [3, 17], "issue": "called $BONK a rug, then bullish", "topic": "$BONK"}, {"issue": "too polite"}, {"issue": " "}]"#;
+    assert_eq!(
+        ConsistencyCheck::parse(response),
+        Some(vec![
+            ConsistencyFinding { posts: vec![3, 17], issue: "called $BONK a rug, then bullish".to_string(), topic: Some("$BONK".to_string()) },
+            ConsistencyFinding { posts: vec![], issue: "too polite".to_string(), topic: None },
+        ])
+    );
+    assert_eq!(ConsistencyCheck::parse("[]"), Some(vec![]));
+    assert_eq!(ConsistencyCheck::parse("all good"), None);
+    assert_eq!(ConsistencyCheck::parse("[{\"posts\": 3}]"), None);
+}
+
+#[test]
+fn test_flag_topics_dedupes_and_expires() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 8, 12, 0, 0).unwrap();
+    let mut avoided = vec![
+        AvoidedTopic { topic: "$WIF".to_string(), reason: "old".to_string(), flagged_at: now - Duration::days(8), until: now },
+        AvoidedTopic { topic: "$bonk".to_string(), reason: "earlier".to_string(), flagged_at: now - Duration::days(1), until: now + Duration::days(1) },
+    ];
+    let until = now + Duration::days(7);
+    let findings = [
+        finding("flip-flopped on bonk", Some("$BONK")),
+        finding("praised popcat", Some(" $POPCAT ")),
+        finding("off-brand", None),
+    ];
+    assert_eq!(ConsistencyCheck::flag_topics(&mut avoided, &findings, now, until), 1);
+
+    let topics: Vec<(&str, _)> = avoided.iter().map(|t| (t.topic.as_str(), t.until)).collect();
+    assert_eq!(topics, vec![("$bonk", until), ("$POPCAT", until)]);
+    assert_eq!(avoided[0].reason, "flip-flopped on bonk");
+}
+
+#[test]
+fn test_is_avoided_matches_symbol_or_mint_until_expiry() {
+    let now = Utc::now();
+    let avoided = vec![
+        AvoidedTopic { topic: "$wif".to_string(), reason: "flip".to_string(), flagged_at: now, until: now + Duration::days(1) },
+        AvoidedTopic { topic: "MintBonk".to_string(), reason: "flip".to_string(), flagged_at: now, until: now + Duration::days(1) },
+        AvoidedTopic { topic: "POPCAT".to_string(), reason: "old".to_string(), flagged_at: now - Duration::days(8), until: now },
+    ];
+    assert!(ConsistencyCheck::is_avoided(&avoided, "WIF", "MintWif", now));
+    assert!(ConsistencyCheck::is_avoided(&avoided, "BONK", "MintBonk", now));
+    assert!(!ConsistencyCheck::is_avoided(&avoided, "POPCAT", "MintPop", now));
+    assert!(!ConsistencyCheck::is_avoided(&avoided, "WIFE", "MintWife", now));
+}
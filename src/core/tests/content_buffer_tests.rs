@@ -0,0 +1,79 @@
+// src/core/tests/content_buffer_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::character::ContentBufferSettings;
+use super::super::content::Post;
+use super::super::content_buffer::ContentBuffer;
+use super::super::outbox::OutboxItem;
+use crate::models::TokenSnapshot;
+
+fn snapshot(price_usd: f64, market_cap: f64, liquidity_usd: f64) -> TokenSnapshot {
+    TokenSnapshot {
+        mint: "MINT".to_string(),
+        symbol: "RUG".to_string(),
+        price_usd,
+        market_cap,
+        liquidity_usd,
+        ..TokenSnapshot::default()
+    }
+}
+
+fn buffered(with_snapshot: bool) -> OutboxItem {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let mut item = OutboxItem::new("FUD", &Post::text("ngmi".to_string()), now);
+    item.buffered = true;
+    item.target_snapshot = with_snapshot.then(|| snapshot(0.0012, 1_200_000.0, 80_000.0));
+    item
+}
+
+#[test]
+fn test_refresh_numbers_swaps_what_summaries_print() {
+    let then = snapshot(0.0012, 1_200_000.0, 80_000.0);
+    let now = snapshot(0.0008, 800_000.0, 45_000.0);
+    let text = "$RUG at $1.2M mcap with $80.0K liq, price $0.0012. dev is cooked";
+    assert_eq!(
+        ContentBuffer::refresh_numbers(text, &then, &now),
+        "$RUG at $800.0K mcap with $45.0K liq, price $0.0008. dev is cooked"
+    );
+}
+
+#[test]
+fn test_refresh_numbers_leaves_reworded_and_unchanged_numbers() {
+    let then = snapshot(0.0012, 1_200_000.0, 80_000.0);
+    let now = snapshot(0.0012, 1_200_000.0, 81_000.0);
+    // "$1.25M" isn't the market cap, and the model's "1.2 million" can't be matched
+    let text = "1.2 million mcap, $1.25M volume, $80.0K liq";
+    assert_eq!(ContentBuffer::refresh_numbers(text, &then, &now), "1.2 million mcap, $1.25M volume, $81.0K liq");
+}
+
+#[test]
+fn test_refresh_numbers_never_swaps_twice() {
+    // The new market cap is the old liquidity
+    let then = snapshot(0.0, 1_200_000.0, 800_000.0);
+    let now = snapshot(0.0, 800_000.0, 500_000.0);
+    assert_eq!(ContentBuffer::refresh_numbers("$1.2M mcap, $800.0K liq", &then, &now), "$800.0K mcap, $500.0K liq");
+}
+
+#[test]
+fn test_buffered_posts_age_out() {
+    let settings = ContentBufferSettings::default();
+    let item = buffered(true);
+    let created = item.created_at;
+    assert!(ContentBuffer::is_fresh(&item, &settings, created + Duration::minutes(89)));
+    assert!(!ContentBuffer::is_fresh(&item, &settings, created + Duration::minutes(90)));
+
+    assert!(!ContentBuffer::needs_refresh(&item, &settings, created + Duration::minutes(14)));
+    assert!(ContentBuffer::needs_refresh(&item, &settings, created + Duration::minutes(15)));
+    // Nothing to refresh without the numbers it was written with
+    assert!(!ContentBuffer::needs_refresh(&buffered(false), &settings, created + Duration::minutes(60)));
+}
+
+#[test]
+fn test_buffered_posts_are_not_retried_by_the_outbox() {
+    let mut item = buffered(false);
+    let later = item.created_at + Duration::minutes(30);
+    assert!(item.is_pending());
+    assert!(!item.is_due(later));
+    item.buffered = false;
+    assert!(item.is_due(later));
+}
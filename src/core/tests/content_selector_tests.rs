@@ -0,0 +1,76 @@
+// src/core/tests/content_selector_tests.rs
+
+use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use super::super::character::{CharacterSettings, ContentSlot};
+use super::super::content::ContentGenerators;
+use super::super::content_selector::ContentSelector;
+
+fn slot(start_hour: u32, end_hour: u32, fud: u32, trending_summary: u32, shitpost: u32) -> ContentSlot {
+    ContentSlot { start_hour, end_hour, fud, trending_summary, shitpost, other: HashMap::new() }
+}
+
+#[test]
+fn test_slot_lookup_wraps_midnight() {
+    let schedule = vec![slot(22, 4, 0, 0, 1), slot(12, 16, 0, 1, 0)];
+
+    assert!(ContentSelector::slot_for(&schedule, 23).is_some_and(|s| s.shitpost == 1));
+    assert!(ContentSelector::slot_for(&schedule, 3).is_some_and(|s| s.shitpost == 1));
+    assert!(ContentSelector::slot_for(&schedule, 12).is_some_and(|s| s.trending_summary == 1));
+    assert!(ContentSelector::slot_for(&schedule, 16).is_none());
+    assert!(ContentSelector::slot_for(&schedule, 4).is_none());
+}
+
+#[test]
+fn test_empty_schedule_always_fuds() {
+    let settings = CharacterSettings::default();
+    let generators = ContentGenerators::get_generators();
+    let mut rng = StdRng::seed_from_u64(7);
+    for hour in 0..24 {
+        assert_eq!(ContentSelector::pick_with(&settings, &generators, hour, &mut rng), "fud");
+    }
+}
+
+#[test]
+fn test_single_weight_slot_picks_that_kind() {
+    let settings = CharacterSettings {
+        content_schedule: vec![slot(0, 6, 0, 0, 5), slot(6, 12, 0, 2, 0)],
+        ..Default::default()
+    };
+    let generators = ContentGenerators::get_generators();
+    let mut rng = StdRng::seed_from_u64(7);
+
+    assert_eq!(ContentSelector::pick_with(&settings, &generators, 2, &mut rng), "shitpost");
+    assert_eq!(ContentSelector::pick_with(&settings, &generators, 8, &mut rng), "trending_summary");
+    assert_eq!(ContentSelector::pick_with(&settings, &generators, 20, &mut rng), "fud");
+}
+
+#[test]
+fn test_zero_weight_slot_falls_back_to_fud() {
+    let settings = CharacterSettings {
+        content_schedule: vec![slot(0, 24, 0, 0, 0)],
+        ..Default::default()
+    };
+    let generators = ContentGenerators::get_generators();
+    let mut rng = StdRng::seed_from_u64(7);
+    assert_eq!(ContentSelector::pick_with(&settings, &generators, 10, &mut rng), "fud");
+}
+
+#[test]
+fn test_registered_types_without_a_field_use_named_weights() {
+    let slot: ContentSlot = serde_json::from_str(
+        r#"{ "start_hour": 0, "end_hour": 24, "image_meme": 4 }"#
+    ).unwrap();
+    assert_eq!(slot.weight("image_meme"), 4);
+    assert_eq!(slot.weight("fud"), 0);
+    assert_eq!(slot.total_weight(), 4);
+
+    let settings = CharacterSettings {
+        content_schedule: vec![slot],
+        ..Default::default()
+    };
+    let generators = ContentGenerators::get_generators();
+    let mut rng = StdRng::seed_from_u64(7);
+    assert_eq!(ContentSelector::pick_with(&settings, &generators, 10, &mut rng), "image_meme");
+}
@@ -0,0 +1,64 @@
+// src/core/tests/dataset_tests.rs
+
+use chrono::Utc;
+use super::super::dataset::{DatasetBuilder, DatasetExample};
+use crate::models::{Memory, Tweet, TweetMetrics, TweetType};
+
+fn post(text: &str, engagement: Option<u64>, tweet_type: TweetType) -> Tweet {
+    Tweet {
+        twitter_id: Some("1".to_string()),
+        text: text.to_string(),
+        prompt: "You are a FUD bot.".to_string(),
+        timestamp: Utc::now(),
+        tweet_type,
+        metrics: engagement.map(|likes| TweetMetrics { likes, ..Default::default() }),
+        ..Default::default()
+    }
+}
+
+fn memory() -> Memory {
+    let mut memory = Memory::default();
+    memory.tweets.push(post("meh", Some(3), TweetType::Original));
+    memory.tweets.push(post("banger", Some(90), TweetType::Original));
+    memory.tweets.push(post("never fetched", None, TweetType::Original));
+    memory.tweets.push(post("viral reply", Some(500), TweetType::Reply));
+    memory.tweets.push(post("decent", Some(40), TweetType::Original));
+    memory
+}
+
+#[test]
+fn test_picks_most_engaged_posts_above_threshold() {
+    let memory = memory();
+    let texts: Vec<&str> = DatasetBuilder::top_posts(&memory, 10, 10).iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(texts, vec!["banger", "decent"]);
+
+    let top_one = DatasetBuilder::top_posts(&memory, 1, 0);
+    assert_eq!(top_one[0].text, "banger");
+}
+
+#[test]
+fn test_example_pairs_context_with_post() {
+    let mut tweet = post("$BONK dev is cooked", Some(50), TweetType::Original);
+    tweet.context = Some("$BONK mcap $2M, -40% 24h".to_string());
+
+    let example = DatasetExample::from_tweet(&tweet);
+    let roles: Vec<&str> = example.messages.iter().map(|m| m.role).collect();
+
+    assert_eq!(roles, vec!["system", "user", "assistant"]);
+    assert_eq!(example.messages[1].content, "$BONK mcap $2M, -40% 24h");
+    assert_eq!(example.messages[2].content, "$BONK dev is cooked");
+
+    let jsonl = DatasetBuilder::to_jsonl(&[&tweet]).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(jsonl.trim()).unwrap();
+    assert_eq!(parsed["messages"][2]["role"], "assistant");
+}
+
+#[test]
+fn test_few_shot_section_lists_best_posts() {
+    let memory = memory();
+    let section = DatasetBuilder::few_shot_section(&memory, 2, 10).unwrap();
+    assert_eq!(section.lines, vec!["- banger", "- decent"]);
+    assert!(section.priority.is_some());
+
+    assert!(DatasetBuilder::few_shot_section(&memory, 2, 1000).is_none());
+}
@@ -0,0 +1,65 @@
+// src/core/tests/drafts_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use super::super::content::Post;
+use super::super::drafts::{Draft, DraftLog};
+use super::super::outbox::Outbox;
+
+fn draft(text: &str, minute: u32) -> Draft {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 14, minute, 0).unwrap();
+    let post = Post {
+        context: Some("$WIF\nmcap $1.8B\nliq $14.5M".to_string()),
+        ..Post::text(text.to_string())
+    };
+    Draft { token: Some(("WIF".to_string(), "EKpQ".to_string())), ..Draft::new("fud", &post, now) }
+}
+
+#[test]
+fn test_markdown_round_trips() {
+    let first = draft("dog with a hat\n\nstill a dog", 5);
+    let second = Draft { context: None, token: None, ..draft("ngmi", 6) };
+    let markdown = format!("# Drafts\n\nnotes up here are ignored\n\n{}{}", first.to_markdown(), second.to_markdown());
+    assert!(markdown.contains("## fud, 14:05 UTC"));
+    assert_eq!(Draft::parse(&markdown), vec![first, second]);
+}
+
+#[test]
+fn test_drafts_without_an_id_are_skipped() {
+    let markdown = "## fud, 14:05 UTC\n\n- written: 2026-10-16T14:05:00+00:00\n\n**Final text**\n\n```text\nhi\n```\n";
+    assert!(Draft::parse(markdown).is_empty());
+}
+
+#[test]
+fn test_promote_queues_the_draft_once() {
+    let root = std::env::temp_dir().join(format!("chainfud-drafts-{}", rand::random::<u64>()));
+    let log = DraftLog::new(root.join("drafts"));
+    let outbox = Outbox::new(root.join("outbox"));
+    let post = Post { image: Some(b"GIF89a...".to_vec()), ..Post::text("look at this chart".to_string()) };
+    let with_image = Draft::new("image meme", &post, Utc.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap());
+    let path = log.append(&with_image, post.image.as_deref()).unwrap();
+    log.append(&draft("ngmi", 30), None).unwrap();
+    assert_eq!(path, log.day_path(with_image.written_at.date_naive()));
+    assert_eq!(with_image.image, Some(format!("{}.gif", with_image.id)));
+
+    let now = with_image.written_at + Duration::hours(1);
+    let item = log.promote(&with_image.id, &outbox, now).unwrap();
+    assert_eq!(item.text, "look at this chart");
+    assert_eq!(item.label, "image meme");
+    assert_eq!(outbox.image(&item), Some(b"GIF89a...".to_vec()));
+    assert_eq!(outbox.load().unwrap(), vec![item]);
+
+    let (_, promoted) = log.find(&with_image.id).unwrap();
+    assert_eq!(promoted.promoted_at, Some(now));
+    assert!(log.promote(&with_image.id, &outbox, now).is_err());
+    assert!(log.find("missing").is_err());
+    assert_eq!(Draft::parse(&fs::read_to_string(&path).unwrap()).len(), 2);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_promoted_fud_keeps_its_token() {
+    let item = draft("ngmi", 5).to_outbox_item(Utc::now(), false);
+    assert_eq!(item.target_mint.as_deref(), Some("EKpQ"));
+    assert_eq!(item.context.as_deref(), Some("$WIF\nmcap $1.8B\nliq $14.5M"));
+}
@@ -0,0 +1,86 @@
+// src/core/tests/export_tests.rs
+
+use chrono::{TimeZone, Utc};
+use super::super::export::{ExportFormat, ExportRecord, Exporter};
+use crate::models::{Memory, Tweet, TweetMetrics};
+
+fn tweet(id: &str, text: &str, day: u32, hour: u32) -> Tweet {
+    Tweet {
+        twitter_id: Some(id.to_string()),
+        text: text.to_string(),
+        prompt: "write fud about $BONK".to_string(),
+        timestamp: Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_groups_tweets_by_day_in_order() {
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("3", "later", 15, 18));
+    memory.tweets.push(tweet("1", "first", 14, 9));
+    memory.tweets.push(tweet("2", "earlier", 15, 8));
+
+    let days = Exporter::by_day(&memory);
+
+    assert_eq!(days.len(), 2);
+    let second: Vec<&str> = days.values().nth(1).unwrap().iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(second, vec!["earlier", "later"]);
+}
+
+#[test]
+fn test_csv_escapes_quotes_commas_and_newlines() {
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("1", "dev said \"wagmi\", then\nrugged", 14, 9));
+    let days = Exporter::by_day(&memory);
+    let (date, tweets) = days.iter().next().unwrap();
+
+    let csv = Exporter::render(ExportFormat::Csv, *date, tweets).unwrap();
+
+    assert!(csv.starts_with("timestamp,kind,twitter_id"));
+    assert!(csv.contains("\"dev said \"\"wagmi\"\", then\nrugged\""));
+    assert!(csv.contains("\"https://x.com/i/status/1\""));
+}
+
+#[test]
+fn test_jsonl_lines_include_metrics() {
+    let mut memory = Memory::default();
+    let mut posted = tweet("1", "$BONK is cooked", 14, 9);
+    posted.metrics = Some(TweetMetrics { likes: 12, retweets: 3, replies: 2, quotes: 1, ..Default::default() });
+    memory.tweets.push(posted);
+    memory.tweets.push(tweet("2", "not fetched yet", 14, 10));
+    let days = Exporter::by_day(&memory);
+    let (date, tweets) = days.iter().next().unwrap();
+
+    let jsonl = Exporter::render(ExportFormat::Jsonl, *date, tweets).unwrap();
+    let lines: Vec<serde_json::Value> = jsonl.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["likes"], 12);
+    assert_eq!(lines[0]["kind"], "post");
+    assert!(lines[1]["likes"].is_null());
+}
+
+#[test]
+fn test_markdown_has_day_heading_and_prompt() {
+    let record = ExportRecord::from_tweet(&tweet("1", "$BONK is cooked", 14, 9));
+    assert_eq!(record.url.as_deref(), Some("https://x.com/i/status/1"));
+
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("1", "$BONK is cooked", 14, 9));
+    let days = Exporter::by_day(&memory);
+    let (date, tweets) = days.iter().next().unwrap();
+    let markdown = Exporter::render(ExportFormat::Markdown, *date, tweets).unwrap();
+
+    assert!(markdown.starts_with("# 2026-10-14"));
+    assert!(markdown.contains("> $BONK is cooked"));
+    assert!(markdown.contains("write fud about $BONK"));
+    assert!(markdown.contains("Engagement: not fetched"));
+}
+
+#[test]
+fn test_parses_format_names() {
+    assert_eq!(ExportFormat::parse("MD"), Some(ExportFormat::Markdown));
+    assert_eq!(ExportFormat::parse("jsonl").map(|f| f.extension()), Some("jsonl"));
+    assert_eq!(ExportFormat::parse("xml"), None);
+}
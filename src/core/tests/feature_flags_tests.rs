@@ -0,0 +1,56 @@
+// src/core/tests/feature_flags_tests.rs
+
+use super::super::feature_flags::{FeatureSwitch, Flag, FlagCommand};
+use crate::models::FeatureFlags;
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(FeatureSwitch::parse_command("/flags"), Some(FlagCommand::List));
+    assert_eq!(FeatureSwitch::parse_command("flags image_posts off"), Some(FlagCommand::Set(Flag::ImagePosts, false)));
+    assert_eq!(FeatureSwitch::parse_command("/flags@fudbot Image-Gen ON"), Some(FlagCommand::Set(Flag::ImageGen, true)));
+    assert_eq!(FeatureSwitch::parse_command("flags threads false"), Some(FlagCommand::Set(Flag::Threads, false)));
+
+    assert_eq!(FeatureSwitch::parse_command("flags threads"), None);
+    assert_eq!(FeatureSwitch::parse_command("flags tweet_mode off"), None);
+    assert_eq!(FeatureSwitch::parse_command("flags threads maybe"), None);
+    assert_eq!(FeatureSwitch::parse_command("flags threads off now"), None);
+    assert_eq!(FeatureSwitch::parse_command("/campaign stop"), None);
+}
+
+#[test]
+fn test_set_and_describe() {
+    let mut flags = FeatureFlags::default();
+    assert!(Flag::ALL.iter().all(|flag| flags.get(*flag)));
+
+    flags.set(Flag::NotificationReplies, false);
+    assert!(!flags.notification_replies);
+    assert!(flags.get(Flag::Threads));
+    assert_eq!(
+        flags.describe(),
+        "image_posts: on, notification_replies: off, threads: on, image_gen: on"
+    );
+}
+
+#[test]
+fn test_missing_flags_default_on() {
+    let flags: FeatureFlags = serde_json::from_str(r#"{"threads": false}"#).unwrap();
+    assert!(!flags.threads);
+    assert!(flags.image_posts && flags.notification_replies && flags.image_gen);
+}
+
+#[test]
+fn test_requests_queue_up_until_taken() {
+    let dir = std::env::temp_dir().join(format!("chainfud-flags-{}", rand::random::<u64>()));
+    let path = dir.join("flags_request");
+    assert!(FeatureSwitch::take_requests_from(&path).unwrap().is_empty());
+
+    FeatureSwitch::append_request(&path, &FlagCommand::Set(Flag::Threads, false)).unwrap();
+    FeatureSwitch::append_request(&path, &FlagCommand::List).unwrap();
+    FeatureSwitch::append_request(&path, &FlagCommand::Set(Flag::ImageGen, true)).unwrap();
+    assert_eq!(
+        FeatureSwitch::take_requests_from(&path).unwrap(),
+        vec![FlagCommand::Set(Flag::Threads, false), FlagCommand::Set(Flag::ImageGen, true)]
+    );
+    assert!(FeatureSwitch::take_requests_from(&path).unwrap().is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
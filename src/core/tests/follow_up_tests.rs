@@ -0,0 +1,73 @@
+// src/core/tests/follow_up_tests.rs
+
+use chrono::Utc;
+use crate::core::follow_up::{FollowUp, FollowUpQuestion};
+use crate::models::{FuddedToken, TokenSnapshot};
+
+fn token(price_usd: f64, market_cap: f64) -> TokenSnapshot {
+    TokenSnapshot {
+        mint: "MINT".to_string(),
+        symbol: "RUG".to_string(),
+        price_usd,
+        market_cap,
+        liquidity_usd: 50_000.0,
+        change_1h: None,
+        change_24h: None,
+        buys: 0,
+        sells: 0,
+        age_hours: None,
+    }
+}
+
+fn fudded(price_usd: f64, market_cap: f64) -> FuddedToken {
+    FuddedToken {
+        mint: "MINT".to_string(),
+        symbol: "RUG".to_string(),
+        tweet_id: Some("1".to_string()),
+        fudded_at: Utc::now(),
+        price_usd,
+        market_cap,
+    }
+}
+
+#[test]
+fn test_detects_follow_up_questions() {
+    assert_eq!(FollowUp::detect("source?"), Some(FollowUpQuestion::Source));
+    assert_eq!(FollowUp::detect("sauce??"), Some(FollowUpQuestion::Source));
+    assert_eq!(FollowUp::detect("what's the mcap now?"), Some(FollowUpQuestion::MarketCap));
+    assert_eq!(FollowUp::detect("@fud @dev how much liq is left"), Some(FollowUpQuestion::Liquidity));
+    assert_eq!(FollowUp::detect("price now?"), Some(FollowUpQuestion::Price));
+}
+
+#[test]
+fn test_ignores_statements_and_partial_words() {
+    assert_eq!(FollowUp::detect("source: trust me"), None);
+    assert_eq!(FollowUp::detect("you eat at mcdonalds?"), None);
+    assert_eq!(FollowUp::detect("ngmi?"), None);
+}
+
+#[test]
+fn test_market_cap_answer_compares_with_when_we_posted() {
+    let answer = FollowUp::answer(FollowUpQuestion::MarketCap, &token(0.001, 600_000.0), Some(&fudded(0.002, 1_000_000.0)));
+    assert!(answer.contains("$RUG mcap is $600.0K right now"));
+    assert!(answer.contains("was $1.0M when i called it (-40.0%)"));
+
+    let answer = FollowUp::answer(FollowUpQuestion::MarketCap, &token(0.001, 600_000.0), None);
+    assert!(!answer.contains("when i called it"));
+}
+
+#[test]
+fn test_source_answer_links_the_mint() {
+    let answer = FollowUp::answer(FollowUpQuestion::Source, &token(0.001, 600_000.0), None);
+    assert!(answer.contains("ca: MINT"));
+    assert!(answer.contains("https://solscan.io/token/MINT"));
+}
+
+#[test]
+fn test_small_prices_keep_their_digits() {
+    assert_eq!(FollowUp::format_price(1.5), "$1.50");
+    assert_eq!(FollowUp::format_price(0.05), "$0.0500");
+    assert_eq!(FollowUp::format_price(0.0000123), "$0.0000123");
+    let answer = FollowUp::answer(FollowUpQuestion::Price, &token(0.0000123, 1.0), Some(&fudded(0.0000246, 1.0)));
+    assert!(answer.contains("$RUG is at $0.0000123 right now, was $0.0000246 when i called it (-50.0%)"));
+}
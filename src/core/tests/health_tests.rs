@@ -0,0 +1,37 @@
+// src/core/tests/health_tests.rs
+
+use crate::core::health::{HealthReport, Provider, ProviderStatus};
+
+#[test]
+fn test_unchecked_providers_are_available() {
+    let report = HealthReport::default();
+    for provider in Provider::ALL {
+        assert_eq!(report.status(provider), &ProviderStatus::Unchecked);
+        assert!(report.is_available(provider));
+    }
+    assert!(report.degraded().is_empty());
+}
+
+#[test]
+fn test_degraded_providers_are_reported() {
+    let mut report = HealthReport::default();
+    report.set(Provider::Twitter, ProviderStatus::Ok("@fudbot".to_string()));
+    report.set(Provider::Telegram, ProviderStatus::Degraded("401 Unauthorized".to_string()));
+    report.set(Provider::SolanaTracker, ProviderStatus::Degraded("timeout".to_string()));
+
+    assert!(report.is_available(Provider::Twitter));
+    assert!(!report.is_available(Provider::Telegram));
+    assert_eq!(report.degraded(), vec![Provider::SolanaTracker, Provider::Telegram]);
+
+    report.set(Provider::Telegram, ProviderStatus::Ok(String::new()));
+    assert_eq!(report.degraded(), vec![Provider::SolanaTracker]);
+}
+
+#[test]
+fn test_disabled_providers_are_unavailable_but_not_degraded() {
+    let mut report = HealthReport::default();
+    report.set(Provider::Telegram, ProviderStatus::Disabled);
+
+    assert!(!report.is_available(Provider::Telegram));
+    assert!(report.degraded().is_empty());
+}
@@ -0,0 +1,76 @@
+// src/core/tests/inline_fud_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+
+use super::super::inline_fud::{InlineCard, InlineFud, InlineRateLimit};
+use super::super::token_extractor::TokenCandidate;
+use crate::models::TokenSnapshot;
+use crate::providers::telegram::InlineRequest;
+
+const MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+fn request(user_id: u64, query: &str) -> InlineRequest {
+    InlineRequest { id: format!("{}-{}", user_id, query), user_id, query: query.to_string() }
+}
+
+#[test]
+fn test_parse_reads_cashtags_addresses_and_bare_tickers() {
+    assert_eq!(InlineFud::parse("$BONK"), Some(TokenCandidate::Ticker("BONK".to_string())));
+    assert_eq!(InlineFud::parse(&format!(" {} ", MINT)), Some(TokenCandidate::Address(MINT.to_string())));
+    assert_eq!(InlineFud::parse("wif"), Some(TokenCandidate::Ticker("wif".to_string())));
+    assert_eq!(InlineFud::parse(""), None);
+    assert_eq!(InlineFud::parse("123"), None);
+    assert_eq!(InlineFud::parse("is this a rug"), None);
+}
+
+#[test]
+fn test_only_each_users_latest_query_is_answered() {
+    let queries = vec![request(1, "$B"), request(2, "$WIF"), request(1, "$BON"), request(1, "$BONK")];
+    let latest: Vec<String> = InlineFud::latest_per_user(queries).into_iter().map(|q| q.query).collect();
+    assert_eq!(latest, vec!["$WIF", "$BONK"]);
+}
+
+#[test]
+fn test_stat_card_and_results() {
+    let token = TokenSnapshot {
+        mint: MINT.to_string(),
+        symbol: "BONK".to_string(),
+        price_usd: 0.0012,
+        market_cap: 1_200_000.0,
+        liquidity_usd: 80_000.0,
+        change_1h: Some(-4.25),
+        change_24h: None,
+        ..TokenSnapshot::default()
+    };
+    let stats = InlineFud::stat_card(&token);
+    assert_eq!(stats, format!("$BONK\nmcap $1.2M | liq $80.0K\nprice $0.0012 | 1h -4.2%\nca: {}", MINT));
+
+    let card = InlineCard { symbol: "BONK".to_string(), fud: "dev wallet still dumping".to_string(), stats };
+    let results = InlineFud::results(&card);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].title, "FUD $BONK");
+    assert_eq!(results[0].text, "dev wallet still dumping");
+    assert_eq!(results[1].description, "mcap $1.2M | liq $80.0K | price $0.0012 | 1h -4.2%");
+}
+
+#[test]
+fn test_cache_key_ignores_case_and_cashtag() {
+    assert_eq!(InlineFud::cache_key("$Bonk"), Some("bonk".to_string()));
+    assert_eq!(InlineFud::cache_key("BONK"), Some("bonk".to_string()));
+    assert_eq!(InlineFud::cache_key("is this a rug"), None);
+}
+
+#[test]
+fn test_rate_limit_per_user_per_hour() {
+    let start = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let mut limit = InlineRateLimit::default();
+    assert!(limit.allow(1, 2, start));
+    assert!(limit.allow(1, 2, start + Duration::minutes(10)));
+    assert!(!limit.allow(1, 2, start + Duration::minutes(20)));
+    // Other users have their own allowance
+    assert!(limit.allow(2, 2, start + Duration::minutes(20)));
+    // The first answer ages out after an hour
+    assert!(limit.allow(1, 2, start + Duration::minutes(61)));
+    assert!(!limit.allow(1, 2, start + Duration::minutes(62)));
+    assert!((0..50).all(|_| limit.allow(3, 0, start)));
+}
@@ -0,0 +1,87 @@
+// src/core/tests/instruction_builder_tests.rs
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs;
+use std::path::PathBuf;
+
+use super::super::instruction_builder::{ExampleRotation, InstructionBuilder, EXAMPLES_DIR};
+
+fn temp_character() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("chainfud-examples-{}", rand::random::<u64>()));
+    fs::create_dir_all(dir.join(EXAMPLES_DIR)).unwrap();
+    fs::copy("characters/fud/character.json", dir.join("character.json")).unwrap();
+    dir
+}
+
+fn rotation(count: usize, per_call: usize) -> ExampleRotation {
+    ExampleRotation::new((0..count).map(|i| format!("post {}", i)).collect(), per_call)
+}
+
+#[test]
+fn test_parse_splits_posts_on_blank_lines() {
+    let text = "dev is selling\nagain\n\n  \r\nchart looks like a ski slope\r\n\n\n\nlast one";
+    assert_eq!(
+        ExampleRotation::parse(text),
+        vec!["dev is selling again", "chart looks like a ski slope", "last one"]
+    );
+    assert!(ExampleRotation::parse("\n\n").is_empty());
+}
+
+#[test]
+fn test_load_reads_txt_files_in_name_order() {
+    let dir = temp_character();
+    let examples = dir.join(EXAMPLES_DIR);
+    fs::write(examples.join("b.txt"), "third").unwrap();
+    fs::write(examples.join("a.txt"), "first\n\nsecond").unwrap();
+    fs::write(examples.join("notes.md"), "not an example").unwrap();
+
+    let rotation = ExampleRotation::load(&examples, 3).unwrap();
+    assert_eq!(rotation.posts(), ["first", "second", "third"]);
+    assert!(ExampleRotation::load(&dir.join("missing"), 3).unwrap().is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sample_skips_last_calls_examples() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut examples = rotation(6, 3);
+    for _ in 0..20 {
+        let first = examples.sample(&mut rng);
+        let second = examples.sample(&mut rng);
+        assert_eq!(first.len(), 3);
+        assert!(second.iter().all(|post| !first.contains(post)));
+    }
+}
+
+#[test]
+fn test_sample_reuses_examples_when_pool_is_small() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut examples = rotation(4, 3);
+    for _ in 0..10 {
+        let mut posts = examples.sample(&mut rng);
+        posts.sort();
+        posts.dedup();
+        assert_eq!(posts.len(), 3);
+    }
+    assert_eq!(rotation(2, 3).sample(&mut rng).len(), 2);
+    assert!(rotation(0, 3).sample(&mut rng).is_empty());
+}
+
+#[test]
+fn test_sample_section_is_empty_when_off() {
+    let mut rng = StdRng::seed_from_u64(7);
+    assert_eq!(rotation(5, 0).sample_section(&mut rng), "");
+    let section = rotation(5, 2).sample_section(&mut rng);
+    assert_eq!(section.lines().filter(|line| line.starts_with("- post ")).count(), 2);
+}
+
+#[test]
+fn test_build_instructions_loads_examples_next_to_character() {
+    let dir = temp_character();
+    fs::write(dir.join(EXAMPLES_DIR).join("posts.txt"), "one\n\ntwo").unwrap();
+    let mut instruction_builder = InstructionBuilder::new();
+    instruction_builder.build_instructions_from_file(&dir.join("character.json")).unwrap();
+    assert_eq!(instruction_builder.get_examples().posts(), ["one", "two"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
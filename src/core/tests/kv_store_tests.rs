@@ -0,0 +1,205 @@
+// src/core/tests/kv_store_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use std::path::PathBuf;
+use super::super::kv_store::{insert_timed, parse_time_key, remove_before, time_key, KvStore, LogStore};
+use super::super::responded::{RespondedIndex, ResponseState};
+use super::super::style::StyleEngine;
+use crate::memory::MemoryStore;
+use crate::models::{Memory, Tweet};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("chainfud-{}-{}", name, rand::random::<u64>()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn tweet(text: &str, timestamp: chrono::DateTime<Utc>) -> Tweet {
+    Tweet {
+        text: text.to_string(),
+        timestamp,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_log_store_replays_inserts_and_removals() {
+    let dir = scratch_dir("kv-replay");
+    let path = dir.join("store.kv");
+    {
+        let mut store = LogStore::open(&path).unwrap();
+        store.insert("a", "1").unwrap();
+        store.insert("b", "2").unwrap();
+        store.insert("a", "3").unwrap();
+        assert!(store.remove("b").unwrap());
+        assert!(!store.remove("missing").unwrap());
+    }
+
+    let store = LogStore::open(&path).unwrap();
+    assert_eq!(store.entries(), vec![("a".to_string(), "3".to_string())]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_skips_torn_last_line() {
+    let dir = scratch_dir("kv-torn");
+    let path = dir.join("store.kv");
+    fs::write(&path, "{\"k\":\"a\",\"v\":\"1\"}\n{\"k\":\"b\",\"v").unwrap();
+
+    let mut store = LogStore::open(&path).unwrap();
+    assert_eq!(store.get("a").as_deref(), Some("1"));
+    assert!(!store.contains("b"));
+    store.insert("c", "2").unwrap();
+    assert_eq!(LogStore::open(&path).unwrap().len(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_recovers_line_cut_before_its_newline() {
+    let dir = scratch_dir("kv-newline");
+    let path = dir.join("store.kv");
+    fs::write(&path, "{\"k\":\"a\",\"v\":\"1\"}\n{\"k\":\"b\",\"v\":\"2\"}").unwrap();
+
+    let mut store = LogStore::open(&path).unwrap();
+    assert_eq!(store.get("b").as_deref(), Some("2"));
+    store.insert("c", "3").unwrap();
+    let reopened = LogStore::open(&path).unwrap();
+    assert_eq!(reopened.entries().len(), 3);
+    assert_eq!(reopened.get("c").as_deref(), Some("3"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_skips_garbage_between_good_lines() {
+    let dir = scratch_dir("kv-garbage");
+    let path = dir.join("store.kv");
+    fs::write(&path, "{\"k\":\"a\",\"v\":\"1\"}\n\u{0}\u{0}{\"k\n{\"k\":\"a\"}\n{\"k\":\"b\",\"v\":\"2\"}\n").unwrap();
+
+    let store = LogStore::open(&path).unwrap();
+    assert_eq!(store.entries(), vec![("b".to_string(), "2".to_string())]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_survives_crash_during_compaction() {
+    let dir = scratch_dir("kv-crash");
+    let path = dir.join("store.kv");
+    {
+        let mut store = LogStore::open(&path).unwrap();
+        store.insert("a", "1").unwrap();
+        store.insert("b", "2").unwrap();
+    }
+    // Died after writing part of the rewrite, before the rename
+    fs::write(path.with_extension("tmp"), "{\"k\":\"a\",\"v").unwrap();
+
+    let mut store = LogStore::open(&path).unwrap();
+    assert_eq!(store.len(), 2);
+    store.compact().unwrap();
+    assert!(!path.with_extension("tmp").exists());
+    assert_eq!(LogStore::open(&path).unwrap().len(), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_compacts_superseded_lines() {
+    let dir = scratch_dir("kv-compact");
+    let path = dir.join("store.kv");
+    let mut store = LogStore::open(&path).unwrap();
+    for i in 0..200 {
+        store.insert("counter", &i.to_string()).unwrap();
+    }
+
+    let lines = fs::read_to_string(&path).unwrap().lines().count();
+    assert!(lines < 100, "log kept {} lines", lines);
+    assert_eq!(LogStore::open(&path).unwrap().get("counter").as_deref(), Some("199"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_time_keys_sort_and_expire() {
+    let mut store = LogStore::in_memory();
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    insert_timed(&mut store, at, "first").unwrap();
+    insert_timed(&mut store, at, "same instant").unwrap();
+    insert_timed(&mut store, at + Duration::hours(2), "later").unwrap();
+
+    let texts: Vec<String> = store.entries().into_iter().map(|(_, text)| text).collect();
+    assert_eq!(texts, vec!["first", "same instant", "later"]);
+    assert_eq!(parse_time_key(&time_key(at)), Some(at));
+
+    assert_eq!(remove_before(&mut store, at + Duration::hours(1)).unwrap(), 2);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_processed_tweets_migrate_from_legacy_files() {
+    let dir = scratch_dir("kv-processed");
+    let now = Utc::now();
+    // Snowflake for a tweet from an hour ago, and one from well past retention
+    let snowflake = |at: chrono::DateTime<Utc>| (((at.timestamp_millis() - 1_288_834_974_657) as u64) << 22).to_string();
+    let recent = snowflake(now - Duration::hours(1));
+    let stale = snowflake(now - Duration::days(60));
+    fs::write(dir.join("processed_tweets.json"), format!("{{\"tweet_ids\":[\"{}\"]}}", recent)).unwrap();
+    fs::write(dir.join("processed_tweets.log"), format!("{}\nnot-a-snowflake\n", stale)).unwrap();
+
+    let store = MemoryStore::open_processed_tweets_in(&dir, now).unwrap();
+    assert!(store.contains(&recent));
+    assert!(store.contains("not-a-snowflake"));
+    assert!(!store.contains(&stale));
+    assert!(!dir.join("processed_tweets.json").exists());
+    assert!(!dir.join("processed_tweets.log").exists());
+
+    let new_id = snowflake(now);
+    let mut index = RespondedIndex::new(Box::new(store));
+    index.record(&new_id, ResponseState::Queued, now).unwrap();
+    let reopened = MemoryStore::open_processed_tweets_in(&dir, now).unwrap();
+    assert!(reopened.contains(&new_id));
+    assert!(reopened.contains(&recent));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_phrase_history_survives_restart() {
+    let dir = scratch_dir("kv-phrases");
+    let now = Utc::now();
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("dev is selling again", now - Duration::hours(2)));
+    memory.tweets.push(tweet("ancient history tweet here", now - Duration::days(30)));
+
+    let history = MemoryStore::open_phrase_history_in(&dir, &memory, now).unwrap();
+    assert_eq!(history.len(), 1);
+    let mut style = StyleEngine::new(100, Duration::hours(24)).with_history(Box::new(history), now);
+    assert!(style.repeats_recent_phrase("lol dev is selling"));
+    style.record_at("liquidity looks thin today", now);
+
+    // Already seeded, so memory isn't read again
+    let history = MemoryStore::open_phrase_history_in(&dir, &Memory::default(), now).unwrap();
+    assert_eq!(history.len(), 2);
+    let mut restarted = StyleEngine::new(100, Duration::hours(1)).with_history(Box::new(history), now);
+    assert!(restarted.repeats_recent_phrase("the liquidity looks thin"));
+    assert!(!restarted.repeats_recent_phrase("lol dev is selling"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_log_store_shared_between_handles_keeps_every_write() {
+    let dir = scratch_dir("kv-shared");
+    let path = dir.join("store.kv");
+    let mut first = LogStore::open(&path).unwrap();
+    let mut second = LogStore::open(&path).unwrap();
+    second.insert("theirs", "1").unwrap();
+    // Enough churn in the first handle to make it compact, which must keep the
+    // other handle's write
+    for i in 0..200 {
+        first.insert("mine", &i.to_string()).unwrap();
+    }
+    first.compact().unwrap();
+
+    let reopened = LogStore::open(&path).unwrap();
+    assert_eq!(reopened.get("theirs").as_deref(), Some("1"));
+    assert_eq!(reopened.get("mine").as_deref(), Some("199"));
+    // Conditional writes see the other handle's value
+    assert!(!second.update("mine", Box::new(|current| current.is_none().then(|| "x".to_string()))).unwrap());
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,57 @@
+// src/core/tests/language_detect_tests.rs
+
+use crate::core::character::ReplyLanguageSettings;
+use crate::core::language_detect::LanguageDetector;
+
+#[test]
+fn test_detects_latin_languages_by_common_words() {
+    assert_eq!(LanguageDetector::detect("why is this token pumping, the dev is selling"), Some("English"));
+    assert_eq!(LanguageDetector::detect("hermano, esto es una estafa y el dev se fue"), Some("Spanish"));
+    assert_eq!(LanguageDetector::detect("cara, isso não é golpe, você tá louco"), Some("Portuguese"));
+    assert_eq!(LanguageDetector::detect("c'est une arnaque, le dev est parti avec tout"), Some("French"));
+    assert_eq!(LanguageDetector::detect("das ist nicht gut, der dev verkauft alles"), Some("German"));
+    assert_eq!(LanguageDetector::detect("bu coin çok iyi, ama dev ne yapıyor"), Some("Turkish"));
+}
+
+#[test]
+fn test_detects_languages_by_script() {
+    assert_eq!(LanguageDetector::detect("这个币是骗局吗"), Some("Chinese"));
+    assert_eq!(LanguageDetector::detect("このトークンは詐欺ですか"), Some("Japanese"));
+    assert_eq!(LanguageDetector::detect("이 코인 사기야?"), Some("Korean"));
+    assert_eq!(LanguageDetector::detect("это скам или нет"), Some("Russian"));
+}
+
+#[test]
+fn test_noise_and_short_mentions_are_unknown() {
+    assert_eq!(LanguageDetector::detect("@chainfud $WIF"), None);
+    assert_eq!(LanguageDetector::detect("gm ser"), None);
+    assert_eq!(
+        LanguageDetector::detect("@chainfud https://t.co/xyz 7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hr"),
+        None
+    );
+    // Handles and tickers don't count towards the language
+    assert_eq!(LanguageDetector::detect("@the_is_are $THE este es el token"), Some("Spanish"));
+}
+
+#[test]
+fn test_languages_match_by_name_or_code() {
+    assert!(LanguageDetector::is_language("spanish", "Spanish"));
+    assert!(LanguageDetector::is_language("es", "Spanish"));
+    assert!(LanguageDetector::is_language(" JA ", "Japanese"));
+    assert!(!LanguageDetector::is_language("pt", "Spanish"));
+}
+
+#[test]
+fn test_reply_language_uses_allowlist_and_fallback() {
+    let settings = ReplyLanguageSettings {
+        match_mention: true,
+        allowed: vec!["en".to_string(), "Spanish".to_string()],
+        fallback: "English".to_string(),
+    };
+    assert_eq!(settings.for_mention("hermano, esto es una estafa").as_deref(), Some("Spanish"));
+    assert_eq!(settings.for_mention("c'est une arnaque, le dev est parti").as_deref(), Some("English"));
+    assert_eq!(settings.for_mention("$WIF").as_deref(), Some("English"));
+
+    let off = ReplyLanguageSettings { match_mention: false, ..settings };
+    assert_eq!(off.for_mention("hermano, esto es una estafa"), None);
+}
@@ -0,0 +1,36 @@
+// src/core/tests/latency_budget_tests.rs
+
+use std::time::Duration;
+use super::super::character::{LatencyBudgetSettings, TimeoutFallback};
+use super::super::latency_budget::within;
+use crate::error::FudError;
+
+#[tokio::test]
+async fn test_within_passes_results_through() {
+    let ok = within("fetch", Some(Duration::from_secs(5)), async { Ok::<_, FudError>(7) }).await;
+    assert_eq!(ok.unwrap(), 7);
+
+    let failed = within("fetch", None, async { Err::<u32, _>(anyhow::anyhow!("boom")) }).await;
+    assert!(!failed.unwrap_err().is_timeout());
+}
+
+#[tokio::test]
+async fn test_within_times_out_slow_stage() {
+    let slow = async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok::<_, FudError>(())
+    };
+    let err = within("llm_call", Some(Duration::from_millis(20)), slow).await.unwrap_err();
+    assert!(err.is_timeout());
+    assert!(matches!(err, FudError::Timeout { stage: "llm_call", .. }));
+}
+
+#[test]
+fn test_latency_budget_defaults() {
+    let settings = LatencyBudgetSettings::default();
+    assert_eq!(settings.limit(settings.llm_seconds), Some(Duration::from_secs(45)));
+    assert_eq!(settings.on_timeout, TimeoutFallback::CachedThenGeneric);
+
+    let disabled = LatencyBudgetSettings { enabled: false, ..settings };
+    assert_eq!(disabled.limit(disabled.llm_seconds), None);
+}
@@ -0,0 +1,90 @@
+// src/core/tests/learned_facts_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::character::LearnedFactsSettings;
+use super::super::learned_facts::LearnedFacts;
+use crate::models::TokenFact;
+
+const MINT: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+fn fact(mint: &str, claim: &str, hours_ago: i64) -> TokenFact {
+    TokenFact {
+        mint: mint.to_string(),
+        symbol: "RUG".to_string(),
+        claim: claim.to_string(),
+        source_tweet_id: "1".to_string(),
+        learned_at: Utc::now() - Duration::hours(hours_ago),
+        authors: vec!["100".to_string()],
+    }
+}
+
+fn fact_by(mint: &str, claim: &str, author: &str) -> TokenFact {
+    TokenFact { authors: vec![author.to_string()], ..fact(mint, claim, 0) }
+}
+
+#[test]
+fn test_worth_extracting() {
+    let settings = LearnedFactsSettings::default();
+    assert!(LearnedFacts::worth_extracting("@fudbot $RUG dev doxxed yesterday and LP unlocked", &settings));
+    assert!(!LearnedFacts::worth_extracting(&format!("@fudbot $RUG {} https://t.co/x", MINT), &settings));
+    assert!(!LearnedFacts::worth_extracting("@fudbot thoughts on $RUG?", &settings));
+}
+
+#[test]
+fn test_parse() {
+    let response = r#"Here you go: ["Dev doxxed yesterday.", "LP unlocked", "lp unlocked", "", "Top wallet sold 30%"]"#;
+    assert_eq!(LearnedFacts::parse(response, 5), vec!["Dev doxxed yesterday", "LP unlocked", "Top wallet sold 30%"]);
+    assert_eq!(LearnedFacts::parse(response, 1), vec!["Dev doxxed yesterday"]);
+    assert!(LearnedFacts::parse("[]", 3).is_empty());
+    assert!(LearnedFacts::parse("no claims here", 3).is_empty());
+    assert!(LearnedFacts::parse(r#"[{"claim": "LP unlocked"}]"#, 3).is_empty());
+    assert!(LearnedFacts::parse(&format!(r#"["{}"]"#, "a".repeat(200)), 3).is_empty());
+}
+
+#[test]
+fn test_add_skips_known_claims_and_caps_per_token() {
+    let mut facts = vec![fact(MINT, "LP unlocked", 30), fact("other", "dev sold", 20), fact(MINT, "dev doxxed", 10)];
+
+    let added = LearnedFacts::add(&mut facts, vec![fact(MINT, "lp UNLOCKED", 0), fact(MINT, "team wallet bridged out", 0)], 2);
+    assert_eq!(added, 1);
+    let claims: Vec<&str> = facts.iter().map(|fact| fact.claim.as_str()).collect();
+    // The oldest fact about the token made room; the other token's were left alone
+    assert_eq!(claims, vec!["dev sold", "dev doxxed", "team wallet bridged out"]);
+}
+
+#[test]
+fn test_claims_need_several_authors() {
+    let mut facts = Vec::new();
+    LearnedFacts::add(&mut facts, vec![fact_by(MINT, "dev is a scammer", "1")], 20);
+    // The same account saying it again doesn't count twice
+    assert_eq!(LearnedFacts::add(&mut facts, vec![fact_by(MINT, "Dev is a scammer", "1")], 20), 0);
+    let since = Utc::now() - Duration::days(7);
+    assert!(LearnedFacts::relevant(&facts, MINT, since, 5, 3).is_empty());
+
+    assert_eq!(LearnedFacts::add(&mut facts, vec![fact_by(MINT, "dev is a scammer", "2")], 20), 1);
+    LearnedFacts::add(&mut facts, vec![fact_by(MINT, "dev is a scammer", "3")], 20);
+    assert_eq!(facts.len(), 1);
+    assert_eq!(facts[0].authors, vec!["1", "2", "3"]);
+    assert_eq!(LearnedFacts::relevant(&facts, MINT, since, 5, 3).len(), 1);
+    assert!(!LearnedFactsSettings::default().enabled);
+}
+
+#[test]
+fn test_relevant_and_prompt_section() {
+    let facts = vec![
+        fact(MINT, "LP unlocked", 24 * 10),
+        fact(MINT, "dev doxxed", 50),
+        fact("other", "dev sold", 5),
+        fact(MINT, "top wallet sold 30%", 3),
+    ];
+    let now = Utc::now();
+    let relevant = LearnedFacts::relevant(&facts, MINT, now - Duration::days(7), 5, 1);
+    let claims: Vec<&str> = relevant.iter().map(|fact| fact.claim.as_str()).collect();
+    assert_eq!(claims, vec!["top wallet sold 30%", "dev doxxed"]);
+    assert_eq!(LearnedFacts::relevant(&facts, MINT, now - Duration::days(30), 1, 1).len(), 1);
+
+    let section = LearnedFacts::prompt_section(&relevant, "RUG", now);
+    assert!(section.starts_with("What people have told you about $RUG in replies (unverified):\n"));
+    assert!(section.contains("- (3 hours ago) top wallet sold 30%\n"));
+    assert!(section.contains("- (2 days ago) dev doxxed\n"));
+}
@@ -0,0 +1,44 @@
+// src/core/tests/local_time_tests.rs
+
+use chrono::{TimeZone, Utc, Weekday};
+use super::super::character::CharacterSettings;
+use super::super::local_time::LocalTime;
+
+#[test]
+fn test_parse_names() {
+    assert_eq!(LocalTime::parse(" America/New_York ").unwrap().name(), "America/New_York");
+    assert!(LocalTime::parse("Mars/Olympus_Mons").is_none());
+    assert!(LocalTime::default().is_utc());
+}
+
+#[test]
+fn test_unset_or_unknown_timezone_is_utc() {
+    let mut settings = CharacterSettings::default();
+    assert!(LocalTime::from_settings(&settings).is_utc());
+    settings.timezone = Some("Nowhere/Special".to_string());
+    assert!(LocalTime::from_settings(&settings).is_utc());
+    settings.timezone = Some("Europe/Berlin".to_string());
+    assert_eq!(LocalTime::from_settings(&settings).name(), "Europe/Berlin");
+}
+
+#[test]
+fn test_hours_follow_daylight_saving() {
+    let new_york = LocalTime::parse("America/New_York").unwrap();
+    let winter = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+    let summer = Utc.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+    assert_eq!(new_york.hour(winter), 7);
+    assert_eq!(new_york.hour(summer), 8);
+
+    // Just after midnight UTC on a Monday is still Sunday evening in New York
+    let monday = Utc.with_ymd_and_hms(2024, 7, 15, 1, 0, 0).unwrap();
+    assert_eq!(new_york.weekday(monday), Weekday::Sun);
+}
+
+#[test]
+fn test_show() {
+    let at = Utc.with_ymd_and_hms(2024, 7, 15, 12, 5, 0).unwrap();
+    let new_york = LocalTime::parse("America/New_York").unwrap();
+    assert_eq!(new_york.show(at), "08:05 EDT");
+    assert_eq!(new_york.show_with_utc(at), "08:05 EDT (12:05 UTC)");
+    assert_eq!(LocalTime::default().show_with_utc(at), "12:05 UTC");
+}
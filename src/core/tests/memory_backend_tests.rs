@@ -0,0 +1,28 @@
+// src/core/tests/memory_backend_tests.rs
+
+use super::super::kv_store::LogStore;
+use super::super::memory_backend::{pending, MemoryBackend, MIGRATIONS};
+
+#[test]
+fn test_pending_migrations_skip_applied_ones() {
+    assert_eq!(pending(&[]).len(), MIGRATIONS.len());
+    assert!(pending(&[1]).is_empty());
+
+    let versions: Vec<i32> = MIGRATIONS.iter().map(|(version, _)| *version).collect();
+    let mut sorted = versions.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(versions, sorted, "migration versions must be unique and in order");
+}
+
+#[test]
+fn test_files_backend_opens_the_file_store() {
+    let backend = MemoryBackend::from_config(None);
+    assert!(!backend.is_shared());
+
+    let mut store = backend.open("processed_tweets", || Ok(LogStore::in_memory())).unwrap();
+    store.insert("1", "handled").unwrap();
+    assert!(!store.update("1", Box::new(|_| None)).unwrap());
+    assert!(store.update("1", Box::new(|current| current.map(|value| format!("{}!", value)))).unwrap());
+    assert_eq!(store.get("1").as_deref(), Some("handled!"));
+}
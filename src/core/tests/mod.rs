@@ -0,0 +1,61 @@
+mod adaptive_schedule_tests;
+mod agent_tests;
+mod aggression_tests;
+mod audit_tests;
+mod backtest_tests;
+mod bundle_check_tests;
+mod callbacks_tests;
+mod campaign_tests;
+mod character_bundle_tests;
+mod character_switch_tests;
+mod clock_tests;
+mod config_tests;
+mod consistency_tests;
+mod content_buffer_tests;
+mod content_selector_tests;
+mod dataset_tests;
+mod drafts_tests;
+mod export_tests;
+mod feature_flags_tests;
+mod follow_up_tests;
+mod health_tests;
+mod inline_fud_tests;
+mod instruction_builder_tests;
+mod kv_store_tests;
+mod language_detect_tests;
+mod latency_budget_tests;
+mod learned_facts_tests;
+mod local_time_tests;
+mod memory_backend_tests;
+mod onchain_receipt_tests;
+mod outbox_tests;
+mod paths_tests;
+mod phrase_tracker_tests;
+mod pipeline_tests;
+mod predictions_tests;
+mod price_check_tests;
+mod prompt_budget_tests;
+mod quota_tests;
+mod rate_limiter_tests;
+mod reply_limits_tests;
+mod reply_policy_tests;
+mod reply_rules_tests;
+mod responded_tests;
+mod sanitizer_tests;
+mod setup_tests;
+mod spam_filter_tests;
+mod stats_tests;
+mod status_tests;
+mod storage_tests;
+mod stream_guard_tests;
+mod style_tests;
+mod target_scorer_tests;
+mod target_selection_tests;
+mod telemetry_tests;
+mod timeline_sync_tests;
+mod token_extractor_tests;
+mod token_heat_tests;
+mod tweet_preview_tests;
+mod tweet_token_tests;
+mod validator_tests;
+mod weekly_recap_tests;
@@ -0,0 +1,58 @@
+// src/core/tests/onchain_receipt_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::character::{OnChainReceiptSettings, ReceiptMode};
+use crate::models::OnChainReceipt;
+
+fn receipt(hours_ago: i64, lamports: u64) -> OnChainReceipt {
+    OnChainReceipt {
+        timestamp: Utc::now() - Duration::hours(hours_ago),
+        mint: "mint".to_string(),
+        signature: "sig".to_string(),
+        lamports,
+    }
+}
+
+#[test]
+fn test_off_by_default() {
+    let settings = OnChainReceiptSettings::default();
+    assert!(settings.refusal(&[], Utc::now()).is_some());
+}
+
+#[test]
+fn test_daily_receipt_and_spend_caps() {
+    let settings = OnChainReceiptSettings {
+        enabled: true,
+        max_receipts_per_day: 2,
+        ..Default::default()
+    };
+    let now = Utc::now();
+
+    assert_eq!(settings.refusal(&[receipt(1, 5_000)], now), None);
+    assert!(settings.refusal(&[receipt(1, 5_000), receipt(2, 5_000)], now).is_some());
+    // Older than a day no longer counts
+    assert_eq!(settings.refusal(&[receipt(30, 5_000), receipt(2, 5_000)], now), None);
+
+    let buy = OnChainReceiptSettings {
+        enabled: true,
+        mode: ReceiptMode::Buy,
+        buy_lamports: 1_000_000,
+        max_lamports_per_day: 5_000_000,
+        ..Default::default()
+    };
+    // One buy with token account rent is ~3.04M lamports, a second would pass the cap
+    assert_eq!(buy.refusal(&[], now), None);
+    assert!(buy.refusal(&[receipt(1, buy.cost_lamports())], now).is_some());
+}
+
+#[test]
+fn test_buy_size_has_a_hard_ceiling() {
+    let settings = OnChainReceiptSettings {
+        enabled: true,
+        mode: ReceiptMode::Buy,
+        buy_lamports: 50_000_000,
+        max_lamports_per_day: u64::MAX / 2,
+        ..Default::default()
+    };
+    assert!(settings.refusal(&[], Utc::now()).unwrap().contains("buy_lamports"));
+}
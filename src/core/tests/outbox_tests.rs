@@ -0,0 +1,114 @@
+// src/core/tests/outbox_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use super::super::character::OutboxSettings;
+use super::super::content::Post;
+use super::super::outbox::{Outbox, OutboxItem};
+
+fn item(text: &str) -> OutboxItem {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    OutboxItem::new("FUD", &Post::text(text.to_string()), now)
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_cap() {
+    let settings = OutboxSettings::default();
+    assert_eq!(OutboxItem::backoff(1, &settings), Duration::minutes(5));
+    assert_eq!(OutboxItem::backoff(2, &settings), Duration::minutes(10));
+    assert_eq!(OutboxItem::backoff(4, &settings), Duration::minutes(40));
+    assert_eq!(OutboxItem::backoff(30, &settings), Duration::minutes(240));
+}
+
+#[test]
+fn test_failed_item_waits_then_gives_up() {
+    let settings = OutboxSettings { max_attempts: 3, ..OutboxSettings::default() };
+    let mut item = item("this chart is cooked");
+    let now = item.created_at;
+    assert!(item.is_due(now));
+
+    item.mark_failed("Twitter rate limited the request", &settings, now);
+    assert!(!item.is_due(now));
+    assert!(item.is_due(now + Duration::minutes(5)));
+    assert_eq!(item.last_error.as_deref(), Some("Twitter rate limited the request"));
+
+    item.mark_failed("still down", &settings, now + Duration::minutes(5));
+    assert!(item.is_pending());
+    item.mark_failed("still down", &settings, now + Duration::minutes(15));
+    assert!(item.abandoned);
+    assert!(!item.is_due(now + Duration::days(1)));
+}
+
+#[test]
+fn test_old_posts_are_not_retried() {
+    let settings = OutboxSettings::default();
+    let mut item = item("dev wallet still dumping");
+    let later = item.created_at + Duration::hours(settings.max_age_hours);
+    assert!(item.is_stale(&settings, later));
+    item.mark_failed("down", &settings, later);
+    assert!(item.abandoned);
+}
+
+#[test]
+fn test_delivered_items_expire_after_keep() {
+    let mut item = item("ngmi");
+    let now = item.created_at;
+    assert!(!item.is_expired(Duration::days(7), now + Duration::days(30)));
+    item.mark_delivered("1790000000000000000", now);
+    assert!(!item.is_pending());
+    assert!(!item.is_expired(Duration::days(7), now + Duration::days(6)));
+    assert!(item.is_expired(Duration::days(7), now + Duration::days(7)));
+}
+
+#[test]
+fn test_items_and_images_survive_a_restart() {
+    let dir = std::env::temp_dir().join(format!("chainfud-outbox-{}", rand::random::<u64>()));
+    let outbox = Outbox::new(&dir);
+    let mut first = item("first");
+    first.created_at -= Duration::minutes(1);
+    let post = Post { image: Some(vec![1, 2, 3]), ..Post::text("second".to_string()) };
+    let second = OutboxItem::new("image meme", &post, first.created_at + Duration::minutes(2));
+    outbox.enqueue(&second, post.image.as_deref()).unwrap();
+    outbox.enqueue(&first, None).unwrap();
+    fs::write(dir.join("broken.json"), "{").unwrap();
+
+    let reopened = Outbox::new(&dir);
+    let items = reopened.load().unwrap();
+    assert_eq!(items, vec![first.clone(), second.clone()]);
+    assert_eq!(reopened.image(&items[1]), Some(vec![1, 2, 3]));
+    assert_eq!(reopened.image(&items[0]), None);
+    assert_eq!(items[1].to_post(reopened.image(&items[1]), None).text, "second");
+
+    reopened.remove(&second).unwrap();
+    assert_eq!(reopened.load().unwrap(), vec![first]);
+    assert!(!dir.join(format!("{}.img", second.id)).exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_load_sees_own_writes_and_other_writers() {
+    let dir = std::env::temp_dir().join(format!("chainfud-outbox-{}", rand::random::<u64>()));
+    let outbox = Outbox::new(&dir);
+    let mut first = item("first");
+    outbox.enqueue(&first, None).unwrap();
+    assert_eq!(outbox.load().unwrap(), vec![first.clone()]);
+
+    first.attempts = 2;
+    outbox.save(&first).unwrap();
+    assert_eq!(outbox.load().unwrap()[0].attempts, 2);
+
+    // Another writer, like the CLI promoting a draft, changes the directory
+    let second = item("second");
+    Outbox::new(&dir).enqueue(&second, None).unwrap();
+    assert_eq!(outbox.load().unwrap().len(), 2);
+
+    outbox.remove(&first).unwrap();
+    assert_eq!(outbox.load().unwrap(), vec![second]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_missing_dir_is_empty() {
+    let dir = std::env::temp_dir().join(format!("chainfud-outbox-{}", rand::random::<u64>()));
+    assert!(Outbox::new(&dir).load().unwrap().is_empty());
+}
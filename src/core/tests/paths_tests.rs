@@ -0,0 +1,64 @@
+// src/core/tests/paths_tests.rs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::super::paths::Layout;
+
+fn temp_home() -> PathBuf {
+    let home = std::env::temp_dir().join(format!("chainfud-home-{}", rand::random::<u64>()));
+    fs::create_dir_all(&home).unwrap();
+    home
+}
+
+fn with_character(home: &Path) {
+    let dir = home.join("characters").join("fud");
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy("characters/fud/character.json", dir.join("character.json")).unwrap();
+}
+
+#[test]
+fn test_resolves_defaults_under_home() {
+    let layout = Layout::new("/data");
+    assert_eq!(layout.resolve("./storage/outbox"), Path::new("/data/storage/outbox"));
+    assert_eq!(layout.resolve("storage/token_heat.kv"), Path::new("/data/storage/token_heat.kv"));
+    assert_eq!(layout.resolve("/var/lib/heat.kv"), Path::new("/var/lib/heat.kv"));
+    assert_eq!(layout.character_file("fud"), Path::new("/data/characters/fud/character.json"));
+
+    // Without FUD_HOME the paths stay what they always were
+    assert_eq!(Layout::new(".").resolve("./storage/memory.json"), Path::new("./storage/memory.json"));
+}
+
+#[test]
+fn test_doctor_passes_a_complete_layout() {
+    let home = temp_home();
+    with_character(&home);
+
+    let report = Layout::new(&home).doctor(Some("fud"));
+    assert!(report.is_ok(), "{:?}", report.errors);
+    assert!(home.join("storage").is_dir());
+    assert!(report.warnings.iter().any(|w| w.contains("charts")));
+
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn test_doctor_reports_what_stops_the_bot() {
+    let home = temp_home();
+    let report = Layout::new(&home).doctor(Some("fud"));
+    assert!(report.errors.iter().any(|e| e.contains("characters")));
+
+    with_character(&home);
+    let report = Layout::new(&home).doctor(Some("nobody"));
+    assert!(report.errors.iter().any(|e| e.contains("nobody")));
+
+    fs::create_dir_all(home.join("storage")).unwrap();
+    fs::write(home.join("storage/memory.json"), "{ not json").unwrap();
+    let report = Layout::new(&home).doctor(Some("fud"));
+    assert!(report.errors.iter().any(|e| e.contains("memory.json")));
+
+    let report = Layout::new(home.join("missing")).doctor(Some("fud"));
+    assert!(!report.is_ok());
+
+    fs::remove_dir_all(home).unwrap();
+}
@@ -0,0 +1,48 @@
+// src/core/tests/phrase_tracker_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::phrase_tracker::PhraseTracker;
+
+#[test]
+fn test_detects_repeated_phrase() {
+    let mut tracker = PhraseTracker::new(100, Duration::hours(1));
+    let now = Utc::now();
+    tracker.record_at("dev is selling again", now);
+
+    assert!(tracker.contains_recent_at("Dev Is Selling right now", now));
+    assert!(!tracker.contains_recent_at("liquidity looks thin", now));
+}
+
+#[test]
+fn test_phrases_expire_by_age() {
+    let mut tracker = PhraseTracker::new(100, Duration::hours(1));
+    let now = Utc::now();
+    tracker.record_at("dev is selling again", now - Duration::hours(2));
+
+    assert!(!tracker.contains_recent_at("dev is selling again", now));
+}
+
+#[test]
+fn test_oldest_phrases_evicted_first() {
+    // Each three-word text is exactly one phrase
+    let mut tracker = PhraseTracker::new(2, Duration::hours(1));
+    let now = Utc::now();
+    tracker.record_at("first rug pull", now);
+    tracker.record_at("second rug pull", now);
+    tracker.record_at("third rug pull", now);
+
+    assert!(!tracker.contains_recent_at("first rug pull", now));
+    assert!(tracker.contains_recent_at("second rug pull", now));
+    assert!(tracker.contains_recent_at("third rug pull", now));
+}
+
+#[test]
+fn test_repeated_phrase_survives_until_last_copy_evicted() {
+    let mut tracker = PhraseTracker::new(2, Duration::hours(1));
+    let now = Utc::now();
+    tracker.record_at("dev is selling", now);
+    tracker.record_at("dev is selling", now);
+    tracker.record_at("charts look dead", now);
+
+    assert!(tracker.contains_recent_at("dev is selling", now));
+}
@@ -0,0 +1,93 @@
+// src/core/tests/pipeline_tests.rs
+
+use async_trait::async_trait;
+use super::super::agent::{Agent, GenerationTask};
+use super::super::character::{GenerationSettings, PipelineSettings, StreamGuardSettings};
+use super::super::pipeline::{Critique, Length, Phase, Pipeline, Stage};
+use super::super::stream_guard::StreamGuard;
+
+fn agent() -> Agent {
+    Agent::new("test-key", "You are a test character.", GenerationSettings::default())
+}
+
+struct Shout;
+
+#[async_trait]
+impl Stage for Shout {
+    fn name(&self) -> &str {
+        "shout"
+    }
+
+    fn phase(&self) -> Phase {
+        Phase::Post
+    }
+
+    async fn apply(&self, _agent: &Agent, _task: GenerationTask, text: String) -> Result<String, anyhow::Error> {
+        Ok(text.to_uppercase())
+    }
+}
+
+#[test]
+fn test_default_and_configured_stages() {
+    assert_eq!(Pipeline::default().names(), vec!["sanitize", "safety", "trim", "style"]);
+
+    let settings = PipelineSettings {
+        stages: vec!["trim".to_string(), "translate".to_string(), "critique".to_string()],
+    };
+    assert_eq!(Pipeline::from_settings(&settings).names(), vec!["trim", "critique"]);
+}
+
+#[tokio::test]
+async fn test_run_only_applies_stages_of_the_phase() {
+    let agent = agent();
+    let pipeline = Pipeline::from_settings(&PipelineSettings { stages: vec!["sanitize".to_string(), "trim".to_string()] })
+        .with_stage(Box::new(Shout));
+
+    let raw = "Here's a tweet:\n\"dev is selling\"  ".to_string();
+    let completed = pipeline.run(&agent, GenerationTask::Fud, Phase::Completion, raw).await.unwrap();
+    assert_eq!(completed, "dev is selling");
+
+    let posted = pipeline.run(&agent, GenerationTask::Fud, Phase::Post, "  dev is selling ".to_string()).await.unwrap();
+    assert_eq!(posted, "DEV IS SELLING");
+}
+
+#[tokio::test]
+async fn test_safety_rejects_banned_terms() {
+    let guard = StreamGuardSettings { enabled: true, blocklist: vec!["guaranteed".to_string()], max_restarts: 0 };
+    let agent = agent().with_stream_guard(&guard, &[]);
+    let pipeline = Pipeline::default();
+
+    let error = pipeline
+        .run(&agent, GenerationTask::Fud, Phase::Completion, "100x Guaranteed, trust me".to_string())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("guaranteed"));
+
+    // Classification answers aren't written text, so nothing is guarded
+    let answer = pipeline.run(&agent, GenerationTask::Classify, Phase::Completion, "guaranteed".to_string()).await;
+    assert_eq!(answer.unwrap(), "guaranteed");
+}
+
+#[test]
+fn test_critique_keeps_the_draft_over_a_banned_rewrite() {
+    let guard = StreamGuard::new(["guaranteed"]);
+    let draft = "dev wallet moved again".to_string();
+    assert_eq!(Critique::accept(Some(&guard), draft.clone(), "100x GUARANTEED".to_string()), draft);
+    assert_eq!(Critique::accept(Some(&guard), draft.clone(), String::new()), draft);
+    assert_eq!(Critique::accept(Some(&guard), draft.clone(), "dev sold".to_string()), "dev sold");
+    assert_eq!(Critique::accept(None, draft, "100x guaranteed".to_string()), "100x guaranteed");
+}
+
+#[test]
+fn test_length_cuts_back_to_a_whole_sentence() {
+    let first = "this chart is a crime scene.";
+    let second = " the dev wallet moved again!";
+    let tail = format!(" {}", "and the rest just keeps going ".repeat(10));
+    let text = format!("{}{}{}", first, second, tail);
+    assert_eq!(Length::shorten(&text), format!("{}{}", first, second));
+
+    assert_eq!(Length::shorten("short and fine"), "short and fine");
+    // Nothing to cut at, so it's left for the runtime to regenerate
+    let unbroken = "a".repeat(300);
+    assert_eq!(Length::shorten(&unbroken), unbroken);
+}
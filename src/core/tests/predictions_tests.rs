@@ -0,0 +1,69 @@
+// src/core/tests/predictions_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::predictions::PredictionRecord;
+use crate::models::Prediction;
+
+fn call() -> Prediction {
+    let made_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    Prediction::call("Mint111", "RUG", 183_000.0, 50.0, 72, made_at)
+}
+
+#[test]
+fn test_call_rounds_target_and_states_claim() {
+    let prediction = call();
+    assert_eq!(prediction.target_market_cap, 92_000.0);
+    assert_eq!(prediction.claim(), "sub-$92K mcap within 72h");
+    assert!(prediction.is_open());
+}
+
+#[test]
+fn test_hit_counts_lowest_seen_before_deadline() {
+    let mut prediction = call();
+    assert!(!prediction.is_due(prediction.made_at + Duration::hours(71)));
+
+    prediction.observe(85_000.0, prediction.made_at + Duration::hours(30));
+    let deadline = prediction.deadline;
+    // Bounced back by the deadline, but it did go under the call
+    let outcome = prediction.grade(Some(150_000.0), deadline).clone();
+    assert!(outcome.hit);
+    assert_eq!(outcome.final_market_cap, 150_000.0);
+    assert!(prediction.result_info().contains("Lowest market cap seen: $85.0K"));
+    assert!(!prediction.is_due(deadline + Duration::hours(1)));
+}
+
+#[test]
+fn test_dip_after_deadline_does_not_count() {
+    let mut prediction = call();
+    let late = prediction.deadline + Duration::minutes(20);
+    // The first check after the deadline catches a dip under the target
+    prediction.observe(60_000.0, late);
+    let outcome = prediction.grade(Some(60_000.0), late).clone();
+    assert!(!outcome.hit);
+    assert_eq!(prediction.lowest_market_cap, 183_000.0);
+    assert_eq!(outcome.final_market_cap, 60_000.0);
+}
+
+#[test]
+fn test_unreachable_token_is_graded_late_on_what_was_seen() {
+    let mut prediction = call();
+    prediction.observe(120_000.0, prediction.made_at + Duration::hours(10));
+    assert!(!prediction.is_overdue(prediction.deadline + Duration::hours(23)));
+    assert!(prediction.is_overdue(prediction.deadline + Duration::hours(24)));
+
+    let outcome = prediction.grade(None, prediction.deadline + Duration::hours(24)).clone();
+    assert!(!outcome.hit);
+    assert_eq!(outcome.final_market_cap, 120_000.0);
+}
+
+#[test]
+fn test_record_counts_wins_losses_and_open_calls() {
+    let mut win = call();
+    win.grade(Some(50_000.0), win.deadline);
+    let mut loss = call();
+    loss.grade(Some(200_000.0), loss.deadline);
+    let record = PredictionRecord::from_predictions(&[win, loss.clone(), loss, call()]);
+
+    assert_eq!(record, PredictionRecord { wins: 1, losses: 2, open: 1 });
+    assert_eq!(record.line(), "prediction record: 1-2");
+}
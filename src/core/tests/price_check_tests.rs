@@ -0,0 +1,48 @@
+// src/core/tests/price_check_tests.rs
+
+use super::super::character::{MismatchAction, PriceCheckSettings};
+use super::super::price_check::PriceCheck;
+
+const SUMMARY: &str = "Token: $RUG\nMarket Cap: $1.0B\nLiquidity: $12.0K\n";
+
+#[test]
+fn test_compare() {
+    assert_eq!(PriceCheck::compare(1_000_000.0, Some(1_500_000.0), 3.0), PriceCheck::Agree);
+    assert_eq!(PriceCheck::compare(1_500_000.0, Some(1_000_000.0), 3.0), PriceCheck::Agree);
+    assert_eq!(
+        PriceCheck::compare(1_000_000_000.0, Some(2_000_000.0), 3.0),
+        PriceCheck::Mismatch { ratio: 500.0 }
+    );
+    assert_eq!(PriceCheck::compare(1_000_000.0, None, 3.0), PriceCheck::Unverified);
+    assert_eq!(PriceCheck::compare(0.0, Some(1_000_000.0), 3.0), PriceCheck::Unverified);
+}
+
+#[test]
+fn test_skip_numbers_drops_market_cap() {
+    let settings = PriceCheckSettings::default();
+    let check = PriceCheck::compare(1_000_000_000.0, Some(2_000_000.0), settings.max_ratio);
+    let summary = check.apply(SUMMARY, 1_000_000_000.0, Some(2_000_000.0), &settings);
+
+    assert!(!summary.contains("Market Cap:"));
+    assert!(summary.contains("Liquidity: $12.0K"));
+    assert!(summary.contains("don't quote a market cap"));
+}
+
+#[test]
+fn test_mock_includes_both_figures() {
+    let settings = PriceCheckSettings {
+        on_mismatch: MismatchAction::Mock,
+        ..Default::default()
+    };
+    let check = PriceCheck::compare(1_000_000_000.0, Some(2_000_000.0), settings.max_ratio);
+    let summary = check.apply(SUMMARY, 1_000_000_000.0, Some(2_000_000.0), &settings);
+
+    assert!(summary.starts_with(SUMMARY));
+    assert!(summary.contains("$1.0B") && summary.contains("$2.0M") && summary.contains("500x"));
+}
+
+#[test]
+fn test_agreement_leaves_summary_alone() {
+    let settings = PriceCheckSettings::default();
+    assert_eq!(PriceCheck::Agree.apply(SUMMARY, 1.0, Some(1.0), &settings), SUMMARY);
+}
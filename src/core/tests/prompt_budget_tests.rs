@@ -0,0 +1,46 @@
+// src/core/tests/prompt_budget_tests.rs
+
+use super::super::prompt_budget::{estimate_tokens, fit, render, PromptSection};
+
+fn sections() -> Vec<PromptSection> {
+    let examples: Vec<String> = (0..20).map(|i| format!("example post number {}", i)).collect();
+    vec![
+        PromptSection::fixed("You are a character named FudAI."),
+        PromptSection::trimmable("Lore:", "got rugged\nalways buys the top", 50),
+        PromptSection::trimmable("Post Examples:", &examples.join("\n"), 10),
+        PromptSection::fixed("Respond back with a tweet."),
+    ]
+}
+
+#[test]
+fn test_render_matches_builder_layout() {
+    let rendered = render(&sections()[..2]);
+    assert_eq!(rendered, "You are a character named FudAI.Lore:\ngot rugged\nalways buys the top\n");
+}
+
+#[test]
+fn test_fits_without_trimming_when_under_budget() {
+    let fitted = fit(&sections(), 10_000);
+    assert!(fitted.trimmed.is_empty());
+    assert_eq!(fitted.text, render(&sections()));
+}
+
+#[test]
+fn test_examples_trimmed_before_lore() {
+    let full = estimate_tokens(&render(&sections()));
+    let fitted = fit(&sections(), full - 40);
+
+    assert_eq!(fitted.trimmed, vec!["Post Examples:".to_string()]);
+    assert!(fitted.tokens <= full - 40);
+    assert!(fitted.text.contains("always buys the top"));
+    assert!(fitted.text.contains("example post number 0"));
+    assert!(!fitted.text.contains("example post number 19"));
+}
+
+#[test]
+fn test_fixed_sections_survive_impossible_budget() {
+    let fitted = fit(&sections(), 1);
+
+    assert_eq!(fitted.trimmed.len(), 2);
+    assert_eq!(fitted.text, "You are a character named FudAI.Respond back with a tweet.");
+}
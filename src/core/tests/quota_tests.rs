@@ -0,0 +1,106 @@
+// src/core/tests/quota_tests.rs
+
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::BTreeMap;
+use super::super::character::ApiQuotaSettings;
+use super::super::quota::{QuotaBlock, QuotaLedger, QuotaPriority, QuotaUsage};
+use crate::models::ApiQuotaLedger;
+
+fn at(month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, month, day, hour, 0, 0).unwrap()
+}
+
+fn settings(limits: &[(&str, u64)]) -> ApiQuotaSettings {
+    ApiQuotaSettings {
+        limits: limits.iter().map(|(endpoint, limit)| (endpoint.to_string(), *limit)).collect(),
+        ..ApiQuotaSettings::default()
+    }
+}
+
+fn ledger_with(settings: &ApiQuotaSettings, calls: &[(&str, u64)], now: DateTime<Utc>) -> ApiQuotaLedger {
+    let mut ledger = ApiQuotaLedger::default();
+    let calls: BTreeMap<&str, u64> = calls.iter().copied().collect();
+    QuotaLedger::record(&mut ledger, settings, &calls, now);
+    ledger
+}
+
+#[test]
+fn test_window_follows_reset_day() {
+    let mut quota = settings(&[]);
+    quota.reset_day = 15;
+    assert_eq!(QuotaLedger::window(&quota, at(3, 20, 12)), (at(3, 15, 0), at(4, 15, 0)));
+    assert_eq!(QuotaLedger::window(&quota, at(3, 2, 12)), (at(2, 15, 0), at(3, 15, 0)));
+    assert_eq!(QuotaLedger::window(&quota, at(3, 15, 0)), (at(3, 15, 0), at(4, 15, 0)));
+}
+
+#[test]
+fn test_record_accumulates_and_resets_with_the_window() {
+    let quota = settings(&[("tweets", 100)]);
+    let mut ledger = ledger_with(&quota, &[("tweets", 2), ("mentions", 1)], at(3, 5, 0));
+    let more: BTreeMap<&str, u64> = [("tweets", 3)].into_iter().collect();
+    assert!(QuotaLedger::record(&mut ledger, &quota, &more, at(3, 20, 0)));
+    assert_eq!(QuotaLedger::used(&ledger, "tweets"), 5);
+    assert_eq!(QuotaLedger::used(&ledger, "mentions"), 1);
+    // Nothing new and nothing rolled over
+    assert!(!QuotaLedger::record(&mut ledger, &quota, &BTreeMap::new(), at(3, 21, 0)));
+
+    assert!(QuotaLedger::record(&mut ledger, &quota, &BTreeMap::new(), at(4, 1, 0)));
+    assert_eq!(QuotaLedger::used(&ledger, "tweets"), 0);
+    assert_eq!(ledger.window_start, Some(at(4, 1, 0)));
+}
+
+#[test]
+fn test_paced_allowance() {
+    // 30-day window, 300 calls: 10 a day
+    let (start, end) = (at(4, 1, 0), at(5, 1, 0));
+    assert_eq!(QuotaLedger::paced_allowance(300, start, end, start), 10);
+    assert_eq!(QuotaLedger::paced_allowance(300, start, end, at(4, 11, 0)), 110);
+    assert_eq!(QuotaLedger::paced_allowance(300, start, end, at(4, 30, 12)), 300);
+}
+
+#[test]
+fn test_sweeps_stop_before_replies_and_posts() {
+    let quota = settings(&[("tweets", 300), ("mentions", 300)]);
+    let now = at(4, 3, 0);
+
+    // Two days in, 40 mentions reads is ahead of the 30 the pace allows
+    let ledger = ledger_with(&quota, &[("mentions", 40), ("tweets", 5)], now);
+    let block = QuotaLedger::check(&ledger, &quota, QuotaPriority::Sweep, &["mentions", "tweets"], now);
+    assert_eq!(block, Some(QuotaBlock::OverPace { endpoint: "mentions".to_string(), used: 40, allowed: 30 }));
+    assert_eq!(QuotaLedger::check(&ledger, &quota, QuotaPriority::Reply, &["tweets"], now), None);
+    assert_eq!(QuotaLedger::check(&ledger, &quota, QuotaPriority::Post, &["tweets"], now), None);
+}
+
+#[test]
+fn test_replies_leave_the_post_reserve() {
+    // 20% of 300 is kept for scheduled posts
+    let quota = settings(&[("tweets", 300)]);
+    let now = at(4, 29, 0);
+    let ledger = ledger_with(&quota, &[("tweets", 240)], now);
+    assert_eq!(
+        QuotaLedger::check(&ledger, &quota, QuotaPriority::Reply, &["tweets"], now),
+        Some(QuotaBlock::PostReserve { remaining: 60 })
+    );
+    assert_eq!(QuotaLedger::check(&ledger, &quota, QuotaPriority::Post, &["tweets"], now), None);
+
+    let ledger = ledger_with(&quota, &[("tweets", 300)], now);
+    assert_eq!(
+        QuotaLedger::check(&ledger, &quota, QuotaPriority::Post, &["tweets"], now),
+        Some(QuotaBlock::Exhausted { endpoint: "tweets".to_string(), limit: 300 })
+    );
+}
+
+#[test]
+fn test_unlisted_endpoints_are_only_tracked() {
+    let quota = settings(&[("tweets", 300)]);
+    let now = at(4, 2, 0);
+    let ledger = ledger_with(&quota, &[("search", 5_000)], now);
+    assert_eq!(QuotaLedger::check(&ledger, &quota, QuotaPriority::Sweep, &["search"], now), None);
+    assert_eq!(
+        QuotaLedger::usage(&ledger),
+        vec![
+            QuotaUsage { endpoint: "search".to_string(), calls: 5_000, limit: None },
+            QuotaUsage { endpoint: "tweets".to_string(), calls: 0, limit: Some(300) },
+        ]
+    );
+}
@@ -0,0 +1,65 @@
+// src/core/tests/rate_limiter_tests.rs
+
+use chrono::{TimeZone, Utc};
+use std::collections::BTreeMap;
+use super::super::character::ApiQuotaSettings;
+use super::super::config::{RedisConfig, Secret};
+use super::super::quota::QuotaLedger;
+use super::super::rate_limiter::{LocalLimiter, RateLimiter, RedisLimiter};
+use crate::models::ApiQuotaLedger;
+
+fn settings() -> ApiQuotaSettings {
+    ApiQuotaSettings {
+        limits: [("tweets".to_string(), 100)].into_iter().collect(),
+        ..ApiQuotaSettings::default()
+    }
+}
+
+// Nothing listens on port 1, so the connection is refused straight away
+fn unreachable() -> RedisLimiter {
+    RedisLimiter::new(&RedisConfig {
+        url: Secret::new("redis://127.0.0.1:1"),
+        key_prefix: "test:quota".to_string(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_local_limiter_keeps_the_ledger_as_before() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 3, 12, 0, 0).unwrap();
+    let calls: BTreeMap<&str, u64> = [("tweets", 2), ("mentions", 1)].into_iter().collect();
+
+    let mut expected = ApiQuotaLedger::default();
+    QuotaLedger::record(&mut expected, &settings(), &calls, now);
+    let mut ledger = ApiQuotaLedger::default();
+    assert!(LocalLimiter.record(&mut ledger, &settings(), &calls, now));
+    assert_eq!(ledger.calls, expected.calls);
+    assert_eq!(ledger.window_start, expected.window_start);
+}
+
+#[test]
+fn test_redis_keys_are_per_window_and_endpoint() {
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    assert_eq!(unreachable().key(start, "tweets"), "test:quota:2024-05-01:tweets");
+}
+
+#[test]
+fn test_redis_limiter_counts_locally_while_unreachable() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 3, 12, 0, 0).unwrap();
+    let mut limiter = unreachable();
+    let mut ledger = ApiQuotaLedger::default();
+
+    let calls: BTreeMap<&str, u64> = [("tweets", 2)].into_iter().collect();
+    assert!(limiter.record(&mut ledger, &settings(), &calls, now));
+    let calls: BTreeMap<&str, u64> = [("tweets", 3)].into_iter().collect();
+    limiter.record(&mut ledger, &settings(), &calls, now);
+
+    assert_eq!(QuotaLedger::used(&ledger, "tweets"), 5);
+    assert_eq!(limiter.unsent().get("tweets"), Some(&5));
+
+    // A new window drops what was held back for the old one
+    let next_month = Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap();
+    limiter.record(&mut ledger, &settings(), &BTreeMap::new(), next_month);
+    assert!(limiter.unsent().is_empty());
+    assert_eq!(QuotaLedger::used(&ledger, "tweets"), 0);
+}
@@ -0,0 +1,69 @@
+// src/core/tests/reply_limits_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::character::ReplyLimitSettings;
+use super::super::reply_limits::{ReplyLimit, ReplyLimits};
+use crate::models::{Memory, ThreadDepth, UserCooldown};
+
+fn memory_with_thread(depth: u32) -> Memory {
+    let mut memory = Memory::default();
+    memory.thread_depths.push(ThreadDepth {
+        conversation_id: "100".to_string(),
+        depth,
+        last_reply: Utc::now(),
+    });
+    memory
+}
+
+#[test]
+fn test_stops_at_max_thread_depth() {
+    let settings = ReplyLimitSettings::default();
+    let now = Utc::now();
+
+    assert_eq!(ReplyLimits::check(&settings, &memory_with_thread(2), "100", Some("7"), 0, now), None);
+    assert_eq!(
+        ReplyLimits::check(&settings, &memory_with_thread(3), "100", Some("7"), 0, now),
+        Some(ReplyLimit::ThreadDepth { depth: 3 })
+    );
+    // Other conversations are unaffected
+    assert_eq!(ReplyLimits::check(&settings, &memory_with_thread(3), "200", Some("7"), 0, now), None);
+}
+
+#[test]
+fn test_counts_replies_planned_this_sweep() {
+    let settings = ReplyLimitSettings::default();
+    let limit = ReplyLimits::check(&settings, &memory_with_thread(1), "100", None, 2, Utc::now());
+    assert_eq!(limit, Some(ReplyLimit::ThreadDepth { depth: 3 }));
+}
+
+#[test]
+fn test_zero_depth_disables_limit() {
+    let settings = ReplyLimitSettings { max_thread_depth: 0, ..Default::default() };
+    assert_eq!(ReplyLimits::check(&settings, &memory_with_thread(50), "100", None, 0, Utc::now()), None);
+}
+
+#[test]
+fn test_author_cooldown_blocks_until_it_expires() {
+    let settings = ReplyLimitSettings::default();
+    let now = Utc::now();
+    let until = now + Duration::hours(2);
+    let mut memory = Memory::default();
+    memory.user_cooldowns.push(UserCooldown { author_id: "7".to_string(), until });
+
+    assert_eq!(
+        ReplyLimits::check(&settings, &memory, "300", Some("7"), 0, now),
+        Some(ReplyLimit::CoolingDown { until })
+    );
+    assert_eq!(ReplyLimits::check(&settings, &memory, "300", Some("8"), 0, now), None);
+    assert_eq!(ReplyLimits::check(&settings, &memory, "300", Some("7"), 0, until + Duration::minutes(1)), None);
+}
+
+#[test]
+fn test_cooldown_end_respects_setting() {
+    let now = Utc::now();
+    let settings = ReplyLimitSettings::default();
+    assert_eq!(ReplyLimits::cooldown_end(&settings, now), Some(now + Duration::hours(12)));
+
+    let off = ReplyLimitSettings { user_cooldown_hours: 0, ..Default::default() };
+    assert_eq!(ReplyLimits::cooldown_end(&off, now), None);
+}
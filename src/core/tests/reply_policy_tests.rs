@@ -0,0 +1,82 @@
+// src/core/tests/reply_policy_tests.rs
+
+use chrono::{Duration, Utc};
+
+use crate::core::character::ReplyPolicySettings;
+use crate::core::reply_policy::{PolicyReason, ReplyPolicy};
+use crate::models::MentionAuthor;
+
+fn author(id: u64, age_days: i64, followers: u64, verified: bool) -> MentionAuthor {
+    MentionAuthor {
+        id,
+        username: format!("user{}", id),
+        created_at: Some(Utc::now() - Duration::days(age_days)),
+        followers: Some(followers),
+        verified: Some(verified),
+    }
+}
+
+#[test]
+fn test_default_policy_allows_everyone() {
+    let policy = ReplyPolicy::new();
+    let settings = ReplyPolicySettings::default();
+    assert!(!settings.is_enabled());
+    assert_eq!(policy.check(&settings, Some(1), None, Utc::now()), None);
+}
+
+#[test]
+fn test_followers_only() {
+    let now = Utc::now();
+    let settings = ReplyPolicySettings { followers_only: true, ..ReplyPolicySettings::default() };
+    let mut policy = ReplyPolicy::new();
+    // Nothing synced yet, so nobody can be checked
+    assert!(policy.needs_follower_sync(&settings, now));
+    assert_eq!(policy.check(&settings, Some(1), None, now), Some(PolicyReason::Unknown));
+
+    policy.set_followers([1, 2], true, now);
+    assert!(!policy.needs_follower_sync(&settings, now + Duration::hours(1)));
+    assert!(policy.needs_follower_sync(&settings, now + Duration::hours(6)));
+    assert_eq!(policy.check(&settings, Some(1), None, now), None);
+    assert_eq!(policy.check(&settings, Some(3), None, now), Some(PolicyReason::NotFollower));
+
+    // Past the sync's page cap, an account that isn't in the list may still follow us
+    policy.set_followers([1, 2], false, now);
+    assert_eq!(policy.check(&settings, Some(1), None, now), None);
+    assert_eq!(policy.check(&settings, Some(3), None, now), Some(PolicyReason::Unknown));
+}
+
+#[test]
+fn test_author_thresholds() {
+    let new_account = author(4, 3, 5_000, true);
+    let now = Utc::now();
+    let settings = ReplyPolicySettings {
+        verified_only: true,
+        min_followers: 1_000,
+        min_account_age_days: 30,
+        ..ReplyPolicySettings::default()
+    };
+    let policy = ReplyPolicy::new();
+    let check = |author: &MentionAuthor| policy.check(&settings, Some(author.id), Some(author), now);
+
+    assert_eq!(check(&author(1, 365, 5_000, true)), None);
+    assert_eq!(check(&author(2, 365, 5_000, false)), Some(PolicyReason::NotVerified));
+    assert_eq!(check(&author(3, 365, 200, true)), Some(PolicyReason::FewFollowers { followers: 200 }));
+    assert_eq!(check(&new_account), Some(PolicyReason::NewAccount { age_days: 3 }));
+    assert_eq!(policy.check(&settings, Some(5), None, now), Some(PolicyReason::Unknown));
+}
+
+#[test]
+fn test_exempt_accounts_always_pass() {
+    let now = Utc::now();
+    let settings = ReplyPolicySettings {
+        followers_only: true,
+        verified_only: true,
+        exempt: vec!["@User7".to_string(), "8".to_string()],
+        ..ReplyPolicySettings::default()
+    };
+    let policy = ReplyPolicy::new();
+    assert!(settings.needs_author_lookup());
+    assert_eq!(policy.check(&settings, Some(7), Some(&author(7, 1, 0, false)), now), None);
+    assert_eq!(policy.check(&settings, Some(8), None, now), None);
+    assert_eq!(policy.check(&settings, Some(9), Some(&author(9, 1, 0, false)), now), Some(PolicyReason::Unknown));
+}
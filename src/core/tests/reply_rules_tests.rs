@@ -0,0 +1,55 @@
+// src/core/tests/reply_rules_tests.rs
+
+use super::super::approval::{ApprovalCommand, Approvals};
+use super::super::reply_rules::{ReplyRule, ReplyRules, RuleAction};
+
+fn rules() -> Vec<ReplyRule> {
+    serde_json::from_str(
+        r#"[
+            { "name": "no gm", "any": ["gm", "gn"], "regex": "^(?i)g[mn]\\W*$", "action": "ignore" },
+            { "all": ["wen", "moon"], "action": "canned", "replies": ["never"] },
+            { "regex": "(?i)what about \\$?(\\w+)", "action": "token_lookup" },
+            { "name": "legal", "any": ["lawyer", "SEC"], "action": "escalate" },
+            { "action": "insult" }
+        ]"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_first_matching_rule_wins() {
+    let rules = rules();
+    let matched = ReplyRules::evaluate(&rules, "GM").unwrap();
+    assert_eq!((matched.label.as_str(), matched.action), ("no gm", RuleAction::Ignore));
+
+    // "gm" appears but the regex doesn't match, so the next rules get a look
+    let matched = ReplyRules::evaluate(&rules, "gm wen moon ser").unwrap();
+    assert_eq!(matched.label, "reply_rules[1]");
+    assert_eq!(matched.canned_reply.as_deref(), Some("never"));
+
+    assert_eq!(ReplyRules::evaluate(&rules, "my lawyer says hi").unwrap().action, RuleAction::Escalate);
+    assert_eq!(ReplyRules::evaluate(&rules, "the sec is coming").unwrap().label, "legal");
+}
+
+#[test]
+fn test_regex_capture_and_unmatched_mentions() {
+    let rules = rules();
+    let matched = ReplyRules::evaluate(&rules, "ok but what about $BONK").unwrap();
+    assert_eq!(matched.action, RuleAction::TokenLookup);
+    assert_eq!(matched.capture.as_deref(), Some("BONK"));
+    assert!(matched.canned_reply.is_none());
+
+    // The insult rule sets no conditions, so it never matches
+    assert!(ReplyRules::evaluate(&rules, "wen lambo").is_none());
+    assert!(!ReplyRules::has_conditions(&rules[4]));
+}
+
+#[test]
+fn test_approval_commands() {
+    assert_eq!(Approvals::parse_command("/approve 1790000000000000000"), Some(ApprovalCommand::Approve("1790000000000000000".to_string())));
+    assert_eq!(Approvals::parse_command("/Reject@fudbot 42"), Some(ApprovalCommand::Reject("42".to_string())));
+    assert_eq!(Approvals::parse_command("pending"), Some(ApprovalCommand::Pending));
+    assert_eq!(Approvals::parse_command("/approve"), None);
+    assert_eq!(Approvals::parse_command("/character current"), None);
+    assert_eq!(Approvals::pending_summary(&[]), "No replies waiting for approval");
+}
@@ -0,0 +1,133 @@
+// src/core/tests/responded_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use std::fs;
+use super::super::kv_store::{KvStore, LogStore};
+use super::super::responded::{parse_entry, RespondedIndex, ResponseState};
+use crate::memory::MemoryStore;
+use crate::models::{Memory, QueuedReply, Tweet, TweetType};
+
+fn reply(reply_to: &str, timestamp: chrono::DateTime<Utc>) -> Tweet {
+    Tweet {
+        text: "ngmi".to_string(),
+        timestamp,
+        tweet_type: TweetType::Reply,
+        reply_to: Some(reply_to.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_entries_read_state_and_time() {
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    assert_eq!(parse_entry(&format!("sending {}", at.to_rfc3339())), (ResponseState::Sending, Some(at)));
+    assert_eq!(parse_entry(&format!("unrepliable {}", at.to_rfc3339())), (ResponseState::Unrepliable, Some(at)));
+    // Older stores only kept the time
+    assert_eq!(parse_entry(&at.to_rfc3339()), (ResponseState::Handled, Some(at)));
+    assert_eq!(parse_entry("garbage"), (ResponseState::Handled, None));
+}
+
+#[test]
+fn test_states_move_forward_and_release() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let mut index = RespondedIndex::new(Box::new(LogStore::in_memory()));
+    assert_eq!(index.state("1"), None);
+    index.record("1", ResponseState::Queued, now).unwrap();
+    index.record("1", ResponseState::Sending, now).unwrap();
+    assert_eq!(index.state("1"), Some(ResponseState::Sending));
+    index.record("1", ResponseState::Replied, now).unwrap();
+    assert_eq!(index.state("1"), Some(ResponseState::Replied));
+
+    index.record("2", ResponseState::Sending, now).unwrap();
+    assert!(index.release("2").unwrap());
+    assert!(!index.contains("2"));
+}
+
+#[test]
+fn test_seed_adds_what_memory_knows_once() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let cutoff = now - Duration::days(30);
+    let mut memory = Memory::default();
+    memory.tweets.push(reply("10", now - Duration::hours(1)));
+    memory.tweets.push(reply("11", now - Duration::days(40)));
+    memory.reply_queue.push(QueuedReply {
+        reply_to: "12".to_string(),
+        text: "cope".to_string(),
+        prompt: String::new(),
+        scheduled_at: now,
+        attempts: 0,
+        token_mint: None,
+        token_symbol: None,
+    });
+
+    let mut store = LogStore::in_memory();
+    store.insert("13", &format!("handled {}", now.to_rfc3339())).unwrap();
+    let mut index = RespondedIndex::new(Box::new(store));
+    assert_eq!(index.seed(&memory, cutoff, now).unwrap(), 2);
+    assert_eq!(index.state("10"), Some(ResponseState::Replied));
+    assert_eq!(index.state("12"), Some(ResponseState::Queued));
+    assert_eq!(index.state("11"), None);
+    assert_eq!(index.seed(&memory, cutoff, now).unwrap(), 0);
+}
+
+#[test]
+fn test_sending_claim_survives_a_restart() {
+    let dir = std::env::temp_dir().join(format!("chainfud-responded-{}", rand::random::<u64>()));
+    fs::create_dir_all(&dir).unwrap();
+    let now = Utc::now();
+    let store = MemoryStore::open_processed_tweets_in(&dir, now).unwrap();
+    let mut index = RespondedIndex::new(Box::new(store));
+    index.record("not-a-snowflake", ResponseState::Sending, now).unwrap();
+    index.record("stale-mention", ResponseState::Replied, now - Duration::days(60)).unwrap();
+
+    let reopened = RespondedIndex::new(Box::new(MemoryStore::open_processed_tweets_in(&dir, now).unwrap()));
+    assert_eq!(reopened.state("not-a-snowflake"), Some(ResponseState::Sending));
+    assert!(!reopened.contains("stale-mention"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_claim_takes_a_mention_only_once() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let mut index = RespondedIndex::new(Box::new(LogStore::in_memory()));
+
+    assert!(index.claim("1", now).unwrap());
+    assert_eq!(index.state("1"), Some(ResponseState::Sending));
+    assert!(!index.claim("1", now).unwrap());
+
+    index.record("2", ResponseState::Queued, now).unwrap();
+    assert!(index.claim("2", now).unwrap());
+
+    for state in [ResponseState::Handled, ResponseState::Held, ResponseState::Replied, ResponseState::Unrepliable] {
+        index.record("3", state, now).unwrap();
+        assert!(!index.claim("3", now).unwrap());
+        assert_eq!(index.state("3"), Some(state));
+    }
+}
+
+#[test]
+fn test_take_only_succeeds_for_new_mentions() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let mut index = RespondedIndex::new(Box::new(LogStore::in_memory()));
+    assert!(index.take("1", ResponseState::Queued, now).unwrap());
+    assert_eq!(index.state("1"), Some(ResponseState::Queued));
+    // A second instance that drafted the same mention doesn't queue it again
+    assert!(!index.take("1", ResponseState::Queued, now).unwrap());
+
+    index.record("2", ResponseState::Replied, now).unwrap();
+    assert!(!index.take("2", ResponseState::Held, now).unwrap());
+    assert_eq!(index.state("2"), Some(ResponseState::Replied));
+}
+
+#[test]
+fn test_forget_reply_only_drops_tweets_never_replied_to() {
+    let mut memory = Memory::default();
+    memory.tweets.push(reply("10", Utc::now()));
+    MemoryStore::rebuild_reply_index(&mut memory);
+    memory.replied_to.insert("20".to_string());
+
+    MemoryStore::forget_reply(&mut memory, "20");
+    MemoryStore::forget_reply(&mut memory, "10");
+    assert!(!MemoryStore::has_replied_to(&memory, "20"));
+    assert!(MemoryStore::has_replied_to(&memory, "10"));
+}
@@ -0,0 +1,68 @@
+// src/core/tests/sanitizer_tests.rs
+//
+// Shapes the model has actually wrapped tweets in. Add new ones here as they show up.
+
+use super::super::sanitizer::Sanitizer;
+
+#[test]
+fn test_clean_text_is_left_alone() {
+    for text in [
+        "$WIF dev wallet still holding 12% lmao",
+        "here's the thing: nobody is buying $BONK at these levels",
+        "note: dev sold 40% of supply an hour ago",
+        "\"community\" \"utility\" \"roadmap\" pick one",
+        "it's not a dip if it never recovers",
+        "ngmi\n\nthis chart is a ski slope",
+    ] {
+        assert_eq!(Sanitizer::clean(text), text);
+    }
+}
+
+#[test]
+fn test_wrapping_quotes_are_stripped() {
+    assert_eq!(Sanitizer::clean("\"$WIF is cooked\""), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("\u{201C}$WIF is cooked\u{201D}"), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("'liquidity is a rumor'"), "liquidity is a rumor");
+    // An apostrophe inside means the quotes may not be a wrapper
+    assert_eq!(Sanitizer::clean("'it's over' said the chart'"), "'it's over' said the chart'");
+}
+
+#[test]
+fn test_preambles_are_stripped() {
+    assert_eq!(Sanitizer::clean("Here's your tweet: $WIF is cooked"), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("Sure! Here is a FUD tweet about $WIF:\n\n\"$WIF is cooked\""), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("Okay, here's my reply:\n$WIF is cooked"), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("Tweet: $WIF is cooked"), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("Here\u{2019}s a revised version that fits:\n$WIF is cooked"), "$WIF is cooked");
+}
+
+#[test]
+fn test_markdown_fences_are_stripped() {
+    assert_eq!(Sanitizer::clean("```\n$WIF is cooked\n```"), "$WIF is cooked");
+    assert_eq!(Sanitizer::clean("```text\n$WIF is cooked\n\nngmi\n```"), "$WIF is cooked\n\nngmi");
+    assert_eq!(Sanitizer::clean("Here's the tweet:\n```\n\"$WIF is cooked\"\n```"), "$WIF is cooked");
+}
+
+#[test]
+fn test_trailing_commentary_is_dropped() {
+    assert_eq!(
+        Sanitizer::clean("$WIF is cooked\n\n(Note: I kept it under 280 characters and all lowercase)"),
+        "$WIF is cooked"
+    );
+    assert_eq!(
+        Sanitizer::clean("$WIF is cooked\n\nThis tweet uses the liquidity numbers to mock the holders."),
+        "$WIF is cooked"
+    );
+    assert_eq!(Sanitizer::clean("$WIF is cooked\nCharacter count: 15"), "$WIF is cooked");
+    assert_eq!(
+        Sanitizer::clean("\"$WIF is cooked\"\n\nI've kept it short and punchy. Let me know if you want another!"),
+        "$WIF is cooked"
+    );
+    assert_eq!(Sanitizer::clean("$WIF is cooked\n\nNote: the tone is sarcastic as requested"), "$WIF is cooked");
+}
+
+#[test]
+fn test_all_wrapper_keeps_the_original() {
+    assert_eq!(Sanitizer::clean("  Here's your tweet:  "), "Here's your tweet:");
+    assert_eq!(Sanitizer::clean("\"\""), "\"\"");
+}
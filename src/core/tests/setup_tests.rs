@@ -0,0 +1,62 @@
+// src/core/tests/setup_tests.rs
+
+use std::io::Cursor;
+
+use super::super::paths::Layout;
+use super::super::setup::{merge_env, starter_character, Setup};
+
+fn pairs(values: &[(&str, &str)]) -> Vec<(String, String)> {
+    values.iter().map(|(var, value)| (var.to_string(), value.to_string())).collect()
+}
+
+#[test]
+fn test_merge_env_replaces_and_appends() {
+    let existing = "# keys\nANTHROPIC_API_KEY=old\nTRENDING_CACHE_TTL_SECONDS=60\nexport CHARACTER_NAME=fud\nANTHROPIC_API_KEY=older\n";
+    let merged = merge_env(
+        existing,
+        &pairs(&[("ANTHROPIC_API_KEY", "new"), ("CHARACTER_NAME", "ripper"), ("SOLANA_TRACKER_API_KEY", "st")]),
+    );
+    assert_eq!(
+        merged,
+        "# keys\nANTHROPIC_API_KEY=new\nTRENDING_CACHE_TTL_SECONDS=60\nCHARACTER_NAME=ripper\nSOLANA_TRACKER_API_KEY=st\n"
+    );
+}
+
+#[test]
+fn test_merge_env_quotes_values_dotenv_would_cut() {
+    let merged = merge_env("", &pairs(&[("A", "has space"), ("B", "x#y"), ("C", "say \"hi\"")]));
+    assert_eq!(merged, "A=\"has space\"\nB=\"x#y\"\nC=\"say \\\"hi\\\"\"\n");
+}
+
+#[test]
+fn test_starter_character_renames_template() {
+    let template = std::fs::read_to_string("characters/fud/character.json").unwrap();
+    let starter: serde_json::Value = serde_json::from_str(&starter_character(&template, "ripper").unwrap()).unwrap();
+    let original: serde_json::Value = serde_json::from_str(&template).unwrap();
+    assert_eq!(starter["character"], "ripper");
+    assert_eq!(starter["alias"], "ripper");
+    assert_eq!(starter["instructions"]["base"], "You are a character named ripper.");
+    assert_eq!(starter["lore"], original["lore"]);
+}
+
+#[test]
+fn test_ask_uses_default_and_reasks_when_required() {
+    let input = Cursor::new("\n\nvalue\n\n");
+    let mut output = Vec::new();
+    let mut setup = Setup::new(input, &mut output, Layout::new("."));
+    assert_eq!(setup.ask("KEY", None, None, false).unwrap().as_deref(), Some("value"));
+    assert_eq!(setup.ask("KEY", Some("kept"), None, false).unwrap().as_deref(), Some("kept"));
+    assert_eq!(setup.ask("KEY", None, None, true).unwrap(), None);
+    drop(setup);
+    assert_eq!(String::from_utf8(output).unwrap().matches("KEY: ").count(), 3);
+}
+
+#[test]
+fn test_confirm_defaults_on_empty_answer() {
+    let input = Cursor::new("\nn\nYES\n");
+    let mut setup = Setup::new(input, Vec::new(), Layout::new("."));
+    assert!(setup.confirm("Again?", true).unwrap());
+    assert!(!setup.confirm("Again?", true).unwrap());
+    assert!(setup.confirm("Again?", false).unwrap());
+    assert!(!setup.confirm("Again?", false).unwrap());
+}
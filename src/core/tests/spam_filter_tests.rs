@@ -0,0 +1,98 @@
+// src/core/tests/spam_filter_tests.rs
+
+use chrono::{Duration, Utc};
+
+use crate::core::character::SpamFilterSettings;
+use crate::core::spam_filter::{SpamFilter, SpamReason};
+use crate::models::MentionAuthor;
+
+fn author(id: u64, username: &str, age_days: i64, followers: u64) -> MentionAuthor {
+    MentionAuthor {
+        id,
+        username: username.to_string(),
+        created_at: Some(Utc::now() - Duration::days(age_days)),
+        followers: Some(followers),
+        verified: None,
+    }
+}
+
+#[test]
+fn test_link_only_mentions() {
+    assert!(SpamFilter::is_link_only("@fud_bot https://t.co/abc123"));
+    assert!(SpamFilter::is_link_only("@fud_bot 👉 pump.fun/coin/xyz"));
+    assert!(!SpamFilter::is_link_only("@fud_bot is this legit? https://t.co/abc123"));
+    assert!(!SpamFilter::is_link_only("@fud_bot what about $BONK"));
+}
+
+#[test]
+fn test_identical_text_counts_distinct_mentions() {
+    let settings = SpamFilterSettings::default();
+    let mut filter = SpamFilter::new();
+    let now = Utc::now();
+
+    assert_eq!(filter.check_at(&settings, "1", "@fud_bot gm check my bio", None, now), None);
+    assert_eq!(filter.check_at(&settings, "2", "@other GM   check my bio", None, now), None);
+    // Seeing the same mention again on the next sweep doesn't count twice
+    assert_eq!(filter.check_at(&settings, "2", "@other GM check my bio", None, now), None);
+    assert_eq!(
+        filter.check_at(&settings, "3", "gm check my bio @fud_bot", None, now),
+        Some(SpamReason::Repeated { count: 3 })
+    );
+    // Old sightings fall out of the window
+    let later = now + Duration::hours(25);
+    assert_eq!(filter.check_at(&settings, "4", "@fud_bot gm check my bio", None, later), None);
+}
+
+#[test]
+fn test_author_checks() {
+    let settings = SpamFilterSettings::default();
+    let mut filter = SpamFilter::new();
+    filter.remember_authors(vec![
+        author(1, "fresh", 2, 500),
+        author(2, "lonely", 400, 1),
+        author(3, "regular", 400, 300),
+    ]);
+
+    assert_eq!(
+        filter.check(&settings, "10", "@fud_bot is $WIF dead", Some(1)),
+        Some(SpamReason::NewAccount { age_days: 2 })
+    );
+    assert_eq!(
+        filter.check(&settings, "11", "@fud_bot thoughts on $JUP", Some(2)),
+        Some(SpamReason::FewFollowers { followers: 1 })
+    );
+    assert_eq!(filter.check(&settings, "12", "@fud_bot roast $BONK", Some(3)), None);
+    // Without author details only the text checks apply
+    assert_eq!(filter.check(&settings, "13", "@fud_bot roast $POPCAT", Some(99)), None);
+    assert_eq!(filter.unknown_authors([3, 99, 99, 42]), vec![42, 99]);
+}
+
+#[test]
+fn test_mute_list_and_disabled_filter() {
+    let settings = SpamFilterSettings {
+        muted: vec!["@Regular".to_string(), "77".to_string()],
+        ..SpamFilterSettings::default()
+    };
+    let mut filter = SpamFilter::new();
+    filter.remember_authors(vec![author(3, "regular", 400, 300)]);
+
+    assert_eq!(filter.check(&settings, "20", "@fud_bot hi", Some(3)), Some(SpamReason::Muted));
+    assert_eq!(filter.check(&settings, "21", "@fud_bot hi there", Some(77)), Some(SpamReason::Muted));
+
+    let disabled = SpamFilterSettings { enabled: false, ..settings };
+    assert_eq!(filter.check(&disabled, "22", "https://t.co/abc", Some(3)), None);
+}
+
+#[test]
+fn test_twitter_blocks_apply_even_when_disabled() {
+    let mut filter = SpamFilter::new();
+    filter.set_blocked([500, 501]);
+    let disabled = SpamFilterSettings { enabled: false, ..SpamFilterSettings::default() };
+
+    assert_eq!(filter.check(&disabled, "30", "@fud_bot gm", Some(500)), Some(SpamReason::BlockedOnTwitter));
+    assert_eq!(filter.check(&disabled, "31", "@fud_bot gm", Some(502)), None);
+
+    // A fresh sync replaces the old list, so unblocked accounts come back
+    filter.set_blocked([501]);
+    assert!(!filter.is_blocked(500));
+}
@@ -0,0 +1,63 @@
+// src/core/tests/stats_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::stats::StatsReport;
+use crate::models::{Memory, Tweet, TweetType};
+
+fn tweet(text: &str, tweet_type: TweetType, days_ago: i64) -> Tweet {
+    Tweet {
+        text: text.to_string(),
+        timestamp: Utc::now() - Duration::days(days_ago),
+        tweet_type,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_counts_posts_replies_and_days() {
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("$BONK dev is selling", TweetType::Original, 0));
+    memory.tweets.push(tweet("ngmi", TweetType::Reply, 0));
+    memory.tweets.push(tweet("$WIF chart is dead", TweetType::Original, 1));
+    memory.tweets.push(tweet("too old to show", TweetType::Original, 30));
+
+    let report = StatsReport::from_memory(&memory, Utc::now(), 7);
+
+    assert_eq!(report.total_posts, 3);
+    assert_eq!(report.total_replies, 1);
+    assert_eq!(report.posts_per_day.len(), 7);
+    let today = report.posts_per_day.last().unwrap();
+    assert_eq!((today.posts, today.replies), (1, 1));
+    assert_eq!(report.posts_per_day[5].posts, 1);
+}
+
+#[test]
+fn test_top_tokens_skip_prices_and_own_token() {
+    let mut memory = Memory {
+        token_symbol: "FUD".to_string(),
+        ..Default::default()
+    };
+    memory.tweets.push(tweet("$bonk down to $0.01, $FUD is the only hope", TweetType::Original, 0));
+    memory.tweets.push(tweet("$BONK, again", TweetType::Reply, 0));
+
+    let report = StatsReport::from_memory(&memory, Utc::now(), 1);
+
+    assert_eq!(report.top_tokens.len(), 1);
+    assert_eq!(report.top_tokens[0].value, "BONK");
+    assert_eq!(report.top_tokens[0].count, 2);
+}
+
+#[test]
+fn test_recurring_phrases_and_latency() {
+    let mut memory = Memory::default();
+    memory.tweets.push(tweet("Dev is selling. again", TweetType::Original, 0));
+    memory.tweets.push(tweet("told you dev is selling", TweetType::Original, 0));
+    memory.usage.generations = 4;
+    memory.usage.generation_ms = 2000;
+
+    let report = StatsReport::from_memory(&memory, Utc::now(), 1);
+
+    assert_eq!(report.top_phrases[0].value, "dev is selling");
+    assert_eq!(report.top_phrases[0].count, 2);
+    assert_eq!(report.avg_generation_ms, Some(500.0));
+}
@@ -0,0 +1,125 @@
+// src/core/tests/status_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::feature_flags::Flag;
+use super::super::health::{HealthReport, Provider, ProviderStatus};
+use super::super::local_time::LocalTime;
+use super::super::status::{StatusServer, StatusSnapshot};
+use crate::providers::solanatracker::CacheMetrics;
+
+fn snapshot() -> StatusSnapshot {
+    let mut health = HealthReport::default();
+    health.set(Provider::Twitter, ProviderStatus::Ok("@fudbot".to_string()));
+    health.set(Provider::Anthropic, ProviderStatus::Ok(String::new()));
+    health.set(Provider::SolanaTracker, ProviderStatus::Degraded("401 Unauthorized".to_string()));
+    health.set(Provider::Telegram, ProviderStatus::Disabled);
+
+    let mut snapshot = StatusSnapshot::new(Utc::now());
+    snapshot.set_health(&health);
+    snapshot.reply_queue = 3;
+    snapshot.outbox = 1;
+    snapshot
+}
+
+#[test]
+fn test_healthz_follows_the_run_loop() {
+    let snapshot = snapshot();
+    let stale = Duration::minutes(30);
+
+    let (status, _) = StatusServer::respond("/healthz", &snapshot, stale, snapshot.updated_at + Duration::minutes(5));
+    assert_eq!(status, 200);
+
+    let (status, body) = StatusServer::respond("/healthz", &snapshot, stale, snapshot.updated_at + Duration::minutes(31));
+    assert_eq!(status, 503);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["live"], false);
+}
+
+#[test]
+fn test_readyz_needs_twitter_and_anthropic() {
+    let mut snapshot = snapshot();
+    let stale = Duration::minutes(30);
+    let now = snapshot.updated_at;
+
+    // A degraded data provider doesn't stop posting
+    assert_eq!(StatusServer::respond("/readyz", &snapshot, stale, now).0, 200);
+
+    let mut health = HealthReport::default();
+    health.set(Provider::Twitter, ProviderStatus::Degraded("429 Too Many Requests".to_string()));
+    health.set(Provider::Anthropic, ProviderStatus::Ok(String::new()));
+    snapshot.set_health(&health);
+    let (status, body) = StatusServer::respond("/readyz", &snapshot, stale, now);
+    assert_eq!(status, 503);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["live"], true);
+    assert_eq!(body["providers"]["Twitter"]["status"], "degraded");
+    assert_eq!(body["providers"]["Twitter"]["detail"], "429 Too Many Requests");
+}
+
+#[test]
+fn test_body_reports_queues_and_last_post() {
+    let mut snapshot = snapshot();
+    let last_post = snapshot.updated_at - Duration::hours(2);
+    snapshot.last_post_at = Some(last_post);
+
+    let (_, body) = StatusServer::respond("/healthz?verbose=1", &snapshot, Duration::minutes(30), snapshot.updated_at);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["last_post_at"], last_post.to_rfc3339());
+    assert_eq!(body["queues"]["reply_queue"], 3);
+    assert_eq!(body["queues"]["outbox"], 1);
+    assert_eq!(body["providers"]["Telegram"]["status"], "disabled");
+    assert!(body["providers"]["Anthropic"]["detail"].is_null());
+    assert_eq!(body["flags"]["image_posts"], true);
+    assert_eq!(body["schedule"]["timezone"], "UTC");
+    assert!(body["schedule"]["next_post_at"].is_null());
+    assert!(body["storage"].is_null());
+    assert_eq!(body["trending_cache"]["misses"], 0);
+}
+
+#[test]
+fn test_body_reports_trending_cache() {
+    let mut snapshot = snapshot();
+    snapshot.trending_cache = CacheMetrics { hits: 7, stale_hits: 2, misses: 1, refreshes: 2, failed_refreshes: 1 };
+    let (_, body) = StatusServer::respond("/healthz", &snapshot, Duration::minutes(30), snapshot.updated_at);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["trending_cache"]["hits"], 7);
+    assert_eq!(body["trending_cache"]["stale_hits"], 2);
+    assert_eq!(body["trending_cache"]["failed_refreshes"], 1);
+}
+
+#[test]
+fn test_body_reports_schedule_in_local_time() {
+    let mut snapshot = snapshot();
+    snapshot.local_time = LocalTime::parse("Asia/Tokyo").unwrap();
+    let next = snapshot.updated_at + Duration::minutes(15);
+    snapshot.next_post_at = Some(next);
+
+    let (_, body) = StatusServer::respond("/healthz", &snapshot, Duration::minutes(30), snapshot.updated_at);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["schedule"]["timezone"], "Asia/Tokyo");
+    assert_eq!(body["schedule"]["next_post_at"], next.to_rfc3339());
+    assert!(body["schedule"]["next_post_local"].as_str().unwrap().ends_with("+09:00"));
+}
+
+#[test]
+fn test_flag_request() {
+    let bearer = Some("Bearer s3cret");
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", bearer, Some("s3cret")), Ok((Flag::Threads, false)));
+
+    // Off unless an admin token is configured, and only with that token
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", bearer, None).unwrap_err().0, 403);
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", None, Some("s3cret")).unwrap_err().0, 401);
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", Some("Bearer guess"), Some("s3cret")).unwrap_err().0, 401);
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", Some("Bearer s3cre"), Some("s3cret")).unwrap_err().0, 401);
+    assert_eq!(StatusServer::flag_request("/flags/threads/off", Some("Bearer s3cret!"), Some("s3cret")).unwrap_err().0, 401);
+
+    assert_eq!(StatusServer::flag_request("/flags/threads", bearer, Some("s3cret")).unwrap_err().0, 400);
+    assert_eq!(StatusServer::flag_request("/flags/debug/on", bearer, Some("s3cret")).unwrap_err().0, 400);
+    assert_eq!(StatusServer::flag_request("/healthz", bearer, Some("s3cret")).unwrap_err().0, 404);
+}
+
+#[test]
+fn test_unknown_path() {
+    let snapshot = snapshot();
+    assert_eq!(StatusServer::respond("/metrics", &snapshot, Duration::minutes(30), snapshot.updated_at).0, 404);
+}
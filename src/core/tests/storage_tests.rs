@@ -0,0 +1,79 @@
+// src/core/tests/storage_tests.rs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use super::super::character::StorageSettings;
+use super::super::storage::{StorageManager, SweepReport};
+
+fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("chainfud-storage-{}", rand::random::<u64>()))
+}
+
+// A file of `bytes` bytes last modified `age_minutes` ago
+fn write(path: &Path, bytes: usize, age_minutes: u64) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, vec![b'x'; bytes]).unwrap();
+    let modified = SystemTime::now() - Duration::from_secs(age_minutes * 60);
+    fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+}
+
+#[test]
+fn test_enforce_deletes_oldest_first() {
+    let root = temp_root();
+    write(&root.join("drafts/2024-05-01.md"), 400, 30);
+    write(&root.join("drafts/images/old.png"), 400, 20);
+    write(&root.join("drafts/2024-05-02.md"), 400, 10);
+
+    let (usage, deleted, freed) = StorageManager::new(&root).enforce("drafts", 900).unwrap();
+    assert_eq!((deleted, freed), (1, 400));
+    assert_eq!((usage.bytes, usage.files), (800, 2));
+    assert!(!root.join("drafts/2024-05-01.md").exists());
+    assert!(root.join("drafts/images/old.png").exists());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_enforce_keeps_the_newest_file() {
+    let root = temp_root();
+    write(&root.join("audit/2024-05-01.jsonl"), 500, 60);
+    write(&root.join("audit/2024-05-02.jsonl"), 500, 1);
+
+    let (usage, deleted, _) = StorageManager::new(&root).enforce("audit", 100).unwrap();
+    assert_eq!(deleted, 1);
+    assert_eq!(usage.files, 1);
+    assert!(root.join("audit/2024-05-02.jsonl").exists());
+
+    // A directory that doesn't exist yet is just empty
+    let (usage, deleted, _) = StorageManager::new(&root).enforce("export", 100).unwrap();
+    assert_eq!((usage.bytes, deleted), (0, 0));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_sweep_skips_protected_dirs() {
+    let root = temp_root();
+    write(&root.join("charts/a.png"), 2 * 1024 * 1024, 60);
+    write(&root.join("charts/b.png"), 2 * 1024 * 1024, 1);
+    let mut settings = StorageSettings::default();
+    settings.quotas_mb.insert("charts".to_string(), 1);
+
+    let report = StorageManager::new(&root).sweep(&settings);
+    assert_eq!(report.deleted_files, 0);
+    assert!(root.join("charts/a.png").exists());
+    assert!(report.usage.iter().all(|dir| dir.name != "charts"));
+    assert_eq!(report.usage.len(), settings.quotas_mb.len() - 1);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_headroom() {
+    let settings = StorageSettings { min_free_mb: 500, ..StorageSettings::default() };
+    let report = |free_bytes| SweepReport { free_bytes, ..SweepReport::default() };
+    assert!(report(Some(600 * 1024 * 1024)).has_headroom(&settings));
+    assert!(!report(Some(100 * 1024 * 1024)).has_headroom(&settings));
+    assert!(report(None).has_headroom(&settings));
+}
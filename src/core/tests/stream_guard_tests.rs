@@ -0,0 +1,72 @@
+// src/core/tests/stream_guard_tests.rs
+
+use crate::core::stream_guard::{SseReader, StreamGuard};
+
+fn delta(text: &str) -> String {
+    format!(
+        "event: content_block_delta\ndata: {{\"type\":\"content_block_delta\",\"index\":0,\"delta\":{{\"type\":\"text_delta\",\"text\":\"{}\"}}}}\n\n",
+        text
+    )
+}
+
+#[test]
+fn test_reader_collects_text_deltas_across_chunks() {
+    let stream = format!(
+        "event: message_start\ndata: {{\"type\":\"message_start\",\"message\":{{}}}}\n\n{}{}event: message_stop\ndata: {{\"type\":\"message_stop\"}}\n\n",
+        delta("dev wallet "),
+        delta("holds 40% 💀"),
+    );
+    let bytes = stream.as_bytes();
+    let mut reader = SseReader::new();
+    let mut text = String::new();
+    // Chunks split mid event and mid character
+    for chunk in bytes.chunks(7) {
+        for part in reader.push(chunk).unwrap() {
+            text.push_str(&part);
+        }
+    }
+    assert_eq!(text, "dev wallet holds 40% 💀");
+    assert!(reader.finished());
+}
+
+#[test]
+fn test_reader_handles_crlf_and_pings() {
+    let mut reader = SseReader::new();
+    let deltas = reader
+        .push(b"event: ping\r\ndata: {\"type\": \"ping\"}\r\n\r\nevent: content_block_delta\r\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"ngmi\"}}\r\n\r\n")
+        .unwrap();
+    assert_eq!(deltas, vec!["ngmi".to_string()]);
+    assert!(!reader.finished());
+}
+
+#[test]
+fn test_reader_fails_on_error_events() {
+    let mut reader = SseReader::new();
+    let error = reader
+        .push(b"event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n")
+        .unwrap_err();
+    assert!(error.to_string().contains("Overloaded"));
+}
+
+#[test]
+fn test_guard_finds_terms_in_partial_text() {
+    let guard = StreamGuard::new(["Financial Advice", " ", "lambo"]);
+    assert_eq!(guard.violation("this is not financial adv"), None);
+    assert_eq!(guard.violation("this is not FINANCIAL ADVICE"), Some("financial advice"));
+    assert_eq!(guard.violation("wen LAMBO"), Some("lambo"));
+    assert_eq!(guard.partial_violation("wen lambo"), None);
+    assert_eq!(guard.partial_violation("wen lambo?"), Some("lambo"));
+    assert!(StreamGuard::new(Vec::<String>::new()).is_empty());
+}
+
+#[test]
+fn test_terms_match_whole_words() {
+    let guard = StreamGuard::new(["rug", "$scam"]);
+    assert_eq!(guard.violation("another RUG."), Some("rug"));
+    assert_eq!(guard.violation("rug-pulled again"), Some("rug"));
+    assert_eq!(guard.violation("dev is on drugs"), None);
+    assert_eq!(guard.violation("rugged terrain"), None);
+    assert_eq!(guard.violation("buy $SCAM now"), Some("$scam"));
+    assert_eq!(guard.violation("buy $scamcoin"), None);
+    assert_eq!(guard.violation("not a drug, a rug"), Some("rug"));
+}
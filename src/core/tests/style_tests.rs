@@ -0,0 +1,96 @@
+// src/core/tests/style_tests.rs
+
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use super::super::character::DecorationSettings;
+use super::super::style::StyleEngine;
+
+#[test]
+fn test_recent_phrases_shared_with_record() {
+    let mut style = StyleEngine::new(100, Duration::hours(1));
+    style.record("dev is selling again");
+
+    assert!(style.repeats_recent_phrase("lol dev is selling"));
+    assert!(!style.repeats_recent_phrase("liquidity looks thin"));
+}
+
+#[test]
+fn test_overused_words_decay() {
+    let mut style = StyleEngine::new(1000, Duration::hours(1));
+    let now = Utc::now();
+    for _ in 0..6 {
+        style.record_at("absolutely cooked", now - Duration::minutes(30));
+    }
+
+    assert!(style.is_overused_at("this one is absolutely dead", now));
+    assert!(!style.is_overused_at("this one is absolutely dead", now + Duration::hours(1)));
+}
+
+#[test]
+fn test_signature_patterns_wear_out_first() {
+    let mut style = StyleEngine::new(1000, Duration::hours(1));
+    let now = Utc::now();
+    for text in ["ser pls", "ngmi ser", "ser, no"] {
+        style.record_at(text, now);
+    }
+    assert!(!style.is_overused_at("ser this is it", now));
+
+    style.record_at("ser...", now);
+    assert!(style.is_overused_at("ser this is it", now));
+    // Short everyday words never count
+    for _ in 0..10 {
+        style.record_at("the dev", now);
+    }
+    assert!(!style.is_overused_at("the dev", now));
+}
+
+#[test]
+fn test_vary_leaves_plain_text_alone() {
+    let style = StyleEngine::default();
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        assert_eq!(style.vary_with("liquidity is thinner than the roadmap?", &mut rng), "liquidity is thinner than the roadmap?");
+    }
+}
+
+#[test]
+fn test_vary_only_replaces_whole_words() {
+    let style = StyleEngine::default();
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let varied = style.vary_with("user says ser it's literally obviously over!", &mut rng);
+        assert!(varied.contains("user says"));
+        assert!(varied.ends_with("over!"));
+    }
+}
+
+#[test]
+fn test_decoration_follows_character_policy() {
+    let style = StyleEngine::default().with_decoration(DecorationSettings {
+        emojis: vec!["🤡".to_string(), "💀".to_string()],
+        emoji_chance: 1.0,
+        max_emojis: 1,
+        punctuation: vec!["...".to_string()],
+        punctuation_chance: 1.0,
+        never_use: vec!["💀".to_string()],
+    });
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let decorated = style.decorate_with("dev sold 💀 again", &mut rng);
+        assert!(decorated.contains("dev sold again..."));
+        assert_eq!(decorated.matches("🤡").count(), 1);
+        assert!(!decorated.contains("💀"));
+    }
+}
+
+#[test]
+fn test_default_policy_adds_no_emojis() {
+    let style = StyleEngine::default();
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let decorated = style.decorate_with("liquidity is a rounding error", &mut rng);
+        assert!(decorated.starts_with("liquidity is a rounding error"));
+        assert!(decorated.is_ascii());
+    }
+}
@@ -0,0 +1,67 @@
+// src/core/tests/target_scorer_tests.rs
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use super::super::character::TargetScoringSettings;
+use super::super::target_scorer::TargetScorer;
+use crate::models::TokenSnapshot;
+
+fn token(change_1h: Option<f64>, buys: u64, sells: u64, liquidity_usd: f64) -> TokenSnapshot {
+    TokenSnapshot {
+        symbol: "TEST".to_string(),
+        market_cap: 1_000_000.0,
+        liquidity_usd,
+        change_1h,
+        buys,
+        sells,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_signals() {
+    let settings = TargetScoringSettings::default();
+
+    let calm = TargetScorer::score(&settings, &token(Some(-5.0), 50, 50, 200_000.0));
+    assert_eq!((calm.gain_1h, calm.trade_imbalance, calm.thin_liquidity), (0.0, 0.0, 0.0));
+    assert_eq!(calm.total, settings.base_weight);
+
+    let pumped = TargetScorer::score(&settings, &token(Some(250.0), 1000, 100, 0.0));
+    assert_eq!(pumped.gain_1h, 1.0);
+    assert!((pumped.trade_imbalance - 1.0).abs() < 1e-9);
+    assert_eq!(pumped.thin_liquidity, 1.0);
+
+    // Too few trades to count, and half-healthy liquidity is half a signal
+    let quiet = TargetScorer::score(&settings, &token(None, 9, 1, 50_000.0));
+    assert_eq!(quiet.trade_imbalance, 0.0);
+    assert!((quiet.thin_liquidity - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_weights_apply() {
+    let settings = TargetScoringSettings {
+        base_weight: 0.0,
+        gain_1h_weight: 2.0,
+        trade_imbalance_weight: 0.0,
+        thin_liquidity_weight: 0.0,
+        ..Default::default()
+    };
+    let score = TargetScorer::score(&settings, &token(Some(50.0), 1000, 10, 0.0));
+    assert!((score.total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_pick_prefers_high_scores() {
+    let settings = TargetScoringSettings {
+        base_weight: 0.0,
+        ..Default::default()
+    };
+    let tokens = vec![token(None, 50, 50, 200_000.0), token(Some(300.0), 50, 50, 200_000.0)];
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let (i, score) = TargetScorer::pick_with(&settings, &tokens, &mut rng).unwrap();
+        assert_eq!(i, 1);
+        assert_eq!(score.gain_1h, 1.0);
+    }
+    assert!(TargetScorer::pick_with(&settings, &[], &mut rng).is_none());
+}
@@ -0,0 +1,64 @@
+// src/core/tests/target_selection_tests.rs
+
+use super::super::character::{TargetSelectionSettings, CEX_LISTED_MINTS};
+use super::super::token_extractor::TokenExtractor;
+use crate::models::TokenSnapshot;
+
+const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+fn token(symbol: &str, age_hours: Option<f64>) -> TokenSnapshot {
+    TokenSnapshot {
+        mint: format!("{}mint", symbol.to_lowercase()),
+        symbol: symbol.to_string(),
+        price_usd: 0.01,
+        market_cap: 1_000_000.0,
+        liquidity_usd: 50_000.0,
+        change_24h: Some(80.0),
+        age_hours,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_cex_listed_majors_excluded_by_default() {
+    let filters = TargetSelectionSettings::default();
+    let bonk = TokenSnapshot { mint: BONK_MINT.to_string(), ..token("BONK", Some(10_000.0)) };
+    assert!(!filters.matches(&bonk));
+    // A copycat with the same symbol is still fair game
+    assert!(filters.matches(&token("BONK", Some(3.0))));
+    assert!(filters.matches(&token("RUGZ", Some(3.0))));
+
+    let filters = TargetSelectionSettings {
+        exclude_cex_listed: false,
+        ..Default::default()
+    };
+    assert!(filters.matches(&bonk));
+}
+
+#[test]
+fn test_builtin_cex_listed_are_mints() {
+    assert!(CEX_LISTED_MINTS.iter().all(|mint| TokenExtractor::is_solana_address(mint)));
+}
+
+#[test]
+fn test_extra_cex_listed_by_mint() {
+    let filters = TargetSelectionSettings {
+        cex_listed: vec!["rugzmint".to_string(), "MOODENG".to_string()],
+        ..Default::default()
+    };
+    assert!(!filters.matches(&token("RUGZ", None)));
+    assert!(filters.matches(&token("MOODENG", None)));
+    assert!(filters.matches(&token("SCAM", None)));
+}
+
+#[test]
+fn test_max_age() {
+    let filters = TargetSelectionSettings {
+        max_age_hours: Some(48.0),
+        ..Default::default()
+    };
+    assert!(filters.matches(&token("NEW", Some(6.0))));
+    assert!(!filters.matches(&token("OLD", Some(200.0))));
+    // Unknown age can't be shown to be young enough
+    assert!(!filters.matches(&token("ANON", None)));
+}
@@ -0,0 +1,71 @@
+// src/core/tests/telemetry_tests.rs
+
+use super::super::telemetry::{otlp_body, parse_headers, traces_url, SpanRecord, Tracer};
+
+fn span(name: &str, error: Option<&str>) -> SpanRecord {
+    SpanRecord {
+        trace_id: "0af7651916cd43dd8448eb211c80319c".to_string(),
+        span_id: "b7ad6b7169203331".to_string(),
+        parent_span_id: Some("00f067aa0ba902b7".to_string()),
+        name: name.to_string(),
+        start_ns: 1_700_000_000_000_000_000,
+        end_ns: 1_700_000_001_500_000_000,
+        attributes: vec![("provider".to_string(), "anthropic".to_string())],
+        error: error.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_parse_headers() {
+    assert_eq!(
+        parse_headers("authorization=Bearer abc, x-team = fud ,"),
+        Some(vec![
+            ("authorization".to_string(), "Bearer abc".to_string()),
+            ("x-team".to_string(), "fud".to_string()),
+        ])
+    );
+    assert_eq!(parse_headers("api-key=a=b"), Some(vec![("api-key".to_string(), "a=b".to_string())]));
+    assert_eq!(parse_headers("missing-value"), None);
+    assert_eq!(parse_headers("=value"), None);
+}
+
+#[test]
+fn test_traces_url() {
+    assert_eq!(traces_url("http://localhost:4318"), "http://localhost:4318/v1/traces");
+    assert_eq!(traces_url("http://localhost:4318/"), "http://localhost:4318/v1/traces");
+    assert_eq!(traces_url("https://otel.example/v1/traces"), "https://otel.example/v1/traces");
+}
+
+#[test]
+fn test_otlp_body() {
+    let body = otlp_body("fud-bot", &[span("llm_call", None), span("post", Some("403 Forbidden"))]);
+    let resource = &body["resourceSpans"][0];
+    assert_eq!(resource["resource"]["attributes"][0]["key"], "service.name");
+    assert_eq!(resource["resource"]["attributes"][0]["value"]["stringValue"], "fud-bot");
+
+    let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0]["name"], "llm_call");
+    assert_eq!(spans[0]["parentSpanId"], "00f067aa0ba902b7");
+    // 64-bit nanosecond timestamps are strings in OTLP/JSON
+    assert_eq!(spans[0]["startTimeUnixNano"], "1700000000000000000");
+    assert_eq!(spans[0]["attributes"][0]["value"]["stringValue"], "anthropic");
+    assert_eq!(spans[0]["status"]["code"], 1);
+    assert_eq!(spans[1]["status"]["code"], 2);
+    assert_eq!(spans[1]["status"]["message"], "403 Forbidden");
+}
+
+#[tokio::test]
+async fn test_disabled_tracer_passes_results_through() {
+    let tracer = Tracer::new(None);
+    assert!(!tracer.is_enabled());
+    tracer.start_trace("scheduled_post");
+
+    let ok = tracer.in_span("fetch_trending", &[], async { Ok::<_, anyhow::Error>(3) }).await;
+    assert_eq!(ok.unwrap(), 3);
+    let err = tracer
+        .in_span("post", &[], async { Err::<(), _>(anyhow::anyhow!("rate limited")) })
+        .await;
+    assert_eq!(err.unwrap_err().to_string(), "rate limited");
+    tracer.finish_trace(None::<&anyhow::Error>);
+}
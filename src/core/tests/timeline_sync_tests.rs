@@ -0,0 +1,128 @@
+// src/core/tests/timeline_sync_tests.rs
+
+use crate::core::timeline_sync::TimelineSync;
+use crate::models::{Memory, QueuedReply, Tweet};
+use crate::providers::twitter::TimelinePost;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap()
+}
+
+fn saved(id: u64, twitter_id: Option<&str>, text: &str, minutes_ago: i64) -> Tweet {
+    Tweet {
+        internal_id: id,
+        twitter_id: twitter_id.map(str::to_string),
+        text: text.to_string(),
+        prompt: "character prompt".to_string(),
+        timestamp: now() - Duration::minutes(minutes_ago),
+        ..Default::default()
+    }
+}
+
+fn posted(id: &str, text: &str, minutes_ago: i64, reply_to: Option<&str>) -> TimelinePost {
+    TimelinePost {
+        id: id.to_string(),
+        text: text.to_string(),
+        created_at: Some(now() - Duration::minutes(minutes_ago)),
+        reply_to: reply_to.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_normalize_ignores_links_entities_and_case() {
+    assert_eq!(
+        TimelineSync::normalize("$BONK devs  &amp; their bags\nhttps://t.co/abc123"),
+        TimelineSync::normalize("$bonk devs & their bags https://dexscreener.com/solana/xyz"),
+    );
+}
+
+#[test]
+fn test_missing_ids_are_backfilled_by_text() {
+    let mut memory = Memory {
+        tweets: vec![saved(0, Some("100"), "old one", 120), saved(1, None, "rug incoming &", 30)],
+        next_id: 2,
+        ..Memory::default()
+    };
+    let timeline = vec![posted("101", "Rug incoming &amp;", 29, None), posted("100", "old one", 120, None)];
+
+    let report = TimelineSync::reconcile(&mut memory, &timeline, now());
+    assert_eq!(report.backfilled, 1);
+    assert_eq!(report.recovered, 0);
+    assert_eq!(report.newest, Some(now() - Duration::minutes(29)));
+    assert_eq!(memory.tweets[1].twitter_id.as_deref(), Some("101"));
+    assert_eq!(memory.tweets.len(), 2);
+
+    let again = TimelineSync::reconcile(&mut memory, &timeline, now());
+    assert!(!again.changed());
+}
+
+#[test]
+fn test_sent_queued_replies_leave_the_queue() {
+    let mut memory = Memory {
+        next_id: 5,
+        reply_queue: vec![
+            QueuedReply {
+                reply_to: "900".to_string(),
+                text: "ngmi".to_string(),
+                prompt: "queued prompt".to_string(),
+                scheduled_at: now() - Duration::minutes(10),
+                attempts: 0,
+                token_mint: Some("MINT".to_string()),
+                token_symbol: Some("RUG".to_string()),
+            },
+            QueuedReply {
+                reply_to: "901".to_string(),
+                text: "still waiting".to_string(),
+                prompt: String::new(),
+                scheduled_at: now() + Duration::minutes(10),
+                attempts: 0,
+                token_mint: None,
+                token_symbol: None,
+            },
+        ],
+        ..Memory::default()
+    };
+    let timeline = vec![posted("200", "@degen ngmi", 9, Some("900"))];
+
+    let report = TimelineSync::reconcile(&mut memory, &timeline, now());
+    assert_eq!(report.dequeued, 1);
+    assert_eq!(memory.reply_queue.len(), 1);
+    assert_eq!(memory.reply_queue[0].reply_to, "901");
+    let reply = &memory.tweets[0];
+    assert_eq!(reply.twitter_id.as_deref(), Some("200"));
+    assert_eq!(reply.reply_to.as_deref(), Some("900"));
+    assert_eq!(reply.token_mint.as_deref(), Some("MINT"));
+    assert_eq!(reply.prompt, "queued prompt");
+    assert_eq!(reply.internal_id, 5);
+    assert!(memory.replied_to.contains("900"));
+}
+
+#[test]
+fn test_unknown_tweets_are_recovered_in_order() {
+    let mut memory = Memory {
+        tweets: vec![saved(0, Some("100"), "known", 90)],
+        next_id: 1,
+        ..Memory::default()
+    };
+    let timeline = vec![
+        posted("103", "newest", 5, None),
+        posted("102", "posted before the crash", 60, None),
+    ];
+
+    let report = TimelineSync::reconcile(&mut memory, &timeline, now());
+    assert_eq!(report.recovered, 2);
+    let ids: Vec<_> = memory.tweets.iter().map(|tweet| tweet.twitter_id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["100", "102", "103"]);
+    assert_eq!(memory.next_id, 3);
+}
+
+#[test]
+fn test_find_posted_matches_original_tweets_only() {
+    let timeline = vec![
+        posted("1", "ratio + dev dumped", 5, Some("99")),
+        posted("2", "Ratio + dev dumped https://t.co/x", 3, None),
+    ];
+    assert_eq!(TimelineSync::find_posted("ratio + dev dumped", &timeline).map(|post| post.id.as_str()), Some("2"));
+    assert!(TimelineSync::find_posted("never posted", &timeline).is_none());
+}
@@ -0,0 +1,118 @@
+// src/core/tests/token_extractor_tests.rs
+
+use super::super::token_extractor::{TokenCandidate, TokenExtractor};
+
+const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn ticker(symbol: &str) -> TokenCandidate {
+    TokenCandidate::Ticker(symbol.to_string())
+}
+
+fn address(mint: &str) -> TokenCandidate {
+    TokenCandidate::Address(mint.to_string())
+}
+
+#[test]
+fn test_cashtag_with_punctuation() {
+    assert_eq!(TokenExtractor::extract_first("thoughts on ($BONK)?"), Some(ticker("BONK")));
+    assert_eq!(TokenExtractor::extract_first("is $wif, cooked"), Some(ticker("wif")));
+}
+
+#[test]
+fn test_prices_are_not_cashtags() {
+    assert_eq!(TokenExtractor::extract_all("went from $0.01 to $100k lol"), vec![]);
+}
+
+#[test]
+fn test_multiple_candidates_in_order_without_duplicates() {
+    let found = TokenExtractor::extract_all("$BONK vs $WIF/$bonk and $POPCAT");
+    assert_eq!(found, vec![ticker("BONK"), ticker("WIF"), ticker("POPCAT")]);
+}
+
+#[test]
+fn test_solana_address_validation() {
+    assert!(TokenExtractor::is_solana_address(BONK_MINT));
+    // Right alphabet and length, but doesn't decode to 32 bytes
+    assert!(!TokenExtractor::is_solana_address("11111111111111111111111111111111111"));
+    assert!(!TokenExtractor::is_solana_address("pumpfunpumpfunpumpfunpumpfunpumpfun"));
+}
+
+#[test]
+fn test_real_mints_and_wallets_are_addresses() {
+    let addresses = [
+        USDC_MINT,
+        // WIF and JUP
+        "EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm",
+        "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+        // Wrapped SOL, 43 characters
+        "So11111111111111111111111111111111111111112",
+        // A wallet
+        "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+    ];
+    for address in addresses {
+        assert!(TokenExtractor::is_solana_address(address), "{}", address);
+        assert_eq!(TokenExtractor::decode_address(address).map(|bytes| bytes.len()), Some(32));
+    }
+}
+
+#[test]
+fn test_look_alike_strings_are_not_addresses() {
+    let look_alikes = [
+        // BONK with an l in place of a 1, which base58 leaves out
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaBlpPB263",
+        // One character too many, and a run that decodes to 33 bytes
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263x",
+        "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz",
+        // Too short
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xj",
+        // An Ethereum address and a transaction signature
+        "0x95aD61b0a150d79219dCF64E1E6Cc01f0B64C4cE",
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW",
+        // The system program: 32 zero bytes
+        "11111111111111111111111111111111",
+    ];
+    for text in look_alikes {
+        assert!(!TokenExtractor::is_solana_address(text), "{}", text);
+    }
+}
+
+#[test]
+fn test_look_alike_in_text_is_not_extracted() {
+    let text = "ca: DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaBlpPB263 trust";
+    assert!(!TokenExtractor::extract_all(text).iter().any(|found| matches!(found, TokenCandidate::Address(_))));
+}
+
+#[test]
+fn test_plain_address_in_text() {
+    let text = format!("ca: {}.", BONK_MINT);
+    assert_eq!(TokenExtractor::extract_first(&text), Some(address(BONK_MINT)));
+}
+
+#[test]
+fn test_mint_from_token_urls() {
+    let dexscreener = format!("look https://dexscreener.com/solana/{}", BONK_MINT);
+    assert_eq!(TokenExtractor::extract_all(&dexscreener), vec![address(BONK_MINT)]);
+
+    let birdeye = format!("(https://birdeye.so/token/{}?chain=solana)", USDC_MINT);
+    assert_eq!(TokenExtractor::extract_all(&birdeye), vec![address(USDC_MINT)]);
+}
+
+#[test]
+fn test_encoded_cashtag_in_url() {
+    let found = TokenExtractor::extract_all("https://x.com/search?q=%24BONK&src=cashtag_click");
+    assert_eq!(found, vec![ticker("BONK")]);
+}
+
+#[test]
+fn test_implied_ticker_fallback() {
+    assert_eq!(TokenExtractor::extract_first("what do you think of bonk"), Some(ticker("bonk")));
+    assert_eq!(TokenExtractor::extract_first("thoughts on the market"), None);
+}
+
+#[test]
+fn test_implied_ticker_after_non_ascii_text() {
+    // "İ" lowercases to more bytes, which must not shift where the ticker is read from
+    assert_eq!(TokenExtractor::extract_first("İ aboutñ"), None);
+    assert_eq!(TokenExtractor::extract_first("İİ thoughts on wif"), Some(ticker("wif")));
+}
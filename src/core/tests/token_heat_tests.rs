@@ -0,0 +1,52 @@
+// src/core/tests/token_heat_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::character::TokenHeatSettings;
+use super::super::kv_store::{KvStore, LogStore};
+use super::super::token_heat::TokenHeat;
+
+fn settings() -> TokenHeatSettings {
+    TokenHeatSettings {
+        enabled: true,
+        ..TokenHeatSettings::default()
+    }
+}
+
+#[test]
+fn test_heat_halves_every_half_life() {
+    let settings = settings();
+    let mut store = LogStore::in_memory();
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    TokenHeat::record(&settings, &mut store, "MintA", "degen", now).unwrap();
+
+    assert!((TokenHeat::heat(&settings, &store, "MintA", now) - 1.0).abs() < 1e-9);
+    let later = now + Duration::hours(6);
+    assert!((TokenHeat::heat(&settings, &store, "MintA", later) - 0.5).abs() < 1e-9);
+    assert_eq!(TokenHeat::heat(&settings, &store, "MintB", now), 0.0);
+}
+
+#[test]
+fn test_cooldown_is_shared_and_stacks() {
+    let settings = settings();
+    let mut store = LogStore::in_memory();
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    TokenHeat::record(&settings, &mut store, "MintA", "degen", now).unwrap();
+    TokenHeat::record(&settings, &mut store, "MintA", "boomer", now).unwrap();
+
+    assert_eq!(TokenHeat::fudded_by(&store, "MintA"), vec!["degen", "boomer"]);
+    // One FUD would have cooled to 0.5 after a half-life; two keep it at 1.0
+    assert!(TokenHeat::too_hot(&settings, &store, "MintA", now + Duration::hours(6)).is_some());
+    assert!(TokenHeat::too_hot(&settings, &store, "MintA", now + Duration::hours(13)).is_none());
+}
+
+#[test]
+fn test_record_drops_cold_entries() {
+    let settings = settings();
+    let mut store = LogStore::in_memory();
+    let then = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    TokenHeat::record(&settings, &mut store, "MintA", "degen", then).unwrap();
+    TokenHeat::record(&settings, &mut store, "MintB", "degen", then + Duration::days(3)).unwrap();
+
+    assert_eq!(store.len(), 1);
+    assert!(TokenHeat::fudded_by(&store, "MintA").is_empty());
+}
@@ -0,0 +1,49 @@
+// src/core/tests/tweet_preview_tests.rs
+
+use super::super::tweet_preview::{TweetPreview, MAX_WEIGHTED_LENGTH};
+
+#[test]
+fn test_latin_text_counts_one_per_char() {
+    assert_eq!(TweetPreview::weighted_length("dev is selling"), 14);
+    assert_eq!(TweetPreview::weighted_length("café — ngmi"), 11);
+}
+
+#[test]
+fn test_cjk_counts_double() {
+    assert_eq!(TweetPreview::weighted_length("开发者跑路"), 10);
+}
+
+#[test]
+fn test_urls_count_as_tco_length() {
+    let long = "https://dexscreener.com/solana/So11111111111111111111111111111111111111112";
+    assert_eq!(TweetPreview::weighted_length(long), 23);
+    assert_eq!(TweetPreview::weighted_length(&format!("see {}.", long)), 4 + 23 + 1);
+    assert_eq!(TweetPreview::weighted_length("launched on pump.fun"), 12 + 23);
+}
+
+#[test]
+fn test_emoji_sequences_count_as_two() {
+    assert_eq!(TweetPreview::weighted_length("🚀"), 2);
+    assert_eq!(TweetPreview::weighted_length("❤️"), 2);
+    assert_eq!(TweetPreview::weighted_length("👍🏽"), 2);
+    assert_eq!(TweetPreview::weighted_length("👨‍👩‍👧"), 2);
+    assert_eq!(TweetPreview::weighted_length("🇺🇸"), 2);
+    assert_eq!(TweetPreview::weighted_length("1️⃣"), 2);
+}
+
+#[test]
+fn test_check_rejects_over_limit() {
+    let ok = "a".repeat(MAX_WEIGHTED_LENGTH);
+    assert!(TweetPreview::check(&ok).is_ok());
+    // 141 CJK chars is 282 weighted even though it's only 141 chars
+    assert!(TweetPreview::check(&"跑".repeat(141)).is_err());
+}
+
+#[test]
+fn test_wrap_keeps_line_breaks() {
+    let preview = TweetPreview::new("rug incoming\n\nthe dev wallet just moved again");
+    assert_eq!(
+        preview.wrap(16),
+        vec!["rug incoming", "", "the dev wallet", "just moved again"]
+    );
+}
@@ -0,0 +1,73 @@
+// src/core/tests/tweet_token_tests.rs
+
+use chrono::Utc;
+use crate::memory::MemoryStore;
+use crate::models::{FuddedToken, Memory, Tweet, TweetType};
+
+const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+fn tweet(twitter_id: &str, text: &str, reply_to: Option<&str>) -> Tweet {
+    Tweet {
+        twitter_id: Some(twitter_id.to_string()),
+        text: text.to_string(),
+        timestamp: Utc::now(),
+        tweet_type: if reply_to.is_some() { TweetType::Reply } else { TweetType::Original },
+        reply_to: reply_to.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_backfills_from_fud_history_and_own_replies() {
+    let mut memory = Memory {
+        tweets: vec![
+            tweet("1", "this dev is cooked", None),
+            tweet("2", "whale just dumped, told you", Some("1")),
+            tweet("3", "@degen ngmi", Some("999")),
+        ],
+        fud_history: vec![FuddedToken {
+            mint: BONK_MINT.to_string(),
+            symbol: "BONK".to_string(),
+            tweet_id: Some("1".to_string()),
+            fudded_at: Utc::now(),
+            price_usd: 0.00002,
+            market_cap: 1_000_000.0,
+        }],
+        ..Memory::default()
+    };
+
+    assert_eq!(MemoryStore::backfill_tweet_tokens(&mut memory), 2);
+    assert_eq!(memory.tweets[0].token_mint.as_deref(), Some(BONK_MINT));
+    assert_eq!(memory.tweets[0].token_symbol.as_deref(), Some("BONK"));
+    assert_eq!(memory.tweets[1].token_mint.as_deref(), Some(BONK_MINT));
+    assert_eq!(memory.tweets[2].token_mint, None);
+    assert_eq!(MemoryStore::backfill_tweet_tokens(&mut memory), 0);
+}
+
+#[test]
+fn test_backfills_a_single_address_in_the_text() {
+    let mut memory = Memory {
+        tweets: vec![
+            tweet("1", &format!("ca {} is a honeypot", BONK_MINT), None),
+            tweet("2", &format!("{} vs {}", BONK_MINT, USDC_MINT), None),
+            tweet("3", "$BONK is cooked", None),
+        ],
+        ..Memory::default()
+    };
+
+    assert_eq!(MemoryStore::backfill_tweet_tokens(&mut memory), 1);
+    assert_eq!(memory.tweets[0].token_mint.as_deref(), Some(BONK_MINT));
+    assert_eq!(memory.tweets[0].token_symbol, None);
+    assert_eq!(memory.tweets[1].token_mint, None);
+    assert_eq!(memory.tweets[2].token_mint, None);
+}
+
+#[test]
+fn test_reads_the_old_mint_field() {
+    let mut json = serde_json::to_value(tweet("1", "rug", None)).unwrap();
+    json.as_object_mut().unwrap().remove("token_mint");
+    json["mint"] = serde_json::json!(BONK_MINT);
+    let tweet: Tweet = serde_json::from_value(json).unwrap();
+    assert_eq!(tweet.token_mint.as_deref(), Some(BONK_MINT));
+}
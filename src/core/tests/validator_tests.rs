@@ -0,0 +1,122 @@
+// src/core/tests/validator_tests.rs
+
+use super::super::character::Character;
+use super::super::validator::CharacterValidator;
+
+fn character_from(extra: &str) -> Character {
+    let json = format!(
+        r#"{{
+            "instructions": {{ "base": "You are a test character.", "suffix": "Respond with a tweet." }},
+            "adjectives": ["cynical"],
+            "bio": {{ "headline": "Test headline", "key_traits": ["gets rugged daily"] }},
+            "lore": ["catchphrase is 'dev is selling!'"],
+            "styles": ["terse"],
+            "topics": ["memecoins"],
+            "post_style_examples": ["another day another rug"]
+            {}
+        }}"#,
+        extra
+    );
+    serde_json::from_str(&json).expect("test character should parse")
+}
+
+#[test]
+fn test_clean_character_passes() {
+    let character = character_from("");
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.is_ok(), "unexpected errors: {:?}", report.errors);
+}
+
+#[test]
+fn test_banned_word_collision() {
+    let character = character_from(r#", "banned_words": ["RUG"]"#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("post_style_examples[0]") && e.contains("rug")));
+    // "gets rugged daily" is a different word
+    assert!(!report.errors.iter().any(|e| e.contains("bio")));
+}
+
+#[test]
+fn test_unfilled_placeholder() {
+    let mut character = character_from("");
+    character.instructions.suffix = "Reply about {token_symbol}".to_string();
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("{token_symbol}")));
+}
+
+#[test]
+fn test_duplicate_catchphrase_across_lists() {
+    let mut character = character_from("");
+    character.post_style_examples.push("Catchphrase is: dev is selling!".to_string());
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("duplicates lore[0]")));
+}
+
+#[test]
+fn test_bad_trend_schedule() {
+    let character = character_from(r#", "trend_engagement": { "enabled": true, "schedule_minutes": [15, 75] }"#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("invalid minute 75")));
+    assert!(report.warnings.iter().any(|w| w.contains("minute 15 collides")));
+}
+
+#[test]
+fn test_api_quota_settings() {
+    let character = character_from(r#", "api_quota": { "reset_day": 31, "limits": { "tweets": 3000, "dms": 10 } }"#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("reset_day 31")));
+    assert!(report.warnings.iter().any(|w| w.contains("unknown endpoint 'dms'")));
+}
+
+#[test]
+fn test_shill_template_placeholders() {
+    let character = character_from(
+        r#", "shill": { "enabled": true, "prompt_template": "shill {ticker} at {address}" }"#,
+    );
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("missing the {symbol}")));
+    assert!(report.errors.iter().any(|e| e.contains("unknown placeholder '{ticker}'")));
+}
+
+#[test]
+fn test_storage_quotas() {
+    let character = character_from(r#", "storage": { "quotas_mb": { "charts": 10, "../home": 10, "audit": 10 } }"#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("can't include 'charts'")));
+    assert!(report.errors.iter().any(|e| e.contains("'../home' must be a subdirectory")));
+    assert_eq!(report.errors.len(), 2);
+}
+
+#[test]
+fn test_unknown_timezone() {
+    let character = character_from(r#", "timezone": "America/Gotham""#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("timezone 'America/Gotham'")));
+    let character = character_from(r#", "timezone": "America/Chicago""#);
+    assert!(CharacterValidator::validate(&character, "You are a test character.").is_ok());
+}
+
+#[test]
+fn test_pipeline_stages() {
+    let character = character_from(r#", "pipeline": { "stages": ["trim", "translate", "trim"] }"#);
+    let report = CharacterValidator::validate(&character, "You are a test character.");
+    assert!(report.errors.iter().any(|e| e.contains("unknown stage 'translate'")));
+    assert!(report.errors.iter().any(|e| e.contains("'trim' more than once")));
+    assert!(report.warnings.iter().any(|w| w.contains("leaves out sanitize")));
+}
+
+#[test]
+fn test_prompt_exceeding_context() {
+    let character = character_from("");
+    let instructions = "x".repeat(900_000);
+    let report = CharacterValidator::validate(&character, &instructions);
+    assert!(report.errors.iter().any(|e| e.contains("exceeds")));
+}
+
+#[test]
+fn test_shipped_characters_are_valid() {
+    for name in ["fud", "rina"] {
+        let report = CharacterValidator::validate_directory(name).expect("character should load");
+        assert!(report.is_ok(), "{} failed validation: {:?}", name, report.errors);
+    }
+}
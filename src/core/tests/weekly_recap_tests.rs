@@ -0,0 +1,62 @@
+// src/core/tests/weekly_recap_tests.rs
+
+use chrono::{Duration, TimeZone, Utc, Weekday};
+use super::super::character::WeeklyRecapSettings;
+use super::super::tweet_preview::TweetPreview;
+use super::super::weekly_recap::{RecapEntry, WeeklyRecap};
+use crate::models::FuddedToken;
+
+fn fudded(mint: &str, price: f64, days_ago: i64) -> FuddedToken {
+    let now = Utc.with_ymd_and_hms(2024, 5, 5, 18, 20, 0).unwrap();
+    FuddedToken {
+        mint: mint.to_string(),
+        symbol: mint.to_uppercase(),
+        tweet_id: None,
+        fudded_at: now - Duration::days(days_ago),
+        price_usd: price,
+        market_cap: price * 1e9,
+    }
+}
+
+#[test]
+fn test_first_fud_of_each_token_this_week() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 5, 18, 20, 0).unwrap();
+    let history = vec![fudded("rug", 0.002, 3), fudded("rug", 0.001, 1), fudded("old", 0.5, 9), fudded("dead", 0.0, 2)];
+    let fudded = WeeklyRecap::fudded_this_week(&history, now);
+    assert_eq!(fudded.len(), 1);
+    assert_eq!(fudded[0].price_usd, 0.002);
+}
+
+#[test]
+fn test_ranks_only_dumps_worst_first() {
+    let entries = vec![
+        RecapEntry::new(&fudded("mild", 1.0, 1), 0.8),
+        RecapEntry::new(&fudded("pumped", 1.0, 1), 1.5),
+        RecapEntry::new(&fudded("rekt", 1.0, 1), 0.1),
+    ];
+    let recap = WeeklyRecap::rank(entries, 3, 5);
+    assert_eq!(recap.lines(), vec!["1. $REKT -90.0% since i called it", "2. $MILD -20.0% since i called it"]);
+    assert!(recap.summary().contains("Tokens FUDded this week: 3"));
+}
+
+#[test]
+fn test_thread_packs_lines_into_tweets_that_fit() {
+    let entries = (0..40).map(|i| RecapEntry::new(&fudded(&format!("tok{}", i), 1.0, 1), 0.5)).collect();
+    let recap = WeeklyRecap::rank(entries, 40, 40);
+    let thread = recap.thread("the carnage:");
+    assert_eq!(thread[0], "the carnage:");
+    assert!(thread.len() > 2);
+    assert!(thread.iter().all(|tweet| TweetPreview::new(tweet).fits()));
+    assert_eq!(thread[1..].iter().map(|tweet| tweet.lines().count()).sum::<usize>(), 40);
+}
+
+#[test]
+fn test_due_once_a_week_at_the_configured_hour() {
+    let settings = WeeklyRecapSettings { enabled: true, weekday: Weekday::Sun, ..Default::default() };
+    let sunday = Utc.with_ymd_and_hms(2024, 5, 5, 18, 20, 0).unwrap();
+    assert!(WeeklyRecap::is_due(&settings, None, sunday));
+    assert!(!WeeklyRecap::is_due(&settings, Some(sunday - Duration::minutes(1)), sunday));
+    assert!(WeeklyRecap::is_due(&settings, Some(sunday - Duration::days(7)), sunday));
+    assert!(!WeeklyRecap::is_due(&settings, None, sunday + Duration::days(1)));
+    assert!(!WeeklyRecap::is_due(&WeeklyRecapSettings::default(), None, sunday));
+}
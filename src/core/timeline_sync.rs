@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::memory::MemoryStore;
+use crate::models::{Memory, Tweet, TweetType};
+use crate::providers::twitter::TimelinePost;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncReport {
+    // Memory entries that were missing their tweet ID
+    pub backfilled: usize,
+    // Queued replies that had already gone out
+    pub dequeued: usize,
+    // Tweets memory knew nothing about
+    pub recovered: usize,
+    // Newest tweet actually on the timeline
+    pub newest: Option<DateTime<Utc>>,
+}
+
+impl SyncReport {
+    pub fn changed(&self) -> bool {
+        self.backfilled + self.dequeued + self.recovered > 0
+    }
+}
+
+// Brings memory back in line with what the account really posted, for when the
+// bot died between posting and saving. Only memory is changed; the caller saves it.
+pub struct TimelineSync;
+
+impl TimelineSync {
+    // Twitter shortens links and escapes a few characters, so texts are compared
+    // without links, entities, case or extra whitespace
+    pub fn normalize(text: &str) -> String {
+        static LINK: OnceLock<Regex> = OnceLock::new();
+        let link = LINK.get_or_init(|| Regex::new(r"https?://\S+").unwrap());
+        let text = text.replace("&amp;", "&").replace("&lt;", "<").replace("&gt;", ">");
+        link.replace_all(&text, " ").split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+    }
+
+    // Original tweet on the timeline with this text
+    pub fn find_posted<'a>(text: &str, timeline: &'a [TimelinePost]) -> Option<&'a TimelinePost> {
+        let text = Self::normalize(text);
+        timeline.iter().find(|post| post.reply_to.is_none() && Self::normalize(&post.text) == text)
+    }
+
+    pub fn reconcile(memory: &mut Memory, timeline: &[TimelinePost], now: DateTime<Utc>) -> SyncReport {
+        let mut report = SyncReport {
+            newest: timeline.iter().filter_map(|post| post.created_at).max(),
+            ..SyncReport::default()
+        };
+
+        // Oldest first, so recovered tweets are added in the order they went out
+        let mut posts: Vec<&TimelinePost> = timeline.iter().collect();
+        posts.sort_by_key(|post| post.created_at);
+        for post in posts {
+            if memory.tweets.iter().any(|tweet| tweet.twitter_id.as_deref() == Some(post.id.as_str())) {
+                continue;
+            }
+
+            let text = Self::normalize(&post.text);
+            let unposted = memory.tweets.iter_mut().find(|tweet| {
+                tweet.twitter_id.is_none() && tweet.reply_to == post.reply_to && Self::normalize(&tweet.text) == text
+            });
+            if let Some(tweet) = unposted {
+                tweet.twitter_id = Some(post.id.clone());
+                report.backfilled += 1;
+                continue;
+            }
+
+            let queued = post
+                .reply_to
+                .as_ref()
+                .and_then(|reply_to| memory.reply_queue.iter().position(|queued| queued.reply_to == *reply_to));
+            let (prompt, token_mint, token_symbol) = match queued {
+                Some(index) => {
+                    report.dequeued += 1;
+                    let queued = memory.reply_queue.remove(index);
+                    (queued.prompt, queued.token_mint, queued.token_symbol)
+                }
+                None => {
+                    report.recovered += 1;
+                    (String::new(), None, None)
+                }
+            };
+            memory.tweets.push(Tweet {
+                internal_id: memory.next_id,
+                twitter_id: Some(post.id.clone()),
+                text: post.text.clone(),
+                prompt,
+                timestamp: post.created_at.unwrap_or(now),
+                tweet_type: if post.reply_to.is_some() { TweetType::Reply } else { TweetType::Original },
+                reply_to: post.reply_to.clone(),
+                metrics: None,
+                context: None,
+                token_mint,
+                token_symbol,
+            });
+            memory.next_id += 1;
+        }
+
+        if report.dequeued + report.recovered > 0 {
+            memory.tweets.sort_by_key(|tweet| tweet.timestamp);
+            MemoryStore::rebuild_reply_index(memory);
+        }
+        report
+    }
+}
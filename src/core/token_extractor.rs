@@ -0,0 +1,182 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+// Hosts whose URLs carry a mint or pair address in the path; a pair is resolved to
+// its token when it's looked up
+const TOKEN_URL_HOSTS: [&str; 6] = [
+    "dexscreener.com",
+    "birdeye.so",
+    "pump.fun",
+    "solscan.io",
+    "jup.ag",
+    "photon-sol.tinyastro.io",
+];
+
+// Words that follow "thoughts on" / "about" but are never tickers
+const IMPLIED_STOPWORDS: [&str; 16] = [
+    "the", "this", "that", "it", "its", "my", "your", "our", "their", "a", "an", "me", "you", "us", "them", "what",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenCandidate {
+    Address(String),
+    Ticker(String),
+}
+
+impl TokenCandidate {
+    // (token, is_address), the shape the runtime lookups take
+    pub fn into_parts(self) -> (String, bool) {
+        match self {
+            TokenCandidate::Address(address) => (address, true),
+            TokenCandidate::Ticker(ticker) => (ticker, false),
+        }
+    }
+}
+
+pub struct TokenExtractor;
+
+impl TokenExtractor {
+    fn cashtag_regex() -> &'static Regex {
+        static CASHTAG: OnceLock<Regex> = OnceLock::new();
+        // A $ not glued to a preceding word, then a ticker that starts with a letter (so $0.01 isn't one)
+        CASHTAG.get_or_init(|| Regex::new(r"(?:^|[^A-Za-z0-9_$])\$([A-Za-z][A-Za-z0-9_]{0,14})\b").unwrap())
+    }
+
+    fn address_regex() -> &'static Regex {
+        static ADDRESS: OnceLock<Regex> = OnceLock::new();
+        ADDRESS.get_or_init(|| Regex::new(r"\b[1-9A-HJ-NP-Za-km-z]{32,44}\b").unwrap())
+    }
+
+    // The 32 bytes a base58 address stands for, if it is one
+    pub fn decode_address(text: &str) -> Option<[u8; 32]> {
+        if text.len() < 32 || text.len() > 44 {
+            return None;
+        }
+        bs58::decode(text).into_vec().ok()?.try_into().ok()
+    }
+
+    // A real Solana address is base58 that decodes to exactly 32 bytes. All zeroes
+    // (a run of 1s) is the system program, never a token.
+    pub fn is_solana_address(text: &str) -> bool {
+        Self::decode_address(text).is_some_and(|bytes| bytes.iter().any(|byte| *byte != 0))
+    }
+
+    // Whether a mint, ticker or $cashtag names the bot's own token
+    pub fn is_own_token(token: &str, own_mint: &str, own_symbol: &str) -> bool {
+        let token = token.trim().trim_start_matches('$');
+        if token.is_empty() {
+            return false;
+        }
+        token == own_mint || (!own_symbol.is_empty() && token.eq_ignore_ascii_case(own_symbol.trim_start_matches('$')))
+    }
+
+    // Every token the text mentions, in the order they appear, without duplicates
+    pub fn extract_all(text: &str) -> Vec<TokenCandidate> {
+        let mut candidates = Vec::new();
+
+        for word in text.split_whitespace() {
+            if let Some(address) = Self::address_from_url(word) {
+                Self::push_unique(&mut candidates, TokenCandidate::Address(address));
+                continue;
+            }
+
+            // Cashtags can be URL-encoded inside links (e.g. search?q=%24BONK)
+            let decoded = word.replace("%24", "$");
+            for found in Self::scan(&decoded) {
+                Self::push_unique(&mut candidates, found);
+            }
+        }
+
+        if candidates.is_empty() {
+            if let Some(implied) = Self::implied_ticker(text) {
+                candidates.push(implied);
+            }
+        }
+
+        candidates
+    }
+
+    pub fn extract_first(text: &str) -> Option<TokenCandidate> {
+        Self::extract_all(text).into_iter().next()
+    }
+
+    // Addresses and cashtags within one whitespace-separated word, in order
+    fn scan(word: &str) -> Vec<TokenCandidate> {
+        let mut found: Vec<(usize, TokenCandidate)> = Vec::new();
+
+        for m in Self::address_regex().find_iter(word) {
+            if Self::is_solana_address(m.as_str()) {
+                found.push((m.start(), TokenCandidate::Address(m.as_str().to_string())));
+            }
+        }
+        for caps in Self::cashtag_regex().captures_iter(word) {
+            if let Some(ticker) = caps.get(1) {
+                found.push((ticker.start(), TokenCandidate::Ticker(ticker.as_str().to_string())));
+            }
+        }
+
+        found.sort_by_key(|(position, _)| *position);
+        found.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+
+    // Pull the mint out of dexscreener/birdeye/pump.fun style links
+    fn address_from_url(word: &str) -> Option<String> {
+        let word = word.trim_matches(|c: char| matches!(c, '(' | ')' | '<' | '>' | '"' | '\'' | ','));
+        let without_scheme = word
+            .strip_prefix("https://")
+            .or_else(|| word.strip_prefix("http://"))
+            .unwrap_or(word);
+        let (host, rest) = without_scheme.split_once('/')?;
+        let host = host.trim_start_matches("www.").to_lowercase();
+        if !TOKEN_URL_HOSTS.iter().any(|known| host == *known || host.ends_with(&format!(".{}", known))) {
+            return None;
+        }
+
+        rest.split(['/', '?', '&', '=', '#'])
+            .find(|segment| Self::is_solana_address(segment))
+            .map(|segment| segment.to_string())
+    }
+
+    // Fallback for "thoughts on bonk?" style questions without a cashtag
+    fn implied_ticker(text: &str) -> Option<TokenCandidate> {
+        // ASCII lowercasing keeps byte offsets, so positions found here slice `text` safely
+        let text_lower = text.to_ascii_lowercase();
+        let trigger_words = ["thoughts on", "think of", "about", "contract", "address"];
+
+        for trigger in trigger_words.iter() {
+            let Some(pos) = text_lower.find(trigger) else {
+                continue;
+            };
+            let after_trigger = &text[pos + trigger.len()..];
+            let Some(token) = after_trigger
+                .split_whitespace()
+                .next()
+                .map(|w| w.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '_'))
+            else {
+                continue;
+            };
+
+            if Self::is_solana_address(token) {
+                return Some(TokenCandidate::Address(token.to_string()));
+            }
+            let plausible = (2..=10).contains(&token.len())
+                && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !IMPLIED_STOPWORDS.contains(&token.to_lowercase().as_str());
+            if plausible {
+                return Some(TokenCandidate::Ticker(token.to_string()));
+            }
+        }
+
+        None
+    }
+
+    fn push_unique(candidates: &mut Vec<TokenCandidate>, candidate: TokenCandidate) {
+        let duplicate = candidates.iter().any(|existing| match (existing, &candidate) {
+            (TokenCandidate::Ticker(a), TokenCandidate::Ticker(b)) => a.eq_ignore_ascii_case(b),
+            (a, b) => a == b,
+        });
+        if !duplicate {
+            candidates.push(candidate);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+
+use super::character::TokenHeatSettings;
+use super::kv_store::{parse_time_key, time_key, KvStore};
+use crate::error::Result;
+
+// Below this a FUD no longer counts and its record is dropped
+const NEGLIGIBLE_HEAT: f64 = 0.01;
+
+// How much FUD a token has had lately from every character sharing the store.
+// Each FUD adds 1 and halves every half-life, so a token FUDded twice in a row
+// stays off limits twice as long as one FUDded once.
+pub struct TokenHeat;
+
+impl TokenHeat {
+    // mint@time, so one token's records sit together in key order
+    fn key(mint: &str, at: DateTime<Utc>) -> String {
+        format!("{}@{}", mint, time_key(at))
+    }
+
+    fn parse_key(key: &str) -> Option<(&str, DateTime<Utc>)> {
+        let (mint, at) = key.rsplit_once('@')?;
+        Some((mint, parse_time_key(at)?))
+    }
+
+    fn decayed(settings: &TokenHeatSettings, at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let half_lives = now.signed_duration_since(at).num_seconds().max(0) as f64
+            / (settings.half_life_hours.max(0.01) * 3600.0);
+        0.5f64.powf(half_lives)
+    }
+
+    pub fn heat(settings: &TokenHeatSettings, store: &dyn KvStore, mint: &str, now: DateTime<Utc>) -> f64 {
+        store
+            .entries()
+            .iter()
+            .filter_map(|(key, _)| Self::parse_key(key))
+            .filter(|(key_mint, _)| *key_mint == mint)
+            .map(|(_, at)| Self::decayed(settings, at, now))
+            .sum()
+    }
+
+    // Heat, if it's high enough that this character should leave the token alone
+    pub fn too_hot(settings: &TokenHeatSettings, store: &dyn KvStore, mint: &str, now: DateTime<Utc>) -> Option<f64> {
+        let heat = Self::heat(settings, store, mint, now);
+        (heat >= settings.max_heat).then_some(heat)
+    }
+
+    // Characters that FUDded the token recently, most recent last
+    pub fn fudded_by(store: &dyn KvStore, mint: &str) -> Vec<String> {
+        store
+            .entries()
+            .into_iter()
+            .filter(|(key, _)| Self::parse_key(key).is_some_and(|(key_mint, _)| key_mint == mint))
+            .map(|(_, character)| character)
+            .collect()
+    }
+
+    // Note a FUD by this character, dropping records that have cooled off entirely
+    pub fn record(
+        settings: &TokenHeatSettings,
+        store: &mut dyn KvStore,
+        mint: &str,
+        character: &str,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let cold: Vec<String> = store
+            .entries()
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| Self::parse_key(key).is_none_or(|(_, at)| Self::decayed(settings, at, now) < NEGLIGIBLE_HEAT))
+            .collect();
+        for key in cold {
+            store.remove(&key)?;
+        }
+        let mut at = now;
+        while store.contains(&Self::key(mint, at)) {
+            at += chrono::Duration::microseconds(1);
+        }
+        store.insert(&Self::key(mint, at), character)
+    }
+}
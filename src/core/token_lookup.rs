@@ -0,0 +1,83 @@
+use crate::providers::geckoterminal::GeckoTerminal;
+use crate::providers::solana_rpc::{AccountKind, SolanaRpc};
+use crate::providers::solanatracker::{SolanaTracker, TokenResponse};
+
+use super::token_extractor::TokenExtractor;
+
+// Finds the token a mention or inline query names. Holds its own handles on the
+// providers so lookups can run off the main loop.
+#[derive(Clone)]
+pub struct TokenLookup {
+    pub solana_tracker: SolanaTracker,
+    pub solana_rpc: SolanaRpc,
+    pub gecko_terminal: GeckoTerminal,
+    // Check an address is a mint on-chain before looking it up
+    pub verify_on_chain: bool,
+}
+
+impl TokenLookup {
+    pub async fn lookup(&self, token: &str, is_address: bool) -> Option<TokenResponse> {
+        if is_address {
+            if !TokenExtractor::is_solana_address(token) {
+                println!("{} isn't a Solana address, not looking it up", token);
+                return None;
+            }
+            if self.verify_on_chain {
+                match self.solana_rpc.get_account_kind(token).await {
+                    Ok(AccountKind::Mint) => {}
+                    // Could be a pool, whose token is the one meant
+                    Ok(AccountKind::Other) => {
+                        let Some(mint) = self.pair_token_mint(token).await else {
+                            println!("{} isn't a token mint or a pool, not looking it up", token);
+                            return None;
+                        };
+                        return self.solana_tracker.get_token_by_address(&mint).await.ok();
+                    }
+                    Ok(kind) => {
+                        println!("{} isn't a token mint on-chain ({:?}), not looking it up", token, kind);
+                        return None;
+                    }
+                    Err(e) => eprintln!("Couldn't check {} on-chain, looking it up anyway: {}", token, e),
+                }
+            }
+            return match self.solana_tracker.get_token_by_address(token).await {
+                Ok(found) => Some(found),
+                Err(_) => {
+                    let mint = self.pair_token_mint(token).await?;
+                    self.solana_tracker.get_token_by_address(&mint).await.ok()
+                }
+            };
+        }
+
+        let mut search_params = self.solana_tracker.create_search_params(token.to_string());
+        search_params.sort_by = Some("marketCapUsd".to_string());
+        search_params.sort_order = Some("desc".to_string());
+        search_params.limit = Some(1);
+        search_params.freeze_authority = Some("null".to_string());
+        search_params.mint_authority = Some("null".to_string());
+
+        match self.solana_tracker.token_search(search_params).await {
+            Ok(results) => results.into_iter().next(),
+            Err(e) => {
+                println!("Error searching for token {}: {}", token, e);
+                None
+            }
+        }
+    }
+
+    // Links like photon-sol's (and many dexscreener ones) carry the pool's address
+    // rather than the mint; GeckoTerminal knows which token a pool trades
+    async fn pair_token_mint(&self, address: &str) -> Option<String> {
+        match self.gecko_terminal.get_pool_token(address).await {
+            Ok(Some(mint)) => {
+                println!("{} is a pool, looking up its token {}", address, mint);
+                Some(mint)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("Couldn't check whether {} is a pool: {}", address, e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+pub const MAX_WEIGHTED_LENGTH: usize = 280;
+// Every link is shortened to a t.co URL of this length, however long it really is
+const URL_LENGTH: usize = 23;
+// Column width the dry-run preview wraps at, roughly a phone screen
+pub const PREVIEW_WIDTH: usize = 40;
+
+// Twitter counts these code points as one; everything else (CJK, emoji, most symbols) as two
+const LIGHT_RANGES: [(u32, u32); 4] = [
+    (0x0000, 0x10FF),
+    (0x2000, 0x200D),
+    (0x2010, 0x201F),
+    (0x2032, 0x2037),
+];
+
+pub(crate) fn url_regex() -> &'static Regex {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    // Not the full TLD list twitter-text uses, just links the bot actually posts
+    URL.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:https?://\S+|www\.\S+|[a-z0-9-]+(?:\.[a-z0-9-]+)*\.(?:com|io|fun|xyz|net|org|co|ai|app|gg|me|so|finance|exchange)\b\S*)",
+        )
+        .unwrap()
+    })
+}
+
+// How a tweet will count against the limit and how it will wrap
+pub struct TweetPreview {
+    pub text: String,
+    pub weighted_length: usize,
+}
+
+impl TweetPreview {
+    pub fn new(text: &str) -> Self {
+        TweetPreview {
+            text: text.to_string(),
+            weighted_length: Self::weighted_length(text),
+        }
+    }
+
+    // Twitter's weighted length: URLs count as 23, emoji sequences as 2, CJK as 2
+    pub fn weighted_length(text: &str) -> usize {
+        let mut length = 0;
+        let mut last = 0;
+        for url in url_regex().find_iter(text) {
+            length += Self::text_length(&text[last..url.start()]);
+            // Trailing punctuation isn't part of the link
+            let link = url.as_str().trim_end_matches(['.', ',', '!', '?', ')', ':', ';']);
+            length += URL_LENGTH + Self::text_length(&url.as_str()[link.len()..]);
+            last = url.end();
+        }
+        length + Self::text_length(&text[last..])
+    }
+
+    fn text_length(text: &str) -> usize {
+        let chars: Vec<char> = text.chars().collect();
+        let mut length = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let emoji_len = Self::emoji_sequence_len(&chars[i..]);
+            if emoji_len > 0 {
+                length += 2;
+                i += emoji_len;
+            } else {
+                length += Self::char_weight(chars[i]);
+                i += 1;
+            }
+        }
+        length
+    }
+
+    fn char_weight(c: char) -> usize {
+        let code = c as u32;
+        if LIGHT_RANGES.iter().any(|(start, end)| (*start..=*end).contains(&code)) {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn is_emoji(c: char) -> bool {
+        matches!(c as u32,
+            0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2300..=0x23FF | 0x2B00..=0x2BFF)
+    }
+
+    fn is_modifier(c: char) -> bool {
+        matches!(c as u32, 0xFE0E | 0xFE0F | 0x1F3FB..=0x1F3FF | 0x20E3 | 0xE0020..=0xE007F)
+    }
+
+    // Chars making up the emoji at the start of `chars` (ZWJ families, flags,
+    // skin tones, keycaps all count once), or 0 if it doesn't start with one
+    fn emoji_sequence_len(chars: &[char]) -> usize {
+        let first = chars[0];
+        let is_regional = |c: char| (0x1F1E6..=0x1F1FF).contains(&(c as u32));
+        if is_regional(first) {
+            return if chars.get(1).is_some_and(|c| is_regional(*c)) { 2 } else { 1 };
+        }
+        let keycap = matches!(first, '0'..='9' | '#' | '*')
+            && chars.get(1).is_some_and(|c| matches!(*c as u32, 0xFE0F | 0x20E3))
+            && chars[1..].iter().take(2).any(|c| *c as u32 == 0x20E3);
+        if !Self::is_emoji(first) && !keycap {
+            return 0;
+        }
+
+        let mut len = 1;
+        while len < chars.len() {
+            if Self::is_modifier(chars[len]) {
+                len += 1;
+            } else if chars[len] == '\u{200D}' && chars.get(len + 1).is_some_and(|c| Self::is_emoji(*c)) {
+                len += 2;
+            } else {
+                break;
+            }
+        }
+        len
+    }
+
+    pub fn fits(&self) -> bool {
+        self.weighted_length <= MAX_WEIGHTED_LENGTH
+    }
+
+    // Refuse locally rather than let the API reject it
+    pub fn check(text: &str) -> Result<(), anyhow::Error> {
+        let preview = Self::new(text);
+        if !preview.fits() {
+            return Err(anyhow::anyhow!(
+                "Tweet is too long: {}/{} weighted characters",
+                preview.weighted_length,
+                MAX_WEIGHTED_LENGTH
+            ));
+        }
+        Ok(())
+    }
+
+    // Word-wrapped lines, keeping the tweet's own line breaks
+    pub fn wrap(&self, width: usize) -> Vec<String> {
+        let width = width.max(1);
+        let mut lines = Vec::new();
+        for paragraph in self.text.lines() {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let needed = if line.is_empty() { 0 } else { 1 } + word.chars().count();
+                if !line.is_empty() && line.chars().count() + needed > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    pub fn render(&self, width: usize) -> String {
+        let lines = self.wrap(width);
+        let inner = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(width);
+        let mut out = format!("┌{}┐\n", "─".repeat(inner + 2));
+        for line in &lines {
+            out.push_str(&format!("│ {}{} │\n", line, " ".repeat(inner - line.chars().count())));
+        }
+        out.push_str(&format!("└{}┘\n", "─".repeat(inner + 2)));
+        if self.fits() {
+            out.push_str(&format!("{}/{}", self.weighted_length, MAX_WEIGHTED_LENGTH));
+        } else {
+            out.push_str(&format!(
+                "{}/{} (over by {}, the API will reject this)",
+                self.weighted_length,
+                MAX_WEIGHTED_LENGTH,
+                self.weighted_length - MAX_WEIGHTED_LENGTH
+            ));
+        }
+        out
+    }
+}
@@ -0,0 +1,724 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::io;
+use super::aggression::{MAX_LEVEL, MIN_LEVEL};
+use super::clock::FUD_SCHEDULE_MINUTES;
+use super::character::{Character, GenerationParams, ReceiptMode, ShillSettings, MAX_RECEIPT_BUY_LAMPORTS};
+use super::content::ContentGenerators;
+use super::instruction_builder::InstructionBuilder;
+use super::local_time::LocalTime;
+use super::pipeline::STAGE_NAMES;
+use super::storage::PROTECTED_DIRS;
+use super::stream_guard::contains_term;
+use super::token_extractor::TokenExtractor;
+use super::prompt_budget::estimate_tokens;
+use super::reply_rules::{ReplyRules, RuleAction};
+use crate::providers::twitter::ENDPOINTS;
+
+// Context window of the model the agents run on (Claude 3 Haiku)
+const MODEL_CONTEXT_TOKENS: usize = 200_000;
+// Above this share of the context the preamble still works but every call gets expensive
+const PROMPT_WARNING_SHARE: f64 = 0.25;
+
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn print(&self, character_name: &str) {
+        for warning in &self.warnings {
+            println!("[{}] warning: {}", character_name, warning);
+        }
+        for error in &self.errors {
+            eprintln!("[{}] error: {}", character_name, error);
+        }
+        println!(
+            "[{}] {} error(s), {} warning(s)",
+            character_name,
+            self.errors.len(),
+            self.warnings.len()
+        );
+    }
+}
+
+pub struct CharacterValidator;
+
+impl CharacterValidator {
+    // Load characters/<name> under FUD_HOME and lint it
+    pub fn validate_directory(character_name: &str) -> io::Result<ValidationReport> {
+        let character = InstructionBuilder::load_character(character_name)?;
+        let mut instruction_builder = InstructionBuilder::new();
+        instruction_builder.build_instructions(character_name)?;
+        Ok(Self::validate(&character, instruction_builder.get_instructions()))
+    }
+
+    // Lint a loaded character and the preamble built from it
+    pub fn validate(character: &Character, instructions: &str) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        Self::check_prompt_length(character, instructions, &mut report);
+        Self::check_banned_words(character, &mut report);
+        Self::check_placeholders(character, &mut report);
+        Self::check_schedules(character, &mut report);
+        Self::check_shill(character, &mut report);
+        Self::check_generation(character, &mut report);
+        Self::check_duplicates(character, &mut report);
+
+        report
+    }
+
+    // Every text field of the character with a label for error messages
+    fn text_fields(character: &Character) -> Vec<(String, &str)> {
+        let mut fields = vec![
+            ("instructions.base".to_string(), character.instructions.base.as_str()),
+            ("instructions.suffix".to_string(), character.instructions.suffix.as_str()),
+            ("bio.headline".to_string(), character.bio.headline.as_str()),
+        ];
+        let lists = [
+            ("adjectives", &character.adjectives),
+            ("bio.key_traits", &character.bio.key_traits),
+            ("lore", &character.lore),
+            ("styles", &character.styles),
+            ("topics", &character.topics),
+            ("post_style_examples", &character.post_style_examples),
+        ];
+        for (name, entries) in lists {
+            for (i, entry) in entries.iter().enumerate() {
+                fields.push((format!("{}[{}]", name, i), entry.as_str()));
+            }
+        }
+        fields
+    }
+
+    fn check_prompt_length(character: &Character, instructions: &str, report: &mut ValidationReport) {
+        if instructions.trim().is_empty() {
+            report.errors.push("built instructions are empty".to_string());
+            return;
+        }
+
+        let prompt_tokens = estimate_tokens(instructions);
+        let generation = &character.generation;
+        let max_output = [&generation.classify, &generation.classify_batch, &generation.reply, &generation.post, &generation.fud, &generation.custom]
+            .iter()
+            .filter_map(|params| params.max_tokens)
+            .max()
+            .unwrap_or(1024) as usize;
+
+        let budget = character.settings.prompt_budget.max_input_tokens;
+        if budget > 0 && prompt_tokens > budget {
+            report.warnings.push(format!(
+                "instructions are ~{} tokens, over the {} token prompt budget; lower-priority sections will be trimmed on every call",
+                prompt_tokens, budget
+            ));
+        }
+
+        if prompt_tokens + max_output > MODEL_CONTEXT_TOKENS {
+            report.errors.push(format!(
+                "instructions are ~{} tokens, which with {} output tokens exceeds the {} token context",
+                prompt_tokens, max_output, MODEL_CONTEXT_TOKENS
+            ));
+        } else if prompt_tokens as f64 > MODEL_CONTEXT_TOKENS as f64 * PROMPT_WARNING_SHARE {
+            report.warnings.push(format!(
+                "instructions are ~{} tokens, over {:.0}% of the model context",
+                prompt_tokens,
+                PROMPT_WARNING_SHARE * 100.0
+            ));
+        }
+    }
+
+    fn check_banned_words(character: &Character, report: &mut ValidationReport) {
+        let banned: Vec<String> = character.settings.banned_words
+            .iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        if banned.is_empty() {
+            return;
+        }
+
+        for (field, text) in Self::text_fields(character) {
+            let lowered = text.to_lowercase();
+            for word in &banned {
+                if contains_term(&lowered, word, false) {
+                    report.errors.push(format!("{} uses banned word '{}'", field, word));
+                }
+            }
+        }
+    }
+
+    // Character text is passed to the model verbatim, so a `{...}` here is a template that never gets filled
+    fn check_placeholders(character: &Character, report: &mut ValidationReport) {
+        if character.instructions.base.trim().is_empty() {
+            report.errors.push("instructions.base is empty".to_string());
+        }
+        if character.instructions.suffix.trim().is_empty() {
+            report.warnings.push("instructions.suffix is empty".to_string());
+        }
+
+        for (field, text) in Self::text_fields(character) {
+            let mut rest = text;
+            while let Some(start) = rest.find('{') {
+                let after = &rest[start + 1..];
+                match after.find('}') {
+                    Some(end) => {
+                        report.errors.push(format!(
+                            "{} contains unfilled placeholder '{{{}}}'",
+                            field,
+                            &after[..end]
+                        ));
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        report.errors.push(format!("{} has an unclosed '{{'", field));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_schedules(character: &Character, report: &mut ValidationReport) {
+        let settings = &character.settings;
+
+        let trends = &settings.trend_engagement;
+        if trends.enabled {
+            if trends.schedule_minutes.is_empty() {
+                report.errors.push("trend_engagement is enabled but schedule_minutes is empty".to_string());
+            }
+            let mut seen = Vec::new();
+            for minute in &trends.schedule_minutes {
+                if *minute >= 60 {
+                    report.errors.push(format!("trend_engagement.schedule_minutes has invalid minute {}", minute));
+                } else if FUD_SCHEDULE_MINUTES.contains(minute) {
+                    report.warnings.push(format!(
+                        "trend_engagement minute {} collides with the scheduled FUD post",
+                        minute
+                    ));
+                }
+                if seen.contains(minute) {
+                    report.warnings.push(format!("trend_engagement.schedule_minutes lists {} twice", minute));
+                }
+                seen.push(*minute);
+            }
+            if trends.max_replies_per_hour == 0 {
+                report.warnings.push("trend_engagement is enabled but max_replies_per_hour is 0".to_string());
+            }
+        }
+
+        let network = &settings.network_fud;
+        if network.enabled {
+            if network.schedule_hours.is_empty() {
+                report.errors.push("network_fud is enabled but schedule_hours is empty".to_string());
+            }
+            if let Some(hour) = network.schedule_hours.iter().find(|hour| **hour >= 24) {
+                report.errors.push(format!("network_fud.schedule_hours has invalid hour {}", hour));
+            }
+            if network.minute >= 60 {
+                report.errors.push(format!("network_fud.minute {} is not a valid minute", network.minute));
+            } else if FUD_SCHEDULE_MINUTES.contains(&network.minute) {
+                report.warnings.push(format!(
+                    "network_fud minute {} collides with the scheduled FUD post",
+                    network.minute
+                ));
+            }
+        }
+
+        let recap = &settings.weekly_recap;
+        if recap.enabled {
+            if recap.hour >= 24 {
+                report.errors.push(format!("weekly_recap.hour {} is not a valid hour", recap.hour));
+            }
+            if recap.minute >= 60 {
+                report.errors.push(format!("weekly_recap.minute {} is not a valid minute", recap.minute));
+            } else if FUD_SCHEDULE_MINUTES.contains(&recap.minute) {
+                report.warnings.push(format!("weekly_recap minute {} collides with the scheduled FUD post", recap.minute));
+            }
+            if recap.top_tokens == 0 {
+                report.errors.push("weekly_recap.top_tokens must be at least 1".to_string());
+            }
+        }
+
+        if let Some(timezone) = &settings.timezone {
+            if LocalTime::parse(timezone).is_none() {
+                report.errors.push(format!("timezone '{}' is not a known IANA timezone name, e.g. America/New_York", timezone));
+            }
+        }
+        let quiet = &settings.quiet_hours;
+        if quiet.enabled {
+            if quiet.start_hour >= 24 || quiet.end_hour > 24 {
+                report.errors.push(format!(
+                    "quiet_hours {}-{} is not a valid range of hours",
+                    quiet.start_hour, quiet.end_hour
+                ));
+            } else if quiet.start_hour == quiet.end_hour {
+                report.warnings.push("quiet_hours is enabled but start_hour equals end_hour, so it never applies".to_string());
+            }
+        }
+
+        let predictions = &settings.predictions;
+        if predictions.drop_pct <= 0.0 || predictions.drop_pct >= 100.0 {
+            report.errors.push("predictions.drop_pct must be above 0 and below 100".to_string());
+        }
+        if predictions.deadline_hours <= 0 {
+            report.errors.push("predictions.deadline_hours must be positive".to_string());
+        }
+        if predictions.check_interval_minutes <= 0 {
+            report.errors.push("predictions.check_interval_minutes must be positive".to_string());
+        }
+
+        let adaptive = &settings.adaptive_schedule;
+        if adaptive.enabled {
+            if adaptive.min_interval_minutes <= 0 || adaptive.max_interval_minutes < adaptive.min_interval_minutes {
+                report.errors.push("adaptive_schedule needs 0 < min_interval_minutes <= max_interval_minutes".to_string());
+            }
+            if adaptive.low_engagement > adaptive.high_engagement {
+                report.errors.push("adaptive_schedule.low_engagement is above high_engagement".to_string());
+            }
+            if adaptive.step <= 1.0 {
+                report.errors.push("adaptive_schedule.step must be above 1".to_string());
+            }
+            if adaptive.lookback_posts == 0 {
+                report.errors.push("adaptive_schedule.lookback_posts must be at least 1".to_string());
+            }
+            if adaptive.min_interval_minutes > 0 && adaptive.min_interval_minutes < 15 {
+                report.warnings.push("adaptive_schedule.min_interval_minutes below 15 has no effect; scheduled posts run every 15 minutes".to_string());
+            }
+        }
+
+        let heat = &settings.token_heat;
+        if heat.enabled {
+            if heat.half_life_hours <= 0.0 {
+                report.errors.push("token_heat.half_life_hours must be positive".to_string());
+            }
+            if heat.max_heat <= 0.0 {
+                report.errors.push("token_heat.max_heat must be positive".to_string());
+            }
+            if heat.store_path.trim().is_empty() {
+                report.errors.push("token_heat.store_path is empty".to_string());
+            }
+        }
+
+        let budget = &settings.latency_budget;
+        if budget.enabled && budget.llm_seconds > 0 && budget.llm_seconds < 10 {
+            report.warnings.push("latency_budget.llm_seconds below 10 will time out most completions".to_string());
+        }
+
+        let news_weighted = settings.content_schedule.iter().any(|slot| slot.weight(ContentGenerators::NEWS) > 0);
+        if news_weighted && settings.news.keywords.iter().all(|k| k.trim().is_empty()) {
+            report.warnings.push("news is weighted in content_schedule but news.keywords is empty, so no headline is ever picked".to_string());
+        }
+
+        let space_weighted = settings.content_schedule.iter().any(|slot| slot.weight(ContentGenerators::SPACE) > 0);
+        if space_weighted && settings.spaces.search_terms.iter().all(|term| term.trim().is_empty()) {
+            report.warnings.push("space is weighted in content_schedule but spaces.search_terms is empty, so no Space is ever found".to_string());
+        }
+        let guard = &settings.stream_guard;
+        if guard.enabled && settings.banned_words.iter().chain(&guard.blocklist).all(|term| term.trim().is_empty()) {
+            report.warnings.push("stream_guard is enabled but banned_words and stream_guard.blocklist are empty, so nothing is streamed".to_string());
+        }
+        let storage = &settings.storage;
+        if storage.enabled && storage.interval_minutes <= 0 {
+            report.errors.push("storage.interval_minutes must be positive".to_string());
+        }
+        for name in storage.quotas_mb.keys() {
+            if PROTECTED_DIRS.contains(&name.as_str()) {
+                report.errors.push(format!("storage.quotas_mb can't include '{}'; its files are never deleted", name));
+            } else if name.contains(['/', '\\']) || name.starts_with('.') {
+                report.errors.push(format!("storage.quotas_mb key '{}' must be a subdirectory name of storage/", name));
+            }
+        }
+        let stages = &settings.pipeline.stages;
+        for (i, stage) in stages.iter().enumerate() {
+            if !STAGE_NAMES.contains(&stage.as_str()) {
+                report.errors.push(format!("pipeline.stages has unknown stage '{}'; known stages are {}", stage, STAGE_NAMES.join(", ")));
+            } else if stages[..i].contains(stage) {
+                report.errors.push(format!("pipeline.stages lists '{}' more than once", stage));
+            }
+        }
+        if !stages.iter().any(|stage| stage == "sanitize") {
+            report.warnings.push("pipeline.stages leaves out sanitize, so model preambles and commentary can end up in posts".to_string());
+        }
+
+        let replies = &settings.language.replies;
+        if replies.match_mention && replies.fallback.trim().is_empty() {
+            report.errors.push("language.replies.fallback is empty".to_string());
+        }
+        if !(5..=100).contains(&settings.timeline_sync.max_tweets) {
+            report.warnings.push("timeline_sync.max_tweets is clamped to 5-100".to_string());
+        }
+        if settings.chart_images.reuse_after_days < 0 {
+            report.errors.push("chart_images.reuse_after_days can't be negative".to_string());
+        }
+        let outbox = &settings.outbox;
+        if outbox.enabled && outbox.max_attempts == 0 {
+            report.errors.push("outbox.max_attempts must be at least 1".to_string());
+        }
+        if outbox.base_backoff_minutes > outbox.max_backoff_minutes {
+            report.warnings.push("outbox.base_backoff_minutes is above max_backoff_minutes, so every retry waits the maximum".to_string());
+        }
+        if outbox.max_age_hours <= 0 || outbox.keep_days < 0 {
+            report.errors.push("outbox.max_age_hours must be positive and outbox.keep_days can't be negative".to_string());
+        }
+        let inline = &settings.telegram_inline;
+        if inline.enabled && inline.max_per_poll == 0 {
+            report.warnings.push("telegram_inline is enabled but max_per_poll is 0, so no query is answered".to_string());
+        }
+        let consistency = &settings.consistency_check;
+        if consistency.enabled && (consistency.sample_size < 2 || consistency.interval_hours <= 0) {
+            report.errors.push("consistency_check needs a sample_size of at least 2 and a positive interval_hours".to_string());
+        }
+        if consistency.enabled && consistency.flag_topics && consistency.avoid_days <= 0 {
+            report.warnings.push("consistency_check.flag_topics is on but avoid_days is not positive, so nothing is avoided".to_string());
+        }
+        let buffer = &settings.content_buffer;
+        if buffer.enabled {
+            if !outbox.enabled {
+                report.warnings.push("content_buffer is enabled but the outbox is off, so nothing is buffered".to_string());
+            }
+            if buffer.size == 0 || buffer.max_age_minutes <= 0 {
+                report.errors.push("content_buffer.size and max_age_minutes must be positive".to_string());
+            } else if buffer.refresh_after_minutes >= buffer.max_age_minutes {
+                report.warnings.push("content_buffer.refresh_after_minutes is not below max_age_minutes, so numbers are never refreshed".to_string());
+            }
+        }
+        if settings.spaces.lookahead_hours <= 0 {
+            report.errors.push("spaces.lookahead_hours must be positive".to_string());
+        }
+
+        for (i, rule) in settings.reply_rules.iter().enumerate() {
+            if !ReplyRules::has_conditions(rule) {
+                report.errors.push(format!("reply_rules[{}] has no any, all or regex, so it never matches", i));
+            }
+            if let Some(Err(e)) = rule.regex.as_deref().map(Regex::new) {
+                report.errors.push(format!("reply_rules[{}].regex is invalid: {}", i, e));
+            }
+            if rule.action == RuleAction::Canned && rule.replies.iter().all(|reply| reply.trim().is_empty()) {
+                report.errors.push(format!("reply_rules[{}] is canned but has no replies", i));
+            }
+        }
+
+        let attachments = &settings.attachments;
+        if attachments.enabled && attachments.max_images == 0 {
+            report.warnings.push("attachments.max_images is 0, so attached images are never looked at".to_string());
+        }
+
+        let quota = &settings.api_quota;
+        if !(1..=28).contains(&quota.reset_day) {
+            report.errors.push(format!("api_quota.reset_day {} must be between 1 and 28", quota.reset_day));
+        }
+        if !(0.0..100.0).contains(&quota.post_reserve_pct) {
+            report.errors.push("api_quota.post_reserve_pct must be at least 0 and below 100".to_string());
+        }
+        for endpoint in quota.limits.keys() {
+            if !ENDPOINTS.contains(&endpoint.as_str()) {
+                report.warnings.push(format!(
+                    "api_quota.limits has unknown endpoint '{}' (known: {})",
+                    endpoint,
+                    ENDPOINTS.join(", ")
+                ));
+            }
+        }
+
+        let whales = &settings.whale_alerts;
+        if whales.enabled {
+            if whales.check_interval_minutes <= 0 {
+                report.errors.push("whale_alerts.check_interval_minutes must be positive".to_string());
+            }
+            if whales.watch_hours <= 0 {
+                report.errors.push("whale_alerts.watch_hours must be positive".to_string());
+            }
+            if whales.bands.is_empty() {
+                report.errors.push("whale_alerts is enabled but has no bands".to_string());
+            }
+            let mut previous_max = 0.0;
+            for (i, band) in whales.bands.iter().enumerate() {
+                if band.min_sell_usd <= 0.0 {
+                    report.errors.push(format!("whale_alerts.bands[{}].min_sell_usd must be positive", i));
+                }
+                match band.max_market_cap {
+                    Some(max) if max <= previous_max => {
+                        report.errors.push(format!(
+                            "whale_alerts.bands[{}] is out of order; bands must have increasing max_market_cap",
+                            i
+                        ));
+                    }
+                    Some(max) => previous_max = max,
+                    None if i + 1 < whales.bands.len() => {
+                        report.warnings.push(format!(
+                            "whale_alerts.bands[{}] has no max_market_cap, so later bands are never used",
+                            i
+                        ));
+                    }
+                    None => {}
+                }
+            }
+            if whales.bands.last().is_some_and(|band| band.max_market_cap.is_some()) {
+                report.warnings.push("whale_alerts has no open-ended last band; larger tokens are never watched".to_string());
+            }
+        }
+
+        let queue = &settings.reply_queue;
+        if queue.replies_per_window == 0 || queue.window_minutes <= 0 {
+            report.errors.push("reply_queue needs a positive replies_per_window and window_minutes".to_string());
+        }
+
+        let decay = &settings.phrase_decay;
+        if decay.max_phrases == 0 || decay.max_age_hours <= 0 {
+            report.errors.push("phrase_decay needs a positive max_phrases and max_age_hours".to_string());
+        }
+
+        if settings.past_takes.enabled && settings.past_takes.lookback_days <= 0 {
+            report.warnings.push("past_takes.lookback_days is not positive, so no past takes are ever shown".to_string());
+        }
+        let callbacks = &settings.callbacks;
+        if callbacks.enabled && callbacks.lookback_days <= callbacks.min_age_days {
+            report.warnings.push("callbacks.lookback_days is not above min_age_days, so no post is ever called back to".to_string());
+        }
+        let facts = &settings.learned_facts;
+        if facts.enabled && (facts.lookback_days <= 0 || facts.max_in_prompt == 0) {
+            report.warnings.push("learned_facts.lookback_days is not positive or max_in_prompt is 0, so learned facts are never shown".to_string());
+        }
+
+        if settings.spam_filter.sync_twitter_blocks && settings.spam_filter.block_sync_hours <= 0 {
+            report.errors.push("spam_filter.block_sync_hours must be positive when sync_twitter_blocks is on".to_string());
+        }
+
+        if settings.birdeye.trades > 50 {
+            report.errors.push("birdeye.trades can be at most 50, the most Birdeye returns at once".to_string());
+        }
+        if settings.birdeye.markets > 20 {
+            report.errors.push("birdeye.markets can be at most 20, the most Birdeye returns at once".to_string());
+        }
+        let targets = &settings.fud_targets;
+        if targets.exclude_cex_listed && targets.check_listings && targets.major_exchanges.is_empty() {
+            report.warnings.push("fud_targets.major_exchanges is empty, so the CoinGecko listing check never excludes anything".to_string());
+        }
+        for listed in targets.cex_listed.iter().filter(|listed| !TokenExtractor::is_solana_address(listed.trim())) {
+            report.warnings.push(format!("fud_targets.cex_listed entry '{}' isn't a mint address, so it's ignored", listed));
+        }
+        let bundle_check = &settings.bundle_check;
+        if bundle_check.enabled && (bundle_check.launch_slots == 0 || bundle_check.max_transactions == 0 || bundle_check.max_signature_pages == 0) {
+            report.errors.push("bundle_check.launch_slots, max_transactions and max_signature_pages must be positive".to_string());
+        }
+        if !(0.0..=100.0).contains(&bundle_check.min_supply_pct) {
+            report.errors.push("bundle_check.min_supply_pct must be between 0 and 100".to_string());
+        }
+        if settings.reply_policy.followers_only && settings.reply_policy.follower_sync_hours <= 0 {
+            report.errors.push("reply_policy.follower_sync_hours must be positive when followers_only is on".to_string());
+        }
+        if settings.reply_policy.min_account_age_days < 0 {
+            report.errors.push("reply_policy.min_account_age_days can't be negative".to_string());
+        }
+        if settings.reply_limits.max_thread_depth == 0 {
+            report.warnings.push("reply_limits.max_thread_depth is 0, so nothing stops reply loops with other bots".to_string());
+        }
+
+        if settings.few_shot.enabled && settings.few_shot.examples == 0 {
+            report.warnings.push("few_shot is enabled with 0 examples, so nothing is added to the prompt".to_string());
+        }
+
+        let targets = &settings.fud_targets;
+        let bounds = [
+            ("market_cap", targets.min_market_cap, targets.max_market_cap),
+            ("change_24h", targets.min_change_24h, targets.max_change_24h),
+        ];
+        for (name, min, max) in bounds {
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    report.errors.push(format!("fud_targets.min_{} is above max_{}, so nothing is ever picked", name, name));
+                }
+            }
+        }
+
+        let scoring = &settings.target_scoring;
+        let weights = [
+            ("base_weight", scoring.base_weight),
+            ("gain_1h_weight", scoring.gain_1h_weight),
+            ("trade_imbalance_weight", scoring.trade_imbalance_weight),
+            ("thin_liquidity_weight", scoring.thin_liquidity_weight),
+        ];
+        for (name, weight) in weights {
+            if weight < 0.0 {
+                report.warnings.push(format!("target_scoring.{} is negative and is treated as 0", name));
+            }
+        }
+
+        if settings.price_check.enabled && settings.price_check.max_ratio < 1.0 {
+            report.warnings.push("price_check.max_ratio is below 1, so every market cap counts as a mismatch".to_string());
+        }
+
+        if settings.spam_filter.muted.iter().any(|entry| entry.trim().trim_start_matches('@').is_empty()) {
+            report.warnings.push("spam_filter.muted has an empty entry".to_string());
+        }
+
+        if targets.max_age_hours.is_some_and(|max| max <= 0.0) {
+            report.errors.push("fud_targets.max_age_hours is not positive, so nothing is ever picked".to_string());
+        }
+
+        let generators = ContentGenerators::get_generators();
+        for (i, slot) in settings.content_schedule.iter().enumerate() {
+            if slot.start_hour >= 24 || slot.end_hour > 24 {
+                report.errors.push(format!("content_schedule[{}] hours must be between 0 and 24", i));
+            } else if slot.start_hour == slot.end_hour {
+                report.warnings.push(format!("content_schedule[{}] covers no hours", i));
+            }
+            for name in slot.other.keys() {
+                if ContentGenerators::find(&generators, name).is_none() {
+                    report.warnings.push(format!("content_schedule[{}] has unknown content type '{}'", i, name));
+                }
+            }
+            if slot.total_weight() == 0 {
+                report.warnings.push(format!("content_schedule[{}] has no weights, so it always posts FUD", i));
+            }
+        }
+
+        let receipts = &settings.onchain_receipts;
+        if receipts.enabled {
+            if receipts.mode == ReceiptMode::Buy && (receipts.buy_lamports == 0 || receipts.buy_lamports > MAX_RECEIPT_BUY_LAMPORTS) {
+                report.errors.push(format!("onchain_receipts.buy_lamports must be between 1 and {}", MAX_RECEIPT_BUY_LAMPORTS));
+            }
+            if receipts.max_receipts_per_day == 0 || receipts.max_lamports_per_day < receipts.cost_lamports() {
+                report.warnings.push("onchain_receipts is enabled but its daily caps never allow a receipt".to_string());
+            }
+        }
+
+        if !(0.0..=1.0).contains(&settings.website_roast.chance) {
+            report.errors.push("website_roast.chance must be between 0 and 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&settings.reaction_clips.chance) {
+            report.errors.push("reaction_clips.chance must be between 0 and 1".to_string());
+        }
+
+        let decoration = &settings.decoration;
+        for (name, chance) in [("emoji_chance", decoration.emoji_chance), ("punctuation_chance", decoration.punctuation_chance)] {
+            if !(0.0..=1.0).contains(&chance) {
+                report.errors.push(format!("decoration.{} must be between 0 and 1", name));
+            }
+        }
+        let campaigns = &settings.campaigns;
+        if !(0.0..=1.0).contains(&campaigns.target_share) {
+            report.errors.push("campaigns.target_share must be between 0 and 1".to_string());
+        }
+        if campaigns.price_check_minutes <= 0 || campaigns.default_hours <= 0 || campaigns.max_hours < campaigns.default_hours {
+            report.errors.push("campaigns needs a positive price_check_minutes and default_hours, and max_hours of at least default_hours".to_string());
+        }
+        let aggression = &settings.aggression;
+        for (name, level) in [("level", aggression.level), ("tame_below", aggression.tame_below)] {
+            if !(MIN_LEVEL..=MAX_LEVEL).contains(&level) {
+                report.errors.push(format!("aggression.{} must be between {} and {}", name, MIN_LEVEL, MAX_LEVEL));
+            }
+        }
+        let usable_emojis = decoration.emojis.iter().filter(|emoji| !decoration.never_use.contains(emoji)).count();
+        if decoration.emoji_chance > 0.0 && (usable_emojis == 0 || decoration.max_emojis == 0) {
+            report.warnings.push("decoration.emoji_chance is set but no emojis can be added".to_string());
+        }
+        for (field, text) in Self::text_fields(character) {
+            if let Some(never) = decoration.never_use.iter().find(|never| !never.is_empty() && text.contains(never.as_str())) {
+                report.warnings.push(format!("{} uses '{}', which decoration.never_use strips from posts", field, never));
+            }
+        }
+
+        let language = &settings.language;
+        if !(0.0..=1.0).contains(&language.rotation_chance) {
+            report.errors.push("language.rotation_chance must be between 0 and 1".to_string());
+        }
+        if language.rotation_chance > 0.0 && language.rotation.is_empty() {
+            report.warnings.push("language.rotation_chance is set but rotation is empty".to_string());
+        }
+    }
+
+    fn check_shill(character: &Character, report: &mut ValidationReport) {
+        let shill = &character.settings.shill;
+        if !shill.enabled {
+            return;
+        }
+        if !(0.0..=1.0).contains(&shill.weight) {
+            report.errors.push("shill.weight must be between 0 and 1".to_string());
+        }
+        if !shill.prompt_template.contains("{symbol}") {
+            report.errors.push("shill.prompt_template is missing the {symbol} placeholder".to_string());
+        }
+
+        let mut rest = shill.prompt_template.as_str();
+        while let Some(start) = rest.find('{') {
+            let after = &rest[start..];
+            let Some(end) = after.find('}') else {
+                report.errors.push("shill.prompt_template has an unclosed '{'".to_string());
+                break;
+            };
+            let placeholder = &after[..=end];
+            if !ShillSettings::PLACEHOLDERS.contains(&placeholder) {
+                report.errors.push(format!("shill.prompt_template has unknown placeholder '{}'", placeholder));
+            }
+            rest = &after[end + 1..];
+        }
+    }
+
+    fn check_generation(character: &Character, report: &mut ValidationReport) {
+        let generation = &character.generation;
+        let tasks: [(&str, &GenerationParams); 6] = [
+            ("classify", &generation.classify),
+            ("classify_batch", &generation.classify_batch),
+            ("reply", &generation.reply),
+            ("post", &generation.post),
+            ("fud", &generation.fud),
+            ("custom", &generation.custom),
+        ];
+
+        for (task, params) in tasks {
+            if params.temperature.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+                report.errors.push(format!("generation.{}.temperature must be between 0 and 1", task));
+            }
+            if params.top_p.is_some_and(|p| p <= 0.0 || p > 1.0) {
+                report.errors.push(format!("generation.{}.top_p must be in (0, 1]", task));
+            }
+            if params.max_tokens == Some(0) {
+                report.errors.push(format!("generation.{}.max_tokens must be positive", task));
+            }
+        }
+    }
+
+    // Repeated lore, traits or examples make the model lean on the same catchphrase every post
+    fn check_duplicates(character: &Character, report: &mut ValidationReport) {
+        let lists = [
+            ("bio.key_traits", &character.bio.key_traits),
+            ("lore", &character.lore),
+            ("post_style_examples", &character.post_style_examples),
+            ("adjectives", &character.adjectives),
+        ];
+
+        let mut seen: HashMap<String, String> = HashMap::new();
+        for (name, entries) in lists {
+            for (i, entry) in entries.iter().enumerate() {
+                let normalized = Self::normalize(entry);
+                if normalized.is_empty() {
+                    report.warnings.push(format!("{}[{}] is empty", name, i));
+                    continue;
+                }
+                let field = format!("{}[{}]", name, i);
+                match seen.get(&normalized) {
+                    Some(first) => report.errors.push(format!("{} duplicates {}", field, first)),
+                    None => {
+                        seen.insert(normalized, field);
+                    }
+                }
+            }
+        }
+    }
+
+    fn normalize(text: &str) -> String {
+        text.to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
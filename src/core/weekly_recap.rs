@@ -0,0 +1,105 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use std::collections::HashSet;
+
+use super::character::WeeklyRecapSettings;
+use super::tweet_preview::TweetPreview;
+use crate::models::FuddedToken;
+
+// Guards against a restart in the posting minute sending the recap twice
+const MIN_DAYS_BETWEEN_RECAPS: i64 = 6;
+
+// How one FUDded token has done since we called it out
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecapEntry {
+    pub symbol: String,
+    pub mint: String,
+    pub fud_price: f64,
+    pub current_price: f64,
+    pub change_pct: f64,
+}
+
+impl RecapEntry {
+    pub fn new(fudded: &FuddedToken, current_price: f64) -> Self {
+        RecapEntry {
+            symbol: fudded.symbol.clone(),
+            mint: fudded.mint.clone(),
+            fud_price: fudded.price_usd,
+            current_price,
+            change_pct: (current_price / fudded.price_usd - 1.0) * 100.0,
+        }
+    }
+}
+
+pub struct WeeklyRecap {
+    // Biggest dumps first
+    pub entries: Vec<RecapEntry>,
+    pub tokens_fudded: usize,
+}
+
+impl WeeklyRecap {
+    // The weekday and hour are read in whatever timezone `now` is in
+    pub fn is_due<T: TimeZone>(settings: &WeeklyRecapSettings, last_recap: Option<DateTime<Utc>>, now: DateTime<T>) -> bool {
+        settings.enabled
+            && now.weekday() == settings.weekday
+            && now.hour() == settings.hour
+            && last_recap.is_none_or(|last| now.with_timezone(&Utc).signed_duration_since(last) >= Duration::days(MIN_DAYS_BETWEEN_RECAPS))
+    }
+
+    // First FUD of each token in the last week, so the change counts from when we first called it
+    pub fn fudded_this_week(history: &[FuddedToken], now: DateTime<Utc>) -> Vec<FuddedToken> {
+        let since = now - Duration::days(7);
+        let mut fudded: Vec<FuddedToken> = history
+            .iter()
+            .filter(|t| t.fudded_at > since && t.price_usd > 0.0)
+            .cloned()
+            .collect();
+        fudded.sort_by_key(|t| t.fudded_at);
+        let mut seen = HashSet::new();
+        fudded.retain(|t| seen.insert(t.mint.clone()));
+        fudded
+    }
+
+    // Only tokens that actually dumped make the leaderboard
+    pub fn rank(mut entries: Vec<RecapEntry>, tokens_fudded: usize, top: usize) -> Self {
+        entries.retain(|entry| entry.change_pct < 0.0);
+        entries.sort_by(|a, b| a.change_pct.partial_cmp(&b.change_pct).unwrap_or(std::cmp::Ordering::Equal));
+        entries.truncate(top);
+        WeeklyRecap { entries, tokens_fudded }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| format!("{}. ${} {:.1}% since i called it", i + 1, entry.symbol, entry.change_pct))
+            .collect()
+    }
+
+    // Numbers for the intro tweet's prompt
+    pub fn summary(&self) -> String {
+        format!(
+            "Tokens FUDded this week: {}\nDumped since: {}\nBiggest dumps:\n{}\n",
+            self.tokens_fudded,
+            self.entries.len(),
+            self.lines().join("\n")
+        )
+    }
+
+    // The intro, then the leaderboard packed into as few replies as fit
+    pub fn thread(&self, intro: &str) -> Vec<String> {
+        let mut tweets = vec![intro.to_string()];
+        let mut current = String::new();
+        for line in self.lines() {
+            let candidate = if current.is_empty() { line.clone() } else { format!("{}\n{}", current, line) };
+            if TweetPreview::new(&candidate).fits() || current.is_empty() {
+                current = candidate;
+            } else {
+                tweets.push(std::mem::replace(&mut current, line));
+            }
+        }
+        if !current.is_empty() {
+            tweets.push(current);
+        }
+        tweets
+    }
+}
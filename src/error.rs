@@ -0,0 +1,86 @@
+use std::error::Error as StdError;
+use std::io;
+use thiserror::Error;
+
+// Crate-wide error. Provider failures keep the provider's own error as the source,
+// and the cases callers act on (rate limits, unparseable payloads, missing things)
+// are variants to match on rather than message text to search.
+#[derive(Debug, Error)]
+pub enum FudError {
+    #[error("{provider} rate limited the request")]
+    RateLimited { provider: &'static str },
+    #[error("failed to parse {what}: {source}")]
+    ParseFailure {
+        what: String,
+        #[source]
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    #[error("{stage} timed out after {seconds}s")]
+    Timeout { stage: &'static str, seconds: u64 },
+    #[error("{0} not found")]
+    NotFound(String),
+    // The tweet being replied to was deleted, is protected or limits who can reply
+    #[error("tweet {tweet_id} can't be replied to: {reason}")]
+    Unrepliable { tweet_id: String, reason: String },
+    #[error("{provider} request failed: {source}")]
+    Provider {
+        provider: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+pub type Result<T, E = FudError> = std::result::Result<T, E>;
+
+impl FudError {
+    pub fn parse(what: impl Into<String>, source: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        FudError::ParseFailure {
+            what: what.into(),
+            source: source.into(),
+        }
+    }
+
+    pub fn provider(provider: &'static str, source: impl Into<anyhow::Error>) -> Self {
+        FudError::Provider {
+            provider,
+            source: source.into(),
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, FudError::RateLimited { .. })
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, FudError::Timeout { .. })
+    }
+
+    pub fn is_unrepliable(&self) -> bool {
+        matches!(self, FudError::Unrepliable { .. })
+    }
+
+    // A FudError anywhere in an anyhow chain, for errors that went through other layers
+    pub fn find(error: &anyhow::Error) -> Option<&FudError> {
+        error.chain().find_map(|cause| cause.downcast_ref::<FudError>())
+    }
+}
+
+// Unwraps a FudError that was passed along as anyhow instead of burying it in Other
+impl From<anyhow::Error> for FudError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<FudError>() {
+            Ok(error) => error,
+            Err(error) => FudError::Other(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for FudError {
+    fn from(error: serde_json::Error) -> Self {
+        FudError::parse("JSON", error)
+    }
+}
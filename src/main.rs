@@ -1,8 +1,12 @@
 mod characteristics;
+mod content;
 pub mod core;
+mod error;
+mod media;
 mod memory;
 mod providers;
-use core::{instruction_builder::InstructionBuilder, runtime::Runtime};
+use core::{aggression::{Aggression, AggressionCommand, MAX_LEVEL, MIN_LEVEL}, backtest::Backtest, campaign::{CampaignCommand, Campaigns}, character_bundle::{CharacterBundle, BUNDLE_EXTENSION}, character_switch::CharacterSwitch, config::Config, dataset::{DatasetBuilder, DEFAULT_DATASET_PATH, DEFAULT_MIN_ENGAGEMENT, DEFAULT_TOP_POSTS}, drafts::{DraftLog, DEFAULT_DRAFTS_DIR}, export::{ExportFormat, Exporter, DEFAULT_EXPORT_DIR}, feature_flags::{FeatureSwitch, Flag, FlagCommand}, instruction_builder::InstructionBuilder, outbox::{Outbox, DEFAULT_OUTBOX_DIR}, paths::{self, Layout}, runtime::Runtime, setup::Setup, stats::StatsReport, validator::CharacterValidator};
+use memory::MemoryStore;
 extern crate dotenv;
 pub mod models;
 pub mod character;
@@ -16,41 +20,234 @@ async fn main() -> Result<(), anyhow::Error> {
         eprintln!("Error loading .env file: {}", e);
     }
 
-    // Get debug mode from environment
-    let debug_mode = env::var("DEBUG_MODE")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse::<bool>()
-        .unwrap_or(false);
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("validate-character") {
+        let character_name = match args.get(2) {
+            Some(name) => name.clone(),
+            None => env::var("CHARACTER_NAME")
+                .map_err(|_| anyhow::anyhow!("Usage: validate-character <name> (or set CHARACTER_NAME)"))?,
+        };
+        let report = CharacterValidator::validate_directory(character_name.trim())?;
+        report.print(character_name.trim());
+        if !report.is_ok() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("doctor") {
+        let layout = Layout::from_env();
+        println!("Base directory: {}", layout.home().display());
+        let character_name = args.get(2).cloned().or_else(|| env::var("CHARACTER_NAME").ok());
+        let report = layout.doctor(character_name.as_deref().map(str::trim));
+        report.print("doctor");
+        if !report.is_ok() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("setup") {
+        // Defaults come from the .env loaded above, so running it again only changes what's retyped
+        let stdin = std::io::stdin();
+        Setup::new(stdin.lock(), std::io::stdout(), Layout::from_env()).run().await?;
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("character") {
+        let usage = "Usage: character use <name> | export [name] [file] | import <file> [name] [--force]";
+        if args.get(2).map(String::as_str) == Some("export") {
+            let name = match args.get(3) {
+                Some(name) => name.trim().to_string(),
+                None => env::var("CHARACTER_NAME").map_err(|_| anyhow::anyhow!(usage))?.trim().to_string(),
+            };
+            let bundle = CharacterBundle::export(&Layout::from_env(), &name, chrono::Utc::now())?;
+            let out = match args.get(4) {
+                Some(out) => std::path::PathBuf::from(out),
+                None => paths::resolve(DEFAULT_EXPORT_DIR).join(format!("{}.{}", name, BUNDLE_EXTENSION)),
+            };
+            if let Some(dir) = out.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&out, bundle.to_json()?)?;
+            println!("Exported {} to {}", name, out.display());
+            return Ok(());
+        }
+        if args.get(2).map(String::as_str) == Some("import") {
+            let file = args.get(3).ok_or_else(|| anyhow::anyhow!(usage))?;
+            let overwrite = args.iter().skip(4).any(|arg| arg == "--force");
+            let name = args.iter().skip(4).find(|arg| !arg.starts_with("--")).map(|name| name.trim());
+            let bundle = CharacterBundle::parse(&std::fs::read_to_string(file)?)?;
+            let skipped = bundle.operator_settings();
+            if !skipped.is_empty() {
+                println!("Leaving out settings that belong to whoever runs the bot: {}", skipped.join(", "));
+            }
+            let (path, report) = bundle.import(&Layout::from_env(), name, overwrite)?;
+            let name = name.unwrap_or(&bundle.name);
+            report.print(name);
+            if !report.is_ok() {
+                return Err(anyhow::anyhow!("Character '{}' failed validation and wasn't imported", name));
+            }
+            println!("Imported {} to {}; switch to it with `character use {}`", name, path.display(), name);
+            return Ok(());
+        }
+        // Picked up by the running bot on its next loop, within a minute
+        let name = match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("use"), Some(name)) => name.trim().to_string(),
+            _ => return Err(anyhow::anyhow!(usage)),
+        };
+        if !CharacterSwitch::is_valid_name(&name) {
+            return Err(anyhow::anyhow!("'{}' is not a valid character name", name));
+        }
+        let report = CharacterValidator::validate_directory(&name)?;
+        report.print(&name);
+        if !report.is_ok() {
+            return Err(anyhow::anyhow!("Character '{}' failed validation", name));
+        }
+        CharacterSwitch::write_request(&name)?;
+        println!("Asked the running bot to switch to {}", name);
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("aggression") {
+        // Picked up by the running bot on its next loop, within a minute, and kept until reset
+        let command = match args.get(2).map(String::as_str) {
+            Some(level) => Aggression::parse_command(&format!("aggression {}", level)),
+            None => None,
+        };
+        let Some(command) = command.filter(|command| *command != AggressionCommand::Current) else {
+            return Err(anyhow::anyhow!("Usage: aggression <{}-{}|reset>", MIN_LEVEL, MAX_LEVEL));
+        };
+        Aggression::write_request(&command)?;
+        match command {
+            AggressionCommand::Set(level) => println!("Asked the running bot to set aggression to {}", level),
+            _ => println!("Asked the running bot to go back to the character's aggression level"),
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("campaign") {
+        // Picked up by the running bot on its next loop, within a minute; it looks the token up itself
+        let command = Campaigns::parse_command(&args[1..].join(" "));
+        let Some(command) = command.filter(|command| *command != CampaignCommand::Status) else {
+            return Err(anyhow::anyhow!("Usage: campaign start <mint> [hours] | campaign stop"));
+        };
+        Campaigns::write_request(&command)?;
+        match command {
+            CampaignCommand::Start { mint, .. } => println!("Asked the running bot to start a campaign on {}", mint),
+            _ => println!("Asked the running bot to end the campaign and post its recap"),
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("flags") {
+        // Picked up by the running bot on its next loop, within a minute; with no arguments, prints what memory has
+        match FeatureSwitch::parse_command(&args[1..].join(" ")) {
+            Some(FlagCommand::List) => println!("{}", MemoryStore::load_memory()?.feature_flags.describe()),
+            Some(command @ FlagCommand::Set(flag, on)) => {
+                FeatureSwitch::write_request(&command)?;
+                println!("Asked the running bot to turn {} {}", flag.name(), if on { "on" } else { "off" });
+            }
+            None => {
+                let names = Flag::ALL.iter().map(Flag::name).collect::<Vec<_>>().join("|");
+                return Err(anyhow::anyhow!("Usage: flags [<{}> on|off]", names));
+            }
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("backtest") {
+        // Filters come from the character, so tuning them in character.json is what gets tested
+        let character_name = match args.get(2).filter(|arg| !arg.starts_with("--")) {
+            Some(name) => name.clone(),
+            None => env::var("CHARACTER_NAME").map_err(|_| anyhow::anyhow!("Usage: backtest <name> (or set CHARACTER_NAME)"))?,
+        };
+        let character = InstructionBuilder::load_character(character_name.trim())?;
+        let snapshots = MemoryStore::load_trending_snapshots()?;
+        let report = Backtest::run(&snapshots, &character.settings.fud_targets);
+        if args.iter().any(|arg| arg == "--json") {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            report.print();
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("stats") {
+        let memory = MemoryStore::load_memory()?;
+        let now = chrono::Utc::now();
+        let mut report = StatsReport::from_memory(&memory, now, 14);
+        // The schedule comes from the character; without one the stats are still shown
+        if let Ok(name) = env::var("CHARACTER_NAME") {
+            let character = InstructionBuilder::load_character(name.trim())?;
+            report = report.with_schedule(&character.settings, &memory, now);
+        }
+        if args.iter().any(|arg| arg == "--json") {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            report.print();
+        }
+        return Ok(());
+    }
+
+    if args.get(1).map(String::as_str) == Some("export") {
+        let flag = |name: &str| args.iter().position(|arg| arg == name).and_then(|i| args.get(i + 1)).cloned();
+        let format_name = flag("--format").unwrap_or_else(|| "markdown".to_string());
+        let format = ExportFormat::parse(&format_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown export format '{}' (use markdown, csv or jsonl)", format_name))?;
+        let dir = flag("--out").unwrap_or_else(|| paths::resolve(DEFAULT_EXPORT_DIR).display().to_string());
+        let memory = MemoryStore::load_memory()?;
+        let written = Exporter::write_all(&memory, format, std::path::Path::new(&dir))?;
+        println!("Exported {} day(s) of tweets to {}", written.len(), dir);
+        return Ok(());
+    }
+
+    if args.get(1).map(String::as_str) == Some("dataset") {
+        let flag = |name: &str| args.iter().position(|arg| arg == name).and_then(|i| args.get(i + 1)).cloned();
+        let top = match flag("--top") {
+            Some(top) => top.parse().map_err(|_| anyhow::anyhow!("--top must be a number, got '{}'", top))?,
+            None => DEFAULT_TOP_POSTS,
+        };
+        let min_engagement = match flag("--min-engagement") {
+            Some(min) => min.parse().map_err(|_| anyhow::anyhow!("--min-engagement must be a number, got '{}'", min))?,
+            None => DEFAULT_MIN_ENGAGEMENT,
+        };
+        let out = flag("--out").unwrap_or_else(|| paths::resolve(DEFAULT_DATASET_PATH).display().to_string());
+        let memory = MemoryStore::load_memory()?;
+        let written = DatasetBuilder::write(&memory, top, min_engagement, std::path::Path::new(&out))?;
+        println!("Wrote {} examples to {}", written, out);
+        return Ok(());
+    }
+
+    if args.get(1).map(String::as_str) == Some("drafts") {
+        // Sent by the running bot's outbox retries, once tweet_mode and the outbox are on
+        let id = match (args.get(2).map(String::as_str), args.get(3)) {
+            (Some("promote"), Some(id)) => id.trim().to_string(),
+            _ => return Err(anyhow::anyhow!("Usage: drafts promote <id>")),
+        };
+        let item = DraftLog::new(paths::resolve(DEFAULT_DRAFTS_DIR))
+            .promote(&id, &Outbox::new(paths::resolve(DEFAULT_OUTBOX_DIR)), chrono::Utc::now())?;
+        println!("Queued draft {} in the outbox as {}", id, item.id);
+        return Ok(());
+    }
+
+    // Report every missing or malformed variable at once instead of panicking on the first
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(errors) => {
+            eprintln!("{}", errors);
+            return Err(anyhow::anyhow!("Fix the environment (or .env) and restart"));
+        }
+    };
+    config.print_features();
+    if config.debug_mode {
+        println!("{:#?}", config);
+    }
 
     let character_config = CharacterConfig {
         name: "fud".to_string(),
-        debug_mode,
+        debug_mode: config.debug_mode,
     };
 
-    let mut runtime = Runtime::new(
-        &env::var("ANTHROPIC_API_KEY").expect("ANTHROPIC_API_KEY not set"),
-        &env::var("TWITTER_CONSUMER_KEY").expect("TWITTER_CONSUMER_KEY not set"),
-        &env::var("TWITTER_CONSUMER_SECRET").expect("TWITTER_CONSUMER_SECRET not set"),
-        &env::var("TWITTER_ACCESS_TOKEN").expect("TWITTER_ACCESS_TOKEN not set"),
-        &env::var("TWITTER_ACCESS_TOKEN_SECRET").expect("TWITTER_ACCESS_TOKEN_SECRET not set"),
-        &env::var("TELEGRAM_BOT_TOKEN").expect("TELEGRAM_BOT_TOKEN not set"),
-        &env::var("SOLANA_TRACKER_API_KEY").expect("SOLANA_TRACKER_API_KEY not set"),
-        character_config,
-    );
-
-    let mut instruction_builder = InstructionBuilder::new();
-    let character_name = env::var("CHARACTER_NAME")
-        .expect("CHARACTER_NAME not set")
-        .trim()
-        .to_string();
+    let mut runtime = Runtime::new(&config, character_config);
+    let character_name = config.character_name.clone();
 
     println!("Running character: {}", character_name);
 
-    if let Err(e) = instruction_builder.build_instructions(&character_name) {
-        eprintln!("Error building instructions: {}", e);
-        return Err(anyhow::anyhow!("Failed to build instructions"));
-    }
-    runtime.add_agent(instruction_builder.get_instructions());
+    // Refuse to start with a broken character rather than posting broken output for hours
+    runtime.use_character(&character_name)?;
 
     runtime.run_periodically().await?;
 
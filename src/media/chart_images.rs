@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use std::fs;
+use std::path::Path;
+
+use crate::error::FudError;
+use crate::models::ImageUsage;
+
+// A stock chart read from disk, with the file name its use is recorded under
+pub struct ChartImage {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+// The stock charts in storage/charts. Twitter flags the same image going out over
+// and over, so each use is remembered and recently used charts are left alone.
+pub struct ChartImages;
+
+impl ChartImages {
+    pub const DIR: &'static str = "./storage/charts";
+
+    // File names of the PNGs in the directory
+    pub fn list(dir: &Path) -> Result<Vec<String>, FudError> {
+        Self::list_with(dir, &["png"])
+    }
+
+    // File names in the directory with one of these extensions
+    pub fn list_with(dir: &Path, extensions: &[&str]) -> Result<Vec<String>, FudError> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let matches = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.iter().any(|wanted| extension.eq_ignore_ascii_case(wanted)));
+            if matches {
+                if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        if names.is_empty() {
+            let kinds = extensions.iter().map(|extension| extension.to_uppercase()).collect::<Vec<_>>().join("/");
+            return Err(FudError::NotFound(format!("{} files in {}", kinds, dir.display())));
+        }
+        Ok(names)
+    }
+
+    // Never-used charts first, then the least recently used, skipping any used in
+    // the last `reuse_after`. Ties are broken at random.
+    pub fn pick(
+        names: Vec<String>,
+        usage: &[ImageUsage],
+        reuse_after: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let last_used = |name: &str| usage.iter().find(|used| used.name == name).map(|used| used.last_used);
+        let mut names: Vec<String> = names
+            .into_iter()
+            .filter(|name| last_used(name).is_none_or(|at| now.signed_duration_since(at) >= reuse_after))
+            .collect();
+        names.shuffle(&mut rand::thread_rng());
+        names.into_iter().min_by_key(|name| last_used(name))
+    }
+
+    pub fn read(dir: &Path, name: &str) -> Result<ChartImage, FudError> {
+        Ok(ChartImage {
+            name: name.to_string(),
+            bytes: fs::read(dir.join(name))?,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use ab_glyph::PxScale;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use std::io::Cursor;
+
+use super::token_card::TokenCard;
+use crate::providers::solanatracker::{SolanaTracker, TokenResponse};
+
+const CARD_WIDTH: u32 = 1000;
+const CARD_HEIGHT: u32 = 620;
+const MARGIN: u32 = 30;
+const BANNER_HEIGHT: u32 = 120;
+const ROW_HEIGHT: u32 = 100;
+const LABEL_WIDTH: u32 = 280;
+const LINE: u32 = 4;
+
+const HAZARD_YELLOW: Rgba<u8> = Rgba([255, 204, 0, 255]);
+const HAZARD_BLACK: Rgba<u8> = Rgba([20, 20, 20, 255]);
+const ALERT_RED: Rgba<u8> = Rgba([200, 16, 16, 255]);
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+// Two tokens side by side: a header row of tickers, then mcap, liquidity and holders
+pub struct ComparisonCard;
+
+impl ComparisonCard {
+    // The table's cells, row by row: label, first token, second token
+    pub fn rows(first: &TokenResponse, second: &TokenResponse) -> Vec<[String; 3]> {
+        let (a, b) = (first.snapshot(), second.snapshot());
+        vec![
+            [String::new(), format!("${}", a.symbol.to_uppercase()), format!("${}", b.symbol.to_uppercase())],
+            ["MCAP".to_string(), SolanaTracker::format_currency(a.market_cap), SolanaTracker::format_currency(b.market_cap)],
+            ["LIQUIDITY".to_string(), SolanaTracker::format_currency(a.liquidity_usd), SolanaTracker::format_currency(b.liquidity_usd)],
+            ["HOLDERS".to_string(), Self::format_holders(first.holders), Self::format_holders(second.holders)],
+        ]
+    }
+
+    // "181.2K"; a question mark when the API didn't say
+    pub fn format_holders(holders: Option<u64>) -> String {
+        match holders {
+            Some(n) if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+            Some(n) if n >= 1_000 => format!("{:.1}K", n as f64 / 1_000.0),
+            Some(n) => n.to_string(),
+            None => "?".to_string(),
+        }
+    }
+
+    // PNG bytes. Like the token card, text is left off when the font is missing.
+    pub fn render(first: &TokenResponse, second: &TokenResponse) -> Result<Vec<u8>, anyhow::Error> {
+        let rows = Self::rows(first, second);
+        let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, HAZARD_BLACK);
+
+        let inner_width = CARD_WIDTH - 2 * MARGIN;
+        draw_filled_rect_mut(&mut card, Rect::at(MARGIN as i32, MARGIN as i32).of_size(inner_width, BANNER_HEIGHT), ALERT_RED);
+
+        // Grid lines: one under each row, and between the three columns
+        let table_top = MARGIN + BANNER_HEIGHT;
+        let column_width = (inner_width - LABEL_WIDTH) / 2;
+        for i in 0..=rows.len() as u32 {
+            let y = table_top + i * ROW_HEIGHT;
+            draw_filled_rect_mut(&mut card, Rect::at(MARGIN as i32, y as i32).of_size(inner_width, LINE), HAZARD_YELLOW);
+        }
+        let table_height = rows.len() as u32 * ROW_HEIGHT + LINE;
+        for x in [MARGIN, MARGIN + LABEL_WIDTH, MARGIN + LABEL_WIDTH + column_width, CARD_WIDTH - MARGIN - LINE] {
+            draw_filled_rect_mut(&mut card, Rect::at(x as i32, table_top as i32).of_size(LINE, table_height), HAZARD_YELLOW);
+        }
+
+        if let Some(font) = TokenCard::load_font() {
+            let banner = PxScale::from(80.0);
+            let (width, _) = text_size(banner, &font, "BOTH GARBAGE");
+            let x = CARD_WIDTH.saturating_sub(width) / 2;
+            draw_text_mut(&mut card, WHITE, x as i32, (MARGIN + 20) as i32, banner, &font, "BOTH GARBAGE");
+
+            let scale = PxScale::from(48.0);
+            let columns = [(MARGIN, LABEL_WIDTH), (MARGIN + LABEL_WIDTH, column_width), (MARGIN + LABEL_WIDTH + column_width, column_width)];
+            for (i, row) in rows.iter().enumerate() {
+                let y = table_top + i as u32 * ROW_HEIGHT + (ROW_HEIGHT - 48) / 2;
+                for ((left, width), text) in columns.iter().zip(row) {
+                    let (text_width, _) = text_size(scale, &font, text);
+                    let x = left + width.saturating_sub(text_width) / 2;
+                    let color = if i == 0 { HAZARD_YELLOW } else { WHITE };
+                    draw_text_mut(&mut card, color, x as i32, y as i32, scale, &font, text);
+                }
+            }
+        }
+
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(card).write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+        Ok(bytes)
+    }
+}
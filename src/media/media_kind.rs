@@ -0,0 +1,59 @@
+// What an attachment is, told from its first bytes. Twitter wants GIFs and videos
+// uploaded in chunks with a category, and each kind has its own size limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaKind {
+    Image,
+    Gif,
+    Video,
+}
+
+impl MediaKind {
+    // Anything that isn't a GIF or an MP4 is sent as an image, as before
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            MediaKind::Gif
+        } else if bytes.len() >= 8 && &bytes[4..8] == b"ftyp" {
+            MediaKind::Video
+        } else {
+            MediaKind::Image
+        }
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "tweet_image",
+            MediaKind::Gif => "tweet_gif",
+            MediaKind::Video => "tweet_video",
+        }
+    }
+
+    // Only used for GIFs and videos; images go up without a declared type
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "image/png",
+            MediaKind::Gif => "image/gif",
+            MediaKind::Video => "video/mp4",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "png",
+            MediaKind::Gif => "gif",
+            MediaKind::Video => "mp4",
+        }
+    }
+
+    // Twitter's upload limits
+    pub fn max_bytes(&self) -> usize {
+        match self {
+            MediaKind::Image => 5 * 1024 * 1024,
+            MediaKind::Gif => 15 * 1024 * 1024,
+            MediaKind::Video => 512 * 1024 * 1024,
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        *self == MediaKind::Image
+    }
+}
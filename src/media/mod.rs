@@ -0,0 +1,8 @@
+pub mod chart_images;
+pub mod comparison_card;
+pub mod media_kind;
+pub mod reaction_clips;
+pub mod token_card;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,17 @@
+use std::path::Path;
+
+use super::chart_images::ChartImages;
+use crate::error::FudError;
+
+// Short GIFs and MP4s in storage/videos posted along with FUD. They rotate like
+// the stock charts, with their own usage kept in memory.json.
+pub struct ReactionClips;
+
+impl ReactionClips {
+    pub const DIR: &'static str = "./storage/videos";
+    const EXTENSIONS: [&'static str; 2] = ["gif", "mp4"];
+
+    pub fn list(dir: &Path) -> Result<Vec<String>, FudError> {
+        ChartImages::list_with(dir, &Self::EXTENSIONS)
+    }
+}
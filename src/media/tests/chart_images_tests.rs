@@ -0,0 +1,75 @@
+// src/media/tests/chart_images_tests.rs
+
+use super::super::chart_images::ChartImages;
+use super::super::reaction_clips::ReactionClips;
+use crate::models::ImageUsage;
+use chrono::{Duration, TimeZone, Utc};
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn used(name: &str, days_ago: i64) -> ImageUsage {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    ImageUsage { name: name.to_string(), uses: 3, last_used: now - Duration::days(days_ago) }
+}
+
+#[test]
+fn test_unused_charts_come_first() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let usage = vec![used("a.png", 30), used("b.png", 20)];
+    let picked = ChartImages::pick(names(&["a.png", "b.png", "c.png"]), &usage, Duration::days(7), now);
+    assert_eq!(picked.as_deref(), Some("c.png"));
+}
+
+#[test]
+fn test_least_recently_used_wins_among_used_charts() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let usage = vec![used("a.png", 10), used("b.png", 40), used("c.png", 8)];
+    for _ in 0..10 {
+        let picked = ChartImages::pick(names(&["a.png", "b.png", "c.png"]), &usage, Duration::days(7), now);
+        assert_eq!(picked.as_deref(), Some("b.png"));
+    }
+}
+
+#[test]
+fn test_recently_used_charts_are_skipped() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let usage = vec![used("a.png", 1), used("b.png", 6)];
+    assert_eq!(ChartImages::pick(names(&["a.png", "b.png"]), &usage, Duration::days(7), now), None);
+    assert_eq!(
+        ChartImages::pick(names(&["a.png", "b.png"]), &usage, Duration::days(5), now).as_deref(),
+        Some("b.png")
+    );
+    assert_eq!(ChartImages::pick(Vec::new(), &usage, Duration::days(0), now), None);
+}
+
+#[test]
+fn test_list_only_picks_up_pngs() {
+    let dir = std::env::temp_dir().join(format!("chainfud-charts-{}", rand::random::<u64>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("pump.png"), b"png").unwrap();
+    std::fs::write(dir.join("notes.txt"), b"txt").unwrap();
+
+    assert_eq!(ChartImages::list(&dir).unwrap(), names(&["pump.png"]));
+    let chart = ChartImages::read(&dir, "pump.png").unwrap();
+    assert_eq!(chart.bytes, b"png");
+
+    std::fs::remove_file(dir.join("pump.png")).unwrap();
+    assert!(ChartImages::list(&dir).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_reaction_clips_are_gifs_and_mp4s() {
+    let dir = std::env::temp_dir().join(format!("chainfud-clips-{}", rand::random::<u64>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("pump.png"), b"png").unwrap();
+    std::fs::write(dir.join("laugh.GIF"), b"gif").unwrap();
+    std::fs::write(dir.join("rug.mp4"), b"mp4").unwrap();
+
+    let mut clips = ReactionClips::list(&dir).unwrap();
+    clips.sort();
+    assert_eq!(clips, names(&["laugh.GIF", "rug.mp4"]));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
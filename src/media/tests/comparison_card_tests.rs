@@ -0,0 +1,40 @@
+// src/media/tests/comparison_card_tests.rs
+
+use super::super::comparison_card::ComparisonCard;
+use crate::providers::solanatracker::TokenResponse;
+
+fn token(symbol: &str, market_cap: f64, liquidity: f64, holders: Option<u64>) -> TokenResponse {
+    serde_json::from_value(serde_json::json!({
+        "token": { "name": symbol, "symbol": symbol, "mint": format!("{}-mint", symbol) },
+        "pools": [{
+            "liquidity": { "quote": 0.0, "usd": liquidity },
+            "price": { "quote": 0.0, "usd": 0.001 },
+            "marketCap": { "quote": 0.0, "usd": market_cap }
+        }],
+        "holders": holders
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_rows_put_the_tokens_side_by_side() {
+    let rows = ComparisonCard::rows(&token("wif", 1_810_507_124.0, 14_533_209.0, Some(181_211)), &token("RODEO", 52_100.0, 9_800.0, None));
+    assert_eq!(rows[0], [String::new(), "$WIF".to_string(), "$RODEO".to_string()]);
+    assert_eq!(rows[1], ["MCAP".to_string(), "$1.8B".to_string(), "$52.1K".to_string()]);
+    assert_eq!(rows[2], ["LIQUIDITY".to_string(), "$14.5M".to_string(), "$9.8K".to_string()]);
+    assert_eq!(rows[3], ["HOLDERS".to_string(), "181.2K".to_string(), "?".to_string()]);
+}
+
+#[test]
+fn test_holder_counts() {
+    assert_eq!(ComparisonCard::format_holders(Some(950)), "950");
+    assert_eq!(ComparisonCard::format_holders(Some(2_400_000)), "2.4M");
+    assert_eq!(ComparisonCard::format_holders(None), "?");
+}
+
+#[test]
+fn test_render_makes_a_png() {
+    let png = ComparisonCard::render(&token("A", 1e6, 1e5, Some(10)), &token("B", 2e6, 2e5, Some(20))).unwrap();
+    let image = image::load_from_memory(&png).unwrap();
+    assert_eq!((image.width(), image.height()), (1000, 620));
+}
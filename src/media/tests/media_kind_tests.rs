@@ -0,0 +1,21 @@
+// src/media/tests/media_kind_tests.rs
+
+use super::super::media_kind::MediaKind;
+
+#[test]
+fn test_detect_from_leading_bytes() {
+    assert_eq!(MediaKind::detect(b"GIF89a\x01\x00\x01\x00"), MediaKind::Gif);
+    assert_eq!(MediaKind::detect(b"GIF87a"), MediaKind::Gif);
+    assert_eq!(MediaKind::detect(b"\x00\x00\x00\x20ftypisom\x00\x00\x02\x00"), MediaKind::Video);
+    assert_eq!(MediaKind::detect(b"\x89PNG\r\n\x1a\n"), MediaKind::Image);
+    assert_eq!(MediaKind::detect(b""), MediaKind::Image);
+}
+
+#[test]
+fn test_upload_categories_and_limits() {
+    assert_eq!(MediaKind::Image.category(), "tweet_image");
+    assert_eq!(MediaKind::Gif.category(), "tweet_gif");
+    assert_eq!(MediaKind::Video.mime_type(), "video/mp4");
+    assert!(MediaKind::Gif.max_bytes() > MediaKind::Image.max_bytes());
+    assert!(MediaKind::Image.is_image() && !MediaKind::Video.is_image());
+}
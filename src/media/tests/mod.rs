@@ -0,0 +1,3 @@
+mod chart_images_tests;
+mod comparison_card_tests;
+mod media_kind_tests;
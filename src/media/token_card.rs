@@ -0,0 +1,127 @@
+use ab_glyph::{FontVec, PxScale};
+use image::{imageops, DynamicImage, ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+use std::fs;
+use std::io::Cursor;
+
+use crate::core::paths;
+
+const CARD_WIDTH: u32 = 800;
+const CARD_HEIGHT: u32 = 900;
+const BORDER: u32 = 40;
+const BANNER_HEIGHT: u32 = 140;
+const FOOTER_HEIGHT: u32 = 100;
+const STRIPE_WIDTH: u32 = 30;
+
+const HAZARD_YELLOW: Rgba<u8> = Rgba([255, 204, 0, 255]);
+const HAZARD_BLACK: Rgba<u8> = Rgba([20, 20, 20, 255]);
+const ALERT_RED: Rgba<u8> = Rgba([200, 16, 16, 255]);
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+pub struct TokenCard;
+
+impl TokenCard {
+    // Optional font used for the banner text. Without it the card is still
+    // rendered, just without the "SCAM ALERT" / ticker captions.
+    pub const FONT_PATH: &'static str = "./storage/fonts/card.ttf";
+
+    // Compose a framed "scam alert" card around the token logo and return PNG bytes
+    pub fn render(logo_bytes: &[u8], symbol: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let logo = image::load_from_memory(logo_bytes)?;
+        let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, HAZARD_BLACK);
+
+        Self::draw_hazard_border(&mut card);
+
+        // Red banner at the top and footer at the bottom, inside the border
+        let inner_width = CARD_WIDTH - 2 * BORDER;
+        draw_filled_rect_mut(
+            &mut card,
+            Rect::at(BORDER as i32, BORDER as i32).of_size(inner_width, BANNER_HEIGHT),
+            ALERT_RED,
+        );
+        draw_filled_rect_mut(
+            &mut card,
+            Rect::at(BORDER as i32, (CARD_HEIGHT - BORDER - FOOTER_HEIGHT) as i32)
+                .of_size(inner_width, FOOTER_HEIGHT),
+            ALERT_RED,
+        );
+
+        // Logo goes in the middle, scaled to fit between banner and footer
+        let logo_area = CARD_HEIGHT - 2 * BORDER - BANNER_HEIGHT - FOOTER_HEIGHT - 40;
+        let logo_size = logo_area.min(inner_width - 40);
+        let logo = Self::condemn(logo.resize_to_fill(logo_size, logo_size, imageops::FilterType::Lanczos3));
+        let logo_x = (CARD_WIDTH - logo_size) / 2;
+        let logo_y = BORDER + BANNER_HEIGHT + 20;
+        imageops::overlay(&mut card, &logo, logo_x as i64, logo_y as i64);
+
+        if let Some(font) = Self::load_font() {
+            Self::draw_centered_text(&mut card, &font, "SCAM ALERT", 96.0, BORDER + 20);
+            Self::draw_centered_text(
+                &mut card,
+                &font,
+                &format!("${}", symbol.to_uppercase()),
+                72.0,
+                CARD_HEIGHT - BORDER - FOOTER_HEIGHT + 14,
+            );
+        }
+
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(card).write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?;
+        Ok(bytes)
+    }
+
+    pub(crate) fn load_font() -> Option<FontVec> {
+        let data = fs::read(paths::resolve(Self::FONT_PATH)).ok()?;
+        FontVec::try_from_vec(data).ok()
+    }
+
+    // Diagonal yellow/black stripes around the edge of the card
+    fn draw_hazard_border(card: &mut RgbaImage) {
+        for (x, y, pixel) in card.enumerate_pixels_mut() {
+            let in_border = x < BORDER
+                || y < BORDER
+                || x >= CARD_WIDTH - BORDER
+                || y >= CARD_HEIGHT - BORDER;
+            if in_border {
+                *pixel = if ((x + y) / STRIPE_WIDTH).is_multiple_of(2) {
+                    HAZARD_YELLOW
+                } else {
+                    HAZARD_BLACK
+                };
+            }
+        }
+    }
+
+    // Desaturate the logo, tint it red and stamp a thick X across it
+    fn condemn(logo: DynamicImage) -> RgbaImage {
+        let mut logo = logo.grayscale().to_rgba8();
+        for pixel in logo.pixels_mut() {
+            let [luma, _, _, alpha] = pixel.0;
+            pixel.0 = [luma.saturating_add(60), luma / 2, luma / 2, alpha];
+        }
+
+        let (width, height) = logo.dimensions();
+        let thickness = (width / 24).max(4) as i64;
+        for x in 0..width as i64 {
+            let y_down = x * height as i64 / width as i64;
+            let y_up = height as i64 - 1 - y_down;
+            for offset in -thickness..=thickness {
+                for y in [y_down + offset, y_up + offset] {
+                    if y >= 0 && y < height as i64 {
+                        logo.put_pixel(x as u32, y as u32, ALERT_RED);
+                    }
+                }
+            }
+        }
+
+        logo
+    }
+
+    fn draw_centered_text(card: &mut RgbaImage, font: &FontVec, text: &str, size: f32, y: u32) {
+        let scale = PxScale::from(size);
+        let (text_width, _) = text_size(scale, font, text);
+        let x = CARD_WIDTH.saturating_sub(text_width) / 2;
+        draw_text_mut(card, WHITE, x as i32, y as i32, scale, font, text);
+    }
+}
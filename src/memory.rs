@@ -1,20 +1,54 @@
-use std::fs;
+use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
 use std::path::Path;
-use crate::models::{Memory, Tweet, ProcessedNotifications, TweetType};
-use std::collections::HashSet;
+use std::collections::HashMap;
+use crate::core::paths;
+use crate::core::kv_store::{insert_timed, KvStore, LogStore};
+use crate::core::responded;
+use crate::core::token_extractor::{TokenCandidate, TokenExtractor};
+use crate::error::Result;
+use crate::core::consistency::{ConsistencyCheck, ConsistencyFinding};
+use crate::core::learned_facts::LearnedFacts;
+use crate::models::{Campaign, CharacterSwap, FeatureFlags, FuddedToken, HeldReply, ImageUsage, Memory, NewsReaction, Tweet, OnChainReceipt, Prediction, ProcessedNotifications, QueuedReply, SpaceComment, ThreadDepth, TokenFact, TrendEngagement, TrendingSnapshot, TweetMetrics, TweetType, UserCooldown, WatchedToken};
 use chrono::{DateTime, Utc};
 
 pub struct MemoryStore;
 
 impl MemoryStore {
     const FILE_PATH: &'static str = "./storage/memory.json";
+    const STORAGE_DIR: &'static str = "./storage";
+    // Held replies nobody approved by then are dropped; the conversation has moved on
+    const HELD_REPLY_HOURS: i64 = 24;
+    // Reacted headlines are forgotten after this; feeds have long dropped them by then
+    const NEWS_RETENTION_DAYS: i64 = 30;
+    // Spaces are found a day or two ahead at most, so a month of history is plenty
+    const SPACE_RETENTION_DAYS: i64 = 30;
+    // Processed notification IDs, each mapped to how far we got and when
+    const PROCESSED_STORE_FILE: &'static str = "processed_tweets.kv";
+    // Older formats, migrated into the store on first open: one ID per line, and
+    // before that the whole set as JSON
+    const LEGACY_PROCESSED_LOG_FILE: &'static str = "processed_tweets.log";
+    const LEGACY_PROCESSED_FILE: &'static str = "processed_tweets.json";
+    // Posted texts keyed by time, replayed into the style engine on startup
+    const PHRASE_HISTORY_FILE: &'static str = "phrase_history.kv";
+    // How far back memory.json is read when starting the phrase history
+    const PHRASE_SEED_DAYS: i64 = 7;
+    const PROCESSED_RETENTION_DAYS: i64 = 30;
+    // One trending snapshot per line
+    const SNAPSHOTS_PATH: &'static str = "./storage/trending_snapshots.jsonl";
+    // Twitter snowflake IDs count milliseconds from this epoch in their upper bits
+    const TWITTER_EPOCH_MS: i64 = 1_288_834_974_657;
+    // Conversations with no reply from us for this long start over at depth 0
+    const THREAD_RETENTION_DAYS: i64 = 7;
 
     // Load memory from file
-    pub fn load_memory() -> io::Result<Memory> {
-        if Path::new(Self::FILE_PATH).exists() {
-            let data = fs::read_to_string(Self::FILE_PATH)?;
-            let memory: Memory = serde_json::from_str(&data)?;
+    pub fn load_memory() -> Result<Memory> {
+        let path = paths::resolve(Self::FILE_PATH);
+        if path.exists() {
+            let data = fs::read_to_string(&path)?;
+            let mut memory: Memory = serde_json::from_str(&data)?;
+            Self::rebuild_reply_index(&mut memory);
+            Self::backfill_tweet_tokens(&mut memory);
             Ok(memory)
         } else {
             Ok(Memory::default())
@@ -22,15 +56,31 @@ impl MemoryStore {
     }
 
     // Add to memory for original tweets
-    pub fn add_to_memory(memory: &mut Memory, text: &str, prompt: &str, twitter_id: Option<String>) -> Result<(), String> {
+    pub fn add_to_memory(memory: &mut Memory, text: &str, prompt: &str, twitter_id: Option<String>, now: DateTime<Utc>) -> Result<()> {
+        Self::add_post_to_memory(memory, text, prompt, twitter_id, None, now)
+    }
+
+    // Original tweet along with the input it was generated from
+    pub fn add_post_to_memory(
+        memory: &mut Memory,
+        text: &str,
+        prompt: &str,
+        twitter_id: Option<String>,
+        context: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
         let tweet = Tweet {
             internal_id: memory.next_id,
             twitter_id,
             text: text.to_string(),
             prompt: prompt.to_string(),
-            timestamp: Utc::now(),
+            timestamp: now,
             tweet_type: TweetType::Original,
             reply_to: None,
+            metrics: None,
+            context,
+            token_mint: None,
+            token_symbol: None,
         };
         
         memory.tweets.push(tweet);
@@ -47,26 +97,447 @@ impl MemoryStore {
         prompt: &str,
         twitter_id: Option<String>,
         reply_to: String,
-    ) -> Result<(), String> {
+        now: DateTime<Utc>,
+    ) -> Result<()> {
         let tweet = Tweet {
             internal_id: memory.next_id,
             twitter_id,
             text: text.to_string(),
             prompt: prompt.to_string(),
-            timestamp: Utc::now(),
+            timestamp: now,
             tweet_type: TweetType::Reply,
-            reply_to: Some(reply_to),
+            reply_to: Some(reply_to.clone()),
+            metrics: None,
+            context: None,
+            token_mint: None,
+            token_symbol: None,
         };
         
         memory.tweets.push(tweet);
+        memory.replied_to.insert(reply_to);
         memory.next_id += 1;
         
         let _ = Self::save_memory(memory);
         Ok(())
     }
 
+    // Index the tweet IDs we've replied to so duplicate checks don't scan every tweet
+    pub fn rebuild_reply_index(memory: &mut Memory) {
+        memory.replied_to = memory.tweets
+            .iter()
+            .filter_map(|t| t.reply_to.clone())
+            .chain(memory.reply_queue.iter().map(|r| r.reply_to.clone()))
+            .collect();
+    }
+
+    // Queue a reply in the next free slot, `spacing` after the last one already queued.
+    // `token` is the (mint, symbol) the reply is about, if any.
+    pub fn enqueue_reply(
+        memory: &mut Memory,
+        reply_to: String,
+        text: &str,
+        prompt: &str,
+        token: Option<(String, String)>,
+        spacing: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>> {
+        let scheduled_at = memory.reply_queue
+            .iter()
+            .map(|r| r.scheduled_at + spacing)
+            .max()
+            .map_or(now, |slot| slot.max(now));
+
+        memory.replied_to.insert(reply_to.clone());
+        memory.reply_queue.push(QueuedReply {
+            reply_to,
+            text: text.to_string(),
+            prompt: prompt.to_string(),
+            scheduled_at,
+            attempts: 0,
+            token_mint: token.as_ref().map(|(mint, _)| mint.clone()),
+            token_symbol: token.map(|(_, symbol)| symbol),
+        });
+        Self::save_memory(memory)?;
+        Ok(scheduled_at)
+    }
+
+    // The earliest queued reply whose slot has come up
+    pub fn next_due_reply(memory: &Memory, now: DateTime<Utc>) -> Option<QueuedReply> {
+        memory.reply_queue
+            .iter()
+            .filter(|r| r.scheduled_at <= now)
+            .min_by_key(|r| r.scheduled_at)
+            .cloned()
+    }
+
+    // Takes a tweet back out of the reply index when its queued reply never went out,
+    // e.g. one given up on. Not saved on its own; remove_queued_reply saves it.
+    pub fn forget_reply(memory: &mut Memory, reply_to: &str) {
+        if !memory.tweets.iter().any(|t| t.reply_to.as_deref() == Some(reply_to)) {
+            memory.replied_to.remove(reply_to);
+        }
+    }
+
+    pub fn remove_queued_reply(memory: &mut Memory, reply_to: &str) -> Result<()> {
+        memory.reply_queue.retain(|r| r.reply_to != reply_to);
+        Self::save_memory(memory)
+    }
+
+    // Push every queued reply back, e.g. after hitting the rate limit
+    pub fn delay_reply_queue(memory: &mut Memory, delay: chrono::Duration) -> Result<()> {
+        for reply in memory.reply_queue.iter_mut() {
+            reply.scheduled_at += delay;
+        }
+        Self::save_memory(memory)
+    }
+
+    pub fn hold_reply(memory: &mut Memory, held: HeldReply) -> Result<()> {
+        let cutoff = held.held_at - chrono::Duration::hours(Self::HELD_REPLY_HOURS);
+        memory.held_replies.retain(|r| r.held_at > cutoff && r.reply_to != held.reply_to);
+        memory.held_replies.push(held);
+        Self::save_memory(memory)
+    }
+
+    // Remove a held reply, to queue it or drop it
+    pub fn take_held_reply(memory: &mut Memory, reply_to: &str) -> Result<Option<HeldReply>> {
+        let Some(i) = memory.held_replies.iter().position(|r| r.reply_to == reply_to) else {
+            return Ok(None);
+        };
+        let held = memory.held_replies.remove(i);
+        Self::save_memory(memory)?;
+        Ok(Some(held))
+    }
+
+    // Whether we've already replied to this tweet
+    pub fn has_replied_to(memory: &Memory, tweet_id: &str) -> bool {
+        memory.replied_to.contains(tweet_id)
+    }
+
+    // Fold model call timings collected by the agents into the persisted totals
+    pub fn record_generations(memory: &mut Memory, count: u64, total_ms: u64, errors: u64) -> Result<()> {
+        memory.usage.generations += count;
+        memory.usage.generation_ms += total_ms;
+        if errors > 0 {
+            *memory.usage.api_errors.entry("anthropic".to_string()).or_insert(0) += errors;
+        }
+        Self::save_memory(memory)
+    }
+
+    // Store freshly fetched metrics on the tweets they belong to
+    pub fn record_metrics(memory: &mut Memory, metrics: Vec<(String, TweetMetrics)>) -> Result<()> {
+        for (twitter_id, fetched) in metrics {
+            if let Some(tweet) = memory.tweets.iter_mut().find(|t| t.twitter_id.as_deref() == Some(twitter_id.as_str())) {
+                tweet.metrics = Some(fetched);
+            }
+        }
+        Self::save_memory(memory)
+    }
+
+    pub fn record_character_swap(memory: &mut Memory, swap: CharacterSwap) -> Result<()> {
+        memory.character_swaps.push(swap);
+        Self::save_memory(memory)
+    }
+
+    pub fn start_campaign(memory: &mut Memory, campaign: Campaign) -> Result<()> {
+        memory.campaigns.push(campaign);
+        Self::save_memory(memory)
+    }
+
+    // Count a post about the running campaign's token
+    pub fn record_campaign_post(memory: &mut Memory, mint: &str, now: DateTime<Utc>) -> Result<()> {
+        let Some(campaign) = memory.campaigns.iter_mut().rev().find(|c| c.is_running(now) && c.mint == mint) else {
+            return Ok(());
+        };
+        campaign.posts += 1;
+        Self::save_memory(memory)
+    }
+
+    // Count mentions of the running campaign's token; how many were new
+    pub fn record_campaign_mentions(memory: &mut Memory, tweet_ids: &[String], now: DateTime<Utc>) -> Result<usize> {
+        let Some(campaign) = memory.campaigns.iter_mut().rev().find(|c| c.is_running(now)) else {
+            return Ok(0);
+        };
+        let added = tweet_ids.iter().filter(|id| campaign.record_mention(id)).count();
+        if added > 0 {
+            Self::save_memory(memory)?;
+        }
+        Ok(added)
+    }
+
+    // Returns how many facts were new or backed by someone new
+    pub fn add_token_facts(memory: &mut Memory, facts: Vec<TokenFact>, max_per_token: usize) -> Result<usize> {
+        let added = LearnedFacts::add(&mut memory.token_facts, facts, max_per_token);
+        if added > 0 {
+            Self::save_memory(memory)?;
+        }
+        Ok(added)
+    }
+
+    pub fn set_feature_flags(memory: &mut Memory, flags: FeatureFlags) -> Result<()> {
+        memory.feature_flags = flags;
+        Self::save_memory(memory)
+    }
+
+    pub fn set_aggression_override(memory: &mut Memory, level: Option<u8>) -> Result<()> {
+        memory.aggression_override = level;
+        Self::save_memory(memory)
+    }
+
+    pub fn record_api_error(memory: &mut Memory, source: &str) -> Result<()> {
+        *memory.usage.api_errors.entry(source.to_string()).or_insert(0) += 1;
+        Self::save_memory(memory)
+    }
+
+    // Our most recent tweets mentioning $SYMBOL since the given time, newest first
+    pub fn recent_takes_about<'a>(memory: &'a Memory, symbol: &str, since: DateTime<Utc>, limit: usize) -> Vec<&'a Tweet> {
+        let cashtag = format!("${}", symbol.trim_start_matches('$').to_lowercase());
+        memory.tweets
+            .iter()
+            .rev()
+            .filter(|t| t.timestamp > since)
+            .filter(|t| {
+                t.text
+                    .split_whitespace()
+                    .any(|word| word.trim_end_matches(|c: char| !c.is_alphanumeric()).to_lowercase() == cashtag)
+            })
+            .take(limit)
+            .collect()
+    }
+
+    // Record a trend engagement reply, dropping entries older than the author cooldown
+    pub fn add_trend_engagement(
+        memory: &mut Memory,
+        engagement: TrendEngagement,
+        retention: chrono::Duration,
+    ) -> Result<()> {
+        let cutoff = Utc::now() - retention;
+        memory.trend_engagements.retain(|e| e.timestamp > cutoff);
+        memory.trend_engagements.push(engagement);
+        Self::save_memory(memory)
+    }
+
+    // Number of trend engagement replies made since the given time
+    pub fn count_trend_engagements_since(memory: &Memory, since: DateTime<Utc>) -> usize {
+        memory.trend_engagements.iter().filter(|e| e.timestamp > since).count()
+    }
+
+    // Whether we've engaged with this author since the given time
+    pub fn engaged_with_author_since(memory: &Memory, author_id: &str, since: DateTime<Utc>) -> bool {
+        memory.trend_engagements
+            .iter()
+            .any(|e| e.author_id == author_id && e.timestamp > since)
+    }
+
+    // Replies we've made in a conversation within the last THREAD_RETENTION_DAYS
+    pub fn thread_depth(memory: &Memory, conversation_id: &str) -> u32 {
+        memory.thread_depths
+            .iter()
+            .find(|thread| thread.conversation_id == conversation_id)
+            .map_or(0, |thread| thread.depth)
+    }
+
+    // Count one more reply in the conversation, forgetting threads that went quiet
+    pub fn record_thread_reply(memory: &mut Memory, conversation_id: &str, now: DateTime<Utc>) -> Result<()> {
+        let cutoff = now - chrono::Duration::days(Self::THREAD_RETENTION_DAYS);
+        memory.thread_depths.retain(|thread| thread.last_reply > cutoff);
+        match memory.thread_depths.iter_mut().find(|thread| thread.conversation_id == conversation_id) {
+            Some(thread) => {
+                thread.depth += 1;
+                thread.last_reply = now;
+            }
+            None => memory.thread_depths.push(ThreadDepth {
+                conversation_id: conversation_id.to_string(),
+                depth: 1,
+                last_reply: now,
+            }),
+        }
+        Self::save_memory(memory)
+    }
+
+    // When the author's reply cooldown ends, if they're on one
+    pub fn cooldown_until(memory: &Memory, author_id: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        memory.user_cooldowns
+            .iter()
+            .find(|cooldown| cooldown.author_id == author_id && cooldown.until > now)
+            .map(|cooldown| cooldown.until)
+    }
+
+    pub fn start_user_cooldown(memory: &mut Memory, author_id: &str, until: DateTime<Utc>) -> Result<()> {
+        let now = Utc::now();
+        memory.user_cooldowns.retain(|cooldown| cooldown.author_id != author_id && cooldown.until > now);
+        memory.user_cooldowns.push(UserCooldown {
+            author_id: author_id.to_string(),
+            until,
+        });
+        Self::save_memory(memory)
+    }
+
+    // Receipts are only needed for the daily caps, so a week is plenty
+    pub fn record_receipt(memory: &mut Memory, receipt: OnChainReceipt) -> Result<()> {
+        let cutoff = receipt.timestamp - chrono::Duration::days(7);
+        memory.onchain_receipts.retain(|r| r.timestamp > cutoff);
+        memory.onchain_receipts.push(receipt);
+        Self::save_memory(memory)
+    }
+
+    // Drops a receipt reserved before sending, once it's certain it never went out
+    pub fn release_receipt(memory: &mut Memory, signature: &str) -> Result<()> {
+        memory.onchain_receipts.retain(|r| r.signature != signature);
+        Self::save_memory(memory)
+    }
+
+    // Tags a saved tweet with the token it was about
+    pub fn record_tweet_token(memory: &mut Memory, twitter_id: &str, mint: &str, symbol: &str) -> Result<()> {
+        let Some(tweet) = memory.tweets.iter_mut().rev().find(|t| t.twitter_id.as_deref() == Some(twitter_id)) else {
+            return Ok(());
+        };
+        tweet.token_mint = Some(mint.to_string());
+        tweet.token_symbol = Some(symbol.to_string()).filter(|symbol| !symbol.is_empty());
+        Self::save_memory(memory)
+    }
+
+    // Fills in the token on tweets saved before it was recorded: from the FUD,
+    // whale watch and prediction records that name the tweet, from our own replies
+    // under a tagged tweet, and from the one contract address a text contains.
+    // Returns how many tweets were tagged; memory isn't saved.
+    pub fn backfill_tweet_tokens(memory: &mut Memory) -> usize {
+        let mut by_tweet: HashMap<String, (String, String)> = HashMap::new();
+        let mut symbols: HashMap<String, String> = HashMap::new();
+        let records = memory.fud_history.iter().map(|t| (&t.tweet_id, &t.mint, &t.symbol))
+            .chain(memory.watched_tokens.iter().map(|t| (&t.tweet_id, &t.mint, &t.symbol)))
+            .chain(memory.predictions.iter().map(|p| (&p.tweet_id, &p.mint, &p.symbol)));
+        for (tweet_id, mint, symbol) in records {
+            if mint.is_empty() {
+                continue;
+            }
+            symbols.insert(mint.clone(), symbol.clone());
+            if let Some(tweet_id) = tweet_id {
+                by_tweet.entry(tweet_id.clone()).or_insert_with(|| (mint.clone(), symbol.clone()));
+            }
+        }
+        for tweet in &memory.tweets {
+            if let (Some(mint), Some(symbol)) = (&tweet.token_mint, &tweet.token_symbol) {
+                symbols.insert(mint.clone(), symbol.clone());
+            }
+        }
+
+        let mut tagged = 0;
+        // Oldest first, so a reply can take the token its parent was just given
+        let mut tokens: HashMap<String, String> = HashMap::new();
+        for tweet in memory.tweets.iter_mut() {
+            if tweet.token_mint.is_none() {
+                let found = tweet.twitter_id.as_ref().and_then(|id| by_tweet.get(id)).map(|(mint, _)| mint.clone())
+                    .or_else(|| tweet.reply_to.as_ref().and_then(|id| tokens.get(id)).cloned())
+                    .or_else(|| {
+                        let addresses: Vec<String> = TokenExtractor::extract_all(&tweet.text)
+                            .into_iter()
+                            .filter_map(|candidate| match candidate {
+                                TokenCandidate::Address(address) => Some(address),
+                                TokenCandidate::Ticker(_) => None,
+                            })
+                            .collect();
+                        match addresses.as_slice() {
+                            [address] => Some(address.clone()),
+                            _ => None,
+                        }
+                    });
+                if let Some(mint) = found {
+                    tweet.token_symbol = symbols.get(&mint).cloned().filter(|symbol| !symbol.is_empty());
+                    tweet.token_mint = Some(mint);
+                    tagged += 1;
+                }
+            }
+            if let (Some(id), Some(mint)) = (&tweet.twitter_id, &tweet.token_mint) {
+                tokens.insert(id.clone(), mint.clone());
+            }
+        }
+        tagged
+    }
+
+    // Remember a FUDded token for the weekly recap; a month is plenty of history
+    // Marks the check done; with `avoid_until` the findings' topics are flagged until then
+    pub fn record_consistency_check(
+        memory: &mut Memory,
+        findings: &[ConsistencyFinding],
+        now: DateTime<Utc>,
+        avoid_until: Option<DateTime<Utc>>,
+    ) -> Result<usize> {
+        memory.last_consistency_check = Some(now);
+        let flagged = match avoid_until {
+            Some(until) => ConsistencyCheck::flag_topics(&mut memory.avoided_topics, findings, now, until),
+            None => 0,
+        };
+        Self::save_memory(memory)?;
+        Ok(flagged)
+    }
+
+    pub fn record_fud(memory: &mut Memory, token: FuddedToken) -> Result<()> {
+        let cutoff = token.fudded_at - chrono::Duration::days(30);
+        memory.fud_history.retain(|t| t.fudded_at > cutoff);
+        memory.fud_history.push(token);
+        Self::save_memory(memory)
+    }
+
+    pub fn has_reacted_to_news(memory: &Memory, url: &str, title: &str) -> bool {
+        memory
+            .news_reactions
+            .iter()
+            .any(|reaction| reaction.url == url || reaction.title.eq_ignore_ascii_case(title))
+    }
+
+    pub fn record_news_reaction(memory: &mut Memory, reaction: NewsReaction) -> Result<()> {
+        let cutoff = reaction.reacted_at - chrono::Duration::days(Self::NEWS_RETENTION_DAYS);
+        memory.news_reactions.retain(|r| r.reacted_at > cutoff);
+        memory.news_reactions.push(reaction);
+        Self::save_memory(memory)
+    }
+
+    pub fn has_commented_on_space(memory: &Memory, space_id: &str) -> bool {
+        memory.space_comments.iter().any(|comment| comment.space_id == space_id)
+    }
+
+    pub fn record_space_comment(memory: &mut Memory, comment: SpaceComment) -> Result<()> {
+        let cutoff = comment.commented_at - chrono::Duration::days(Self::SPACE_RETENTION_DAYS);
+        memory.space_comments.retain(|c| c.commented_at > cutoff);
+        memory.space_comments.push(comment);
+        Self::save_memory(memory)
+    }
+
+    pub fn record_image_use(memory: &mut Memory, name: &str, at: DateTime<Utc>) -> Result<()> {
+        Self::count_use(&mut memory.image_usage, name, at);
+        Self::save_memory(memory)
+    }
+
+    pub fn record_clip_use(memory: &mut Memory, name: &str, at: DateTime<Utc>) -> Result<()> {
+        Self::count_use(&mut memory.clip_usage, name, at);
+        Self::save_memory(memory)
+    }
+
+    fn count_use(usage: &mut Vec<ImageUsage>, name: &str, at: DateTime<Utc>) {
+        match usage.iter_mut().find(|used| used.name == name) {
+            Some(used) => {
+                used.uses += 1;
+                used.last_used = at;
+            }
+            None => usage.push(ImageUsage { name: name.to_string(), uses: 1, last_used: at }),
+        }
+    }
+
+    pub fn add_prediction(memory: &mut Memory, prediction: Prediction) -> Result<()> {
+        memory.predictions.push(prediction);
+        Self::save_memory(memory)
+    }
+
+    // Start watching a freshly FUDded token, replacing any older entry for the same mint
+    pub fn watch_token(memory: &mut Memory, token: WatchedToken, watch_for: chrono::Duration) -> Result<()> {
+        let cutoff = Utc::now() - watch_for;
+        memory.watched_tokens.retain(|t| t.mint != token.mint && t.fudded_at > cutoff);
+        memory.watched_tokens.push(token);
+        Self::save_memory(memory)
+    }
+
     // Update next tweet time
-    pub fn update_next_tweet_time(memory: &mut Memory, next_tweet: DateTime<Utc>) -> io::Result<()> {
+    pub fn update_next_tweet_time(memory: &mut Memory, next_tweet: DateTime<Utc>) -> Result<()> {
         memory.next_tweet = Some(next_tweet);
         Self::save_memory(memory)
     }
@@ -77,22 +548,133 @@ impl MemoryStore {
     }
 
     // Save memory to file
-    pub fn save_memory(memory: &Memory) -> io::Result<()> {
-        fs::create_dir_all("./storage")?;
+    pub fn save_memory(memory: &Memory) -> Result<()> {
+        fs::create_dir_all(paths::resolve(Self::STORAGE_DIR))?;
         let data = serde_json::to_string_pretty(memory)?;
-        let mut file = fs::File::create(Self::FILE_PATH)?;
+        let mut file = fs::File::create(paths::resolve(Self::FILE_PATH))?;
         file.write_all(data.as_bytes())?;
         Ok(())
     }
 
-    pub fn load_processed_tweets() -> Result<HashSet<String>, anyhow::Error> {
-        match fs::read_to_string("storage/processed_tweets.json") {
-            Ok(contents) => {
-                let data: ProcessedNotifications = serde_json::from_str(&contents)?;
-                Ok(data.tweet_ids)
+    // When a tweet was created, read from its snowflake ID
+    pub fn snowflake_timestamp(tweet_id: &str) -> Option<DateTime<Utc>> {
+        let id = tweet_id.parse::<u64>().ok()?;
+        let millis = (id >> 22) as i64 + Self::TWITTER_EPOCH_MS;
+        DateTime::from_timestamp_millis(millis)
+    }
+
+    // Processed notification IDs, migrating the older files into the store the first
+    // time and dropping IDs too old to show up again
+    pub fn open_processed_tweets() -> Result<LogStore> {
+        Self::open_processed_tweets_in(&paths::resolve(Self::STORAGE_DIR), Utc::now())
+    }
+
+    pub fn open_processed_tweets_in(dir: &Path, now: DateTime<Utc>) -> Result<LogStore> {
+        let mut store = LogStore::open(dir.join(Self::PROCESSED_STORE_FILE))?;
+
+        let mut legacy: Vec<String> = Vec::new();
+        let legacy_json = dir.join(Self::LEGACY_PROCESSED_FILE);
+        if let Ok(contents) = fs::read_to_string(&legacy_json) {
+            let data: ProcessedNotifications = serde_json::from_str(&contents)?;
+            legacy.extend(data.tweet_ids);
+        }
+        let legacy_log = dir.join(Self::LEGACY_PROCESSED_LOG_FILE);
+        if let Ok(contents) = fs::read_to_string(&legacy_log) {
+            legacy.extend(contents.lines().map(str::trim).filter(|id| !id.is_empty()).map(String::from));
+        }
+        if !legacy.is_empty() {
+            let processed_at = now.to_rfc3339();
+            for id in &legacy {
+                if !store.contains(id) {
+                    store.insert(id, &processed_at)?;
+                }
+            }
+            println!("Migrated {} processed notifications into {}", store.len(), Self::PROCESSED_STORE_FILE);
+        }
+        for path in [legacy_json, legacy_log] {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Self::prune_processed(&mut store, now)?;
+        Ok(store)
+    }
+
+    // Drops mentions past the retention from the responded index. IDs that aren't
+    // snowflakes age from when we processed them.
+    pub fn prune_processed(store: &mut dyn KvStore, now: DateTime<Utc>) -> Result<usize> {
+        let cutoff = Self::processed_cutoff(now);
+        let mut pruned = 0;
+        for (id, value) in store.entries() {
+            let created = Self::snowflake_timestamp(&id).or_else(|| responded::parse_entry(&value).1);
+            if created.is_some_and(|created| created <= cutoff) {
+                store.remove(&id)?;
+                pruned += 1;
             }
-            Err(_) => Ok(HashSet::new())
         }
+        if pruned > 0 {
+            println!("Pruned {} processed notifications older than {} days", pruned, Self::PROCESSED_RETENTION_DAYS);
+        }
+        Ok(pruned)
+    }
+
+    // Mentions older than this are dropped from the responded index
+    pub fn processed_cutoff(now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::days(Self::PROCESSED_RETENTION_DAYS)
+    }
+
+    // Texts we've posted, for the style engine to remember across restarts. Started
+    // from memory.json's recent tweets the first time.
+    pub fn open_phrase_history(memory: &Memory) -> Result<LogStore> {
+        Self::open_phrase_history_in(&paths::resolve(Self::STORAGE_DIR), memory, Utc::now())
+    }
+
+    pub fn open_phrase_history_in(dir: &Path, memory: &Memory, now: DateTime<Utc>) -> Result<LogStore> {
+        let path = dir.join(Self::PHRASE_HISTORY_FILE);
+        let fresh = !path.exists();
+        let mut store = LogStore::open(&path)?;
+        if fresh {
+            Self::seed_phrase_history(&mut store, memory, now)?;
+        }
+        Ok(store)
+    }
+
+    // Starts an empty phrase history from memory.json's recent tweets
+    pub fn seed_phrase_history(store: &mut dyn KvStore, memory: &Memory, now: DateTime<Utc>) -> Result<()> {
+        let since = now - chrono::Duration::days(Self::PHRASE_SEED_DAYS);
+        let recent: Vec<&Tweet> = memory.tweets.iter().filter(|t| t.timestamp > since).collect();
+        for tweet in &recent {
+            insert_timed(store, tweet.timestamp, &tweet.text)?;
+        }
+        if !recent.is_empty() {
+            println!("Seeded phrase history with {} recent tweets", recent.len());
+        }
+        Ok(())
+    }
+
+    pub fn append_trending_snapshot(snapshot: &TrendingSnapshot) -> Result<()> {
+        fs::create_dir_all(paths::resolve(Self::STORAGE_DIR))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(paths::resolve(Self::SNAPSHOTS_PATH))?;
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+        Ok(())
+    }
+
+    // Recorded snapshots in file order; unreadable lines are skipped
+    pub fn load_trending_snapshots() -> Result<Vec<TrendingSnapshot>> {
+        let contents = match fs::read_to_string(paths::resolve(Self::SNAPSHOTS_PATH)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
     }
 
     // Get Tweeting mode status
@@ -110,18 +692,8 @@ impl MemoryStore {
     }
 
     // Set debug mode status
-    pub fn set_debug_mode(memory: &mut Memory, debug: bool) -> io::Result<()> {
+    pub fn set_debug_mode(memory: &mut Memory, debug: bool) -> Result<()> {
         memory.debug_mode = debug;
         Self::save_memory(memory)
     }
-
-    pub fn save_processed_tweets(processed_tweets: &HashSet<String>) -> Result<(), anyhow::Error> {
-        let data = ProcessedNotifications {
-            tweet_ids: processed_tweets.clone(),
-        };
-        let json = serde_json::to_string_pretty(&data)?;
-        fs::create_dir_all("storage")?;
-        fs::write("storage/processed_tweets.json", json)?;
-        Ok(())
-    }
 }
\ No newline at end of file
@@ -1,14 +1,15 @@
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub enum TweetType {
+    #[default]
     Original,
     Reply
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Tweet {
     pub internal_id: u64,
     pub twitter_id: Option<String>,
@@ -17,6 +18,33 @@ pub struct Tweet {
     pub timestamp: DateTime<Utc>,
     pub tweet_type: TweetType,
     pub reply_to: Option<String>,
+    // Latest public metrics for posted tweets, refreshed while they're recent
+    #[serde(default)]
+    pub metrics: Option<TweetMetrics>,
+    // Token summary (or other input) the text was generated from, kept for dataset building
+    #[serde(default)]
+    pub context: Option<String>,
+    // Token the tweet is about, when it's about one, so per-token history and
+    // follow-ups don't have to parse the text
+    #[serde(default, alias = "mint")]
+    pub token_mint: Option<String>,
+    #[serde(default)]
+    pub token_symbol: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TweetMetrics {
+    pub likes: u64,
+    pub retweets: u64,
+    pub replies: u64,
+    pub quotes: u64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl TweetMetrics {
+    pub fn total(&self) -> u64 {
+        self.likes + self.retweets + self.replies + self.quotes
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -29,6 +57,351 @@ pub struct Memory {
     pub fud_only: bool,
     pub token_symbol: String,      // Your token's ticker
     pub token_address: String,  // Your tokens CA
+    #[serde(default)]
+    pub trend_engagements: Vec<TrendEngagement>,
+    #[serde(default)]
+    pub watched_tokens: Vec<WatchedToken>,
+    #[serde(default)]
+    pub reply_queue: Vec<QueuedReply>,
+    #[serde(default)]
+    pub usage: UsageStats,
+    #[serde(default)]
+    pub thread_depths: Vec<ThreadDepth>,
+    #[serde(default)]
+    pub user_cooldowns: Vec<UserCooldown>,
+    #[serde(default)]
+    pub onchain_receipts: Vec<OnChainReceipt>,
+    #[serde(default)]
+    pub predictions: Vec<Prediction>,
+    #[serde(default)]
+    pub fud_history: Vec<FuddedToken>,
+    #[serde(default)]
+    pub last_weekly_recap: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub api_quota: ApiQuotaLedger,
+    // Gap between scheduled posts picked by the adaptive schedule
+    #[serde(default)]
+    pub post_interval_minutes: Option<i64>,
+    #[serde(default)]
+    pub character_swaps: Vec<CharacterSwap>,
+    // Headlines the "news" content type already posted about
+    #[serde(default)]
+    pub news_reactions: Vec<NewsReaction>,
+    // Scheduled Spaces the "space" content type already commented on
+    #[serde(default)]
+    pub space_comments: Vec<SpaceComment>,
+    // Replies an escalate rule is holding until a Telegram admin approves them
+    #[serde(default)]
+    pub held_replies: Vec<HeldReply>,
+    // How often each stock chart has been posted, so the same one isn't attached every time
+    #[serde(default)]
+    pub image_usage: Vec<ImageUsage>,
+    // Same for the reaction clips in storage/videos
+    #[serde(default)]
+    pub clip_usage: Vec<ImageUsage>,
+    #[serde(default)]
+    pub last_consistency_check: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub avoided_topics: Vec<AvoidedTopic>,
+    // Aggression level the operator dialed in, kept over the character's until reset
+    #[serde(default)]
+    pub aggression_override: Option<u8>,
+    // Every FUD campaign, the running one last
+    #[serde(default)]
+    pub campaigns: Vec<Campaign>,
+    // Claims people made about tokens in mentions, oldest first
+    #[serde(default)]
+    pub token_facts: Vec<TokenFact>,
+    // Features the operator switched off without a restart
+    #[serde(default)]
+    pub feature_flags: FeatureFlags,
+    // Tweet IDs we've already replied to (or queued a reply for), rebuilt on load
+    #[serde(skip)]
+    pub replied_to: HashSet<String>,
+}
+
+// Trending list as seen at one point in time, replayed by the backtest command
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrendingSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub tokens: Vec<TokenSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TokenSnapshot {
+    pub mint: String,
+    pub symbol: String,
+    pub price_usd: f64,
+    pub market_cap: f64,
+    pub liquidity_usd: f64,
+    #[serde(default)]
+    pub change_1h: Option<f64>,
+    #[serde(default)]
+    pub change_24h: Option<f64>,
+    // Pool transaction counts, 0 when the API didn't report them
+    #[serde(default)]
+    pub buys: u64,
+    #[serde(default)]
+    pub sells: u64,
+    // Hours since the token's first pool was created, when known
+    #[serde(default)]
+    pub age_hours: Option<f64>,
+}
+
+// Running totals for the stats command
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct UsageStats {
+    pub generations: u64,
+    pub generation_ms: u64,
+    // Failures keyed by where they happened ("anthropic", "notifications", ...)
+    pub api_errors: BTreeMap<String, u64>,
+}
+
+// A topic the consistency check found the character contradicting itself on,
+// kept out of generated posts until `until`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AvoidedTopic {
+    pub topic: String,
+    pub reason: String,
+    pub flagged_at: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+}
+
+// A runtime switch of the active character, kept for auditing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CharacterSwap {
+    pub from: String,
+    pub to: String,
+    // "cli" or "telegram:<user id>"
+    pub source: String,
+    pub at: DateTime<Utc>,
+}
+
+// Twitter API calls made in the current quota window, by endpoint
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ApiQuotaLedger {
+    pub window_start: Option<DateTime<Utc>>,
+    pub window_end: Option<DateTime<Utc>>,
+    pub calls: BTreeMap<String, u64>,
+    // Limits in effect when the ledger was last updated, so stats can show them
+    #[serde(default)]
+    pub limits: BTreeMap<String, u64>,
+}
+
+// A generated reply waiting for its slot in the rate window
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QueuedReply {
+    pub reply_to: String,
+    pub text: String,
+    pub prompt: String,
+    pub scheduled_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attempts: u32,
+    // Token the reply is about, copied to the tweet once it's sent
+    #[serde(default)]
+    pub token_mint: Option<String>,
+    #[serde(default)]
+    pub token_symbol: Option<String>,
+}
+
+// A generated reply waiting on an admin's /approve before it joins the reply queue
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HeldReply {
+    pub reply_to: String,
+    pub mention: String,
+    pub text: String,
+    pub prompt: String,
+    // Label of the reply rule that held it
+    pub rule: String,
+    pub held_at: DateTime<Utc>,
+    #[serde(default)]
+    pub token_mint: Option<String>,
+    #[serde(default)]
+    pub token_symbol: Option<String>,
+}
+
+// A token we posted FUD about, watched for whale sells we can gloat over
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WatchedToken {
+    pub mint: String,
+    pub symbol: String,
+    pub tweet_id: Option<String>,
+    pub fudded_at: DateTime<Utc>,
+    pub alerts_sent: usize,
+    pub last_trade_time: i64,
+}
+
+// A reply the bot made to someone else's tweet about a trending token
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrendEngagement {
+    pub tweet_id: String,
+    pub author_id: String,
+    pub token_symbol: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+// Replies the bot has made in one conversation, for loop protection
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ThreadDepth {
+    pub conversation_id: String,
+    pub depth: u32,
+    pub last_reply: DateTime<Utc>,
+}
+
+// An author the bot won't reply to before `until`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserCooldown {
+    pub author_id: String,
+    pub until: DateTime<Utc>,
+}
+
+// A transaction sent as proof the bot looked at a token, counted against the spend caps
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OnChainReceipt {
+    pub timestamp: DateTime<Utc>,
+    pub mint: String,
+    pub signature: String,
+    // Upper bound on what it cost, fees and rent included
+    pub lamports: u64,
+}
+
+// A news headline we posted a reaction to, kept so it isn't reacted to twice
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NewsReaction {
+    pub url: String,
+    pub title: String,
+    pub reacted_at: DateTime<Utc>,
+}
+
+// Posts a stock chart (or reaction clip) went out with, by file name
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ImageUsage {
+    pub name: String,
+    pub uses: u32,
+    pub last_used: DateTime<Utc>,
+}
+
+// A scheduled Space we posted about before it started
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SpaceComment {
+    pub space_id: String,
+    pub title: String,
+    pub commented_at: DateTime<Utc>,
+}
+
+// A token scheduled FUD went after, with its price at the time, for the weekly recap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FuddedToken {
+    pub mint: String,
+    pub symbol: String,
+    pub tweet_id: Option<String>,
+    pub fudded_at: DateTime<Utc>,
+    pub price_usd: f64,
+    pub market_cap: f64,
+}
+
+// A falsifiable call the bot posted: the token trades under a market cap before the deadline
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Prediction {
+    pub mint: String,
+    pub symbol: String,
+    pub tweet_id: Option<String>,
+    pub made_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    pub start_market_cap: f64,
+    pub target_market_cap: f64,
+    // Lowest market cap seen while the call was open
+    pub lowest_market_cap: f64,
+    #[serde(default)]
+    pub outcome: Option<PredictionOutcome>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PredictionOutcome {
+    pub hit: bool,
+    pub graded_at: DateTime<Utc>,
+    pub final_market_cap: f64,
+}
+
+// Something a user told the bot about a token, as the model extracted it from their mention
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TokenFact {
+    pub mint: String,
+    pub symbol: String,
+    pub claim: String,
+    pub source_tweet_id: String,
+    pub learned_at: DateTime<Utc>,
+    // Twitter IDs of everyone who has made the claim; one alone isn't enough for the prompt
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+// Runtime switches, flipped from the CLI, Telegram or the status endpoint. Everything
+// is on until the operator turns it off.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct FeatureFlags {
+    // Token cards, charts and screenshots attached to posts
+    pub image_posts: bool,
+    // Replies to mentions from the notification sweep
+    pub notification_replies: bool,
+    // Multi-tweet posts such as the weekly recap; off posts only the first tweet
+    pub threads: bool,
+    // Heurist image generation for image memes
+    pub image_gen: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        FeatureFlags {
+            image_posts: true,
+            notification_replies: true,
+            threads: true,
+            image_gen: true,
+        }
+    }
+}
+
+// A stretch of concentrated FUD on one token, started from the CLI or Telegram and
+// closed with a recap post
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Campaign {
+    pub mint: String,
+    pub symbol: String,
+    // Who started it: "cli" or "telegram:<user id>"
+    pub source: String,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub start_price: f64,
+    pub start_market_cap: f64,
+    // Price range seen at the periodic checks
+    pub low_price: f64,
+    pub high_price: f64,
+    pub last_price: f64,
+    pub last_market_cap: f64,
+    #[serde(default)]
+    pub last_checked_at: Option<DateTime<Utc>>,
+    // Posts we made about the token while it ran
+    #[serde(default)]
+    pub posts: u32,
+    // Mentions of the token we got while it ran, by tweet ID
+    #[serde(default)]
+    pub mentions: Vec<String>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub recap_tweet_id: Option<String>,
+}
+
+// Author details looked up for a mention, used to filter spam and apply the
+// reply policy before replying
+#[derive(Clone, Debug, PartialEq)]
+pub struct MentionAuthor {
+    pub id: u64,
+    pub username: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub followers: Option<u64>,
+    pub verified: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Default)]
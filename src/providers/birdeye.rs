@@ -0,0 +1,284 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::time::Duration;
+
+const BIRDEYE_API_URL: &str = "https://public-api.birdeye.so";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+// Above this the top 10 wallets can dump the chart on their own
+const CONCENTRATED_TOP10_PCT: f64 = 50.0;
+
+#[derive(Debug, Deserialize)]
+struct Envelope<T> {
+    #[serde(default)]
+    success: bool,
+    data: Option<T>,
+}
+
+// Birdeye's token_security response for a Solana token; shares are fractions of supply
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TokenSecurity {
+    pub creator_address: Option<String>,
+    pub creator_percentage: Option<f64>,
+    pub top10_holder_percent: Option<f64>,
+    pub freezeable: Option<bool>,
+    pub freeze_authority: Option<String>,
+    pub mutable_metadata: Option<bool>,
+    pub transfer_fee_enable: Option<bool>,
+    pub non_transferable: Option<bool>,
+}
+
+impl TokenSecurity {
+    pub fn top10_pct(&self) -> Option<f64> {
+        self.top10_holder_percent.map(|share| share * 100.0)
+    }
+
+    pub fn creator_pct(&self) -> Option<f64> {
+        self.creator_percentage.map(|share| share * 100.0)
+    }
+
+    pub fn can_freeze(&self) -> bool {
+        self.freezeable == Some(true) || self.freeze_authority.as_deref().is_some_and(|authority| !authority.is_empty())
+    }
+
+    // Lines appended to the token summary; the flags are what FUD should lead with
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(pct) = self.top10_pct() {
+            let note = if pct >= CONCENTRATED_TOP10_PCT { " (concentrated)" } else { "" };
+            lines.push(format!("Birdeye: top 10 holders own {:.1}% of supply{}", pct, note));
+        }
+        match self.creator_pct() {
+            Some(pct) if pct > 0.0 => lines.push(format!("Creator wallet still holds {:.2}% of supply", pct)),
+            Some(_) => lines.push("Creator wallet holds none of the supply".to_string()),
+            None => {}
+        }
+        if self.can_freeze() {
+            lines.push("Security flag: freeze authority is still enabled".to_string());
+        }
+        if self.mutable_metadata == Some(true) {
+            lines.push("Security flag: metadata is mutable".to_string());
+        }
+        if self.transfer_fee_enable == Some(true) {
+            lines.push("Security flag: transfer fee enabled".to_string());
+        }
+        if self.non_transferable == Some(true) {
+            lines.push("Security flag: token is non-transferable".to_string());
+        }
+        if lines.is_empty() {
+            return String::new();
+        }
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub block_unix_time: i64,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct TradeList {
+    #[serde(default)]
+    items: Vec<Trade>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Market {
+    pub address: String,
+    // The DEX running the pool, such as Raydium or Meteora
+    pub source: String,
+    pub liquidity: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MarketList {
+    #[serde(default)]
+    items: Vec<Market>,
+}
+
+// Where the token's liquidity sits, by DEX, largest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquiditySplit {
+    pub total_usd: f64,
+    pub pools: usize,
+    pub providers: Vec<(String, f64)>,
+}
+
+impl LiquiditySplit {
+    pub fn from_markets(markets: &[Market]) -> Option<Self> {
+        let mut providers: Vec<(String, f64)> = Vec::new();
+        let mut pools = 0;
+        for market in markets {
+            let Some(liquidity) = market.liquidity.filter(|liquidity| *liquidity > 0.0) else {
+                continue;
+            };
+            pools += 1;
+            let source = if market.source.is_empty() { "unknown" } else { market.source.as_str() };
+            match providers.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(source)) {
+                Some((_, usd)) => *usd += liquidity,
+                None => providers.push((source.to_string(), liquidity)),
+            }
+        }
+        if providers.is_empty() {
+            return None;
+        }
+        providers.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        Some(LiquiditySplit { total_usd: providers.iter().map(|(_, usd)| usd).sum(), pools, providers })
+    }
+
+    pub fn summary(&self) -> String {
+        let shares: Vec<String> = self
+            .providers
+            .iter()
+            .map(|(name, usd)| format!("{} {:.0}%", name, usd / self.total_usd * 100.0))
+            .collect();
+        format!("Liquidity: ${:.0} across {} pools ({})\n", self.total_usd, self.pools, shares.join(", "))
+    }
+}
+
+// Who's been buying and selling in the latest swaps
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeFlow {
+    pub buys: usize,
+    pub sells: usize,
+    pub wallets: usize,
+    pub span_minutes: i64,
+}
+
+impl TradeFlow {
+    pub fn from_trades(trades: &[Trade]) -> Option<Self> {
+        // Anything that isn't plainly a buy or a sell is left out rather than guessed at
+        let is_side = |trade: &&Trade, side: &str| trade.side.eq_ignore_ascii_case(side);
+        let trades: Vec<&Trade> = trades.iter().filter(|trade| is_side(trade, "buy") || is_side(trade, "sell")).collect();
+        if trades.is_empty() {
+            return None;
+        }
+        let buys = trades.iter().filter(|trade| is_side(trade, "buy")).count();
+        let sells = trades.len() - buys;
+        let wallets: HashSet<&str> = trades.iter().map(|trade| trade.owner.as_str()).filter(|owner| !owner.is_empty()).collect();
+        let newest = trades.iter().map(|trade| trade.block_unix_time).max().unwrap_or(0);
+        let oldest = trades.iter().map(|trade| trade.block_unix_time).min().unwrap_or(0);
+        Some(TradeFlow {
+            buys,
+            sells,
+            wallets: wallets.len(),
+            span_minutes: (newest - oldest) / 60,
+        })
+    }
+
+    pub fn summary(&self) -> String {
+        let lean = if self.sells > self.buys {
+            ", sellers in control"
+        } else if self.buys > self.sells {
+            ", buyers in control"
+        } else {
+            ""
+        };
+        format!(
+            "Last {} trades over {} minutes: {} buys, {} sells from {} wallets{}\n",
+            self.buys + self.sells,
+            self.span_minutes,
+            self.buys,
+            self.sells,
+            self.wallets,
+            lean
+        )
+    }
+}
+
+pub fn parse_security(body: &str) -> Result<TokenSecurity> {
+    let envelope: Envelope<TokenSecurity> =
+        serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse Birdeye token security: {}", e))?;
+    match envelope.data {
+        Some(security) if envelope.success => Ok(security),
+        _ => Err(anyhow::anyhow!("Birdeye returned no security data")),
+    }
+}
+
+pub fn parse_trades(body: &str) -> Result<Vec<Trade>> {
+    let envelope: Envelope<TradeList> =
+        serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse Birdeye trades: {}", e))?;
+    if !envelope.success {
+        return Err(anyhow::anyhow!("Birdeye returned no trades"));
+    }
+    Ok(envelope.data.unwrap_or_default().items)
+}
+
+pub fn parse_markets(body: &str) -> Result<Vec<Market>> {
+    let envelope: Envelope<MarketList> =
+        serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse Birdeye markets: {}", e))?;
+    if !envelope.success {
+        return Err(anyhow::anyhow!("Birdeye returned no markets"));
+    }
+    Ok(envelope.data.unwrap_or_default().items)
+}
+
+pub struct Birdeye {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl Birdeye {
+    pub fn new(api_key: &str) -> Self {
+        Birdeye {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<String> {
+        let response = self
+            .client
+            .get(format!("{}{}", BIRDEYE_API_URL, path))
+            .header("X-API-KEY", &self.api_key)
+            .header("x-chain", "solana")
+            .query(query)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Birdeye request failed with status: {}", status));
+        }
+        Ok(response.text().await?)
+    }
+
+    pub async fn get_token_security(&self, mint: &str) -> Result<TokenSecurity> {
+        parse_security(&self.get("/defi/token_security", &[("address", mint.to_string())]).await?)
+    }
+
+    // Newest swaps first
+    pub async fn get_recent_trades(&self, mint: &str, limit: usize) -> Result<Vec<Trade>> {
+        let query = [
+            ("address", mint.to_string()),
+            ("offset", "0".to_string()),
+            ("limit", limit.to_string()),
+            ("tx_type", "swap".to_string()),
+        ];
+        parse_trades(&self.get("/defi/txs/token", &query).await?)
+    }
+
+    // The token's pools, deepest first
+    pub async fn get_markets(&self, mint: &str, limit: usize) -> Result<Vec<Market>> {
+        let query = [
+            ("address", mint.to_string()),
+            ("time_frame", "24h".to_string()),
+            ("sort_type", "desc".to_string()),
+            ("sort_by", "liquidity".to_string()),
+            ("offset", "0".to_string()),
+            ("limit", limit.to_string()),
+        ];
+        parse_markets(&self.get("/defi/v2/markets", &query).await?)
+    }
+}
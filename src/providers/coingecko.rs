@@ -0,0 +1,96 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::time::Duration;
+
+const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Deserialize)]
+struct CoinResponse {
+    #[serde(default)]
+    tickers: Vec<Ticker>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ticker {
+    market: Market,
+}
+
+#[derive(Debug, Deserialize)]
+struct Market {
+    #[serde(default)]
+    identifier: String,
+}
+
+// Exchanges CoinGecko has a market for the token on, by CoinGecko's exchange id
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Listing {
+    pub exchanges: Vec<String>,
+}
+
+impl Listing {
+    // The listed exchanges that are on the given list of majors
+    pub fn major_exchanges(&self, majors: &[String]) -> Vec<String> {
+        self.exchanges
+            .iter()
+            .filter(|exchange| majors.iter().any(|major| major.eq_ignore_ascii_case(exchange)))
+            .cloned()
+            .collect()
+    }
+
+    pub fn parse(body: &str) -> Result<Self> {
+        let coin: CoinResponse =
+            serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse CoinGecko coin: {}", e))?;
+        let mut exchanges: Vec<String> = Vec::new();
+        for ticker in coin.tickers {
+            if !ticker.market.identifier.is_empty() && !exchanges.contains(&ticker.market.identifier) {
+                exchanges.push(ticker.market.identifier);
+            }
+        }
+        Ok(Listing { exchanges })
+    }
+}
+
+// Which exchanges list a token, so the bot doesn't punch up at majors. Works
+// without a key on the public rate limit; a demo key raises it.
+pub struct CoinGecko {
+    client: reqwest::Client,
+    api_key: Option<String>,
+}
+
+impl CoinGecko {
+    pub fn new(api_key: Option<&str>) -> Self {
+        CoinGecko {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            api_key: api_key.map(str::to_string),
+        }
+    }
+
+    // None when CoinGecko doesn't know the token at all, which fresh launches never are
+    pub async fn get_listing(&self, mint: &str) -> Result<Option<Listing>> {
+        let url = format!("{}/coins/solana/contract/{}", COINGECKO_API_URL, mint);
+        let mut request = self.client.get(&url).query(&[
+            ("localization", "false"),
+            ("tickers", "true"),
+            ("market_data", "false"),
+            ("community_data", "false"),
+            ("developer_data", "false"),
+            ("sparkline", "false"),
+        ]);
+        if let Some(api_key) = &self.api_key {
+            request = request.header("x-cg-demo-api-key", api_key);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("CoinGecko request failed with status: {}", status));
+        }
+        Listing::parse(&response.text().await?).map(Some)
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Result};
+use reqwest::multipart::{Form, Part};
+use serde_json::{json, Value};
+
+use super::solanatracker::SolanaTracker;
+use super::twitter::Twitter;
+use crate::media::media_kind::MediaKind;
+use crate::models::TokenSnapshot;
+
+const EMBED_COLOR: u32 = 0xE7_4C_3C;
+
+// Posts FUD as embeds to plain Discord webhooks, no bot account required
+pub struct Discord {
+    client: reqwest::Client,
+    webhook_urls: Vec<String>,
+}
+
+impl Discord {
+    pub fn new(webhook_urls: Vec<String>) -> Self {
+        Discord {
+            client: reqwest::Client::new(),
+            webhook_urls,
+        }
+    }
+
+    // Comma or whitespace separated list, as it comes out of the environment
+    pub fn parse_webhook_urls(list: &str) -> Vec<String> {
+        list.split(|c: char| c == ',' || c.is_whitespace())
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.webhook_urls.is_empty()
+    }
+
+    pub fn fud_embed(token: &TokenSnapshot, text: &str, tweet_id: Option<&str>, image_name: Option<&str>) -> Value {
+        let change = match token.change_24h {
+            Some(change) => format!("{:+.1}%", change),
+            None => "n/a".to_string(),
+        };
+        let mut embed = json!({
+            "title": format!("${}", token.symbol),
+            "description": text,
+            "color": EMBED_COLOR,
+            "fields": [
+                { "name": "Market Cap", "value": SolanaTracker::format_currency(token.market_cap), "inline": true },
+                { "name": "Liquidity", "value": SolanaTracker::format_currency(token.liquidity_usd), "inline": true },
+                { "name": "24h Change", "value": change, "inline": true },
+            ],
+        });
+        if !token.mint.is_empty() {
+            embed["footer"] = json!({ "text": token.mint });
+        }
+        if let Some(tweet_id) = tweet_id {
+            embed["url"] = json!(Twitter::tweet_url(tweet_id));
+        }
+        if let Some(name) = image_name {
+            embed["image"] = json!({ "url": format!("attachment://{}", name) });
+        }
+        embed
+    }
+
+    pub async fn post_fud(
+        &self,
+        token: &TokenSnapshot,
+        text: &str,
+        tweet_id: Option<&str>,
+        image: Option<Vec<u8>>,
+    ) -> Result<()> {
+        let image_name = image.as_ref().map(|bytes| Self::image_file_name(bytes));
+        // Embeds can't play videos; the clip still goes along as an attachment
+        let is_video = image.as_deref().is_some_and(|bytes| MediaKind::detect(bytes) == MediaKind::Video);
+        let embed_image = image_name.as_deref().filter(|_| !is_video);
+        let payload = json!({ "embeds": [Self::fud_embed(token, text, tweet_id, embed_image)] });
+
+        let mut failures = Vec::new();
+        for url in &self.webhook_urls {
+            let request = match (&image, &image_name) {
+                (Some(bytes), Some(name)) => {
+                    let file = Part::bytes(bytes.clone()).file_name(name.clone());
+                    let form = Form::new()
+                        .text("payload_json", payload.to_string())
+                        .part("files[0]", file);
+                    self.client.post(url).multipart(form)
+                }
+                _ => self.client.post(url).json(&payload),
+            };
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => failures.push(format!("HTTP {}", response.status())),
+                Err(e) => failures.push(e.to_string()),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "{} of {} Discord webhooks failed: {}",
+                failures.len(),
+                self.webhook_urls.len(),
+                failures.join(", ")
+            ))
+        }
+    }
+
+    // Token cards are PNGs but the chart folder can hold anything the image crate reads,
+    // and reaction clips are MP4s
+    fn image_file_name(bytes: &[u8]) -> String {
+        let kind = MediaKind::detect(bytes);
+        if !kind.is_image() {
+            return format!("fud.{}", kind.extension());
+        }
+        let extension = image::guess_format(bytes)
+            .ok()
+            .and_then(|format| format.extensions_str().first().copied())
+            .unwrap_or("png");
+        format!("fud.{}", extension)
+    }
+}
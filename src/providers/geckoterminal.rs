@@ -0,0 +1,162 @@
+use anyhow::Result;
+use serde::de::Deserializer;
+use serde::Deserialize;
+
+// What a pool's other side usually is: wrapped SOL, USDC and USDT. The token a pool
+// is for is the side that isn't one of these.
+const QUOTE_MINTS: [&str; 3] = [
+    "So11111111111111111111111111111111111111112",
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+];
+
+// Second opinion on prices; GeckoTerminal needs no API key
+#[derive(Clone)]
+pub struct GeckoTerminal {
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenEnvelope {
+    data: TokenData,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenData {
+    attributes: GeckoToken,
+}
+
+// GeckoTerminal sends numbers as strings, and null when it has no figure
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct GeckoToken {
+    #[serde(default, deserialize_with = "deserialize_number_string")]
+    pub price_usd: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_number_string")]
+    pub market_cap_usd: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_number_string")]
+    pub fdv_usd: Option<f64>,
+}
+
+fn deserialize_number_string<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::String(s)) => s.parse().ok(),
+        Some(serde_json::Value::Number(n)) => n.as_f64(),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolEnvelope {
+    data: PoolData,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolData {
+    relationships: PoolRelationships,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolRelationships {
+    base_token: Relationship,
+    quote_token: Relationship,
+}
+
+#[derive(Debug, Deserialize)]
+struct Relationship {
+    data: RelationshipData,
+}
+
+// Ids are "<network>_<address>", e.g. "solana_So111..."
+#[derive(Debug, Deserialize)]
+struct RelationshipData {
+    id: String,
+}
+
+// A trading pair (pool) address, as dexscreener and photon links carry
+pub struct GeckoPool;
+
+impl GeckoPool {
+    // The mint of the token the pool trades: its base token, unless that's SOL or a
+    // stablecoin and the quote token isn't
+    pub fn token_mint(body: &str) -> Result<String> {
+        let envelope: PoolEnvelope = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse GeckoTerminal pool: {}", e))?;
+        let mint = |relationship: &Relationship| {
+            let id = &relationship.data.id;
+            id.split_once('_').map_or(id.as_str(), |(_, address)| address).to_string()
+        };
+        let (base, quote) = (mint(&envelope.data.relationships.base_token), mint(&envelope.data.relationships.quote_token));
+        if QUOTE_MINTS.contains(&base.as_str()) && !QUOTE_MINTS.contains(&quote.as_str()) {
+            Ok(quote)
+        } else {
+            Ok(base)
+        }
+    }
+}
+
+impl GeckoToken {
+    // Reported market cap when there is one, else fully diluted value
+    pub fn market_cap(&self) -> Option<f64> {
+        self.market_cap_usd.or(self.fdv_usd).filter(|value| *value > 0.0)
+    }
+
+    pub fn parse(body: &str) -> Result<Self> {
+        let envelope: TokenEnvelope = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse GeckoTerminal response: {}", e))?;
+        Ok(envelope.data.attributes)
+    }
+}
+
+impl Default for GeckoTerminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeckoTerminal {
+    pub fn new() -> Self {
+        GeckoTerminal {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn get_token(&self, mint: &str) -> Result<GeckoToken> {
+        let url = format!("https://api.geckoterminal.com/api/v2/networks/solana/tokens/{}", mint);
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("GeckoTerminal request failed with status: {}", status));
+        }
+        GeckoToken::parse(&response.text().await?)
+    }
+
+    // The mint the pool at `address` trades, or None if it isn't a pool GeckoTerminal knows
+    pub async fn get_pool_token(&self, address: &str) -> Result<Option<String>> {
+        let url = format!("https://api.geckoterminal.com/api/v2/networks/solana/pools/{}", address);
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("GeckoTerminal request failed with status: {}", status));
+        }
+        GeckoPool::token_mint(&response.text().await?).map(Some)
+    }
+}
@@ -1,6 +1,18 @@
 pub mod twitter;
 pub mod telegram;
+pub mod discord;
+pub mod birdeye;
+pub mod coingecko;
+pub mod geckoterminal;
+pub mod metaplex;
+pub mod network_stats;
+pub mod public_fetch;
+pub mod news;
+pub mod pumpfun;
+pub mod screenshot;
+pub mod solana_rpc;
 pub mod solanatracker;
+pub mod wallet;
 
 #[cfg(test)]
 mod tests;
\ No newline at end of file
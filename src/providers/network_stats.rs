@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::solana_rpc::{BlockOutcomes, PerformanceSample, SolanaRpc};
+
+const JITO_TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+// Performance samples cover about a minute each
+const SAMPLES_TO_AVERAGE: usize = 10;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+// Network-wide numbers for the chain congestion posts
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkStats {
+    pub tps: f64,
+    pub non_vote_tps: Option<f64>,
+    // Micro-lamports per compute unit over recent slots
+    pub median_priority_fee: u64,
+    pub p90_priority_fee: u64,
+    // Share of non-vote transactions that failed in a recent block, 0-100
+    pub failed_rate: Option<f64>,
+    // Median landed Jito tip, in SOL
+    pub jito_tip: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TipFloor {
+    landed_tips_50th_percentile: f64,
+}
+
+pub fn parse_jito_tip_floor(body: &str) -> Result<f64> {
+    let floors: Vec<TipFloor> = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Failed to parse Jito tip floor: {}", e))?;
+    floors
+        .first()
+        .map(|floor| floor.landed_tips_50th_percentile)
+        .ok_or_else(|| anyhow::anyhow!("Jito returned no tip floor"))
+}
+
+impl NetworkStats {
+    // (all transactions, non-vote transactions) per second over the samples
+    pub fn tps(samples: &[PerformanceSample]) -> Option<(f64, Option<f64>)> {
+        let seconds: u64 = samples.iter().map(|sample| sample.sample_period_secs).sum();
+        if seconds == 0 {
+            return None;
+        }
+        let total: u64 = samples.iter().map(|sample| sample.num_transactions).sum();
+        let non_vote: Option<u64> = samples.iter().map(|sample| sample.num_non_vote_transactions).sum();
+        Some((
+            total as f64 / seconds as f64,
+            non_vote.map(|count| count as f64 / seconds as f64),
+        ))
+    }
+
+    // Nearest-rank percentile; 0 when there are no fees
+    pub fn percentile(fees: &[u64], pct: f64) -> u64 {
+        if fees.is_empty() {
+            return 0;
+        }
+        let mut sorted = fees.to_vec();
+        sorted.sort_unstable();
+        let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    pub fn failed_rate(outcomes: &BlockOutcomes) -> Option<f64> {
+        if outcomes.transactions == 0 {
+            return None;
+        }
+        Some(outcomes.failed as f64 / outcomes.transactions as f64 * 100.0)
+    }
+
+    pub fn summary(&self) -> String {
+        let mut lines = vec![match self.non_vote_tps {
+            Some(non_vote) => format!("TPS: {:.0} ({:.0} excluding votes)", self.tps, non_vote),
+            None => format!("TPS: {:.0}", self.tps),
+        }];
+        lines.push(format!(
+            "Priority fees: median {} micro-lamports/CU, 90th percentile {}",
+            self.median_priority_fee, self.p90_priority_fee
+        ));
+        if let Some(rate) = self.failed_rate {
+            lines.push(format!("Failed transactions in the latest block: {:.1}% (excluding votes)", rate));
+        }
+        if let Some(tip) = self.jito_tip {
+            lines.push(format!("Median landed Jito tip: {:.6} SOL", tip));
+        }
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+pub struct NetworkMonitor {
+    client: reqwest::Client,
+}
+
+impl Default for NetworkMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkMonitor {
+    pub fn new() -> Self {
+        NetworkMonitor {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    // TPS and priority fees are required; the failure rate and Jito tips are best effort
+    pub async fn fetch(&self, rpc: &SolanaRpc) -> Result<NetworkStats> {
+        let samples = rpc.get_recent_performance_samples(SAMPLES_TO_AVERAGE).await?;
+        let (tps, non_vote_tps) =
+            NetworkStats::tps(&samples).ok_or_else(|| anyhow::anyhow!("RPC returned no performance samples"))?;
+        let fees = rpc.get_recent_prioritization_fees().await?;
+
+        let failed_rate = match self.latest_block_outcomes(rpc).await {
+            Ok(outcomes) => NetworkStats::failed_rate(&outcomes),
+            Err(e) => {
+                eprintln!("Couldn't read the latest block for the failure rate: {}", e);
+                None
+            }
+        };
+        let jito_tip = match self.jito_tip_floor().await {
+            Ok(tip) => Some(tip),
+            Err(e) => {
+                eprintln!("Couldn't fetch the Jito tip floor: {}", e);
+                None
+            }
+        };
+
+        Ok(NetworkStats {
+            tps,
+            non_vote_tps,
+            median_priority_fee: NetworkStats::percentile(&fees, 50.0),
+            p90_priority_fee: NetworkStats::percentile(&fees, 90.0),
+            failed_rate,
+            jito_tip,
+        })
+    }
+
+    async fn latest_block_outcomes(&self, rpc: &SolanaRpc) -> Result<BlockOutcomes> {
+        let slot = rpc.get_slot().await?;
+        rpc.get_block_outcomes(slot).await
+    }
+
+    async fn jito_tip_floor(&self) -> Result<f64> {
+        let response = self.client.get(JITO_TIP_FLOOR_URL).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Jito tip floor request failed with status: {}", status));
+        }
+        parse_jito_tip_floor(&response.text().await?)
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+use crate::core::stream_guard::contains_term;
+
+const CRYPTOPANIC_URL: &str = "https://cryptopanic.com/api/v1/posts/";
+
+// One news item, from CryptoPanic or an RSS feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Headline {
+    pub title: String,
+    pub url: String,
+    pub source: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl Headline {
+    // Any keyword appears in the title as a whole word, ignoring case
+    pub fn mentions_any(&self, keywords: &[String]) -> bool {
+        let title = self.title.to_lowercase();
+        keywords.iter().any(|keyword| contains_term(&title, &keyword.to_lowercase(), false))
+    }
+
+    // Items without a date are treated as fresh; feeds that omit it are usually live ones
+    pub fn is_fresh(&self, max_age: chrono::Duration, now: DateTime<Utc>) -> bool {
+        self.published_at.is_none_or(|at| now.signed_duration_since(at) <= max_age)
+    }
+
+    pub fn prompt_line(&self) -> String {
+        match self.published_at {
+            Some(at) => format!("\"{}\" ({}, {})", self.title, self.source, at.format("%Y-%m-%d %H:%M UTC")),
+            None => format!("\"{}\" ({})", self.title, self.source),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CryptoPanicResponse {
+    #[serde(default)]
+    results: Vec<CryptoPanicPost>,
+}
+
+#[derive(Deserialize)]
+struct CryptoPanicPost {
+    title: String,
+    url: String,
+    #[serde(default)]
+    published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    source: Option<CryptoPanicSource>,
+}
+
+#[derive(Deserialize)]
+struct CryptoPanicSource {
+    title: String,
+}
+
+pub fn parse_cryptopanic(body: &str) -> Result<Vec<Headline>> {
+    let response: CryptoPanicResponse = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Failed to parse CryptoPanic response: {}", e))?;
+    Ok(response
+        .results
+        .into_iter()
+        .map(|post| Headline {
+            title: post.title,
+            url: post.url,
+            source: post.source.map(|s| s.title).unwrap_or_else(|| "CryptoPanic".to_string()),
+            published_at: post.published_at,
+        })
+        .collect())
+}
+
+fn rss_tag(item: &str, tag: &str) -> Option<String> {
+    let pattern = format!(r"(?s)<{0}(?:\s[^>]*)?>(.*?)</{0}>", regex::escape(tag));
+    let captured = Regex::new(&pattern).ok()?.captures(item)?.get(1)?.as_str().trim();
+    let text = captured
+        .strip_prefix("<![CDATA[")
+        .and_then(|inner| inner.strip_suffix("]]>"))
+        .unwrap_or(captured);
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+// Items of an RSS 2.0 feed. Only title, link and pubDate are read, so a regex is
+// enough; items missing a title or link are skipped.
+pub fn parse_rss(body: &str, source: &str) -> Vec<Headline> {
+    static ITEM: OnceLock<Regex> = OnceLock::new();
+    let item = ITEM.get_or_init(|| Regex::new(r"(?s)<item(?:\s[^>]*)?>(.*?)</item>").unwrap());
+    item.captures_iter(body)
+        .filter_map(|captures| {
+            let item = captures.get(1)?.as_str();
+            Some(Headline {
+                title: rss_tag(item, "title")?,
+                url: rss_tag(item, "link")?,
+                source: source.to_string(),
+                published_at: rss_tag(item, "pubDate")
+                    .and_then(|date| DateTime::parse_from_rfc2822(&date).ok())
+                    .map(|date| date.with_timezone(&Utc)),
+            })
+        })
+        .collect()
+}
+
+// Host of a feed URL, used as the source name for its headlines
+pub fn feed_source(url: &str) -> String {
+    let host = url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or(url);
+    host.trim_start_matches("www.").to_string()
+}
+
+// Newest relevant headline from the last max_age that hasn't been reacted to yet.
+// The same story often comes from several sources, so titles count as seen too.
+pub fn pick_headline(
+    headlines: Vec<Headline>,
+    keywords: &[String],
+    max_age: chrono::Duration,
+    now: DateTime<Utc>,
+    seen: impl Fn(&Headline) -> bool,
+) -> Option<Headline> {
+    headlines
+        .into_iter()
+        .filter(|headline| headline.mentions_any(keywords) && headline.is_fresh(max_age, now) && !seen(headline))
+        .max_by_key(|headline| headline.published_at)
+}
+
+// Crypto headlines from CryptoPanic, when there's an API key, and any RSS feeds
+pub struct NewsClient {
+    client: reqwest::Client,
+    cryptopanic_key: Option<String>,
+}
+
+impl NewsClient {
+    pub fn new(cryptopanic_key: Option<&str>) -> Self {
+        NewsClient {
+            client: reqwest::Client::new(),
+            cryptopanic_key: cryptopanic_key.map(str::to_string),
+        }
+    }
+
+    async fn get_text(&self, url: &str, query: &[(&str, &str)]) -> Result<String> {
+        // The CryptoPanic key travels in the query string, so keep URLs out of errors
+        let response = self.client.get(url).query(query).send().await.map_err(|e| e.without_url())?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("News request to {} failed with status: {}", url, status));
+        }
+        Ok(response.text().await.map_err(|e| e.without_url())?)
+    }
+
+    pub async fn get_cryptopanic(&self, api_key: &str) -> Result<Vec<Headline>> {
+        let query = [("auth_token", api_key), ("currencies", "SOL"), ("kind", "news"), ("public", "true")];
+        parse_cryptopanic(&self.get_text(CRYPTOPANIC_URL, &query).await?)
+    }
+
+    pub async fn get_feed(&self, url: &str) -> Result<Vec<Headline>> {
+        Ok(parse_rss(&self.get_text(url, &[]).await?, &feed_source(url)))
+    }
+
+    // Every source that answered; one failing only costs its own headlines
+    pub async fn fetch_headlines(&self, feeds: &[String]) -> Vec<Headline> {
+        let mut headlines = Vec::new();
+        if let Some(api_key) = &self.cryptopanic_key {
+            match self.get_cryptopanic(api_key).await {
+                Ok(items) => headlines.extend(items),
+                Err(e) => eprintln!("Couldn't fetch CryptoPanic headlines: {}", e),
+            }
+        }
+        for feed in feeds {
+            match self.get_feed(feed).await {
+                Ok(items) => headlines.extend(items),
+                Err(e) => eprintln!("Couldn't fetch news feed {}: {}", feed, e),
+            }
+        }
+        headlines
+    }
+}
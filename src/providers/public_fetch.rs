@@ -0,0 +1,102 @@
+use anyhow::Result;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Token metadata, logos and websites are links whoever launched the token chose. They
+// are fetched with a client that only reaches public addresses.
+
+const MAX_REDIRECTS: usize = 5;
+
+// Whether an address is on the public internet. The URLs fetched with this come from
+// whoever deployed the token, so nothing on the bot's own host or network is reached.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (64..128).contains(&b))
+                || a == 0)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local fc00::/7 and link-local fe80::/10
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+// Whether a URL's host is an IP literal that isn't public. Names are checked when
+// they're resolved, by PublicResolver.
+pub fn is_private_literal(url: &reqwest::Url) -> bool {
+    url.host_str()
+        .and_then(|host| host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().ok())
+        .is_some_and(|ip| !is_public_ip(ip))
+}
+
+// Resolves names as usual, then drops every address that isn't public
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+// A client for token-supplied links: public addresses only, redirects checked the
+// same way, no proxy, and `timeout` for the whole request
+pub fn public_client(timeout: Duration) -> reqwest::Client {
+    let redirects = reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if is_private_literal(attempt.url()) {
+            attempt.error("redirected to a private address")
+        } else {
+            attempt.follow()
+        }
+    });
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(redirects)
+        // A proxy would resolve the name itself, past the address check
+        .no_proxy()
+        .build()
+        .unwrap_or_default()
+}
+
+// The link parsed, if it's http(s) and not a private IP literal
+pub fn public_url(url: &str) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url)?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(anyhow::anyhow!("{} isn't an http(s) link", url));
+    }
+    if is_private_literal(&parsed) {
+        return Err(anyhow::anyhow!("{} points at a private address", url));
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const PUMP_FUN_API_URL: &str = "https://frontend-api-v3.pump.fun";
+// Tokens a fresh bonding curve can sell before it migrates (793.1M, 6 decimals)
+const INITIAL_REAL_TOKEN_RESERVES: f64 = 793_100_000_000_000.0;
+// The newest trades fetched. For a coin with more than this the launch is only in
+// them if the oldest one is from its first minute; otherwise there's no dev buy figure.
+pub const TRADES_TO_SCAN: usize = 50;
+const LAUNCH_WINDOW_SECS: i64 = 60;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PumpCoin {
+    #[serde(default)]
+    pub creator: String,
+    // True once the curve filled and the token migrated to an AMM
+    #[serde(default)]
+    pub complete: bool,
+    #[serde(default)]
+    pub real_token_reserves: Option<f64>,
+    #[serde(default)]
+    pub total_supply: Option<f64>,
+    // Milliseconds
+    #[serde(default)]
+    pub created_timestamp: Option<i64>,
+    // Milliseconds; set once the coin has been king of the hill
+    #[serde(default)]
+    pub king_of_the_hill_timestamp: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PumpTrade {
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub is_buy: bool,
+    #[serde(default)]
+    pub token_amount: f64,
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+// Where a pump.fun token stands on its bonding curve
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondingStatus {
+    pub graduated: bool,
+    // 0-100
+    pub progress: f64,
+    // Share of supply the creator bought in their opening buys
+    pub dev_buy_pct: Option<f64>,
+    pub king_of_the_hill_at: Option<DateTime<Utc>>,
+}
+
+impl BondingStatus {
+    pub fn from_coin(coin: &PumpCoin, trades: &[PumpTrade]) -> Self {
+        let progress = if coin.complete {
+            100.0
+        } else {
+            coin.real_token_reserves
+                .map(|reserves| (1.0 - reserves / INITIAL_REAL_TOKEN_RESERVES) * 100.0)
+                .unwrap_or(0.0)
+                .clamp(0.0, 100.0)
+        };
+
+        BondingStatus {
+            graduated: coin.complete,
+            progress,
+            dev_buy_pct: Self::dev_buy_pct(coin, trades),
+            king_of_the_hill_at: coin
+                .king_of_the_hill_timestamp
+                .and_then(DateTime::from_timestamp_millis),
+        }
+    }
+
+    // Creator buys before anyone else traded, as a share of total supply
+    fn dev_buy_pct(coin: &PumpCoin, trades: &[PumpTrade]) -> Option<f64> {
+        let supply = coin.total_supply.filter(|supply| *supply > 0.0)?;
+        // Without trades (fetch failed) we can't tell a dev who didn't buy from one who did
+        if coin.creator.is_empty() || trades.is_empty() {
+            return None;
+        }
+        let mut oldest_first: Vec<&PumpTrade> = trades.iter().collect();
+        oldest_first.sort_by_key(|trade| trade.timestamp);
+        // A full window may have cut off the launch, and with it the dev's buys
+        if trades.len() >= TRADES_TO_SCAN {
+            let created = coin.created_timestamp? / 1000;
+            if oldest_first[0].timestamp > created + LAUNCH_WINDOW_SECS {
+                return None;
+            }
+        }
+        let bought: f64 = oldest_first
+            .iter()
+            .take_while(|trade| trade.user == coin.creator)
+            .filter(|trade| trade.is_buy)
+            .map(|trade| trade.token_amount)
+            .sum();
+        Some(bought / supply * 100.0)
+    }
+
+    pub fn is_pre_bond(&self) -> bool {
+        !self.graduated
+    }
+
+    // Lines appended to the token summary
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let mut lines = Vec::new();
+        if self.graduated {
+            lines.push("Pump.fun: graduated from the bonding curve".to_string());
+        } else {
+            lines.push(format!("Pump.fun: still on the bonding curve, {:.0}% of the way to graduating", self.progress));
+        }
+        if let Some(pct) = self.dev_buy_pct {
+            if pct > 0.0 {
+                lines.push(format!("Dev bought {:.1}% of supply at launch", pct));
+            } else {
+                lines.push("Dev didn't buy any at launch".to_string());
+            }
+        }
+        match self.king_of_the_hill_at {
+            Some(at) => {
+                let hours = now.signed_duration_since(at).num_hours();
+                lines.push(format!("Reached king of the hill {} hours ago", hours.max(0)));
+            }
+            None if !self.graduated => lines.push("Never made king of the hill".to_string()),
+            None => {}
+        }
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+pub fn parse_coin(body: &str) -> Result<PumpCoin> {
+    serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse pump.fun coin: {}", e))
+}
+
+pub fn parse_trades(body: &str) -> Result<Vec<PumpTrade>> {
+    serde_json::from_str(body).map_err(|e| anyhow::anyhow!("Failed to parse pump.fun trades: {}", e))
+}
+
+pub struct PumpFun {
+    client: reqwest::Client,
+}
+
+impl Default for PumpFun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PumpFun {
+    pub fn new() -> Self {
+        PumpFun {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    // Launched on pump.fun; its vanity mints all end in "pump"
+    pub fn is_pump_token(mint: &str) -> bool {
+        mint.ends_with("pump")
+    }
+
+    async fn get(&self, path: &str) -> Result<String> {
+        let response = self.client.get(format!("{}{}", PUMP_FUN_API_URL, path)).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("pump.fun request failed with status: {}", status));
+        }
+        Ok(response.text().await?)
+    }
+
+    pub async fn get_bonding_status(&self, mint: &str) -> Result<BondingStatus> {
+        let coin = parse_coin(&self.get(&format!("/coins/{}", mint)).await?)?;
+        // Trades only matter for the dev buy; the curve status is still useful without them
+        let trades = match self.get(&format!("/trades/all/{}?limit={}&offset=0&minimumSize=0", mint, TRADES_TO_SCAN)).await {
+            Ok(body) => parse_trades(&body).unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Couldn't fetch pump.fun trades for {}: {}", mint, e);
+                Vec::new()
+            }
+        };
+        Ok(BondingStatus::from_coin(&coin, &trades))
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::core::config::ScreenshotConfig;
+
+// ScreenshotOne's capture endpoint; any API taking the same query parameters works
+pub const DEFAULT_SCREENSHOT_API_URL: &str = "https://api.screenshotone.com/take";
+
+// Rendering a slow memecoin site can take a while
+const CAPTURE_TIMEOUT_SECS: u64 = 45;
+// Twitter rejects images over 5MB
+const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+// Client for a hosted headless-browser screenshot API
+pub struct ScreenshotApi {
+    client: reqwest::Client,
+    config: ScreenshotConfig,
+}
+
+impl ScreenshotApi {
+    pub fn new(config: ScreenshotConfig) -> Self {
+        ScreenshotApi {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(CAPTURE_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            config,
+        }
+    }
+
+    // Desktop-sized JPEG of the page above the fold, with ads and cookie banners hidden
+    pub fn request_url(&self, website: &str) -> Result<reqwest::Url> {
+        let url = reqwest::Url::parse_with_params(
+            &self.config.api_url,
+            [
+                ("access_key", self.config.api_key.expose()),
+                ("url", website),
+                ("format", "jpg"),
+                ("image_quality", "80"),
+                ("viewport_width", "1280"),
+                ("viewport_height", "800"),
+                ("block_ads", "true"),
+                ("block_cookie_banners", "true"),
+                ("delay", "2"),
+            ],
+        )?;
+        Ok(url)
+    }
+
+    pub async fn capture(&self, website: &str) -> Result<Vec<u8>> {
+        // reqwest errors print the request URL, which carries the key, so it's dropped
+        let response = self.client.get(self.request_url(website)?).send().await.map_err(reqwest::Error::without_url)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // Don't echo the request URL, it carries the key
+            return Err(anyhow::anyhow!("Screenshot of {} failed with status: {}", website, status));
+        }
+        let is_image = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("image/"));
+        if !is_image {
+            return Err(anyhow::anyhow!("Screenshot API returned something other than an image for {}", website));
+        }
+
+        let bytes = response.bytes().await.map_err(reqwest::Error::without_url)?.to_vec();
+        if bytes.is_empty() || bytes.len() > MAX_IMAGE_BYTES {
+            return Err(anyhow::anyhow!("Screenshot of {} is {} bytes, not attaching it", website, bytes.len()));
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,514 @@
+use anyhow::Result;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+// Minimal JSON-RPC client: token supply when the tracker doesn't report a
+// market cap, raw accounts for on-chain token metadata, network stats and the
+// launch transactions the bundle check reads
+#[derive(Clone)]
+pub struct SolanaRpc {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenSupply {
+    // Raw base units, as a string so u64 supplies survive intact
+    pub amount: String,
+    pub decimals: u8,
+}
+
+impl TokenSupply {
+    // Whole tokens
+    pub fn ui_amount(&self) -> Option<f64> {
+        crate::providers::solanatracker::ui_amount(&self.amount, self.decimals)
+    }
+
+    pub fn parse(body: &str) -> Result<Self> {
+        let value: RpcSupplyValue = parse_value(body, "getTokenSupply")?;
+        Ok(TokenSupply {
+            amount: value.amount,
+            decimals: value.decimals,
+        })
+    }
+}
+
+// An account as returned by getMultipleAccounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountData {
+    pub owner: String,
+    pub data: Vec<u8>,
+}
+
+impl AccountData {
+    // One entry per requested address, None where the account doesn't exist
+    pub fn parse_many(body: &str) -> Result<Vec<Option<Self>>> {
+        let accounts: Vec<Option<RpcAccount>> = parse_value(body, "getMultipleAccounts")?;
+        accounts
+            .into_iter()
+            .map(|account| {
+                account
+                    .map(|account| {
+                        let encoded = account.data.first().map(String::as_str).unwrap_or_default();
+                        let data = base64::engine::general_purpose::STANDARD
+                            .decode(encoded)
+                            .map_err(|e| anyhow::anyhow!("Bad account data from getMultipleAccounts: {}", e))?;
+                        Ok(AccountData { owner: account.owner, data })
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
+// What an address holds, from getAccountInfo with jsonParsed encoding
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountKind {
+    Missing,
+    Mint,
+    // A wallet, token account, program or anything else that isn't a mint
+    Other,
+}
+
+impl AccountKind {
+    pub fn parse(body: &str) -> Result<Self> {
+        let value: serde_json::Value = parse_value(body, "getAccountInfo")?;
+        if value.is_null() {
+            return Ok(AccountKind::Missing);
+        }
+        // Token program accounts come back parsed; anything else is still [data, encoding]
+        Ok(match value["data"]["parsed"]["type"].as_str() {
+            Some("mint") => AccountKind::Mint,
+            _ => AccountKind::Other,
+        })
+    }
+}
+
+// One entry of getRecentPerformanceSamples, covering about a minute of slots
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceSample {
+    pub num_transactions: u64,
+    #[serde(default)]
+    pub num_non_vote_transactions: Option<u64>,
+    pub sample_period_secs: u64,
+}
+
+impl PerformanceSample {
+    pub fn parse_many(body: &str) -> Result<Vec<Self>> {
+        parse_result(body, "getRecentPerformanceSamples")
+    }
+}
+
+// Per-slot minimum priority fees (micro-lamports per compute unit) from getRecentPrioritizationFees
+pub fn parse_prioritization_fees(body: &str) -> Result<Vec<u64>> {
+    let fees: Vec<RpcPrioritizationFee> = parse_result(body, "getRecentPrioritizationFees")?;
+    Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+}
+
+// One entry of getSignaturesForAddress, newest first as the node returns them
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub failed: bool,
+}
+
+impl SignatureInfo {
+    pub fn parse_many(body: &str) -> Result<Vec<Self>> {
+        let signatures: Vec<RpcSignature> = parse_result(body, "getSignaturesForAddress")?;
+        Ok(signatures
+            .into_iter()
+            .map(|info| SignatureInfo {
+                signature: info.signature,
+                slot: info.slot,
+                failed: !info.err.is_null(),
+            })
+            .collect())
+    }
+}
+
+// How much of a mint one wallet gained or lost in a transaction, in whole tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalanceChange {
+    pub owner: String,
+    pub amount: f64,
+}
+
+// Balance changes of the mint per owner, from getTransaction's pre and post token
+// balances. A wallet with no balance before the transaction started from zero.
+pub fn parse_balance_changes(body: &str, mint: &str) -> Result<Vec<TokenBalanceChange>> {
+    // A null result is a transaction the node doesn't have, not an error
+    let response: RpcResponse<RpcTransaction> = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Failed to parse getTransaction response: {}", e))?;
+    if let Some(error) = response.error {
+        return Err(anyhow::anyhow!("getTransaction failed: {}", error.message));
+    }
+    let Some(meta) = response.result.and_then(|transaction| transaction.meta) else {
+        return Ok(Vec::new());
+    };
+    let mut changes: Vec<TokenBalanceChange> = Vec::new();
+    let balances = meta
+        .pre_token_balances
+        .iter()
+        .map(|balance| (balance, -1.0))
+        .chain(meta.post_token_balances.iter().map(|balance| (balance, 1.0)));
+    for (balance, sign) in balances.filter(|(balance, _)| balance.mint == mint) {
+        let Some(owner) = balance.owner.as_ref() else {
+            continue;
+        };
+        let amount = balance.ui_token_amount.ui_amount_string.parse::<f64>().unwrap_or(0.0) * sign;
+        match changes.iter_mut().find(|change| &change.owner == owner) {
+            Some(change) => change.amount += amount,
+            None => changes.push(TokenBalanceChange { owner: owner.clone(), amount }),
+        }
+    }
+    Ok(changes)
+}
+
+pub const VOTE_PROGRAM_ID: &str = "Vote111111111111111111111111111111111111111";
+
+// Non-vote transactions in a block and how many of them failed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockOutcomes {
+    pub transactions: usize,
+    pub failed: usize,
+}
+
+impl BlockOutcomes {
+    // getBlock with transactionDetails "accounts"; votes are left out since they
+    // make up most of a block and almost never fail
+    pub fn parse(body: &str) -> Result<Self> {
+        let block: RpcBlock = parse_result(body, "getBlock")?;
+        let user_transactions: Vec<&RpcBlockTransaction> = block
+            .transactions
+            .iter()
+            .filter(|tx| !tx.transaction.account_keys.iter().any(|key| key.pubkey == VOTE_PROGRAM_ID))
+            .collect();
+        Ok(BlockOutcomes {
+            transactions: user_transactions.len(),
+            failed: user_transactions
+                .iter()
+                .filter(|tx| tx.meta.as_ref().is_some_and(|meta| !meta.err.is_null()))
+                .count(),
+        })
+    }
+}
+
+// The node answered the call with an error. For sendTransaction that means the
+// transaction was refused and never forwarded, unlike a timeout or HTTP failure.
+#[derive(Debug, thiserror::Error)]
+#[error("{method} failed: {message}")]
+pub struct RpcRejected {
+    pub method: String,
+    pub message: String,
+}
+
+impl RpcRejected {
+    pub fn is(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<RpcRejected>().is_some()
+    }
+}
+
+fn parse_result<T: DeserializeOwned>(body: &str, method: &str) -> Result<T> {
+    let response: RpcResponse<T> = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {} response: {}", method, e))?;
+    if let Some(error) = response.error {
+        return Err(RpcRejected { method: method.to_string(), message: error.message }.into());
+    }
+    response
+        .result
+        .ok_or_else(|| anyhow::anyhow!("{} returned no result", method))
+}
+
+// Most methods wrap their answer in { context, value }
+fn parse_value<T: DeserializeOwned>(body: &str, method: &str) -> Result<T> {
+    parse_result::<RpcResult<T>>(body, method).map(|result| result.value)
+}
+
+// Blockhash a new transaction has to reference
+pub fn parse_blockhash(body: &str) -> Result<String> {
+    let value: RpcBlockhash = parse_value(body, "getLatestBlockhash")?;
+    Ok(value.blockhash)
+}
+
+// Signature of a submitted transaction
+pub fn parse_signature(body: &str) -> Result<String> {
+    parse_result(body, "sendTransaction")
+}
+
+// Lamports the first requested account would hold after a simulated transaction;
+// 0 if the transaction closes it. A simulation that fails is an error.
+pub fn parse_simulated_lamports(body: &str) -> Result<u64> {
+    let value: RpcSimulation = parse_value(body, "simulateTransaction")?;
+    if !value.err.is_null() {
+        return Err(anyhow::anyhow!("Simulation failed: {}", value.err));
+    }
+    match value.accounts.unwrap_or_default().into_iter().next() {
+        Some(Some(account)) => Ok(account.lamports),
+        Some(None) => Ok(0),
+        None => Err(anyhow::anyhow!("Simulation returned no accounts")),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound = "T: DeserializeOwned")]
+struct RpcResponse<T> {
+    #[serde(default = "Option::default")]
+    result: Option<T>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResult<T> {
+    value: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcSupplyValue {
+    amount: String,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcSimulation {
+    // null on success
+    #[serde(default)]
+    err: serde_json::Value,
+    // null when the simulation failed
+    #[serde(default)]
+    accounts: Option<Vec<Option<RpcLamports>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcLamports {
+    lamports: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcBlockhash {
+    blockhash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcAccount {
+    // [base64 data, "base64"]
+    data: Vec<String>,
+    owner: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    prioritization_fee: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcBlock {
+    #[serde(default)]
+    transactions: Vec<RpcBlockTransaction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcBlockTransaction {
+    transaction: RpcBlockTransactionAccounts,
+    #[serde(default)]
+    meta: Option<RpcTransactionMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlockTransactionAccounts {
+    #[serde(default)]
+    account_keys: Vec<RpcAccountKey>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcAccountKey {
+    pubkey: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcTransactionMeta {
+    // null on success
+    #[serde(default)]
+    err: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcSignature {
+    signature: String,
+    slot: u64,
+    // null on success
+    #[serde(default)]
+    err: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcTransaction {
+    #[serde(default)]
+    meta: Option<RpcTokenBalances>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTokenBalances {
+    #[serde(default)]
+    pre_token_balances: Vec<RpcTokenBalance>,
+    #[serde(default)]
+    post_token_balances: Vec<RpcTokenBalance>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTokenBalance {
+    mint: String,
+    #[serde(default)]
+    owner: Option<String>,
+    ui_token_amount: RpcTokenAmount,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTokenAmount {
+    ui_amount_string: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+impl SolanaRpc {
+    pub fn new(url: &str) -> Self {
+        SolanaRpc {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: serde_json::Value) -> Result<String> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = self.client.post(&self.url).json(&request).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("Solana RPC request failed with status: {}", status));
+        }
+        Ok(response.text().await?)
+    }
+
+    pub async fn get_token_supply(&self, mint: &str) -> Result<TokenSupply> {
+        TokenSupply::parse(&self.call("getTokenSupply", json!([mint])).await?)
+    }
+
+    pub async fn get_latest_blockhash(&self) -> Result<String> {
+        parse_blockhash(&self.call("getLatestBlockhash", json!([{ "commitment": "finalized" }])).await?)
+    }
+
+    // Submits a signed transaction; preflight simulation catches most failures before they cost a fee
+    pub async fn send_transaction(&self, transaction: &[u8]) -> Result<String> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(transaction);
+        let body = self
+            .call("sendTransaction", json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }]))
+            .await?;
+        parse_signature(&body)
+    }
+
+    pub async fn get_balance(&self, address: &str) -> Result<u64> {
+        parse_value(&self.call("getBalance", json!([address, { "commitment": "confirmed" }])).await?, "getBalance")
+    }
+
+    // `address`'s lamports once `transaction` ran, without sending it. Our signature
+    // isn't checked and the blockhash is swapped for a fresh one.
+    pub async fn simulate_lamports_after(&self, transaction: &[u8], address: &str) -> Result<u64> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(transaction);
+        let body = self
+            .call(
+                "simulateTransaction",
+                json!([encoded, {
+                    "encoding": "base64",
+                    "commitment": "confirmed",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true,
+                    "accounts": { "encoding": "base64", "addresses": [address] },
+                }]),
+            )
+            .await?;
+        parse_simulated_lamports(&body)
+    }
+
+    pub async fn get_recent_performance_samples(&self, limit: usize) -> Result<Vec<PerformanceSample>> {
+        PerformanceSample::parse_many(&self.call("getRecentPerformanceSamples", json!([limit])).await?)
+    }
+
+    pub async fn get_recent_prioritization_fees(&self) -> Result<Vec<u64>> {
+        parse_prioritization_fees(&self.call("getRecentPrioritizationFees", json!([])).await?)
+    }
+
+    pub async fn get_slot(&self) -> Result<u64> {
+        parse_result(&self.call("getSlot", json!([{ "commitment": "confirmed" }])).await?, "getSlot")
+    }
+
+    pub async fn get_block_outcomes(&self, slot: u64) -> Result<BlockOutcomes> {
+        let body = self
+            .call(
+                "getBlock",
+                json!([slot, {
+                    "commitment": "confirmed",
+                    "transactionDetails": "accounts",
+                    "maxSupportedTransactionVersion": 0,
+                    "rewards": false,
+                }]),
+            )
+            .await?;
+        BlockOutcomes::parse(&body)
+    }
+
+    pub async fn get_account_kind(&self, address: &str) -> Result<AccountKind> {
+        let body = self
+            .call("getAccountInfo", json!([address, { "encoding": "jsonParsed" }]))
+            .await?;
+        AccountKind::parse(&body)
+    }
+
+    // Up to `limit` signatures involving the address, newest first, older than `before` when given
+    pub async fn get_signatures_for_address(&self, address: &str, before: Option<&str>, limit: usize) -> Result<Vec<SignatureInfo>> {
+        let mut options = json!({ "limit": limit, "commitment": "confirmed" });
+        if let Some(before) = before {
+            options["before"] = json!(before);
+        }
+        SignatureInfo::parse_many(&self.call("getSignaturesForAddress", json!([address, options])).await?)
+    }
+
+    pub async fn get_token_balance_changes(&self, signature: &str, mint: &str) -> Result<Vec<TokenBalanceChange>> {
+        let body = self
+            .call(
+                "getTransaction",
+                json!([signature, {
+                    "commitment": "confirmed",
+                    "encoding": "jsonParsed",
+                    "maxSupportedTransactionVersion": 0,
+                }]),
+            )
+            .await?;
+        parse_balance_changes(&body, mint)
+    }
+
+    pub async fn get_multiple_accounts(&self, addresses: &[String]) -> Result<Vec<Option<AccountData>>> {
+        let body = self
+            .call("getMultipleAccounts", json!([addresses, { "encoding": "base64" }]))
+            .await?;
+        AccountData::parse_many(&body)
+    }
+}
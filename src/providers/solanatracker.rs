@@ -2,28 +2,65 @@ use serde::{Deserialize, Serialize};
 use serde::de::Deserializer;
 
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use reqwest::header::{HeaderMap, HeaderValue};
+use std::fmt;
+use std::sync::{Arc, Mutex};
 use crate::core::agent::Agent;  
+use crate::models::TokenSnapshot;
+use super::public_fetch::{public_client, public_url};
 use rand::Rng;
 
+const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+// Logos and their metadata come from whatever the token's creator points at, so the
+// download stops here; a real logo is far smaller
+pub const MAX_TOKEN_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+const MAX_TOKEN_METADATA_BYTES: usize = 256 * 1024;
+const TOKEN_IMAGE_TIMEOUT_SECS: u64 = 15;
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct TokenResponse {
     pub token: TokenInfo,
     #[serde(default)]
     pub pools: Vec<Pool>,
+    #[serde(default)]
+    pub risk: Option<TokenRisk>,
+    // Not every endpoint reports it
+    #[serde(default)]
+    pub holders: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TokenRisk {
+    #[serde(default)]
+    pub rugged: bool,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub score: f64,
+    #[serde(default)]
+    pub risks: Vec<RiskFactor>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RiskFactor {
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub name: String,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub level: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct TokenInfo {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub name: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub symbol: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub mint: String,
     #[serde(default)]
     pub uri: Option<String>,
     #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
     pub description: Option<String>,
 }
 
@@ -35,13 +72,23 @@ pub struct Pool {
     pub liquidity: Liquidity,
     #[serde(default)]
     pub events: Events,
+    #[serde(default)]
+    pub txns: Txns,
+    #[serde(rename = "marketCap", default)]
+    pub market_cap: Option<MarketCap>,
+    // Whole tokens, not raw base units
+    #[serde(rename = "tokenSupply", default, deserialize_with = "deserialize_null_default")]
+    pub token_supply: Option<f64>,
+    // Milliseconds since the epoch
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct Liquidity {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub quote: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub usd: f64,
     #[serde(default)]
     pub price: Price,
@@ -66,18 +113,71 @@ where
 
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct MarketCap {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub quote: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub usd: f64,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct Events {
-    #[serde(rename = "24h", default)]
+    #[serde(rename = "1h", default, deserialize_with = "deserialize_price_change")]
+    pub price_change_percentage_1h: Option<f64>,
+    #[serde(rename = "24h", default, deserialize_with = "deserialize_price_change")]
     pub price_change_percentage_24h: Option<f64>,
 }
 
+// Sometimes a bare number, sometimes {"priceChangePercentage": n}
+fn deserialize_price_change<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::Number(n)) => n.as_f64(),
+        Some(serde_json::Value::Object(fields)) => fields
+            .get("priceChangePercentage")
+            .and_then(serde_json::Value::as_f64),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Txns {
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub buys: u64,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub sells: u64,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TradesResponse {
+    #[serde(default)]
+    pub trades: Vec<Trade>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Trade {
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub amount: f64,
+    #[serde(rename = "priceUsd", default, deserialize_with = "deserialize_null_default")]
+    pub price_usd: f64,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
+    pub volume: f64,
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub wallet: String,
+    #[serde(default)]
+    pub time: i64,
+}
+
+impl Trade {
+    pub fn is_sell(&self) -> bool {
+        self.kind.eq_ignore_ascii_case("sell")
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct SearchParams {
     pub query: String,
@@ -137,24 +237,28 @@ pub struct SearchResponse {
 
 #[derive(Debug, Deserialize)]
 pub struct SearchResult {
+    #[serde(default)]
     pub decimals: u8,
     #[serde(rename = "freezeAuthority")]
     pub freeze_authority: Option<String>,
     pub image: Option<String>,
     pub jupiter: Option<bool>,
-    #[serde(rename = "liquidityUsd")]
+    #[serde(rename = "liquidityUsd", default, deserialize_with = "deserialize_null_default")]
     pub liquidity_usd: f64,
     #[serde(rename = "lpBurn")]
     pub lp_burn: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub market: String,
-    #[serde(rename = "marketCapUsd")]
+    #[serde(rename = "marketCapUsd", default, deserialize_with = "deserialize_null_default")]
     pub market_cap_usd: f64,
     pub mint: String,
     #[serde(rename = "mintAuthority")]
     pub mint_authority: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub name: String,
-    #[serde(rename = "poolAddress")]
+    #[serde(rename = "poolAddress", default, deserialize_with = "deserialize_null_default")]
     pub pool_address: String,
+    #[serde(default, deserialize_with = "deserialize_null_default")]
     pub symbol: String,
     #[serde(rename = "totalBuys")]
     pub total_buys: Option<u32>,
@@ -180,6 +284,13 @@ impl From<SearchResult> for TokenResponse {
                 price: Price::default(),
             },
             events: Events::default(),
+            txns: Txns::default(),
+            market_cap: Some(MarketCap {
+                quote: 0.0,
+                usd: result.market_cap_usd,
+            }),
+            token_supply: None,
+            created_at: None,
         };
 
         TokenResponse {
@@ -188,23 +299,223 @@ impl From<SearchResult> for TokenResponse {
                 symbol: result.symbol,
                 mint: result.mint,
                 uri: None,
+                image: result.image,
                 description: None,
             },
             pools: vec![pool],
+            risk: None,
+            holders: None,
         }
     }
 }
 
+// Cloning shares the trending cache, so a background refresh fills it for everyone
+#[derive(Clone)]
 pub struct SolanaTracker {
     api_key: String,
     client: reqwest::Client,
+    // For the logo and metadata links the token's creator chose
+    fetch_client: reqwest::Client,
+    trending: Arc<Mutex<TrendingCache>>,
+}
+
+pub const DEFAULT_TRENDING_TTL_SECONDS: u64 = 180;
+// A list this many TTLs old is still served while a fresh one is fetched behind it
+const STALE_SERVE_TTLS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    // Served past the TTL while a refresh ran in the background
+    pub stale_hits: u64,
+    pub misses: u64,
+    pub refreshes: u64,
+    pub failed_refreshes: u64,
 }
 
-impl Price {
-    // Function to calculate market cap
-    pub fn calculate_market_cap(&self) -> f64 {
-        // Assuming shifting decimal is equivalent to multiplying by 10^8
-        self.usd * 1e9
+impl fmt::Display for CacheMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} stale, {} misses, {} background refreshes ({} failed)",
+            self.hits, self.stale_hits, self.misses, self.refreshes, self.failed_refreshes
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum CacheLookup {
+    Fresh(Vec<TokenResponse>),
+    // Past the TTL but still usable; refresh is true for the one caller that should fetch
+    Stale { tokens: Vec<TokenResponse>, refresh: bool },
+    Miss,
+}
+
+// Last trending list and when it was fetched. Within the TTL it's served as is;
+// after that it's served stale while one background refresh runs, until it's
+// too old to serve at all.
+pub struct TrendingCache {
+    ttl: Duration,
+    fetched: Option<(DateTime<Utc>, Vec<TokenResponse>)>,
+    refreshing: bool,
+    metrics: CacheMetrics,
+}
+
+impl TrendingCache {
+    // A TTL of 0 turns caching off, though the last list is still kept for cached()
+    pub fn new(ttl_seconds: u64) -> Self {
+        TrendingCache {
+            ttl: Duration::seconds(ttl_seconds as i64),
+            fetched: None,
+            refreshing: false,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    pub fn lookup(&mut self, now: DateTime<Utc>) -> CacheLookup {
+        let age = match &self.fetched {
+            Some((fetched_at, _)) if self.ttl > Duration::zero() => now.signed_duration_since(*fetched_at),
+            _ => {
+                self.metrics.misses += 1;
+                return CacheLookup::Miss;
+            }
+        };
+        let tokens = self.fetched.as_ref().map(|(_, tokens)| tokens.clone()).unwrap_or_default();
+        if age <= self.ttl {
+            self.metrics.hits += 1;
+            CacheLookup::Fresh(tokens)
+        } else if age <= self.ttl * STALE_SERVE_TTLS {
+            self.metrics.stale_hits += 1;
+            let refresh = !self.refreshing;
+            self.refreshing = true;
+            CacheLookup::Stale { tokens, refresh }
+        } else {
+            self.metrics.misses += 1;
+            CacheLookup::Miss
+        }
+    }
+
+    pub fn store(&mut self, tokens: Vec<TokenResponse>, now: DateTime<Utc>) {
+        self.fetched = Some((now, tokens));
+    }
+
+    // Outcome of a background refresh started by a stale lookup
+    pub fn finish_refresh(&mut self, fetched: Option<Vec<TokenResponse>>, now: DateTime<Utc>) {
+        self.refreshing = false;
+        self.metrics.refreshes += 1;
+        match fetched {
+            Some(tokens) => self.store(tokens, now),
+            None => self.metrics.failed_refreshes += 1,
+        }
+    }
+
+    // Last list regardless of TTL, if fetched within max_age; for when a live fetch fails
+    pub fn cached(&self, max_age: Duration, now: DateTime<Utc>) -> Option<(DateTime<Utc>, &[TokenResponse])> {
+        let (fetched_at, tokens) = self.fetched.as_ref()?;
+        (now.signed_duration_since(*fetched_at) <= max_age && !tokens.is_empty()).then_some((*fetched_at, tokens.as_slice()))
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+}
+
+// Supply assumed when neither the API nor the chain told us; the pump.fun standard
+const ASSUMED_SUPPLY: f64 = 1e9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketCapFigure {
+    pub usd: f64,
+    // Price times the assumed supply rather than a real figure
+    pub estimated: bool,
+}
+
+impl MarketCapFigure {
+    pub fn from_price(price_usd: f64, supply: Option<f64>) -> Self {
+        match supply.filter(|supply| *supply > 0.0) {
+            Some(supply) => MarketCapFigure { usd: price_usd * supply, estimated: false },
+            None => MarketCapFigure { usd: price_usd * ASSUMED_SUPPLY, estimated: true },
+        }
+    }
+
+    pub fn display(&self) -> String {
+        if self.estimated {
+            format!("~{} (estimated)", SolanaTracker::format_currency(self.usd))
+        } else {
+            SolanaTracker::format_currency(self.usd)
+        }
+    }
+}
+
+// Raw base-unit amount (as RPC returns it) to whole tokens
+pub fn ui_amount(raw_amount: &str, decimals: u8) -> Option<f64> {
+    let raw: u128 = raw_amount.trim().parse().ok()?;
+    Some(raw as f64 / 10f64.powi(decimals as i32))
+}
+
+impl Pool {
+    // Reported market cap first, then price times known supply, then the 1B guess
+    pub fn market_cap(&self) -> MarketCapFigure {
+        if let Some(reported) = self.market_cap.as_ref().filter(|m| m.usd > 0.0) {
+            return MarketCapFigure { usd: reported.usd, estimated: false };
+        }
+        MarketCapFigure::from_price(self.price.usd, self.token_supply)
+    }
+}
+
+impl TokenResponse {
+    // Token lists are parsed one entry at a time so a single token in an
+    // unexpected shape is skipped instead of failing the whole response
+    pub fn parse_list(body: &str) -> Result<Vec<TokenResponse>> {
+        let entries: Vec<serde_json::Value> = serde_json::from_str(body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse response: {}", e))?;
+
+        let mut tokens = Vec::with_capacity(entries.len());
+        for (i, entry) in entries.into_iter().enumerate() {
+            let symbol = entry
+                .get("token")
+                .and_then(|t| t.get("symbol"))
+                .and_then(|s| s.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            match serde_json::from_value::<TokenResponse>(entry) {
+                Ok(token) => tokens.push(token),
+                Err(e) => println!("Skipping token {} (${}): {}", i, symbol, e),
+            }
+        }
+        Ok(tokens)
+    }
+
+    pub fn market_cap(&self) -> MarketCapFigure {
+        self.pools.first().map(Pool::market_cap).unwrap_or_default()
+    }
+
+    // Supply looked up elsewhere (e.g. RPC) for pools that didn't report one
+    pub fn set_supply(&mut self, supply: f64) {
+        for pool in &mut self.pools {
+            if pool.token_supply.is_none() {
+                pool.token_supply = Some(supply);
+            }
+        }
+    }
+
+    // The numbers target selection and the backtest work from
+    pub fn snapshot(&self) -> TokenSnapshot {
+        let pool = self.pools.first();
+        TokenSnapshot {
+            mint: self.token.mint.clone(),
+            symbol: self.token.symbol.clone(),
+            price_usd: pool.map(|p| p.price.usd).unwrap_or(0.0),
+            market_cap: self.market_cap().usd,
+            liquidity_usd: pool.map(|p| p.get_liquidity_usd()).unwrap_or(0.0),
+            change_1h: pool.and_then(|p| p.events.price_change_percentage_1h),
+            change_24h: pool.and_then(|p| p.events.price_change_percentage_24h),
+            buys: pool.map(|p| p.txns.buys).unwrap_or(0),
+            sells: pool.map(|p| p.txns.sells).unwrap_or(0),
+            age_hours: self.pools.iter().filter_map(|p| p.created_at).min().map(|created_at| {
+                (chrono::Utc::now().timestamp_millis() - created_at) as f64 / 3_600_000.0
+            }),
+        }
     }
 }
 
@@ -220,9 +531,37 @@ impl SolanaTracker {
         SolanaTracker {
             api_key: api_key.to_string(),
             client: reqwest::Client::new(),
+            fetch_client: public_client(std::time::Duration::from_secs(TOKEN_IMAGE_TIMEOUT_SECS)),
+            trending: Arc::new(Mutex::new(TrendingCache::new(DEFAULT_TRENDING_TTL_SECONDS))),
         }
     }
 
+    // A client set up with a proxy or user agent
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_trending_ttl(mut self, ttl_seconds: u64) -> Self {
+        self.trending = Arc::new(Mutex::new(TrendingCache::new(ttl_seconds)));
+        self
+    }
+
+    fn trending_cache(&self) -> std::sync::MutexGuard<'_, TrendingCache> {
+        self.trending.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn trending_cache_metrics(&self) -> CacheMetrics {
+        self.trending_cache().metrics()
+    }
+
+    // Top of the last trending list fetched within max_age, and when it was fetched
+    pub fn cached_top_tokens(&self, limit: usize, max_age: Duration) -> Option<(DateTime<Utc>, Vec<TokenResponse>)> {
+        let cache = self.trending_cache();
+        let (fetched_at, tokens) = cache.cached(max_age, Utc::now())?;
+        Some((fetched_at, tokens.iter().take(limit).cloned().collect()))
+    }
+
     pub async fn get_trending_tokens(&self, timeframe: &str) -> Result<Vec<TokenResponse>> {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -258,31 +597,40 @@ impl SolanaTracker {
         }
 
         let body = response.text().await?;
-        
-        // Try parsing token by token to identify problematic ones
-        match serde_json::from_str::<Vec<TokenResponse>>(&body) {
-            Ok(tokens) => Ok(tokens),
-            Err(e) => {
-                println!("Error parsing response: {}", e);
-                // Try parsing as Value first to debug
-                let v: serde_json::Value = serde_json::from_str(&body)?;
-                if let Some(array) = v.as_array() {
-                    for (i, token) in array.iter().enumerate() {
-                        println!("Token {}: Symbol: {}", i, 
-                            token.get("token")
-                                .and_then(|t| t.get("symbol"))
-                                .and_then(|s| s.as_str())
-                                .unwrap_or("unknown")
-                        );
-                    }
+        TokenResponse::parse_list(&body)
+    }
+
+    // Cached; the list only moves a little between cycles
+    pub async fn get_daily_trending(&self) -> Result<Vec<TokenResponse>> {
+        let lookup = self.trending_cache().lookup(Utc::now());
+        match lookup {
+            CacheLookup::Fresh(tokens) => Ok(tokens),
+            CacheLookup::Stale { tokens, refresh } => {
+                if refresh {
+                    let tracker = self.clone();
+                    tokio::spawn(async move { tracker.refresh_trending().await });
                 }
-                Err(anyhow::anyhow!("Failed to parse response: {}", e))
+                Ok(tokens)
+            }
+            CacheLookup::Miss => {
+                let tokens = self.get_trending_tokens("5m").await?;
+                self.trending_cache().store(tokens.clone(), Utc::now());
+                Ok(tokens)
             }
         }
     }
 
-    pub async fn get_daily_trending(&self) -> Result<Vec<TokenResponse>> {
-        self.get_trending_tokens("5m").await
+    async fn refresh_trending(&self) {
+        let fetched = match self.get_trending_tokens("5m").await {
+            Ok(tokens) => Some(tokens),
+            Err(e) => {
+                eprintln!("Background trending refresh failed: {}", e);
+                None
+            }
+        };
+        let mut cache = self.trending_cache();
+        cache.finish_refresh(fetched, Utc::now());
+        println!("Trending cache: {}", cache.metrics());
     }
 
     pub async fn get_token_by_address(&self, address: &str) -> Result<TokenResponse> {
@@ -333,6 +681,112 @@ impl SolanaTracker {
         }
     }
 
+    // Startup check that the API key is accepted; looks up wrapped SOL, which always exists
+    pub async fn check_api_key(&self) -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-API-Key",
+            HeaderValue::from_str(&self.api_key)?,
+        );
+
+        let response = self
+            .client
+            .get(format!("https://data.solanatracker.io/tokens/{}", WRAPPED_SOL_MINT))
+            .headers(headers)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("API key check failed with status: {}", status));
+        }
+        Ok(())
+    }
+
+    // Most recent trades for a token, newest first
+    pub async fn get_token_trades(&self, address: &str) -> Result<Vec<Trade>> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-API-Key",
+            HeaderValue::from_str(&self.api_key)?,
+        );
+
+        let url = format!(
+            "https://data.solanatracker.io/trades/{}",
+            address
+        );
+
+        println!("Making request to: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(headers)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            println!("Error response body: {}", error_text);
+            return Err(anyhow::anyhow!(
+                "API request failed with status: {}. Response: {}",
+                status,
+                error_text
+            ));
+        }
+
+        let body = response.text().await?;
+        let trades: TradesResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Failed to parse trades response: {}", e))?;
+        Ok(trades.trades)
+    }
+
+    // Download the token's logo, falling back to the image in its metadata uri. Both
+    // links come from the token's creator, so only public addresses are fetched.
+    pub async fn fetch_token_image(&self, token: &TokenInfo) -> Result<Vec<u8>> {
+        let image_url = match &token.image {
+            Some(image) if !image.is_empty() => image.clone(),
+            _ => {
+                let uri = token.uri.as_ref()
+                    .filter(|uri| !uri.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Token {} has no image or uri", token.symbol))?;
+                let response = self.fetch_client.get(public_url(uri)?).send().await?;
+                let body = Self::read_capped(response, MAX_TOKEN_METADATA_BYTES).await?;
+                let metadata: serde_json::Value = serde_json::from_slice(&body)?;
+                metadata.get("image")
+                    .and_then(|i| i.as_str())
+                    .map(|i| i.to_string())
+                    .ok_or_else(|| anyhow::anyhow!("No image in metadata for {}", token.symbol))?
+            }
+        };
+
+        println!("Fetching token image: {}", image_url);
+        let response = self.fetch_client.get(public_url(&image_url)?).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Image request failed with status: {}", response.status()));
+        }
+
+        Self::read_capped(response, MAX_TOKEN_IMAGE_BYTES).await
+    }
+
+    // The response body, or an error once it passes `max` bytes rather than reading on
+    pub(crate) async fn read_capped(mut response: reqwest::Response, max: usize) -> Result<Vec<u8>> {
+        let url = response.url().clone();
+        let too_big = || anyhow::anyhow!("{} is over {} bytes, not downloading it", url, max);
+        if response.content_length().is_some_and(|length| length > max as u64) {
+            return Err(too_big());
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if body.len() > max {
+                return Err(too_big());
+            }
+        }
+        Ok(body)
+    }
+
     pub fn find_token_by_symbol<'a>(tokens: &'a [TokenResponse], symbol: &str) -> Option<&'a TokenResponse> {
         // Get all tokens matching the symbol
         let matching_tokens: Vec<&TokenResponse> = tokens
@@ -502,10 +956,52 @@ impl SolanaTracker {
              Market Cap: {}\n\
              Liquidity: {}\n",
             token.token.symbol,
-            Self::format_currency(pool.price.calculate_market_cap()),
+            pool.market_cap().display(),
             Self::format_currency(pool.get_liquidity_usd()),
         )
     }
+    // One-line risk rundown used when answering contract address requests
+    pub fn format_risk_summary(token: &TokenResponse) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(risk) = &token.risk {
+            if risk.rugged {
+                parts.push("already rugged".to_string());
+            }
+            parts.push(format!("risk score {:.0}/10", risk.score));
+        }
+
+        if let Some(pool) = token.pools.first() {
+            let liquidity = pool.get_liquidity_usd();
+            parts.push(format!("mcap {}", pool.market_cap().display()));
+            parts.push(format!("liq {}", Self::format_currency(liquidity)));
+        }
+
+        if let Some(risk) = &token.risk {
+            // Danger-level risks first
+            let mut factors: Vec<&RiskFactor> = risk.risks
+                .iter()
+                .filter(|r| !r.name.is_empty())
+                .collect();
+            factors.sort_by_key(|r| r.level != "danger");
+
+            let flags: Vec<String> = factors
+                .into_iter()
+                .take(3)
+                .map(|r| r.name.to_lowercase())
+                .collect();
+            if !flags.is_empty() {
+                parts.push(format!("flags: {}", flags.join(", ")));
+            }
+        }
+
+        if parts.is_empty() {
+            "no data, which is somehow even worse".to_string()
+        } else {
+            parts.join(" | ")
+        }
+    }
+
     pub fn format_tokens_summary(&self, tokens: &[TokenResponse], limit: usize) -> String {
         let tokens = &tokens[..tokens.len().min(limit)];
         let mut summary = String::from("🚀💩 Worst Trending Shitcoins on Solana:\n\n");
@@ -527,7 +1023,7 @@ impl SolanaTracker {
                 };
 
                 // Market cap
-                let mcap = pool.price.calculate_market_cap();
+                let mcap = pool.market_cap().usd;
                 let mcap_str = if mcap > 0.0 {
                     if mcap >= 1_000_000_000.0 {
                         format!("${:.1}B", mcap / 1_000_000_000.0)
@@ -620,7 +1116,7 @@ impl SolanaTracker {
         let closing = fud_closings[rng.gen_range(0..fud_closings.len())];
 
         if let Some(pool) = token.pools.first() {
-            let mcap = pool.price.calculate_market_cap();
+            let mcap = pool.market_cap().usd;
             let mcap_str = if mcap > 0.0 {
                 if mcap >= 1_000_000_000.0 {
                     format!("${:.1}B", mcap / 1_000_000_000.0)
@@ -780,48 +1276,12 @@ impl SolanaTracker {
         )
     }
 
-    // This is a helper method to add emojis to the final response
-    fn add_emojis(response: String) -> String {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
-        let emoji_sets = [
-            "💀",
-            "🤡",
-            "🚮",
-            "🗑️",
-            "⚰️",
-            "🤮",
-            "🚨",
-            "⚠️",
-            "🤢",
-            "💩",
-        ];
-
-        // Add 1-2 random emojis
-        let num_emojis = rng.gen_range(1..=2);
-        let mut final_response = response;
-        
-        for _ in 0..num_emojis {
-            let emoji = emoji_sets[rng.gen_range(0..emoji_sets.len())];
-            if rng.gen_bool(0.5) {
-                final_response = format!("{} {}", emoji, final_response);
-            } else {
-                final_response = format!("{} {}", final_response, emoji);
-            }
-        }
-
-        final_response
-    }
-
     pub async fn generate_generic_fud_with_agent(&self, agent: &Agent) -> Result<String, anyhow::Error> {
         // Get random components
         let (intro, reason, closing) = self.get_fud_components();
         
         // Generate AI response using the components
-        let response = agent.generate_generic_fud(&intro, &reason, &closing).await?;
-        
-        // Add emojis to the final response
-        Ok(Self::add_emojis(response))
+        // Emojis, if the character wants any, come from its style policy
+        agent.generate_generic_fud(&intro, &reason, &closing).await
     }
 }
\ No newline at end of file
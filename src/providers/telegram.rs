@@ -1,13 +1,210 @@
-use teloxide::Bot;
+use anyhow::Result;
+use teloxide::prelude::*;
+use teloxide::types::{
+    AllowedUpdate, InlineQueryResult, InlineQueryResultArticle, InputFile, InputMessageContent, InputMessageContentText,
+    Recipient, UpdateKind,
+};
 
+use super::twitter::Twitter;
+use crate::media::media_kind::MediaKind;
+
+// A text message sent to the bot by one of its admins
+#[derive(Debug, Clone)]
+pub struct AdminMessage {
+    pub chat_id: ChatId,
+    pub user_id: u64,
+    pub text: String,
+}
+
+// Someone typing "@bot <query>" in any chat
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineRequest {
+    pub id: String,
+    pub user_id: u64,
+    pub query: String,
+}
+
+// One result offered for an inline query; picking it sends `text`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineResult {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+pub struct TelegramUpdates {
+    pub messages: Vec<AdminMessage>,
+    pub inline_queries: Vec<InlineRequest>,
+}
+
+#[derive(Clone)]
 pub struct Telegram {
     pub bot: Bot,
+    channel: Option<Recipient>,
+    admin_ids: Vec<u64>,
+    // Inline mode also has to be switched on for the bot with @BotFather's /setinline
+    inline: bool,
+    // Next update id to ask for, so each message is only seen once
+    update_offset: i32,
 }
 
 impl Telegram {
     pub fn new(token: &str) -> Self {
         Telegram {
             bot: Bot::new(token),
+            channel: None,
+            admin_ids: Vec::new(),
+            inline: false,
+            update_offset: 0,
+        }
+    }
+
+    pub fn set_admins(&mut self, admin_ids: Vec<u64>) {
+        self.admin_ids = admin_ids;
+    }
+
+    pub fn accepts_commands(&self) -> bool {
+        !self.admin_ids.is_empty()
+    }
+
+    pub fn set_inline(&mut self, enabled: bool) {
+        self.inline = enabled;
+    }
+
+    pub fn answers_inline(&self) -> bool {
+        self.inline
+    }
+
+    // Whether there's anything to ask Telegram for updates about
+    pub fn polls_updates(&self) -> bool {
+        self.accepts_commands() || self.inline
+    }
+
+    // Accepts either a numeric chat id (-100...) or a public @channelname
+    pub fn set_channel(&mut self, channel: &str) {
+        let channel = channel.trim();
+        if channel.is_empty() {
+            self.channel = None;
+            return;
         }
+        self.channel = Some(match channel.parse::<i64>() {
+            Ok(id) => Recipient::Id(ChatId(id)),
+            Err(_) if channel.starts_with('@') => Recipient::ChannelUsername(channel.to_string()),
+            Err(_) => Recipient::ChannelUsername(format!("@{}", channel)),
+        });
     }
-}
\ No newline at end of file
+
+    // Returns the bot's username if the token is valid
+    pub async fn verify(&self) -> Result<String> {
+        let me = self.bot.get_me().await?;
+        Ok(me.user.username.clone().unwrap_or_default())
+    }
+
+    pub fn has_channel(&self) -> bool {
+        self.channel.is_some()
+    }
+
+    // New messages and inline queries since the last poll, without waiting for more.
+    // Messages not sent by an admin are dropped.
+    pub async fn poll_updates(&mut self) -> Result<TelegramUpdates> {
+        let mut allowed = vec![AllowedUpdate::Message];
+        if self.inline {
+            allowed.push(AllowedUpdate::InlineQuery);
+        }
+        let updates = self
+            .bot
+            .get_updates()
+            .offset(self.update_offset)
+            .timeout(0)
+            .allowed_updates(allowed)
+            .await?;
+
+        let mut polled = TelegramUpdates::default();
+        for update in updates {
+            self.update_offset = update.id + 1;
+            let message = match update.kind {
+                UpdateKind::Message(message) => message,
+                UpdateKind::InlineQuery(query) if self.inline => {
+                    polled.inline_queries.push(InlineRequest {
+                        id: query.id,
+                        user_id: query.from.id.0,
+                        query: query.query,
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            let (Some(user), Some(text)) = (message.from(), message.text()) else {
+                continue;
+            };
+            if !self.admin_ids.contains(&user.id.0) {
+                println!("Ignoring Telegram message from non-admin user {}", user.id);
+                continue;
+            }
+            polled.messages.push(AdminMessage {
+                chat_id: message.chat.id,
+                user_id: user.id.0,
+                text: text.to_string(),
+            });
+        }
+        Ok(polled)
+    }
+
+    // Offer results for an inline query; Telegram keeps them for `cache_seconds`.
+    // No results shows the user nothing.
+    pub async fn answer_inline(&self, query_id: &str, results: &[InlineResult], cache_seconds: u32) -> Result<()> {
+        let results: Vec<InlineQueryResult> = results
+            .iter()
+            .map(|result| {
+                let content = InputMessageContent::Text(InputMessageContentText::new(result.text.clone()));
+                InlineQueryResult::Article(
+                    InlineQueryResultArticle::new(result.id.clone(), result.title.clone(), content)
+                        .description(result.description.clone()),
+                )
+            })
+            .collect();
+        self.bot.answer_inline_query(query_id, results).cache_time(cache_seconds).await?;
+        Ok(())
+    }
+
+    pub async fn reply(&self, chat_id: ChatId, text: &str) -> Result<()> {
+        self.bot.send_message(chat_id, text).await?;
+        Ok(())
+    }
+
+    // Message every admin directly; they need to have started a chat with the bot
+    pub async fn notify_admins(&self, text: &str) -> Result<()> {
+        for admin_id in &self.admin_ids {
+            self.bot.send_message(ChatId(*admin_id as i64), text).await?;
+        }
+        Ok(())
+    }
+
+    // Repost a tweet into the configured channel with a link back to it
+    pub async fn mirror_tweet(&self, text: &str, tweet_id: &str, image: Option<Vec<u8>>) -> Result<()> {
+        let Some(channel) = self.channel.clone() else {
+            return Ok(());
+        };
+        let message = format!("{}\n\n{}", text, Twitter::tweet_url(tweet_id));
+
+        match image {
+            Some(bytes) => match MediaKind::detect(&bytes) {
+                MediaKind::Image => {
+                    self.bot.send_photo(channel, InputFile::memory(bytes)).caption(message).await?;
+                }
+                MediaKind::Gif => {
+                    self.bot.send_animation(channel, InputFile::memory(bytes)).caption(message).await?;
+                }
+                MediaKind::Video => {
+                    self.bot.send_video(channel, InputFile::memory(bytes)).caption(message).await?;
+                }
+            },
+            None => {
+                self.bot.send_message(channel, message).await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// src/providers/tests/birdeye_tests.rs
+
+use super::super::birdeye::{parse_markets, parse_security, parse_trades, LiquiditySplit, TradeFlow};
+
+const SECURITY: &str = r#"{"success": true, "data": {
+  "creatorAddress": "DevWa11et1111111111111111111111111111111111",
+  "creatorBalance": 41000000,
+  "creatorPercentage": 0.041,
+  "top10HolderBalance": 623000000,
+  "top10HolderPercent": 0.623,
+  "freezeable": null,
+  "freezeAuthority": "FreezeAuth11111111111111111111111111111111",
+  "mutableMetadata": true,
+  "transferFeeEnable": false,
+  "nonTransferable": null
+}}"#;
+
+const CLEAN: &str = r#"{"success": true, "data": {
+  "creatorPercentage": 0,
+  "top10HolderPercent": 0.18,
+  "freezeable": false,
+  "freezeAuthority": null,
+  "mutableMetadata": false
+}}"#;
+
+const TRADES: &str = r#"{"success": true, "data": {"items": [
+  {"txHash": "a", "side": "sell", "owner": "W1", "blockUnixTime": 1700000900},
+  {"txHash": "b", "side": "sell", "owner": "W2", "blockUnixTime": 1700000600},
+  {"txHash": "c", "side": "buy", "owner": "W3", "blockUnixTime": 1700000300},
+  {"txHash": "d", "side": "sell", "owner": "W1", "blockUnixTime": 1700000000},
+  {"txHash": "e", "side": "", "owner": "W4", "blockUnixTime": 1700000000}
+], "hasNext": true}}"#;
+
+const MARKETS: &str = r#"{"success": true, "data": {"items": [
+  {"address": "P1", "name": "RUG-SOL", "source": "Raydium", "liquidity": 60000.0},
+  {"address": "P2", "name": "RUG-SOL", "source": "Meteora", "liquidity": 25000.0},
+  {"address": "P3", "name": "RUG-USDC", "source": "raydium", "liquidity": 15000.0},
+  {"address": "P4", "name": "RUG-SOL", "source": "Orca", "liquidity": null}
+], "total": 4}}"#;
+
+#[test]
+fn test_security_flags() {
+    let security = parse_security(SECURITY).unwrap();
+    assert!((security.top10_pct().unwrap() - 62.3).abs() < 1e-9);
+    assert!(security.can_freeze());
+
+    let summary = security.summary();
+    assert!(summary.contains("top 10 holders own 62.3% of supply (concentrated)"));
+    assert!(summary.contains("Creator wallet still holds 4.10%"));
+    assert!(summary.contains("freeze authority is still enabled"));
+    assert!(summary.contains("metadata is mutable"));
+    assert!(!summary.contains("transfer fee"));
+}
+
+#[test]
+fn test_clean_token_has_no_flags() {
+    let summary = parse_security(CLEAN).unwrap().summary();
+    assert!(summary.contains("top 10 holders own 18.0% of supply\n"));
+    assert!(summary.contains("Creator wallet holds none"));
+    assert!(!summary.contains("Security flag"));
+}
+
+#[test]
+fn test_failed_responses() {
+    assert!(parse_security(r#"{"success": false, "message": "Unauthorized"}"#).is_err());
+    assert!(parse_security("<html>").is_err());
+    assert!(parse_trades(r#"{"success": false}"#).is_err());
+}
+
+#[test]
+fn test_trade_flow() {
+    let flow = TradeFlow::from_trades(&parse_trades(TRADES).unwrap()).unwrap();
+    assert_eq!(flow, TradeFlow { buys: 1, sells: 3, wallets: 3, span_minutes: 15 });
+    assert_eq!(flow.summary(), "Last 4 trades over 15 minutes: 1 buys, 3 sells from 3 wallets, sellers in control\n");
+    assert!(TradeFlow::from_trades(&[]).is_none());
+}
+
+#[test]
+fn test_trade_flow_skips_unknown_sides() {
+    let trades = parse_trades(TRADES).unwrap();
+    let flow = TradeFlow::from_trades(&trades[4..]);
+    assert!(flow.is_none());
+}
+
+#[test]
+fn test_liquidity_split_by_provider() {
+    let split = LiquiditySplit::from_markets(&parse_markets(MARKETS).unwrap()).unwrap();
+    assert_eq!(split.pools, 3);
+    assert_eq!(split.providers, vec![("Raydium".to_string(), 75000.0), ("Meteora".to_string(), 25000.0)]);
+    assert_eq!(split.summary(), "Liquidity: $100000 across 3 pools (Raydium 75%, Meteora 25%)\n");
+    assert!(LiquiditySplit::from_markets(&[]).is_none());
+    assert!(parse_markets(r#"{"success": false}"#).is_err());
+}
@@ -0,0 +1,33 @@
+// src/providers/tests/coingecko_tests.rs
+
+use super::super::coingecko::Listing;
+
+const COIN: &str = r#"{
+  "id": "dogwifcoin",
+  "symbol": "wif",
+  "tickers": [
+    {"base": "WIF", "target": "USDT", "market": {"name": "Binance", "identifier": "binance", "has_trading_incentive": false}},
+    {"base": "WIF", "target": "USDC", "market": {"name": "Binance", "identifier": "binance", "has_trading_incentive": false}},
+    {"base": "EKPQ", "target": "SO11", "market": {"name": "Raydium", "identifier": "raydium", "has_trading_incentive": false}},
+    {"base": "WIF", "target": "USD", "market": {"name": "Coinbase Exchange", "identifier": "gdax", "has_trading_incentive": false}}
+  ]
+}"#;
+
+fn majors() -> Vec<String> {
+    vec!["binance".to_string(), "gdax".to_string(), "kraken".to_string()]
+}
+
+#[test]
+fn test_listed_on_majors() {
+    let listing = Listing::parse(COIN).unwrap();
+    assert_eq!(listing.exchanges, ["binance", "raydium", "gdax"]);
+    assert_eq!(listing.major_exchanges(&majors()), ["binance", "gdax"]);
+}
+
+#[test]
+fn test_dex_only_token() {
+    let listing = Listing::parse(r#"{"id": "rugz", "tickers": [{"market": {"identifier": "raydium"}}]}"#).unwrap();
+    assert!(listing.major_exchanges(&majors()).is_empty());
+    assert!(Listing::parse(r#"{"id": "rugz"}"#).unwrap().exchanges.is_empty());
+    assert!(Listing::parse("<html>").is_err());
+}
@@ -0,0 +1,49 @@
+// src/providers/tests/discord_tests.rs
+
+use super::super::discord::Discord;
+use crate::models::TokenSnapshot;
+
+fn token() -> TokenSnapshot {
+    TokenSnapshot {
+        mint: "mint1".to_string(),
+        symbol: "RUG".to_string(),
+        price_usd: 0.0001,
+        market_cap: 2_500_000.0,
+        liquidity_usd: 40_000.0,
+        change_24h: Some(-42.25),
+        age_hours: Some(5.0),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_webhook_urls() {
+    let urls = Discord::parse_webhook_urls(" https://a/1, https://b/2 ,,\nhttps://c/3 ");
+    assert_eq!(urls, vec!["https://a/1", "https://b/2", "https://c/3"]);
+    assert!(Discord::parse_webhook_urls("  ").is_empty());
+}
+
+#[test]
+fn test_fud_embed_fields() {
+    let embed = Discord::fud_embed(&token(), "dev already sold", Some("123"), Some("fud.png"));
+
+    assert_eq!(embed["title"], "$RUG");
+    assert_eq!(embed["description"], "dev already sold");
+    assert_eq!(embed["url"], "https://x.com/i/status/123");
+    assert_eq!(embed["image"]["url"], "attachment://fud.png");
+    assert_eq!(embed["footer"]["text"], "mint1");
+    assert_eq!(embed["fields"][0]["value"], "$2.5M");
+    assert_eq!(embed["fields"][1]["value"], "$40.0K");
+    assert_eq!(embed["fields"][2]["value"], "-42.2%");
+}
+
+#[test]
+fn test_fud_embed_without_extras() {
+    let mut token = token();
+    token.change_24h = None;
+    let embed = Discord::fud_embed(&token, "ngmi", None, None);
+
+    assert!(embed.get("url").is_none());
+    assert!(embed.get("image").is_none());
+    assert_eq!(embed["fields"][2]["value"], "n/a");
+}
@@ -0,0 +1,39 @@
+// src/providers/tests/geckoterminal_tests.rs
+
+use super::super::geckoterminal::{GeckoPool, GeckoToken};
+
+#[test]
+fn test_parse_string_numbers() {
+    let body = r#"{"data":{"id":"solana_abc","type":"token","attributes":{
+        "address":"abc","symbol":"RUG","price_usd":"0.00123","fdv_usd":"1230000.5",
+        "market_cap_usd":null,"total_supply":"1000000000000000"}}}"#;
+    let token = GeckoToken::parse(body).unwrap();
+
+    assert_eq!(token.price_usd, Some(0.00123));
+    assert_eq!(token.market_cap_usd, None);
+    // Falls back to FDV without a reported market cap
+    assert_eq!(token.market_cap(), Some(1230000.5));
+}
+
+#[test]
+fn test_parse_rejects_error_payload() {
+    assert!(GeckoToken::parse(r#"{"errors":[{"status":"404","title":"Not Found"}]}"#).is_err());
+}
+
+#[test]
+fn test_pool_token_is_the_side_that_isnt_sol_or_a_stablecoin() {
+    let pool = |base: &str, quote: &str| {
+        format!(
+            r#"{{"data":{{"id":"solana_pool","type":"pool","attributes":{{"name":"X / SOL"}},"relationships":{{
+                "base_token":{{"data":{{"id":"solana_{}","type":"token"}}}},
+                "quote_token":{{"data":{{"id":"solana_{}","type":"token"}}}},
+                "dex":{{"data":{{"id":"raydium","type":"dex"}}}}}}}}}}"#,
+            base, quote
+        )
+    };
+    let sol = "So11111111111111111111111111111111111111112";
+    let bonk = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+    assert_eq!(GeckoPool::token_mint(&pool(bonk, sol)).unwrap(), bonk);
+    assert_eq!(GeckoPool::token_mint(&pool(sol, bonk)).unwrap(), bonk);
+    assert!(GeckoPool::token_mint(r#"{"errors":[{"status":"404","title":"Not Found"}]}"#).is_err());
+}
@@ -1 +1,15 @@
 mod solanatracker_tests;
+mod birdeye_tests;
+mod coingecko_tests;
+mod discord_tests;
+mod geckoterminal_tests;
+mod metaplex_tests;
+mod network_stats_tests;
+mod news_tests;
+mod pumpfun_tests;
+mod screenshot_tests;
+mod solana_rpc_tests;
+mod solanatracker_fixture_tests;
+mod trending_cache_tests;
+mod twitter_tests;
+mod wallet_tests;
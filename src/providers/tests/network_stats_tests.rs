@@ -0,0 +1,81 @@
+// src/providers/tests/network_stats_tests.rs
+
+use super::super::network_stats::{parse_jito_tip_floor, NetworkStats};
+use super::super::solana_rpc::{parse_prioritization_fees, BlockOutcomes, PerformanceSample, VOTE_PROGRAM_ID};
+
+#[test]
+fn test_tps_from_performance_samples() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "result": [
+            { "numSlots": 150, "numTransactions": 240000, "numNonVoteTransactions": 60000, "samplePeriodSecs": 60, "slot": 348125 },
+            { "numSlots": 148, "numTransactions": 120000, "numNonVoteTransactions": 30000, "samplePeriodSecs": 60, "slot": 347975 }
+        ],
+        "id": 1
+    }"#;
+    let samples = PerformanceSample::parse_many(body).unwrap();
+    let (tps, non_vote) = NetworkStats::tps(&samples).unwrap();
+    assert_eq!(tps, 3000.0);
+    assert_eq!(non_vote, Some(750.0));
+    assert!(NetworkStats::tps(&[]).is_none());
+}
+
+#[test]
+fn test_priority_fee_percentiles() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "result": [
+            { "slot": 1, "prioritizationFee": 0 },
+            { "slot": 2, "prioritizationFee": 1000 },
+            { "slot": 3, "prioritizationFee": 500 },
+            { "slot": 4, "prioritizationFee": 250000 }
+        ],
+        "id": 1
+    }"#;
+    let fees = parse_prioritization_fees(body).unwrap();
+    assert_eq!(NetworkStats::percentile(&fees, 50.0), 500);
+    assert_eq!(NetworkStats::percentile(&fees, 90.0), 250000);
+    assert_eq!(NetworkStats::percentile(&[], 50.0), 0);
+}
+
+#[test]
+fn test_failed_rate_ignores_votes() {
+    let body = format!(
+        r#"{{
+        "jsonrpc": "2.0",
+        "result": {{
+            "blockHeight": 300,
+            "transactions": [
+                {{ "transaction": {{ "accountKeys": [{{ "pubkey": "Payer1" }}, {{ "pubkey": "{vote}" }}] }}, "meta": {{ "err": {{ "InstructionError": [0, "Custom"] }} }} }},
+                {{ "transaction": {{ "accountKeys": [{{ "pubkey": "Payer2" }}] }}, "meta": {{ "err": null }} }},
+                {{ "transaction": {{ "accountKeys": [{{ "pubkey": "Payer3" }}] }}, "meta": {{ "err": {{ "InstructionError": [1, "Custom"] }} }} }},
+                {{ "transaction": {{ "accountKeys": [{{ "pubkey": "Payer4" }}] }}, "meta": {{ "err": null }} }}
+            ]
+        }},
+        "id": 1
+    }}"#,
+        vote = VOTE_PROGRAM_ID
+    );
+    let outcomes = BlockOutcomes::parse(&body).unwrap();
+    assert_eq!(outcomes, BlockOutcomes { transactions: 3, failed: 1 });
+    let rate = NetworkStats::failed_rate(&outcomes).unwrap();
+    assert!((rate - 33.33).abs() < 0.01);
+}
+
+#[test]
+fn test_summary_lists_what_was_fetched() {
+    let tips = r#"[{ "time": "2024-05-01T00:00:00Z", "landed_tips_50th_percentile": 0.00001, "landed_tips_75th_percentile": 0.00005 }]"#;
+    let stats = NetworkStats {
+        tps: 3000.0,
+        non_vote_tps: Some(750.0),
+        median_priority_fee: 500,
+        p90_priority_fee: 250000,
+        failed_rate: None,
+        jito_tip: Some(parse_jito_tip_floor(tips).unwrap()),
+    };
+    let summary = stats.summary();
+    assert!(summary.contains("TPS: 3000 (750 excluding votes)"));
+    assert!(summary.contains("median 500 micro-lamports/CU, 90th percentile 250000"));
+    assert!(summary.contains("Jito tip: 0.000010 SOL"));
+    assert!(!summary.contains("Failed"));
+}
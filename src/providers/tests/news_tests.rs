@@ -0,0 +1,88 @@
+// src/providers/tests/news_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use super::super::news::{feed_source, parse_cryptopanic, parse_rss, pick_headline, Headline};
+
+const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"><channel>
+<title>Crypto News</title>
+<item>
+  <title><![CDATA[Solana memecoin volume hits record as pump.fun launches surge]]></title>
+  <link>https://example.com/solana-record</link>
+  <pubDate>Wed, 01 May 2024 10:00:00 +0000</pubDate>
+</item>
+<item>
+  <title>Bitcoin ETF flows &amp; what they mean</title>
+  <link>https://example.com/btc-etf</link>
+  <pubDate>Wed, 01 May 2024 11:00:00 GMT</pubDate>
+</item>
+<item>
+  <title>Item without a link</title>
+</item>
+</channel></rss>"#;
+
+const CRYPTOPANIC: &str = r#"{"count": 1, "results": [
+  {"kind": "news", "title": "BONK burns 1T tokens", "url": "https://cryptopanic.com/news/1/bonk",
+   "published_at": "2024-05-01T12:30:00Z", "source": {"title": "The Block", "domain": "theblock.co"}},
+  {"kind": "news", "title": "No source on this one", "url": "https://cryptopanic.com/news/2/x"}
+]}"#;
+
+fn keywords() -> Vec<String> {
+    vec!["solana".to_string(), "bonk".to_string(), "meme coin".to_string()]
+}
+
+#[test]
+fn test_parse_rss_items() {
+    let headlines = parse_rss(RSS, "example.com");
+    assert_eq!(headlines.len(), 2);
+    assert_eq!(headlines[0].title, "Solana memecoin volume hits record as pump.fun launches surge");
+    assert_eq!(headlines[0].published_at, Some(Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap()));
+    assert_eq!(headlines[1].title, "Bitcoin ETF flows & what they mean");
+    assert_eq!(headlines[1].url, "https://example.com/btc-etf");
+}
+
+#[test]
+fn test_parse_cryptopanic_posts() {
+    let headlines = parse_cryptopanic(CRYPTOPANIC).unwrap();
+    assert_eq!(headlines.len(), 2);
+    assert_eq!(headlines[0].source, "The Block");
+    assert_eq!(headlines[1].source, "CryptoPanic");
+    assert!(headlines[1].published_at.is_none());
+    assert!(parse_cryptopanic("<html>").is_err());
+}
+
+#[test]
+fn test_keywords_match_whole_words() {
+    let headline = |title: &str| Headline {
+        title: title.to_string(),
+        url: String::new(),
+        source: String::new(),
+        published_at: None,
+    };
+    assert!(headline("Why SOLANA is down").mentions_any(&keywords()));
+    assert!(headline("New meme coin season?").mentions_any(&keywords()));
+    assert!(!headline("Solanart marketplace shuts down").mentions_any(&keywords()));
+    assert!(!headline("Bonkers week for ETH").mentions_any(&keywords()));
+}
+
+#[test]
+fn test_pick_newest_unseen_relevant_headline() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap();
+    let mut headlines = parse_rss(RSS, "example.com");
+    headlines.extend(parse_cryptopanic(CRYPTOPANIC).unwrap());
+
+    let picked = pick_headline(headlines.clone(), &keywords(), Duration::hours(12), now, |_| false).unwrap();
+    assert_eq!(picked.title, "BONK burns 1T tokens");
+
+    let seen = |h: &Headline| h.url.contains("bonk");
+    let picked = pick_headline(headlines.clone(), &keywords(), Duration::hours(12), now, seen).unwrap();
+    assert_eq!(picked.url, "https://example.com/solana-record");
+
+    assert!(pick_headline(headlines, &keywords(), Duration::hours(1), now, seen).is_none());
+}
+
+#[test]
+fn test_feed_source_is_host() {
+    assert_eq!(feed_source("https://www.coindesk.com/arc/outboundfeeds/rss/"), "coindesk.com");
+    assert_eq!(feed_source("https://decrypt.co/feed"), "decrypt.co");
+}
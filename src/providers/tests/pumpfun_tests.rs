@@ -0,0 +1,91 @@
+// src/providers/tests/pumpfun_tests.rs
+
+use chrono::{Duration, TimeZone, Utc};
+
+use super::super::pumpfun::{parse_coin, parse_trades, BondingStatus, PumpFun, PumpTrade, TRADES_TO_SCAN};
+
+const CREATOR: &str = "DevWa11et1111111111111111111111111111111111";
+
+fn coin(complete: bool, reserves: f64, king_of_the_hill: Option<i64>) -> String {
+    serde_json::json!({
+        "mint": "7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hrpump",
+        "creator": CREATOR,
+        "complete": complete,
+        "real_token_reserves": reserves,
+        "total_supply": 1_000_000_000_000_000u64,
+        "usd_market_cap": 8123.4,
+        "king_of_the_hill_timestamp": king_of_the_hill,
+    })
+    .to_string()
+}
+
+fn trades() -> String {
+    // Newest first, as the API returns them
+    serde_json::json!([
+        { "user": "Buyer2", "is_buy": true, "token_amount": 5_000_000_000_000u64, "timestamp": 1_700_000_300 },
+        { "user": CREATOR, "is_buy": true, "token_amount": 90_000_000_000_000u64, "timestamp": 1_700_000_200 },
+        { "user": "Buyer1", "is_buy": true, "token_amount": 10_000_000_000_000u64, "timestamp": 1_700_000_100 },
+        { "user": CREATOR, "is_buy": true, "token_amount": 30_000_000_000_000u64, "timestamp": 1_700_000_000 },
+    ])
+    .to_string()
+}
+
+#[test]
+fn test_progress_and_dev_buy_of_pre_bond_token() {
+    let coin = parse_coin(&coin(false, 396_550_000_000_000.0, None)).unwrap();
+    let status = BondingStatus::from_coin(&coin, &parse_trades(&trades()).unwrap());
+
+    assert!(status.is_pre_bond());
+    assert!((status.progress - 50.0).abs() < 0.01);
+    // Only the creator's buys before anyone else traded count
+    assert!((status.dev_buy_pct.unwrap() - 3.0).abs() < 0.001);
+
+    let summary = status.summary(Utc::now());
+    assert!(summary.contains("still on the bonding curve, 50%"));
+    assert!(summary.contains("Dev bought 3.0% of supply"));
+    assert!(summary.contains("Never made king of the hill"));
+}
+
+#[test]
+fn test_no_dev_buy_when_launch_is_past_the_window() {
+    let mut coin = parse_coin(&coin(false, 396_550_000_000_000.0, None)).unwrap();
+    let launched = 1_700_000_000;
+    // A busy coin: the fetched trades are all from an hour after launch
+    let trades: Vec<PumpTrade> = (0..TRADES_TO_SCAN as i64)
+        .map(|i| PumpTrade {
+            user: CREATOR.to_string(),
+            is_buy: true,
+            token_amount: 1_000_000_000_000.0,
+            timestamp: launched + 3_600 + i,
+        })
+        .collect();
+    assert!(BondingStatus::from_coin(&coin, &trades).dev_buy_pct.is_none());
+    coin.created_timestamp = Some(launched * 1000);
+    assert!(BondingStatus::from_coin(&coin, &trades).dev_buy_pct.is_none());
+
+    coin.created_timestamp = Some((launched + 3_590) * 1000);
+    assert!(BondingStatus::from_coin(&coin, &trades).dev_buy_pct.is_some());
+}
+
+#[test]
+fn test_graduated_token() {
+    let crowned = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let coin = parse_coin(&coin(true, 0.0, Some(crowned.timestamp_millis()))).unwrap();
+    let status = BondingStatus::from_coin(&coin, &[]);
+
+    assert!(!status.is_pre_bond());
+    assert_eq!(status.progress, 100.0);
+    assert_eq!(status.king_of_the_hill_at, Some(crowned));
+    // No trades, so no dev buy line rather than a made-up 0%
+    assert!(status.dev_buy_pct.is_none());
+
+    let summary = status.summary(crowned + Duration::hours(5));
+    assert!(summary.contains("graduated from the bonding curve"));
+    assert!(summary.contains("king of the hill 5 hours ago"));
+}
+
+#[test]
+fn test_only_pump_mints_are_looked_up() {
+    assert!(PumpFun::is_pump_token("7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hrpump"));
+    assert!(!PumpFun::is_pump_token("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"));
+}
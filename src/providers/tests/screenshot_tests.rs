@@ -0,0 +1,30 @@
+// src/providers/tests/screenshot_tests.rs
+
+use super::super::screenshot::ScreenshotApi;
+use crate::core::config::{ScreenshotConfig, Secret};
+
+#[test]
+fn test_request_url_encodes_website() {
+    let api = ScreenshotApi::new(ScreenshotConfig {
+        api_key: Secret::new("shot-key"),
+        api_url: "https://shots.example/take".to_string(),
+    });
+    let url = api.request_url("https://rug.fun/?ref=ct&lang=en").unwrap();
+
+    assert_eq!(url.host_str(), Some("shots.example"));
+    let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    assert!(params.contains(&("access_key".to_string(), "shot-key".to_string())));
+    assert!(params.contains(&("url".to_string(), "https://rug.fun/?ref=ct&lang=en".to_string())));
+    assert!(params.contains(&("format".to_string(), "jpg".to_string())));
+}
+
+#[tokio::test]
+async fn test_capture_errors_leave_out_the_key() {
+    // Nothing listens on port 1, so the request fails before any response
+    let api = ScreenshotApi::new(ScreenshotConfig {
+        api_key: Secret::new("shot-key"),
+        api_url: "http://127.0.0.1:1/take".to_string(),
+    });
+    let error = api.capture("https://rug.fun").await.unwrap_err();
+    assert!(!format!("{:#}", error).contains("shot-key"));
+}
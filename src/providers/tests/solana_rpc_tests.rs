@@ -0,0 +1,169 @@
+// src/providers/tests/solana_rpc_tests.rs
+
+use super::super::solana_rpc::{parse_balance_changes, parse_signature, parse_simulated_lamports, AccountData, AccountKind, RpcRejected, SignatureInfo, TokenSupply};
+
+#[test]
+fn test_parse_token_supply() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 1114 },
+            "value": {
+                "amount": "999999876543210",
+                "decimals": 6,
+                "uiAmount": 999999876.54321,
+                "uiAmountString": "999999876.54321"
+            }
+        },
+        "id": 1
+    }"#;
+    let supply = TokenSupply::parse(body).unwrap();
+    assert_eq!(supply.decimals, 6);
+    assert!((supply.ui_amount().unwrap() - 999_999_876.543_21).abs() < 1e-3);
+}
+
+#[test]
+fn test_parse_token_supply_error() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "error": { "code": -32602, "message": "Invalid param: not a Token mint" },
+        "id": 1
+    }"#;
+    let err = TokenSupply::parse(body).unwrap_err();
+    assert!(err.to_string().contains("not a Token mint"));
+}
+
+#[test]
+fn test_parse_multiple_accounts() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 1114 },
+            "value": [
+                null,
+                {
+                    "data": ["AQID", "base64"],
+                    "executable": false,
+                    "lamports": 5616720,
+                    "owner": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+                    "rentEpoch": 18446744073709551615
+                }
+            ]
+        },
+        "id": 1
+    }"#;
+    let accounts = AccountData::parse_many(body).unwrap();
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0], None);
+    let account = accounts[1].as_ref().unwrap();
+    assert_eq!(account.data, vec![1, 2, 3]);
+    assert_eq!(account.owner, "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+#[test]
+fn test_parse_account_kind() {
+    let mint = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 1114 },
+            "value": {
+                "data": {
+                    "parsed": {
+                        "info": { "decimals": 5, "isInitialized": true, "supply": "88000000000000000" },
+                        "type": "mint"
+                    },
+                    "program": "spl-token",
+                    "space": 82
+                },
+                "executable": false,
+                "lamports": 1461600,
+                "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWU5tfPtwnd6VEFXnVgs8",
+                "rentEpoch": 18446744073709551615
+            }
+        },
+        "id": 1
+    }"#;
+    assert_eq!(AccountKind::parse(mint).unwrap(), AccountKind::Mint);
+
+    let wallet = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "context": { "slot": 1114 },
+            "value": {
+                "data": ["", "base64"],
+                "executable": false,
+                "lamports": 88849814690250,
+                "owner": "11111111111111111111111111111111",
+                "rentEpoch": 18446744073709551615
+            }
+        },
+        "id": 1
+    }"#;
+    assert_eq!(AccountKind::parse(wallet).unwrap(), AccountKind::Other);
+
+    let missing = r#"{"jsonrpc": "2.0", "result": {"context": {"slot": 1114}, "value": null}, "id": 1}"#;
+    assert_eq!(AccountKind::parse(missing).unwrap(), AccountKind::Missing);
+}
+
+#[test]
+fn test_parse_signatures() {
+    let body = r#"{
+        "jsonrpc": "2.0",
+        "result": [
+            { "signature": "5h6x", "slot": 114, "err": null, "memo": null, "blockTime": 1700000100 },
+            { "signature": "3kGp", "slot": 113, "err": { "InstructionError": [0, "Custom"] }, "memo": null, "blockTime": null }
+        ],
+        "id": 1
+    }"#;
+    let signatures = SignatureInfo::parse_many(body).unwrap();
+    assert_eq!(signatures.len(), 2);
+    assert_eq!((signatures[0].signature.as_str(), signatures[0].slot, signatures[0].failed), ("5h6x", 114, false));
+    assert!(signatures[1].failed);
+}
+
+#[test]
+fn test_parse_balance_changes() {
+    let mint = "7GCihgDB8fe6KNjn2MYtkzZcRjQy3t9GHdC8uHYmW2hrpump";
+    let balance = |index: u8, mint: &str, owner: &str, amount: &str| {
+        format!(
+            r#"{{ "accountIndex": {}, "mint": "{}", "owner": "{}", "uiTokenAmount": {{ "amount": "0", "decimals": 6, "uiAmount": null, "uiAmountString": "{}" }} }}"#,
+            index, mint, owner, amount
+        )
+    };
+    let body = format!(
+        r#"{{ "jsonrpc": "2.0", "id": 1, "result": {{ "slot": 100, "meta": {{ "err": null,
+            "preTokenBalances": [{}, {}],
+            "postTokenBalances": [{}, {}, {}] }} }} }}"#,
+        balance(1, mint, "Curve", "793100000"),
+        balance(3, "So11111111111111111111111111111111111111112", "Buyer", "2"),
+        balance(1, mint, "Curve", "759100000"),
+        balance(2, mint, "Buyer", "34000000"),
+        balance(3, "So11111111111111111111111111111111111111112", "Buyer", "1"),
+    );
+    let changes = parse_balance_changes(&body, mint).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].owner, "Curve");
+    assert!((changes[0].amount + 34_000_000.0).abs() < 1e-6);
+    assert_eq!(changes[1].owner, "Buyer");
+    assert!((changes[1].amount - 34_000_000.0).abs() < 1e-6);
+
+    // A transaction the node doesn't have
+    assert!(parse_balance_changes(r#"{"jsonrpc": "2.0", "result": null, "id": 1}"#, mint).unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_simulated_lamports() {
+    let body = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":null,"logs":[],"accounts":[{"lamports":7000000,"owner":"11111111111111111111111111111111","data":["","base64"]}]}}}"#;
+    assert_eq!(parse_simulated_lamports(body).unwrap(), 7_000_000);
+    let closed = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":null,"accounts":[null]}}}"#;
+    assert_eq!(parse_simulated_lamports(closed).unwrap(), 0);
+    let failed = r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":{"err":{"InstructionError":[2,{"Custom":6001}]},"accounts":null}}}"#;
+    assert!(parse_simulated_lamports(failed).is_err());
+}
+
+#[test]
+fn test_rejected_sends_are_told_apart() {
+    let rejected = parse_signature(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32002,"message":"Transaction simulation failed"}}"#).unwrap_err();
+    assert!(RpcRejected::is(&rejected));
+    assert!(!RpcRejected::is(&anyhow::anyhow!("operation timed out")));
+}
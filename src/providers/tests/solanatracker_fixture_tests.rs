@@ -0,0 +1,101 @@
+// src/providers/tests/solanatracker_fixture_tests.rs
+//
+// Deserialization of trimmed SolanaTracker responses kept in fixtures/solanatracker.
+// When the API changes shape, add the new payload there and a test here.
+
+use super::super::solanatracker::{SearchResponse, TokenResponse, TradesResponse};
+
+const TRENDING: &str = include_str!("fixtures/solanatracker/trending.json");
+const TRENDING_NULL_FIELDS: &str = include_str!("fixtures/solanatracker/trending_null_fields.json");
+const TRENDING_MALFORMED_TOKEN: &str = include_str!("fixtures/solanatracker/trending_malformed_token.json");
+const TOKEN: &str = include_str!("fixtures/solanatracker/token.json");
+const SEARCH: &str = include_str!("fixtures/solanatracker/search.json");
+const TRADES: &str = include_str!("fixtures/solanatracker/trades.json");
+
+#[test]
+fn test_trending_fixture() {
+    let tokens = TokenResponse::parse_list(TRENDING).unwrap();
+    assert_eq!(tokens.len(), 2);
+
+    let wif = &tokens[0];
+    assert_eq!(wif.token.symbol, "WIF");
+    let pool = &wif.pools[0];
+    assert_eq!(pool.liquidity.usd, 14533209.36);
+    assert_eq!(pool.txns.buys, 61234);
+    assert_eq!(pool.created_at, Some(1701269812000));
+    assert_eq!(pool.events.price_change_percentage_24h, Some(-6.41));
+    assert!(!wif.market_cap().estimated);
+    assert_eq!(wif.holders, Some(181211));
+
+    let rodeo = &tokens[1];
+    assert_eq!(rodeo.holders, None);
+    let risk = rodeo.risk.as_ref().unwrap();
+    assert_eq!(risk.risks[0].level, "danger");
+    assert_eq!(rodeo.market_cap().usd, 52100.0);
+}
+
+#[test]
+fn test_trending_fixture_with_null_fields() {
+    let tokens = TokenResponse::parse_list(TRENDING_NULL_FIELDS).unwrap();
+    assert_eq!(tokens.len(), 2, "Nulls and missing fields should fall back to defaults");
+
+    let nullcat = &tokens[0];
+    assert_eq!(nullcat.token.name, "");
+    let pool = &nullcat.pools[0];
+    assert_eq!(pool.liquidity.usd, 0.0);
+    assert_eq!(pool.txns.buys, 0);
+    assert_eq!(pool.txns.sells, 12);
+    assert_eq!(pool.events.price_change_percentage_1h, None);
+    assert_eq!(pool.events.price_change_percentage_24h, Some(212.5));
+    assert_eq!(nullcat.risk.as_ref().unwrap().risks[0].level, "");
+    // A null market cap and supply leave only the estimate
+    assert!(nullcat.market_cap().estimated);
+
+    let bare = &tokens[1];
+    assert_eq!(bare.token.symbol, "BARE");
+    assert!(bare.pools.is_empty());
+    assert!(bare.risk.is_none());
+}
+
+#[test]
+fn test_trending_fixture_skips_malformed_tokens() {
+    let tokens = TokenResponse::parse_list(TRENDING_MALFORMED_TOKEN).unwrap();
+    let symbols: Vec<&str> = tokens.iter().map(|t| t.token.symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["FINE", "FINE2"]);
+}
+
+#[test]
+fn test_parse_list_rejects_non_array() {
+    assert!(TokenResponse::parse_list(r#"{"error": "Invalid API key"}"#).is_err());
+    assert!(TokenResponse::parse_list("<html>502 Bad Gateway</html>").is_err());
+}
+
+#[test]
+fn test_token_fixture() {
+    let token: TokenResponse = serde_json::from_str(TOKEN).unwrap();
+    assert_eq!(token.token.mint, "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263");
+    assert_eq!(token.pools[0].token_supply, Some(88849204153742.9));
+    assert_eq!(token.market_cap().usd, 1883603128.06);
+}
+
+#[test]
+fn test_search_fixture() {
+    let response: SearchResponse = serde_json::from_str(SEARCH).unwrap();
+    assert_eq!(response.status, "success");
+    let tokens: Vec<TokenResponse> = response.data.into_iter().map(TokenResponse::from).collect();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].pools[0].liquidity.usd, 9812.44);
+    assert_eq!(tokens[0].market_cap().usd, 52100.0);
+    assert_eq!(tokens[1].token.symbol, "HALF");
+    assert_eq!(tokens[1].pools[0].liquidity.usd, 0.0);
+}
+
+#[test]
+fn test_trades_fixture() {
+    let response: TradesResponse = serde_json::from_str(TRADES).unwrap();
+    assert_eq!(response.trades.len(), 2);
+    assert!(response.trades[0].is_sell());
+    assert_eq!(response.trades[0].volume, 79.19);
+    assert_eq!(response.trades[1].amount, 0.0);
+    assert!(!response.trades[1].is_sell());
+}
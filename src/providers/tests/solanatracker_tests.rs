@@ -1,6 +1,11 @@
 // src/providers/tests/solanatracker_tests.rs
 
-use super::super::solanatracker::{SolanaTracker, TokenResponse, TokenInfo, Pool, Liquidity};
+use super::super::solanatracker::{
+    ui_amount, Liquidity, MarketCap, MarketCapFigure, Pool, Price, SolanaTracker, TokenInfo,
+    TokenResponse, MAX_TOKEN_IMAGE_BYTES,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 #[test]
 fn test_find_token_by_symbol() {
@@ -12,6 +17,7 @@ fn test_find_token_by_symbol() {
                 name: "Test Token 1".to_string(),
                 mint: "mint1".to_string(),
                 uri: None,
+                image: None,
                 description: None,
             },
             pools: vec![Pool {
@@ -22,7 +28,13 @@ fn test_find_token_by_symbol() {
                 },
                 price: Default::default(),
                 events: Default::default(),
-            }]
+                txns: Default::default(),
+                market_cap: None,
+                token_supply: None,
+                created_at: None,
+            }],
+            risk: None,
+            holders: None,
         },
         TokenResponse {
             token: TokenInfo { 
@@ -30,6 +42,7 @@ fn test_find_token_by_symbol() {
                 name: "Test Token 2".to_string(),
                 mint: "mint2".to_string(),
                 uri: None,
+                image: None,
                 description: None,
             },
             pools: vec![Pool {
@@ -40,7 +53,13 @@ fn test_find_token_by_symbol() {
                 },
                 price: Default::default(),
                 events: Default::default(),
-            }]
+                txns: Default::default(),
+                market_cap: None,
+                token_supply: None,
+                created_at: None,
+            }],
+            risk: None,
+            holders: None,
         },
     ];
 
@@ -72,12 +91,144 @@ fn test_find_token_empty_pools() {
                 name: "Test Token".to_string(),
                 mint: "mint1".to_string(),
                 uri: None,
+                image: None,
                 description: None,
             },
-            pools: vec![] // Empty pools
+            pools: vec![], // Empty pools
+            risk: None,
+            holders: None,
         },
     ];
 
     let result = SolanaTracker::find_token_by_symbol(&tokens, "TEST");
     assert!(result.is_some(), "Should find token even with empty pools");
-}
\ No newline at end of file
+}
+
+fn priced_pool(price_usd: f64) -> Pool {
+    Pool {
+        liquidity: Liquidity::default(),
+        price: Price { quote: 0.0, usd: price_usd },
+        events: Default::default(),
+        txns: Default::default(),
+        market_cap: None,
+        token_supply: None,
+        created_at: None,
+    }
+}
+
+#[test]
+fn test_ui_amount_respects_decimals() {
+    // 1B tokens at 6 decimals and at 9 decimals
+    assert_eq!(ui_amount("1000000000000000", 6), Some(1e9));
+    assert_eq!(ui_amount("1000000000000000000", 9), Some(1e9));
+    // Same raw amount, very different supplies
+    assert_eq!(ui_amount("1000000000000000", 9), Some(1e6));
+    assert_eq!(ui_amount("not a number", 6), None);
+}
+
+#[test]
+fn test_market_cap_uses_real_supply() {
+    let mut six = priced_pool(0.002);
+    six.token_supply = ui_amount("500000000000000", 6); // 500M tokens
+    let figure = six.market_cap();
+    assert!((figure.usd - 1_000_000.0).abs() < 1e-6);
+    assert!(!figure.estimated);
+
+    let mut nine = priced_pool(0.002);
+    nine.token_supply = ui_amount("500000000000000", 9); // 500K tokens
+    let figure = nine.market_cap();
+    assert!((figure.usd - 1_000.0).abs() < 1e-6);
+    assert!(!figure.estimated);
+}
+
+#[test]
+fn test_market_cap_fallback_order() {
+    let mut pool = priced_pool(0.001);
+    let estimated = pool.market_cap();
+    assert!(estimated.estimated, "No supply means the 1B guess");
+    assert!((estimated.usd - 1_000_000.0).abs() < 1e-6);
+    assert!(estimated.display().contains("(estimated)"));
+
+    pool.token_supply = Some(2e9);
+    assert!((pool.market_cap().usd - 2_000_000.0).abs() < 1e-6);
+
+    // A reported figure beats our own arithmetic
+    pool.market_cap = Some(MarketCap { quote: 0.0, usd: 3_000_000.0 });
+    assert_eq!(pool.market_cap(), MarketCapFigure { usd: 3_000_000.0, estimated: false });
+}
+
+#[test]
+fn test_set_supply_keeps_reported_supply() {
+    let mut reported = priced_pool(0.001);
+    reported.token_supply = Some(5e8);
+    let mut token = TokenResponse {
+        token: TokenInfo {
+            symbol: "SUP".to_string(),
+            name: "Supply Token".to_string(),
+            mint: "mint3".to_string(),
+            uri: None,
+            image: None,
+            description: None,
+        },
+        pools: vec![priced_pool(0.001), reported],
+        risk: None,
+        holders: None,
+    };
+    token.set_supply(1e9);
+    assert_eq!(token.pools[0].token_supply, Some(1e9));
+    assert_eq!(token.pools[1].token_supply, Some(5e8));
+    assert!(!token.market_cap().estimated);
+}
+
+// Serves one response with a body of `size` bytes, with or without a Content-Length
+async fn serve_image(size: usize, content_length: bool) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 1024];
+        let _ = stream.read(&mut request).await;
+        let length = if content_length { format!("Content-Length: {}\r\n", size) } else { String::new() };
+        let head = format!("HTTP/1.1 200 OK\r\nContent-Type: image/png\r\n{}Connection: close\r\n\r\n", length);
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(&vec![0u8; size]).await;
+    });
+    format!("http://{}/logo.png", address)
+}
+
+fn image_token(image: String) -> TokenInfo {
+    TokenInfo {
+        symbol: "PIC".to_string(),
+        name: "Picture Token".to_string(),
+        mint: "mint4".to_string(),
+        uri: None,
+        image: Some(image),
+        description: None,
+    }
+}
+
+async fn read_image(url: String) -> anyhow::Result<Vec<u8>> {
+    SolanaTracker::read_capped(reqwest::get(url).await.unwrap(), MAX_TOKEN_IMAGE_BYTES).await
+}
+
+#[tokio::test]
+async fn test_token_image_download_is_capped() {
+    assert_eq!(read_image(serve_image(1024, true).await).await.unwrap().len(), 1024);
+    assert!(read_image(serve_image(MAX_TOKEN_IMAGE_BYTES + 1, true).await).await.is_err());
+    // A body without a Content-Length is cut off once it passes the cap
+    assert!(read_image(serve_image(MAX_TOKEN_IMAGE_BYTES + 1, false).await).await.is_err());
+}
+
+#[tokio::test]
+async fn test_fetch_token_image_refuses_private_addresses() {
+    let tracker = SolanaTracker::new("key");
+    let local = serve_image(1024, true).await;
+    let error = tracker.fetch_token_image(&image_token(local)).await.unwrap_err();
+    assert!(error.to_string().contains("private address"));
+
+    let mut token = image_token(String::new());
+    token.uri = Some("http://169.254.169.254/latest/meta-data/".to_string());
+    assert!(tracker.fetch_token_image(&token).await.is_err());
+    token.uri = Some("file:///etc/passwd".to_string());
+    assert!(tracker.fetch_token_image(&token).await.is_err());
+}
@@ -0,0 +1,56 @@
+// src/providers/tests/trending_cache_tests.rs
+
+use chrono::{Duration, Utc};
+use super::super::solanatracker::{CacheLookup, CacheMetrics, TokenResponse, TrendingCache};
+
+const TRENDING: &str = include_str!("fixtures/solanatracker/trending.json");
+
+fn tokens() -> Vec<TokenResponse> {
+    TokenResponse::parse_list(TRENDING).unwrap()
+}
+
+#[test]
+fn test_fresh_stale_and_expired_lookups() {
+    let mut cache = TrendingCache::new(180);
+    let fetched_at = Utc::now();
+    assert!(matches!(cache.lookup(fetched_at), CacheLookup::Miss));
+    cache.store(tokens(), fetched_at);
+
+    assert!(matches!(cache.lookup(fetched_at + Duration::seconds(60)), CacheLookup::Fresh(t) if t.len() == tokens().len()));
+    // Only the first stale caller kicks off a refresh
+    assert!(matches!(cache.lookup(fetched_at + Duration::minutes(4)), CacheLookup::Stale { refresh: true, .. }));
+    assert!(matches!(cache.lookup(fetched_at + Duration::minutes(5)), CacheLookup::Stale { refresh: false, .. }));
+    assert!(matches!(cache.lookup(fetched_at + Duration::minutes(10)), CacheLookup::Miss));
+
+    assert_eq!(cache.metrics(), CacheMetrics { hits: 1, stale_hits: 2, misses: 2, ..Default::default() });
+}
+
+#[test]
+fn test_background_refresh_outcomes() {
+    let mut cache = TrendingCache::new(60);
+    let fetched_at = Utc::now();
+    cache.store(tokens(), fetched_at);
+
+    let stale_at = fetched_at + Duration::seconds(90);
+    assert!(matches!(cache.lookup(stale_at), CacheLookup::Stale { refresh: true, .. }));
+    cache.finish_refresh(None, stale_at);
+    // A failed refresh leaves the old list and lets the next caller try again
+    assert!(matches!(cache.lookup(stale_at), CacheLookup::Stale { refresh: true, .. }));
+    cache.finish_refresh(Some(tokens()), stale_at);
+    assert!(matches!(cache.lookup(stale_at), CacheLookup::Fresh(_)));
+
+    let metrics = cache.metrics();
+    assert_eq!((metrics.refreshes, metrics.failed_refreshes), (2, 1));
+}
+
+#[test]
+fn test_zero_ttl_still_keeps_last_list() {
+    let mut cache = TrendingCache::new(0);
+    let fetched_at = Utc::now();
+    cache.store(tokens(), fetched_at);
+    assert!(matches!(cache.lookup(fetched_at), CacheLookup::Miss));
+
+    let (at, cached) = cache.cached(Duration::minutes(60), fetched_at + Duration::minutes(30)).unwrap();
+    assert_eq!((at, cached.len()), (fetched_at, tokens().len()));
+    assert!(cache.cached(Duration::minutes(60), fetched_at + Duration::minutes(61)).is_none());
+}
@@ -0,0 +1,211 @@
+// src/providers/tests/twitter_tests.rs
+
+use super::super::twitter::{ProcessingInfo, ReferencedPost, ScheduledSpace, Twitter};
+use chrono::{Duration, TimeZone, Utc};
+use crate::error::FudError;
+
+fn api_error(status: u16) -> twitter_v2::Error {
+    let body = format!(r#"{{ "title": "Twitter said no", "type": "about:blank", "status": {} }}"#, status);
+    twitter_v2::Error::Api(serde_json::from_str(&body).unwrap())
+}
+
+#[test]
+fn test_rate_limits_are_their_own_kind() {
+    let error = Twitter::api_error(api_error(429));
+    assert!(error.is_rate_limited());
+    assert!(matches!(error, FudError::RateLimited { provider: "Twitter" }));
+}
+
+#[test]
+fn test_rate_limits_go_by_status_not_message() {
+    let body = r#"{ "title": "Too Many Requests", "type": "about:blank", "status": 429 }"#;
+    assert!(Twitter::api_error(twitter_v2::Error::Api(serde_json::from_str(body).unwrap())).is_rate_limited());
+    let body = r#"{ "title": "Internal Error", "type": "about:blank", "status": 500, "detail": "upstream returned 429" }"#;
+    assert!(!Twitter::api_error(twitter_v2::Error::Api(serde_json::from_str(body).unwrap())).is_rate_limited());
+}
+
+#[test]
+fn test_other_api_errors_keep_their_source() {
+    let error = Twitter::api_error(api_error(403));
+    assert!(!error.is_rate_limited());
+    let FudError::Provider { provider, source } = &error else {
+        panic!("expected a provider error, got {:?}", error);
+    };
+    assert_eq!(*provider, "Twitter");
+    assert!(source.downcast_ref::<twitter_v2::Error>().is_some());
+}
+
+#[test]
+fn test_replies_to_deleted_or_protected_tweets_are_unrepliable() {
+    let body = r#"{ "title": "Forbidden", "type": "about:blank", "status": 403,
+        "detail": "You attempted to reply to a Tweet that is deleted or not visible to you." }"#;
+    let error = Twitter::reply_error(twitter_v2::Error::Api(serde_json::from_str(body).unwrap()), 1790000000000000000);
+    let FudError::Unrepliable { tweet_id, reason } = &error else {
+        panic!("expected an unrepliable error, got {:?}", error);
+    };
+    assert_eq!(tweet_id, "1790000000000000000");
+    assert!(reason.contains("deleted"));
+
+    assert!(Twitter::is_unrepliable(
+        403,
+        "Reply to this conversation is not allowed because you have not been mentioned or otherwise engaged by the author of the post you are replying to."
+    ));
+    // Other refusals, and rate limits, are still retried
+    assert!(!Twitter::is_unrepliable(403, "You are not permitted to perform this action."));
+    assert!(!Twitter::is_unrepliable(429, "Too Many Requests"));
+    assert!(Twitter::reply_error(api_error(429), 1).is_rate_limited());
+    assert!(!Twitter::reply_error(api_error(403), 1).is_unrepliable());
+}
+
+#[test]
+fn test_kind_survives_a_trip_through_anyhow() {
+    let wrapped = anyhow::Error::new(FudError::RateLimited { provider: "Twitter" });
+    assert!(FudError::from(wrapped).is_rate_limited());
+
+    let with_context = anyhow::Error::new(FudError::NotFound("agent".to_string())).context("handling notifications");
+    assert!(matches!(FudError::find(&with_context), Some(FudError::NotFound(_))));
+    assert!(matches!(FudError::from(anyhow::anyhow!("boom")), FudError::Other(_)));
+}
+
+#[test]
+fn test_attached_images_follow_media_keys() {
+    let tweets: Vec<twitter_v2::Tweet> = serde_json::from_str(
+        r#"[
+            { "id": "1", "text": "chart looks bullish", "attachments": { "media_keys": ["3_1", "7_2"] } },
+            { "id": "2", "text": "no picture here" },
+            { "id": "3", "text": "missing media", "attachments": { "media_keys": ["3_9"] } }
+        ]"#,
+    )
+    .unwrap();
+    let media: Vec<twitter_v2::data::Media> = serde_json::from_str(
+        r#"[
+            { "media_key": "3_1", "type": "photo", "url": "https://pbs.twimg.com/media/chart.png" },
+            { "media_key": "7_2", "type": "video", "preview_image_url": "https://pbs.twimg.com/media/still.jpg" }
+        ]"#,
+    )
+    .unwrap();
+
+    let images = Twitter::attached_images(&tweets, &media);
+    assert_eq!(
+        images.get("1"),
+        Some(&vec![
+            "https://pbs.twimg.com/media/chart.png".to_string(),
+            "https://pbs.twimg.com/media/still.jpg".to_string(),
+        ])
+    );
+    assert_eq!(images.len(), 1);
+}
+
+#[test]
+fn test_referenced_posts_pick_up_quotes_and_parents() {
+    let tweets: Vec<twitter_v2::Tweet> = serde_json::from_str(
+        r#"[
+            { "id": "10", "text": "@chainfud thoughts?", "referenced_tweets": [{ "type": "quoted", "id": "1" }] },
+            { "id": "11", "text": "@chainfud this one", "referenced_tweets": [{ "type": "replied_to", "id": "2" }] },
+            { "id": "12", "text": "@chainfud gone", "referenced_tweets": [{ "type": "quoted", "id": "99" }] }
+        ]"#,
+    )
+    .unwrap();
+    let included: Vec<twitter_v2::Tweet> = serde_json::from_str(
+        r#"[
+            { "id": "1", "text": "$WIF to 10b\nnot financial advice" },
+            { "id": "2", "text": "just aped my rent into $BONK" }
+        ]"#,
+    )
+    .unwrap();
+
+    let referenced = Twitter::referenced_posts(&tweets, &included);
+    assert_eq!(referenced.len(), 2);
+    assert_eq!(
+        ReferencedPost::prompt_context(&referenced["10"]),
+        "They're quoting this post: '$WIF to 10b not financial advice'"
+    );
+    assert_eq!(
+        ReferencedPost::prompt_context(&referenced["11"]),
+        "They're replying under this post: 'just aped my rent into $BONK'"
+    );
+}
+
+#[test]
+fn test_scheduled_spaces_resolve_hosts_and_skip_untitled() {
+    let spaces: Vec<twitter_v2::data::Space> = serde_json::from_str(
+        r#"[
+            { "id": "1OdKrjXkZLjGX", "state": "scheduled", "title": "Solana memecoin alpha hour", "host_ids": ["7", "8"],
+              "scheduled_start": "2026-10-17T18:00:00Z", "subscriber_count": 412 },
+            { "id": "1YqKDqkQkwVGV", "state": "scheduled", "title": "   " },
+            { "id": "1vOxwjkLBqZJB", "state": "live", "title": "already talking" }
+        ]"#,
+    )
+    .unwrap();
+    let users: Vec<twitter_v2::User> = serde_json::from_str(
+        r#"[{ "id": "7", "name": "Degen", "username": "degenhost" }]"#,
+    )
+    .unwrap();
+
+    let scheduled = Twitter::scheduled_spaces(&spaces, &users);
+    assert_eq!(scheduled.len(), 1);
+    let space = &scheduled[0];
+    assert_eq!(space.hosts, vec!["degenhost".to_string()]);
+    assert_eq!(space.scheduled_start, Some(Utc.with_ymd_and_hms(2026, 10, 17, 18, 0, 0).unwrap()));
+    assert_eq!(space.url(), "https://x.com/i/spaces/1OdKrjXkZLjGX");
+    assert_eq!(
+        space.prompt_line(),
+        "\"Solana memecoin alpha hour\" hosted by @degenhost, starting 2026-10-17 18:00 UTC, 412 people set reminders"
+    );
+}
+
+#[test]
+fn test_space_pick_prefers_the_biggest_within_lookahead() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+    let space = |id: &str, hours: Option<i64>, subscribers: usize| ScheduledSpace {
+        id: id.to_string(),
+        title: format!("space {}", id),
+        hosts: Vec::new(),
+        scheduled_start: hours.map(|hours| now + Duration::hours(hours)),
+        subscriber_count: Some(subscribers),
+    };
+    let spaces = vec![
+        space("small", Some(2), 10),
+        space("big", Some(5), 500),
+        space("too_far", Some(48), 9000),
+        space("undated", None, 9000),
+        space("done", Some(1), 1000),
+    ];
+
+    let picked = ScheduledSpace::pick(spaces.clone(), Duration::hours(24), now, |space| space.id == "done");
+    assert_eq!(picked.map(|space| space.id), Some("big".to_string()));
+    let picked = ScheduledSpace::pick(spaces, Duration::hours(24), now, |space| space.id != "small");
+    assert_eq!(picked.map(|space| space.id), Some("small".to_string()));
+}
+
+#[test]
+fn test_community_ids_only_for_community_posts() {
+    let body = r#"{
+        "data": [
+            { "id": "100", "text": "gm", "community_id": "1600000000000000000" },
+            { "id": "101", "text": "no community" }
+        ]
+    }"#;
+    let ids = Twitter::parse_community_ids(body).unwrap();
+    assert_eq!(ids.len(), 1);
+    assert_eq!(ids["100"], "1600000000000000000");
+
+    assert!(Twitter::parse_community_ids(r#"{ "errors": [] }"#).unwrap().is_empty());
+    assert!(Twitter::parse_community_ids("not json").is_err());
+}
+
+#[test]
+fn test_processing_info_says_when_to_check_again() {
+    let pending: ProcessingInfo = serde_json::from_str(r#"{ "state": "pending", "check_after_secs": 5 }"#).unwrap();
+    assert_eq!(pending.next_check().unwrap(), Some(std::time::Duration::from_secs(5)));
+    let in_progress: ProcessingInfo = serde_json::from_str(r#"{ "state": "in_progress" }"#).unwrap();
+    assert_eq!(in_progress.next_check().unwrap(), Some(std::time::Duration::from_secs(1)));
+    let done: ProcessingInfo = serde_json::from_str(r#"{ "state": "succeeded" }"#).unwrap();
+    assert_eq!(done.next_check().unwrap(), None);
+
+    let failed: ProcessingInfo =
+        serde_json::from_str(r#"{ "state": "failed", "error": { "code": 1, "name": "InvalidMedia", "message": "Unsupported video format" } }"#)
+            .unwrap();
+    let error = failed.next_check().unwrap_err();
+    assert!(error.to_string().contains("Unsupported video format"), "{}", error);
+}
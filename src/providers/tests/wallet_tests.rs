@@ -0,0 +1,101 @@
+// src/providers/tests/wallet_tests.rs
+
+use ring::signature::{UnparsedPublicKey, ED25519};
+use super::super::wallet::{check_outflow, check_quote, compact_u16, memo_message, Keypair};
+
+const BLOCKHASH: &str = "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N";
+
+fn keypair() -> Keypair {
+    Keypair::parse(&bs58::encode([7u8; 32]).into_string()).unwrap()
+}
+
+#[test]
+fn test_compact_u16() {
+    assert_eq!(compact_u16(0), vec![0]);
+    assert_eq!(compact_u16(0x7f), vec![0x7f]);
+    assert_eq!(compact_u16(0x80), vec![0x80, 0x01]);
+    assert_eq!(compact_u16(0x3fff), vec![0xff, 0x7f]);
+    assert_eq!(compact_u16(0x4000), vec![0x80, 0x80, 0x01]);
+}
+
+#[test]
+fn test_parses_wallet_key_formats() {
+    let from_seed = keypair();
+
+    let mut full = vec![7u8; 32];
+    full.extend(from_seed.public_key());
+    let from_base58 = Keypair::parse(&bs58::encode(&full).into_string()).unwrap();
+    let from_json = Keypair::parse(&serde_json::to_string(&full).unwrap()).unwrap();
+    assert_eq!(from_base58.address(), from_seed.address());
+    assert_eq!(from_json.address(), from_seed.address());
+
+    // Public half that doesn't match the seed
+    let mut mismatched = vec![7u8; 32];
+    mismatched.extend([9u8; 32]);
+    assert!(Keypair::parse(&bs58::encode(&mismatched).into_string()).is_err());
+    assert!(Keypair::parse("not base58 0OIl").is_err());
+    assert!(Keypair::parse(&bs58::encode([1u8; 16]).into_string()).is_err());
+}
+
+#[test]
+fn test_memo_transaction_is_signed_by_payer() {
+    let keypair = keypair();
+    let message = memo_message(&keypair.public_key(), BLOCKHASH, "fud receipt: $RUG").unwrap();
+    assert_eq!(&message[..4], &[1, 0, 1, 2]);
+    assert_eq!(&message[4..36], &keypair.public_key());
+    assert!(message.ends_with(b"fud receipt: $RUG"));
+
+    let transaction = keypair.sign_message(&message);
+    assert_eq!(transaction[0], 1);
+    let public_key = UnparsedPublicKey::new(&ED25519, keypair.public_key());
+    assert!(public_key.verify(&message, &transaction[1..65]).is_ok());
+    assert_eq!(&transaction[65..], &message[..]);
+}
+
+#[test]
+fn test_signs_only_transactions_paid_by_wallet() {
+    let keypair = keypair();
+    let message = memo_message(&keypair.public_key(), BLOCKHASH, "gm").unwrap();
+
+    // As returned by an API: v0 prefix, signature slot left empty
+    let mut versioned = vec![0x80];
+    versioned.extend(&message);
+    let mut unsigned = compact_u16(1);
+    unsigned.extend([0u8; 64]);
+    unsigned.extend(&versioned);
+
+    let signed = keypair.sign_transaction(&unsigned).unwrap();
+    let public_key = UnparsedPublicKey::new(&ED25519, keypair.public_key());
+    assert!(public_key.verify(&versioned, &signed[1..65]).is_ok());
+
+    let other = Keypair::parse(&bs58::encode([8u8; 32]).into_string()).unwrap();
+    assert!(other.sign_transaction(&unsigned).is_err());
+    assert!(keypair.sign_transaction(&[1, 0, 0]).is_err());
+}
+
+#[test]
+fn test_quote_must_spend_at_most_the_buy() {
+    let quote = |mint: &str, amount: &str| serde_json::json!({ "inputMint": mint, "inAmount": amount });
+    let sol = "So11111111111111111111111111111111111111112";
+    assert!(check_quote(&quote(sol, "100000"), 100_000).is_ok());
+    assert!(check_quote(&quote(sol, "100001"), 100_000).is_err());
+    assert!(check_quote(&quote("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "1"), 100_000).is_err());
+    assert!(check_quote(&serde_json::json!({ "inputMint": sol }), 100_000).is_err());
+}
+
+#[test]
+fn test_simulated_outflow_is_capped() {
+    assert_eq!(check_outflow(10_000_000, 7_000_000, 3_000_000).unwrap(), 3_000_000);
+    assert!(check_outflow(10_000_000, 6_999_999, 3_000_000).is_err());
+    // Receiving lamports is never over the cap
+    assert_eq!(check_outflow(1, 5, 0).unwrap(), 0);
+}
+
+#[test]
+fn test_signature_is_the_fee_payers() {
+    let keypair = keypair();
+    let message = memo_message(&keypair.public_key(), BLOCKHASH, "gm").unwrap();
+    let transaction = keypair.sign_message(&message);
+    assert_eq!(Keypair::signature_of(&transaction).unwrap(), bs58::encode(&transaction[1..65]).into_string());
+    assert!(Keypair::signature_of(&[0]).is_err());
+}
@@ -1,10 +1,207 @@
-use twitter_v2::{authorization::Oauth1aToken, TwitterApi, id::IntoNumericId};
+use twitter_v2::{authorization::Oauth1aToken, TwitterApi, id::IntoNumericId, prelude::PaginableApiResponse};
+use twitter_v2::data::{Media, MediaType, ReferencedTweetKind, Space, SpaceState, User};
+use twitter_v2::query::{Exclude, MediaField, SpaceExpansion, SpaceField, SpaceStateQuery, TweetExpansion, TweetField, UserField};
 use reqwest::multipart;
 use serde::Deserialize;
 use reqwest_oauth1::OAuthClientProvider;
+use chrono::{DateTime, Utc};
+use crate::core::tweet_preview::TweetPreview;
+use crate::error::FudError;
+use crate::media::media_kind::MediaKind;
+use crate::models::{MentionAuthor, TweetMetrics};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+const PROVIDER: &str = "Twitter";
+const UPLOAD_URL: &str = "https://upload.twitter.com/1.1/media/upload.json";
+// Chunks for GIF and video uploads; Twitter takes up to 5MB each
+const UPLOAD_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+// STATUS checks before a video still processing is given up on
+const MAX_PROCESSING_CHECKS: u32 = 20;
+// What Twitter says when the tweet being replied to is gone, protected, or only
+// takes replies from people it mentions
+const UNREPLIABLE_DETAILS: [&str; 3] = [
+    "deleted or not visible",
+    "reply to this conversation is not allowed",
+    "protected",
+];
+
+// Endpoint names in the quota ledger
+pub const ENDPOINT_TWEETS: &str = "tweets";
+pub const ENDPOINT_MENTIONS: &str = "mentions";
+pub const ENDPOINT_SEARCH: &str = "search";
+pub const ENDPOINT_TWEET_LOOKUP: &str = "tweet_lookup";
+pub const ENDPOINT_USERS: &str = "users";
+pub const ENDPOINT_BLOCKS: &str = "blocks";
+pub const ENDPOINT_ME: &str = "me";
+pub const ENDPOINT_MEDIA_UPLOAD: &str = "media_upload";
+pub const ENDPOINT_SPACES: &str = "spaces";
+pub const ENDPOINT_COMMUNITIES: &str = "communities";
+pub const ENDPOINT_TIMELINE: &str = "timeline";
+pub const ENDPOINT_FOLLOWERS: &str = "followers";
+pub const ENDPOINTS: [&str; 12] = [
+    ENDPOINT_TWEETS,
+    ENDPOINT_MENTIONS,
+    ENDPOINT_SEARCH,
+    ENDPOINT_TWEET_LOOKUP,
+    ENDPOINT_USERS,
+    ENDPOINT_BLOCKS,
+    ENDPOINT_ME,
+    ENDPOINT_MEDIA_UPLOAD,
+    ENDPOINT_SPACES,
+    ENDPOINT_COMMUNITIES,
+    ENDPOINT_TIMELINE,
+    ENDPOINT_FOLLOWERS,
+];
+
+// Mentions along with the images attached to each and the posts they quote or
+// reply to, keyed by tweet ID
+#[derive(Debug, Default)]
+pub struct Mentions {
+    pub tweets: Vec<twitter_v2::Tweet>,
+    pub images: HashMap<String, Vec<String>>,
+    pub referenced: HashMap<String, Vec<ReferencedPost>>,
+}
+
+// A quoted or parent post a mention was made under
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferencedPost {
+    pub kind: ReferencedTweetKind,
+    pub text: String,
+}
+
+impl ReferencedPost {
+    // Lines for a reply prompt saying what the mention was made under
+    pub fn prompt_context(posts: &[ReferencedPost]) -> String {
+        posts
+            .iter()
+            .map(|post| {
+                let text = post.text.split_whitespace().collect::<Vec<_>>().join(" ");
+                match post.kind {
+                    ReferencedTweetKind::Quoted => format!("They're quoting this post: '{}'", text),
+                    _ => format!("They're replying under this post: '{}'", text),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// One of our own tweets as the timeline reports it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelinePost {
+    pub id: String,
+    pub text: String,
+    pub created_at: Option<DateTime<Utc>>,
+    // Tweet this replies to, for replies
+    pub reply_to: Option<String>,
+}
+
+// A Space that hasn't started yet, as found by search
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledSpace {
+    pub id: String,
+    pub title: String,
+    // Usernames of the hosts, where the expansion returned them
+    pub hosts: Vec<String>,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub subscriber_count: Option<usize>,
+}
+
+impl ScheduledSpace {
+    pub fn url(&self) -> String {
+        format!("https://x.com/i/spaces/{}", self.id)
+    }
+
+    pub fn prompt_line(&self) -> String {
+        let mut line = format!("\"{}\"", self.title);
+        if !self.hosts.is_empty() {
+            let hosts: Vec<String> = self.hosts.iter().map(|host| format!("@{}", host)).collect();
+            line.push_str(&format!(" hosted by {}", hosts.join(", ")));
+        }
+        if let Some(start) = self.scheduled_start {
+            line.push_str(&format!(", starting {}", start.format("%Y-%m-%d %H:%M UTC")));
+        }
+        if let Some(count) = self.subscriber_count {
+            line.push_str(&format!(", {} people set reminders", count));
+        }
+        line
+    }
+
+    // The Space most people are waiting for among those starting within the lookahead
+    // that haven't been commented on; ones without a start time can't be placed, so they're skipped
+    pub fn pick(
+        spaces: Vec<ScheduledSpace>,
+        lookahead: chrono::Duration,
+        now: DateTime<Utc>,
+        seen: impl Fn(&ScheduledSpace) -> bool,
+    ) -> Option<ScheduledSpace> {
+        spaces
+            .into_iter()
+            .filter(|space| space.scheduled_start.is_some_and(|start| start > now && start <= now + lookahead))
+            .filter(|space| !seen(space))
+            .max_by_key(|space| space.subscriber_count.unwrap_or(0))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct MediaUploadResponse {
     media_id: u64,
+    #[serde(default)]
+    processing_info: Option<ProcessingInfo>,
+}
+
+// Where Twitter is with a chunked upload after FINALIZE
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ProcessingInfo {
+    pub state: String,
+    #[serde(default)]
+    pub check_after_secs: Option<u64>,
+    #[serde(default)]
+    pub error: Option<ProcessingError>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ProcessingError {
+    #[serde(default)]
+    pub message: String,
+}
+
+impl ProcessingInfo {
+    // How long to wait before asking again, None once the media is ready
+    pub fn next_check(&self) -> Result<Option<std::time::Duration>, FudError> {
+        match self.state.as_str() {
+            "succeeded" => Ok(None),
+            "failed" => {
+                let message = self.error.as_ref().map(|e| e.message.as_str()).unwrap_or("no reason given");
+                Err(FudError::provider(PROVIDER, anyhow::anyhow!("Media processing failed: {}", message)))
+            }
+            _ => Ok(Some(std::time::Duration::from_secs(self.check_after_secs.unwrap_or(1).max(1)))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunityTweets {
+    #[serde(default)]
+    data: Vec<CommunityTweet>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunityTweet {
+    id: String,
+    #[serde(default)]
+    community_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommunityLookup {
+    data: Community,
+}
+
+#[derive(Debug, Deserialize)]
+struct Community {
+    name: String,
 }
 pub struct Twitter {
     auth: Oauth1aToken,
@@ -12,9 +209,18 @@ pub struct Twitter {
     twitter_consumer_secret: String,
     twitter_access_token: String,
     twitter_access_token_secret: String,
+    // Requests made since the runtime last moved them into the quota ledger
+    calls: Mutex<BTreeMap<&'static str, u64>>,
+    // For the requests we sign ourselves; twitter_v2 builds its own client
+    http: reqwest::Client,
 }
 
 impl Twitter {
+    // Works without knowing the account's handle
+    pub fn tweet_url(tweet_id: &str) -> String {
+        format!("https://x.com/i/status/{}", tweet_id)
+    }
+
     pub fn new(
         twitter_consumer_key: &str,
         twitter_consumer_secret: &str,
@@ -33,29 +239,85 @@ impl Twitter {
             twitter_consumer_secret: twitter_consumer_secret.to_string(),
             twitter_access_token: twitter_access_token.to_string(),
             twitter_access_token_secret: twitter_access_token_secret.to_string(),
+            calls: Mutex::new(BTreeMap::new()),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    // A client set up with a proxy or user agent, for media uploads and community lookups
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.http = client;
+        self
+    }
+
+    // 429s become RateLimited so callers can back off; anything else keeps the API error
+    pub(crate) fn api_error(error: twitter_v2::Error) -> FudError {
+        let rate_limited = match &error {
+            twitter_v2::Error::Api(api) => api.status.as_u16() == 429,
+            twitter_v2::Error::Request(e) => e.status().is_some_and(|status| status.as_u16() == 429),
+            _ => false,
+        };
+        if rate_limited {
+            FudError::RateLimited { provider: PROVIDER }
+        } else {
+            FudError::provider(PROVIDER, error)
+        }
+    }
+
+    // Refusals that won't change on a retry because of the tweet being replied to
+    pub fn is_unrepliable(status: u16, detail: &str) -> bool {
+        let detail = detail.to_ascii_lowercase();
+        matches!(status, 400 | 403 | 404) && UNREPLIABLE_DETAILS.iter().any(|phrase| detail.contains(phrase))
+    }
+
+    pub(crate) fn reply_error(error: twitter_v2::Error, tweet_id: u64) -> FudError {
+        if let twitter_v2::Error::Api(api) = &error {
+            if Self::is_unrepliable(api.status.as_u16(), &api.detail) {
+                return FudError::Unrepliable {
+                    tweet_id: tweet_id.to_string(),
+                    reason: api.detail.clone(),
+                };
+            }
         }
+        Self::api_error(error)
     }
 
-    pub async fn tweet_with_image(&self, text: String, media_id: u64, user_id: impl IntoNumericId) -> Result<(), anyhow::Error> {
+    // Counted before sending; failed requests can still count against the plan
+    fn count(&self, endpoint: &'static str) {
+        let mut calls = self.calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *calls.entry(endpoint).or_insert(0) += 1;
+    }
+
+    pub fn take_calls(&self) -> BTreeMap<&'static str, u64> {
+        std::mem::take(&mut *self.calls.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    pub async fn tweet_with_image(&self, text: String, media_id: u64, user_id: impl IntoNumericId) -> Result<twitter_v2::Tweet, FudError> {
+        TweetPreview::check(&text)?;
+        self.count(ENDPOINT_TWEETS);
         let tweet = TwitterApi::new(self.auth.clone())
             .post_tweet()
             .add_media([media_id], [user_id])
             .text(text)
             .send()
-            .await?
+            .await
+            .map_err(Self::api_error)?
             .into_data()
             .expect("this tweet should exist");
         println!("Tweet posted successfully with ID: {}", tweet.id);
 
-        Ok(())
+        Ok(tweet)
     }
 
-    pub async fn tweet(&self, text: String) -> Result<twitter_v2::Tweet, anyhow::Error> {
+    pub async fn tweet(&self, text: String) -> Result<twitter_v2::Tweet, FudError> {
+        TweetPreview::check(&text)?;
+        self.count(ENDPOINT_TWEETS);
         let tweet = TwitterApi::new(self.auth.clone())
             .post_tweet()
             .text(text)
             .send()
-            .await?
+            .await
+            .map_err(Self::api_error)?
             .into_data()
             .expect("this tweet should exist");
         println!("Tweet posted successfully with ID: {}", tweet.id);
@@ -63,70 +325,470 @@ impl Twitter {
         Ok(tweet)
     }
 
-    pub async fn reply_to_tweet(&self, tweet_id: &str, text: String) -> Result<(), anyhow::Error> {
-        let tweet_id = tweet_id.parse::<u64>()?;
+    pub async fn reply_to_tweet(&self, tweet_id: &str, text: String) -> Result<twitter_v2::Tweet, FudError> {
+        let tweet_id = tweet_id.parse::<u64>().map_err(|e| FudError::parse("tweet id", e))?;
+        TweetPreview::check(&text)?;
+        self.count(ENDPOINT_TWEETS);
         let tweet = TwitterApi::new(self.auth.clone())
             .post_tweet()
             .in_reply_to_tweet_id(tweet_id)
             .text(text)
             .send()
-            .await?
+            .await
+            .map_err(|e| Self::reply_error(e, tweet_id))?
             .into_data()
             .expect("this tweet should exist");
         println!("Reply posted successfully with ID: {}", tweet.id);
 
-        Ok(())
+        Ok(tweet)
     }
     
-    pub async fn get_notifications(&self, user_id: impl IntoNumericId) -> Result<Vec<twitter_v2::Tweet>, anyhow::Error> {
+    pub async fn get_notifications(&self, user_id: impl IntoNumericId) -> Result<Mentions, FudError> {
+        self.count(ENDPOINT_MENTIONS);
         let api = TwitterApi::new(self.auth.clone());
-        let mentions = api
+        let response = api
             .get_user_mentions(user_id)
+            .tweet_fields([
+                TweetField::AuthorId,
+                TweetField::ConversationId,
+                TweetField::ReferencedTweets,
+                TweetField::Attachments,
+            ])
+            .expansions([TweetExpansion::AttachmentsMediaKeys, TweetExpansion::ReferencedTweetsId])
+            .media_fields([MediaField::Type, MediaField::Url, MediaField::PreviewImageUrl])
             .send()
-            .await?
+            .await
+            .map_err(Self::api_error)?;
+        let includes = response.includes();
+        let media = includes.and_then(|includes| includes.media.clone()).unwrap_or_default();
+        let included_tweets = includes.and_then(|includes| includes.tweets.clone()).unwrap_or_default();
+        let tweets = response.into_data().unwrap_or_default();
+        let images = Self::attached_images(&tweets, &media);
+        let referenced = Self::referenced_posts(&tweets, &included_tweets);
+
+        Ok(Mentions { tweets, images, referenced })
+    }
+
+    // Our most recent tweets and replies, newest first; retweets are left out
+    pub async fn get_own_timeline(&self, user_id: impl IntoNumericId, max_results: usize) -> Result<Vec<TimelinePost>, FudError> {
+        self.count(ENDPOINT_TIMELINE);
+        let api = TwitterApi::new(self.auth.clone());
+        let tweets = api
+            .get_user_tweets(user_id)
+            .tweet_fields([TweetField::CreatedAt, TweetField::ReferencedTweets])
+            .exclude([Exclude::Retweets])
+            .max_results(max_results.clamp(5, 100))
+            .send()
+            .await
+            .map_err(Self::api_error)?
             .into_data()
             .unwrap_or_default();
+        Ok(Self::timeline_posts(&tweets))
+    }
 
-        Ok(mentions)
+    pub fn timeline_posts(tweets: &[twitter_v2::Tweet]) -> Vec<TimelinePost> {
+        tweets
+            .iter()
+            .map(|tweet| TimelinePost {
+                id: tweet.id.to_string(),
+                text: tweet.text.clone(),
+                created_at: tweet.created_at.and_then(|at| DateTime::from_timestamp(at.unix_timestamp(), 0)),
+                reply_to: tweet
+                    .referenced_tweets
+                    .iter()
+                    .flatten()
+                    .find(|r| matches!(r.kind, ReferencedTweetKind::RepliedTo))
+                    .map(|r| r.id.to_string()),
+            })
+            .collect()
     }
 
-    pub async fn get_user_id(&self) -> Result<impl IntoNumericId, anyhow::Error> {
+    // Text of the posts each tweet quotes or replies to, from the expanded tweets.
+    // Retweets are left out; a mention is never one.
+    pub fn referenced_posts(tweets: &[twitter_v2::Tweet], included: &[twitter_v2::Tweet]) -> HashMap<String, Vec<ReferencedPost>> {
+        let mut referenced = HashMap::new();
+        for tweet in tweets {
+            let posts: Vec<ReferencedPost> = tweet
+                .referenced_tweets
+                .iter()
+                .flatten()
+                .filter(|r| matches!(r.kind, ReferencedTweetKind::Quoted | ReferencedTweetKind::RepliedTo))
+                .filter_map(|r| {
+                    let parent = included.iter().find(|t| t.id == r.id)?;
+                    Some(ReferencedPost { kind: r.kind.clone(), text: parent.text.clone() })
+                })
+                .collect();
+            if !posts.is_empty() {
+                referenced.insert(tweet.id.to_string(), posts);
+            }
+        }
+        referenced
+    }
+
+    // Image URLs per tweet from the expanded media. Videos and GIFs only have a
+    // still preview, which is still enough to see what was posted.
+    pub fn attached_images(tweets: &[twitter_v2::Tweet], media: &[Media]) -> HashMap<String, Vec<String>> {
+        let mut images = HashMap::new();
+        for tweet in tweets {
+            let keys = tweet.attachments.as_ref().and_then(|a| a.media_keys.as_ref());
+            let urls: Vec<String> = keys
+                .into_iter()
+                .flatten()
+                .filter_map(|key| media.iter().find(|m| m.media_key == *key))
+                .filter_map(|m| match m.kind {
+                    MediaType::Photo => m.url.as_ref(),
+                    MediaType::AnimatedGif | MediaType::Video => m.preview_image_url.as_ref(),
+                })
+                .map(|url| url.to_string())
+                .collect();
+            if !urls.is_empty() {
+                images.insert(tweet.id.to_string(), urls);
+            }
+        }
+        images
+    }
+
+    // Scheduled Spaces whose title matches the query, with their hosts' usernames
+    pub async fn search_scheduled_spaces(&self, query: &str) -> Result<Vec<ScheduledSpace>, FudError> {
+        self.count(ENDPOINT_SPACES);
         let api = TwitterApi::new(self.auth.clone());
-        let me = api.get_users_me()
+        let response = api
+            .get_spaces_search(query)
+            .state(SpaceStateQuery::Scheduled)
+            .space_fields([SpaceField::Title, SpaceField::State, SpaceField::ScheduledStart, SpaceField::SubscriberCount, SpaceField::HostIds])
+            .expansions([SpaceExpansion::HostIds])
+            .user_fields([UserField::Username])
             .send()
-            .await?
-            .into_data()
-            .expect("should have user data");
-        
-        Ok(me.id)
+            .await
+            .map_err(Self::api_error)?;
+        let hosts = response.includes().and_then(|includes| includes.users.clone()).unwrap_or_default();
+        Ok(Self::scheduled_spaces(&response.into_data().unwrap_or_default(), &hosts))
     }
-    
-    pub async fn upload_bytes(&self, bytes: Vec<u8>) -> Result<u64, anyhow::Error> {
-        let part = multipart::Part::bytes(bytes);
 
-        let form = multipart::Form::new().part("media", part);
+    // Spaces still to come, with host ids resolved to usernames; untitled ones are dropped
+    pub fn scheduled_spaces(spaces: &[Space], users: &[User]) -> Vec<ScheduledSpace> {
+        spaces
+            .iter()
+            .filter(|space| space.state == SpaceState::Scheduled)
+            .filter_map(|space| {
+                let title = space.title.as_deref().map(str::trim).filter(|title| !title.is_empty())?;
+                let hosts = space
+                    .host_ids
+                    .iter()
+                    .flatten()
+                    .filter_map(|id| users.iter().find(|user| user.id == *id))
+                    .map(|user| user.username.clone())
+                    .collect();
+                Some(ScheduledSpace {
+                    id: space.id.to_string(),
+                    title: title.to_string(),
+                    hosts,
+                    scheduled_start: space
+                        .scheduled_start
+                        .and_then(|at| DateTime::from_timestamp(at.unix_timestamp(), 0)),
+                    subscriber_count: space.subscriber_count,
+                })
+            })
+            .collect()
+    }
 
-        // Extract OAuth credentials from the auth token
+    // twitter_v2 doesn't know about communities yet, so these go through a signed
+    // request of our own
+    async fn get_json(&self, url: &str, query: &[(&str, &str)]) -> Result<String, FudError> {
         let secrets = reqwest_oauth1::Secrets::new(&self.twitter_consumer_key, &self.twitter_consumer_secret)
             .token(&self.twitter_access_token, &self.twitter_access_token_secret);
-
-        let client = reqwest::Client::new();
-        let response = client
+        let response = self.http
+            .clone()
             .oauth1(secrets)
-            .post("https://upload.twitter.com/1.1/media/upload.json")
-            .multipart(form)
+            .get(url)
+            .query(query)
             .send()
-            .await;
-        match response {
-            Ok(res) => {
-                if res.status().is_success() {
-                    let media_response = res.json::<MediaUploadResponse>().await?;
-                    Ok(media_response.media_id)
-                } else {
-                    Err(anyhow::anyhow!("Failed to upload media: {}", res.status()))
+            .await
+            .map_err(|e| FudError::provider(PROVIDER, anyhow::anyhow!("Request to {} failed: {}", url, e)))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(FudError::RateLimited { provider: PROVIDER });
+        }
+        if !status.is_success() {
+            return Err(FudError::provider(PROVIDER, anyhow::anyhow!("Request to {} failed: {}", url, status)));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| FudError::provider(PROVIDER, anyhow::anyhow!("Request to {} failed: {}", url, e)))
+    }
+
+    // Community each tweet was posted in, for those posted in one (at most 100 per call)
+    pub async fn get_community_ids(&self, tweet_ids: &[String]) -> Result<HashMap<String, String>, FudError> {
+        if tweet_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        self.count(ENDPOINT_TWEET_LOOKUP);
+        let ids = tweet_ids.iter().take(100).cloned().collect::<Vec<_>>().join(",");
+        let body = self
+            .get_json("https://api.twitter.com/2/tweets", &[("ids", &ids), ("tweet.fields", "community_id")])
+            .await?;
+        Self::parse_community_ids(&body)
+    }
+
+    pub fn parse_community_ids(body: &str) -> Result<HashMap<String, String>, FudError> {
+        let tweets: CommunityTweets = serde_json::from_str(body).map_err(|e| FudError::parse("tweet lookup", e))?;
+        Ok(tweets
+            .data
+            .into_iter()
+            .filter_map(|tweet| Some((tweet.id, tweet.community_id?)))
+            .collect())
+    }
+
+    pub async fn get_community_name(&self, community_id: &str) -> Result<String, FudError> {
+        self.count(ENDPOINT_COMMUNITIES);
+        let url = format!("https://api.twitter.com/2/communities/{}", community_id);
+        let body = self.get_json(&url, &[]).await?;
+        let lookup: CommunityLookup = serde_json::from_str(&body).map_err(|e| FudError::parse("community lookup", e))?;
+        Ok(lookup.data.name)
+    }
+
+    pub async fn search_recent_tweets(&self, query: &str, max_results: usize) -> Result<Vec<twitter_v2::Tweet>, FudError> {
+        self.count(ENDPOINT_SEARCH);
+        let api = TwitterApi::new(self.auth.clone());
+        let tweets = api
+            .get_tweets_search_recent(query)
+            .tweet_fields([TweetField::AuthorId, TweetField::PublicMetrics, TweetField::CreatedAt])
+            .max_results(max_results.clamp(10, 100))
+            .send()
+            .await
+            .map_err(Self::api_error)?
+            .into_data()
+            .unwrap_or_default();
+
+        Ok(tweets)
+    }
+
+    // Public metrics for our own posted tweets, keyed by tweet id (at most 100 per call)
+    pub async fn get_tweet_metrics(&self, ids: &[u64]) -> Result<Vec<(String, TweetMetrics)>, FudError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.count(ENDPOINT_TWEET_LOOKUP);
+        let api = TwitterApi::new(self.auth.clone());
+        let tweets = api
+            .get_tweets(ids.iter().copied())
+            .tweet_fields([TweetField::PublicMetrics])
+            .send()
+            .await
+            .map_err(Self::api_error)?
+            .into_data()
+            .unwrap_or_default();
+
+        let fetched_at = chrono::Utc::now();
+        Ok(tweets
+            .into_iter()
+            .filter_map(|tweet| {
+                let metrics = tweet.public_metrics?;
+                Some((
+                    tweet.id.to_string(),
+                    TweetMetrics {
+                        likes: metrics.like_count as u64,
+                        retweets: metrics.retweet_count as u64,
+                        replies: metrics.reply_count as u64,
+                        quotes: metrics.quote_count.unwrap_or(0) as u64,
+                        fetched_at,
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    // Account age, follower counts and verification for mention authors, for the
+    // spam filter and the reply policy
+    pub async fn get_users(&self, ids: &[u64]) -> Result<Vec<MentionAuthor>, FudError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.count(ENDPOINT_USERS);
+        let api = TwitterApi::new(self.auth.clone());
+        let users = api
+            .get_users(ids.iter().copied())
+            .user_fields([UserField::CreatedAt, UserField::PublicMetrics, UserField::Verified])
+            .send()
+            .await
+            .map_err(Self::api_error)?
+            .into_data()
+            .unwrap_or_default();
+
+        Ok(users
+            .into_iter()
+            .map(|user| MentionAuthor {
+                id: user.id.as_u64(),
+                username: user.username,
+                created_at: user
+                    .created_at
+                    .and_then(|at| chrono::DateTime::from_timestamp(at.unix_timestamp(), 0)),
+                followers: user.public_metrics.map(|metrics| metrics.followers_count as u64),
+                verified: user.verified,
+            })
+            .collect())
+    }
+
+    // Ids of the account's followers, paging through at most 5,000, and whether
+    // that was all of them
+    pub async fn get_followers(&self, user_id: u64) -> Result<(Vec<u64>, bool), FudError> {
+        const MAX_PAGES: usize = 5;
+        let api = TwitterApi::new(self.auth.clone());
+        self.count(ENDPOINT_FOLLOWERS);
+        let mut page = Some(api.get_user_followers(user_id).max_results(1000).send().await.map_err(Self::api_error)?);
+        let mut ids = Vec::new();
+        let mut pages = 0;
+        let mut complete = true;
+        while let Some(response) = page {
+            ids.extend(response.data().into_iter().flatten().map(|user| user.id.as_u64()));
+            pages += 1;
+            if pages == MAX_PAGES {
+                complete = response.meta().and_then(|meta| meta.next_token.as_ref()).is_none();
+                break;
+            }
+            page = response.next_page().await.map_err(Self::api_error)?;
+            if page.is_some() {
+                self.count(ENDPOINT_FOLLOWERS);
+            }
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        Ok((ids, complete))
+    }
+
+    // Everyone the account has blocked or muted; pages through at most 5,000 of each
+    pub async fn get_blocked_and_muted(&self, user_id: u64) -> Result<Vec<u64>, FudError> {
+        const MAX_PAGES: usize = 5;
+        let api = TwitterApi::new(self.auth.clone());
+        let mut ids = Vec::new();
+        for blocking in [true, false] {
+            let mut request = if blocking {
+                api.get_user_blocking(user_id)
+            } else {
+                api.get_user_muting(user_id)
+            };
+            self.count(ENDPOINT_BLOCKS);
+            let mut page = Some(request.max_results(1000).send().await.map_err(Self::api_error)?);
+            let mut pages = 0;
+            while let Some(response) = page {
+                ids.extend(response.data().into_iter().flatten().map(|user| user.id.as_u64()));
+                pages += 1;
+                page = if pages < MAX_PAGES { response.next_page().await.map_err(Self::api_error)? } else { None };
+                if page.is_some() {
+                    self.count(ENDPOINT_BLOCKS);
                 }
             }
-            Err(err) => Err(anyhow::anyhow!("Failed to upload media: {}", err))
         }
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    pub async fn get_user_id(&self) -> Result<impl IntoNumericId, FudError> {
+        let (id, _) = self.verify_credentials().await?;
+        Ok(id)
+    }
+
+    // Returns the authenticated account's id and username
+    pub async fn verify_credentials(&self) -> Result<(u64, String), FudError> {
+        self.count(ENDPOINT_ME);
+        let api = TwitterApi::new(self.auth.clone());
+        let me = api.get_users_me()
+            .send()
+            .await
+            .map_err(Self::api_error)?
+            .into_data()
+            .ok_or_else(|| anyhow::anyhow!("Twitter returned no user for these credentials"))?;
+
+        Ok((me.id.as_u64(), me.username))
+    }
+    
+    // Images go up in one request; GIFs and videos are uploaded in chunks and
+    // waited on until Twitter has processed them
+    pub async fn upload_bytes(&self, bytes: Vec<u8>) -> Result<u64, FudError> {
+        let kind = MediaKind::detect(&bytes);
+        if bytes.len() > kind.max_bytes() {
+            return Err(FudError::provider(
+                PROVIDER,
+                anyhow::anyhow!("{} upload is {} bytes, over the {} byte limit", kind.category(), bytes.len(), kind.max_bytes()),
+            ));
+        }
+        if kind.is_image() {
+            let form = multipart::Form::new().part("media", multipart::Part::bytes(bytes));
+            self.count(ENDPOINT_MEDIA_UPLOAD);
+            let response = self.http.clone().oauth1(self.upload_secrets()).post(UPLOAD_URL).multipart(form).send().await;
+            let uploaded = Self::upload_response(response).await?;
+            return Self::parse_upload(uploaded).await.map(|upload| upload.media_id);
+        }
+        self.upload_chunked(bytes, kind).await
+    }
+
+    // INIT, one APPEND per chunk, FINALIZE, then STATUS until processing is done
+    async fn upload_chunked(&self, bytes: Vec<u8>, kind: MediaKind) -> Result<u64, FudError> {
+        let total_bytes = bytes.len().to_string();
+        self.count(ENDPOINT_MEDIA_UPLOAD);
+        let init = [
+            ("command", "INIT"),
+            ("total_bytes", total_bytes.as_str()),
+            ("media_type", kind.mime_type()),
+            ("media_category", kind.category()),
+        ];
+        let response = self.http.clone().oauth1(self.upload_secrets()).post(UPLOAD_URL).form(&init).send().await;
+        let media_id = Self::parse_upload(Self::upload_response(response).await?).await?.media_id;
+        let media_id_string = media_id.to_string();
+
+        for (index, chunk) in bytes.chunks(UPLOAD_CHUNK_BYTES).enumerate() {
+            let form = multipart::Form::new()
+                .text("command", "APPEND")
+                .text("media_id", media_id_string.clone())
+                .text("segment_index", index.to_string())
+                .part("media", multipart::Part::bytes(chunk.to_vec()));
+            self.count(ENDPOINT_MEDIA_UPLOAD);
+            let response = self.http.clone().oauth1(self.upload_secrets()).post(UPLOAD_URL).multipart(form).send().await;
+            Self::upload_response(response).await?;
+        }
+
+        let finalize = [("command", "FINALIZE"), ("media_id", media_id_string.as_str())];
+        self.count(ENDPOINT_MEDIA_UPLOAD);
+        let response = self.http.clone().oauth1(self.upload_secrets()).post(UPLOAD_URL).form(&finalize).send().await;
+        let mut processing = Self::parse_upload(Self::upload_response(response).await?).await?.processing_info;
+
+        let mut checks = 0;
+        while let Some(wait) = processing.as_ref().map(ProcessingInfo::next_check).transpose()?.flatten() {
+            checks += 1;
+            if checks > MAX_PROCESSING_CHECKS {
+                return Err(FudError::provider(PROVIDER, anyhow::anyhow!("Media {} was still processing after {} checks", media_id, MAX_PROCESSING_CHECKS)));
+            }
+            tokio::time::sleep(wait).await;
+            let status = [("command", "STATUS"), ("media_id", media_id_string.as_str())];
+            self.count(ENDPOINT_MEDIA_UPLOAD);
+            let response = self.http.clone().oauth1(self.upload_secrets()).get(UPLOAD_URL).query(&status).send().await;
+            processing = Self::parse_upload(Self::upload_response(response).await?).await?.processing_info;
+        }
+        Ok(media_id)
+    }
+
+    fn upload_secrets(&self) -> reqwest_oauth1::Secrets<'_> {
+        reqwest_oauth1::Secrets::new(&self.twitter_consumer_key, &self.twitter_consumer_secret)
+            .token(&self.twitter_access_token, &self.twitter_access_token_secret)
+    }
+
+    async fn upload_response(
+        response: Result<reqwest::Response, reqwest_oauth1::Error>,
+    ) -> Result<reqwest::Response, FudError> {
+        match response {
+            Ok(res) if res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => Err(FudError::RateLimited { provider: PROVIDER }),
+            Ok(res) if res.status().is_success() => Ok(res),
+            Ok(res) => Err(FudError::provider(PROVIDER, anyhow::anyhow!("Failed to upload media: {}", res.status()))),
+            Err(err) => Err(FudError::provider(PROVIDER, anyhow::anyhow!("Failed to upload media: {}", err))),
+        }
+    }
+
+    async fn parse_upload(response: reqwest::Response) -> Result<MediaUploadResponse, FudError> {
+        response
+            .json::<MediaUploadResponse>()
+            .await
+            .map_err(|e| FudError::parse("media upload response", e))
     }
 }
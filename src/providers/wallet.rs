@@ -0,0 +1,278 @@
+use anyhow::Result;
+use base64::Engine;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+
+use super::solana_rpc::SolanaRpc;
+
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+const WRAPPED_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
+
+// Base fee for a single-signature transaction
+pub const SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+// Most Jupiter may add as a priority fee to a receipt buy
+pub const MAX_PRIORITY_FEE_LAMPORTS: u64 = 50_000;
+// Rent for the token account a first buy of a mint creates, plus the fees.
+// Counted against the spend cap even though the rent is reclaimable.
+pub const BUY_OVERHEAD_LAMPORTS: u64 = 2_039_280 + SIGNATURE_FEE_LAMPORTS + MAX_PRIORITY_FEE_LAMPORTS;
+
+// Solana's compact-u16 length prefix: 7 bits per byte, high bit means more follow
+pub fn compact_u16(value: usize) -> Vec<u8> {
+    let mut value = value;
+    let mut out = Vec::new();
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            return out;
+        }
+    }
+}
+
+// (value, bytes used)
+fn read_compact_u16(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for (i, byte) in bytes.iter().take(3).enumerate() {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+fn decode_pubkey(address: &str) -> Result<[u8; 32]> {
+    bs58::decode(address)
+        .into_vec()?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("'{}' is not a 32-byte address", address))
+}
+
+// Legacy message with a single memo instruction paid for by `payer`
+pub fn memo_message(payer: &[u8; 32], blockhash: &str, memo: &str) -> Result<Vec<u8>> {
+    let program = decode_pubkey(MEMO_PROGRAM_ID)?;
+    let blockhash = decode_pubkey(blockhash)?;
+
+    // One signer (the payer), no read-only signers, the memo program read-only
+    let mut message = vec![1, 0, 1];
+    message.extend(compact_u16(2));
+    message.extend(payer);
+    message.extend(program);
+    message.extend(blockhash);
+    message.extend(compact_u16(1));
+    message.push(1);
+    message.extend(compact_u16(0));
+    message.extend(compact_u16(memo.len()));
+    message.extend(memo.as_bytes());
+    Ok(message)
+}
+
+pub struct Keypair {
+    pair: Ed25519KeyPair,
+}
+
+impl Keypair {
+    // Base58 (as wallets export it) or a solana-keygen JSON byte array; 64-byte
+    // secret keys are checked against their public half, 32-byte ones are seeds
+    pub fn parse(secret: &str) -> Result<Self> {
+        let secret = secret.trim();
+        let bytes: Vec<u8> = if secret.starts_with('[') {
+            serde_json::from_str(secret).map_err(|_| anyhow::anyhow!("Wallet key is not a JSON byte array"))?
+        } else {
+            bs58::decode(secret).into_vec().map_err(|_| anyhow::anyhow!("Wallet key is not valid base58"))?
+        };
+        let pair = match bytes.len() {
+            64 => Ed25519KeyPair::from_seed_and_public_key(&bytes[..32], &bytes[32..]),
+            32 => Ed25519KeyPair::from_seed_unchecked(&bytes),
+            len => return Err(anyhow::anyhow!("Wallet key is {} bytes, expected 32 or 64", len)),
+        }
+        .map_err(|_| anyhow::anyhow!("Wallet key is not a valid ed25519 keypair"))?;
+        Ok(Keypair { pair })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.pair.public_key().as_ref().try_into().expect("ed25519 public keys are 32 bytes")
+    }
+
+    pub fn address(&self) -> String {
+        bs58::encode(self.public_key()).into_string()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.pair.sign(message).as_ref().try_into().expect("ed25519 signatures are 64 bytes")
+    }
+
+    // Wire transaction carrying just our signature
+    pub fn sign_message(&self, message: &[u8]) -> Vec<u8> {
+        let mut transaction = compact_u16(1);
+        transaction.extend(self.sign(message));
+        transaction.extend(message);
+        transaction
+    }
+
+    // The fee payer's signature, which is also the transaction's id
+    pub fn signature_of(transaction: &[u8]) -> Result<String> {
+        let (signatures, prefix) = read_compact_u16(transaction).ok_or_else(|| anyhow::anyhow!("Malformed transaction"))?;
+        let signature = transaction
+            .get(prefix..prefix + 64)
+            .filter(|_| signatures > 0)
+            .ok_or_else(|| anyhow::anyhow!("Malformed transaction"))?;
+        Ok(bs58::encode(signature).into_string())
+    }
+
+    // Fill in the fee payer signature of a transaction built elsewhere (legacy or
+    // v0), refusing anything that isn't paid for by this wallet
+    pub fn sign_transaction(&self, transaction: &[u8]) -> Result<Vec<u8>> {
+        let malformed = || anyhow::anyhow!("Malformed transaction");
+        let (signatures, prefix) = read_compact_u16(transaction).ok_or_else(malformed)?;
+        let message_start = prefix + signatures * 64;
+        let message = transaction.get(message_start..).ok_or_else(malformed)?;
+        if signatures == 0 {
+            return Err(malformed());
+        }
+
+        // Versioned messages start with 0x80 | version before the header
+        let header_start = if message.first().is_some_and(|b| b & 0x80 != 0) { 1 } else { 0 };
+        let keys_start = header_start + 3;
+        let (_, key_prefix) = read_compact_u16(message.get(keys_start..).ok_or_else(malformed)?).ok_or_else(malformed)?;
+        let payer_start = keys_start + key_prefix;
+        let payer = message.get(payer_start..payer_start + 32).ok_or_else(malformed)?;
+        if payer != self.public_key() {
+            return Err(anyhow::anyhow!("Transaction fee payer is not this wallet"));
+        }
+
+        let mut signed = transaction.to_vec();
+        signed[prefix..prefix + 64].copy_from_slice(&self.sign(message));
+        Ok(signed)
+    }
+}
+
+// A receipt transaction ready to send, with the most it can cost the wallet
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    pub bytes: Vec<u8>,
+    pub signature: String,
+    pub max_lamports: u64,
+}
+
+// Jupiter's quote has to spend SOL, and no more of it than asked for
+pub fn check_quote(quote: &Value, lamports: u64) -> Result<()> {
+    if quote["inputMint"].as_str() != Some(WRAPPED_SOL_MINT) {
+        return Err(anyhow::anyhow!("Jupiter quoted a swap from {} instead of SOL", quote["inputMint"]));
+    }
+    let in_amount: u64 = quote["inAmount"]
+        .as_str()
+        .and_then(|amount| amount.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Jupiter quote has no inAmount"))?;
+    if in_amount > lamports {
+        return Err(anyhow::anyhow!("Jupiter quoted {} lamports for a {} lamport buy", in_amount, lamports));
+    }
+    Ok(())
+}
+
+// What the simulated transaction takes out of the wallet, refused over `max_lamports`
+pub fn check_outflow(before: u64, after: u64, max_lamports: u64) -> Result<u64> {
+    let outflow = before.saturating_sub(after);
+    if outflow > max_lamports {
+        return Err(anyhow::anyhow!(
+            "Swap would take {} lamports from the wallet, more than the {} allowed",
+            outflow,
+            max_lamports
+        ));
+    }
+    Ok(outflow)
+}
+
+// Hot wallet for on-chain receipts. Holds only dust; caps live in the runtime.
+pub struct Wallet {
+    keypair: Keypair,
+    client: reqwest::Client,
+}
+
+impl Wallet {
+    pub fn new(secret: &str) -> Result<Self> {
+        Ok(Wallet {
+            keypair: Keypair::parse(secret)?,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    pub fn address(&self) -> String {
+        self.keypair.address()
+    }
+
+    // Cheapest receipt: a memo transaction, costs only the signature fee
+    pub async fn memo_transaction(&self, rpc: &SolanaRpc, memo: &str) -> Result<SignedTransaction> {
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let message = memo_message(&self.keypair.public_key(), &blockhash, memo)?;
+        let bytes = self.keypair.sign_message(&message);
+        Ok(SignedTransaction {
+            signature: Keypair::signature_of(&bytes)?,
+            bytes,
+            max_lamports: SIGNATURE_FEE_LAMPORTS,
+        })
+    }
+
+    // Swap `lamports` of SOL into the token through Jupiter. The transaction Jupiter
+    // builds is only signed once the quote spends at most `lamports` and a simulation
+    // shows the wallet losing no more than that plus fees and rent.
+    pub async fn buy_transaction(&self, rpc: &SolanaRpc, mint: &str, lamports: u64, slippage_bps: u16) -> Result<SignedTransaction> {
+        let quote: Value = self
+            .client
+            .get(format!("{}/quote", JUPITER_API_URL))
+            .query(&[
+                ("inputMint", WRAPPED_SOL_MINT.to_string()),
+                ("outputMint", mint.to_string()),
+                ("amount", lamports.to_string()),
+                ("slippageBps", slippage_bps.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        check_quote(&quote, lamports)?;
+
+        let swap: Value = self
+            .client
+            .post(format!("{}/swap", JUPITER_API_URL))
+            .json(&json!({
+                "quoteResponse": quote,
+                "userPublicKey": self.address(),
+                "wrapAndUnwrapSol": true,
+                "dynamicComputeUnitLimit": true,
+                "prioritizationFeeLamports": {
+                    "priorityLevelWithMaxLamports": {
+                        "maxLamports": MAX_PRIORITY_FEE_LAMPORTS,
+                        "priorityLevel": "medium",
+                    },
+                },
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let encoded = swap["swapTransaction"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Jupiter returned no swap transaction"))?;
+        let transaction = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+
+        let max_lamports = lamports + BUY_OVERHEAD_LAMPORTS;
+        let before = rpc.get_balance(&self.address()).await?;
+        let after = rpc.simulate_lamports_after(&transaction, &self.address()).await?;
+        check_outflow(before, after, max_lamports)?;
+
+        let bytes = self.keypair.sign_transaction(&transaction)?;
+        Ok(SignedTransaction {
+            signature: Keypair::signature_of(&bytes)?,
+            bytes,
+            max_lamports,
+        })
+    }
+}